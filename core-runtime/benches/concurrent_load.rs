@@ -21,6 +21,7 @@ fn create_request(id: u64, token_count: usize) -> QueuedRequest {
             top_k: 50,
             stream: false,
             timeout_ms: None,
+            sampler: None,
         },
     )
 }
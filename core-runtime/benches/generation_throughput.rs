@@ -67,6 +67,8 @@ fn bench_finish_reason_matching(c: &mut Criterion) {
                     FinishReason::MaxTokens => 1,
                     FinishReason::Timeout => 2,
                     FinishReason::ContentFiltered => 3,
+                    FinishReason::Degenerated => 4,
+                    FinishReason::EarlyExit => 5,
                 });
             }
         })
@@ -64,6 +64,7 @@ fn bench_params_creation(c: &mut Criterion) {
                 top_k: black_box(50),
                 stream: false,
                 timeout_ms: None,
+                sampler: None,
             }
         })
     });
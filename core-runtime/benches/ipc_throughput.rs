@@ -24,7 +24,7 @@ fn fixture_to_request(fixture: &serde_json::Value, request_id: u64) -> Inference
 
     let params = &fixture["parameters"];
     InferenceRequest {
-        request_id: RequestId(request_id),
+        request_id: RequestId(request_id.into()),
         model_id: fixture["model_id"].as_str().unwrap().to_string(),
         prompt,
         parameters: InferenceParams {
@@ -34,7 +34,19 @@ fn fixture_to_request(fixture: &serde_json::Value, request_id: u64) -> Inference
             top_k: 50,
             stream: false,
             timeout_ms: None,
+            sampler: None,
         },
+        preset: None,
+        priority: Default::default(),
+        session_affinity_key: None,
+        tools: Vec::new(),
+        images: Vec::new(),
+        template_id: None,
+        variables: Default::default(),
+        context_documents: Vec::new(),
+        cite_sources: false,
+        privacy: Default::default(),
+        idempotency_key: None,
     }
 }
 
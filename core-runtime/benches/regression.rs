@@ -0,0 +1,25 @@
+//! Regression workloads shared with `GG-CORE bench --self`.
+//!
+//! Compare against a saved criterion baseline with
+//! `cargo bench --bench regression -- --save-baseline main` and later
+//! `-- --baseline main`.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use gg_core::bench::SUITE;
+
+fn bench_regression(c: &mut Criterion) {
+    let mut groups: Vec<&str> = SUITE.iter().map(|w| w.group).collect();
+    groups.dedup();
+    for name in groups {
+        let mut group = c.benchmark_group(name);
+        for workload in SUITE.iter().filter(|w| w.group == name) {
+            let mut run = (workload.setup)();
+            group.bench_function(workload.name, |b| b.iter(&mut run));
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_regression);
+criterion_main!(benches);
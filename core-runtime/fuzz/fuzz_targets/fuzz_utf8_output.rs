@@ -0,0 +1,47 @@
+//! Fuzz target for UTF-8 output finalization.
+//!
+//! Feeds random byte streams, split at random points, through the
+//! incremental decoder and checks the result matches a one-shot lossy
+//! decode. The decoded text is then truncated and sanitized, which must
+//! never split a character or panic.
+
+#![no_main]
+
+use gg_core::engine::utf8::{self, Utf8Decoder};
+use gg_core::security::output_sanitizer::SanitizerConfig;
+use gg_core::security::OutputSanitizer;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Some((&split, bytes)) = data.split_first() else {
+        return;
+    };
+
+    // Chunk sizes 1-16 emulate token pieces
+    let chunk = (split % 16) as usize + 1;
+    let mut decoder = Utf8Decoder::new();
+    let mut text = String::new();
+    for piece in bytes.chunks(chunk) {
+        text.push_str(&decoder.push(piece));
+    }
+    text.push_str(&decoder.finish());
+    assert_eq!(text, String::from_utf8_lossy(bytes));
+
+    let score = utf8::mojibake_score(&text);
+    assert!((0.0..=1.0).contains(&score));
+
+    let limit = split as usize;
+    let mut by_bytes = text.clone();
+    utf8::truncate_bytes(&mut by_bytes, limit);
+    assert!(by_bytes.len() <= limit && text.starts_with(&by_bytes));
+    let mut by_chars = text.clone();
+    utf8::truncate_chars(&mut by_chars, limit);
+    assert!(by_chars.chars().count() <= limit && text.starts_with(&by_chars));
+
+    let sanitizer = OutputSanitizer::new(SanitizerConfig {
+        max_length: limit,
+        ..Default::default()
+    });
+    let _ = sanitizer.sanitize(&text);
+    let _ = sanitizer.validate_format(&text);
+});
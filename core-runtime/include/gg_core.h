@@ -88,6 +88,27 @@ extern "C" {
 
 #define EXIT_UNHEALTHY 1
 
+/**
+ * ABI version of the C surface, encoded as `major << 16 | minor`.
+ *
+ * The major component changes on any breaking change to a function
+ * signature or struct layout; the minor component changes when entry
+ * points are added. Managed wrappers should compare the major component
+ * against the one they were built for before calling anything else.
+ */
+#define CORE_ABI_VERSION ((1 << 16) | 1)
+
+/**
+ * Layout version of [`CoreInferenceParams`]. Callers must set
+ * `struct_version` to this value (or use `core_inference_params_default`).
+ */
+#define CORE_INFERENCE_PARAMS_VERSION 1
+
+/**
+ * Longest string argument accepted (1 MiB).
+ */
+#define CORE_MAX_STRING_BYTES (1024 * 1024)
+
 /**
  * Error codes for FFI functions
  */
@@ -121,6 +142,30 @@ typedef enum CoreHealthState {
   CORE_HEALTH_STATE_UNHEALTHY = 2,
 } CoreHealthState;
 
+/**
+ * Log and event severity, ordered from most to least verbose
+ */
+typedef enum CoreLogLevel {
+  Trace = 0,
+  Debug = 1,
+  Info = 2,
+  Warn = 3,
+  Error = 4,
+} CoreLogLevel;
+
+/**
+ * Runtime event kind
+ */
+typedef enum CoreEventKind {
+  ModelLoaded = 0,
+  ModelUnloaded = 1,
+  ModelEvicted = 2,
+  HealthChanged = 3,
+  SloBurn = 4,
+  ThermalThrottle = 5,
+  ResourceGrowth = 6,
+} CoreEventKind;
+
 /**
  * Opaque handle wrapping Rust runtime
  */
@@ -172,8 +217,15 @@ typedef struct CoreHealthReport {
 
 /**
  * Inference parameters (matches InferenceParams)
+ *
+ * Booleans are one byte; .NET hosts must marshal them with
+ * `[MarshalAs(UnmanagedType.U1)]`.
  */
 typedef struct CoreInferenceParams {
+  /**
+   * Layout version (must be CORE_INFERENCE_PARAMS_VERSION)
+   */
+  uint32_t struct_version;
   /**
    * Maximum tokens to generate (default: 256)
    */
@@ -266,6 +318,50 @@ typedef struct CoreConfig {
   uint64_t shutdown_timeout_secs;
 } CoreConfig;
 
+/**
+ * Runtime event (borrowed, valid only for the duration of the callback)
+ */
+typedef struct CoreEvent {
+  /**
+   * Event kind
+   */
+  CoreEventKind kind;
+  /**
+   * Event severity
+   */
+  CoreLogLevel level;
+  /**
+   * Subject of the event (model ID or health state)
+   */
+  const char *subject;
+  /**
+   * Human-readable detail
+   */
+  const char *detail;
+  /**
+   * Milliseconds since the Unix epoch
+   */
+  uint64_t timestamp_ms;
+} CoreEvent;
+
+/**
+ * Event callback signature
+ * The event is borrowed for the call only. The callback may run on any
+ * runtime thread and must not unwind.
+ */
+typedef void (*CoreEventCallback)(void *user_data, const struct CoreEvent *event);
+
+/**
+ * Log callback signature
+ * `target`, `message` and `fields_json` are borrowed for the call only.
+ * The callback may run on any runtime thread and must not unwind.
+ */
+typedef void (*CoreLogCallback)(void *user_data,
+                                CoreLogLevel level,
+                                const char *target,
+                                const char *message,
+                                const char *fields_json);
+
 /**
  * Streaming callback signature
  * Return false to cancel streaming
@@ -283,6 +379,18 @@ CoreErrorCode core_authenticate(struct CoreRuntime *runtime,
                                 const char *token,
                                 struct CoreSession **out_session);
 
+/**
+ * Authenticate with a length-delimited UTF-8 token
+ *
+ * # Safety
+ * `token` must be valid for reads of `token_len` bytes; `runtime` and
+ * `out_session` must be valid pointers.
+ */
+CoreErrorCode core_authenticate_utf8(struct CoreRuntime *runtime,
+                                     const uint8_t *token,
+                                     uintptr_t token_len,
+                                     struct CoreSession **out_session);
+
 /**
  * Validate existing session
  */
@@ -318,6 +426,23 @@ CoreErrorCode core_health_check(struct CoreRuntime *runtime, struct CoreHealthRe
  */
 bool core_is_alive(struct CoreRuntime *runtime);
 
+/**
+ * Subscribe to runtime events at or above `min_level`
+ *
+ * # Safety
+ * `user_data` is passed back verbatim; the caller keeps it valid until
+ * `core_unsubscribe_events` returns. `out_subscription_id` must be valid.
+ */
+CoreErrorCode core_subscribe_events(CoreEventCallback callback,
+                                    CoreLogLevel min_level,
+                                    void *user_data,
+                                    uint64_t *out_subscription_id);
+
+/**
+ * Cancel an event subscription
+ */
+CoreErrorCode core_unsubscribe_events(uint64_t subscription_id);
+
 /**
  * Readiness check (simple boolean)
  */
@@ -339,6 +464,22 @@ CoreErrorCode core_infer(struct CoreRuntime *runtime,
                          const struct CoreInferenceParams *params,
                          struct CoreInferenceResult *out_result);
 
+/**
+ * Submit inference request with a length-delimited UTF-8 model ID (blocking)
+ *
+ * # Safety
+ * `model_id` must be valid for reads of `model_id_len` bytes and
+ * `prompt_tokens` for `prompt_token_count` elements; `params` may be NULL.
+ */
+CoreErrorCode core_infer_utf8(struct CoreRuntime *runtime,
+                              struct CoreSession *session,
+                              const uint8_t *model_id,
+                              uintptr_t model_id_len,
+                              const uint32_t *prompt_tokens,
+                              uint32_t prompt_token_count,
+                              const struct CoreInferenceParams *params,
+                              struct CoreInferenceResult *out_result);
+
 /**
  * Submit inference request with timeout (blocking)
  */
@@ -356,6 +497,22 @@ CoreErrorCode core_infer_with_timeout(struct CoreRuntime *runtime,
  */
 void core_free_tokens(uint32_t *tokens, uint32_t count);
 
+/**
+ * Get default inference parameters (sets the current struct_version)
+ */
+void core_inference_params_default(struct CoreInferenceParams *params);
+
+/**
+ * Set (or clear, with a NULL callback) the log callback
+ *
+ * # Safety
+ * `user_data` is passed back verbatim; the caller keeps it valid until
+ * the callback is replaced or cleared.
+ */
+CoreErrorCode core_set_log_callback(CoreLogCallback callback,
+                                    CoreLogLevel min_level,
+                                    void *user_data);
+
 /**
  * Load a model from path (relative to base_path/models/)
  */
@@ -363,6 +520,18 @@ CoreErrorCode core_model_load(struct CoreRuntime *runtime,
                               const char *model_path,
                               uint64_t *out_handle_id);
 
+/**
+ * Load a model from a length-delimited UTF-8 path
+ *
+ * # Safety
+ * `model_path` must be valid for reads of `model_path_len` bytes;
+ * `runtime` and `out_handle_id` must be valid pointers.
+ */
+CoreErrorCode core_model_load_utf8(struct CoreRuntime *runtime,
+                                   const uint8_t *model_path,
+                                   uintptr_t model_path_len,
+                                   uint64_t *out_handle_id);
+
 /**
  * Unload a model by handle
  */
@@ -393,6 +562,11 @@ CoreErrorCode core_model_list(struct CoreRuntime *runtime,
  */
 CoreErrorCode core_model_count(struct CoreRuntime *runtime, uint32_t *out_count);
 
+/**
+ * ABI version (`major << 16 | minor`) for load-time compatibility checks
+ */
+uint32_t core_abi_version(void);
+
 /**
  * Get default configuration values
  */
@@ -421,6 +595,24 @@ CoreErrorCode core_infer_streaming(struct CoreRuntime *runtime,
                                    CoreStreamCallback callback,
                                    void *user_data);
 
+/**
+ * Submit streaming inference with a length-delimited UTF-8 model ID
+ *
+ * # Safety
+ * `model_id` must be valid for reads of `model_id_len` bytes and
+ * `prompt_tokens` for `prompt_token_count` elements; `params` may be NULL.
+ * `callback` is invoked on the calling thread and must not unwind.
+ */
+CoreErrorCode core_infer_streaming_utf8(struct CoreRuntime *runtime,
+                                        struct CoreSession *session,
+                                        const uint8_t *model_id,
+                                        uintptr_t model_id_len,
+                                        const uint32_t *prompt_tokens,
+                                        uint32_t prompt_token_count,
+                                        const struct CoreInferenceParams *params,
+                                        CoreStreamCallback callback,
+                                        void *user_data);
+
 /**
  * Free string allocated by core functions
  */
@@ -0,0 +1,354 @@
+//! Backup and restore of security-critical installation state.
+//!
+//! Machine-bound model keys derive from the machine id and the
+//! installation salt. Losing the salt in a node rebuild orphans every
+//! encrypted model, so it is exported here together with the registry
+//! catalog, the on-disk format version, runtime snapshots and the
+//! non-secret configuration.
+//!
+//! The archive is a plain ustar file (pipe it through `zstd` for a
+//! `.tar.zst`). `manifest.json` lists every other entry with its SHA-256;
+//! restore verifies all of them before writing anything. The salt is only
+//! stored wrapped under a passphrase (PBKDF2 + AES-256-GCM).
+//!
+//! There is no persistent keystore (rotation keys live in memory) and
+//! audit events go to the log pipeline rather than to local files, so
+//! neither has anything on disk to export.
+
+pub mod tar;
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use self::tar::Entry;
+use crate::migrations::VERSION_FILE;
+use crate::security::encryption::{self, ModelEncryption, NONCE_SIZE};
+use crate::snapshot::{ConfigSnapshot, SNAPSHOT_DIR};
+
+/// Archive layout version written by this build.
+pub const BACKUP_FORMAT_VERSION: u32 = 1;
+/// Shortest passphrase accepted for wrapping the salt.
+pub const MIN_PASSPHRASE_LEN: usize = 12;
+
+const MANIFEST: &str = "manifest.json";
+const WRAPPED_SALT: &str = "salt.wrapped";
+const CONFIG: &str = "config.json";
+const CATALOG_FILES: [&str; 2] = ["registry_state.json", VERSION_FILE];
+const WRAP_MAGIC: &[u8; 5] = b"GGSW1";
+const KDF_SALT_LEN: usize = 16;
+/// Largest entry read back from an archive.
+const MAX_ENTRY_BYTES: u64 = 64 * 1024 * 1024;
+
+#[derive(Error, Debug)]
+pub enum BackupError {
+    #[error("IO error on {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Backup archive is unreadable: {0}")]
+    Archive(#[source] std::io::Error),
+
+    #[error("Backup manifest is invalid: {0}")]
+    Manifest(String),
+
+    #[error("Unsupported backup format version {found} (supported: {supported})")]
+    UnsupportedVersion { found: u32, supported: u32 },
+
+    #[error("Integrity check failed for {0}")]
+    Integrity(String),
+
+    #[error("Passphrase must be at least {MIN_PASSPHRASE_LEN} characters")]
+    WeakPassphrase,
+
+    #[error("Cannot unwrap the installation salt: wrong passphrase or corrupted backup")]
+    Unwrap,
+
+    #[error("Encryption error: {0}")]
+    Encryption(String),
+
+    #[error("{0} exists with different contents; use --force to overwrite")]
+    Conflict(PathBuf),
+}
+
+fn io_err(path: &Path) -> impl FnOnce(std::io::Error) -> BackupError + '_ {
+    move |source| BackupError::Io { path: path.to_path_buf(), source }
+}
+
+/// Where installation state lives; used both to collect and to restore.
+#[derive(Debug, Clone)]
+pub struct BackupSources {
+    pub base_path: PathBuf,
+    /// Installation salt file; `None` if the platform has no location.
+    pub salt_path: Option<PathBuf>,
+    /// Non-secret configuration to record (create only).
+    pub config: Option<ConfigSnapshot>,
+}
+
+impl BackupSources {
+    /// Sources for this installation: `base_path` and the default salt location.
+    pub fn installation(base_path: impl Into<PathBuf>, config: Option<ConfigSnapshot>) -> Self {
+        Self {
+            base_path: base_path.into(),
+            salt_path: encryption::installation_salt_path().ok(),
+            config,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub name: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub format_version: u32,
+    pub runtime_version: String,
+    /// Unix seconds.
+    pub created_at: u64,
+    pub entries: Vec<ManifestEntry>,
+}
+
+/// Files written (or found identical) by a restore.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RestoreReport {
+    pub written: Vec<PathBuf>,
+    pub unchanged: Vec<PathBuf>,
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    hex::encode(Sha256::digest(data))
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs())
+}
+
+fn read_optional(path: &Path) -> Result<Option<Vec<u8>>, BackupError> {
+    match fs::read(path) {
+        Ok(data) => Ok(Some(data)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(io_err(path)(e)),
+    }
+}
+
+/// Wrap the salt: magic, KDF salt, nonce, then ciphertext with tag.
+fn wrap_salt(salt: &[u8], passphrase: &str) -> Result<Vec<u8>, BackupError> {
+    let mut kdf_salt = [0u8; KDF_SALT_LEN];
+    rand::rngs::OsRng.fill_bytes(&mut kdf_salt);
+    let (nonce, ciphertext) = ModelEncryption::from_password(passphrase, &kdf_salt)
+        .encrypt(salt)
+        .map_err(|e| BackupError::Encryption(e.to_string()))?;
+    Ok([WRAP_MAGIC.as_slice(), &kdf_salt, &nonce, &ciphertext].concat())
+}
+
+fn unwrap_salt(wrapped: &[u8], passphrase: &str) -> Result<Vec<u8>, BackupError> {
+    let header = WRAP_MAGIC.len() + KDF_SALT_LEN + NONCE_SIZE;
+    if wrapped.len() <= header || !wrapped.starts_with(WRAP_MAGIC) {
+        return Err(BackupError::Unwrap);
+    }
+    let (kdf_salt, rest) = wrapped[WRAP_MAGIC.len()..].split_at(KDF_SALT_LEN);
+    let (nonce, ciphertext) = rest.split_at(NONCE_SIZE);
+    ModelEncryption::from_password(passphrase, kdf_salt)
+        .decrypt(nonce, ciphertext)
+        .map_err(|_| BackupError::Unwrap)
+}
+
+/// Entry names accepted on restore: fixed files plus flat snapshot files.
+fn is_known_entry(name: &str) -> bool {
+    if name == WRAPPED_SALT || name == CONFIG || CATALOG_FILES.contains(&name) {
+        return true;
+    }
+    name.strip_prefix("snapshots/").is_some_and(|file| {
+        !file.is_empty() && !file.contains('/') && file != "." && file != ".."
+    })
+}
+
+/// Collect installation state and write it as a ustar archive to `out`.
+pub fn create<W: Write>(
+    sources: &BackupSources,
+    passphrase: &str,
+    out: &mut W,
+) -> Result<BackupManifest, BackupError> {
+    if passphrase.chars().count() < MIN_PASSPHRASE_LEN {
+        return Err(BackupError::WeakPassphrase);
+    }
+    let mut entries = Vec::new();
+
+    if let Some(salt_path) = &sources.salt_path {
+        if let Some(salt) = read_optional(salt_path)? {
+            entries.push(Entry { name: WRAPPED_SALT.into(), mode: 0o600, data: wrap_salt(&salt, passphrase)? });
+        }
+    }
+    for name in CATALOG_FILES {
+        if let Some(data) = read_optional(&sources.base_path.join(name))? {
+            entries.push(Entry { name: name.into(), mode: 0o600, data });
+        }
+    }
+    if let Some(config) = &sources.config {
+        let data = serde_json::to_vec_pretty(config).map_err(|e| BackupError::Manifest(e.to_string()))?;
+        entries.push(Entry { name: CONFIG.into(), mode: 0o600, data });
+    }
+    let snapshot_dir = sources.base_path.join(SNAPSHOT_DIR);
+    match fs::read_dir(&snapshot_dir) {
+        Ok(dir) => {
+            let mut files = Vec::new();
+            for item in dir {
+                let item = item.map_err(io_err(&snapshot_dir))?;
+                if item.file_type().map_err(io_err(&item.path()))?.is_file() {
+                    files.push(item.path());
+                }
+            }
+            files.sort();
+            for path in files {
+                let name = format!("{}/{}", SNAPSHOT_DIR, path.file_name().unwrap_or_default().to_string_lossy());
+                if is_known_entry(&name) {
+                    let data = fs::read(&path).map_err(io_err(&path))?;
+                    entries.push(Entry { name, mode: 0o600, data });
+                }
+            }
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => return Err(io_err(&snapshot_dir)(e)),
+    }
+
+    let manifest = BackupManifest {
+        format_version: BACKUP_FORMAT_VERSION,
+        runtime_version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at: unix_now(),
+        entries: entries
+            .iter()
+            .map(|e| ManifestEntry { name: e.name.clone(), size: e.data.len() as u64, sha256: sha256_hex(&e.data) })
+            .collect(),
+    };
+    let manifest_data = serde_json::to_vec_pretty(&manifest).map_err(|e| BackupError::Manifest(e.to_string()))?;
+    entries.insert(0, Entry { name: MANIFEST.into(), mode: 0o600, data: manifest_data });
+
+    tar::write(out, &entries, manifest.created_at).map_err(BackupError::Archive)?;
+    Ok(manifest)
+}
+
+/// Read an archive and verify every entry against the manifest.
+pub fn verify<R: Read>(input: &mut R) -> Result<(BackupManifest, Vec<Entry>), BackupError> {
+    let mut entries = tar::read(input, MAX_ENTRY_BYTES).map_err(BackupError::Archive)?;
+    if entries.first().map(|e| e.name.as_str()) != Some(MANIFEST) {
+        return Err(BackupError::Manifest("manifest.json must be the first entry".into()));
+    }
+    let manifest_entry = entries.remove(0);
+    let manifest: BackupManifest =
+        serde_json::from_slice(&manifest_entry.data).map_err(|e| BackupError::Manifest(e.to_string()))?;
+    if manifest.format_version != BACKUP_FORMAT_VERSION {
+        return Err(BackupError::UnsupportedVersion {
+            found: manifest.format_version,
+            supported: BACKUP_FORMAT_VERSION,
+        });
+    }
+
+    let listed: HashMap<&str, &ManifestEntry> = manifest.entries.iter().map(|e| (e.name.as_str(), e)).collect();
+    if listed.len() != entries.len() {
+        return Err(BackupError::Manifest(format!(
+            "manifest lists {} entries, archive holds {}",
+            listed.len(),
+            entries.len()
+        )));
+    }
+    for entry in &entries {
+        if !is_known_entry(&entry.name) {
+            return Err(BackupError::Manifest(format!("unexpected entry {}", entry.name)));
+        }
+        let expected = listed
+            .get(entry.name.as_str())
+            .ok_or_else(|| BackupError::Manifest(format!("{} is not in the manifest", entry.name)))?;
+        if expected.size != entry.data.len() as u64 || expected.sha256 != sha256_hex(&entry.data) {
+            return Err(BackupError::Integrity(entry.name.clone()));
+        }
+    }
+    Ok((manifest, entries))
+}
+
+/// Verify an archive, then write its contents back to `targets`.
+///
+/// Existing files with different contents are only replaced with `force`;
+/// nothing is written unless every entry verifies and the salt unwraps.
+pub fn restore<R: Read>(
+    input: &mut R,
+    passphrase: &str,
+    targets: &BackupSources,
+    force: bool,
+) -> Result<RestoreReport, BackupError> {
+    let (_, entries) = verify(input)?;
+
+    let mut plan = Vec::new();
+    for entry in entries {
+        if entry.name == WRAPPED_SALT {
+            let Some(salt_path) = &targets.salt_path else {
+                return Err(BackupError::Manifest("no installation salt location on this platform".into()));
+            };
+            plan.push((salt_path.clone(), unwrap_salt(&entry.data, passphrase)?, true));
+        } else {
+            plan.push((targets.base_path.join(&entry.name), entry.data, false));
+        }
+    }
+
+    let mut report = RestoreReport::default();
+    for (path, data, _) in &plan {
+        if let Some(existing) = read_optional(path)? {
+            if &existing == data {
+                report.unchanged.push(path.clone());
+            } else if !force {
+                return Err(BackupError::Conflict(path.clone()));
+            }
+        }
+    }
+    for (path, data, is_salt) in plan {
+        if report.unchanged.contains(&path) {
+            continue;
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(io_err(parent))?;
+        }
+        if is_salt {
+            encryption::write_salt_file(&path, &data).map_err(|e| BackupError::Encryption(e.to_string()))?;
+        } else {
+            let tmp = path.with_extension("restore.tmp");
+            fs::write(&tmp, &data).map_err(io_err(&tmp))?;
+            fs::rename(&tmp, &path).map_err(io_err(&path))?;
+        }
+        report.written.push(path);
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_salt_wrap_roundtrip() {
+        let wrapped = wrap_salt(b"0123456789abcdef", "correct horse battery").unwrap();
+        assert_eq!(unwrap_salt(&wrapped, "correct horse battery").unwrap(), b"0123456789abcdef");
+        assert!(matches!(unwrap_salt(&wrapped, "wrong passphrase!!"), Err(BackupError::Unwrap)));
+    }
+
+    #[test]
+    fn test_known_entries_reject_traversal() {
+        assert!(is_known_entry("salt.wrapped"));
+        assert!(is_known_entry("snapshots/node-a.json"));
+        assert!(!is_known_entry("snapshots/../../etc/passwd"));
+        assert!(!is_known_entry("snapshots/.."));
+        assert!(!is_known_entry("../registry_state.json"));
+    }
+}
@@ -0,0 +1,149 @@
+//! Minimal POSIX ustar reader and writer.
+//!
+//! Backups hold a handful of small regular files, so this covers exactly
+//! that: flat or nested names under 100 bytes, regular-file entries, no
+//! links or extended headers. Output is readable by any `tar`.
+
+use std::io::{self, Read, Write};
+
+const BLOCK: usize = 512;
+const NAME_LEN: usize = 100;
+
+/// One regular file in the archive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub name: String,
+    pub mode: u32,
+    pub data: Vec<u8>,
+}
+
+fn octal(field: &mut [u8], value: u64) {
+    // Width minus the NUL terminator
+    let width = field.len() - 1;
+    let text = format!("{:0width$o}", value, width = width);
+    field[..width].copy_from_slice(text.as_bytes());
+    field[width] = 0;
+}
+
+fn parse_octal(field: &[u8]) -> io::Result<u64> {
+    let text: String = field
+        .iter()
+        .take_while(|b| **b != 0)
+        .map(|b| *b as char)
+        .collect();
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(0);
+    }
+    u64::from_str_radix(text, 8).map_err(|_| invalid("bad octal field"))
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+fn header(entry: &Entry, mtime: u64) -> io::Result<[u8; BLOCK]> {
+    let name = entry.name.as_bytes();
+    if name.is_empty() || name.len() >= NAME_LEN {
+        return Err(invalid("entry name must be 1..100 bytes"));
+    }
+    let mut block = [0u8; BLOCK];
+    block[..name.len()].copy_from_slice(name);
+    octal(&mut block[100..108], u64::from(entry.mode & 0o7777));
+    octal(&mut block[108..116], 0);
+    octal(&mut block[116..124], 0);
+    octal(&mut block[124..136], entry.data.len() as u64);
+    octal(&mut block[136..148], mtime);
+    block[156] = b'0';
+    block[257..263].copy_from_slice(b"ustar\0");
+    block[263..265].copy_from_slice(b"00");
+
+    // Checksum is computed with its own field as spaces
+    block[148..156].copy_from_slice(b"        ");
+    let sum: u64 = block.iter().map(|b| u64::from(*b)).sum();
+    let text = format!("{:06o}\0 ", sum);
+    block[148..156].copy_from_slice(text.as_bytes());
+    Ok(block)
+}
+
+/// Write `entries` followed by the end-of-archive marker.
+pub fn write<W: Write>(out: &mut W, entries: &[Entry], mtime: u64) -> io::Result<()> {
+    for entry in entries {
+        out.write_all(&header(entry, mtime)?)?;
+        out.write_all(&entry.data)?;
+        let pad = (BLOCK - entry.data.len() % BLOCK) % BLOCK;
+        out.write_all(&[0u8; BLOCK][..pad])?;
+    }
+    out.write_all(&[0u8; BLOCK * 2])
+}
+
+/// Read every regular-file entry, refusing any larger than `max_entry_bytes`.
+pub fn read<R: Read>(input: &mut R, max_entry_bytes: u64) -> io::Result<Vec<Entry>> {
+    let mut entries = Vec::new();
+    let mut block = [0u8; BLOCK];
+    loop {
+        input.read_exact(&mut block)?;
+        if block.iter().all(|b| *b == 0) {
+            return Ok(entries);
+        }
+
+        let stored = parse_octal(&block[148..156])?;
+        block[148..156].copy_from_slice(b"        ");
+        let sum: u64 = block.iter().map(|b| u64::from(*b)).sum();
+        if sum != stored {
+            return Err(invalid("header checksum mismatch"));
+        }
+
+        let name_end = block[..NAME_LEN].iter().position(|b| *b == 0).unwrap_or(NAME_LEN);
+        let name = std::str::from_utf8(&block[..name_end])
+            .map_err(|_| invalid("entry name is not UTF-8"))?
+            .to_string();
+        let mode = parse_octal(&block[100..108])? as u32;
+        let size = parse_octal(&block[124..136])?;
+        if size > max_entry_bytes {
+            return Err(invalid("entry exceeds size limit"));
+        }
+
+        let mut data = vec![0u8; size as usize];
+        input.read_exact(&mut data)?;
+        let pad = (BLOCK - data.len() % BLOCK) % BLOCK;
+        io::copy(&mut input.take(pad as u64), &mut io::sink())?;
+
+        // Directories and other types carry nothing a backup needs
+        if matches!(block[156], b'0' | 0) {
+            entries.push(Entry { name, mode, data });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let entries = vec![
+            Entry { name: "manifest.json".into(), mode: 0o600, data: b"{}".to_vec() },
+            Entry { name: "snapshots/a.json".into(), mode: 0o644, data: vec![7u8; 1500] },
+            Entry { name: "empty".into(), mode: 0o600, data: Vec::new() },
+        ];
+        let mut archive = Vec::new();
+        write(&mut archive, &entries, 1_700_000_000).unwrap();
+        assert_eq!(archive.len() % BLOCK, 0);
+
+        let read_back = read(&mut archive.as_slice(), 1 << 20).unwrap();
+        assert_eq!(read_back, entries);
+    }
+
+    #[test]
+    fn test_rejects_corrupt_header_and_oversized_entry() {
+        let entries = vec![Entry { name: "big".into(), mode: 0o600, data: vec![1u8; 4096] }];
+        let mut archive = Vec::new();
+        write(&mut archive, &entries, 0).unwrap();
+
+        assert!(read(&mut archive.as_slice(), 1024).is_err());
+
+        archive[0] = b'X';
+        assert!(read(&mut archive.as_slice(), 1 << 20).is_err());
+    }
+}
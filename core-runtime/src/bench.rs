@@ -0,0 +1,265 @@
+//! Regression benchmark suite.
+//!
+//! The same workloads back the `regression` criterion bench and the
+//! in-binary `GG-CORE bench --self` mode. The in-binary runner times each
+//! workload, compares the median against a recorded baseline and reports
+//! any workload slower than the baseline allows. The default baseline is
+//! compiled in from `benches/baselines/self.json`; it holds the ceilings
+//! the former timing asserts in unit tests used, so re-record it with
+//! `--record` on reference hardware before tightening the limits.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::models::{ModelHandle, ModelPool, ModelRegistry, PoolConfig, PoolModelTier};
+use crate::security::{ModelEncryption, OutputSanitizer};
+
+/// Baseline compiled into the binary.
+pub const DEFAULT_BASELINE: &str = include_str!("../benches/baselines/self.json");
+
+/// Allowed slowdown when neither the baseline nor its entry sets one.
+pub const DEFAULT_MAX_REGRESSION: f64 = 0.25;
+
+/// Timed samples per workload.
+const SAMPLES: usize = 15;
+
+/// Shortest sample; cheap workloads repeat until a sample lasts this long.
+const MIN_SAMPLE: Duration = Duration::from_millis(5);
+
+/// One benchmarked operation. `setup` prepares its inputs untimed and
+/// returns the closure that is timed.
+pub struct Workload {
+    pub group: &'static str,
+    pub name: &'static str,
+    pub setup: fn() -> Box<dyn FnMut()>,
+}
+
+impl Workload {
+    /// `group/name`, the key used in baseline files.
+    pub fn id(&self) -> String {
+        format!("{}/{}", self.group, self.name)
+    }
+}
+
+/// Every regression workload.
+pub const SUITE: &[Workload] = &[
+    Workload { group: "encryption", name: "encrypt_1mb", setup: encrypt_1mb },
+    Workload { group: "encryption", name: "decrypt_1mb", setup: decrypt_1mb },
+    Workload { group: "sanitizer", name: "sanitize_7kb", setup: sanitize_7kb },
+    Workload { group: "pool", name: "switch_preloaded", setup: switch_preloaded },
+];
+
+fn bench_key() -> [u8; 32] {
+    std::array::from_fn(|i| i as u8)
+}
+
+fn megabyte() -> Vec<u8> {
+    (0..1_000_000).map(|i| (i % 256) as u8).collect()
+}
+
+fn encrypt_1mb() -> Box<dyn FnMut()> {
+    let encryption = ModelEncryption::new(bench_key());
+    let plaintext = megabyte();
+    Box::new(move || {
+        let _ = std::hint::black_box(encryption.encrypt(std::hint::black_box(&plaintext)));
+    })
+}
+
+fn decrypt_1mb() -> Box<dyn FnMut()> {
+    let encryption = ModelEncryption::new(bench_key());
+    let (nonce, ciphertext) = encryption.encrypt(&megabyte()).expect("encrypt bench input");
+    Box::new(move || {
+        let _ = std::hint::black_box(encryption.decrypt(&nonce, std::hint::black_box(&ciphertext)));
+    })
+}
+
+fn sanitize_7kb() -> Box<dyn FnMut()> {
+    let sanitizer = OutputSanitizer::default_sanitizer();
+    let output = "Contact support@example.com for help. Call 555-123-4567. SSN: 123-45-6789.".repeat(100);
+    Box::new(move || {
+        std::hint::black_box(sanitizer.sanitize(std::hint::black_box(&output)));
+    })
+}
+
+fn switch_preloaded() -> Box<dyn FnMut()> {
+    let runtime = tokio::runtime::Builder::new_current_thread().build().expect("bench runtime");
+    let pool = ModelPool::new(PoolConfig::default(), Arc::new(ModelRegistry::new()));
+    runtime
+        .block_on(pool.preload("bench".to_string(), ModelHandle::new(1), PoolModelTier::Default, 100))
+        .expect("preload bench model");
+    Box::new(move || {
+        let _ = std::hint::black_box(runtime.block_on(pool.switch_to("bench")));
+    })
+}
+
+/// Median time of one workload run.
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchResult {
+    pub id: String,
+    pub median_ns: u64,
+    pub iterations: u64,
+}
+
+/// Time `workload`. Must not be called from inside an async runtime,
+/// since some workloads drive their own.
+pub fn measure(workload: &Workload) -> BenchResult {
+    let mut run = (workload.setup)();
+    let start = Instant::now();
+    run();
+    let once = start.elapsed().max(Duration::from_nanos(1));
+    let per_sample = (MIN_SAMPLE.as_nanos() / once.as_nanos()).clamp(1, 100_000) as u64;
+
+    let mut samples: Vec<u64> = (0..SAMPLES)
+        .map(|_| {
+            let start = Instant::now();
+            for _ in 0..per_sample {
+                run();
+            }
+            (start.elapsed().as_nanos() / per_sample as u128) as u64
+        })
+        .collect();
+    samples.sort_unstable();
+    BenchResult { id: workload.id(), median_ns: samples[SAMPLES / 2], iterations: per_sample * SAMPLES as u64 }
+}
+
+/// Run the workloads whose id contains `filter` (all when `None`).
+pub fn run_suite(filter: Option<&str>) -> Vec<BenchResult> {
+    SUITE
+        .iter()
+        .filter(|w| filter.is_none_or(|f| w.id().contains(f)))
+        .map(measure)
+        .collect()
+}
+
+/// Baseline file errors.
+#[derive(Debug, Error)]
+pub enum BaselineError {
+    #[error("baseline I/O: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("invalid baseline: {0}")]
+    Invalid(#[from] serde_json::Error),
+}
+
+/// Recorded medians, keyed by workload id.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Baseline {
+    /// Allowed slowdown for entries without their own, e.g. 0.25 = 25%.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_regression: Option<f64>,
+    pub benches: BTreeMap<String, BaselineEntry>,
+}
+
+/// One recorded workload median.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    pub median_ns: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_regression: Option<f64>,
+}
+
+impl Baseline {
+    pub fn parse(json: &str) -> Result<Self, BaselineError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn load(path: &std::path::Path) -> Result<Self, BaselineError> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// Baseline recording `results`, keeping the configured limits.
+    pub fn record(&self, results: &[BenchResult]) -> Self {
+        let mut recorded = self.clone();
+        for result in results {
+            let max_regression = self.benches.get(&result.id).and_then(|e| e.max_regression);
+            recorded
+                .benches
+                .insert(result.id.clone(), BaselineEntry { median_ns: result.median_ns, max_regression });
+        }
+        recorded
+    }
+
+    /// Compare `results` against this baseline. `max_regression`, when
+    /// set, overrides every configured limit.
+    pub fn compare(&self, results: &[BenchResult], max_regression: Option<f64>) -> Vec<Comparison> {
+        results
+            .iter()
+            .map(|result| {
+                let entry = self.benches.get(&result.id);
+                let limit = max_regression
+                    .or_else(|| entry.and_then(|e| e.max_regression))
+                    .or(self.max_regression)
+                    .unwrap_or(DEFAULT_MAX_REGRESSION);
+                let baseline_ns = entry.map(|e| e.median_ns);
+                let ratio = baseline_ns.map(|b| result.median_ns as f64 / b.max(1) as f64);
+                Comparison {
+                    id: result.id.clone(),
+                    measured_ns: result.median_ns,
+                    baseline_ns,
+                    ratio,
+                    max_regression: limit,
+                    regressed: ratio.is_some_and(|r| r > 1.0 + limit),
+                }
+            })
+            .collect()
+    }
+}
+
+/// One workload measured against its baseline.
+#[derive(Debug, Clone, Serialize)]
+pub struct Comparison {
+    pub id: String,
+    pub measured_ns: u64,
+    /// `None` when the baseline has no entry; such workloads never fail.
+    pub baseline_ns: Option<u64>,
+    /// Measured / baseline.
+    pub ratio: Option<f64>,
+    pub max_regression: f64,
+    pub regressed: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(id: &str, median_ns: u64) -> BenchResult {
+        BenchResult { id: id.to_string(), median_ns, iterations: 1 }
+    }
+
+    #[test]
+    fn test_default_baseline_covers_suite() {
+        let baseline = Baseline::parse(DEFAULT_BASELINE).unwrap();
+        for workload in SUITE {
+            assert!(baseline.benches.contains_key(&workload.id()), "{}", workload.id());
+        }
+    }
+
+    #[test]
+    fn test_compare_applies_limits() {
+        let baseline = Baseline::parse(
+            r#"{"max_regression": 0.1, "benches": {
+                "a/fast": {"median_ns": 100},
+                "a/loose": {"median_ns": 100, "max_regression": 1.0}
+            }}"#,
+        )
+        .unwrap();
+        let results = [result("a/fast", 115), result("a/loose", 150), result("a/new", 9)];
+        let report = baseline.compare(&results, None);
+        assert!(report[0].regressed);
+        assert!(!report[1].regressed);
+        assert_eq!((report[2].baseline_ns, report[2].regressed), (None, false));
+        assert!(!baseline.compare(&results, Some(0.2))[0].regressed);
+    }
+
+    #[test]
+    fn test_record_keeps_entry_limits() {
+        let baseline = Baseline::parse(r#"{"benches": {"a/x": {"median_ns": 100, "max_regression": 0.5}}}"#).unwrap();
+        let recorded = baseline.record(&[result("a/x", 80), result("a/y", 7)]);
+        assert_eq!(recorded.benches["a/x"].median_ns, 80);
+        assert_eq!(recorded.benches["a/x"].max_regression, Some(0.5));
+        assert_eq!(recorded.benches["a/y"].median_ns, 7);
+    }
+}
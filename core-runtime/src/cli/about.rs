@@ -0,0 +1,125 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! `about`: build and platform information.
+
+use crate::engine::cpu_dispatch::{self, SimdTier};
+use crate::security::fips_mode;
+
+use super::config::{fips_mode_setting, simd_tier_override};
+
+/// Print build and platform information, with `--provenance` the build
+/// features and FIPS allow-list and with `--cpu` the SIMD dispatch table
+/// this process would use.
+pub fn run_about(args: &[String]) -> i32 {
+    println!("GG-CORE {}", env!("CARGO_PKG_VERSION"));
+    println!("Platform: {}-{}", std::env::consts::ARCH, std::env::consts::OS);
+    let flag = |name: &str| args.iter().skip(2).any(|a| a == name);
+    if flag("--provenance") && print_provenance() != 0 {
+        return 1;
+    }
+    if flag("--cpu") {
+        print_cpu_dispatch();
+    }
+    0
+}
+
+/// Cargo features compiled into this binary.
+pub fn build_features() -> Vec<&'static str> {
+    [
+        ("onnx", cfg!(feature = "onnx")),
+        ("safetensors", cfg!(feature = "safetensors")),
+        ("gguf", cfg!(feature = "gguf")),
+        ("cuda", cfg!(feature = "cuda")),
+        ("metal", cfg!(feature = "metal")),
+        ("ffi", cfg!(feature = "ffi")),
+        ("profiling", cfg!(feature = "profiling")),
+        ("heap-profiling", cfg!(feature = "heap-profiling")),
+        ("web-console", cfg!(feature = "web-console")),
+        ("redis-quota", cfg!(feature = "redis-quota")),
+        ("python", cfg!(feature = "python")),
+        ("fips-strict", cfg!(feature = "fips-strict")),
+        ("object-storage", cfg!(feature = "object-storage")),
+        ("lock-order-debug", cfg!(feature = "lock-order-debug")),
+        ("testing", cfg!(feature = "testing")),
+        ("mock", cfg!(feature = "mock")),
+        ("step-hooks", cfg!(feature = "step-hooks")),
+    ]
+    .into_iter()
+    .filter_map(|(name, on)| on.then_some(name))
+    .collect()
+}
+
+fn print_provenance() -> i32 {
+    let features = build_features();
+    println!("\nCommit: {}", option_env!("VERGEN_GIT_SHA").unwrap_or("unknown"));
+    println!("Built: {}", option_env!("VERGEN_BUILD_DATE").unwrap_or("unknown"));
+    println!("Features: {}", if features.is_empty() { "(none)".to_string() } else { features.join(", ") });
+
+    let report = match fips_mode::verify_allow_list(fips_mode::init(fips_mode_setting())) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return 1;
+        }
+    };
+    println!("FIPS mode: {}{}", report.mode, if cfg!(feature = "fips-strict") { " (fixed by build)" } else { "" });
+    println!("\n{:<22} {:<10} STATUS", "ALGORITHM", "APPROVED");
+    for algorithm in &report.algorithms {
+        println!(
+            "{:<22} {:<10} {}",
+            algorithm.algorithm,
+            if algorithm.approved { "yes" } else { "no" },
+            if algorithm.allowed { "allowed" } else { "refused" }
+        );
+    }
+    0
+}
+
+fn print_cpu_dispatch() {
+    let dispatch = cpu_dispatch::init(simd_tier_override());
+    let flag = |on: bool| if on { "yes" } else { "no" };
+    let features = &dispatch.features;
+    println!("\nCPU features:");
+    println!("  AVX2     {}", flag(features.avx2));
+    println!("  AVX-512  {}", flag(features.avx512f));
+    println!("  AMX      {}", flag(features.amx));
+    println!("  NEON     {}", flag(features.neon));
+    println!("  AES-NI   {}", flag(features.aes_ni));
+    println!("\nSIMD tier: {} (detected {})", dispatch.max_tier, dispatch.detected);
+    if dispatch.overridden {
+        println!("  lowered by CORE_SIMD_MAX_TIER");
+    }
+    println!("\n{:<24} {:<10} AVAILABLE", "KERNEL", "SELECTED");
+    for kernel in dispatch.kernels() {
+        let available: Vec<&str> = kernel.available.iter().map(SimdTier::as_str).collect();
+        println!("{:<24} {:<10} {}", kernel.kernel, kernel.selected, available.join(", "));
+    }
+}
+
+/// Print detailed help for `about`.
+pub fn print_help() {
+    eprintln!(
+        "GG-CORE about - Show build and platform information
+
+USAGE:
+    GG-CORE about [--cpu] [--provenance]
+
+OPTIONS:
+    --cpu          Show the CPU features probed at startup and the SIMD tier
+                   each kernel dispatches to
+    --provenance   Show the cargo features the binary was built with, the
+                   FIPS mode and which linked algorithms it allows
+
+DESCRIPTION:
+    Kernels use the highest tier the CPU offers, capped by CORE_SIMD_MAX_TIER.
+    Comparing `about --cpu` across hosts explains performance differences
+    between otherwise identical deployments.
+
+EXAMPLES:
+    GG-CORE about --cpu
+    CORE_SIMD_MAX_TIER=scalar GG-CORE about --cpu
+    CORE_FIPS_MODE=strict GG-CORE about --provenance
+"
+    );
+}
@@ -0,0 +1,142 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! `backup`: export, verify or restore salt, catalog and config.
+
+use crate::backup::{self, BackupSources};
+
+use super::config::load_config;
+
+pub fn run_backup(args: &[String]) -> i32 {
+    let value = |name: &str| args.iter().position(|a| a == name).and_then(|i| args.get(i + 1));
+    let has = |name: &str| args.iter().any(|a| a == name);
+    let action = args.get(2).map(|s| s.as_str());
+    if !matches!(action, Some("create" | "restore" | "verify")) {
+        print_help();
+        return 2;
+    }
+
+    let passphrase_var = value("--passphrase-env").map_or("CORE_BACKUP_PASSPHRASE", |s| s.as_str());
+    let passphrase = || match std::env::var(passphrase_var) {
+        Ok(p) if !p.is_empty() => Ok(p),
+        _ => Err(format!("Set {} to the backup passphrase", passphrase_var)),
+    };
+    let config = load_config();
+
+    let result = match action {
+        Some("create") => {
+            let Some(out) = value("--out") else {
+                eprintln!("Missing --out FILE");
+                return 2;
+            };
+            passphrase().and_then(|passphrase| {
+                let sources = BackupSources::installation(
+                    &config.base_path,
+                    Some(crate::snapshot::ConfigSnapshot::capture(&config)),
+                );
+                let manifest = if out == "-" {
+                    backup::create(&sources, &passphrase, &mut std::io::stdout().lock())
+                } else {
+                    let mut file = std::fs::File::create(out).map_err(|e| format!("{}: {}", out, e))?;
+                    backup::create(&sources, &passphrase, &mut file)
+                }
+                .map_err(|e| e.to_string())?;
+                for entry in &manifest.entries {
+                    eprintln!("{:<40} {:>10}  {}", entry.name, entry.size, entry.sha256);
+                }
+                Ok(())
+            })
+        }
+        _ => {
+            let Some(input) = value("--in") else {
+                eprintln!("Missing --in FILE");
+                return 2;
+            };
+            let mut reader: Box<dyn std::io::Read> = if input == "-" {
+                Box::new(std::io::stdin().lock())
+            } else {
+                match std::fs::File::open(input) {
+                    Ok(file) => Box::new(std::io::BufReader::new(file)),
+                    Err(e) => {
+                        eprintln!("Error: {}: {}", input, e);
+                        return 1;
+                    }
+                }
+            };
+            if action == Some("verify") {
+                backup::verify(&mut reader).map_err(|e| e.to_string()).map(|(manifest, _)| {
+                    if has("--json") {
+                        println!("{}", serde_json::to_string_pretty(&manifest).unwrap_or_default());
+                    } else {
+                        println!(
+                            "OK: {} entries, created {} by {}",
+                            manifest.entries.len(),
+                            manifest.created_at,
+                            manifest.runtime_version
+                        );
+                    }
+                })
+            } else {
+                passphrase().and_then(|passphrase| {
+                    let targets = BackupSources::installation(&config.base_path, None);
+                    let report = backup::restore(&mut reader, &passphrase, &targets, has("--force"))
+                        .map_err(|e| e.to_string())?;
+                    if has("--json") {
+                        println!("{}", serde_json::to_string_pretty(&report).unwrap_or_default());
+                    } else {
+                        for path in &report.written {
+                            println!("restored   {}", path.display());
+                        }
+                        for path in &report.unchanged {
+                            println!("unchanged  {}", path.display());
+                        }
+                    }
+                    Ok(())
+                })
+            }
+        }
+    };
+    match result {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            1
+        }
+    }
+}
+
+/// Print detailed help for `backup`.
+pub fn print_help() {
+    eprintln!(
+        "GG-CORE backup - Export or restore security-critical state
+
+USAGE:
+    GG-CORE backup create --out FILE
+    GG-CORE backup restore --in FILE [--force]
+    GG-CORE backup verify --in FILE
+
+OPTIONS:
+    --out FILE             Archive to write; - for stdout
+    --in FILE              Archive to read; - for stdin
+    --passphrase-env NAME  Variable holding the passphrase
+                           (default: CORE_BACKUP_PASSPHRASE)
+    --force                Overwrite files whose contents differ
+    --json                 Print the manifest or restore report as JSON
+
+The archive is a tar file holding the installation salt (wrapped with the
+passphrase), the registry catalog, the on-disk format version, snapshots
+and the non-secret configuration, plus a manifest with a SHA-256 for each
+entry. Restore checks every entry and unwraps the salt before writing
+anything. Without the salt, machine-bound encrypted models cannot be
+decrypted after a rebuild, so keep backups and the passphrase separate.
+
+Compression is left to the pipeline. Audit events are shipped through
+the log pipeline and are not part of the archive.
+
+EXAMPLES:
+    GG-CORE backup create --out - | zstd > backup.tar.zst
+    zstd -dc backup.tar.zst | GG-CORE backup restore --in -
+    GG-CORE backup verify --in backup.tar
+"
+    );
+}
@@ -0,0 +1,119 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! `bench --self`: run the regression benchmarks in-binary.
+
+use crate::bench::{self as selfbench, Baseline};
+
+pub async fn run_bench(args: &[String]) -> i32 {
+    let value = |name: &str| args.iter().position(|a| a == name).and_then(|i| args.get(i + 1));
+    let has = |name: &str| args.iter().any(|a| a == name);
+    if !has("--self") {
+        print_help();
+        return 2;
+    }
+    let baseline = match value("--baseline") {
+        Some(path) => Baseline::load(std::path::Path::new(path)),
+        None => Baseline::parse(selfbench::DEFAULT_BASELINE),
+    };
+    let baseline = match baseline {
+        Ok(baseline) => baseline,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return 2;
+        }
+    };
+    let max_regression = match value("--max-regression").map(|v| v.parse::<f64>()) {
+        None => None,
+        Some(Ok(pct)) if pct >= 0.0 => Some(pct / 100.0),
+        Some(_) => {
+            eprintln!("Error: --max-regression must be a non-negative percentage");
+            return 2;
+        }
+    };
+
+    // Workloads drive their own runtimes, so keep them off this one
+    let filter = value("--filter").cloned();
+    let results = match tokio::task::spawn_blocking(move || selfbench::run_suite(filter.as_deref())).await {
+        Ok(results) => results,
+        Err(e) => {
+            eprintln!("Error: benchmark run failed: {}", e);
+            return 1;
+        }
+    };
+    if let Some(path) = value("--record") {
+        let recorded = serde_json::to_string_pretty(&baseline.record(&results)).unwrap_or_default();
+        if let Err(e) = std::fs::write(path, recorded + "\n") {
+            eprintln!("Error: {}: {}", path, e);
+            return 1;
+        }
+        eprintln!("Recorded {} workloads to {}", results.len(), path);
+    }
+
+    let report = baseline.compare(&results, max_regression);
+    let regressed = report.iter().filter(|c| c.regressed).count();
+    if has("--json") {
+        println!("{}", serde_json::to_string_pretty(&report).unwrap_or_default());
+    } else {
+        for c in &report {
+            let (baseline, change) = match (c.baseline_ns, c.ratio) {
+                (Some(ns), Some(ratio)) => (format_ns(ns), format!("{:+.1}%", (ratio - 1.0) * 100.0)),
+                _ => ("-".to_string(), "new".to_string()),
+            };
+            let verdict = if c.regressed { "REGRESSED" } else { "ok" };
+            println!(
+                "{:<28} {:>10}  baseline {:>10}  {:>8}  (limit +{:.0}%)  {}",
+                c.id,
+                format_ns(c.measured_ns),
+                baseline,
+                change,
+                c.max_regression * 100.0,
+                verdict
+            );
+        }
+    }
+    if regressed > 0 {
+        eprintln!("{} of {} workloads regressed", regressed, report.len());
+        return 1;
+    }
+    0
+}
+
+fn format_ns(ns: u64) -> String {
+    match ns {
+        0..=9_999 => format!("{}ns", ns),
+        10_000..=9_999_999 => format!("{:.1}us", ns as f64 / 1e3),
+        10_000_000..=9_999_999_999 => format!("{:.1}ms", ns as f64 / 1e6),
+        _ => format!("{:.2}s", ns as f64 / 1e9),
+    }
+}
+
+/// Print detailed help for `bench`.
+pub fn print_help() {
+    eprintln!(
+        "GG-CORE bench - Run regression benchmarks against a baseline
+
+USAGE:
+    GG-CORE bench --self [OPTIONS]
+
+OPTIONS:
+    --self                 Run the suite inside this binary
+    --baseline FILE        Baseline to compare against (default: built in)
+    --max-regression PCT   Allowed slowdown in percent, overriding the
+                           baseline's limits
+    --filter TEXT          Only run workloads whose id contains TEXT
+    --record FILE          Write the measured medians as a new baseline
+    --json                 Print the comparison as JSON
+
+Times encryption, output sanitization and model pool switching, the same
+workloads as `cargo bench --bench regression`. Exits 1 when any workload
+is slower than its baseline plus the allowed regression. Workloads absent
+from the baseline are reported but never fail.
+
+EXAMPLES:
+    GG-CORE bench --self
+    GG-CORE bench --self --max-regression 10 --json
+    GG-CORE bench --self --record baseline.json
+"
+    );
+}
@@ -0,0 +1,133 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The `config` command: show the effective settings or validate them.
+
+use crate::config_schema;
+
+use super::load_config;
+use crate::cli::doctor::DOCTOR_ENV;
+
+/// Run a `config` subcommand (`show` when none is given).
+pub fn run_config(args: &[String]) -> i32 {
+    let subcommand = args.get(2).map(|s| s.as_str()).unwrap_or("show");
+    match subcommand {
+        "show" => run_config_show(),
+        "validate" => run_config_validate(args),
+        "defaults" => {
+            eprintln!("Config {} not yet implemented.", subcommand);
+            2
+        }
+        _ => {
+            eprintln!("Unknown config subcommand: {}", subcommand);
+            print_help();
+            1
+        }
+    }
+}
+
+/// Print the effective configuration sections that can be shown.
+fn run_config_show() -> i32 {
+    let config = load_config();
+    let view = serde_json::json!({
+        "base_path": config.base_path,
+        "retention": config.retention,
+        "retention_journal_dir": config.base_path.join("journal"),
+    });
+    match serde_json::to_string_pretty(&view) {
+        Ok(json) => {
+            println!("{}", json);
+            0
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            1
+        }
+    }
+}
+
+/// Check settings from the environment, or `--file` (KEY=VALUE lines),
+/// against the config schema and validate their values. Exits 2 on invalid
+/// values, or with `--strict` on unknown or deprecated keys too.
+fn run_config_validate(args: &[String]) -> i32 {
+    let strict = args.iter().any(|a| a == "--strict");
+    let file = args.iter().position(|a| a == "--file").and_then(|i| args.get(i + 1));
+    let (source, settings) = match file {
+        Some(path) => {
+            let parsed = std::fs::read_to_string(path)
+                .map_err(|e| e.to_string())
+                .and_then(|text| config_schema::parse_env_file(&text));
+            match parsed {
+                Ok(settings) => (path.clone(), settings),
+                Err(e) => {
+                    eprintln!("Error: cannot read {}: {}", path, e);
+                    return 2;
+                }
+            }
+        }
+        None => ("environment".to_string(), config_schema::env_settings()),
+    };
+
+    let check = config_schema::check(settings.iter().map(|(k, v)| (k.as_str(), v.as_str())));
+    println!("{}: config version {} (this build: {})", source, check.version, config_schema::CONFIG_VERSION);
+    for finding in &check.findings {
+        let level = if finding.is_error() || (strict && finding.fails_strict()) { "FAIL" } else { "WARN" };
+        println!("  {:<5} {}", level, finding);
+    }
+    let mut invalid = 0;
+    for (name, kind) in DOCTOR_ENV {
+        let Some((_, value)) = settings.iter().rev().find(|(k, v)| k == name && !v.is_empty()) else {
+            continue;
+        };
+        if let Err(problem) = kind.validate(value) {
+            println!("  FAIL  {}: {}", name, problem);
+            invalid += 1;
+        }
+    }
+
+    if check.passes(strict) && invalid == 0 {
+        println!("Configuration valid{}", if strict { " (strict)" } else { "" });
+        0
+    } else {
+        println!("Configuration invalid");
+        2
+    }
+}
+
+/// Report settings the config schema flags, before they are read.
+pub fn warn_config_schema() {
+    for finding in config_schema::check_env().findings {
+        if finding != config_schema::Finding::Unversioned {
+            eprintln!("Warning: config: {}", finding);
+        }
+    }
+}
+
+/// Print detailed help for `config`.
+pub fn print_help() {
+    eprintln!(
+        "GG-CORE config - Manage configuration
+
+USAGE:
+    GG-CORE config <SUBCOMMAND> [OPTIONS]
+
+SUBCOMMANDS:
+    show           Show current configuration (retention policies)
+    validate       Check settings against the config schema (CORE_CONFIG_VERSION)
+                   and validate their values; deprecated keys name their
+                   replacement, unknown keys the nearest known one
+    defaults       Show default configuration
+
+OPTIONS:
+    --socket PATH  Override IPC socket path
+    --file PATH    Settings file of KEY=VALUE lines (default: the environment)
+    --strict       Also fail on unknown or deprecated keys and newer versions
+
+EXAMPLES:
+    GG-CORE config show
+    GG-CORE config validate --file gg-core.env
+    GG-CORE config validate --strict
+    GG-CORE config defaults
+"
+    );
+}
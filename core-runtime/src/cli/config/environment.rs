@@ -0,0 +1,128 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The ENVIRONMENT section of `help`: every setting read at startup.
+
+/// Print the settings read from the environment, one per entry.
+pub fn print_environment() {
+    eprintln!(
+        "    VERITAS_SOCKET_PATH  IPC socket path (default: /var/run/veritas/GG-CORE.sock on Unix;
+                         @name for a Linux abstract socket)
+    LISTEN_FDS           Socket activation: adopt fd 3 instead of binding
+    CORE_CONFIG_VERSION  Config schema the settings were written for (current: 2); older
+                         keys still work with a warning, see `config validate`
+    CORE_AUTH_TOKEN      Authentication token for server mode
+    CORE_ADMIN_TOKEN     Admin token (sessions may request critical priority)
+    CORE_OBSERVER_TOKEN  Observer token: read-only sessions limited to health, status,
+                         metrics and spans queries (for dashboards)
+    CORE_TENANT_TOKENS   Per-tenant tokens as name=token,...; a tenant keeps its legal
+                         holds, usage and quotas across reconnects
+    CORE_PLACEMENT_DIR   Shared directory for replica placement adverts
+    CORE_REPLICA_ID      Replica id in placement adverts (default: $HOSTNAME)
+    CORE_SLO_AVAILABILITY   Availability SLO target (default: 0.995)
+    CORE_SLO_LATENCY_P95_MS p95 request latency SLO in ms (default: 5000)
+    CORE_PROFILING       Set to 1 to allow `profile` captures (needs 'profiling' build)
+    CORE_MIGRATIONS      auto (default) applies pending on-disk migrations at startup;
+                         check refuses to start until `migrate` has been run
+    CORE_RECOVERY_PUBLIC_KEY  Recovery public key file; encrypted models get an escrow record
+    CORE_BACKUP_PASSPHRASE  Passphrase wrapping the installation salt in backups
+    CORE_POLICY_SHADOW   Candidate sanitizer/policy JSON evaluated in shadow mode;
+                         divergences count as core_policy_shadow_* metrics
+    CORE_POLICY_SHADOW_SAMPLE  Audit every Nth divergence (default: 100, 0 = off)
+    CORE_PII_BLOCK       PII types that fail a response with PolicyBlocked instead of
+                         being emitted, e.g. ssn,credit_card; blocks are audited
+    CORE_PII_BLOCK_CONFIDENCE  Lowest detection confidence that blocks (default: 0.8)
+    CORE_SERVICE_NAME    service.name resource attribute (default: gg-core)
+    CORE_NODE_NAME       host.name resource attribute (default: $HOSTNAME)
+    CORE_CIRCUIT         off disables the per-model circuit breaker
+    CORE_CIRCUIT_FAILURE_RATIO  Failure ratio that opens a model's circuit (default: 0.5)
+    CORE_CIRCUIT_OPEN_SECS  Seconds a circuit stays open before canaries (default: 30)
+    CORE_QUEUE_ON_SWITCH  keep (default) leaves queued requests for a model the pool
+                         switched away from, reported per model as stranded; fail-fast
+                         fails them and new ones with ModelDeactivated
+    CORE_WARM_SCHEDULE   JSON file of time-of-day rules choosing which model tiers stay
+                         warm; each transition is audited
+    CORE_USAGE_DP_EPSILON  Privacy budget for `usage` exports; adds Laplace noise to
+                         per-tenant aggregates (unset: exact counts)
+    CORE_USAGE_DP_MAX_TOKENS  Tokens counted per request, the token sensitivity (default: 4096)
+    CORE_TENANT_RATE_LIMIT  Requests each tenant may start per window (unset: unlimited)
+    CORE_TENANT_RATE_WINDOW_SECS  Rate limit window in seconds (default: 60)
+    CORE_QUOTA_BACKEND   redis://[:password@]host:port[/db] sharing rate limits and
+                         idempotency keys across replicas; falls back to local
+                         limits while unreachable (needs 'redis-quota' build)
+    CORE_HEDGE_PAIRS     primary=secondary[,...] model replicas to race when the primary
+                         is slow; the first to finish wins and the other is aborted
+    CORE_HEDGE_TTFT_MS   Milliseconds before a slow primary is hedged (default: 500)
+    CORE_HEDGE_MAX_RATIO  Largest fraction of paired requests that may hedge (default: 0.05)
+    CORE_CONVERSATION_PREFETCH  Set to 1 to prefill each conversation's next-turn prefix
+                         in the background while the runtime is idle
+    CORE_PREFETCH_MAX_CACHE_MB  Prefix state kept per model before prefetch stops (default: 512)
+    CORE_GPU_SHARE       off (default), serialized or time-sliced: models in
+                         CORE_GPU_SHARES take turns on the GPU instead of contending
+    CORE_GPU_SHARES      model=share[,...] relative GPU time per model under contention
+    CORE_GPU_SLICE_MS    Time-sliced turn length for a share of 1 (default: 200)
+    CORE_THERMAL         Set to 1 to poll GPU temperature and power (nvidia-smi) and
+                         throttle GPU traffic while a GPU runs hot
+    CORE_THERMAL_TEMP_C  Throttle at this temperature (default: 83; recovers 8 below)
+    CORE_THERMAL_POWER_RATIO  Throttle at this fraction of the power limit (default: 0.98)
+    CORE_THERMAL_BATCH   Requests per GPU model at once while throttled (default: 1)
+    CORE_THERMAL_GPU_MODELS  Models on the GPU, comma-separated (default: all); throttled
+                         traffic shifts to fallback alternates not in this list
+    CORE_ATTESTATION     Model provenance policy (off, verify, require); require refuses
+                         models without a Sigstore bundle or in-toto link beside them
+    CORE_TRUSTED_IDENTITIES  JSON list of {{name, public_key}} signers whose attestations
+                         are accepted (default: ./trusted_identities.json)
+    CORE_INTEGRITY       off disables background re-hashing of loaded model files; a
+                         file that no longer matches its digest holds the model's
+                         circuit open and is audited
+    CORE_INTEGRITY_INTERVAL_SECS  Seconds between re-hashes of a model (default: 21600)
+    CORE_INTEGRITY_MAX_MBPS  Read rate while re-hashing in MiB/s (default: 64, 0: uncapped)
+    CORE_MAX_PROMPT_KB   Largest assembled prompt in KiB (default: 4096, 0: unlimited)
+    CORE_MAX_PROMPT_TOKENS  Largest assembled prompt in estimated tokens (default: unlimited)
+    CORE_MAX_METADATA_KB  Largest tools/variables/keys total per request in KiB (default: 256)
+    CORE_MAX_OUTPUT_KB   Largest generated text per response in KiB (default: 4096)
+    CORE_MAX_OUTPUT_TOKENS  Tokens generated per request; streams are cut at the limit
+                         with OutputLimitExceeded (default: unlimited)
+    CORE_PROBE_MAX_PER_SEC  Liveness probes answered per second before 429 (default: 20)
+    CORE_RETENTION       JSON file of retention policies (max_age_secs, max_bytes, shred)
+                         for audit, journal and usage data; see `config show`
+    CORE_DEPRECATIONS    JSON file of deprecated message types, fields and protocol
+                         versions ({{\"deprecations\": [{{target, sunset, replacement}}],
+                         \"enforce_sunset\": bool}}); announced in handshake_ack, use is
+                         counted per client, and retired targets get 410 when enforced
+    CORE_MAINTENANCE     JSON file of maintenance windows ({{\"windows\": [{{start_minute,
+                         duration_minutes, weekdays}}], \"run_self_tests\": bool,
+                         \"kv_idle_secs\": n}}); loads are refused during a window while
+                         audit rotation, cache GC and KV compaction run
+    CORE_AUDIT_SAMPLE_RATE  Fraction of inference requests audited with prompt and
+                         output SHA-256 hashes, 0.0-1.0 (default: 0)
+    CORE_LEGAL_HOLD_MAX_DAYS  Longest legal hold before renewal (default: 365)
+    CORE_LEGAL_HOLD_RETAIN_DAYS  Days sealed records are kept after their hold ends
+                         (default: 30)
+    CORE_LEGAL_HOLD_KEY  Sealing key for legal hold records (default:
+                         <base path>/legal_hold.key); keep it off the store's volume
+    CORE_TCP_ADDR        Also serve IPC on loopback TCP for clients without Unix socket
+                         or named pipe support: auto ([::1] or 127.0.0.1, ephemeral
+                         port) or an address like 127.0.0.1:7878. Needs
+                         CORE_AUTH_TOKEN; the port is written to ipc-tcp.json
+    CORE_CONSOLE_ADDR    Loopback address for the admin web console, e.g. 127.0.0.1:8787
+                         (needs 'web-console' build and CORE_ADMIN_TOKEN)
+    CORE_SIMD_MAX_TIER   Highest SIMD tier kernels may use (scalar, neon, avx2, avx512,
+                         amx); forces a lower tier than detected for debugging
+    CORE_FIPS_MODE       Cryptographic policy (off, strict); strict refuses algorithms
+                         that are not FIPS-approved (forced by 'fips-strict' builds)
+    CORE_STORAGE         s3://bucket[/prefix] keeping the installation salt and model
+                         catalog in object storage, cached under base_path
+                         (needs 'object-storage' build)
+    CORE_STORAGE_ENDPOINT  https://host[:port] of the S3-compatible service
+    CORE_STORAGE_REGION  Signing region (default: $AWS_REGION or us-east-1)
+    CORE_STORAGE_ACCESS_KEY, CORE_STORAGE_SECRET_KEY  Credentials (default:
+                         $AWS_ACCESS_KEY_ID, $AWS_SECRET_ACCESS_KEY)
+    CORE_STORAGE_CACHE_DIR  Local copy of stored objects (default: storage-cache)
+    CORE_HEAP_SOFT_LIMIT Tracked heap size that dumps a heap report, e.g. 6Gi
+                         (needs 'heap-profiling' build)
+    RUST_LOG             Log level (debug, info, warn, error)
+    VERITAS_ENV          Environment (development, staging, production); reported as
+                         deployment.environment"
+    );
+}
@@ -0,0 +1,96 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Settings loaded from JSON files named in the environment, or from the
+//! base path. A file that fails to load keeps the defaults.
+
+use std::path::PathBuf;
+
+use crate::engine::{PresetCatalog, PRESETS_FILE};
+use crate::ipc::DeprecationConfig;
+use crate::maintenance::MaintenanceConfig;
+use crate::models::WarmScheduleConfig;
+use crate::retention::RetentionConfig;
+
+/// Parameter presets: the built-ins plus `presets.json` in the base path.
+/// A file that fails to load keeps the built-ins.
+pub(super) fn presets_config() -> PresetCatalog {
+    let path = PathBuf::from(".").join(PRESETS_FILE);
+    PresetCatalog::load(&path).unwrap_or_else(|e| {
+        eprintln!("Warning: cannot load {}, using built-in presets only: {}", path.display(), e);
+        PresetCatalog::builtin()
+    })
+}
+
+/// Retention policies from the JSON file named by `CORE_RETENTION`.
+/// A file that fails to load keeps the default policies.
+pub(super) fn retention_config() -> RetentionConfig {
+    let Some(path) = std::env::var("CORE_RETENTION").ok().filter(|p| !p.is_empty()) else {
+        return RetentionConfig::default();
+    };
+    let config = std::fs::read(&path)
+        .map_err(|e| e.to_string())
+        .and_then(|bytes| serde_json::from_slice::<RetentionConfig>(&bytes).map_err(|e| e.to_string()))
+        .and_then(|config| config.validate().map(|()| config).map_err(|e| e.to_string()));
+    match config {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Warning: default retention in effect, cannot load {}: {}", path, e);
+            RetentionConfig::default()
+        }
+    }
+}
+
+/// Protocol deprecations from the JSON file named by `CORE_DEPRECATIONS`.
+pub(super) fn deprecation_config() -> DeprecationConfig {
+    let Some(path) = std::env::var("CORE_DEPRECATIONS").ok().filter(|p| !p.is_empty()) else {
+        return DeprecationConfig::default();
+    };
+    let config = std::fs::read(&path)
+        .map_err(|e| e.to_string())
+        .and_then(|bytes| serde_json::from_slice::<DeprecationConfig>(&bytes).map_err(|e| e.to_string()))
+        .and_then(|config| config.validate().map(|()| config).map_err(|e| e.to_string()));
+    match config {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Warning: no protocol deprecations in effect, cannot load {}: {}", path, e);
+            DeprecationConfig::default()
+        }
+    }
+}
+
+/// Warm-pool schedule from the JSON file named by `CORE_WARM_SCHEDULE`.
+/// A schedule that fails to load leaves the pool unmanaged.
+pub(super) fn warm_schedule_config() -> Option<WarmScheduleConfig> {
+    let path = std::env::var("CORE_WARM_SCHEDULE").ok().filter(|p| !p.is_empty())?;
+    let schedule = std::fs::read(&path)
+        .map_err(|e| e.to_string())
+        .and_then(|bytes| serde_json::from_slice::<WarmScheduleConfig>(&bytes).map_err(|e| e.to_string()))
+        .and_then(|schedule| schedule.validate().map(|()| schedule).map_err(|e| e.to_string()));
+    match schedule {
+        Ok(schedule) => Some(schedule),
+        Err(e) => {
+            eprintln!("Warning: warm schedule disabled, cannot load {}: {}", path, e);
+            None
+        }
+    }
+}
+
+/// Maintenance windows from the JSON file named by `CORE_MAINTENANCE`.
+/// A file that fails to load schedules no maintenance.
+pub(super) fn maintenance_config() -> MaintenanceConfig {
+    let Some(path) = std::env::var("CORE_MAINTENANCE").ok().filter(|p| !p.is_empty()) else {
+        return MaintenanceConfig::default();
+    };
+    let config = std::fs::read(&path)
+        .map_err(|e| e.to_string())
+        .and_then(|bytes| serde_json::from_slice::<MaintenanceConfig>(&bytes).map_err(|e| e.to_string()))
+        .and_then(|config| config.validate().map(|()| config).map_err(|e| e.to_string()));
+    match config {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Warning: no maintenance windows scheduled, cannot load {}: {}", path, e);
+            MaintenanceConfig::default()
+        }
+    }
+}
@@ -0,0 +1,205 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Runtime configuration from the environment.
+//!
+//! Each setting group reads its `CORE_*` keys and falls back to the
+//! defaults, with a warning, when a value is invalid. See
+//! [`crate::config_schema`] for the list of known keys.
+
+mod command;
+mod environment;
+mod files;
+mod requests;
+mod scheduler;
+mod security;
+
+pub use command::{print_help, run_config, warn_config_schema};
+pub use environment::print_environment;
+pub use scheduler::quota_config;
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::engine::cpu_dispatch::SimdTier;
+use crate::ipc::SocketPermissions;
+use crate::models::PlacementConfig;
+use crate::security::FipsMode;
+use crate::storage::{self, StorageConfig, StorageError};
+use crate::telemetry::ProfilerConfig;
+use crate::RuntimeConfig;
+
+use files::{deprecation_config, maintenance_config, presets_config, retention_config, warm_schedule_config};
+use requests::{limits_config, prefetch_config, probe_config, slo_config, usage_privacy_config};
+use scheduler::{circuit_config, gpu_share_config, hedge_config, queue_switch_policy, thermal_config};
+use security::{attestation_config, integrity_config, legal_hold_config, pii_block_config, policy_shadow_config};
+
+/// The runtime configuration, read from `CORE_*` environment settings.
+pub fn load_config() -> RuntimeConfig {
+    // In production, load from environment or config file
+    // For now, use secure defaults
+    RuntimeConfig {
+        base_path: PathBuf::from("."),
+        auth_token: std::env::var("CORE_AUTH_TOKEN").unwrap_or_default(),
+        admin_token: std::env::var("CORE_ADMIN_TOKEN").ok().filter(|t| !t.is_empty()),
+        observer_token: std::env::var("CORE_OBSERVER_TOKEN").ok().filter(|t| !t.is_empty()),
+        tenant_tokens: tenant_tokens_config(),
+        placement: placement_config(),
+        session_timeout: Duration::from_secs(3600),
+        max_context_length: 4096,
+        socket: socket_permissions(),
+        slo: slo_config(),
+        profiler: ProfilerConfig {
+            enabled: matches!(std::env::var("CORE_PROFILING").as_deref(), Ok("1" | "true")),
+            ..Default::default()
+        },
+        policy_shadow: policy_shadow_config(),
+        pii_block: pii_block_config(),
+        circuit: circuit_config(),
+        queue_on_switch: queue_switch_policy(),
+        warm_schedule: warm_schedule_config(),
+        usage_privacy: usage_privacy_config(),
+        retention: retention_config(),
+        quota: quota_config(),
+        hedge: hedge_config(),
+        prefetch: prefetch_config(),
+        limits: limits_config(),
+        probe: probe_config(),
+        presets: presets_config(),
+        gpu_share: gpu_share_config(),
+        thermal: thermal_config(),
+        attestation: attestation_config(),
+        integrity: integrity_config(),
+        deprecations: deprecation_config(),
+        legal_hold: legal_hold_config(),
+        maintenance: maintenance_config(),
+        ..Default::default()
+    }
+}
+
+/// Per-tenant handshake tokens from `CORE_TENANT_TOKENS` (`name=token,...`).
+fn tenant_tokens_config() -> Vec<(String, String)> {
+    let Ok(spec) = std::env::var("CORE_TENANT_TOKENS") else {
+        return Vec::new();
+    };
+    spec.split(',')
+        .filter(|entry| !entry.trim().is_empty())
+        .filter_map(|entry| match entry.split_once('=') {
+            Some((name, token)) if !name.trim().is_empty() && !token.is_empty() => {
+                Some((name.trim().to_string(), token.to_string()))
+            }
+            _ => {
+                eprintln!("Warning: ignoring malformed CORE_TENANT_TOKENS entry (expected name=token)");
+                None
+            }
+        })
+        .collect()
+}
+
+/// Placement adverts are enabled by setting `CORE_PLACEMENT_DIR`.
+pub fn placement_config() -> Option<PlacementConfig> {
+    let dir = std::env::var("CORE_PLACEMENT_DIR").ok().filter(|d| !d.is_empty())?;
+    let replica_id = std::env::var("CORE_REPLICA_ID")
+        .or_else(|_| std::env::var("HOSTNAME"))
+        .unwrap_or_else(|_| "replica".to_string());
+    Some(PlacementConfig {
+        dir: PathBuf::from(dir),
+        replica_id,
+        ttl: Duration::from_secs(30),
+    })
+}
+
+/// Socket mode (octal) and ownership from `CORE_SOCKET_MODE`,
+/// `CORE_SOCKET_UID` and `CORE_SOCKET_GID`; unset or invalid values keep
+/// the owner-only default.
+pub fn socket_permissions() -> SocketPermissions {
+    let env = |name| std::env::var(name).ok().filter(|v: &String| !v.is_empty());
+    let defaults = SocketPermissions::default();
+    SocketPermissions {
+        mode: env("CORE_SOCKET_MODE")
+            .and_then(|m| u32::from_str_radix(&m, 8).ok())
+            .unwrap_or(defaults.mode),
+        owner: env("CORE_SOCKET_UID").and_then(|u| u.parse().ok()),
+        group: env("CORE_SOCKET_GID").and_then(|g| g.parse().ok()),
+    }
+}
+
+/// Object storage from `CORE_STORAGE` and its `CORE_STORAGE_*` settings,
+/// with the standard AWS variables as fallbacks for region and credentials.
+pub fn storage_config() -> StorageConfig {
+    let var = |name: &str, fallback: &str| {
+        std::env::var(name).or_else(|_| std::env::var(fallback)).ok().filter(|v| !v.is_empty())
+    };
+    let mut config = StorageConfig {
+        url: std::env::var("CORE_STORAGE").ok().filter(|url| !url.is_empty()),
+        endpoint: std::env::var("CORE_STORAGE_ENDPOINT").ok().filter(|url| !url.is_empty()),
+        access_key: var("CORE_STORAGE_ACCESS_KEY", "AWS_ACCESS_KEY_ID").unwrap_or_default(),
+        secret_key: var("CORE_STORAGE_SECRET_KEY", "AWS_SECRET_ACCESS_KEY").unwrap_or_default(),
+        cache_dir: std::env::var("CORE_STORAGE_CACHE_DIR").ok().filter(|p| !p.is_empty()).map(PathBuf::from),
+        ..Default::default()
+    };
+    if let Some(region) = var("CORE_STORAGE_REGION", "AWS_REGION") {
+        config.region = region;
+    }
+    config
+}
+
+/// Install the configured object store for the process, if any.
+pub fn install_storage(base_path: &Path) -> Result<(), StorageError> {
+    if let Some(store) = storage::open(&storage_config(), base_path)? {
+        eprintln!("State storage: {}", store.describe());
+        storage::install(store);
+    }
+    Ok(())
+}
+
+/// FIPS mode from `CORE_FIPS_MODE`; an unknown mode is treated as strict,
+/// failing closed.
+pub fn fips_mode_setting() -> FipsMode {
+    let Ok(value) = std::env::var("CORE_FIPS_MODE") else {
+        return FipsMode::Off;
+    };
+    match value.parse() {
+        Ok(mode) => mode,
+        Err(e) => {
+            eprintln!("Warning: CORE_FIPS_MODE: {}; using strict", e);
+            FipsMode::Strict
+        }
+    }
+}
+
+/// SIMD tier cap from `CORE_SIMD_MAX_TIER`; an unknown tier is ignored.
+pub fn simd_tier_override() -> Option<SimdTier> {
+    let value = std::env::var("CORE_SIMD_MAX_TIER").ok().filter(|v| !v.is_empty())?;
+    match value.parse() {
+        Ok(tier) => Some(tier),
+        Err(e) => {
+            eprintln!("Warning: ignoring CORE_SIMD_MAX_TIER: {}", e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_config_reads_maintenance_windows() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("maintenance.json");
+        let windows = r#"{"windows": [{"start_minute": 120, "duration_minutes": 60}], "kv_idle_secs": 90}"#;
+        std::fs::write(&path, windows).unwrap();
+
+        std::env::set_var("CORE_MAINTENANCE", &path);
+        let config = load_config();
+        std::fs::write(&path, r#"{"windows": [{"start_minute": 1440, "duration_minutes": 60}]}"#).unwrap();
+        let invalid = load_config();
+        std::env::remove_var("CORE_MAINTENANCE");
+
+        assert_eq!(config.maintenance.windows.len(), 1);
+        assert_eq!(config.maintenance.windows[0].start_minute, 120);
+        assert_eq!(config.maintenance.kv_idle(), Duration::from_secs(90));
+        assert!(invalid.maintenance.windows.is_empty());
+    }
+}
@@ -0,0 +1,90 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Request handling settings: prefetch, size limits, probes, SLOs and
+//! usage export privacy.
+
+use crate::conversations::PrefetchConfig;
+use crate::ipc::{ProbeConfig, RequestLimits};
+use crate::telemetry::{DpConfig, SloConfig, SloIndicator};
+
+/// Follow-up prefetch: enabled by `CORE_CONVERSATION_PREFETCH`, bounded by
+/// `CORE_PREFETCH_MAX_CACHE_MB` of kept prefix state per model.
+pub(super) fn prefetch_config() -> PrefetchConfig {
+    let mut config = PrefetchConfig {
+        enabled: matches!(std::env::var("CORE_CONVERSATION_PREFETCH").as_deref(), Ok("1" | "true")),
+        ..Default::default()
+    };
+    if let Some(mb) = std::env::var("CORE_PREFETCH_MAX_CACHE_MB").ok().and_then(|v| v.parse::<usize>().ok()) {
+        config.max_cache_bytes = mb.saturating_mul(1024 * 1024);
+    }
+    config
+}
+
+/// Per-field size limits from `CORE_MAX_*`; 0 disables a limit.
+pub(super) fn limits_config() -> RequestLimits {
+    let mut limits = RequestLimits::default();
+    let read = |name: &str| std::env::var(name).ok().and_then(|v| v.parse::<usize>().ok());
+    if let Some(kb) = read("CORE_MAX_PROMPT_KB") {
+        limits.max_prompt_bytes = kb.saturating_mul(1024);
+    }
+    if let Some(tokens) = read("CORE_MAX_PROMPT_TOKENS") {
+        limits.max_prompt_tokens = tokens;
+    }
+    if let Some(kb) = read("CORE_MAX_METADATA_KB") {
+        limits.max_metadata_bytes = kb.saturating_mul(1024);
+    }
+    if let Some(kb) = read("CORE_MAX_OUTPUT_KB") {
+        limits.max_output_bytes = kb.saturating_mul(1024);
+    }
+    if let Some(tokens) = read("CORE_MAX_OUTPUT_TOKENS") {
+        limits.max_output_tokens = tokens;
+    }
+    limits
+}
+
+/// Liveness probe rate limit from `CORE_PROBE_MAX_PER_SEC`.
+pub(super) fn probe_config() -> ProbeConfig {
+    let mut config = ProbeConfig::default();
+    if let Some(max) = std::env::var("CORE_PROBE_MAX_PER_SEC").ok().and_then(|v| v.parse().ok()) {
+        config.max_per_sec = max;
+    }
+    config
+}
+
+/// SLO targets from `CORE_SLO_AVAILABILITY` (e.g. `0.995`) and
+/// `CORE_SLO_LATENCY_P95_MS`; unset or invalid values keep the defaults.
+pub(super) fn slo_config() -> SloConfig {
+    let env = |name| std::env::var(name).ok().and_then(|v| v.parse::<f64>().ok());
+    let mut config = SloConfig::default();
+    for objective in &mut config.objectives {
+        match &mut objective.indicator {
+            SloIndicator::Availability => {
+                if let Some(target) = env("CORE_SLO_AVAILABILITY").filter(|t| *t > 0.0 && *t < 1.0) {
+                    objective.target = target;
+                }
+            }
+            SloIndicator::Latency { threshold_ms, .. } => {
+                if let Some(ms) = env("CORE_SLO_LATENCY_P95_MS").filter(|ms| *ms > 0.0) {
+                    *threshold_ms = ms;
+                }
+            }
+        }
+    }
+    config
+}
+
+/// Usage export privacy from `CORE_USAGE_DP_EPSILON` (a positive budget)
+/// and `CORE_USAGE_DP_MAX_TOKENS`; without epsilon exports are exact.
+pub(super) fn usage_privacy_config() -> Option<DpConfig> {
+    let epsilon = std::env::var("CORE_USAGE_DP_EPSILON").ok().filter(|v| !v.is_empty())?;
+    let Some(epsilon) = epsilon.parse::<f64>().ok().filter(|e| e.is_finite() && *e > 0.0) else {
+        eprintln!("Warning: invalid CORE_USAGE_DP_EPSILON '{}', usage exports stay exact", epsilon);
+        return None;
+    };
+    let mut config = DpConfig { epsilon, ..Default::default() };
+    if let Some(max) = std::env::var("CORE_USAGE_DP_MAX_TOKENS").ok().and_then(|v| v.parse().ok()).filter(|m| *m > 0) {
+        config.max_tokens_per_request = max;
+    }
+    Some(config)
+}
@@ -0,0 +1,148 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Scheduler settings: quotas, hedging, switching, GPU sharing, thermal
+//! throttling and the circuit breaker.
+
+use std::time::Duration;
+
+use crate::scheduler::{
+    CircuitConfig, GpuShareConfig, GpuShareMode, HedgeConfig, QuotaConfig, SwitchPolicy, ThermalConfig,
+};
+
+/// Tenant quotas: `CORE_TENANT_RATE_LIMIT` requests per
+/// `CORE_TENANT_RATE_WINDOW_SECS`, shared through `CORE_QUOTA_BACKEND`.
+pub fn quota_config() -> QuotaConfig {
+    let mut config = QuotaConfig {
+        tenant_requests_per_window: std::env::var("CORE_TENANT_RATE_LIMIT")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|limit| *limit > 0),
+        backend_url: std::env::var("CORE_QUOTA_BACKEND").ok().filter(|url| !url.is_empty()),
+        ..Default::default()
+    };
+    if let Some(secs) = std::env::var("CORE_TENANT_RATE_WINDOW_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|s| *s > 0)
+    {
+        config.window = Duration::from_secs(secs);
+    }
+    config
+}
+
+/// Replica hedging: `CORE_HEDGE_PAIRS` is `primary=secondary[,...]`,
+/// raced after `CORE_HEDGE_TTFT_MS` for at most `CORE_HEDGE_MAX_RATIO`
+/// of paired requests.
+pub(super) fn hedge_config() -> HedgeConfig {
+    let mut config = HedgeConfig::default();
+    if let Ok(pairs) = std::env::var("CORE_HEDGE_PAIRS") {
+        for pair in pairs.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            match pair.split_once('=') {
+                Some((primary, secondary)) if !primary.is_empty() && !secondary.is_empty() && primary != secondary => {
+                    config.pairs.insert(primary.to_string(), secondary.to_string());
+                }
+                _ => eprintln!("Warning: ignoring invalid CORE_HEDGE_PAIRS entry '{}'", pair),
+            }
+        }
+    }
+    if let Some(ms) = std::env::var("CORE_HEDGE_TTFT_MS").ok().and_then(|v| v.parse().ok()) {
+        config.ttft_threshold = Duration::from_millis(ms);
+    }
+    if let Some(ratio) = std::env::var("CORE_HEDGE_MAX_RATIO")
+        .ok()
+        .and_then(|v| v.parse::<f64>().ok())
+        .filter(|r| *r > 0.0 && *r <= 1.0)
+    {
+        config.max_ratio = ratio;
+    }
+    config
+}
+
+/// Switch handling for queued requests from `CORE_QUEUE_ON_SWITCH`.
+pub(super) fn queue_switch_policy() -> SwitchPolicy {
+    let Ok(policy) = std::env::var("CORE_QUEUE_ON_SWITCH") else {
+        return SwitchPolicy::default();
+    };
+    policy.parse().unwrap_or_else(|e| {
+        eprintln!("Warning: {}, keeping queued requests on switch", e);
+        SwitchPolicy::default()
+    })
+}
+
+/// GPU sharing: `CORE_GPU_SHARE` picks the mode for the models listed in
+/// `CORE_GPU_SHARES` as `model=share[,...]`; `CORE_GPU_SLICE_MS` sets
+/// the time-sliced turn length.
+pub(super) fn gpu_share_config() -> GpuShareConfig {
+    let mut config = GpuShareConfig::default();
+    if let Ok(mode) = std::env::var("CORE_GPU_SHARE") {
+        match mode.parse::<GpuShareMode>() {
+            Ok(mode) => config.mode = mode,
+            Err(e) => eprintln!("Warning: {}, GPU sharing stays off", e),
+        }
+    }
+    if let Ok(shares) = std::env::var("CORE_GPU_SHARES") {
+        for entry in shares.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            match entry.split_once('=').map(|(model, share)| (model, share.parse::<u32>())) {
+                Some((model, Ok(share))) if !model.is_empty() && share > 0 => {
+                    config.shares.insert(model.to_string(), share);
+                }
+                _ => eprintln!("Warning: ignoring invalid CORE_GPU_SHARES entry '{}'", entry),
+            }
+        }
+    }
+    if let Some(ms) = std::env::var("CORE_GPU_SLICE_MS").ok().and_then(|v| v.parse().ok()).filter(|ms| *ms > 0) {
+        config.slice = Duration::from_millis(ms);
+    }
+    config
+}
+
+/// Thermal throttling: enabled by `CORE_THERMAL`, with limits from
+/// `CORE_THERMAL_TEMP_C` and `CORE_THERMAL_POWER_RATIO`.
+pub(super) fn thermal_config() -> ThermalConfig {
+    let mut config = ThermalConfig {
+        enabled: matches!(std::env::var("CORE_THERMAL").as_deref(), Ok("1" | "true")),
+        ..Default::default()
+    };
+    if let Some(limit) = std::env::var("CORE_THERMAL_TEMP_C").ok().and_then(|v| v.parse::<f64>().ok()) {
+        let margin = config.temp_limit_c - config.temp_recover_c;
+        config.temp_limit_c = limit;
+        config.temp_recover_c = limit - margin;
+    }
+    if let Some(ratio) = std::env::var("CORE_THERMAL_POWER_RATIO")
+        .ok()
+        .and_then(|v| v.parse::<f64>().ok())
+        .filter(|r| *r > 0.0 && *r <= 1.0)
+    {
+        let margin = config.power_limit_ratio - config.power_recover_ratio;
+        config.power_limit_ratio = ratio;
+        config.power_recover_ratio = (ratio - margin).max(0.0);
+    }
+    if let Some(batch) = std::env::var("CORE_THERMAL_BATCH").ok().and_then(|v| v.parse().ok()).filter(|b| *b > 0) {
+        config.throttled_batch = batch;
+    }
+    if let Ok(models) = std::env::var("CORE_THERMAL_GPU_MODELS") {
+        config.gpu_models = models.split(',').map(str::trim).filter(|m| !m.is_empty()).map(String::from).collect();
+    }
+    config
+}
+
+/// Circuit breaker settings: `CORE_CIRCUIT=off` disables it,
+/// `CORE_CIRCUIT_FAILURE_RATIO` and `CORE_CIRCUIT_OPEN_SECS` tune it.
+pub(super) fn circuit_config() -> CircuitConfig {
+    let mut config = CircuitConfig::default();
+    if matches!(std::env::var("CORE_CIRCUIT").as_deref(), Ok("off" | "0" | "false")) {
+        config.enabled = false;
+    }
+    if let Some(ratio) = std::env::var("CORE_CIRCUIT_FAILURE_RATIO")
+        .ok()
+        .and_then(|v| v.parse::<f64>().ok())
+        .filter(|r| *r > 0.0 && *r <= 1.0)
+    {
+        config.failure_ratio = ratio;
+    }
+    if let Some(secs) = std::env::var("CORE_CIRCUIT_OPEN_SECS").ok().and_then(|v| v.parse().ok()) {
+        config.open_duration = Duration::from_secs(secs);
+    }
+    config
+}
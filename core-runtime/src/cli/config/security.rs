@@ -0,0 +1,126 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Security settings: legal hold, attestation, integrity checks, shadow
+//! policy and PII blocking.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::models::attestation::{load_identities, TRUSTED_IDENTITIES_FILE};
+use crate::models::{AttestationConfig, AttestationPolicy, IntegrityConfig};
+use crate::security::{LegalHoldConfig, PIIType, PiiBlockConfig, ShadowConfig};
+
+/// Audit sampling from `CORE_AUDIT_SAMPLE_RATE` and legal hold limits from
+/// `CORE_LEGAL_HOLD_*`; the store and key default under the base path.
+pub(super) fn legal_hold_config() -> LegalHoldConfig {
+    let mut config = LegalHoldConfig {
+        key_path: std::env::var("CORE_LEGAL_HOLD_KEY").ok().filter(|p| !p.is_empty()).map(PathBuf::from),
+        ..Default::default()
+    };
+    if let Ok(rate) = std::env::var("CORE_AUDIT_SAMPLE_RATE") {
+        match rate.parse::<f64>() {
+            Ok(r) if (0.0..=1.0).contains(&r) => config.sample_rate = r,
+            _ => eprintln!("Warning: invalid CORE_AUDIT_SAMPLE_RATE '{}', inference audit sampling is off", rate),
+        }
+    }
+    if let Some(days) = std::env::var("CORE_LEGAL_HOLD_MAX_DAYS").ok().and_then(|v| v.parse().ok()).filter(|d| *d > 0) {
+        config.max_hold_days = days;
+    }
+    if let Some(days) = std::env::var("CORE_LEGAL_HOLD_RETAIN_DAYS").ok().and_then(|v| v.parse().ok()) {
+        config.retain_after_release_days = days;
+    }
+    config
+}
+
+/// Attestation policy from `CORE_ATTESTATION`, trusting the signers in
+/// `CORE_TRUSTED_IDENTITIES`. Bad settings fail closed: an unknown policy
+/// requires attestations and unreadable identities trust no one.
+pub(super) fn attestation_config() -> AttestationConfig {
+    let policy = match std::env::var("CORE_ATTESTATION").ok().filter(|v| !v.is_empty()) {
+        None => AttestationPolicy::Off,
+        Some(value) => value.parse().unwrap_or_else(|e| {
+            eprintln!("Warning: CORE_ATTESTATION: {}; requiring attestations", e);
+            AttestationPolicy::Require
+        }),
+    };
+    if policy == AttestationPolicy::Off {
+        return AttestationConfig::default();
+    }
+    let path = std::env::var("CORE_TRUSTED_IDENTITIES")
+        .ok()
+        .filter(|p| !p.is_empty())
+        .map_or_else(|| PathBuf::from(".").join(TRUSTED_IDENTITIES_FILE), PathBuf::from);
+    let identities = load_identities(&path).unwrap_or_else(|e| {
+        eprintln!("Warning: no trusted identities, every attestation will be refused: {}", e);
+        Vec::new()
+    });
+    AttestationConfig { policy, identities }
+}
+
+/// Model file re-verification: `CORE_INTEGRITY=off` disables it,
+/// `CORE_INTEGRITY_INTERVAL_SECS` and `CORE_INTEGRITY_MAX_MBPS` tune it.
+pub(super) fn integrity_config() -> IntegrityConfig {
+    let mut config = IntegrityConfig::default();
+    if matches!(std::env::var("CORE_INTEGRITY").as_deref(), Ok("off" | "0" | "false")) {
+        config.enabled = false;
+    }
+    if let Some(secs) = std::env::var("CORE_INTEGRITY_INTERVAL_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|s| *s > 0)
+    {
+        config.interval = Duration::from_secs(secs);
+    }
+    if let Some(mbps) = std::env::var("CORE_INTEGRITY_MAX_MBPS").ok().and_then(|v| v.parse::<u64>().ok()) {
+        config.max_bytes_per_sec = mbps * 1024 * 1024;
+    }
+    config
+}
+
+/// Candidate policy from the JSON file named by `CORE_POLICY_SHADOW`,
+/// sampled into audit every `CORE_POLICY_SHADOW_SAMPLE` divergences.
+/// A candidate that fails to load only disables shadowing: it never
+/// affects responses.
+pub(super) fn policy_shadow_config() -> Option<ShadowConfig> {
+    let path = std::env::var("CORE_POLICY_SHADOW").ok().filter(|p| !p.is_empty())?;
+    let candidate = std::fs::read(&path)
+        .map_err(|e| e.to_string())
+        .and_then(|bytes| serde_json::from_slice(&bytes).map_err(|e| e.to_string()));
+    match candidate {
+        Ok(candidate) => {
+            let mut config = ShadowConfig { candidate, ..Default::default() };
+            if let Some(n) = std::env::var("CORE_POLICY_SHADOW_SAMPLE").ok().and_then(|v| v.parse().ok()) {
+                config.sample_every = n;
+            }
+            Some(config)
+        }
+        Err(e) => {
+            eprintln!("Warning: shadow policy disabled, cannot load {}: {}", path, e);
+            None
+        }
+    }
+}
+
+/// PII types from `CORE_PII_BLOCK` (comma-separated, e.g. `ssn,credit_card`)
+/// whose detection at `CORE_PII_BLOCK_CONFIDENCE` or above fails the
+/// response instead of emitting it.
+pub(super) fn pii_block_config() -> Option<PiiBlockConfig> {
+    let types = std::env::var("CORE_PII_BLOCK").ok().filter(|v| !v.is_empty())?;
+    let mut config = PiiBlockConfig::default();
+    for name in types.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+        match name.parse::<PIIType>() {
+            Ok(pii_type) if !config.types.contains(&pii_type) => config.types.push(pii_type),
+            Ok(_) => {}
+            Err(e) => eprintln!("Warning: CORE_PII_BLOCK: {}", e),
+        }
+    }
+    if let Some(confidence) = std::env::var("CORE_PII_BLOCK_CONFIDENCE")
+        .ok()
+        .and_then(|v| v.parse::<f32>().ok())
+        .filter(|c| (0.0..=1.0).contains(c))
+    {
+        config.min_confidence = confidence;
+    }
+    Some(config).filter(|c| !c.types.is_empty())
+}
@@ -0,0 +1,61 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! `conversations`: list or evict server-held conversations.
+
+use super::ipc_client::CliIpcClient;
+use super::get_socket_path;
+
+/// Run the conversations admin command.
+pub async fn run_conversations(args: &[String]) -> i32 {
+    let client = CliIpcClient::new(get_socket_path());
+    let result = match (args.get(2).map(|s| s.as_str()), args.get(3)) {
+        (Some("list") | None, _) => client.list_conversations().await.map(|conversations| {
+            for c in conversations {
+                println!(
+                    "{}\t{}\t{} turns\t{} bytes\tidle {}s",
+                    c.conversation_id, c.model_id, c.turns, c.history_bytes, c.idle_secs
+                );
+            }
+        }),
+        (Some("evict"), Some(id)) => client.evict_conversation(id).await.map(|evicted| {
+            if evicted {
+                println!("Evicted {}", id);
+            } else {
+                println!("No conversation {}", id);
+            }
+        }),
+        _ => {
+            print_help();
+            return 1;
+        }
+    };
+    match result {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            3
+        }
+    }
+}
+
+/// Print detailed help for `conversations`.
+pub fn print_help() {
+    eprintln!(
+        "GG-CORE conversations - Manage server-held conversations
+
+USAGE:
+    GG-CORE conversations <SUBCOMMAND>
+
+SUBCOMMANDS:
+    list        List live conversations (id, model, turns, size, idle time)
+    evict <ID>  Drop a conversation and its history
+
+Requires an admin session when authentication is enabled.
+
+EXAMPLES:
+    GG-CORE conversations list
+    GG-CORE conversations evict 3f2b9c1e-...
+"
+    );
+}
@@ -0,0 +1,135 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The `doctor` command: gathers inputs from this process's configuration.
+
+use std::path::PathBuf;
+
+use crate::engine::cpu_dispatch::{self, SimdTier};
+use crate::models::AttestationPolicy;
+use crate::scheduler::{GpuShareMode, SwitchPolicy};
+use crate::security::{legal_hold, FipsMode, PIIType};
+
+use super::{run_checks, CheckStatus, DoctorInputs, EnvKind, Sink};
+use crate::cli::about::build_features;
+use crate::cli::config::{load_config, quota_config, simd_tier_override, storage_config};
+use crate::cli::get_socket_path;
+
+/// Settings `doctor` validates when set.
+pub const DOCTOR_ENV: &[(&str, EnvKind)] = &[
+    ("CORE_POLICY_SHADOW", EnvKind::JsonFile),
+    ("CORE_WARM_SCHEDULE", EnvKind::JsonFile),
+    ("CORE_RETENTION", EnvKind::JsonFile),
+    ("CORE_DEPRECATIONS", EnvKind::JsonFile),
+    ("CORE_MAINTENANCE", EnvKind::JsonFile),
+    ("CORE_TRUSTED_IDENTITIES", EnvKind::JsonFile),
+    ("CORE_POLICY_SHADOW_SAMPLE", EnvKind::Integer),
+    ("CORE_PII_BLOCK_CONFIDENCE", EnvKind::Float),
+    ("CORE_SLO_AVAILABILITY", EnvKind::Float),
+    ("CORE_SLO_LATENCY_P95_MS", EnvKind::Integer),
+    ("CORE_CIRCUIT_FAILURE_RATIO", EnvKind::Float),
+    ("CORE_CIRCUIT_OPEN_SECS", EnvKind::Integer),
+    ("CORE_INTEGRITY_INTERVAL_SECS", EnvKind::Integer),
+    ("CORE_INTEGRITY_MAX_MBPS", EnvKind::Integer),
+    ("CORE_TENANT_RATE_LIMIT", EnvKind::Integer),
+    ("CORE_TENANT_RATE_WINDOW_SECS", EnvKind::Integer),
+    ("CORE_AUDIT_SAMPLE_RATE", EnvKind::Float),
+    ("CORE_MAX_PROMPT_KB", EnvKind::Integer),
+    ("CORE_MAX_OUTPUT_KB", EnvKind::Integer),
+    ("CORE_PROBE_MAX_PER_SEC", EnvKind::Integer),
+    ("CORE_MIGRATIONS", EnvKind::OneOf(&["auto", "check"])),
+    ("CORE_ATTESTATION", EnvKind::Parsed(|v| v.parse::<AttestationPolicy>().map(|_| ()))),
+    ("CORE_FIPS_MODE", EnvKind::Parsed(|v| v.parse::<FipsMode>().map(|_| ()))),
+    ("CORE_GPU_SHARE", EnvKind::Parsed(|v| v.parse::<GpuShareMode>().map(|_| ()))),
+    ("CORE_QUEUE_ON_SWITCH", EnvKind::Parsed(|v| v.parse::<SwitchPolicy>().map(|_| ()))),
+    ("CORE_SIMD_MAX_TIER", EnvKind::Parsed(|v| v.parse::<SimdTier>().map(|_| ()))),
+    ("CORE_PII_BLOCK", EnvKind::Parsed(|v| {
+        v.split(',').map(str::trim).filter(|t| !t.is_empty()).try_for_each(|t| t.parse::<PIIType>().map(|_| ()))
+    })),
+];
+
+/// Run the doctor CLI command: diagnose the installation from this
+/// process's configuration. Exits 1 when any check fails.
+pub fn run_doctor(args: &[String]) -> i32 {
+    let config = load_config();
+    let socket_path = args
+        .iter()
+        .position(|a| a == "--socket")
+        .and_then(|i| args.get(i + 1).cloned())
+        .unwrap_or_else(get_socket_path);
+    let env = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+
+    let storage = storage_config();
+    let mut sinks = Vec::new();
+    if let Some(url) = storage.url.as_ref().and(storage.endpoint.clone()) {
+        sinks.push(Sink { name: "storage".into(), url });
+    }
+    if let Some(url) = quota_config().backend_url {
+        sinks.push(Sink { name: "quota".into(), url });
+    }
+    let legal_hold_key = config
+        .legal_hold
+        .key_path
+        .clone()
+        .unwrap_or_else(|| legal_hold::default_key_path(&config.base_path));
+    let shared_dirs = env("CORE_PLACEMENT_DIR").map(|dir| ("placement dir".to_string(), PathBuf::from(dir)));
+    let inputs = DoctorInputs {
+        features: build_features(),
+        salt_path: match storage.url {
+            Some(_) => None,
+            None => crate::security::encryption::installation_salt_path().ok(),
+        },
+        key_files: vec![
+            ("purge receipt key".into(), crate::security::receipt::default_key_path(&config.base_path)),
+            ("legal hold key".into(), legal_hold_key),
+        ],
+        env: DOCTOR_ENV.to_vec(),
+        shared_dirs: shared_dirs.into_iter().collect(),
+        sinks,
+        base_path: config.base_path,
+        socket_path,
+    };
+    let report = run_checks(&inputs, cpu_dispatch::init(simd_tier_override()));
+
+    if args.iter().any(|a| a == "--json") {
+        println!("{}", serde_json::to_string_pretty(&report).unwrap_or_default());
+    } else {
+        print!("{}", report.render());
+    }
+    i32::from(report.status() == CheckStatus::Fail)
+}
+
+/// Print detailed help for `doctor`.
+pub fn print_help() {
+    eprintln!(
+        "GG-CORE doctor - Diagnose the installation
+
+USAGE:
+    GG-CORE doctor [OPTIONS]
+
+OPTIONS:
+    --socket PATH  Override IPC socket path
+    --json         Output the report in JSON format
+
+DESCRIPTION:
+    Checks the installation without a running server and prints a fix
+    for each problem found:
+    - Build features, SIMD tier and GPU driver
+    - base_path exists, is writable and not world-writable
+    - Installation salt and key files are present and private (0600)
+    - Socket path is free, stale, or already served by another instance
+    - CORE_* settings parse and their JSON files are valid
+    - Clock is synchronized (and agrees with an HTTP sink's clock)
+    - CORE_STORAGE_ENDPOINT, CORE_QUOTA_BACKEND and CORE_PLACEMENT_DIR
+      are reachable
+
+EXIT CODES:
+    0  No failures (warnings may be present)
+    1  One or more checks failed
+
+EXAMPLES:
+    GG-CORE doctor
+    GG-CORE doctor --json
+"
+    );
+}
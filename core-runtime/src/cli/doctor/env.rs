@@ -0,0 +1,72 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Validation of configuration from the environment.
+
+use super::{Check, EnvKind};
+
+pub(super) fn check_env(vars: &[(&'static str, EnvKind)]) -> Vec<Check> {
+    let mut checks = Vec::new();
+    let mut valid = 0;
+    for (name, kind) in vars {
+        let Ok(value) = std::env::var(name) else {
+            continue;
+        };
+        if value.is_empty() {
+            continue;
+        }
+        match validate_env(&value, *kind) {
+            Ok(()) => valid += 1,
+            Err(problem) => checks.push(Check::fail(
+                format!("config {}", name),
+                problem,
+                format!("correct or unset {}; invalid values fall back to defaults or disable the feature", name),
+            )),
+        }
+    }
+    if checks.is_empty() {
+        checks.push(Check::ok("config", format!("{} settings valid", valid)));
+    }
+    checks
+}
+
+pub(super) fn validate_env(value: &str, kind: EnvKind) -> Result<(), String> {
+    match kind {
+        EnvKind::JsonFile => {
+            let data = std::fs::read(value).map_err(|e| format!("cannot read {}: {}", value, e))?;
+            serde_json::from_slice::<serde_json::Value>(&data)
+                .map(|_| ())
+                .map_err(|e| format!("{} is not valid JSON: {}", value, e))
+        }
+        EnvKind::Integer => value.parse::<i64>().map(|_| ()).map_err(|_| format!("'{}' is not an integer", value)),
+        EnvKind::Float => value.parse::<f64>().map(|_| ()).map_err(|_| format!("'{}' is not a number", value)),
+        EnvKind::OneOf(words) => {
+            if words.iter().any(|w| w.eq_ignore_ascii_case(value)) {
+                Ok(())
+            } else {
+                Err(format!("'{}' is not one of {}", value, words.join(", ")))
+            }
+        }
+        EnvKind::Parsed(parse) => parse(value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_env_validation() {
+        assert!(validate_env("42", EnvKind::Integer).is_ok());
+        assert!(validate_env("0.5x", EnvKind::Float).is_err());
+        assert!(validate_env("Strict", EnvKind::OneOf(&["off", "strict"])).is_ok());
+        let positive = EnvKind::Parsed(|v| v.parse::<u8>().map(|_| ()).map_err(|e| e.to_string()));
+        assert!(validate_env("300", positive).is_err());
+
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("retention.json");
+        std::fs::write(&file, "{\"audit\": ").unwrap();
+        let err = validate_env(file.to_str().unwrap(), EnvKind::JsonFile).unwrap_err();
+        assert!(err.contains("not valid JSON"), "{err}");
+    }
+}
@@ -0,0 +1,169 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Checks of the base path, secret files, the socket and shared directories.
+
+use std::path::Path;
+
+use super::Check;
+
+/// Smallest installation salt the runtime accepts.
+const MIN_SALT_BYTES: u64 = 16;
+
+pub(super) fn check_base_path(base_path: &Path) -> Check {
+    let shown = base_path.display();
+    let metadata = match std::fs::metadata(base_path) {
+        Ok(metadata) if metadata.is_dir() => metadata,
+        Ok(_) => {
+            let detail = format!("{} is not a directory", shown);
+            return Check::fail("base_path", detail, "point base_path at a directory");
+        }
+        Err(e) => {
+            return Check::fail("base_path", format!("{}: {}", shown, e), format!("mkdir -p {}", shown));
+        }
+    };
+    let probe = base_path.join(format!(".doctor-{}", std::process::id()));
+    if let Err(e) = std::fs::write(&probe, b"").and_then(|()| std::fs::remove_file(&probe)) {
+        return Check::fail(
+            "base_path",
+            format!("{} is not writable: {}", shown, e),
+            format!("chown the directory to the service user, or run as its owner: {}", shown),
+        );
+    }
+    if let Some(mode) = unix_mode(&metadata).filter(|mode| mode & 0o002 != 0) {
+        return Check::warn(
+            "base_path",
+            format!("{} is world-writable ({:o})", shown, mode & 0o777),
+            format!("chmod o-w {}", shown),
+        );
+    }
+    Check::ok("base_path", format!("{} writable", shown))
+}
+
+pub(super) fn check_salt(salt_path: Option<&Path>) -> Check {
+    let Some(path) = salt_path else {
+        return Check::skip("salt", "kept in object storage");
+    };
+    let shown = path.display();
+    match std::fs::metadata(path) {
+        Err(_) => Check::warn(
+            "salt",
+            format!("{} missing; a new one is created on first start", shown),
+            "if this host served encrypted models before, restore the salt with `GG-CORE backup restore` first",
+        ),
+        Ok(metadata) if metadata.len() < MIN_SALT_BYTES => Check::fail(
+            "salt",
+            format!("{} is {} bytes, below {}", shown, metadata.len(), MIN_SALT_BYTES),
+            "restore the salt from a backup; the runtime would replace it and lose derived keys",
+        ),
+        Ok(metadata) => private_file_check("salt", path, &metadata),
+    }
+}
+
+pub(super) fn check_key_file(name: &str, path: &Path) -> Check {
+    match std::fs::metadata(path) {
+        Err(_) => Check::skip(name, format!("{} not created yet", path.display())),
+        Ok(metadata) => private_file_check(name, path, &metadata),
+    }
+}
+
+/// Secret files must not be readable beyond their owner.
+fn private_file_check(name: &str, path: &Path, metadata: &std::fs::Metadata) -> Check {
+    match unix_mode(metadata).filter(|mode| mode & 0o077 != 0) {
+        Some(mode) => Check::warn(
+            name,
+            format!("{} is accessible to other users ({:o})", path.display(), mode & 0o777),
+            format!("chmod 600 {}", path.display()),
+        ),
+        None => Check::ok(name, format!("{} present", path.display())),
+    }
+}
+
+#[cfg(unix)]
+fn unix_mode(metadata: &std::fs::Metadata) -> Option<u32> {
+    use std::os::unix::fs::PermissionsExt;
+    Some(metadata.permissions().mode())
+}
+
+#[cfg(not(unix))]
+fn unix_mode(_metadata: &std::fs::Metadata) -> Option<u32> {
+    None
+}
+
+#[cfg(unix)]
+pub(super) fn check_socket(socket_path: &str) -> Check {
+    use std::os::unix::net::UnixStream;
+
+    if socket_path.starts_with('@') {
+        return Check::ok("socket", format!("{} (abstract)", socket_path));
+    }
+    let path = Path::new(socket_path);
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty() && !p.is_dir()) {
+        return Check::fail(
+            "socket",
+            format!("directory {} does not exist", parent.display()),
+            format!("mkdir -p {}, or set VERITAS_SOCKET_PATH", parent.display()),
+        );
+    }
+    if !path.exists() {
+        return Check::ok("socket", format!("{} free", socket_path));
+    }
+    match UnixStream::connect(path) {
+        Ok(_) => Check::warn(
+            "socket",
+            format!("a server is already listening on {}", socket_path),
+            "stop the running instance before `serve`, or give this one its own VERITAS_SOCKET_PATH",
+        ),
+        Err(_) => Check::warn(
+            "socket",
+            format!("stale socket file {} (nothing listening)", socket_path),
+            format!("rm {} if no runtime is starting up", socket_path),
+        ),
+    }
+}
+
+#[cfg(windows)]
+pub(super) fn check_socket(socket_path: &str) -> Check {
+    Check::ok("socket", format!("{} (named pipe)", socket_path))
+}
+
+pub(super) fn check_shared_dir(name: &str, path: &Path) -> Check {
+    match std::fs::read_dir(path) {
+        Ok(_) => Check::ok(name, format!("{} readable", path.display())),
+        Err(e) => Check::fail(
+            name,
+            format!("{}: {}", path.display(), e),
+            "mount the shared directory on this host and grant the service user access",
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::CheckStatus;
+    #[cfg(unix)]
+    #[test]
+    fn test_file_checks_report_fixes() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(check_base_path(dir.path()).status, CheckStatus::Ok);
+        assert_eq!(check_base_path(&dir.path().join("missing")).status, CheckStatus::Fail);
+
+        let salt = dir.path().join(".gg-core-salt");
+        assert_eq!(check_salt(Some(&salt)).status, CheckStatus::Warn);
+        std::fs::write(&salt, [7u8; 32]).unwrap();
+        std::fs::set_permissions(&salt, std::fs::Permissions::from_mode(0o644)).unwrap();
+        let loose = check_salt(Some(&salt));
+        assert_eq!(loose.status, CheckStatus::Warn);
+        assert!(loose.fix.unwrap().starts_with("chmod 600"));
+        std::fs::set_permissions(&salt, std::fs::Permissions::from_mode(0o600)).unwrap();
+        assert_eq!(check_salt(Some(&salt)).status, CheckStatus::Ok);
+
+        // A socket file nobody listens on is stale
+        let socket = dir.path().join("core.sock");
+        drop(std::os::unix::net::UnixListener::bind(&socket).unwrap());
+        assert!(check_socket(socket.to_str().unwrap()).detail.starts_with("stale"));
+    }
+}
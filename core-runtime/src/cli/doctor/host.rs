@@ -0,0 +1,58 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Checks of the build and the hardware it runs on.
+
+use std::path::Path;
+
+use super::Check;
+use crate::engine::cpu_dispatch::{Dispatch, SimdTier};
+
+pub(super) fn check_build(features: &[&str]) -> Check {
+    let list = if features.is_empty() { "(no optional features)".to_string() } else { features.join(", ") };
+    if !features.iter().any(|f| matches!(*f, "gguf" | "onnx" | "safetensors")) {
+        return Check::warn(
+            "build",
+            format!("{}; no model backend compiled in", list),
+            "install a build with --features gguf (or onnx, safetensors) to load models",
+        );
+    }
+    Check::ok("build", list)
+}
+
+pub(super) fn check_simd(dispatch: &Dispatch) -> Check {
+    let detail = format!("{} (detected {})", dispatch.max_tier, dispatch.detected);
+    if dispatch.overridden {
+        return Check::warn("simd", detail, "unset CORE_SIMD_MAX_TIER unless debugging a kernel");
+    }
+    if dispatch.detected == SimdTier::Scalar && cfg!(target_arch = "x86_64") {
+        return Check::warn(
+            "simd",
+            format!("{}; no AVX2, kernels run scalar", detail),
+            "run on a CPU with AVX2, or expose it to the VM (e.g. -cpu host)",
+        );
+    }
+    Check::ok("simd", detail)
+}
+
+pub(super) fn check_gpu(features: &[&str]) -> Check {
+    if features.contains(&"cuda") {
+        let driver = Path::new("/proc/driver/nvidia/version");
+        return match std::fs::read_to_string(driver) {
+            Ok(version) => Check::ok("gpu", version.lines().next().unwrap_or("NVIDIA driver loaded").trim()),
+            Err(_) if Path::new("/dev/nvidiactl").exists() => Check::ok("gpu", "NVIDIA device nodes present"),
+            Err(_) => Check::fail(
+                "gpu",
+                "CUDA build but no NVIDIA driver found",
+                "install the NVIDIA driver (check with nvidia-smi), or pass the GPU into the container",
+            ),
+        };
+    }
+    if features.contains(&"metal") {
+        return match cfg!(target_os = "macos") {
+            true => Check::ok("gpu", "Metal"),
+            false => Check::fail("gpu", "Metal build on a non-macOS host", "install the build for this platform"),
+        };
+    }
+    Check::skip("gpu", "CPU-only build")
+}
@@ -0,0 +1,211 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Installation diagnostics for `GG-CORE doctor`.
+//!
+//! Each check inspects one part of the installation without starting the
+//! runtime: what the binary was built with, the CPU and GPU it runs on,
+//! base path and key file permissions, the socket path, configuration
+//! from the environment, the system clock and reachability of configured
+//! sinks. Problems carry the fix an operator should try first. The
+//! report prints as text or, with `--json`, as a [`DoctorReport`].
+
+mod command;
+mod env;
+mod files;
+mod host;
+mod network;
+
+pub use command::{print_help, run_doctor, DOCTOR_ENV};
+
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::engine::cpu_dispatch::Dispatch;
+
+use env::{check_env, validate_env};
+use files::{check_base_path, check_key_file, check_salt, check_shared_dir, check_socket};
+use host::{check_build, check_gpu, check_simd};
+use network::{check_clock, check_sink};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Ok,
+    /// Not applicable to this build or configuration.
+    Skip,
+    Warn,
+    Fail,
+}
+
+impl CheckStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Ok => "OK",
+            Self::Skip => "SKIP",
+            Self::Warn => "WARN",
+            Self::Fail => "FAIL",
+        }
+    }
+}
+
+/// Outcome of one check.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Check {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+    /// What to do about a warning or failure.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix: Option<String>,
+}
+
+impl Check {
+    fn new(name: impl Into<String>, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self { name: name.into(), status, detail: detail.into(), fix: None }
+    }
+
+    fn ok(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self::new(name, CheckStatus::Ok, detail)
+    }
+
+    fn skip(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self::new(name, CheckStatus::Skip, detail)
+    }
+
+    fn warn(name: impl Into<String>, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self { fix: Some(fix.into()), ..Self::new(name, CheckStatus::Warn, detail) }
+    }
+
+    fn fail(name: impl Into<String>, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self { fix: Some(fix.into()), ..Self::new(name, CheckStatus::Fail, detail) }
+    }
+}
+
+/// How an environment variable's value is validated.
+#[derive(Debug, Clone, Copy)]
+pub enum EnvKind {
+    /// Path to a JSON file.
+    JsonFile,
+    Integer,
+    Float,
+    /// One of the listed words, case-insensitive.
+    OneOf(&'static [&'static str]),
+    /// Accepted by the setting's own parser.
+    Parsed(fn(&str) -> Result<(), String>),
+}
+
+impl EnvKind {
+    /// Check `value` against this kind.
+    pub fn validate(self, value: &str) -> Result<(), String> {
+        validate_env(value, self)
+    }
+}
+
+/// A configured remote endpoint, e.g. the object store.
+#[derive(Debug, Clone)]
+pub struct Sink {
+    pub name: String,
+    /// `scheme://[user@]host:port[/...]`.
+    pub url: String,
+}
+
+/// What the checks inspect, gathered from the configuration.
+#[derive(Debug, Clone, Default)]
+pub struct DoctorInputs {
+    /// Cargo features compiled in.
+    pub features: Vec<&'static str>,
+    pub base_path: PathBuf,
+    pub socket_path: String,
+    /// Local salt file; `None` when it lives in object storage.
+    pub salt_path: Option<PathBuf>,
+    /// Key files by purpose; absent ones are created when first needed.
+    pub key_files: Vec<(String, PathBuf)>,
+    /// Environment variables to validate when set.
+    pub env: Vec<(&'static str, EnvKind)>,
+    /// Directories the runtime shares with other replicas.
+    pub shared_dirs: Vec<(String, PathBuf)>,
+    pub sinks: Vec<Sink>,
+}
+
+/// All check results.
+#[derive(Debug, Clone, Serialize)]
+pub struct DoctorReport {
+    pub version: String,
+    pub checked_at: DateTime<Utc>,
+    pub checks: Vec<Check>,
+}
+
+impl DoctorReport {
+    /// Worst status across the checks.
+    pub fn status(&self) -> CheckStatus {
+        self.checks.iter().map(|c| c.status).max().unwrap_or(CheckStatus::Ok)
+    }
+
+    pub fn count(&self, status: CheckStatus) -> usize {
+        self.checks.iter().filter(|c| c.status == status).count()
+    }
+
+    /// Human-readable report with a fix under each problem.
+    pub fn render(&self) -> String {
+        let mut out = format!("GG-CORE {} doctor\n\n", self.version);
+        let width = self.checks.iter().map(|c| c.name.len()).max().unwrap_or(0);
+        for check in &self.checks {
+            out.push_str(&format!("  {:<5} {:<width$}  {}\n", check.status.as_str(), check.name, check.detail));
+            if let Some(fix) = &check.fix {
+                out.push_str(&format!("        {:<width$}  fix: {}\n", "", fix));
+            }
+        }
+        out.push_str(&format!(
+            "\n{} ok, {} warnings, {} failures\n",
+            self.count(CheckStatus::Ok),
+            self.count(CheckStatus::Warn),
+            self.count(CheckStatus::Fail)
+        ));
+        out
+    }
+}
+
+/// Run every check.
+pub fn run_checks(inputs: &DoctorInputs, dispatch: &Dispatch) -> DoctorReport {
+    let mut checks = vec![check_build(&inputs.features), check_simd(dispatch), check_gpu(&inputs.features)];
+    checks.push(check_base_path(&inputs.base_path));
+    checks.push(check_salt(inputs.salt_path.as_deref()));
+    checks.extend(inputs.key_files.iter().map(|(name, path)| check_key_file(name, path)));
+    checks.push(check_socket(&inputs.socket_path));
+    checks.extend(check_env(&inputs.env));
+    checks.extend(inputs.shared_dirs.iter().map(|(name, path)| check_shared_dir(name, path)));
+
+    let mut remote_time = None;
+    for sink in &inputs.sinks {
+        let (check, date) = check_sink(sink);
+        remote_time = remote_time.or(date);
+        checks.push(check);
+    }
+    checks.push(check_clock(remote_time));
+
+    DoctorReport { version: env!("CARGO_PKG_VERSION").to_string(), checked_at: Utc::now(), checks }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_status_and_render() {
+        let report = DoctorReport {
+            version: "0.0.0".into(),
+            checked_at: Utc::now(),
+            checks: vec![
+                Check::ok("build", "gguf"),
+                Check::warn("socket", "stale socket file", "rm it"),
+            ],
+        };
+        assert_eq!(report.status(), CheckStatus::Warn);
+        let text = report.render();
+        assert!(text.contains("WARN  socket") && text.contains("fix: rm it"), "{text}");
+        assert!(text.ends_with("1 ok, 1 warnings, 0 failures\n"));
+    }
+}
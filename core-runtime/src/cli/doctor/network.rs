@@ -0,0 +1,123 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Reachability of configured sinks and the system clock.
+
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use super::{Check, Sink};
+
+/// Deadline for each network probe.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Clock difference from a sink's `Date` header worth reporting.
+const MAX_CLOCK_SKEW: Duration = Duration::from_secs(30);
+
+/// Connect to a sink; plain HTTP sinks also report their clock.
+pub(super) fn check_sink(sink: &Sink) -> (Check, Option<DateTime<Utc>>) {
+    let name = format!("sink {}", sink.name);
+    let Some((scheme, host, port)) = parse_endpoint(&sink.url) else {
+        return (Check::fail(name, format!("cannot parse {}", sink.url), "use scheme://host:port"), None);
+    };
+    let addr = match (host.as_str(), port).to_socket_addrs().map(|mut addrs| addrs.next()) {
+        Ok(Some(addr)) => addr,
+        Ok(None) | Err(_) => {
+            let fix = "check the host name and this host's DNS resolver";
+            return (Check::fail(name, format!("cannot resolve {}", host), fix), None);
+        }
+    };
+    let mut stream = match TcpStream::connect_timeout(&addr, PROBE_TIMEOUT) {
+        Ok(stream) => stream,
+        Err(e) => {
+            let detail = format!("{}:{} unreachable: {}", host, port, e);
+            return (Check::fail(name, detail, "check the endpoint is up and firewalls allow this host"), None);
+        }
+    };
+    let date = (scheme == "http").then(|| http_date(&mut stream, &host)).flatten();
+    (Check::ok(name, format!("{}:{} reachable", host, port)), date)
+}
+
+/// `(scheme, host, port)` of a URL, with the scheme's default port.
+fn parse_endpoint(url: &str) -> Option<(String, String, u16)> {
+    let (scheme, rest) = url.split_once("://")?;
+    let authority = rest.split('/').next()?.rsplit('@').next()?;
+    let default_port = match scheme {
+        "http" => 80,
+        "https" => 443,
+        "redis" => 6379,
+        _ => return None,
+    };
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) if !host.ends_with(']') || authority.starts_with('[') => (host, port.parse().ok()?),
+        _ => (authority, default_port),
+    };
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    (!host.is_empty()).then(|| (scheme.to_string(), host.to_string(), port))
+}
+
+/// The `Date` header of a `HEAD /` response.
+fn http_date(stream: &mut TcpStream, host: &str) -> Option<DateTime<Utc>> {
+    stream.set_read_timeout(Some(PROBE_TIMEOUT)).ok()?;
+    write!(stream, "HEAD / HTTP/1.0\r\nHost: {}\r\n\r\n", host).ok()?;
+    let mut response = vec![0u8; 4096];
+    let n = stream.read(&mut response).ok()?;
+    String::from_utf8_lossy(&response[..n]).lines().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        if !name.eq_ignore_ascii_case("date") {
+            return None;
+        }
+        DateTime::parse_from_rfc2822(value.trim()).ok().map(|d| d.with_timezone(&Utc))
+    })
+}
+
+/// Compare against a sink's clock when one answered, and ask the kernel
+/// whether the clock is synchronized.
+pub(super) fn check_clock(remote: Option<DateTime<Utc>>) -> Check {
+    if let Some(remote) = remote {
+        let skew = (Utc::now() - remote).abs().to_std().unwrap_or_default();
+        if skew > MAX_CLOCK_SKEW {
+            return Check::fail(
+                "clock",
+                format!("{}s off a sink's clock", skew.as_secs()),
+                "enable time sync (chronyd or systemd-timesyncd); signatures, holds and retention use wall time",
+            );
+        }
+    }
+    if clock_unsynchronized() {
+        return Check::warn(
+            "clock",
+            "system clock is not synchronized",
+            "enable time sync (chronyd or systemd-timesyncd)",
+        );
+    }
+    Check::ok("clock", if remote.is_some() { "synchronized, matches sink" } else { "synchronized" })
+}
+
+#[cfg(target_os = "linux")]
+fn clock_unsynchronized() -> bool {
+    // SAFETY: modes = 0 only reads the kernel clock state
+    let mut timex: libc::timex = unsafe { std::mem::zeroed() };
+    unsafe { libc::adjtimex(&mut timex) == libc::TIME_ERROR }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn clock_unsynchronized() -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_endpoint() {
+        assert_eq!(parse_endpoint("http://minio:9000/bucket"), Some(("http".into(), "minio".into(), 9000)));
+        assert_eq!(parse_endpoint("redis://:secret@cache/0"), Some(("redis".into(), "cache".into(), 6379)));
+        assert_eq!(parse_endpoint("https://[::1]:8443"), Some(("https".into(), "::1".into(), 8443)));
+        assert_eq!(parse_endpoint("minio:9000"), None);
+    }
+}
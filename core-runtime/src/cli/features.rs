@@ -0,0 +1,64 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! `features`: show or toggle runtime feature flags.
+
+use super::ipc_client::CliIpcClient;
+use super::get_socket_path;
+
+pub async fn run_features(args: &[String]) -> i32 {
+    let client = CliIpcClient::new(get_socket_path());
+    let result = match (args.get(2).map(|s| s.as_str()), args.get(3)) {
+        (Some("list") | None, _) => client.features().await,
+        (Some("enable"), Some(name)) => client.set_feature(name, true).await,
+        (Some("disable"), Some(name)) => client.set_feature(name, false).await,
+        _ => {
+            print_help();
+            return 1;
+        }
+    };
+    match result {
+        Ok(features) => {
+            for f in features {
+                let state = match (f.available, f.enabled) {
+                    (false, _) => "unavailable",
+                    (true, true) => "on",
+                    (true, false) => "off",
+                };
+                let fixed = if f.toggleable { "" } else { " (fixed)" };
+                println!("{:<24} {:<12} {}{}", f.name, state, f.description, fixed);
+            }
+            0
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            3
+        }
+    }
+}
+
+/// Print detailed help for `features`.
+pub fn print_help() {
+    eprintln!(
+        "GG-CORE features - Show or toggle runtime feature flags
+
+USAGE:
+    GG-CORE features <SUBCOMMAND>
+
+SUBCOMMANDS:
+    list             Show every flag, its state and whether it can be toggled
+    enable <NAME>    Turn a flag on in the running server
+    disable <NAME>   Turn a flag off in the running server
+
+Toggling requires an admin session when authentication is enabled and is
+recorded in the audit log. Changes last until restart. Flags for
+components that are not configured are unavailable, and require_auth can
+only be changed by restarting with a different configuration.
+
+EXAMPLES:
+    GG-CORE features list
+    GG-CORE features disable response_cache
+    GG-CORE features enable conversation_prefetch
+"
+    );
+}
@@ -21,8 +21,26 @@ pub async fn run_health(socket_path: &str) -> i32 {
 }
 
 /// Run liveness probe - exits 0 if alive, 1 if dead.
+///
+/// Uses the probe fast path, so a runtime saturated with requests or
+/// connections still reports alive.
 pub async fn run_liveness(socket_path: &str) -> i32 {
-    run_check(socket_path, HealthCheckType::Liveness, "liveness").await
+    let client = CliIpcClient::new(socket_path.to_string());
+
+    match client.probe().await {
+        Ok(probe) if probe.alive => {
+            eprintln!("liveness check: OK");
+            EXIT_HEALTHY
+        }
+        Ok(_) => {
+            eprintln!("liveness check: FAILED");
+            EXIT_UNHEALTHY
+        }
+        Err(e) => {
+            eprintln!("liveness check error: {}", e);
+            EXIT_UNHEALTHY
+        }
+    }
 }
 
 /// Run readiness probe - exits 0 if ready, 1 if not ready.
@@ -78,6 +96,97 @@ pub async fn run_health_verbose(socket_path: &str) -> i32 {
     }
 }
 
+/// Print detailed help for `health`.
+pub fn print_health_help() {
+    eprintln!(
+        "GG-CORE health - Full health check
+
+USAGE:
+    GG-CORE health [OPTIONS]
+
+OPTIONS:
+    --socket PATH  Override IPC socket path
+    --timeout SEC  Connection timeout in seconds (default: 5)
+    --json         Output in JSON format
+
+DESCRIPTION:
+    Performs a comprehensive health check of the GG-CORE runtime.
+    Checks model loading, memory status, and inference capability.
+
+EXIT CODES:
+    0  System is healthy
+    1  System is unhealthy
+    3  Connection error
+
+EXAMPLES:
+    GG-CORE health
+    GG-CORE health --json
+    GG-CORE health --timeout 10
+"
+    );
+}
+
+/// Print detailed help for `live`.
+pub fn print_liveness_help() {
+    eprintln!(
+        "GG-CORE live - Liveness probe
+
+USAGE:
+    GG-CORE live [OPTIONS]
+
+OPTIONS:
+    --socket PATH  Override IPC socket path
+
+DESCRIPTION:
+    Kubernetes liveness probe. Returns success (0) if the process is alive
+    and responsive to IPC requests. Use for kubelet livenessProbe.
+
+EXIT CODES:
+    0  Process is alive
+    1  Process is unresponsive
+    3  Connection error
+
+KUBERNETES USAGE:
+    livenessProbe:
+      exec:
+        command: [GG-CORE, live]
+      initialDelaySeconds: 30
+      periodSeconds: 10
+"
+    );
+}
+
+/// Print detailed help for `ready`.
+pub fn print_readiness_help() {
+    eprintln!(
+        "GG-CORE ready - Readiness probe
+
+USAGE:
+    GG-CORE ready [OPTIONS]
+
+OPTIONS:
+    --socket PATH  Override IPC socket path
+
+DESCRIPTION:
+    Kubernetes readiness probe. Returns success (0) if the runtime is
+    ready to accept inference requests (models loaded, warmed up).
+    Use for kubelet readinessProbe.
+
+EXIT CODES:
+    0  Ready to serve traffic
+    1  Not ready (still loading models, warming up)
+    3  Connection error
+
+KUBERNETES USAGE:
+    readinessProbe:
+      exec:
+        command: [GG-CORE, ready]
+      initialDelaySeconds: 60
+      periodSeconds: 5
+"
+    );
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,64 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! `heap`: top heap allocation sites of the running server.
+
+use super::ipc_client::CliIpcClient;
+use super::get_socket_path;
+
+pub async fn run_heap(args: &[String]) -> i32 {
+    let top = match args.iter().position(|a| a == "--top").and_then(|i| args.get(i + 1)) {
+        None => 20,
+        Some(n) => match n.parse::<usize>() {
+            Ok(n) if n > 0 => n,
+            _ => {
+                eprintln!("Error: --top must be a positive number");
+                return 2;
+            }
+        },
+    };
+
+    let client = CliIpcClient::new(get_socket_path());
+    let report = match client.heap_profile(top).await {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return 3;
+        }
+    };
+    if args.iter().any(|a| a == "--json") {
+        println!("{}", serde_json::to_string_pretty(&report).unwrap_or_default());
+        return 0;
+    }
+    println!("Live tracked heap: {} bytes", report.live_bytes);
+    println!("{:<24} {:>14} {:>10} {:>16}", "TAG", "LIVE BYTES", "LIVE", "TOTAL BYTES");
+    for site in &report.sites {
+        println!(
+            "{:<24} {:>14} {:>10} {:>16}",
+            site.tag, site.live_bytes, site.live_allocations, site.total_bytes
+        );
+    }
+    0
+}
+
+/// Print detailed help for `heap`.
+pub fn print_help() {
+    eprintln!(
+        "GG-CORE heap - Show top heap allocation sites of the running server
+
+USAGE:
+    GG-CORE heap [OPTIONS]
+
+OPTIONS:
+    --top N    Number of sites to show (default: 20)
+    --json     Print the raw report as JSON
+
+Allocations are grouped by tag (ipc_codec, model_load, kv_cache, ...).
+The server must be built with the 'heap-profiling' feature. Requires an
+admin session when authentication is enabled.
+
+With CORE_HEAP_SOFT_LIMIT set, the server also writes this report to
+<base path>/heap-reports/ whenever tracked usage crosses the limit.
+"
+    );
+}
@@ -0,0 +1,125 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! `help`: general usage and per-command help.
+
+use super::{
+    about, backup, bench, config, conversations, doctor, features, health, heap, infer, legal_hold, migrate, models,
+    placement, profile, serve, snapshot, status, templates, usage, verify,
+};
+
+/// Print general usage: commands, options, environment and exit codes.
+pub fn print_usage() {
+    let version = env!("CARGO_PKG_VERSION");
+    eprintln!(
+        "GG-CORE - Secure Performance-Accelerated Runtime Kernel v{}
+
+USAGE:
+    GG-CORE [COMMAND] [OPTIONS]
+
+COMMANDS:
+    serve        Run the IPC server (default when no command given)
+    infer        Run inference on a model (supports streaming)
+    health       Full health check (exit 0 if healthy, 1 if unhealthy)
+    live         Liveness probe for Kubernetes (exit 0 if alive)
+    ready        Readiness probe for Kubernetes (exit 0 if ready)
+    status       Show system status and statistics
+    verify       Verify deployment health and configuration
+    doctor       Diagnose the installation: build, permissions, keys, socket,
+                 config, clock and sinks, with fixes (--json for a report)
+    models       Manage models (list, load, unload, quantize)
+    snapshot     Create or restore a runtime state snapshot
+    profile      Capture a CPU profile of the running server (pprof)
+    heap         Show top heap allocation sites of the running server
+    usage        Export per-tenant usage aggregates (optionally DP-noised)
+    legal-hold   Place, release or list tenant legal holds; export sealed records
+    migrate      Upgrade on-disk formats (--check to list pending steps)
+    backup       Export or restore salt, catalog and config (create, restore, verify)
+    bench        Run the regression benchmarks in-binary (--self)
+    placement    Show which replica serves a model
+    templates    Manage server-side prompt templates (list, add, show)
+    conversations  Manage server-held conversations (list, evict)
+    features     Show or toggle runtime feature flags (list, enable, disable)
+    config       Manage configuration (validate, show)
+    version      Show version information
+    about        Show build and platform information (--cpu: SIMD kernel dispatch,
+                 --provenance: build features and FIPS allow-list)
+    help         Show this help message
+
+OPTIONS:
+    -h, --help     Show help for command
+    -V, --version  Show version information
+    -v, --verbose  Enable verbose output
+    --socket PATH  Override IPC socket path
+
+EXAMPLES:
+    GG-CORE                          # Run IPC server (default)
+    GG-CORE serve                    # Explicitly run IPC server
+    GG-CORE infer --model phi-3 --prompt \"Hello\"  # Run inference
+    GG-CORE infer --model phi-3 --prompt \"Hi\" --stream  # Streaming
+    GG-CORE health                   # Full health check
+    GG-CORE live                     # Liveness probe
+    GG-CORE ready                    # Readiness probe
+    GG-CORE status                   # Show system status
+    GG-CORE doctor                   # Diagnose the installation
+    GG-CORE models list              # List loaded models
+    GG-CORE snapshot create nightly  # Snapshot catalog and warmup state
+    GG-CORE config validate          # Validate configuration
+    GG-CORE --socket /custom/path    # Use custom socket path
+
+ENVIRONMENT:",
+        version
+    );
+    super::config::print_environment();
+    eprintln!(
+        "
+EXIT CODES:
+    0  Success / Healthy
+    1  Failure / Unhealthy
+    2  Configuration error
+    3  Connection error
+
+DOCUMENTATION:
+    https://docs.GG-CORE.io
+
+SUPPORT:
+    GitHub Issues: https://github.com/GG-CORE/core/issues
+    Community:     https://slack.GG-CORE.io
+"
+    );
+}
+
+/// Print detailed help for a specific command.
+pub fn print_command_help(command: &str) {
+    match command {
+        "serve" => serve::print_help(),
+        "health" => health::print_health_help(),
+        "live" | "liveness" => health::print_liveness_help(),
+        "ready" | "readiness" => health::print_readiness_help(),
+        "status" => status::print_help(),
+        "infer" => infer::print_help(),
+        "verify" => verify::print_help(),
+        "doctor" => doctor::print_help(),
+        "models" => models::print_help(),
+        "conversations" => conversations::print_help(),
+        "features" => features::print_help(),
+        "templates" => templates::print_help(),
+        "placement" => placement::print_help(),
+        "profile" => profile::print_help(),
+        "migrate" => migrate::print_help(),
+        "bench" => bench::print_help(),
+        "backup" => backup::print_help(),
+        "heap" => heap::print_help(),
+        "usage" => usage::print_help(),
+        "legal-hold" => legal_hold::print_help(),
+        "snapshot" => snapshot::print_help(),
+        "about" => about::print_help(),
+        "config" => config::print_help(),
+        _ => {
+            eprintln!(
+                "No detailed help available for '{}'. Use 'GG-CORE help' for general usage.",
+                command
+            );
+        }
+    }
+}
@@ -0,0 +1,170 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! `infer`: run inference against the running server.
+
+use crate::engine::InferenceParams;
+use crate::telemetry::StreamTimings;
+
+use super::ipc_client::CliIpcClient;
+use super::get_socket_path;
+
+/// Run the inference CLI command.
+pub async fn run_inference(args: &[String]) -> i32 {
+    let mut model_id = String::new();
+    let mut prompt = String::new();
+    let mut max_tokens = 256usize;
+    let mut preset = None;
+    let mut stream = false;
+    let mut timings = false;
+
+    // Parse arguments
+    let mut i = 2;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--model" => {
+                if i + 1 < args.len() {
+                    model_id = args[i + 1].clone();
+                    i += 2;
+                } else {
+                    eprintln!("Missing value for --model");
+                    return 1;
+                }
+            }
+            "--prompt" => {
+                if i + 1 < args.len() {
+                    prompt = args[i + 1].clone();
+                    i += 2;
+                } else {
+                    eprintln!("Missing value for --prompt");
+                    return 1;
+                }
+            }
+            "--max-tokens" => {
+                if i + 1 < args.len() {
+                    max_tokens = args[i + 1].parse().unwrap_or(256);
+                    i += 2;
+                } else {
+                    eprintln!("Missing value for --max-tokens");
+                    return 1;
+                }
+            }
+            "--preset" => {
+                if i + 1 < args.len() {
+                    preset = Some(args[i + 1].clone());
+                    i += 2;
+                } else {
+                    eprintln!("Missing value for --preset");
+                    return 1;
+                }
+            }
+            "--stream" => {
+                stream = true;
+                i += 1;
+            }
+            "--timings" => {
+                timings = true;
+                i += 1;
+            }
+            _ => {
+                eprintln!("Unknown argument: {}", args[i]);
+                return 1;
+            }
+        }
+    }
+
+    if model_id.is_empty() || prompt.is_empty() {
+        eprintln!(
+            "Usage: GG-CORE infer --model <MODEL> --prompt <PROMPT> [--max-tokens N] [--preset NAME] \
+             [--stream [--timings]]"
+        );
+        return 1;
+    }
+
+    let socket_path = get_socket_path();
+    let client = CliIpcClient::new(socket_path);
+    let params = InferenceParams {
+        max_tokens,
+        ..Default::default()
+    };
+
+    let result = if stream {
+        client
+            .send_streaming_inference_timed(&model_id, &prompt, &params, preset.as_deref())
+            .await
+            .map(|(output, stream_timings)| {
+                if timings {
+                    print_stream_timings(&stream_timings);
+                }
+                output
+            })
+    } else {
+        client.send_inference(&model_id, &prompt, &params, preset.as_deref()).await
+    };
+
+    match result {
+        Ok(output) => {
+            if !stream {
+                println!("{}", output);
+            }
+            0
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            1
+        }
+    }
+}
+
+/// Client-perceived streaming latency for `infer --stream --timings`.
+fn print_stream_timings(timings: &StreamTimings) {
+    match timings.ttft_ms {
+        Some(ttft) => eprintln!(
+            "ttft {:.1}ms, inter-token avg {:.1}ms max {:.1}ms over {} gaps",
+            ttft,
+            timings.avg_inter_token_ms(),
+            timings.inter_token_max_ms,
+            timings.inter_token_count,
+        ),
+        None => eprintln!("no tokens received"),
+    }
+}
+
+/// Print detailed help for `infer`.
+pub fn print_help() {
+    eprintln!(
+        "GG-CORE infer - Run inference
+
+USAGE:
+    GG-CORE infer --model <MODEL> --prompt <PROMPT> [OPTIONS]
+
+OPTIONS:
+    --model <MODEL>      Model ID to use for inference
+    --prompt <PROMPT>    Input prompt for generation
+    --max-tokens <N>     Maximum tokens to generate (default: 256)
+    --preset <NAME>      Server-side parameter preset (deterministic, creative,
+                         json-strict, or one defined in presets.json);
+                         --max-tokens overrides it
+    --stream             Enable token-by-token streaming output
+    --timings            With --stream, report time to first token and
+                         inter-token latency on stderr
+    --socket PATH        Override IPC socket path
+
+DESCRIPTION:
+    Sends an inference request to the running GG-CORE server
+    and prints the generated output. Use --stream for real-time
+    token streaming.
+
+EXIT CODES:
+    0  Inference completed successfully
+    1  Inference failed or connection error
+
+EXAMPLES:
+    GG-CORE infer --model phi-3 --prompt \"Hello, world!\"
+    GG-CORE infer --model phi-3 --prompt \"Count to 5\" --stream
+    GG-CORE infer --model phi-3 --prompt \"Count to 5\" --stream --timings
+    GG-CORE infer --model qwen --prompt \"Hi\" --max-tokens 100
+    GG-CORE infer --model qwen --prompt \"List 3 colors as JSON\" --preset json-strict
+"
+    );
+}
@@ -0,0 +1,116 @@
+//! Admin and operator requests: snapshots, profiles, usage, holds, conversations and features.
+
+use crate::conversations::ConversationSummary;
+use crate::features::FeatureState;
+use crate::ipc::protocol::{
+    decode_message, encode_message, IpcMessage, LegalHoldAction, SnapshotAction, SnapshotRequest, SnapshotResponse,
+};
+use crate::security::LegalHold;
+use crate::telemetry::{HeapReport, UsageExport};
+
+use super::{CliError, CliIpcClient};
+
+impl CliIpcClient {
+    /// Create or restore a named runtime snapshot via IPC.
+    pub async fn snapshot(
+        &self,
+        action: SnapshotAction,
+        name: &str,
+    ) -> Result<SnapshotResponse, CliError> {
+        let message = IpcMessage::SnapshotRequest(SnapshotRequest {
+            action,
+            name: name.to_string(),
+        });
+        let request_bytes =
+            encode_message(&message).map_err(|e| CliError::Protocol(e.to_string()))?;
+
+        let response_bytes = self.send_receive(&request_bytes).await?;
+        let response =
+            decode_message(&response_bytes).map_err(|e| CliError::Protocol(e.to_string()))?;
+
+        match response {
+            IpcMessage::SnapshotResponse(resp) => Ok(resp),
+            IpcMessage::Error { message, .. } => Err(CliError::Protocol(message)),
+            _ => Err(CliError::Protocol("Unexpected response type".to_string())),
+        }
+    }
+
+    /// Capture a CPU profile of the server (admin); returns gzipped pprof bytes.
+    ///
+    /// The call blocks for `seconds`, so the client timeout must exceed it.
+    pub async fn capture_profile(&self, seconds: u32) -> Result<Vec<u8>, CliError> {
+        match self.request(&IpcMessage::ProfileRequest { seconds }).await? {
+            IpcMessage::ProfileResponse { profile } => crate::security::image_input::decode_base64(&profile)
+                .map_err(|e| CliError::Protocol(format!("invalid profile encoding: {}", e))),
+            IpcMessage::Error { message, .. } => Err(CliError::Protocol(message)),
+            _ => Err(CliError::Protocol("Unexpected response type".to_string())),
+        }
+    }
+
+    /// Top heap allocation sites of the server (admin).
+    pub async fn heap_profile(&self, top: usize) -> Result<HeapReport, CliError> {
+        match self.request(&IpcMessage::HeapProfileRequest { top }).await? {
+            IpcMessage::HeapProfileResponse(report) => Ok(report),
+            IpcMessage::Error { message, .. } => Err(CliError::Protocol(message)),
+            _ => Err(CliError::Protocol("Unexpected response type".to_string())),
+        }
+    }
+
+    /// Per-tenant usage aggregates for external export (admin).
+    pub async fn usage_export(&self) -> Result<UsageExport, CliError> {
+        match self.request(&IpcMessage::UsageExportRequest).await? {
+            IpcMessage::UsageExportResponse(export) => Ok(export),
+            IpcMessage::Error { message, .. } => Err(CliError::Protocol(message)),
+            _ => Err(CliError::Protocol("Unexpected response type".to_string())),
+        }
+    }
+
+    /// Place, release or list legal holds (admin); returns all holds.
+    pub async fn legal_hold(&self, action: LegalHoldAction) -> Result<Vec<LegalHold>, CliError> {
+        match self.request(&IpcMessage::LegalHoldRequest { action }).await? {
+            IpcMessage::LegalHoldResponse { holds } => Ok(holds),
+            IpcMessage::Error { message, .. } => Err(CliError::Protocol(message)),
+            _ => Err(CliError::Protocol("Unexpected response type".to_string())),
+        }
+    }
+
+    /// List server-held conversations (admin).
+    pub async fn list_conversations(&self) -> Result<Vec<ConversationSummary>, CliError> {
+        match self.request(&IpcMessage::ConversationList).await? {
+            IpcMessage::ConversationListResponse { conversations } => Ok(conversations),
+            IpcMessage::Error { message, .. } => Err(CliError::Protocol(message)),
+            _ => Err(CliError::Protocol("Unexpected response type".to_string())),
+        }
+    }
+
+    /// Runtime feature flags and their state.
+    pub async fn features(&self) -> Result<Vec<FeatureState>, CliError> {
+        self.features_exchange(&IpcMessage::FeaturesRequest).await
+    }
+
+    /// Flip a runtime feature flag (admin). Returns every flag afterwards.
+    pub async fn set_feature(&self, name: &str, enabled: bool) -> Result<Vec<FeatureState>, CliError> {
+        let message = IpcMessage::FeatureToggle { name: name.to_string(), enabled };
+        self.features_exchange(&message).await
+    }
+
+    async fn features_exchange(&self, message: &IpcMessage) -> Result<Vec<FeatureState>, CliError> {
+        match self.request(message).await? {
+            IpcMessage::FeaturesResponse { features } => Ok(features),
+            IpcMessage::Error { message, .. } => Err(CliError::Protocol(message)),
+            _ => Err(CliError::Protocol("Unexpected response type".to_string())),
+        }
+    }
+
+    /// Evict a server-held conversation (admin). Returns whether it existed.
+    pub async fn evict_conversation(&self, conversation_id: &str) -> Result<bool, CliError> {
+        let message = IpcMessage::ConversationEvict {
+            conversation_id: conversation_id.to_string(),
+        };
+        match self.request(&message).await? {
+            IpcMessage::ConversationEvicted { evicted, .. } => Ok(evicted),
+            IpcMessage::Error { message, .. } => Err(CliError::Protocol(message)),
+            _ => Err(CliError::Protocol("Unexpected response type".to_string())),
+        }
+    }
+}
@@ -0,0 +1,87 @@
+//! Health, metrics, model listing and liveness queries.
+
+use crate::ipc::probe::ProbeResponse;
+use crate::ipc::protocol::{
+    decode_message, encode_message, HealthCheckResponse, HealthCheckType, IpcMessage, ModelsListResponse,
+};
+use crate::telemetry::MetricsSnapshot;
+
+use super::{CliError, CliIpcClient};
+
+impl CliIpcClient {
+    /// Perform a health check via IPC.
+    pub async fn check_health(&self, check_type: HealthCheckType) -> Result<bool, CliError> {
+        let response = self.send_health_request(check_type).await?;
+        Ok(response.ok)
+    }
+
+    /// Get full health report via IPC.
+    pub async fn get_health_report(&self) -> Result<HealthCheckResponse, CliError> {
+        self.send_health_request(HealthCheckType::Full).await
+    }
+
+    /// Get metrics snapshot via IPC.
+    pub async fn get_metrics(&self) -> Result<MetricsSnapshot, CliError> {
+        let message = IpcMessage::MetricsRequest;
+        let request_bytes =
+            encode_message(&message).map_err(|e| CliError::Protocol(e.to_string()))?;
+
+        let response_bytes = self.send_receive(&request_bytes).await?;
+
+        let response =
+            decode_message(&response_bytes).map_err(|e| CliError::Protocol(e.to_string()))?;
+
+        match response {
+            IpcMessage::MetricsResponse(snapshot) => Ok(snapshot),
+            IpcMessage::Error { message, .. } => Err(CliError::Protocol(message)),
+            _ => Err(CliError::Protocol("Unexpected response type".to_string())),
+        }
+    }
+
+    /// Get loaded models list via IPC.
+    pub async fn get_models(&self) -> Result<ModelsListResponse, CliError> {
+        let message = IpcMessage::ModelsRequest;
+        let request_bytes =
+            encode_message(&message).map_err(|e| CliError::Protocol(e.to_string()))?;
+
+        let response_bytes = self.send_receive(&request_bytes).await?;
+
+        let response =
+            decode_message(&response_bytes).map_err(|e| CliError::Protocol(e.to_string()))?;
+
+        match response {
+            IpcMessage::ModelsResponse(models) => Ok(models),
+            IpcMessage::Error { message, .. } => Err(CliError::Protocol(message)),
+            _ => Err(CliError::Protocol("Unexpected response type".to_string())),
+        }
+    }
+
+    /// Liveness probe; answered ahead of auth and the request queue.
+    pub async fn probe(&self) -> Result<ProbeResponse, CliError> {
+        match self.request(&IpcMessage::Probe).await? {
+            IpcMessage::ProbeResponse(response) => Ok(response),
+            IpcMessage::Error { message, .. } => Err(CliError::Protocol(message)),
+            _ => Err(CliError::Protocol("Unexpected response type".to_string())),
+        }
+    }
+
+    async fn send_health_request(
+        &self,
+        check_type: HealthCheckType,
+    ) -> Result<HealthCheckResponse, CliError> {
+        let message = IpcMessage::HealthCheck { check_type };
+        let request_bytes =
+            encode_message(&message).map_err(|e| CliError::Protocol(e.to_string()))?;
+
+        let response_bytes = self.send_receive(&request_bytes).await?;
+
+        let response =
+            decode_message(&response_bytes).map_err(|e| CliError::Protocol(e.to_string()))?;
+
+        match response {
+            IpcMessage::HealthResponse(health_response) => Ok(health_response),
+            IpcMessage::Error { message, .. } => Err(CliError::Protocol(message)),
+            _ => Err(CliError::Protocol("Unexpected response type".to_string())),
+        }
+    }
+}
@@ -0,0 +1,188 @@
+//! Unary and streaming inference requests.
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::time::timeout;
+
+use crate::engine::InferenceParams;
+use crate::ipc::protocol::{decode_message, encode_message, InferenceRequest, IpcMessage, RequestId};
+use crate::telemetry::{StreamTimer, StreamTimings};
+
+use super::{CliError, CliIpcClient};
+
+impl CliIpcClient {
+    /// Send inference request and return response text. `preset` names a
+    /// server-side parameter preset that `params` override.
+    pub async fn send_inference(
+        &self,
+        model_id: &str,
+        prompt: &str,
+        params: &InferenceParams,
+        preset: Option<&str>,
+    ) -> Result<String, CliError> {
+        let request = InferenceRequest {
+            request_id: RequestId::generate(),
+            model_id: model_id.to_string(),
+            prompt: prompt.to_string(),
+            parameters: params.clone(),
+            preset: preset.map(str::to_string),
+            priority: Default::default(),
+            session_affinity_key: None,
+            tools: Vec::new(),
+            images: Vec::new(),
+            template_id: None,
+            variables: Default::default(),
+            context_documents: Vec::new(),
+            cite_sources: false,
+            privacy: Default::default(),
+            idempotency_key: None,
+        };
+        let message = IpcMessage::InferenceRequest(request);
+        let request_bytes =
+            encode_message(&message).map_err(|e| CliError::Protocol(e.to_string()))?;
+
+        let response_bytes = self.send_receive(&request_bytes).await?;
+        let response =
+            decode_message(&response_bytes).map_err(|e| CliError::Protocol(e.to_string()))?;
+
+        match response {
+            IpcMessage::InferenceResponse(resp) => Ok(resp.output),
+            IpcMessage::Error { message, .. } => Err(CliError::Protocol(message)),
+            _ => Err(CliError::Protocol("Unexpected response type".to_string())),
+        }
+    }
+
+    /// Send streaming inference request, printing tokens as they arrive.
+    pub async fn send_streaming_inference(
+        &self,
+        model_id: &str,
+        prompt: &str,
+        params: &InferenceParams,
+        preset: Option<&str>,
+    ) -> Result<String, CliError> {
+        let (output, _) = self.send_streaming_inference_timed(model_id, prompt, params, preset).await?;
+        Ok(output)
+    }
+
+    /// Stream an inference and measure client-perceived time to first
+    /// token and inter-token gaps.
+    pub async fn send_streaming_inference_timed(
+        &self,
+        model_id: &str,
+        prompt: &str,
+        params: &InferenceParams,
+        preset: Option<&str>,
+    ) -> Result<(String, StreamTimings), CliError> {
+        let mut params = params.clone();
+        params.stream = true;
+
+        let request = InferenceRequest {
+            request_id: RequestId::generate(),
+            model_id: model_id.to_string(),
+            prompt: prompt.to_string(),
+            parameters: params,
+            preset: preset.map(str::to_string),
+            priority: Default::default(),
+            session_affinity_key: None,
+            tools: Vec::new(),
+            images: Vec::new(),
+            template_id: None,
+            variables: Default::default(),
+            context_documents: Vec::new(),
+            cite_sources: false,
+            privacy: Default::default(),
+            idempotency_key: None,
+        };
+        let message = IpcMessage::InferenceRequest(request);
+        let request_bytes =
+            encode_message(&message).map_err(|e| CliError::Protocol(e.to_string()))?;
+
+        self.receive_streaming_response(&request_bytes).await
+    }
+
+    #[cfg(unix)]
+    async fn receive_streaming_response(
+        &self,
+        request: &[u8],
+    ) -> Result<(String, StreamTimings), CliError> {
+        let connect_future = crate::ipc::listener::connect(&self.socket_path);
+        let mut stream = timeout(self.timeout_duration, connect_future)
+            .await
+            .map_err(|_| CliError::Timeout)?
+            .map_err(|e| CliError::ConnectionFailed(e.to_string()))?;
+
+        self.stream_exchange(&mut stream, request).await
+    }
+
+    #[cfg(windows)]
+    async fn receive_streaming_response(
+        &self,
+        request: &[u8],
+    ) -> Result<(String, StreamTimings), CliError> {
+        use tokio::net::windows::named_pipe::ClientOptions;
+
+        let connect_future = ClientOptions::new().open(&self.socket_path);
+        let mut pipe = timeout(self.timeout_duration, async { connect_future })
+            .await
+            .map_err(|_| CliError::Timeout)?
+            .map_err(|e| CliError::ConnectionFailed(e.to_string()))?;
+
+        self.stream_exchange(&mut pipe, request).await
+    }
+
+    async fn stream_exchange<S>(
+        &self,
+        stream: &mut S,
+        request: &[u8],
+    ) -> Result<(String, StreamTimings), CliError>
+    where
+        S: AsyncReadExt + AsyncWriteExt + Unpin,
+    {
+        // Send length-prefixed request
+        let len = request.len() as u32;
+        stream.write_all(&len.to_le_bytes()).await?;
+        stream.write_all(request).await?;
+        stream.flush().await?;
+
+        let mut full_output = String::new();
+        let mut timer = StreamTimer::start();
+
+        // Read streaming chunks until final
+        loop {
+            let mut len_buf = [0u8; 4];
+            stream.read_exact(&mut len_buf).await?;
+            let response_len = u32::from_le_bytes(len_buf) as usize;
+
+            if response_len > 16 * 1024 * 1024 {
+                return Err(CliError::Protocol("Response too large".to_string()));
+            }
+
+            let mut response = vec![0u8; response_len];
+            stream.read_exact(&mut response).await?;
+
+            let message =
+                decode_message(&response).map_err(|e| CliError::Protocol(e.to_string()))?;
+
+            match message {
+                IpcMessage::StreamChunk(chunk) => {
+                    if let Some(text) = &chunk.text {
+                        timer.token();
+                        print!("{}", text);
+                        full_output.push_str(text);
+                    }
+                    if chunk.is_final {
+                        println!();
+                        break;
+                    }
+                }
+                IpcMessage::Error { message, .. } => {
+                    return Err(CliError::Protocol(message));
+                }
+                _ => {
+                    return Err(CliError::Protocol("Unexpected response type".to_string()));
+                }
+            }
+        }
+
+        Ok((full_output, timer.timings()))
+    }
+}
@@ -0,0 +1,117 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! `legal-hold`: place, release or list holds; export sealed records.
+
+use std::path::PathBuf;
+
+use crate::ipc::LegalHoldAction;
+use crate::security::legal_hold;
+
+use super::config::load_config;
+use super::ipc_client::CliIpcClient;
+use super::get_socket_path;
+
+/// Place, release or list legal holds over IPC, or export sealed records
+/// locally with the sealing key.
+pub async fn run_legal_hold(args: &[String]) -> i32 {
+    let value = |name: &str| args.iter().position(|a| a == name).and_then(|i| args.get(i + 1));
+    let tenant = args.get(3).filter(|t| !t.starts_with("--"));
+    let action = match (args.get(2).map(|s| s.as_str()), tenant) {
+        (Some("list") | None, _) => LegalHoldAction::List,
+        (Some("activate"), Some(tenant)) => {
+            let Some(reason) = value("--reason") else {
+                eprintln!("Missing --reason TEXT");
+                return 2;
+            };
+            let days = match value("--days").map(|d| d.parse::<u32>()) {
+                None => None,
+                Some(Ok(days)) => Some(days),
+                Some(Err(_)) => {
+                    eprintln!("Error: --days must be a whole number");
+                    return 2;
+                }
+            };
+            LegalHoldAction::Activate { tenant: tenant.clone(), days, reason: reason.clone() }
+        }
+        (Some("release"), Some(tenant)) => LegalHoldAction::Release { tenant: tenant.clone() },
+        (Some("export"), Some(tenant)) => return run_legal_hold_export(tenant, value("--key"), value("--out")),
+        _ => {
+            print_help();
+            return 2;
+        }
+    };
+
+    let client = CliIpcClient::new(get_socket_path());
+    match client.legal_hold(action).await {
+        Ok(holds) => {
+            let now = chrono::Utc::now();
+            println!("{:<20} {:<7} {:<17} {:>8}  REASON", "TENANT", "STATE", "EXPIRES", "RECORDS");
+            for hold in holds {
+                let state = if hold.is_active(now) { "active" } else { "ended" };
+                let expires = hold.expires_at.format("%Y-%m-%d %H:%M");
+                println!("{:<20} {:<7} {:<17} {:>8}  {}", hold.tenant, state, expires, hold.records, hold.reason);
+            }
+            0
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            3
+        }
+    }
+}
+
+fn run_legal_hold_export(tenant: &str, key: Option<&String>, out: Option<&String>) -> i32 {
+    let config = load_config();
+    let store_dir = config.legal_hold.store_dir.unwrap_or_else(|| legal_hold::default_store_dir(&config.base_path));
+    let key_path = key
+        .map(PathBuf::from)
+        .or(config.legal_hold.key_path)
+        .unwrap_or_else(|| legal_hold::default_key_path(&config.base_path));
+    let records = match legal_hold::export(&store_dir, &key_path, tenant) {
+        Ok(records) => records,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return 1;
+        }
+    };
+    let json = serde_json::to_string_pretty(&records).unwrap_or_default();
+    match out {
+        Some(path) => {
+            if let Err(e) = std::fs::write(path, json) {
+                eprintln!("Error: {}: {}", path, e);
+                return 1;
+            }
+        }
+        None => println!("{}", json),
+    }
+    eprintln!("Exported {} sealed record(s) for {}", records.len(), tenant);
+    0
+}
+
+/// Print detailed help for `legal-hold`.
+pub fn print_help() {
+    eprintln!(
+        "GG-CORE legal-hold - Capture a tenant's inference in full under legal hold
+
+USAGE:
+    GG-CORE legal-hold list
+    GG-CORE legal-hold activate <TENANT> --reason TEXT [--days N]
+    GG-CORE legal-hold release <TENANT>
+    GG-CORE legal-hold export <TENANT> [--key PATH] [--out FILE]
+
+Tenants are named by the labels in usage exports. While a hold is active
+every request of the tenant is sealed (AES-256-GCM) into
+<base path>/legal-hold/ instead of being audited by hash only; the hold
+overrides the client's no_log flag. Holds expire after --days (default
+and maximum CORE_LEGAL_HOLD_MAX_DAYS) and their records are disposed of
+CORE_LEGAL_HOLD_RETAIN_DAYS after the hold ends. Activation, release and
+expiry are Critical audit events. list, activate and release require an
+admin session when authentication is enabled.
+
+export reads the store directly and needs the sealing key file
+(CORE_LEGAL_HOLD_KEY or --key); the server never returns sealed content.
+Each export is appended to the store's access.log.
+"
+    );
+}
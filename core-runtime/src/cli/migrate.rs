@@ -0,0 +1,111 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! `migrate`: upgrade on-disk formats, also applied at server startup.
+
+use crate::migrations::{MigrationOptions, Migrator};
+
+use super::config::load_config;
+
+/// Apply pending on-disk migrations, or with `CORE_MIGRATIONS=check`
+/// refuse to start while any are pending.
+pub fn run_startup_migrations(base_path: &std::path::Path) -> Result<(), String> {
+    let migrator = Migrator::builtin(base_path);
+    if std::env::var("CORE_MIGRATIONS").as_deref() == Ok("check") {
+        let pending = migrator.pending().map_err(|e| e.to_string())?;
+        if let Some(step) = pending.first() {
+            return Err(format!(
+                "{} on-disk migration(s) pending, starting with {} ({}); run `GG-CORE migrate`",
+                pending.len(),
+                step.version,
+                step.id
+            ));
+        }
+        return Ok(());
+    }
+    let report = migrator.run(MigrationOptions::default()).map_err(|e| e.to_string())?;
+    for step in &report.steps {
+        eprintln!("Migrated on-disk format to {} ({})", step.version, step.id);
+    }
+    Ok(())
+}
+
+pub fn run_migrate(args: &[String]) -> i32 {
+    let has = |name: &str| args.iter().any(|a| a == name);
+    let migrator = Migrator::builtin(load_config().base_path);
+
+    if has("--check") {
+        return match migrator.pending() {
+            Ok(pending) if pending.is_empty() => {
+                println!("On-disk formats are current (version {})", migrator.latest_version());
+                0
+            }
+            Ok(pending) => {
+                for step in &pending {
+                    println!("pending  {:>3}  {:<20} {}", step.version, step.id, step.description);
+                }
+                1
+            }
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                2
+            }
+        };
+    }
+
+    let options = MigrationOptions { dry_run: has("--dry-run"), backup: !has("--no-backup") };
+    let report = match migrator.run(options) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return 1;
+        }
+    };
+    if has("--json") {
+        println!("{}", serde_json::to_string_pretty(&report).unwrap_or_default());
+        return 0;
+    }
+    if report.steps.is_empty() {
+        println!("Nothing to migrate (version {})", report.from_version);
+        return 0;
+    }
+    let verb = if report.dry_run { "would apply" } else { "applied" };
+    for step in &report.steps {
+        println!("{}  {:>3}  {:<20} {}", verb, step.version, step.id, step.description);
+        for file in &step.files {
+            println!("         {}", file.display());
+        }
+    }
+    for backup in &report.backups {
+        println!("backup   {}", backup.display());
+    }
+    0
+}
+
+/// Print detailed help for `migrate`.
+pub fn print_help() {
+    eprintln!(
+        "GG-CORE migrate - Upgrade on-disk formats
+
+USAGE:
+    GG-CORE migrate [OPTIONS]
+
+OPTIONS:
+    --check        List pending migrations; exit 1 if any are pending
+    --dry-run      Show the steps and files that would change, change nothing
+    --no-backup    Skip copying affected files to migration-backups/
+    --json         Print the report as JSON
+
+Files written by older builds (registry catalog, snapshots, encrypted
+models) are upgraded by versioned, idempotent steps. The server applies
+them at startup unless CORE_MIGRATIONS=check. Affected files are copied
+to <base path>/migration-backups/ before each step, and the applied
+version is recorded in <base path>/format-version.json.
+
+EXAMPLES:
+    GG-CORE migrate --check
+    GG-CORE migrate --dry-run
+    GG-CORE migrate
+"
+    );
+}
@@ -6,6 +6,9 @@
 //! Provides subcommands for health checks via IPC, enabling K8s exec probes
 //! without requiring HTTP endpoints. Maintains the Alcatraz principle.
 //!
+//! Each subcommand lives in its own module with its `help` text; the
+//! binary only dispatches on the command name.
+//!
 //! ## Usage
 //!
 //! ```bash
@@ -13,12 +16,34 @@
 //! GG-CORE live     # Liveness probe, exits 0 if alive
 //! GG-CORE ready    # Readiness probe, exits 0 if ready
 //! GG-CORE status   # Show system status and statistics
+//! GG-CORE doctor   # Diagnose the installation without a running server
 //! ```
 
+pub mod about;
+pub mod backup;
+pub mod bench;
+pub mod config;
+pub mod conversations;
+pub mod doctor;
+pub mod features;
 pub mod health;
+pub mod heap;
+pub mod help;
+pub mod infer;
 pub mod ipc_client;
+pub mod legal_hold;
+pub mod migrate;
+pub mod models;
+pub mod placement;
+pub mod profile;
+pub mod serve;
+pub mod snapshot;
 pub mod status;
+pub mod templates;
+pub mod usage;
+pub mod verify;
 
+pub use doctor::{DoctorInputs, DoctorReport, EnvKind, Sink};
 pub use health::{run_health, run_liveness, run_readiness};
 pub use ipc_client::{CliError, CliIpcClient};
 pub use status::{run_status, SystemStatus};
@@ -30,9 +55,9 @@ pub const DEFAULT_SOCKET_PATH: &str = "/var/run/veritas/GG-CORE.sock";
 #[cfg(windows)]
 pub const DEFAULT_SOCKET_PATH: &str = r"\\.\pipe\GG-CORE";
 
-/// Get socket path from environment or use default.
+/// Get socket path from environment (or its deprecated key) or use default.
 pub fn get_socket_path() -> String {
-    std::env::var("VERITAS_SOCKET_PATH").unwrap_or_else(|_| DEFAULT_SOCKET_PATH.to_string())
+    crate::config_schema::var("VERITAS_SOCKET_PATH").unwrap_or_else(|| DEFAULT_SOCKET_PATH.to_string())
 }
 
 #[cfg(test)]
@@ -0,0 +1,63 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! `models fallback`: edit the fallback chains in the model catalog.
+
+use crate::models::fallback::parse_chain;
+use crate::models::{PersistenceError, RegistryPersistence, RegistryState};
+
+use crate::cli::config::{install_storage, load_config};
+
+/// `models fallback`: edits the catalog file directly; the runtime reads
+/// it at startup.
+pub(super) fn run_models_fallback(args: &[String]) -> i32 {
+    let base_path = load_config().base_path;
+    if let Err(e) = install_storage(&base_path) {
+        eprintln!("Error: state storage: {}", e);
+        return 1;
+    }
+    let persistence = RegistryPersistence::for_base_path(&base_path);
+    let mut state = match persistence.load() {
+        Ok(state) => state,
+        Err(PersistenceError::NotFound) => RegistryState::default(),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return 1;
+        }
+    };
+    let result = match args.get(3).map(|s| s.as_str()).unwrap_or("list") {
+        "list" => {
+            let chains = state.fallbacks.list();
+            if chains.is_empty() {
+                println!("No fallback chains configured");
+            }
+            for (primary, alternates) in chains {
+                println!("{} -> {}", primary, alternates.join(" -> "));
+            }
+            return 0;
+        }
+        "set" => parse_chain(&args[4.min(args.len())..].join(" "))
+            .and_then(|(primary, alternates)| state.fallbacks.set(&primary, alternates)),
+        "clear" => match args.get(4) {
+            Some(primary) => state.fallbacks.set(primary, Vec::new()),
+            None => {
+                eprintln!("Error: clear requires a model name");
+                return 2;
+            }
+        },
+        other => {
+            eprintln!("Unknown fallback subcommand: {}", other);
+            return 2;
+        }
+    };
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        return 2;
+    }
+    if let Err(e) = persistence.save(&state) {
+        eprintln!("Error: {}", e);
+        return 1;
+    }
+    println!("Fallback chains saved; restart the runtime to apply");
+    0
+}
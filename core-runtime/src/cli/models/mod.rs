@@ -0,0 +1,133 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! `models`: list served models and manage model artifacts offline.
+
+mod fallback;
+mod quantize;
+mod recommend;
+mod recovery;
+
+use super::ipc_client::CliIpcClient;
+use super::get_socket_path;
+
+/// Run a `models` subcommand (`list` when none is given).
+pub async fn run_models(args: &[String]) -> i32 {
+    let subcommand = args.get(2).map(|s| s.as_str()).unwrap_or("list");
+    match subcommand {
+        "list" => run_models_list(args).await,
+        "quantize" => quantize::run_models_quantize(args),
+        "recommend" => recommend::run_models_recommend(args),
+        "recovery-keygen" => recovery::run_models_recovery_keygen(args),
+        "recover" => recovery::run_models_recover(args),
+        "fallback" => fallback::run_models_fallback(args),
+        _ => {
+            eprintln!("Unknown models subcommand: {}", subcommand);
+            print_help();
+            1
+        }
+    }
+}
+
+/// `models list`: the running server's models with lifetime request figures.
+async fn run_models_list(args: &[String]) -> i32 {
+    let socket_path = args
+        .iter()
+        .position(|a| a == "--socket")
+        .and_then(|i| args.get(i + 1).cloned())
+        .unwrap_or_else(get_socket_path);
+    let response = match CliIpcClient::new(socket_path).get_models().await {
+        Ok(response) => response,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return 3;
+        }
+    };
+    if args.iter().any(|a| a == "--json") {
+        println!("{}", serde_json::to_string_pretty(&response).unwrap_or_default());
+        return 0;
+    }
+    // Request counts and latencies are lifetime figures, carried across restarts
+    println!(
+        "{:<32} {:<12} {:<10} {:>10} {:>12} {:>12}",
+        "NAME", "FORMAT", "STATE", "REQUESTS", "AVG MS", "RECENT P95"
+    );
+    for m in &response.models {
+        println!(
+            "{:<32} {:<12} {:<10} {:>10} {:>12.1} {:>12.1}",
+            m.name, m.format, m.state, m.request_count, m.avg_latency_ms, m.recent_p95_latency_ms
+        );
+    }
+    0
+}
+
+/// Print detailed help for `models`.
+pub fn print_help() {
+    eprintln!(
+        "GG-CORE models - Manage models
+
+USAGE:
+    GG-CORE models <SUBCOMMAND> [OPTIONS]
+
+SUBCOMMANDS:
+    list           List loaded models
+    load <NAME>    Load a model
+    unload <NAME>  Unload a model
+    info <NAME>    Show model information
+    quantize       Convert a GGUF model to a smaller quantization (offline)
+    recommend      Recommend a quantization from estimated latency on this host (offline)
+    recovery-keygen  Create a recovery key pair for model key escrow
+    recover        Re-encrypt escrowed models for this machine's identity
+    fallback       List, set or clear fallback chains in the registry catalog
+
+OPTIONS:
+    --socket PATH  Override IPC socket path
+    --json         Output in JSON format
+
+QUANTIZE OPTIONS:
+    --in PATH        Source GGUF model (may be an encrypted model)
+    --out PATH       Destination file (must not exist); PATH.sha256 is written alongside
+    --method NAME    f16, q8_0, q6_k, q5_k_m, q4_k_m or q4_0
+    --threads N      Worker threads (default: backend choice)
+    --encrypt        Encrypt the output with this machine's model key; with
+                     CORE_RECOVERY_PUBLIC_KEY set, the key is escrowed to
+                     PATH.escrow.json
+
+RECOVERY OPTIONS:
+    --out PREFIX           (recovery-keygen) Write PREFIX.pub and PREFIX.key
+    --recovery-key FILE    (recover) Recovery private key; followed by model paths
+
+Encrypted models are bound to the hostname and user. Keep the .key file
+offline; after a rename or rebuild, `recover` unseals each model's old key
+from its escrow record, re-encrypts the model under the new identity and
+seals the new key to the same recovery key.
+
+FALLBACK SUBCOMMANDS:
+    fallback list                    Show fallback chains
+    fallback set \"A -> B -> C\"       Try B, then C, when A is not loaded or
+                                     its circuit is open
+    fallback clear <MODEL>           Remove MODEL's chain
+
+Chains are stored in <base_path>/registry_state.json and read at startup.
+Responses name the model that served them (served_model) and the
+core_fallback_ratio_1m/5m metrics track how often fallbacks are used.
+
+RECOMMEND OPTIONS:
+    --path PATH            Source GGUF model
+    --target-latency DUR   Per-token decode latency target (e.g. 50ms)
+    --memory QTY           Memory budget (e.g. 8Gi, 512Mi)
+    --output FILE          Write the JSON report to FILE instead of stdout
+
+EXAMPLES:
+    GG-CORE models list
+    GG-CORE models load llama-2-7b-chat
+    GG-CORE models info llama-2-7b-chat
+    GG-CORE models unload llama-2-7b-chat
+    GG-CORE models quantize --in model-f16.gguf --out model-q4.gguf --method q4_k_m
+    GG-CORE models recommend --path model.gguf --target-latency 50ms --memory 8Gi
+    GG-CORE models fallback set \"prod-13b -> prod-7b -> tiny\"
+    GG-CORE models recovery-keygen --out /secure/gg-recovery
+    GG-CORE models recover --recovery-key /secure/gg-recovery.key models/*.gguf
+"
+    );
+}
@@ -0,0 +1,118 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! `models quantize`: offline conversion with progress on stderr.
+
+use std::path::PathBuf;
+
+use crate::models::{quantize_model, LlamaQuantizer, QuantizeOptions, QuantizeStage};
+use crate::security::escrow::{self, RecoveryPublicKey};
+
+use super::print_help;
+
+/// `models quantize`: offline conversion with progress on stderr.
+pub(super) fn run_models_quantize(args: &[String]) -> i32 {
+    let flag = |name: &str| {
+        args.iter()
+            .position(|a| a == name)
+            .and_then(|i| args.get(i + 1))
+            .map(|s| s.as_str())
+    };
+    let (Some(input), Some(output), Some(method)) = (flag("--in"), flag("--out"), flag("--method")) else {
+        eprintln!("Error: --in, --out and --method are required");
+        print_help();
+        return 2;
+    };
+    let method = match method.parse() {
+        Ok(m) => m,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return 2;
+        }
+    };
+    let threads = match flag("--threads").map(str::parse::<u32>) {
+        None => 0,
+        Some(Ok(n)) => n,
+        Some(Err(_)) => {
+            eprintln!("Error: --threads must be a number");
+            return 2;
+        }
+    };
+    let options = QuantizeOptions {
+        input: PathBuf::from(input),
+        output: PathBuf::from(output),
+        method,
+        threads,
+        encrypt_output: args.iter().any(|a| a == "--encrypt"),
+    };
+
+    // The machine key is only derived when an encrypted artifact is involved
+    let needs_key = options.encrypt_output
+        || crate::models::quantize::is_encrypted(&options.input).unwrap_or(false);
+    let key = if needs_key {
+        match crate::security::ModelEncryption::from_machine_id() {
+            Ok(key) => Some(key),
+            Err(e) => {
+                eprintln!("Error: cannot derive model key: {}", e);
+                return 1;
+            }
+        }
+    } else {
+        None
+    };
+    // Loaded up front so a bad key file fails before any work is done
+    let recovery_key = match std::env::var("CORE_RECOVERY_PUBLIC_KEY") {
+        Ok(path) if options.encrypt_output => match RecoveryPublicKey::load(std::path::Path::new(&path)) {
+            Ok(key) => Some(key),
+            Err(e) => {
+                eprintln!("Error: CORE_RECOVERY_PUBLIC_KEY: {}", e);
+                return 2;
+            }
+        },
+        _ => None,
+    };
+
+    let mut last_percent = None;
+    let mut progress = |stage: QuantizeStage| match stage {
+        QuantizeStage::Hashing { done, total } => {
+            let percent = (done * 100).checked_div(total).unwrap_or(100);
+            if last_percent != Some(percent / 10) {
+                last_percent = Some(percent / 10);
+                eprintln!("  hashing      {:>3}%", percent);
+            }
+        }
+        QuantizeStage::Decrypting => eprintln!("  decrypting input"),
+        QuantizeStage::Validating => eprintln!("  validating header"),
+        QuantizeStage::Quantizing => eprintln!("  quantizing to {}", method),
+        QuantizeStage::Encrypting => eprintln!("  encrypting output"),
+    };
+    match quantize_model(&options, &LlamaQuantizer, key.as_ref(), &mut progress) {
+        Ok(report) => {
+            println!(
+                "OK   {} -> {} ({}, {:.1} MiB -> {:.1} MiB{}) in {:.1}s",
+                input,
+                output,
+                report.method,
+                report.input_bytes as f64 / (1024.0 * 1024.0),
+                report.output_bytes as f64 / (1024.0 * 1024.0),
+                if report.encrypted { ", encrypted" } else { "" },
+                report.elapsed.as_secs_f64()
+            );
+            println!("     sha256 {} ({})", report.sha256, report.checksum_path.display());
+            if let (Some(recovery_key), Some(key)) = (recovery_key, key.as_ref()) {
+                match escrow::write_escrow(&options.output, key, &recovery_key) {
+                    Ok(path) => println!("     escrow {} (recovery key {})", path.display(), recovery_key.key_id()),
+                    Err(e) => {
+                        eprintln!("FAIL escrow: {}", e);
+                        return 1;
+                    }
+                }
+            }
+            0
+        }
+        Err(e) => {
+            eprintln!("FAIL {}", e);
+            1
+        }
+    }
+}
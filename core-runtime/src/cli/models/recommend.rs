@@ -0,0 +1,68 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! `models recommend`: pick a quantization for this host and a latency target.
+
+use std::time::Duration;
+
+use crate::models::recommend::{parse_latency, parse_memory};
+use crate::models::{recommend, CpuCalibration, RecommendTarget};
+
+use super::print_help;
+
+/// `models recommend`: measure this host's bandwidth and print a JSON
+/// report of estimated per-token latencies.
+pub(super) fn run_models_recommend(args: &[String]) -> i32 {
+    let flag = |name: &str| {
+        args.iter()
+            .position(|a| a == name)
+            .and_then(|i| args.get(i + 1))
+            .map(|s| s.as_str())
+    };
+    let (Some(path), Some(latency), Some(memory)) =
+        (flag("--path"), flag("--target-latency"), flag("--memory"))
+    else {
+        eprintln!("Error: --path, --target-latency and --memory are required");
+        print_help();
+        return 2;
+    };
+    let (Some(latency), Some(memory_bytes)) = (parse_latency(latency), parse_memory(memory)) else {
+        eprintln!("Error: expected a latency like 50ms and a memory quantity like 8Gi");
+        return 2;
+    };
+
+    eprintln!("  calibrating memory bandwidth...");
+    let cpu = CpuCalibration::run(256 << 20, Duration::from_millis(500));
+    let target = RecommendTarget { latency, memory_bytes };
+    let report = match recommend(std::path::Path::new(path), target, &[&cpu]) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("FAIL {}", e);
+            return 1;
+        }
+    };
+    match &report.recommendation {
+        Some(r) => eprintln!(
+            "  recommend {} on {} (est. ~{:.1} ms/token, {:.1} MiB): {}",
+            r.method,
+            r.backend,
+            r.estimated_ms_per_token,
+            r.estimated_bytes as f64 / (1024.0 * 1024.0),
+            report.reason
+        ),
+        None => eprintln!("  no recommendation: {}", report.reason),
+    }
+    eprintln!("  latencies are estimates: {}", report.estimate_basis);
+
+    let json = serde_json::to_string_pretty(&report).unwrap_or_default();
+    match flag("--output") {
+        Some(file) => {
+            if let Err(e) = std::fs::write(file, json + "\n") {
+                eprintln!("Error: cannot write {}: {}", file, e);
+                return 1;
+            }
+        }
+        None => println!("{}", json),
+    }
+    if report.recommendation.is_some() { 0 } else { 1 }
+}
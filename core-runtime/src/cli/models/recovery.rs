@@ -0,0 +1,79 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! `models recovery-keygen` and `models recover`: escrowed model key recovery.
+
+use std::path::PathBuf;
+
+use crate::security::escrow::{self, RecoveryPrivateKey};
+
+pub(super) fn run_models_recovery_keygen(args: &[String]) -> i32 {
+    let Some(prefix) = args.iter().position(|a| a == "--out").and_then(|i| args.get(i + 1)) else {
+        eprintln!("Error: --out PREFIX is required");
+        return 2;
+    };
+    let private_path = PathBuf::from(format!("{}.key", prefix));
+    let public_path = PathBuf::from(format!("{}.pub", prefix));
+    let key = RecoveryPrivateKey::generate();
+    let public = key.public_key();
+    if let Err(e) = key.save(&private_path) {
+        eprintln!("Error: {}", e);
+        return 1;
+    }
+    if let Err(e) = std::fs::write(&public_path, format!("{}\n", public.to_hex())) {
+        eprintln!("Error: {}: {}", public_path.display(), e);
+        return 1;
+    }
+    println!("Recovery key {}", public.key_id());
+    println!("  public   {}  (set CORE_RECOVERY_PUBLIC_KEY to this file)", public_path.display());
+    println!("  private  {}  (store offline)", private_path.display());
+    0
+}
+
+pub(super) fn run_models_recover(args: &[String]) -> i32 {
+    let Some(key_index) = args.iter().position(|a| a == "--recovery-key") else {
+        eprintln!("Error: --recovery-key FILE is required");
+        return 2;
+    };
+    let Some(key_path) = args.get(key_index + 1) else {
+        eprintln!("Error: --recovery-key FILE is required");
+        return 2;
+    };
+    let models: Vec<&String> = args
+        .iter()
+        .enumerate()
+        .skip(3)
+        .filter(|(i, _)| *i != key_index && *i != key_index + 1)
+        .map(|(_, a)| a)
+        .collect();
+    if models.is_empty() {
+        eprintln!("Error: no model paths given");
+        return 2;
+    }
+    let recovery = match RecoveryPrivateKey::load(std::path::Path::new(key_path)) {
+        Ok(key) => key,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return 2;
+        }
+    };
+    let machine_key = match crate::security::ModelEncryption::from_machine_id() {
+        Ok(key) => key,
+        Err(e) => {
+            eprintln!("Error: cannot derive model key: {}", e);
+            return 1;
+        }
+    };
+
+    let mut failed = 0;
+    for model in models {
+        match escrow::rewrap_artifact(std::path::Path::new(model), &recovery, &machine_key) {
+            Ok(()) => println!("OK   {}", model),
+            Err(e) => {
+                eprintln!("FAIL {}: {}", model, e);
+                failed += 1;
+            }
+        }
+    }
+    i32::from(failed > 0)
+}
@@ -0,0 +1,70 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! `placement`: show which replica serves a model.
+
+use crate::models::read_adverts;
+
+use super::config::placement_config;
+
+/// Run the placement CLI command (reads the shared directory directly).
+pub fn run_placement(args: &[String]) -> i32 {
+    let Some(config) = placement_config() else {
+        eprintln!("CORE_PLACEMENT_DIR is not set");
+        return 2;
+    };
+    let adverts = match read_adverts(&config.dir, config.ttl) {
+        Ok(adverts) => adverts,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return 1;
+        }
+    };
+    match (args.get(2).map(|s| s.as_str()), args.get(3)) {
+        (Some("list") | None, _) => {
+            for advert in adverts {
+                println!("{}\t{}", advert.replica_id, advert.models.join(","));
+            }
+            0
+        }
+        (Some("which"), Some(model)) => {
+            let replicas: Vec<_> = adverts
+                .into_iter()
+                .filter(|a| a.models.contains(model))
+                .map(|a| a.replica_id)
+                .collect();
+            if replicas.is_empty() {
+                eprintln!("No replica serves '{}'", model);
+                return 1;
+            }
+            println!("{}", replicas.join("\n"));
+            0
+        }
+        _ => {
+            print_help();
+            1
+        }
+    }
+}
+
+/// Print detailed help for `placement`.
+pub fn print_help() {
+    eprintln!(
+        "GG-CORE placement - Model placement across replicas
+
+USAGE:
+    GG-CORE placement <SUBCOMMAND>
+
+SUBCOMMANDS:
+    list           List live replicas and their loaded models
+    which <MODEL>  Print replicas currently serving MODEL
+
+Reads adverts from CORE_PLACEMENT_DIR. Each serving replica refreshes its
+advert periodically; adverts older than 30s are ignored.
+
+EXAMPLES:
+    GG-CORE placement list
+    GG-CORE placement which phi-3
+"
+    );
+}
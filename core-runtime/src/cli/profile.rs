@@ -0,0 +1,71 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! `profile`: capture a CPU profile of the running server.
+
+use std::time::Duration;
+
+use super::ipc_client::CliIpcClient;
+use super::get_socket_path;
+
+/// Run the profile CLI command.
+pub async fn run_profile(args: &[String]) -> i32 {
+    let flag = |name: &str| {
+        args.iter()
+            .position(|a| a == name)
+            .and_then(|i| args.get(i + 1))
+            .map(|s| s.as_str())
+    };
+    let seconds = match flag("--seconds").map(str::parse::<u32>) {
+        None => 30,
+        Some(Ok(n)) if n > 0 => n,
+        Some(_) => {
+            eprintln!("Error: --seconds must be a positive number");
+            return 2;
+        }
+    };
+    let out = flag("--out").unwrap_or("cpu.pb.gz");
+
+    eprintln!("Profiling for {}s...", seconds);
+    let client = CliIpcClient::new(get_socket_path())
+        .with_timeout(Duration::from_secs(u64::from(seconds) + 30));
+    match client.capture_profile(seconds).await {
+        Ok(profile) => match std::fs::write(out, &profile) {
+            Ok(()) => {
+                println!("Wrote {} ({} bytes)", out, profile.len());
+                0
+            }
+            Err(e) => {
+                eprintln!("Error: cannot write {}: {}", out, e);
+                1
+            }
+        },
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            3
+        }
+    }
+}
+
+/// Print detailed help for `profile`.
+pub fn print_help() {
+    eprintln!(
+        "GG-CORE profile - Capture a CPU profile of the running server
+
+USAGE:
+    GG-CORE profile [OPTIONS]
+
+OPTIONS:
+    --seconds N    Sampling window (default: 30, server maximum: 120)
+    --out FILE     Output file (default: cpu.pb.gz)
+
+The server must be built with the 'profiling' feature and started with
+CORE_PROFILING=1. Requires an admin session when authentication is enabled.
+The output is a gzipped pprof protobuf.
+
+EXAMPLES:
+    GG-CORE profile --seconds 30 --out cpu.pb.gz
+    go tool pprof -http=:8080 cpu.pb.gz
+"
+    );
+}
@@ -0,0 +1,180 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! `serve`: bootstrap the runtime and run the IPC server until a signal.
+//!
+//! Startup runs the FIPS self-tests, storage and migration checks before
+//! any configuration is applied; a failure in any of them still leaves an
+//! exit report behind.
+
+mod server;
+#[cfg(unix)]
+mod supervise;
+mod tasks;
+
+use std::path::Path;
+use std::process::ExitCode;
+
+use crate::engine::cpu_dispatch;
+use crate::exit_report::{self, ExitReason, ExitReport, RecentErrors};
+use crate::security::{fips_mode, fips_tests};
+use crate::snapshot::{ConfigSnapshot, SNAPSHOT_DIR};
+use crate::telemetry::{init_resource, ResourceAttributes};
+use crate::Runtime;
+
+use super::config::{fips_mode_setting, install_storage, load_config, simd_tier_override, warn_config_schema};
+use super::migrate::run_startup_migrations;
+
+/// Run the server, or with `--supervise` (Unix) a supervisor around it.
+#[cfg_attr(not(unix), allow(unused_variables))]
+pub async fn run_serve(args: &[String]) -> ExitCode {
+    #[cfg(unix)]
+    if args.iter().skip(2).any(|a| a == "--supervise") {
+        return supervise::run_supervise(args).await;
+    }
+    let started_at = chrono::Utc::now();
+
+    // Refuse to start (before loading anything) if another runtime
+    // already serves this socket path; its exit report is not ours
+    #[cfg(unix)]
+    let _instance = match supervise::acquire_instance_lock(&super::get_socket_path()) {
+        Ok(lock) => lock,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    warn_config_schema();
+    let mut config = load_config();
+    if let Err(reason) = preflight(&config.base_path).await {
+        finish_run(ExitReport::new(reason, started_at), &config.base_path).await;
+        return ExitCode::FAILURE;
+    }
+    // A snapshot restore stages its configuration for the next start
+    let snapshot_dir = config.base_path.join(SNAPSHOT_DIR);
+    match ConfigSnapshot::apply_staged(&snapshot_dir, &mut config) {
+        Ok(true) => eprintln!("Applied configuration staged by a snapshot restore"),
+        Ok(false) => {}
+        Err(e) => eprintln!("Warning: staged snapshot configuration not applied: {}", e),
+    }
+    config.previous_exit = exit_report::begin_run(&config.base_path, started_at);
+    if let Some(previous) = &config.previous_exit {
+        eprintln!("Previous exit: {}", previous.reason.describe());
+    }
+    let resource = init_resource(ResourceAttributes::detect(&config.base_path));
+    eprintln!("Instance ID: {} ({})", resource.instance_id, resource.node);
+
+    let base_path = config.base_path.clone();
+    let errors = RecentErrors::install();
+    let (code, report) = match server::run_ipc_server(Runtime::new(config), started_at, &errors).await {
+        Ok(report) => (ExitCode::SUCCESS, report),
+        Err(e) => {
+            eprintln!("Server error: {}", e);
+            let reason = ExitReason::FatalError { error: e.to_string() };
+            (ExitCode::FAILURE, ExitReport::new(reason, started_at))
+        }
+    };
+    // The server failing on its own still drains, but is not a success
+    let code = match report.reason {
+        ExitReason::FatalError { .. } => ExitCode::FAILURE,
+        _ => code,
+    };
+    finish_run(report.with_errors(errors.snapshot()), &base_path).await;
+    code
+}
+
+/// Checks that must pass before any configuration is applied: SIMD
+/// dispatch, FIPS self-tests and allow-list, state storage, migrations.
+async fn preflight(base_path: &Path) -> Result<(), ExitReason> {
+    // Fix SIMD kernel dispatch before anything runs a kernel
+    let dispatch = cpu_dispatch::init(simd_tier_override());
+    eprintln!(
+        "SIMD dispatch: {} (detected {}{})",
+        dispatch.max_tier,
+        dispatch.detected,
+        if dispatch.overridden { ", lowered by CORE_SIMD_MAX_TIER" } else { "" }
+    );
+
+    // FIPS 140-3 power-on self-tests (fail-fast)
+    if let Err(e) = fips_tests::run_power_on_self_tests() {
+        eprintln!("FIPS self-test FAILED: {}", e);
+        eprintln!("Cryptographic operations disabled. Aborting startup.");
+        return Err(ExitReason::FipsFailure { error: e.to_string() });
+    }
+    eprintln!("FIPS 140-3 self-tests: PASSED");
+
+    // Check the linked algorithms against the allow-list before any
+    // key is touched
+    let allow_list = fips_mode::verify_allow_list(fips_mode::init(fips_mode_setting())).map_err(|e| {
+        eprintln!("FIPS allow-list check FAILED: {}", e);
+        ExitReason::FipsFailure { error: e.to_string() }
+    })?;
+    match allow_list.blocked().as_slice() {
+        [] => eprintln!("FIPS mode: {}", allow_list.mode),
+        blocked => eprintln!("FIPS mode: {} (refusing {})", allow_list.mode, blocked.join(", ")),
+    }
+    allow_list.audit().await;
+
+    // Salt and catalog may live in object storage; a store that is
+    // configured but unusable must not fall back to local disk
+    install_storage(base_path).map_err(|e| {
+        eprintln!("Error: state storage: {}", e);
+        ExitReason::FatalError { error: e.to_string() }
+    })?;
+    run_startup_migrations(base_path).map_err(|e| {
+        eprintln!("Error: {}", e);
+        ExitReason::FatalError { error: e }
+    })
+}
+
+/// Persist the exit report and emit it as the final audit event.
+async fn finish_run(report: ExitReport, base_path: &Path) {
+    if let Err(e) = report.write(base_path) {
+        eprintln!("Failed to write exit report: {}", e);
+    }
+    report.audit().await;
+}
+
+/// Print detailed help for `serve`.
+pub fn print_help() {
+    eprintln!(
+        "GG-CORE serve - Run the IPC server
+
+USAGE:
+    GG-CORE serve [OPTIONS]
+
+OPTIONS:
+    --socket PATH     Override IPC socket path
+    --config FILE     Load configuration from file
+    --auth-token TKN  Set authentication token
+    --supervise       Run the server as a child process and restart it on
+                      crashes (Unix; for installs without systemd)
+
+DESCRIPTION:
+    Starts the GG-CORE IPC server, which handles inference requests
+    through inter-process communication. This is the default command when
+    no command is specified.
+
+    The server performs FIPS 140-3 power-on self-tests before starting
+    and will fail-fast if any cryptographic self-test fails.
+
+    Only one server may own a socket path. A second server started on the
+    same path exits with an error naming the running instance's PID (held
+    in <socket>.lock); a socket left behind by a dead server is replaced.
+
+    With --supervise a small parent process binds the socket and holds the
+    lock, then runs the server as a child. A crashed child is restarted
+    with backoff (1s doubling to 60s, reset after a minute of uptime) on
+    the same socket, so clients queue instead of failing to connect. A
+    clean exit or SIGTERM to the parent ends supervision. The child's pid,
+    restart count and last exit are kept in <base path>/supervisor.json.
+
+EXAMPLES:
+    GG-CORE serve
+    GG-CORE serve --supervise
+    GG-CORE serve --socket /custom/veritas.sock
+    GG-CORE serve --config /etc/veritas/config.toml
+"
+    );
+}
@@ -0,0 +1,159 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The server loop: listeners, background tasks and the drain on exit.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+use crate::exit_report::{ExitReason, ExitReport, RecentErrors};
+use crate::ipc::{server, tcp, ConnectionPool, IpcHandler, TcpEndpoint, TcpTransportError};
+use crate::shutdown::{ShutdownCoordinator, ShutdownReport, ShutdownResult};
+use crate::Runtime;
+
+use super::tasks::BackgroundTasks;
+use crate::cli::get_socket_path;
+
+/// Wait for SIGINT or, on Unix, SIGTERM. Returns the signal's name.
+pub(super) async fn wait_for_signal() -> std::io::Result<String> {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        let mut terminate = signal(SignalKind::terminate())?;
+        tokio::select! {
+            result = tokio::signal::ctrl_c() => result.map(|()| "SIGINT".to_string()),
+            _ = terminate.recv() => Ok("SIGTERM".to_string()),
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        tokio::signal::ctrl_c().await.map(|()| "Ctrl+C".to_string())
+    }
+}
+
+/// Serve IPC until a signal or a server failure, then drain in-flight work.
+pub(super) async fn run_ipc_server(
+    runtime: Runtime,
+    started_at: chrono::DateTime<chrono::Utc>,
+    errors: &RecentErrors,
+) -> Result<ExitReport, Box<dyn std::error::Error>> {
+    let socket_path = get_socket_path();
+    let tasks = BackgroundTasks::spawn(&runtime)?;
+    let handler = Arc::new(runtime.ipc_handler);
+    let connections = runtime.connections;
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+
+    // Optional loopback web console, driven through the same handler
+    #[cfg(feature = "web-console")]
+    if let Ok(addr) = std::env::var("CORE_CONSOLE_ADDR") {
+        if runtime.config.admin_token.is_none() {
+            return Err("CORE_CONSOLE_ADDR requires CORE_ADMIN_TOKEN for console actions".into());
+        }
+        let addr: std::net::SocketAddr = addr.parse()?;
+        let listener = crate::console::Console::bind(addr).await?;
+        eprintln!("Admin console listening on http://{}", addr);
+        let console = Arc::new(crate::console::Console::new(Arc::clone(&handler)));
+        tokio::spawn(console.run(listener, shutdown_rx.clone()));
+    }
+
+    let base_path = runtime.config.base_path.clone();
+    let tcp_handle = spawn_tcp(&runtime.config, &base_path, &handler, &connections, &shutdown_rx).await?;
+
+    #[cfg(unix)]
+    let server_future = server::run_server_with_permissions(
+        socket_path,
+        runtime.config.socket,
+        handler,
+        connections,
+        shutdown_rx,
+    );
+    #[cfg(windows)]
+    let server_future = server::run_server(socket_path, handler, connections, shutdown_rx);
+    let mut server_handle = tokio::spawn(server_future);
+
+    // Wait for a signal, or for the server to fail, then drain
+    let (reason, server_result) = tokio::select! {
+        signal = wait_for_signal() => (ExitReason::Signal { signal: signal? }, None),
+        joined = &mut server_handle => {
+            let error = match &joined {
+                Ok(Ok(())) => "server loop exited".to_string(),
+                Ok(Err(e)) => e.to_string(),
+                Err(e) => e.to_string(),
+            };
+            (ExitReason::FatalError { error }, Some(joined))
+        }
+    };
+    eprintln!("Runtime {}, draining...", reason.describe());
+
+    // Signal the server loop to stop accepting
+    let _ = shutdown_tx.send(true);
+    let report = drain(&runtime.shutdown, runtime.config.shutdown_timeout).await;
+
+    tasks.stop();
+    if let Err(e) = runtime.model_registry.persist_stats().await {
+        eprintln!("Failed to persist model statistics: {}", e);
+        errors.note(format!("persist model statistics: {}", e));
+    }
+    if let Some(handle) = tcp_handle {
+        handle.abort();
+        TcpEndpoint::remove(&base_path);
+    }
+
+    // Wait for server task to finish
+    let server_result = match server_result {
+        Some(joined) => joined,
+        None => server_handle.await,
+    };
+    if let Err(e) = server_result? {
+        eprintln!("Server error: {}", e);
+        errors.note(format!("server: {}", e));
+    }
+
+    Ok(ExitReport::new(reason, started_at).with_shutdown(&report))
+}
+
+/// Optional loopback TCP listener for clients that cannot open the socket.
+async fn spawn_tcp(
+    config: &crate::RuntimeConfig,
+    base_path: &Path,
+    handler: &Arc<IpcHandler>,
+    connections: &Arc<ConnectionPool>,
+    shutdown_rx: &watch::Receiver<bool>,
+) -> Result<Option<JoinHandle<Result<(), server::ServerError>>>, Box<dyn std::error::Error>> {
+    let Some(spec) = std::env::var("CORE_TCP_ADDR").ok().filter(|v| !v.is_empty()) else {
+        return Ok(None);
+    };
+    if config.auth_token.is_empty() {
+        return Err(TcpTransportError::NoAuthToken.into());
+    }
+    let listener = tcp::bind(spec.parse()?).await?;
+    TcpEndpoint::new(listener.local_addr()?).write(base_path)?;
+    Ok(Some(tokio::spawn(server::run_tcp_server(
+        listener,
+        Arc::clone(handler),
+        Arc::clone(connections),
+        shutdown_rx.clone(),
+    ))))
+}
+
+/// Drain in-flight requests, then close or abort connections.
+async fn drain(shutdown: &ShutdownCoordinator, timeout: std::time::Duration) -> ShutdownReport {
+    let report = shutdown.initiate_with_report(timeout).await;
+    match report.result {
+        ShutdownResult::Complete => eprintln!("Shutdown complete"),
+        ShutdownResult::Timeout { remaining } => {
+            eprintln!("Shutdown timeout, {} requests remaining", remaining);
+        }
+    }
+    eprintln!(
+        "Requests drained {}, aborted {}; connections drained {}, aborted {}",
+        report.requests.drained,
+        report.requests.aborted,
+        report.connections.drained,
+        report.connections.aborted,
+    );
+    report
+}
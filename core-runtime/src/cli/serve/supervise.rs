@@ -0,0 +1,92 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! `serve --supervise`: a parent process that owns the socket and restarts
+//! the server child on crashes.
+
+use std::path::Path;
+use std::process::ExitCode;
+
+use crate::ipc::{listener, InstanceLock, InstanceLockError};
+use crate::supervisor::{Supervisor, SupervisorConfig, SUPERVISOR_STATUS_FILE};
+
+use super::server::wait_for_signal;
+use crate::cli::config::{load_config, socket_permissions};
+use crate::cli::get_socket_path;
+
+/// Single-instance lock for a filesystem socket. Abstract sockets fail to
+/// bind when taken and activated sockets belong to systemd or the
+/// supervisor, so neither needs one.
+pub(super) fn acquire_instance_lock(socket_path: &str) -> Result<Option<InstanceLock>, InstanceLockError> {
+    if socket_path.starts_with('@') || listener::activated_fd_from_env().is_some() {
+        return Ok(None);
+    }
+    InstanceLock::acquire(Path::new(socket_path)).map(Some)
+}
+
+/// Run `serve --supervise`: bind the socket once, then run `serve` as a
+/// child process, restarting it on crashes.
+pub(super) async fn run_supervise(args: &[String]) -> ExitCode {
+    let socket_path = get_socket_path();
+    let _instance = match acquire_instance_lock(&socket_path) {
+        Ok(lock) => lock,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let base_path = load_config().base_path;
+    let (listener, source) = match listener::open(&socket_path, &socket_permissions()) {
+        Ok(opened) => opened,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let listener = match listener.into_std() {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let program = match std::env::current_exe() {
+        Ok(program) => program,
+        Err(e) => {
+            eprintln!("Error: cannot locate own executable: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let child_args = args[1..].iter().filter(|a| *a != "--supervise").cloned().collect();
+    eprintln!(
+        "Supervisor {} listening on {}; status in {}",
+        std::process::id(),
+        source,
+        base_path.join(SUPERVISOR_STATUS_FILE).display()
+    );
+
+    let supervisor = Supervisor::new(SupervisorConfig::default(), program, child_args, base_path);
+    let result = supervisor
+        .run(&listener, async {
+            let _ = wait_for_signal().await;
+        })
+        .await;
+    drop(listener);
+    if let Some(path) = source.owned_path() {
+        let _ = std::fs::remove_file(path);
+    }
+    match result {
+        Ok(status) => {
+            eprintln!(
+                "Supervisor stopped after {} restarts (last child: {})",
+                status.restarts,
+                status.last_exit.as_deref().unwrap_or("none")
+            );
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Supervisor error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
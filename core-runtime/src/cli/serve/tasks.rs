@@ -0,0 +1,106 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Periodic work the server runs beside its listeners.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+
+use crate::maintenance::MaintenanceScheduler;
+use crate::models::recommend::parse_memory;
+use crate::models::{ModelRegistry, PlacementBoard};
+use crate::telemetry::{HeapGuard, HeapGuardConfig, StabilityConfig, StabilityMonitor};
+use crate::Runtime;
+
+/// Background tasks spawned for the server's lifetime, aborted once
+/// in-flight requests have drained.
+pub(super) struct BackgroundTasks {
+    handles: Vec<JoinHandle<()>>,
+    placement: Option<Arc<PlacementBoard>>,
+}
+
+impl BackgroundTasks {
+    /// Start every task this runtime's configuration calls for.
+    pub(super) fn spawn(runtime: &Runtime) -> Result<Self, Box<dyn std::error::Error>> {
+        let placement = match runtime.config.placement.clone().map(PlacementBoard::new) {
+            Some(Ok(board)) => Some(Arc::new(board)),
+            Some(Err(e)) => return Err(e.into()),
+            None => None,
+        };
+        let mut handles = Vec::new();
+        if let Some(board) = &placement {
+            let interval = runtime.config.placement.as_ref().map_or(Duration::from_secs(10), |c| c.ttl / 3);
+            handles.push(tokio::spawn(Arc::clone(board).run_advertiser(runtime.model_registry.clone(), interval)));
+        }
+
+        // Run maintenance tasks when a configured window opens
+        handles.push(tokio::spawn(run_maintenance(Arc::clone(&runtime.maintenance))));
+
+        // Apply the warm-pool schedule as the hour changes
+        if let Some(controller) = runtime.warm_pool.clone() {
+            handles.push(tokio::spawn(controller.run(Duration::from_secs(60))));
+        }
+
+        // Persist per-model request statistics so they survive restarts
+        handles.push(tokio::spawn(persist_stats(runtime.model_registry.clone())));
+
+        // Enforce audit, journal and usage retention policies
+        handles.push(tokio::spawn(Arc::clone(&runtime.retention).run()));
+
+        // Evaluate SLOs continuously so burn-rate events fire without queries
+        handles.push(tokio::spawn(Arc::clone(runtime.ipc_handler.slo()).run()));
+
+        // Throttle GPU traffic while a GPU runs hot or at its power limit
+        handles.push(tokio::spawn(Arc::clone(runtime.ipc_handler.thermal()).run()));
+
+        // Dump top allocation sites when tracked heap crosses the soft limit
+        let heap_guard = Arc::new(HeapGuard::new(HeapGuardConfig {
+            soft_limit_bytes: std::env::var("CORE_HEAP_SOFT_LIMIT").ok().and_then(|v| parse_memory(&v)),
+            report_dir: Some(runtime.config.base_path.join("heap-reports")),
+            ..Default::default()
+        }));
+        handles.push(tokio::spawn(heap_guard.run()));
+
+        // Re-hash resident model files; degrade models whose file changed
+        if let Some(monitor) = runtime.integrity.clone() {
+            handles.push(tokio::spawn(monitor.run()));
+        }
+
+        // Track FD, thread, memory and task counts; warn on sustained growth
+        let stability = StabilityMonitor::new(StabilityConfig::default(), Arc::clone(&runtime.metrics_store));
+        handles.push(tokio::spawn(Arc::new(stability).run()));
+
+        Ok(Self { handles, placement })
+    }
+
+    /// Abort every task and withdraw this replica's placement advert.
+    pub(super) fn stop(self) {
+        for handle in self.handles {
+            handle.abort();
+        }
+        if let Some(board) = self.placement {
+            let _ = board.withdraw();
+        }
+    }
+}
+
+async fn run_maintenance(maintenance: Arc<MaintenanceScheduler>) {
+    let mut interval = tokio::time::interval(Duration::from_secs(60));
+    loop {
+        interval.tick().await;
+        maintenance.tick(chrono::Utc::now()).await;
+    }
+}
+
+async fn persist_stats(registry: Arc<ModelRegistry>) {
+    let mut interval = tokio::time::interval(Duration::from_secs(60));
+    interval.tick().await;
+    loop {
+        interval.tick().await;
+        if let Err(e) = registry.persist_stats().await {
+            tracing::warn!(error = %e, "Failed to persist model statistics");
+        }
+    }
+}
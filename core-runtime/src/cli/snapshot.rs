@@ -0,0 +1,66 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! `snapshot`: create or restore a runtime state snapshot.
+
+use crate::ipc::SnapshotAction;
+
+use super::ipc_client::CliIpcClient;
+use super::get_socket_path;
+
+pub async fn run_snapshot(args: &[String]) -> i32 {
+    let action = match args.get(2).map(|s| s.as_str()) {
+        Some("create") => SnapshotAction::Create,
+        Some("restore") => SnapshotAction::Restore,
+        _ => {
+            print_help();
+            return 1;
+        }
+    };
+    let Some(name) = args.get(3) else {
+        eprintln!("Missing snapshot name");
+        return 1;
+    };
+
+    let client = CliIpcClient::new(get_socket_path());
+    match client.snapshot(action, name).await {
+        Ok(resp) if resp.success => {
+            println!("Snapshot '{}': {} model(s)", resp.name, resp.models);
+            0
+        }
+        Ok(resp) => {
+            eprintln!("Snapshot failed: {}", resp.error.unwrap_or_default());
+            1
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            3
+        }
+    }
+}
+
+/// Print detailed help for `snapshot`.
+pub fn print_help() {
+    eprintln!(
+        "GG-CORE snapshot - Snapshot and restore runtime state
+
+USAGE:
+    GG-CORE snapshot <SUBCOMMAND> <NAME>
+
+SUBCOMMANDS:
+    create <NAME>   Save model catalog, pool, warmup state and config
+    restore <NAME>  Reload models from their files, keeping warmup state (admin)
+
+Snapshots are stored as versioned JSON under <base_path>/snapshots/.
+NAME may contain only letters, digits, '-' and '_'. Restored configuration
+takes effect on the next start. Auth tokens are never written.
+
+OPTIONS:
+    --socket PATH  Override IPC socket path
+
+EXAMPLES:
+    GG-CORE snapshot create known-good
+    GG-CORE snapshot restore known-good
+"
+    );
+}
@@ -0,0 +1,96 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Resource, GPU and scheduler sections of the status output.
+
+use super::format::{format_bytes, truncate};
+use super::{SchedulerStatus, SystemStatus};
+
+/// Memory, CPU and, when reported, GPUs.
+pub(super) fn print_resources(status: &SystemStatus) {
+    // Resource utilization
+    println!("\n💾 Resources");
+    println!("┌─────────────────────────────────────────────────────────────────┐");
+    println!(
+        "│ Memory: {:>10} / {:>10} ({:>5.1}%)                      │",
+        format_bytes(status.resources.memory_rss_bytes),
+        format_bytes(status.resources.memory_limit_bytes),
+        status.resources.memory_utilization_percent
+    );
+    println!(
+        "│   KV Cache: {:>10}   Arena: {:>10}                       │",
+        format_bytes(status.resources.kv_cache_bytes),
+        format_bytes(status.resources.arena_bytes)
+    );
+    println!(
+        "│ CPU: {:>5.1}%    Threads: {:>3}                                     │",
+        status.resources.cpu_utilization_percent, status.resources.active_threads
+    );
+    println!("└─────────────────────────────────────────────────────────────────┘");
+
+    // GPU status (if available)
+    if let Some(ref gpus) = status.gpus {
+        if !gpus.is_empty() {
+            println!("\n🖥️  GPUs ({} devices)", gpus.len());
+            println!("┌──────┬────────────────────────────┬──────────┬───────┬──────────┐");
+            println!("│ ID   │ Name                        │ Memory   │ Util  │ Temp     │");
+            println!("├──────┼────────────────────────────┼──────────┼───────┼──────────┤");
+            for gpu in gpus {
+                println!(
+                    "│ {:4} │ {:26} │ {:>8} │ {:>5.0}% │ {:>6.0}°C │",
+                    gpu.gpu_id,
+                    truncate(&gpu.name, 26),
+                    format_bytes(gpu.memory_used_bytes),
+                    gpu.utilization_percent,
+                    gpu.temperature_celsius
+                );
+            }
+            println!("└──────┴────────────────────────────┴──────────┴───────┴──────────┘");
+        }
+    }
+}
+
+/// Queue, batches, busy workers and GPU shares.
+pub(super) fn print_scheduler(scheduler: &SchedulerStatus) {
+    // Scheduler status
+    println!("\n⚙️  Scheduler");
+    println!("┌─────────────────────────────────────────────────────────────────┐");
+    println!(
+        "│ Queue Depth: {:>5}   Active Batches: {:>3}   Pending: {:>5}    │",
+        scheduler.queue_depth,
+        scheduler.active_batches,
+        scheduler.pending_requests
+    );
+    println!(
+        "│ Completed: {:>8}   Avg Batch Size: {:>5.1}                      │",
+        scheduler.completed_requests, scheduler.avg_batch_size
+    );
+    println!(
+        "│ Workers: {:>3}/{:<3} busy   In Flight: {:>5}                        │",
+        scheduler.busy_workers, scheduler.workers, scheduler.in_flight
+    );
+    for batch in &scheduler.batches {
+        println!("│   batch {:40} {:>5} requests   │", truncate(&batch.model_id, 40), batch.requests);
+    }
+    for slot in scheduler.worker_slots.iter().filter(|s| s.request_id.is_some()) {
+        println!(
+            "│ {:>3} {:26} {:22} {:>6}ms │",
+            slot.worker,
+            slot.request_id.as_deref().unwrap_or_default(),
+            truncate(slot.model_id.as_deref().unwrap_or("-"), 22),
+            slot.busy_ms
+        );
+    }
+    for gpu in &scheduler.gpu_shares {
+        println!(
+            "│ gpu {:22} share {:>3} {:>4} run {:>4} wait {:>5.1}%{} │",
+            truncate(&gpu.model_id, 22),
+            gpu.share,
+            gpu.running,
+            gpu.waiting,
+            gpu.occupancy * 100.0,
+            if gpu.holding { "*" } else { " " }
+        );
+    }
+    println!("└─────────────────────────────────────────────────────────────────┘");
+}
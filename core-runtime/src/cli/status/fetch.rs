@@ -0,0 +1,191 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Assemble a status report from the runtime's health, metrics and models.
+
+use crate::cli::ipc_client::{CliError, CliIpcClient};
+use crate::telemetry::streaming::{INTER_TOKEN_HISTOGRAM, TTFT_HISTOGRAM};
+
+use super::{
+    HealthState, ModelState, ModelStatus, RequestStats, ResourceUtilization, SchedulerStatus, SystemStatus, VersionInfo,
+};
+
+/// Fetch status from the IPC server.
+pub(super) async fn fetch_status(socket_path: &str) -> Result<SystemStatus, CliError> {
+    let client = CliIpcClient::new(socket_path.to_string());
+
+    // Get health report and metrics from the runtime
+    let health_response = client.get_health_report().await?;
+    let report = health_response.report;
+
+    // Get metrics snapshot (may fail if runtime doesn't support it yet)
+    let metrics = client.get_metrics().await.ok();
+
+    // Get loaded models (may fail if runtime doesn't support it yet)
+    let models_response = client.get_models().await.ok();
+
+    // Extract counters from metrics
+    let total_requests = metrics
+        .as_ref()
+        .and_then(|m| m.counters.get("core_requests_total").copied())
+        .unwrap_or(0);
+    let successful_requests = metrics
+        .as_ref()
+        .and_then(|m| m.counters.get("core_requests_success").copied())
+        .unwrap_or(0);
+    let failed_requests = metrics
+        .as_ref()
+        .and_then(|m| m.counters.get("core_requests_failed").copied())
+        .unwrap_or(0);
+    let tokens_generated = metrics
+        .as_ref()
+        .and_then(|m| m.counters.get("core_tokens_output_total").copied())
+        .unwrap_or(0);
+
+    // Extract gauges from metrics
+    let memory_pool_bytes = metrics
+        .as_ref()
+        .and_then(|m| m.gauges.get("core_memory_pool_used_bytes").copied())
+        .unwrap_or(0.0) as u64;
+    let arena_bytes = metrics
+        .as_ref()
+        .and_then(|m| m.gauges.get("core_arena_used_bytes").copied())
+        .unwrap_or(0.0) as u64;
+    let queue_depth = metrics
+        .as_ref()
+        .and_then(|m| m.gauges.get("core_queue_depth").copied())
+        .unwrap_or(0.0) as u64;
+
+    // Extract histogram data for latency
+    let latency_hist = metrics
+        .as_ref()
+        .and_then(|m| m.histograms.get("core_inference_latency_ms"));
+    let avg_latency_ms = latency_hist
+        .map(|h| if h.count > 0 { h.sum / h.count as f64 } else { 0.0 })
+        .unwrap_or(0.0);
+
+    // Streaming latency from bucketed histograms
+    let bucketed = |name: &str| metrics.as_ref().and_then(|m| m.bucketed_histograms.get(name));
+    let ttft = bucketed(TTFT_HISTOGRAM);
+    let inter_token = bucketed(INTER_TOKEN_HISTOGRAM);
+
+    // Prefer the server's 1m rates; fall back to lifetime averages for
+    // runtimes that don't report them
+    let uptime_secs = report.as_ref().map(|r| r.uptime_secs).unwrap_or(1).max(1);
+    let rate = |name: &str| metrics.as_ref().and_then(|m| m.rates.get(name).copied());
+    let requests_per_second = rate("core_requests_rate_1m")
+        .unwrap_or(total_requests as f64 / uptime_secs as f64);
+    let tokens_per_second = rate("core_tokens_rate_1m")
+        .unwrap_or(tokens_generated as f64 / uptime_secs as f64);
+
+    let status = SystemStatus {
+        health: if health_response.ok {
+            HealthState::Healthy
+        } else {
+            HealthState::Unhealthy
+        },
+        uptime_secs,
+        version: VersionInfo {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            commit: option_env!("VERGEN_GIT_SHA").unwrap_or("unknown").to_string(),
+            build_date: option_env!("VERGEN_BUILD_DATE").unwrap_or("unknown").to_string(),
+            rust_version: option_env!("VERGEN_RUSTC_SEMVER").unwrap_or("unknown").to_string(),
+        },
+        resource: metrics.as_ref().and_then(|m| m.resource.clone()),
+        previous_exit: report.as_ref().and_then(|r| r.previous_exit.clone()),
+        fips_mode: report.as_ref().map(|r| r.fips_mode).unwrap_or_default(),
+        models: models_response
+            .as_ref()
+            .map(|r| {
+                r.models
+                    .iter()
+                    .map(|m| {
+                        let avg_latency = if m.request_count > 0 {
+                            m.avg_latency_ms / m.request_count as f64
+                        } else {
+                            0.0
+                        };
+                        ModelStatus {
+                            name: m.name.clone(),
+                            format: m.format.clone(),
+                            size_bytes: m.size_bytes,
+                            loaded_at: m.loaded_at.clone(),
+                            request_count: m.request_count,
+                            avg_latency_ms: avg_latency,
+                            avg_ttft_ms: m.avg_ttft_ms,
+                            avg_inter_token_ms: m.avg_inter_token_ms,
+                            state: match m.state.as_str() {
+                                "loading" => ModelState::Loading,
+                                "ready" => ModelState::Ready,
+                                "unloading" => ModelState::Unloading,
+                                "error" => ModelState::Error,
+                                _ => ModelState::Ready,
+                            },
+                        }
+                    })
+                    .collect()
+            })
+            .unwrap_or_default(),
+        circuits: models_response.as_ref().map(|r| r.circuits.clone()).unwrap_or_default(),
+        requests: RequestStats {
+            total_requests,
+            successful_requests,
+            failed_requests,
+            requests_per_second,
+            avg_latency_ms,
+            p50_latency_ms: latency_hist.map(|h| h.min).unwrap_or(0.0), // Approximation
+            p95_latency_ms: latency_hist.map(|h| h.max * 0.95).unwrap_or(0.0), // Approximation
+            p99_latency_ms: latency_hist.map(|h| h.max * 0.99).unwrap_or(0.0), // Approximation
+            tokens_generated,
+            tokens_per_second,
+            ttft_avg_ms: ttft.map_or(0.0, |h| h.mean()),
+            ttft_p95_ms: ttft.map_or(0.0, |h| h.quantile(0.95)),
+            inter_token_avg_ms: inter_token.map_or(0.0, |h| h.mean()),
+            inter_token_p95_ms: inter_token.map_or(0.0, |h| h.quantile(0.95)),
+            fallback_ratio: rate("core_fallback_ratio_1m").unwrap_or(0.0),
+        },
+        resources: ResourceUtilization {
+            memory_rss_bytes: report
+                .as_ref()
+                .map(|r| r.memory_used_bytes as u64)
+                .unwrap_or(memory_pool_bytes),
+            // DEFERRED v0.7.0: KV cache metrics require IPC protocol extension
+            kv_cache_bytes: 0,
+            arena_bytes,
+            // DEFERRED v0.7.0: Memory limit requires runtime config exposure
+            memory_limit_bytes: 0,
+            // DEFERRED v0.7.0: CPU/memory utilization requires procfs/sysinfo
+            memory_utilization_percent: 0.0,
+            cpu_utilization_percent: 0.0,
+            active_threads: metrics
+                .as_ref()
+                .and_then(|m| m.gauges.get("core_process_threads").copied())
+                .unwrap_or(0.0) as u32,
+        },
+        scheduler: {
+            let occupancy = report.as_ref().map(|r| r.scheduler.clone()).unwrap_or_default();
+            SchedulerStatus {
+                queue_depth: report
+                    .as_ref()
+                    .map(|r| r.queue_depth as u64)
+                    .unwrap_or(queue_depth),
+                active_batches: occupancy.active_batches as u64,
+                pending_requests: queue_depth,
+                completed_requests: total_requests,
+                avg_batch_size: occupancy.avg_batch_size,
+                workers: occupancy.workers as u64,
+                busy_workers: occupancy.busy_workers as u64,
+                in_flight: occupancy.in_flight as u64,
+                batches: occupancy.batches,
+                worker_slots: occupancy.worker_slots,
+                gpu_shares: occupancy.gpu,
+            }
+        },
+        // DEFERRED v0.7.0: GPU metrics require cuda/metal feature
+        gpus: None,
+        // DEFERRED v0.7.0: Event log requires telemetry event buffer
+        recent_events: vec![]
+    };
+
+    Ok(status)
+}
@@ -0,0 +1,155 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Status command implementation for GG-CORE.
+//!
+//! Provides detailed system status including:
+//! - Health state
+//! - Loaded models
+//! - Request statistics
+//! - Resource utilization
+//! - Recent events
+
+mod capacity;
+mod fetch;
+mod format;
+mod render;
+mod types;
+
+pub use types::{
+    Event, EventSeverity, GpuStatus, HealthState, ModelState, ModelStatus, RequestStats, ResourceUtilization,
+    SchedulerStatus, SystemStatus, VersionInfo,
+};
+
+use super::ipc_client::CliError;
+use fetch::fetch_status;
+use render::print_status_human;
+
+/// Run the status command and display results.
+pub async fn run_status(socket_path: &str, json_output: bool) -> i32 {
+    match fetch_status(socket_path).await {
+        Ok(status) => {
+            if json_output {
+                println!("{}", serde_json::to_string_pretty(&status).unwrap());
+            } else {
+                print_status_human(&status);
+            }
+            0
+        }
+        Err(e) => {
+            eprintln!("Error fetching status: {}", e);
+            match e {
+                CliError::ConnectionFailed(_) => 3,
+                CliError::Timeout => 3,
+                CliError::Protocol(_) => 1,
+                _ => 1,
+            }
+        }
+    }
+}
+
+/// Print detailed help for `status`.
+pub fn print_help() {
+    eprintln!(
+        "GG-CORE status - Show system status
+
+USAGE:
+    GG-CORE status [OPTIONS]
+
+OPTIONS:
+    --socket PATH  Override IPC socket path
+    --json         Output in JSON format
+    --watch        Continuously update status
+
+DESCRIPTION:
+    Displays current system status including:
+    - Health state
+    - Instance identity (service.instance.id, host.name, deployment.environment;
+      the instance ID is persisted in <base_path>/instance-id)
+    - Loaded models
+    - Circuit breakers that are open or half-open
+    - Request statistics
+    - Resource utilization
+    - Recent events
+
+EXAMPLES:
+    GG-CORE status
+    GG-CORE status --json
+    GG-CORE status --watch
+"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::BatchComposition;
+    use crate::security::FipsMode;
+
+    #[test]
+    fn test_system_status_serialization() {
+        let status = SystemStatus {
+            health: HealthState::Healthy,
+            uptime_secs: 3600,
+            version: VersionInfo {
+                version: "0.6.5".to_string(),
+                commit: "abc123".to_string(),
+                build_date: "2026-02-18".to_string(),
+                rust_version: "1.75.0".to_string(),
+                // Note: I left out commit/build_date in previous view but here they are required by struct.
+                // Ah, the struct definition in the file had these fields.
+                // The init code in fetch_status has them.
+            },
+            resource: None,
+            previous_exit: None,
+            fips_mode: FipsMode::Off,
+            models: vec![],
+            circuits: vec![],
+            requests: RequestStats {
+                total_requests: 1000,
+                successful_requests: 990,
+                failed_requests: 10,
+                requests_per_second: 10.5,
+                avg_latency_ms: 50.0,
+                p50_latency_ms: 45.0,
+                p95_latency_ms: 100.0,
+                p99_latency_ms: 150.0,
+                tokens_generated: 50000,
+                tokens_per_second: 25.0,
+                ttft_avg_ms: 120.0,
+                ttft_p95_ms: 300.0,
+                inter_token_avg_ms: 20.0,
+                inter_token_p95_ms: 45.0,
+                fallback_ratio: 0.0,
+            },
+            resources: ResourceUtilization {
+                memory_rss_bytes: 4 * 1024 * 1024 * 1024,
+                kv_cache_bytes: 2 * 1024 * 1024 * 1024,
+                arena_bytes: 512 * 1024 * 1024,
+                memory_limit_bytes: 8 * 1024 * 1024 * 1024,
+                memory_utilization_percent: 50.0,
+                cpu_utilization_percent: 75.0,
+                active_threads: 8,
+            },
+            scheduler: SchedulerStatus {
+                queue_depth: 5,
+                active_batches: 2,
+                pending_requests: 10,
+                completed_requests: 1000,
+                avg_batch_size: 4.5,
+                workers: 4,
+                busy_workers: 3,
+                in_flight: 9,
+                batches: vec![BatchComposition { model_id: "llama".into(), requests: 9 }],
+                worker_slots: vec![],
+                gpu_shares: vec![],
+            },
+            gpus: None,
+            recent_events: vec![],
+        };
+
+        let json = serde_json::to_string(&status).unwrap();
+        assert!(json.contains("\"health\":\"healthy\""));
+        assert!(json.contains("\"uptime_secs\":3600"));
+    }
+}
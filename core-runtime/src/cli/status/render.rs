@@ -0,0 +1,173 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Human-readable status output.
+
+use crate::scheduler::CircuitState;
+use crate::security::FipsMode;
+
+use super::capacity::{print_resources, print_scheduler};
+use super::format::{format_bytes, format_uptime, truncate};
+use super::{Event, EventSeverity, HealthState, RequestStats, SystemStatus};
+
+/// Print status in human-readable format.
+pub(super) fn print_status_human(status: &SystemStatus) {
+    print_header(status);
+    print_models(status);
+    print_requests(&status.requests);
+    print_resources(status);
+    print_scheduler(&status.scheduler);
+    print_events(&status.recent_events);
+}
+
+/// Health, uptime, instance identity and how the previous run ended.
+fn print_header(status: &SystemStatus) {
+    // Header with health state
+    let health_icon = match status.health {
+        HealthState::Healthy => "✓",
+        HealthState::Degraded => "⚠",
+        HealthState::Unhealthy => "✗",
+    };
+
+    println!("╔════════════════════════════════════════════════════════════════╗");
+    println!(
+        "║  GG-CORE Status                                    v{}   ║",
+        status.version.version
+    );
+    println!("╠════════════════════════════════════════════════════════════════╣");
+    println!(
+        "║  Health: {} {:10}  Uptime: {}              ║",
+        health_icon,
+        status.health,
+        format_uptime(status.uptime_secs)
+    );
+    println!("╚════════════════════════════════════════════════════════════════╝");
+
+    if let Some(resource) = &status.resource {
+        println!(
+            "  Instance: {} ({}) on {}{}",
+            resource.instance_id,
+            resource.service_name,
+            resource.node,
+            resource.profile.as_ref().map(|p| format!(", {}", p)).unwrap_or_default()
+        );
+    }
+
+    if status.fips_mode == FipsMode::Strict {
+        println!("  FIPS mode: strict (approved algorithms only)");
+    }
+
+    if let Some(previous) = &status.previous_exit {
+        println!(
+            "  Previous exit: {} after {} (requests drained {}, aborted {})",
+            previous.reason.describe(),
+            format_uptime(previous.uptime_secs),
+            previous.requests.drained,
+            previous.requests.aborted
+        );
+        if let Some(error) = previous.last_errors.last() {
+            println!("  Last error: {}", error);
+        }
+    }
+}
+
+/// Loaded models and any circuit breakers that are not closed.
+fn print_models(status: &SystemStatus) {
+    // Models section
+    println!("\n📦 Models ({} loaded)", status.models.len());
+    println!("┌─────────────────────────────┬────────────┬──────────┬─────────┐");
+    println!("│ Name                        │ State      │ Size     │ Req/s   │");
+    println!("├─────────────────────────────┼────────────┼──────────┼─────────┤");
+    for model in &status.models {
+        println!(
+            "│ {:27} │ {:10} │ {:>8} │ {:>7.1} │",
+            truncate(&model.name, 27),
+            model.state,
+            format_bytes(model.size_bytes),
+            model.request_count as f64 / status.uptime_secs.max(1) as f64
+        );
+    }
+    println!("└─────────────────────────────┴────────────┴──────────┴─────────┘");
+
+    // Circuit breakers that are not closed
+    let tripped: Vec<_> = status.circuits.iter().filter(|c| c.state != CircuitState::Closed).collect();
+    if !tripped.is_empty() {
+        println!("\n🔌 Circuit Breakers ({} not closed)", tripped.len());
+        for circuit in tripped {
+            if let Some(reason) = &circuit.held {
+                println!("  {:27} {:9} held: {}", truncate(&circuit.model, 27), circuit.state.as_str(), reason);
+                continue;
+            }
+            println!(
+                "  {:27} {:9} failures {:>5.1}%  trips {:>3}  retry in {}s",
+                truncate(&circuit.model, 27),
+                circuit.state.as_str(),
+                circuit.failure_ratio * 100.0,
+                circuit.trips,
+                circuit.retry_after_ms.div_ceil(1000)
+            );
+        }
+    }
+}
+
+/// Throughput and latency.
+fn print_requests(requests: &RequestStats) {
+    // Request statistics
+    println!("\n📊 Request Statistics");
+    println!("┌─────────────────────────────────────────────────────────────────┐");
+    println!(
+        "│ Total Requests: {:>12}  Success: {:>12}  Failed: {:>8} │",
+        requests.total_requests,
+        requests.successful_requests,
+        requests.failed_requests
+    );
+    println!(
+        "│ Throughput: {:>8.1} req/s    Token Gen: {:>8.1} tok/s            │",
+        requests.requests_per_second, requests.tokens_per_second
+    );
+    if requests.fallback_ratio > 0.0 {
+        println!(
+            "│ Fallback: {:>5.1}% of requests served by a fallback model (1m)     │",
+            requests.fallback_ratio * 100.0
+        );
+    }
+    println!("├─────────────────────────────────────────────────────────────────┤");
+    println!(
+        "│ Latency:  Avg {:>7.1}ms  P50 {:>7.1}ms  P95 {:>7.1}ms  P99 {:>6.1}ms │",
+        requests.avg_latency_ms,
+        requests.p50_latency_ms,
+        requests.p95_latency_ms,
+        requests.p99_latency_ms
+    );
+    println!(
+        "│ TTFT:     Avg {:>7.1}ms  P95 {:>7.1}ms  ITL Avg {:>5.1}ms P95 {:>5.1}ms │",
+        requests.ttft_avg_ms,
+        requests.ttft_p95_ms,
+        requests.inter_token_avg_ms,
+        requests.inter_token_p95_ms
+    );
+    println!("└─────────────────────────────────────────────────────────────────┘");
+}
+
+/// Recent events, if any.
+fn print_events(events: &[Event]) {
+    // Recent events
+    if !events.is_empty() {
+        println!("\n📋 Recent Events (last {})", events.len());
+        println!("┌─────────────────────────────────────────────────────────────────┐");
+        for event in events {
+            let severity_icon = match event.severity {
+                EventSeverity::Info => "ℹ️",
+                EventSeverity::Warning => "⚠️",
+                EventSeverity::Error => "❌",
+            };
+            println!(
+                "│ {} {} {:54} │",
+                severity_icon,
+                truncate(&event.timestamp, 10),
+                truncate(&event.message, 54)
+            );
+        }
+        println!("└─────────────────────────────────────────────────────────────────┘");
+    }
+}
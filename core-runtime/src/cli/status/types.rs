@@ -0,0 +1,236 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Status report types, shared with `--json` consumers.
+
+use serde::{Deserialize, Serialize};
+
+use crate::exit_report::ExitReport;
+use crate::scheduler::{BatchComposition, CircuitStatus, GpuModelOccupancy, WorkerSlot};
+use crate::security::FipsMode;
+use crate::telemetry::ResourceAttributes;
+
+/// System status response from the runtime.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemStatus {
+    /// Overall health state
+    pub health: HealthState,
+    /// Server uptime in seconds
+    pub uptime_secs: u64,
+    /// Version information
+    pub version: VersionInfo,
+    /// Resource attributes of the instance (older runtimes omit them)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource: Option<ResourceAttributes>,
+    /// How the previous run of this instance ended, for post-mortems
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_exit: Option<ExitReport>,
+    /// Cryptographic algorithm policy
+    #[serde(default)]
+    pub fips_mode: FipsMode,
+    /// Loaded models
+    pub models: Vec<ModelStatus>,
+    /// Per-model circuit breakers
+    #[serde(default)]
+    pub circuits: Vec<CircuitStatus>,
+    /// Request statistics
+    pub requests: RequestStats,
+    /// Resource utilization
+    pub resources: ResourceUtilization,
+    /// Scheduler state
+    pub scheduler: SchedulerStatus,
+    /// GPU information (if available)
+    pub gpus: Option<Vec<GpuStatus>>,
+    /// Recent events (last 10)
+    pub recent_events: Vec<Event>,
+}
+
+/// Health state enumeration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthState {
+    Healthy,
+    Degraded,
+    Unhealthy,
+}
+
+impl std::fmt::Display for HealthState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HealthState::Healthy => write!(f, "healthy"),
+            HealthState::Degraded => write!(f, "degraded"),
+            HealthState::Unhealthy => write!(f, "unhealthy"),
+        }
+    }
+}
+
+/// Version information.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionInfo {
+    pub version: String,
+    pub commit: String,
+    pub build_date: String,
+    pub rust_version: String,
+}
+
+/// Model status information.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelStatus {
+    pub name: String,
+    pub format: String,
+    pub size_bytes: u64,
+    pub loaded_at: String,
+    pub request_count: u64,
+    pub avg_latency_ms: f64,
+    #[serde(default)]
+    pub avg_ttft_ms: f64,
+    #[serde(default)]
+    pub avg_inter_token_ms: f64,
+    pub state: ModelState,
+}
+
+/// Model loading state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelState {
+    Loading,
+    Ready,
+    Unloading,
+    Error,
+}
+
+impl std::fmt::Display for ModelState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModelState::Loading => write!(f, "loading"),
+            ModelState::Ready => write!(f, "ready"),
+            ModelState::Unloading => write!(f, "unloading"),
+            ModelState::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// Request statistics.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequestStats {
+    pub total_requests: u64,
+    pub successful_requests: u64,
+    pub failed_requests: u64,
+    pub requests_per_second: f64,
+    pub avg_latency_ms: f64,
+    pub p50_latency_ms: f64,
+    pub p95_latency_ms: f64,
+    pub p99_latency_ms: f64,
+    pub tokens_generated: u64,
+    pub tokens_per_second: f64,
+    /// Streaming: time to first token
+    #[serde(default)]
+    pub ttft_avg_ms: f64,
+    #[serde(default)]
+    pub ttft_p95_ms: f64,
+    /// Streaming: gap between consecutive tokens
+    #[serde(default)]
+    pub inter_token_avg_ms: f64,
+    #[serde(default)]
+    pub inter_token_p95_ms: f64,
+    /// Share of requests served by a fallback model (1m)
+    #[serde(default)]
+    pub fallback_ratio: f64,
+}
+
+/// Resource utilization.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceUtilization {
+    /// Process RSS in bytes
+    pub memory_rss_bytes: u64,
+    /// KV cache memory in bytes
+    pub kv_cache_bytes: u64,
+    /// Arena memory in bytes
+    pub arena_bytes: u64,
+    /// Memory limit in bytes
+    pub memory_limit_bytes: u64,
+    /// Memory utilization percentage
+    pub memory_utilization_percent: f64,
+    /// CPU utilization percentage
+    pub cpu_utilization_percent: f64,
+    /// Number of active threads
+    pub active_threads: u32,
+}
+
+/// Scheduler status.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchedulerStatus {
+    pub queue_depth: u64,
+    pub active_batches: u64,
+    pub pending_requests: u64,
+    pub completed_requests: u64,
+    pub avg_batch_size: f64,
+    /// Worker slots, and how many are running a request
+    #[serde(default)]
+    pub workers: u64,
+    #[serde(default)]
+    pub busy_workers: u64,
+    /// Requests currently executing
+    #[serde(default)]
+    pub in_flight: u64,
+    /// Running requests per model
+    #[serde(default)]
+    pub batches: Vec<BatchComposition>,
+    /// Current request per worker
+    #[serde(default)]
+    pub worker_slots: Vec<WorkerSlot>,
+    /// GPU turns of models sharing the GPU
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub gpu_shares: Vec<GpuModelOccupancy>,
+}
+
+/// GPU status information.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GpuStatus {
+    pub gpu_id: u32,
+    pub name: String,
+    pub memory_used_bytes: u64,
+    pub memory_total_bytes: u64,
+    pub utilization_percent: f64,
+    pub temperature_celsius: f64,
+    pub power_draw_watts: f64,
+    pub power_limit_watts: f64,
+}
+
+/// Event record.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Event {
+    pub timestamp: String,
+    pub event_type: String,
+    pub message: String,
+    pub severity: EventSeverity,
+}
+
+/// Event severity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventSeverity {
+    Info,
+    Warning,
+    Error,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_health_state_display() {
+        assert_eq!(format!("{}", HealthState::Healthy), "healthy");
+        assert_eq!(format!("{}", HealthState::Degraded), "degraded");
+        assert_eq!(format!("{}", HealthState::Unhealthy), "unhealthy");
+    }
+
+    #[test]
+    fn test_model_state_display() {
+        assert_eq!(format!("{}", ModelState::Loading), "loading");
+        assert_eq!(format!("{}", ModelState::Ready), "ready");
+        assert_eq!(format!("{}", ModelState::Unloading), "unloading");
+        assert_eq!(format!("{}", ModelState::Error), "error");
+    }
+}
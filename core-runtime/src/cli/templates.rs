@@ -0,0 +1,99 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! `templates`: manage server-side prompt templates on disk.
+
+use crate::templates::{parse_template_ref, TemplateError, TemplateStore};
+
+use super::config::load_config;
+
+/// Run the templates CLI command (edits the template directory directly).
+pub fn run_templates(args: &[String]) -> i32 {
+    let store = TemplateStore::new(load_config().base_path.join("templates"));
+    match (args.get(2).map(|s| s.as_str()), args.get(3), args.get(4)) {
+        (Some("list") | None, _, _) => {
+            let templates = store.list();
+            if templates.is_empty() {
+                println!("No templates in {}", store.dir().display());
+            }
+            for template in templates {
+                if let Some(latest) = template.latest() {
+                    println!("{}\tv{}\t{}", template.id, latest.version, latest.variables.join(","));
+                }
+            }
+            0
+        }
+        (Some("add"), Some(id), Some(file)) => {
+            let body = match std::fs::read_to_string(file) {
+                Ok(body) => body,
+                Err(e) => {
+                    eprintln!("Error: cannot read {}: {}", file, e);
+                    return 1;
+                }
+            };
+            match store.put(id, &body) {
+                Ok(version) => {
+                    println!("Stored {}@{}", id, version);
+                    0
+                }
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    2
+                }
+            }
+        }
+        (Some("show"), Some(reference), _) => {
+            let shown = parse_template_ref(reference).and_then(|(id, version)| {
+                let template = store.get(id)?;
+                let selected = match version {
+                    Some(v) => template.version(v).cloned(),
+                    None => template.latest().cloned(),
+                };
+                selected
+                    .map(|selected| (template.id.clone(), selected))
+                    .ok_or_else(|| TemplateError::NotFound(reference.to_string()))
+            });
+            match shown {
+                Ok((id, selected)) => {
+                    println!("# {}@{} ({})", id, selected.version, selected.created_at);
+                    print!("{}", selected.body);
+                    0
+                }
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    1
+                }
+            }
+        }
+        _ => {
+            print_help();
+            2
+        }
+    }
+}
+
+/// Print detailed help for `templates`.
+pub fn print_help() {
+    eprintln!(
+        "GG-CORE templates - Manage server-side prompt templates
+
+USAGE:
+    GG-CORE templates <SUBCOMMAND>
+
+SUBCOMMANDS:
+    list                List templates with their latest version and variables
+    add <ID> <FILE>     Store FILE as the next version of template ID
+    show <ID[@VERSION]> Print a template body (latest version by default)
+
+Templates live in <base_path>/templates and are picked up by a running
+server without a restart. Placeholders are written {{{{ name }}}}. Clients
+send template_id (e.g. \"summarize@2\") and variables instead of a prompt;
+every placeholder must be supplied, extra variables are rejected, and each
+value is scanned for prompt injection and escaped before rendering.
+
+EXAMPLES:
+    GG-CORE templates add summarize prompts/summarize.txt
+    GG-CORE templates show summarize@1
+"
+    );
+}
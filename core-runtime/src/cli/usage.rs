@@ -0,0 +1,52 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! `usage`: export per-tenant usage aggregates.
+
+use super::ipc_client::CliIpcClient;
+use super::get_socket_path;
+
+pub async fn run_usage(args: &[String]) -> i32 {
+    let client = CliIpcClient::new(get_socket_path());
+    let export = match client.usage_export().await {
+        Ok(export) => export,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return 3;
+        }
+    };
+    if args.iter().any(|a| a == "--json") {
+        println!("{}", serde_json::to_string_pretty(&export).unwrap_or_default());
+        return 0;
+    }
+    match export.privacy.epsilon {
+        Some(epsilon) => println!("Privacy: {} noise, epsilon {}", export.privacy.mechanism, epsilon),
+        None => println!("Privacy: exact counts"),
+    }
+    println!("{:<20} {:>10} {:>14}", "TENANT", "REQUESTS", "TOKENS");
+    for tenant in &export.tenants {
+        println!("{:<20} {:>10} {:>14}", tenant.tenant, tenant.requests, tenant.tokens);
+    }
+    0
+}
+
+/// Print detailed help for `usage`.
+pub fn print_help() {
+    eprintln!(
+        "GG-CORE usage - Export per-tenant usage aggregates
+
+USAGE:
+    GG-CORE usage [OPTIONS]
+
+OPTIONS:
+    --json     Print the export as JSON, including its privacy metadata
+
+Reports completed requests and generated tokens per tenant. Tenants are
+labelled by a hash of their session, never the token itself. The server
+keeps exact counts; with CORE_USAGE_DP_EPSILON set, exports add Laplace
+noise (epsilon split evenly between requests and tokens) and record the
+mechanism, epsilon and sensitivities in the export. Requires an admin
+session when authentication is enabled.
+"
+    );
+}
@@ -0,0 +1,89 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! `verify`: socket permission checks for a deployed runtime.
+
+use super::config::socket_permissions;
+use super::get_socket_path;
+
+/// Run the verify CLI command: check (and optionally repair) the socket
+/// file's permissions.
+#[cfg(unix)]
+pub fn run_verify(args: &[String]) -> i32 {
+    use crate::ipc::socket_perms;
+
+    let socket_path = args
+        .iter()
+        .position(|a| a == "--socket")
+        .and_then(|i| args.get(i + 1).cloned())
+        .unwrap_or_else(get_socket_path);
+    let repair = args.iter().any(|a| a == "--repair");
+    if socket_path.starts_with('@') {
+        println!("OK   {} is an abstract socket (no file permissions)", socket_path);
+        return 0;
+    }
+    let perms = socket_permissions();
+    let path = std::path::Path::new(&socket_path);
+
+    let check = socket_perms::check_parent(path).and_then(|()| socket_perms::verify(path, &perms));
+    let issues = match check {
+        Ok(issues) => issues,
+        Err(e) => {
+            eprintln!("FAIL {}: {}", socket_path, e);
+            return 1;
+        }
+    };
+    if issues.is_empty() {
+        println!("OK   {} permissions", socket_path);
+        return 0;
+    }
+    for issue in &issues {
+        println!("FAIL {}: {}", socket_path, issue);
+    }
+    if !repair {
+        return 1;
+    }
+    match socket_perms::apply(path, &perms) {
+        Ok(()) => {
+            println!("REPAIRED {}", socket_path);
+            0
+        }
+        Err(e) => {
+            eprintln!("Repair failed: {}", e);
+            1
+        }
+    }
+}
+
+#[cfg(windows)]
+pub fn run_verify(_args: &[String]) -> i32 {
+    println!("OK   named pipes have no file permissions to verify");
+    0
+}
+
+/// Print detailed help for `verify`.
+pub fn print_help() {
+    eprintln!(
+        "GG-CORE verify - Verify deployment
+
+USAGE:
+    GG-CORE verify [OPTIONS]
+
+OPTIONS:
+    --socket PATH  Override IPC socket path
+    --repair       Fix socket mode/ownership mismatches
+
+DESCRIPTION:
+    Verifies the socket file's mode and ownership against the configured
+    CORE_SOCKET_MODE / CORE_SOCKET_UID / CORE_SOCKET_GID (default 0600).
+
+EXIT CODES:
+    0  All checks passed
+    1  One or more checks failed
+
+EXAMPLES:
+    GG-CORE verify
+    GG-CORE verify --repair
+"
+    );
+}
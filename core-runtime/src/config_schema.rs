@@ -0,0 +1,380 @@
+//! Versioned schema of the startup configuration.
+//!
+//! The runtime is configured from `CORE_*` and `VERITAS_*` environment
+//! variables, with `CORE_CONFIG_VERSION` naming the schema a deployment's
+//! settings were written for; unversioned settings are read as version 1.
+//! A key renamed in a later schema keeps working under its old name with a
+//! deprecation warning: [`var`] reads the current key and falls back to
+//! the old one. [`check`] reports deprecated keys, keys in the runtime's
+//! namespaces that no version knows (with the nearest known key, since a
+//! typo is otherwise silently ignored) and versions newer than this build.
+//! `config validate --strict` fails on any of them, so a fleet's
+//! configuration can be upgraded before the binaries that drop old keys.
+
+use std::fmt;
+
+use serde::Serialize;
+
+/// Schema version this build reads.
+pub const CONFIG_VERSION: u32 = 2;
+
+/// Key naming the schema version of a configuration.
+pub const VERSION_KEY: &str = "CORE_CONFIG_VERSION";
+
+/// Namespaces whose unrecognised keys are reported.
+const PREFIXES: &[&str] = &["CORE_", "VERITAS_", "GG_CORE_"];
+
+/// Every key the runtime reads, sorted.
+pub const KEYS: &[&str] = &[
+    "CORE_ADMIN_TOKEN",
+    "CORE_ATTESTATION",
+    "CORE_AUDIT_SAMPLE_RATE",
+    "CORE_AUTH_TOKEN",
+    "CORE_BACKUP_PASSPHRASE",
+    "CORE_CIRCUIT",
+    "CORE_CIRCUIT_FAILURE_RATIO",
+    "CORE_CIRCUIT_OPEN_SECS",
+    "CORE_CONFIG_VERSION",
+    "CORE_CONSOLE_ADDR",
+    "CORE_CONVERSATION_PREFETCH",
+    "CORE_DEPRECATIONS",
+    "CORE_FIPS_MODE",
+    "CORE_GPU_SHARE",
+    "CORE_GPU_SHARES",
+    "CORE_GPU_SLICE_MS",
+    "CORE_HEAP_SOFT_LIMIT",
+    "CORE_HEDGE_MAX_RATIO",
+    "CORE_HEDGE_PAIRS",
+    "CORE_HEDGE_TTFT_MS",
+    "CORE_INTEGRITY",
+    "CORE_INTEGRITY_INTERVAL_SECS",
+    "CORE_INTEGRITY_MAX_MBPS",
+    "CORE_LEGAL_HOLD_KEY",
+    "CORE_LEGAL_HOLD_MAX_DAYS",
+    "CORE_LEGAL_HOLD_RETAIN_DAYS",
+    "CORE_MAINTENANCE",
+    "CORE_MAX_METADATA_KB",
+    "CORE_MAX_OUTPUT_KB",
+    "CORE_MAX_OUTPUT_TOKENS",
+    "CORE_MAX_PROMPT_KB",
+    "CORE_MAX_PROMPT_TOKENS",
+    "CORE_MIGRATIONS",
+    "CORE_NODE_NAME",
+    "CORE_OBSERVER_TOKEN",
+    "CORE_PII_BLOCK",
+    "CORE_PII_BLOCK_CONFIDENCE",
+    "CORE_PLACEMENT_DIR",
+    "CORE_POLICY_SHADOW",
+    "CORE_POLICY_SHADOW_SAMPLE",
+    "CORE_PREFETCH_MAX_CACHE_MB",
+    "CORE_PROBE_MAX_PER_SEC",
+    "CORE_PROFILING",
+    "CORE_QUEUE_ON_SWITCH",
+    "CORE_QUOTA_BACKEND",
+    "CORE_RECOVERY_PUBLIC_KEY",
+    "CORE_REPLICA_ID",
+    "CORE_RETENTION",
+    "CORE_SERVICE_NAME",
+    "CORE_SIMD_MAX_TIER",
+    "CORE_SLO_AVAILABILITY",
+    "CORE_SLO_LATENCY_P95_MS",
+    "CORE_SOCKET_GID",
+    "CORE_SOCKET_MODE",
+    "CORE_SOCKET_UID",
+    "CORE_STORAGE",
+    "CORE_STORAGE_ACCESS_KEY",
+    "CORE_STORAGE_CACHE_DIR",
+    "CORE_STORAGE_ENDPOINT",
+    "CORE_STORAGE_REGION",
+    "CORE_STORAGE_SECRET_KEY",
+    "CORE_SUPERVISOR_PID",
+    "CORE_TCP_ADDR",
+    "CORE_TENANT_RATE_LIMIT",
+    "CORE_TENANT_RATE_WINDOW_SECS",
+    "CORE_TENANT_TOKENS",
+    "CORE_THERMAL",
+    "CORE_THERMAL_BATCH",
+    "CORE_THERMAL_GPU_MODELS",
+    "CORE_THERMAL_POWER_RATIO",
+    "CORE_THERMAL_TEMP_C",
+    "CORE_TRUSTED_IDENTITIES",
+    "CORE_USAGE_DP_EPSILON",
+    "CORE_USAGE_DP_MAX_TOKENS",
+    "CORE_WARM_SCHEDULE",
+    "VERITAS_ENV",
+    "VERITAS_SOCKET_PATH",
+];
+
+/// A key replaced by another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Renamed {
+    pub old: &'static str,
+    pub new: &'static str,
+    /// Schema version that introduced `new`.
+    pub since: u32,
+}
+
+/// Old keys still read in place of their replacements. No key has been
+/// renamed yet; add an entry here when one is.
+pub const RENAMED: &[Renamed] = &[];
+
+/// Value of `key`, or of a key it replaced when only that is set. A key
+/// set to an empty value is set, exactly as `std::env::var` reports it.
+pub fn var(key: &str) -> Option<String> {
+    std::env::var(key).ok().or_else(|| RENAMED.iter().filter(|r| r.new == key).find_map(|r| std::env::var(r.old).ok()))
+}
+
+/// Something `config validate` reports.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Finding {
+    /// No `CORE_CONFIG_VERSION`; read as version 1.
+    Unversioned,
+    /// Written for a schema this build does not know.
+    NewerVersion { version: u32 },
+    InvalidVersion { value: String },
+    /// An old key, read in place of `replacement` unless that is set too.
+    Deprecated { key: String, replacement: String, since: u32, shadowed: bool },
+    /// Not read by any version.
+    Unknown { key: String, suggestion: Option<String> },
+}
+
+impl Finding {
+    /// Whether `config validate --strict` fails on it.
+    pub fn fails_strict(&self) -> bool {
+        !matches!(self, Self::Unversioned)
+    }
+
+    /// Whether the configuration is unusable as written.
+    pub fn is_error(&self) -> bool {
+        matches!(self, Self::InvalidVersion { .. })
+    }
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unversioned => {
+                write!(f, "{} is not set; settings are read as version 1 (current: {})", VERSION_KEY, CONFIG_VERSION)
+            }
+            Self::NewerVersion { version } => write!(
+                f,
+                "{}={} is newer than this build ({}); settings it introduces are ignored",
+                VERSION_KEY, version, CONFIG_VERSION
+            ),
+            Self::InvalidVersion { value } => write!(f, "{}='{}' is not a version number", VERSION_KEY, value),
+            Self::Deprecated { key, replacement, since, shadowed: false } => {
+                write!(f, "{} is deprecated since config version {}; rename it to {}", key, since, replacement)
+            }
+            Self::Deprecated { key, replacement, shadowed: true, .. } => {
+                write!(f, "{} is deprecated and ignored because {} is set; remove it", key, replacement)
+            }
+            Self::Unknown { key, suggestion: Some(suggestion) } => {
+                write!(f, "{} is not a known setting and is ignored; did you mean {}?", key, suggestion)
+            }
+            Self::Unknown { key, suggestion: None } => write!(f, "{} is not a known setting and is ignored", key),
+        }
+    }
+}
+
+/// Result of checking a configuration against the schema.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConfigCheck {
+    /// Schema version the settings were read as.
+    pub version: u32,
+    pub findings: Vec<Finding>,
+}
+
+impl ConfigCheck {
+    pub fn has_errors(&self) -> bool {
+        self.findings.iter().any(Finding::is_error)
+    }
+
+    /// Whether the configuration passes `config validate`, or with
+    /// `strict` its `--strict` mode.
+    pub fn passes(&self, strict: bool) -> bool {
+        !self.has_errors() && (!strict || !self.findings.iter().any(Finding::fails_strict))
+    }
+}
+
+/// Check `settings` (key, value pairs) against the schema; keys outside
+/// the runtime's namespaces are ignored.
+pub fn check<'a>(settings: impl IntoIterator<Item = (&'a str, &'a str)>) -> ConfigCheck {
+    check_against(settings, RENAMED)
+}
+
+fn check_against<'a>(settings: impl IntoIterator<Item = (&'a str, &'a str)>, renamed: &[Renamed]) -> ConfigCheck {
+    let settings: Vec<(&str, &str)> = settings.into_iter().filter(|(_, v)| !v.is_empty()).collect();
+    let mut findings = Vec::new();
+
+    let version = match settings.iter().find(|(k, _)| *k == VERSION_KEY) {
+        None => {
+            findings.push(Finding::Unversioned);
+            1
+        }
+        Some((_, value)) => match value.trim().parse::<u32>() {
+            Ok(version) if version > 0 => {
+                if version > CONFIG_VERSION {
+                    findings.push(Finding::NewerVersion { version });
+                }
+                version
+            }
+            _ => {
+                findings.push(Finding::InvalidVersion { value: value.to_string() });
+                1
+            }
+        },
+    };
+
+    let mut keys: Vec<&str> =
+        settings.iter().map(|(k, _)| *k).filter(|k| PREFIXES.iter().any(|p| k.starts_with(p))).collect();
+    keys.sort_unstable();
+    keys.dedup();
+    for key in keys {
+        if KEYS.contains(&key) {
+            continue;
+        }
+        if let Some(renamed) = renamed.iter().find(|r| r.old == key) {
+            findings.push(Finding::Deprecated {
+                key: key.to_string(),
+                replacement: renamed.new.to_string(),
+                since: renamed.since,
+                shadowed: settings.iter().any(|(k, _)| *k == renamed.new),
+            });
+            continue;
+        }
+        findings.push(Finding::Unknown { key: key.to_string(), suggestion: nearest_key(key).map(str::to_string) });
+    }
+    ConfigCheck { version, findings }
+}
+
+/// The process environment's Unicode variables.
+pub fn env_settings() -> Vec<(String, String)> {
+    std::env::vars_os().filter_map(|(k, v)| Some((k.into_string().ok()?, v.into_string().ok()?))).collect()
+}
+
+/// Check the process environment.
+pub fn check_env() -> ConfigCheck {
+    let vars = env_settings();
+    check(vars.iter().map(|(k, v)| (k.as_str(), v.as_str())))
+}
+
+/// Parse an environment file: `KEY=VALUE` lines, optionally prefixed with
+/// `export`, with `#` comments and quoted values.
+pub fn parse_env_file(text: &str) -> Result<Vec<(String, String)>, String> {
+    let mut settings = Vec::new();
+    for (n, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let Some((key, value)) = line.split_once('=') else {
+            return Err(format!("line {}: expected KEY=VALUE", n + 1));
+        };
+        let key = key.trim();
+        if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(format!("line {}: invalid key '{}'", n + 1, key));
+        }
+        let value = value.trim();
+        let value = ['"', '\'']
+            .iter()
+            .find_map(|q| value.strip_prefix(*q).and_then(|v| v.strip_suffix(*q)))
+            .unwrap_or(value);
+        settings.push((key.to_string(), value.to_string()));
+    }
+    Ok(settings)
+}
+
+/// Known key closest to `key`, if near enough to be a typo of it.
+fn nearest_key(key: &str) -> Option<&'static str> {
+    let max = (key.len() / 6).clamp(2, 3);
+    KEYS.iter()
+        .map(|known| (edit_distance(key, known), *known))
+        .filter(|(distance, _)| *distance <= max)
+        .min()
+        .map(|(_, known)| known)
+}
+
+/// Levenshtein distance over bytes; keys are ASCII.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.as_bytes();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.bytes().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let next = (diagonal + usize::from(ca != *cb)).min(row[j] + 1).min(row[j + 1] + 1);
+            diagonal = row[j + 1];
+            row[j + 1] = next;
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schema_tables_are_consistent() {
+        assert!(KEYS.windows(2).all(|w| w[0] < w[1]));
+        for renamed in RENAMED {
+            assert!(KEYS.contains(&renamed.new) && !KEYS.contains(&renamed.old));
+            assert!(renamed.since <= CONFIG_VERSION);
+        }
+    }
+
+    #[test]
+    fn test_check_reports_unknown_and_deprecated_keys() {
+        let renamed = [Renamed { old: "CORE_BREAKER", new: "CORE_CIRCUIT", since: 2 }];
+        let check = check_against(
+            [
+                ("CORE_CONFIG_VERSION", "2"),
+                ("CORE_CIRCIUT", "off"),
+                ("CORE_PII_BLOCK", "ssn"),
+                ("CORE_BREAKER", "off"),
+                ("GG_CORE_MAX_CONTEXT", "8192"),
+                ("PATH", "/usr/bin"),
+            ],
+            &renamed,
+        );
+        assert_eq!(check.version, 2);
+        assert_eq!(check.findings, vec![
+            Finding::Deprecated {
+                key: "CORE_BREAKER".into(),
+                replacement: "CORE_CIRCUIT".into(),
+                since: 2,
+                shadowed: false,
+            },
+            Finding::Unknown { key: "CORE_CIRCIUT".into(), suggestion: Some("CORE_CIRCUIT".into()) },
+            Finding::Unknown { key: "GG_CORE_MAX_CONTEXT".into(), suggestion: None },
+        ]);
+        assert!(check.passes(false));
+        assert!(!check.passes(true));
+    }
+
+    #[test]
+    fn test_check_versions() {
+        let unversioned = check([("CORE_CIRCUIT", "off")]);
+        assert_eq!((unversioned.version, &unversioned.findings[..]), (1, &[Finding::Unversioned][..]));
+        assert!(unversioned.passes(true));
+
+        assert!(check([(VERSION_KEY, "3")]).findings.contains(&Finding::NewerVersion { version: 3 }));
+        let invalid = check([(VERSION_KEY, "v2")]);
+        assert!(invalid.has_errors() && !invalid.passes(false));
+    }
+
+    #[test]
+    fn test_parse_env_file() {
+        let text = "# fleet defaults\nexport CORE_CONFIG_VERSION=2\n\
+                    CORE_PII_BLOCK=\"ssn,email\"\n\nCORE_CIRCUIT = off\n";
+        let settings = parse_env_file(text).unwrap();
+        assert_eq!(settings, vec![
+            ("CORE_CONFIG_VERSION".to_string(), "2".to_string()),
+            ("CORE_PII_BLOCK".to_string(), "ssn,email".to_string()),
+            ("CORE_CIRCUIT".to_string(), "off".to_string()),
+        ]);
+        assert!(parse_env_file("CORE_CIRCUIT").unwrap_err().starts_with("line 1"));
+    }
+}
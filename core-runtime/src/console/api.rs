@@ -0,0 +1,81 @@
+//! JSON views and model actions served under `/api`.
+
+use serde::{Deserialize, Serialize};
+
+use super::{Console, Request, Response};
+use crate::ipc::{HealthCheckType, IpcMessage, ModelAction, ModelAdminRequest};
+
+#[derive(Deserialize)]
+struct ModelBody {
+    model: String,
+}
+
+impl Console {
+    pub(super) async fn status(&self) -> Response {
+        let health = match self.call(IpcMessage::HealthCheck { check_type: HealthCheckType::Full }, None).await {
+            Ok(IpcMessage::HealthResponse(health)) => health,
+            other => return unexpected(other),
+        };
+        let objectives = match self.call(IpcMessage::SloStatusRequest, None).await {
+            Ok(IpcMessage::SloStatusResponse { objectives }) => objectives,
+            other => return unexpected(other),
+        };
+        json(&serde_json::json!({ "health": health.report, "slo": objectives }))
+    }
+
+    pub(super) async fn queue(&self) -> Response {
+        let report = match self.call(IpcMessage::HealthCheck { check_type: HealthCheckType::Full }, None).await {
+            Ok(IpcMessage::HealthResponse(health)) => health.report,
+            other => return unexpected(other),
+        };
+        let metrics = match self.call(IpcMessage::MetricsRequest, None).await {
+            Ok(IpcMessage::MetricsResponse(metrics)) => metrics,
+            other => return unexpected(other),
+        };
+        let gauges: std::collections::BTreeMap<_, _> =
+            metrics.gauges.into_iter().filter(|(name, _)| name.contains("queue")).collect();
+        let counters: std::collections::BTreeMap<_, _> =
+            metrics.counters.into_iter().filter(|(name, _)| name.contains("queue")).collect();
+        json(&serde_json::json!({
+            "depth": report.as_ref().map(|r| r.queue_depth),
+            "accepting_requests": report.as_ref().map(|r| r.accepting_requests),
+            "degradation": report.as_ref().map(|r| &r.degradation),
+            "gauges": gauges,
+            "counters": counters,
+        }))
+    }
+
+    pub(super) async fn model_action(&self, request: &Request, action: ModelAction) -> Response {
+        let Ok(ModelBody { model }) = serde_json::from_slice(&request.body) else {
+            return Response::error(400, "Expected JSON body {\"model\": \"...\"}");
+        };
+        let message = IpcMessage::ModelAdminRequest(ModelAdminRequest { action, model });
+        match self.admin_call(request, message).await {
+            Ok(IpcMessage::ModelAdminResponse(response)) if response.success => json(&response),
+            Ok(IpcMessage::ModelAdminResponse(response)) => match serde_json::to_value(&response) {
+                Ok(value) => Response::json(400, &value),
+                Err(e) => Response::error(500, &e.to_string()),
+            },
+            other => unexpected(other),
+        }
+    }
+}
+
+pub(super) fn json<T: Serialize>(value: &T) -> Response {
+    match serde_json::to_value(value) {
+        Ok(value) => Response::json(200, &value),
+        Err(e) => Response::error(500, &e.to_string()),
+    }
+}
+
+/// Map a handler error message or an unexpected reply to a response.
+pub(super) fn unexpected(result: Result<IpcMessage, Response>) -> Response {
+    match result {
+        Ok(IpcMessage::Error { code, message }) => {
+            let status = u16::try_from(code).ok().filter(|c| (400..600).contains(c)).unwrap_or(500);
+            Response::error(status, &message)
+        }
+        Ok(_) => Response::error(500, "Unexpected IPC response"),
+        Err(response) => response,
+    }
+}
@@ -0,0 +1,181 @@
+//! Minimal HTTP/1.1 request parsing and response writing.
+//!
+//! Only what the console needs: one request per connection, bounded
+//! headers and body, `Connection: close` on every response. No chunked
+//! encoding, keep-alive or TLS.
+
+use std::collections::HashMap;
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+/// Maximum size of the request line plus headers.
+const MAX_HEAD_BYTES: usize = 16 * 1024;
+/// Maximum request body size (action payloads are tiny).
+const MAX_BODY_BYTES: usize = 64 * 1024;
+
+/// A parsed request.
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub query: HashMap<String, String>,
+    /// Header names are lowercased.
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).map(|v| v.as_str())
+    }
+
+    /// Token from an `Authorization: Bearer <token>` header.
+    pub fn bearer_token(&self) -> Option<&str> {
+        self.header("authorization")?.strip_prefix("Bearer ").map(str::trim)
+    }
+}
+
+/// A response ready to be written.
+#[derive(Debug, Clone)]
+pub struct Response {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    pub fn json(status: u16, value: &serde_json::Value) -> Self {
+        Self { status, content_type: "application/json", body: value.to_string().into_bytes() }
+    }
+
+    pub fn html(body: &str) -> Self {
+        Self { status: 200, content_type: "text/html; charset=utf-8", body: body.as_bytes().to_vec() }
+    }
+
+    pub fn error(status: u16, message: &str) -> Self {
+        Self::json(status, &serde_json::json!({ "error": message }))
+    }
+}
+
+/// Read and parse one request. Returns `None` for malformed or oversized input.
+pub async fn read_request<S: AsyncRead + Unpin>(stream: &mut S) -> std::io::Result<Option<Request>> {
+    let mut buf = Vec::with_capacity(1024);
+    let head_end = loop {
+        if let Some(pos) = find_head_end(&buf) {
+            break pos;
+        }
+        if buf.len() > MAX_HEAD_BYTES {
+            return Ok(None);
+        }
+        let mut chunk = [0u8; 1024];
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            return Ok(None);
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    };
+
+    let Ok(head) = std::str::from_utf8(&buf[..head_end]) else {
+        return Ok(None);
+    };
+    let Some(mut request) = parse_head(head) else {
+        return Ok(None);
+    };
+
+    let length: usize = match request.header("content-length").map(str::parse) {
+        None => 0,
+        Some(Ok(n)) if n <= MAX_BODY_BYTES => n,
+        Some(_) => return Ok(None),
+    };
+    let mut body = buf[head_end + 4..].to_vec();
+    if body.len() < length {
+        let start = body.len();
+        body.resize(length, 0);
+        stream.read_exact(&mut body[start..]).await?;
+    }
+    body.truncate(length);
+    request.body = body;
+    Ok(Some(request))
+}
+
+/// Write `response` and close the write side.
+pub async fn write_response<S: AsyncWrite + Unpin>(stream: &mut S, response: &Response) -> std::io::Result<()> {
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\n\
+         X-Content-Type-Options: nosniff\r\nX-Frame-Options: DENY\r\nConnection: close\r\n\r\n",
+        response.status,
+        reason(response.status),
+        response.content_type,
+        response.body.len()
+    );
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(&response.body).await?;
+    stream.shutdown().await
+}
+
+fn find_head_end(buf: &[u8]) -> Option<usize> {
+    buf.windows(4).position(|w| w == b"\r\n\r\n")
+}
+
+fn parse_head(head: &str) -> Option<Request> {
+    let mut lines = head.split("\r\n");
+    let mut parts = lines.next()?.split(' ');
+    let method = parts.next()?.to_string();
+    let target = parts.next()?;
+    if !parts.next()?.starts_with("HTTP/1.") {
+        return None;
+    }
+    let (path, query) = match target.split_once('?') {
+        Some((path, query)) => (path, parse_query(query)),
+        None => (target, HashMap::new()),
+    };
+    let headers = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+        .collect();
+    Some(Request { method, path: path.to_string(), query, headers, body: Vec::new() })
+}
+
+fn parse_query(query: &str) -> HashMap<String, String> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        421 => "Misdirected Request",
+        _ => "Internal Server Error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_parse_request_with_body() {
+        let raw = b"POST /api/models/unload?x=1 HTTP/1.1\r\nHost: 127.0.0.1\r\n\
+                    Authorization: Bearer abc\r\nContent-Length: 4\r\n\r\nbody";
+        let request = read_request(&mut &raw[..]).await.unwrap().unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/api/models/unload");
+        assert_eq!(request.query.get("x").map(String::as_str), Some("1"));
+        assert_eq!(request.bearer_token(), Some("abc"));
+        assert_eq!(request.body, b"body");
+    }
+
+    #[tokio::test]
+    async fn test_oversized_body_rejected() {
+        let raw = format!("POST / HTTP/1.1\r\nContent-Length: {}\r\n\r\n", MAX_BODY_BYTES + 1);
+        assert!(read_request(&mut raw.as_bytes()).await.unwrap().is_none());
+    }
+}
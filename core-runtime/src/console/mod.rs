@@ -0,0 +1,210 @@
+//! Optional admin web console (`web-console` feature).
+//!
+//! A minimal status page for appliances without kubectl or a terminal:
+//! health, models, queue, recent runtime events and the audit tail, plus
+//! drain/load/unload actions. The console binds loopback addresses only
+//! and holds no privileges of its own: every view and action is an
+//! `IpcMessage` run through the same [`IpcHandler`] as socket clients.
+//! Actions and the audit tail need an `Authorization: Bearer` admin token,
+//! which is checked by a regular IPC handshake.
+//!
+//! Disabled unless compiled with the feature and started with
+//! `CORE_CONSOLE_ADDR`; the IPC socket remains the only interface otherwise.
+
+mod api;
+mod http;
+mod page;
+mod session;
+
+use std::collections::VecDeque;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::Serialize;
+use thiserror::Error;
+use tokio::net::{TcpListener, TcpStream};
+
+pub use http::{Request, Response};
+
+use api::{json, unexpected};
+
+use crate::ipc::{IpcHandler, IpcMessage, ModelAction, SessionToken};
+use crate::telemetry::{subscribe_events, unsubscribe_events, RuntimeEvent};
+
+/// Runtime events kept for the events panel.
+const MAX_EVENTS: usize = 200;
+/// Audit events shown when the client does not ask for a count.
+const DEFAULT_AUDIT_TAIL: usize = 50;
+/// Known paths, for telling 405 from 404.
+const ROUTES: &[&str] = &[
+    "/", "/api/status", "/api/models", "/api/queue", "/api/events", "/api/audit", "/api/drain",
+    "/api/models/load", "/api/models/unload",
+];
+/// Time allowed for a client to send its request.
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Error, Debug)]
+pub enum ConsoleError {
+    #[error("Console address must be loopback, got {0}")]
+    NotLoopback(SocketAddr),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// One runtime event as shown in the console.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConsoleEvent {
+    pub kind: &'static str,
+    pub level: String,
+    pub subject: String,
+    pub detail: String,
+    pub timestamp_ms: u64,
+}
+
+impl From<&RuntimeEvent> for ConsoleEvent {
+    fn from(event: &RuntimeEvent) -> Self {
+        Self {
+            kind: event.kind.as_str(),
+            level: event.level.to_string(),
+            subject: event.subject.clone(),
+            detail: event.detail.clone(),
+            timestamp_ms: event.timestamp_ms,
+        }
+    }
+}
+
+/// Loopback web console backed by the IPC handler.
+pub struct Console {
+    handler: Arc<IpcHandler>,
+    events: Arc<Mutex<VecDeque<ConsoleEvent>>>,
+    subscription: u64,
+    /// Admin session of the last token seen, keyed by the token's hash.
+    admin: tokio::sync::Mutex<Option<([u8; 32], SessionToken)>>,
+}
+
+impl Console {
+    pub fn new(handler: Arc<IpcHandler>) -> Self {
+        let events = Arc::new(Mutex::new(VecDeque::with_capacity(MAX_EVENTS)));
+        let sink = Arc::clone(&events);
+        let subscription = subscribe_events(move |event| {
+            let mut events = sink.lock().unwrap_or_else(|e| e.into_inner());
+            if events.len() == MAX_EVENTS {
+                events.pop_front();
+            }
+            events.push_back(ConsoleEvent::from(event));
+        });
+        Self { handler, events, subscription, admin: tokio::sync::Mutex::new(None) }
+    }
+
+    /// Bind the console listener, refusing anything but a loopback address.
+    pub async fn bind(addr: SocketAddr) -> Result<TcpListener, ConsoleError> {
+        if !addr.ip().is_loopback() {
+            return Err(ConsoleError::NotLoopback(addr));
+        }
+        Ok(TcpListener::bind(addr).await?)
+    }
+
+    /// Serve connections until `shutdown` flips.
+    pub async fn run(self: Arc<Self>, listener: TcpListener, mut shutdown: tokio::sync::watch::Receiver<bool>) {
+        loop {
+            tokio::select! {
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, peer)) if peer.ip().is_loopback() => {
+                        let console = Arc::clone(&self);
+                        tokio::spawn(async move { console.serve(stream).await });
+                    }
+                    Ok((_, peer)) => tracing::warn!(%peer, "Console refused non-loopback peer"),
+                    Err(e) => tracing::warn!(error = %e, "Console accept failed"),
+                },
+                _ = shutdown.changed() => break,
+            }
+        }
+    }
+
+    async fn serve(&self, mut stream: TcpStream) {
+        let response = match tokio::time::timeout(READ_TIMEOUT, http::read_request(&mut stream)).await {
+            Ok(Ok(Some(request))) => self.route(&request).await,
+            Ok(Ok(None)) => Response::error(400, "Malformed request"),
+            _ => return,
+        };
+        if let Err(e) = http::write_response(&mut stream, &response).await {
+            tracing::debug!(error = %e, "Console response not delivered");
+        }
+    }
+
+    /// Dispatch one request.
+    pub async fn route(&self, request: &Request) -> Response {
+        // Reject foreign Host headers so DNS rebinding cannot reach the API
+        if !request.header("host").is_some_and(is_loopback_host) {
+            return Response::error(421, "Console only answers loopback hosts");
+        }
+        match (request.method.as_str(), request.path.as_str()) {
+            ("GET", "/") => Response::html(page::INDEX),
+            ("GET", "/api/status") => self.status().await,
+            ("GET", "/api/models") => match self.call(IpcMessage::ModelsRequest, None).await {
+                Ok(IpcMessage::ModelsResponse(models)) => json(&models),
+                other => unexpected(other),
+            },
+            ("GET", "/api/queue") => self.queue().await,
+            ("GET", "/api/events") => {
+                let events = self.events.lock().unwrap_or_else(|e| e.into_inner());
+                json(&events.iter().collect::<Vec<_>>())
+            }
+            ("GET", "/api/audit") => {
+                let limit = request.query.get("limit").and_then(|l| l.parse().ok());
+                let message = IpcMessage::AuditTailRequest { limit: limit.unwrap_or(DEFAULT_AUDIT_TAIL) };
+                match self.admin_call(request, message).await {
+                    Ok(IpcMessage::AuditTailResponse { events }) => json(&events),
+                    other => unexpected(other),
+                }
+            }
+            ("POST", "/api/drain") => match self.admin_call(request, IpcMessage::DrainRequest).await {
+                Ok(IpcMessage::DrainResponse { in_flight }) => json(&serde_json::json!({ "in_flight": in_flight })),
+                other => unexpected(other),
+            },
+            ("POST", "/api/models/load") => self.model_action(request, ModelAction::Load).await,
+            ("POST", "/api/models/unload") => self.model_action(request, ModelAction::Unload).await,
+            (_, path) if ROUTES.contains(&path) => Response::error(405, "Method not allowed"),
+            _ => Response::error(404, "Not found"),
+        }
+    }
+
+}
+
+impl Drop for Console {
+    fn drop(&mut self) {
+        unsubscribe_events(self.subscription);
+    }
+}
+
+/// `localhost`, `127.0.0.1` or `[::1]`, with or without a port.
+fn is_loopback_host(host: &str) -> bool {
+    let name = match host.strip_prefix('[') {
+        Some(rest) => rest.split(']').next().unwrap_or(""),
+        None => host.split(':').next().unwrap_or(""),
+    };
+    name.eq_ignore_ascii_case("localhost")
+        || name.parse::<std::net::IpAddr>().is_ok_and(|ip| ip.is_loopback())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_loopback_hosts() {
+        assert!(is_loopback_host("127.0.0.1:8787"));
+        assert!(is_loopback_host("localhost"));
+        assert!(is_loopback_host("[::1]:8787"));
+        assert!(!is_loopback_host("evil.example:8787"));
+        assert!(!is_loopback_host("10.0.0.5"));
+    }
+
+    #[tokio::test]
+    async fn test_bind_refuses_non_loopback() {
+        let addr: SocketAddr = "0.0.0.0:0".parse().unwrap();
+        assert!(matches!(Console::bind(addr).await, Err(ConsoleError::NotLoopback(_))));
+    }
+}
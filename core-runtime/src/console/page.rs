@@ -0,0 +1,80 @@
+//! Static console page. Polls the JSON API; the admin token stays in
+//! session storage and is sent as a bearer header, never as a cookie.
+
+pub const INDEX: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>GG-CORE Console</title>
+<style>
+body { font-family: system-ui, sans-serif; margin: 1.5rem; color: #222; }
+h1 { font-size: 1.3rem; } h2 { font-size: 1.05rem; margin-top: 1.5rem; }
+table { border-collapse: collapse; font-size: 0.9rem; }
+td, th { border: 1px solid #ccc; padding: 0.2rem 0.5rem; text-align: left; }
+pre { background: #f5f5f5; padding: 0.5rem; max-height: 16rem; overflow: auto; }
+#message { color: #a00; }
+</style>
+</head>
+<body>
+<h1>GG-CORE Console</h1>
+<p>
+  Admin token <input id="token" type="password" size="32">
+  <button onclick="saveToken()">Use</button>
+  <button onclick="post('/api/drain')">Drain</button>
+  Model <input id="model" size="32" placeholder="models/name.gguf or name">
+  <button onclick="post('/api/models/load', true)">Load</button>
+  <button onclick="post('/api/models/unload', true)">Unload</button>
+</p>
+<p id="message"></p>
+<h2>Status</h2><pre id="status"></pre>
+<h2>Models</h2><table id="models"></table>
+<h2>Queue</h2><pre id="queue"></pre>
+<h2>Recent events</h2><pre id="events"></pre>
+<h2>Audit tail</h2><pre id="audit">Enter an admin token to view.</pre>
+<script>
+function token() { return sessionStorage.getItem("gg-core-token") || ""; }
+function saveToken() {
+  sessionStorage.setItem("gg-core-token", document.getElementById("token").value);
+  document.getElementById("token").value = "";
+  refresh();
+}
+function headers() {
+  const h = { "Content-Type": "application/json" };
+  if (token()) h["Authorization"] = "Bearer " + token();
+  return h;
+}
+async function get(path) {
+  const r = await fetch(path, { headers: headers() });
+  return [r.ok, await r.json()];
+}
+async function post(path, withModel) {
+  const body = withModel ? JSON.stringify({ model: document.getElementById("model").value }) : "{}";
+  const r = await fetch(path, { method: "POST", headers: headers(), body: body });
+  const data = await r.json();
+  document.getElementById("message").textContent = r.ok ? "" : (data.error || "Request failed");
+  refresh();
+}
+function show(id, data) { document.getElementById(id).textContent = JSON.stringify(data, null, 2); }
+function showModels(list) {
+  const table = document.getElementById("models");
+  table.replaceChildren();
+  const rows = [["Name", "Format", "State", "Requests", "Avg ms"]].concat(
+    list.models.map(m => [m.name, m.format, m.state, m.request_count, m.avg_latency_ms.toFixed(1)]));
+  rows.forEach((cells, i) => {
+    const tr = table.insertRow();
+    cells.forEach(c => { const td = document.createElement(i ? "td" : "th"); td.textContent = c; tr.appendChild(td); });
+  });
+}
+async function refresh() {
+  const [, status] = await get("/api/status"); show("status", status);
+  const [, models] = await get("/api/models"); if (models.models) showModels(models);
+  const [, queue] = await get("/api/queue"); show("queue", queue);
+  const [, events] = await get("/api/events"); show("events", events.slice().reverse());
+  if (token()) { const [ok, audit] = await get("/api/audit"); show("audit", ok ? audit.slice().reverse() : audit); }
+}
+refresh();
+setInterval(refresh, 5000);
+</script>
+</body>
+</html>
+"#;
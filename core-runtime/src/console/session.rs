@@ -0,0 +1,51 @@
+//! IPC calls on behalf of console clients, and their admin sessions.
+
+use sha2::{Digest, Sha256};
+
+use super::{Console, Request, Response};
+use crate::ipc::{decode_message, encode_message, HandlerError, IpcMessage, SessionToken};
+
+impl Console {
+    /// Run `message` under the admin session of the request's bearer token,
+    /// re-authenticating once if the cached session has expired.
+    pub(super) async fn admin_call(&self, request: &Request, message: IpcMessage) -> Result<IpcMessage, Response> {
+        let token = request.bearer_token().ok_or_else(|| Response::error(401, "Admin token required"))?;
+        for _ in 0..2 {
+            let session = self.admin_session(token).await?;
+            match self.call(message.clone(), Some(&session)).await {
+                Err(response) if response.status == 401 => *self.admin.lock().await = None,
+                result => return result,
+            }
+        }
+        Err(Response::error(401, "Invalid or expired token"))
+    }
+
+    pub(super) async fn admin_session(&self, token: &str) -> Result<SessionToken, Response> {
+        let hash: [u8; 32] = Sha256::digest(token.as_bytes()).into();
+        let mut cached = self.admin.lock().await;
+        if let Some((cached_hash, session)) = cached.as_ref() {
+            if *cached_hash == hash {
+                return Ok(session.clone());
+            }
+        }
+        let handshake = IpcMessage::Handshake { token: token.to_string(), protocol_version: None };
+        let bytes = encode_message(&handshake).map_err(|e| Response::error(500, &e.to_string()))?;
+        let session = match self.handler.process(&bytes, None).await {
+            Ok((_, Some(session))) => session,
+            _ => return Err(Response::error(401, "Invalid or expired token")),
+        };
+        *cached = Some((hash, session.clone()));
+        Ok(session)
+    }
+
+    pub(super) async fn call(&self, message: IpcMessage, session: Option<&SessionToken>) -> Result<IpcMessage, Response> {
+        let bytes = encode_message(&message).map_err(|e| Response::error(500, &e.to_string()))?;
+        match self.handler.process(&bytes, session).await {
+            Ok((bytes, _)) => decode_message(&bytes).map_err(|e| Response::error(500, &e.to_string())),
+            Err(HandlerError::Auth(_) | HandlerError::NotAuthenticated) => {
+                Err(Response::error(401, "Invalid or expired token"))
+            }
+            Err(e) => Err(Response::error(500, &e.to_string())),
+        }
+    }
+}
@@ -0,0 +1,56 @@
+//! Rollback guard for in-progress conversation turns.
+
+use super::ConversationStore;
+
+/// In-progress turn; see [`ConversationStore::guard_turn`].
+pub struct TurnGuard<'a> {
+    store: &'a ConversationStore,
+    id: String,
+    finished: bool,
+}
+
+impl ConversationStore {
+    /// Guard for the turn just begun on `id`: dropping it before
+    /// [`TurnGuard::finish`] aborts the turn, so a cancelled request cannot
+    /// leave the conversation stuck in progress and exempt from the TTL.
+    pub fn guard_turn(&self, id: &str) -> TurnGuard<'_> {
+        TurnGuard { store: self, id: id.to_string(), finished: false }
+    }
+}
+
+impl TurnGuard<'_> {
+    /// Record the assistant reply and complete the turn.
+    pub fn finish(mut self, reply: &str) {
+        self.store.finish_turn(&self.id, reply);
+        self.finished = true;
+    }
+}
+
+impl Drop for TurnGuard<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.store.abort_turn(&self.id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dropped_guard_aborts_turn() {
+        let store = ConversationStore::default();
+        let id = store.create("m").unwrap();
+        store.begin_turn(&id, "one").unwrap();
+        drop(store.guard_turn(&id));
+        assert!(store.history(&id).unwrap().is_empty());
+        assert!(store.begin_turn(&id, "two").is_ok());
+
+        let store = ConversationStore::default();
+        let id = store.create("m").unwrap();
+        store.begin_turn(&id, "one").unwrap();
+        store.guard_turn(&id).finish("reply");
+        assert_eq!(store.history(&id).unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,21 @@
+//! Server-held conversation state.
+//!
+//! Thin clients create a conversation once and then send only the new user
+//! message each turn. The store keeps the transcript (bounded by turn count
+//! and byte size, oldest turns dropped first), renders it into the prompt,
+//! and ties the conversation to a stable affinity key so follow-up turns
+//! land on the replica holding its KV state. Idle conversations expire
+//! after a TTL. Optionally, the next turn's prompt prefix is prefilled in
+//! the background once a turn completes (see `prefetch`).
+
+mod guard;
+pub mod prefetch;
+mod store;
+
+pub use guard::TurnGuard;
+pub use prefetch::{PrefetchConfig, PrefetchGate, PrefetchLoad, PrefetchSkip};
+
+pub use store::{
+    ConversationConfig, ConversationError, ConversationStore, ConversationSummary, PreparedTurn,
+    Role, Turn,
+};
@@ -0,0 +1,152 @@
+//! Speculative KV prefetch for conversation follow-ups.
+//!
+//! When a turn completes, the next prompt is known up to the user's
+//! message: the transcript so far plus the user label. Prefilling that
+//! prefix in the background lets the next turn prefill only its own
+//! tokens. Prefetch is pure speculation, so it only runs on spare
+//! capacity: few queued or in-flight requests, the overload ladder at
+//! `Normal`, a bounded number of prefetches at once, and the model's kept
+//! prefix states under their memory budget.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use crate::scheduler::DegradationLevel;
+
+/// Follow-up prefetch configuration. Disabled by default.
+#[derive(Debug, Clone)]
+pub struct PrefetchConfig {
+    pub enabled: bool,
+    /// Skip while more requests than this are queued.
+    pub max_queue_depth: usize,
+    /// Skip while more requests than this are running.
+    pub max_active_requests: usize,
+    /// Prefetches running at once.
+    pub max_in_flight: usize,
+    /// Skip once the model keeps this many bytes of prefix state.
+    pub max_cache_bytes: usize,
+}
+
+impl Default for PrefetchConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_queue_depth: 0,
+            max_active_requests: 0,
+            max_in_flight: 1,
+            max_cache_bytes: 512 * 1024 * 1024,
+        }
+    }
+}
+
+/// Current load, sampled when a turn completes.
+#[derive(Debug, Clone, Copy)]
+pub struct PrefetchLoad {
+    pub queue_depth: usize,
+    pub active_requests: usize,
+    pub level: DegradationLevel,
+    pub cache_bytes: usize,
+}
+
+/// Reason a prefetch was not started.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrefetchSkip {
+    Busy,
+    Overloaded,
+    InFlight,
+    MemoryHeadroom,
+}
+
+impl PrefetchSkip {
+    /// Label for skip metrics.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Busy => "busy",
+            Self::Overloaded => "overloaded",
+            Self::InFlight => "in_flight",
+            Self::MemoryHeadroom => "memory_headroom",
+        }
+    }
+}
+
+/// Admits prefetches only on spare capacity.
+pub struct PrefetchGate {
+    config: PrefetchConfig,
+    in_flight: Arc<AtomicUsize>,
+}
+
+impl PrefetchGate {
+    pub fn new(config: PrefetchConfig) -> Self {
+        Self { config, in_flight: Arc::new(AtomicUsize::new(0)) }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Reserve a prefetch slot under `load`. The guard frees it on drop.
+    pub fn try_begin(&self, load: PrefetchLoad) -> Result<PrefetchGuard, PrefetchSkip> {
+        if load.level > DegradationLevel::Normal {
+            return Err(PrefetchSkip::Overloaded);
+        }
+        if load.queue_depth > self.config.max_queue_depth
+            || load.active_requests > self.config.max_active_requests
+        {
+            return Err(PrefetchSkip::Busy);
+        }
+        if load.cache_bytes >= self.config.max_cache_bytes {
+            return Err(PrefetchSkip::MemoryHeadroom);
+        }
+        let max = self.config.max_in_flight;
+        self.in_flight
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| (n < max).then_some(n + 1))
+            .map_err(|_| PrefetchSkip::InFlight)?;
+        Ok(PrefetchGuard { in_flight: Arc::clone(&self.in_flight) })
+    }
+}
+
+impl Default for PrefetchGate {
+    fn default() -> Self {
+        Self::new(PrefetchConfig::default())
+    }
+}
+
+/// RAII guard holding one prefetch slot.
+pub struct PrefetchGuard {
+    in_flight: Arc<AtomicUsize>,
+}
+
+impl Drop for PrefetchGuard {
+    fn drop(&mut self) {
+        self.in_flight.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn idle() -> PrefetchLoad {
+        PrefetchLoad { queue_depth: 0, active_requests: 0, level: DegradationLevel::Normal, cache_bytes: 0 }
+    }
+
+    #[test]
+    fn test_gate_requires_spare_capacity() {
+        let gate = PrefetchGate::new(PrefetchConfig { enabled: true, max_cache_bytes: 100, ..Default::default() });
+        let busy = PrefetchLoad { queue_depth: 1, ..idle() };
+        assert_eq!(gate.try_begin(busy).err(), Some(PrefetchSkip::Busy));
+        let overloaded = PrefetchLoad { level: DegradationLevel::NoSpeculative, ..idle() };
+        assert_eq!(gate.try_begin(overloaded).err(), Some(PrefetchSkip::Overloaded));
+        let full = PrefetchLoad { cache_bytes: 100, ..idle() };
+        assert_eq!(gate.try_begin(full).err(), Some(PrefetchSkip::MemoryHeadroom));
+    }
+
+    #[test]
+    fn test_in_flight_limit_released_on_drop() {
+        let gate = PrefetchGate::new(PrefetchConfig { enabled: true, ..Default::default() });
+        let guard = gate.try_begin(idle()).unwrap();
+        assert_eq!(gate.try_begin(idle()).err(), Some(PrefetchSkip::InFlight));
+        drop(guard);
+        assert!(gate.try_begin(idle()).is_ok());
+    }
+}
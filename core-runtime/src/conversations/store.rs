@@ -0,0 +1,442 @@
+//! Conversation store with TTL and size limits.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::ipc::AffinityTracker;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ConversationError {
+    #[error("Conversation not found: {0}")]
+    NotFound(String),
+
+    #[error("Conversation limit reached ({0})")]
+    LimitReached(usize),
+
+    #[error("Message exceeds history limit of {0} bytes")]
+    MessageTooLarge(usize),
+
+    #[error("Conversation {0} already has a turn in progress")]
+    TurnInProgress(String),
+}
+
+/// Conversation store limits.
+#[derive(Debug, Clone)]
+pub struct ConversationConfig {
+    /// Idle conversations are evicted after this long.
+    pub ttl: Duration,
+    pub max_conversations: usize,
+    /// Oldest turns are dropped beyond this count.
+    pub max_turns: usize,
+    /// Oldest turns are dropped once the transcript exceeds this size.
+    pub max_history_bytes: usize,
+}
+
+impl Default for ConversationConfig {
+    fn default() -> Self {
+        Self {
+            ttl: Duration::from_secs(1800),
+            max_conversations: 1024,
+            max_turns: 64,
+            max_history_bytes: 64 * 1024,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    User,
+    Assistant,
+    /// Result of a tool call, returned by the client.
+    Tool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Turn {
+    pub role: Role,
+    pub content: String,
+    /// Tool call this turn answers (`Role::Tool` only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_call_id: Option<String>,
+}
+
+impl Turn {
+    fn new(role: Role, content: &str) -> Self {
+        Self { role, content: content.to_string(), tool_call_id: None }
+    }
+}
+
+/// Admin view of a conversation (no transcript content).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversationSummary {
+    pub conversation_id: String,
+    pub model_id: String,
+    pub turns: usize,
+    pub history_bytes: usize,
+    pub idle_secs: u64,
+}
+
+struct Conversation {
+    model_id: String,
+    /// Tenant label of the creating session, for purges.
+    owner: Option<String>,
+    turns: Vec<Turn>,
+    affinity_key: String,
+    last_active: Instant,
+    /// Set between `begin_turn` and `finish_turn`/`abort_turn`.
+    pending: bool,
+}
+
+impl Conversation {
+    fn history_bytes(&self) -> usize {
+        self.turns.iter().map(|t| t.content.len()).sum()
+    }
+
+    fn trim(&mut self, config: &ConversationConfig) {
+        while self.turns.len() > config.max_turns
+            || (self.history_bytes() > config.max_history_bytes && self.turns.len() > 1)
+        {
+            self.turns.remove(0);
+        }
+    }
+
+    fn render(&self) -> String {
+        let mut prompt = self.render_turns();
+        prompt.push_str("Assistant:");
+        prompt
+    }
+
+    fn render_turns(&self) -> String {
+        let mut prompt = String::new();
+        for turn in &self.turns {
+            let label = match turn.role {
+                Role::User => "User",
+                Role::Assistant => "Assistant",
+                Role::Tool => "Tool",
+            };
+            prompt.push_str(label);
+            if let Some(ref call_id) = turn.tool_call_id {
+                prompt.push_str(&format!(" [{call_id}]"));
+            }
+            prompt.push_str(": ");
+            prompt.push_str(&turn.content);
+            prompt.push('\n');
+        }
+        prompt
+    }
+}
+
+/// A turn ready for inference.
+#[derive(Debug, Clone)]
+pub struct PreparedTurn {
+    pub model_id: String,
+    pub prompt: String,
+    pub affinity_key: String,
+}
+
+/// Thread-safe conversation store.
+pub struct ConversationStore {
+    config: ConversationConfig,
+    conversations: Mutex<HashMap<String, Conversation>>,
+}
+
+impl ConversationStore {
+    pub fn new(config: ConversationConfig) -> Self {
+        Self {
+            config,
+            conversations: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Conversation>> {
+        self.conversations.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Start a conversation and return its id.
+    pub fn create(&self, model_id: &str) -> Result<String, ConversationError> {
+        self.create_owned(model_id, None)
+    }
+
+    /// Start a conversation owned by a tenant, so a purge can find it.
+    pub fn create_owned(&self, model_id: &str, owner: Option<String>) -> Result<String, ConversationError> {
+        let mut conversations = self.lock();
+        let ttl = self.config.ttl;
+        conversations.retain(|_, c| c.pending || c.last_active.elapsed() < ttl);
+        if conversations.len() >= self.config.max_conversations {
+            return Err(ConversationError::LimitReached(self.config.max_conversations));
+        }
+        let id = uuid::Uuid::new_v4().to_string();
+        let conversation = Conversation {
+            model_id: model_id.to_string(),
+            owner,
+            turns: Vec::new(),
+            affinity_key: AffinityTracker::derive_key(model_id, &id),
+            last_active: Instant::now(),
+            pending: false,
+        };
+        conversations.insert(id.clone(), conversation);
+        Ok(id)
+    }
+
+    /// Confirm `owner` owns the conversation. Anyone else gets `NotFound`,
+    /// so a guessed id reveals nothing about other tenants' conversations.
+    pub fn check_owner(&self, id: &str, owner: Option<&str>) -> Result<(), ConversationError> {
+        let mut conversations = self.lock();
+        if self.live(&mut conversations, id)?.owner.as_deref() != owner {
+            return Err(ConversationError::NotFound(id.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Append a user message and render the prompt for inference.
+    pub fn begin_turn(&self, id: &str, message: &str) -> Result<PreparedTurn, ConversationError> {
+        self.begin(id, Turn::new(Role::User, message))
+    }
+
+    /// Append a tool result and render the prompt to continue generation.
+    pub fn begin_tool_result(
+        &self,
+        id: &str,
+        tool_call_id: &str,
+        content: &str,
+    ) -> Result<PreparedTurn, ConversationError> {
+        let turn = Turn {
+            tool_call_id: Some(tool_call_id.to_string()),
+            ..Turn::new(Role::Tool, content)
+        };
+        self.begin(id, turn)
+    }
+
+    fn begin(&self, id: &str, turn: Turn) -> Result<PreparedTurn, ConversationError> {
+        if turn.content.len() > self.config.max_history_bytes {
+            return Err(ConversationError::MessageTooLarge(self.config.max_history_bytes));
+        }
+        let mut conversations = self.lock();
+        let conversation = self.live(&mut conversations, id)?;
+        if conversation.pending {
+            return Err(ConversationError::TurnInProgress(id.to_string()));
+        }
+        conversation.turns.push(turn);
+        conversation.trim(&self.config);
+        conversation.pending = true;
+        conversation.last_active = Instant::now();
+        Ok(PreparedTurn {
+            model_id: conversation.model_id.clone(),
+            prompt: conversation.render(),
+            affinity_key: conversation.affinity_key.clone(),
+        })
+    }
+
+    /// Record the assistant reply for the in-progress turn.
+    pub fn finish_turn(&self, id: &str, reply: &str) {
+        if let Some(conversation) = self.lock().get_mut(id) {
+            conversation.turns.push(Turn::new(Role::Assistant, reply));
+            conversation.trim(&self.config);
+            conversation.pending = false;
+            conversation.last_active = Instant::now();
+        }
+    }
+
+    /// Roll back the user message of a failed turn.
+    pub fn abort_turn(&self, id: &str) {
+        if let Some(conversation) = self.lock().get_mut(id) {
+            if conversation.pending {
+                conversation.turns.pop();
+                conversation.pending = false;
+            }
+        }
+    }
+
+    /// Model and prompt prefix the next user turn will start with: the
+    /// transcript so far plus the user label. The space after the label is
+    /// left out, since tokenizers merge it into the message's first word.
+    /// `None` while a turn is in progress.
+    pub fn next_turn_prefix(&self, id: &str) -> Option<(String, String)> {
+        let mut conversations = self.lock();
+        let conversation = self.live(&mut conversations, id).ok().filter(|c| !c.pending)?;
+        let mut prefix = conversation.render_turns();
+        prefix.push_str("User:");
+        Some((conversation.model_id.clone(), prefix))
+    }
+
+    /// Transcript of a conversation.
+    pub fn history(&self, id: &str) -> Result<Vec<Turn>, ConversationError> {
+        let mut conversations = self.lock();
+        Ok(self.live(&mut conversations, id)?.turns.clone())
+    }
+
+    /// Summaries of live conversations, most recently active first.
+    pub fn list(&self) -> Vec<ConversationSummary> {
+        let mut summaries: Vec<_> = self
+            .lock()
+            .iter()
+            .filter(|(_, c)| c.last_active.elapsed() < self.config.ttl)
+            .map(|(id, c)| ConversationSummary {
+                conversation_id: id.clone(),
+                model_id: c.model_id.clone(),
+                turns: c.turns.len(),
+                history_bytes: c.history_bytes(),
+                idle_secs: c.last_active.elapsed().as_secs(),
+            })
+            .collect();
+        summaries.sort_by_key(|s| s.idle_secs);
+        summaries
+    }
+
+    /// Drop a conversation. Returns false if it did not exist.
+    pub fn evict(&self, id: &str) -> bool {
+        self.lock().remove(id).is_some()
+    }
+
+    /// Remove a conversation, returning its affinity key.
+    pub fn purge(&self, id: &str) -> Option<String> {
+        self.lock().remove(id).map(|c| c.affinity_key)
+    }
+
+    /// Remove every conversation owned by `owner`, returning their affinity keys.
+    pub fn purge_owner(&self, owner: &str) -> Vec<String> {
+        let mut conversations = self.lock();
+        let ids: Vec<String> = conversations
+            .iter()
+            .filter(|(_, c)| c.owner.as_deref() == Some(owner))
+            .map(|(id, _)| id.clone())
+            .collect();
+        ids.iter().filter_map(|id| conversations.remove(id)).map(|c| c.affinity_key).collect()
+    }
+
+    fn live<'a>(
+        &self,
+        conversations: &'a mut HashMap<String, Conversation>,
+        id: &str,
+    ) -> Result<&'a mut Conversation, ConversationError> {
+        let expired = conversations
+            .get(id)
+            .is_some_and(|c| !c.pending && c.last_active.elapsed() >= self.config.ttl);
+        if expired {
+            conversations.remove(id);
+        }
+        conversations
+            .get_mut(id)
+            .ok_or_else(|| ConversationError::NotFound(id.to_string()))
+    }
+}
+
+impl Default for ConversationStore {
+    fn default() -> Self {
+        Self::new(ConversationConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_purge_owner_removes_only_owned() {
+        let store = ConversationStore::default();
+        let owned = store.create_owned("m", Some("tenant-a".into())).unwrap();
+        let other = store.create_owned("m", Some("tenant-b".into())).unwrap();
+
+        assert_eq!(store.purge_owner("tenant-a").len(), 1);
+        assert!(store.history(&owned).is_err());
+        assert!(store.history(&other).is_ok());
+        assert!(store.purge(&other).is_some());
+        assert!(store.purge(&other).is_none());
+    }
+
+    #[test]
+    fn test_turns_build_transcript() {
+        let store = ConversationStore::default();
+        let id = store.create("phi-3").unwrap();
+
+        let first = store.begin_turn(&id, "Hi").unwrap();
+        assert_eq!(first.prompt, "User: Hi\nAssistant:");
+        store.finish_turn(&id, "Hello!");
+
+        let second = store.begin_turn(&id, "How are you?").unwrap();
+        assert_eq!(second.prompt, "User: Hi\nAssistant: Hello!\nUser: How are you?\nAssistant:");
+        assert_eq!(second.affinity_key, first.affinity_key);
+    }
+
+    #[test]
+    fn test_next_turn_prefix_is_prompt_prefix() {
+        let store = ConversationStore::default();
+        let id = store.create("phi-3").unwrap();
+        store.begin_turn(&id, "Hi").unwrap();
+        assert!(store.next_turn_prefix(&id).is_none());
+        store.finish_turn(&id, "Hello!");
+
+        let (model, prefix) = store.next_turn_prefix(&id).unwrap();
+        assert_eq!(model, "phi-3");
+        assert_eq!(prefix, "User: Hi\nAssistant: Hello!\nUser:");
+        assert!(store.begin_turn(&id, "More").unwrap().prompt.starts_with(&prefix));
+        assert!(store.next_turn_prefix("missing").is_none());
+    }
+
+    #[test]
+    fn test_tool_result_rendered_with_call_id() {
+        let store = ConversationStore::default();
+        let id = store.create("m").unwrap();
+        store.begin_turn(&id, "Weather?").unwrap();
+        store.finish_turn(&id, "<tool_call>{}");
+        let next = store.begin_tool_result(&id, "call-1", "12C").unwrap();
+        assert!(next.prompt.ends_with("Tool [call-1]: 12C\nAssistant:"));
+    }
+
+    #[test]
+    fn test_history_trimmed_to_limits() {
+        let store = ConversationStore::new(ConversationConfig { max_turns: 2, ..Default::default() });
+        let id = store.create("m").unwrap();
+        for i in 0..3 {
+            store.begin_turn(&id, &format!("q{i}")).unwrap();
+            store.finish_turn(&id, &format!("a{i}"));
+        }
+        let history = store.history(&id).unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].content, "q2");
+    }
+
+    #[test]
+    fn test_abort_rolls_back_and_concurrent_turn_rejected() {
+        let store = ConversationStore::default();
+        let id = store.create("m").unwrap();
+        store.begin_turn(&id, "one").unwrap();
+        assert!(matches!(store.begin_turn(&id, "two"), Err(ConversationError::TurnInProgress(_))));
+        store.abort_turn(&id);
+        assert!(store.history(&id).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_only_owner_passes_check() {
+        let store = ConversationStore::default();
+        let id = store.create_owned("m", Some("tenant-a".into())).unwrap();
+        assert!(store.check_owner(&id, Some("tenant-a")).is_ok());
+        assert!(matches!(store.check_owner(&id, Some("tenant-b")), Err(ConversationError::NotFound(_))));
+        assert!(matches!(store.check_owner(&id, None), Err(ConversationError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_ttl_expiry_and_evict() {
+        let store = ConversationStore::new(ConversationConfig { ttl: Duration::ZERO, ..Default::default() });
+        let id = store.create("m").unwrap();
+        assert!(matches!(store.history(&id), Err(ConversationError::NotFound(_))));
+
+        let store = ConversationStore::default();
+        let id = store.create("m").unwrap();
+        assert_eq!(store.list().len(), 1);
+        assert!(store.evict(&id));
+        assert!(store.list().is_empty());
+    }
+
+    #[test]
+    fn test_conversation_limit() {
+        let store = ConversationStore::new(ConversationConfig { max_conversations: 1, ..Default::default() });
+        store.create("m").unwrap();
+        assert!(matches!(store.create("m"), Err(ConversationError::LimitReached(1))));
+    }
+}
@@ -0,0 +1,86 @@
+//! Format-to-backend dispatch.
+//!
+//! Every backend hands back an `Arc<dyn GgufModel>` so the inference engine
+//! can hold models of different formats side by side.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::engine::gguf::{load_gguf_model, GgufConfig, GgufModel};
+use crate::engine::mock::load_mock_model;
+use crate::engine::onnx::{load_onnx_model, OnnxConfig, OnnxModel};
+use crate::engine::safetensors::{load_safetensors_model, SafetensorsTask};
+use crate::engine::{InferenceCapability, InferenceConfig, InferenceError};
+use crate::engine::{InferenceInput, InferenceOutput};
+use crate::models::ModelFormat;
+
+/// Load `path` with the backend for `format` using default settings.
+///
+/// # Errors
+/// Returns error if the backend is not compiled in or the load fails.
+pub fn load_model(
+    format: ModelFormat,
+    path: &Path,
+    model_id: &str,
+) -> Result<Arc<dyn GgufModel>, InferenceError> {
+    match format {
+        ModelFormat::Gguf => load_gguf_model(path, model_id, &GgufConfig::default()),
+        ModelFormat::Safetensors => load_safetensors_model(path, model_id, SafetensorsTask::Embedding),
+        ModelFormat::Onnx => load_onnx_model(path, model_id, &OnnxConfig::default())
+            .map(|model| Arc::new(OnnxAdapter { inner: model }) as Arc<dyn GgufModel>),
+        ModelFormat::Mock => load_mock_model(path, model_id),
+    }
+}
+
+/// Presents an ONNX model through the engine's model trait.
+struct OnnxAdapter {
+    inner: Arc<dyn OnnxModel>,
+}
+
+#[async_trait::async_trait]
+impl GgufModel for OnnxAdapter {
+    fn model_id(&self) -> &str {
+        self.inner.model_id()
+    }
+
+    fn capabilities(&self) -> &[InferenceCapability] {
+        self.inner.capabilities()
+    }
+
+    fn memory_usage(&self) -> usize {
+        self.inner.memory_usage()
+    }
+
+    async fn infer(
+        &self,
+        input: &InferenceInput,
+        config: &InferenceConfig,
+    ) -> Result<InferenceOutput, InferenceError> {
+        self.inner.infer(input, config).await
+    }
+
+    async fn unload(&mut self) -> Result<(), InferenceError> {
+        // Shared handles release their resources when the last one drops
+        match Arc::get_mut(&mut self.inner) {
+            Some(model) => model.unload().await,
+            None => Ok(()),
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uncompiled_backends_report_feature() {
+        for format in ModelFormat::ALL.into_iter().filter(|f| !f.is_compiled()) {
+            let err = load_model(format, Path::new("/nonexistent"), "m").err().unwrap();
+            assert!(err.to_string().contains(format.feature()), "{format}: {err}");
+        }
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! All fields have safe defaults. Configuration is validated before use.
 
+use super::degeneration::DegenerationConfig;
 use super::error::InferenceError;
 
 /// Per-call inference configuration.
@@ -21,6 +22,10 @@ pub struct InferenceConfig {
     pub timeout_ms: u64,
     /// Maximum memory allowed for this call (bytes). None = use global limit.
     pub max_memory_bytes: Option<usize>,
+    /// Stop generation early when the output starts looping.
+    pub degeneration: DegenerationConfig,
+    /// Registered sampler to use. None = the standard sampler.
+    pub sampler: Option<String>,
 }
 
 impl Default for InferenceConfig {
@@ -33,6 +38,8 @@ impl Default for InferenceConfig {
             repetition_penalty: 1.1,
             timeout_ms: 30_000,
             max_memory_bytes: Some(1024 * 1024 * 1024), // 1GB
+            degeneration: DegenerationConfig::default(),
+            sampler: None,
         }
     }
 }
@@ -73,6 +80,8 @@ impl InferenceConfig {
             repetition_penalty: 1.0,
             timeout_ms: 5_000,
             max_memory_bytes: Some(512 * 1024 * 1024), // 512MB
+            degeneration: DegenerationConfig::disabled(),
+            sampler: None,
         }
     }
 
@@ -86,6 +95,8 @@ impl InferenceConfig {
             repetition_penalty: 1.0,
             timeout_ms: 2_000,
             max_memory_bytes: Some(256 * 1024 * 1024), // 256MB
+            degeneration: DegenerationConfig::disabled(),
+            sampler: None,
         }
     }
 }
@@ -0,0 +1,292 @@
+//! Retrieved-context (RAG) documents with provenance.
+//!
+//! Callers attach `context_documents` to a request instead of pasting
+//! retrieved text into the prompt. The runtime escapes each document,
+//! enforces per-document and total token budgets (truncating long documents
+//! and dropping the ones that do not fit), and wraps them around the prompt
+//! with a fixed template that asks the model to cite `[doc-id]`. The IDs that
+//! were used are reported back, and citation markers in the output can be
+//! resolved to output spans attributed to their source documents.
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::templates::escape_variable;
+
+/// Most documents accepted on one request.
+pub const MAX_CONTEXT_DOCUMENTS: usize = 32;
+
+/// Smallest fragment worth including when a document only partly fits
+/// the remaining total budget.
+const MIN_FRAGMENT_TOKENS: usize = 32;
+
+/// Longest accepted document ID.
+pub const MAX_DOCUMENT_ID_LEN: usize = 128;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ContextError {
+    #[error("Too many context documents: {count} (max {max})")]
+    TooMany { count: usize, max: usize },
+
+    #[error("Invalid context document ID '{0}'")]
+    InvalidId(String),
+
+    #[error("Duplicate context document ID: {0}")]
+    DuplicateId(String),
+}
+
+/// A retrieved document supplied with a request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContextDocument {
+    pub id: String,
+    pub text: String,
+    /// Where the document came from (URL, path, index name).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+/// Token budgets for context documents, estimated at ~4 bytes per token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContextBudget {
+    pub max_document_tokens: usize,
+    pub max_total_tokens: usize,
+}
+
+impl Default for ContextBudget {
+    fn default() -> Self {
+        Self { max_document_tokens: 1024, max_total_tokens: 3072 }
+    }
+}
+
+/// How a document was used in the assembled prompt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DocumentUse {
+    Included,
+    Truncated,
+    /// Did not fit the total budget.
+    Dropped,
+}
+
+/// Per-document usage record returned with the response.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DocumentUsage {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// Estimated tokens placed in the prompt.
+    pub tokens: usize,
+    pub usage: DocumentUse,
+}
+
+/// An output span attributed to a source document.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Citation {
+    pub document_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// Byte range in the output: the sentence ending at the marker.
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Prompt with documents assembled, plus what was used.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssembledContext {
+    pub prompt: String,
+    pub documents: Vec<DocumentUsage>,
+}
+
+impl AssembledContext {
+    /// IDs of documents that made it into the prompt.
+    pub fn used_ids(&self) -> Vec<&str> {
+        self.documents
+            .iter()
+            .filter(|d| d.usage != DocumentUse::Dropped)
+            .map(|d| d.id.as_str())
+            .collect()
+    }
+}
+
+fn estimate_tokens(text: &str) -> usize {
+    text.len().div_ceil(4)
+}
+
+fn validate_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= MAX_DOCUMENT_ID_LEN
+        && id.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':' | '/'))
+}
+
+/// Cut `text` to about `tokens` tokens, on a char boundary and preferably
+/// at a word break.
+fn truncate_to_tokens(text: &str, tokens: usize) -> &str {
+    let mut end = tokens.saturating_mul(4);
+    if end >= text.len() {
+        return text;
+    }
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    let cut = &text[..end];
+    match cut.rfind(char::is_whitespace) {
+        Some(space) if space > end / 2 => &cut[..space],
+        _ => cut,
+    }
+}
+
+/// Assemble `documents` around `prompt` within `budget`. Documents are kept
+/// in request order; the first one that no longer fits the total budget and
+/// every later one are dropped.
+pub fn assemble_context(
+    documents: &[ContextDocument],
+    prompt: &str,
+    budget: ContextBudget,
+) -> Result<AssembledContext, ContextError> {
+    if documents.len() > MAX_CONTEXT_DOCUMENTS {
+        return Err(ContextError::TooMany { count: documents.len(), max: MAX_CONTEXT_DOCUMENTS });
+    }
+    let mut seen = Vec::with_capacity(documents.len());
+    for doc in documents {
+        if !validate_id(&doc.id) {
+            return Err(ContextError::InvalidId(doc.id.clone()));
+        }
+        if seen.contains(&doc.id.as_str()) {
+            return Err(ContextError::DuplicateId(doc.id.clone()));
+        }
+        seen.push(&doc.id);
+    }
+
+    let mut body = String::new();
+    let mut usages = Vec::with_capacity(documents.len());
+    let mut total = 0;
+    let mut full = false;
+    for doc in documents {
+        let escaped = escape_variable(&doc.text);
+        let mut text = truncate_to_tokens(&escaped, budget.max_document_tokens);
+        let mut usage = if text.len() < escaped.len() { DocumentUse::Truncated } else { DocumentUse::Included };
+        let remaining = budget.max_total_tokens.saturating_sub(total);
+        let fits = estimate_tokens(text) <= remaining;
+        if full || (!fits && remaining < MIN_FRAGMENT_TOKENS) {
+            full = true;
+            usages.push(DocumentUsage {
+                id: doc.id.clone(),
+                source: doc.source.clone(),
+                tokens: 0,
+                usage: DocumentUse::Dropped,
+            });
+            continue;
+        }
+        if !fits {
+            text = truncate_to_tokens(text, remaining);
+            usage = DocumentUse::Truncated;
+            full = true;
+        }
+        let tokens = estimate_tokens(text);
+        total += tokens;
+        match &doc.source {
+            Some(source) => body.push_str(&format!("[{}] (source: {})\n", doc.id, escape_variable(source))),
+            None => body.push_str(&format!("[{}]\n", doc.id)),
+        }
+        body.push_str(text);
+        body.push_str("\n\n");
+        usages.push(DocumentUsage { id: doc.id.clone(), source: doc.source.clone(), tokens, usage });
+    }
+
+    let prompt = if body.is_empty() {
+        prompt.to_string()
+    } else {
+        format!(
+            "Answer using the documents below. After each statement drawn from a \
+             document, cite it as [document-id].\n\n{body}Request: {prompt}"
+        )
+    };
+    Ok(AssembledContext { prompt, documents: usages })
+}
+
+/// Attribute output sentences to the documents they cite. Each `[id]`
+/// marker naming a used document yields the span from the start of its
+/// sentence up to the end of the marker.
+pub fn attribute_citations(output: &str, documents: &[DocumentUsage]) -> Vec<Citation> {
+    let mut citations = Vec::new();
+    let mut search = 0;
+    while let Some(open) = output[search..].find('[').map(|i| i + search) {
+        let Some(close) = output[open..].find(']').map(|i| i + open) else {
+            break;
+        };
+        let id = &output[open + 1..close];
+        if let Some(doc) = documents.iter().find(|d| d.id == id && d.usage != DocumentUse::Dropped) {
+            let start = output[..open]
+                .trim_end()
+                .rfind(['.', '!', '?', '\n'])
+                .map_or(0, |i| i + 1);
+            let start = start + (output[start..open].len() - output[start..open].trim_start().len());
+            citations.push(Citation { document_id: doc.id.clone(), source: doc.source.clone(), start, end: close + 1 });
+        }
+        search = close + 1;
+    }
+    citations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc(id: &str, text: &str) -> ContextDocument {
+        ContextDocument { id: id.into(), text: text.into(), source: Some(format!("kb://{id}")) }
+    }
+
+    #[test]
+    fn test_assembles_documents_around_prompt() {
+        let docs = [doc("a", "Paris is the capital of France."), doc("b", "Berlin <|end|> is in Germany.")];
+        let assembled = assemble_context(&docs, "Capitals?", ContextBudget::default()).unwrap();
+        assert!(assembled.prompt.contains("[a] (source: kb://a)\nParis is the capital of France."));
+        assert!(assembled.prompt.contains("Berlin < |end| > is in Germany."));
+        assert!(assembled.prompt.ends_with("Request: Capitals?"));
+        assert_eq!(assembled.used_ids(), ["a", "b"]);
+
+        assert_eq!(assemble_context(&[], "Hi", ContextBudget::default()).unwrap().prompt, "Hi");
+    }
+
+    #[test]
+    fn test_budgets_truncate_and_drop() {
+        let long = "word ".repeat(100);
+        let docs = [doc("a", &long), doc("b", &long), doc("c", "short")];
+        let budget = ContextBudget { max_document_tokens: 40, max_total_tokens: 75 };
+        let assembled = assemble_context(&docs, "q", budget).unwrap();
+        let uses: Vec<_> = assembled.documents.iter().map(|d| d.usage).collect();
+        assert_eq!(uses, [DocumentUse::Truncated, DocumentUse::Truncated, DocumentUse::Dropped]);
+        assert!(assembled.documents[0].tokens <= 40);
+        assert!(assembled.documents.iter().map(|d| d.tokens).sum::<usize>() <= 75);
+        assert_eq!(assembled.used_ids(), ["a", "b"]);
+
+        // Short documents still fit the remainder; tiny fragments are not cut
+        let docs = [doc("a", &long), doc("b", "short")];
+        let budget = ContextBudget { max_document_tokens: 40, max_total_tokens: 45 };
+        let uses: Vec<_> = assemble_context(&docs, "q", budget).unwrap().documents.iter().map(|d| d.usage).collect();
+        assert_eq!(uses, [DocumentUse::Truncated, DocumentUse::Included]);
+    }
+
+    #[test]
+    fn test_rejects_bad_ids() {
+        let budget = ContextBudget::default();
+        assert_eq!(
+            assemble_context(&[doc("a", "x"), doc("a", "y")], "q", budget),
+            Err(ContextError::DuplicateId("a".into()))
+        );
+        assert!(matches!(assemble_context(&[doc("a b", "x")], "q", budget), Err(ContextError::InvalidId(_))));
+    }
+
+    #[test]
+    fn test_citations_cover_citing_sentence() {
+        let docs = assemble_context(&[doc("a", "x"), doc("b", "y")], "q", ContextBudget::default())
+            .unwrap()
+            .documents;
+        let output = "Paris is the capital [a]. Berlin is German [b]. Unknown [zz].";
+        let citations = attribute_citations(output, &docs);
+        assert_eq!(citations.len(), 2);
+        assert_eq!(&output[citations[0].start..citations[0].end], "Paris is the capital [a]");
+        assert_eq!(&output[citations[1].start..citations[1].end], "Berlin is German [b]");
+        assert_eq!(citations[1].source.as_deref(), Some("kb://b"));
+    }
+}
@@ -0,0 +1,253 @@
+//! CPU capability probe and SIMD kernel dispatch.
+//!
+//! The probe runs once per process and records which instruction set
+//! extensions the CPU (and OS) offer. Kernels ask the dispatch table which
+//! tier they may use instead of probing on their own, so one override
+//! (`CORE_SIMD_MAX_TIER`) forces every kernel down to a lower tier when
+//! debugging, and `GG-CORE about --cpu` can report what each kernel runs.
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use serde::Serialize;
+
+/// SIMD tiers, lowest first. A machine offers tiers of one family only
+/// (x86 or ARM), plus scalar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SimdTier {
+    Scalar,
+    Neon,
+    Avx2,
+    Avx512,
+    Amx,
+}
+
+impl SimdTier {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Scalar => "scalar",
+            Self::Neon => "neon",
+            Self::Avx2 => "avx2",
+            Self::Avx512 => "avx512",
+            Self::Amx => "amx",
+        }
+    }
+}
+
+impl fmt::Display for SimdTier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl FromStr for SimdTier {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "scalar" | "none" => Ok(Self::Scalar),
+            "neon" => Ok(Self::Neon),
+            "avx2" => Ok(Self::Avx2),
+            "avx512" | "avx-512" => Ok(Self::Avx512),
+            "amx" => Ok(Self::Amx),
+            other => Err(format!("unknown SIMD tier '{}' (scalar, neon, avx2, avx512, amx)", other)),
+        }
+    }
+}
+
+/// Instruction set extensions available to this process.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct CpuFeatures {
+    pub avx2: bool,
+    pub avx512f: bool,
+    pub neon: bool,
+    /// AMX tiles, as enabled by the kernel (Linux only).
+    pub amx: bool,
+    pub aes_ni: bool,
+}
+
+impl CpuFeatures {
+    pub fn detect() -> Self {
+        #[cfg(target_arch = "x86_64")]
+        {
+            Self {
+                avx2: is_x86_feature_detected!("avx2"),
+                avx512f: is_x86_feature_detected!("avx512f"),
+                neon: false,
+                amx: amx_enabled(),
+                aes_ni: is_x86_feature_detected!("aes"),
+            }
+        }
+        #[cfg(target_arch = "aarch64")]
+        {
+            Self { neon: std::arch::is_aarch64_feature_detected!("neon"), ..Self::default() }
+        }
+        #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+        {
+            Self::default()
+        }
+    }
+
+    pub fn supports(&self, tier: SimdTier) -> bool {
+        match tier {
+            SimdTier::Scalar => true,
+            SimdTier::Neon => self.neon,
+            SimdTier::Avx2 => self.avx2,
+            SimdTier::Avx512 => self.avx512f,
+            SimdTier::Amx => self.amx,
+        }
+    }
+
+    /// Highest tier the CPU offers.
+    pub fn best_tier(&self) -> SimdTier {
+        [SimdTier::Amx, SimdTier::Avx512, SimdTier::Avx2, SimdTier::Neon]
+            .into_iter()
+            .find(|tier| self.supports(*tier))
+            .unwrap_or(SimdTier::Scalar)
+    }
+}
+
+/// AMX needs both the CPU flag and kernel support for tile state; the
+/// kernel only lists `amx_tile` when it has enabled it.
+#[cfg(target_arch = "x86_64")]
+fn amx_enabled() -> bool {
+    #[cfg(target_os = "linux")]
+    {
+        std::fs::read_to_string("/proc/cpuinfo")
+            .map(|info| {
+                info.lines()
+                    .find(|line| line.starts_with("flags"))
+                    .is_some_and(|flags| flags.split_whitespace().any(|flag| flag == "amx_tile"))
+            })
+            .unwrap_or(false)
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        false
+    }
+}
+
+/// Kernels that dispatch through the table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kernel {
+    /// Whitespace scan in the SIMD tokenizer.
+    TokenizerWhitespace,
+}
+
+impl Kernel {
+    pub const ALL: [Kernel; 1] = [Kernel::TokenizerWhitespace];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::TokenizerWhitespace => "tokenizer.whitespace",
+        }
+    }
+
+    /// Tiers the kernel has implementations for, lowest first.
+    pub fn implementations(&self) -> &'static [SimdTier] {
+        match self {
+            Self::TokenizerWhitespace => &[SimdTier::Scalar, SimdTier::Avx2],
+        }
+    }
+}
+
+/// The implementation chosen for one kernel.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct KernelSelection {
+    pub kernel: &'static str,
+    pub selected: SimdTier,
+    pub available: Vec<SimdTier>,
+}
+
+/// Probed features and the tier kernels may use.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Dispatch {
+    pub features: CpuFeatures,
+    /// Best tier the CPU offers.
+    pub detected: SimdTier,
+    /// Tier kernels may use: `detected`, or lower when overridden.
+    pub max_tier: SimdTier,
+    pub overridden: bool,
+}
+
+impl Dispatch {
+    /// Dispatch for `features`, capped at `max_tier`. A cap above what
+    /// the CPU offers has no effect.
+    pub fn new(features: CpuFeatures, max_tier: Option<SimdTier>) -> Self {
+        let detected = features.best_tier();
+        let capped = max_tier.map_or(detected, |tier| tier.min(detected));
+        Self { features, detected, max_tier: capped, overridden: capped < detected }
+    }
+
+    /// Whether kernels may use `tier`.
+    pub fn allows(&self, tier: SimdTier) -> bool {
+        tier <= self.max_tier && self.features.supports(tier)
+    }
+
+    pub fn select(&self, kernel: Kernel) -> SimdTier {
+        kernel
+            .implementations()
+            .iter()
+            .rev()
+            .copied()
+            .find(|tier| self.allows(*tier))
+            .unwrap_or(SimdTier::Scalar)
+    }
+
+    pub fn kernels(&self) -> Vec<KernelSelection> {
+        Kernel::ALL
+            .iter()
+            .map(|kernel| KernelSelection {
+                kernel: kernel.name(),
+                selected: self.select(*kernel),
+                available: kernel.implementations().to_vec(),
+            })
+            .collect()
+    }
+}
+
+static DISPATCH: OnceLock<Dispatch> = OnceLock::new();
+
+/// Probe the CPU and fix the dispatch table for the process. The first
+/// call wins; later calls return the table already in use.
+pub fn init(max_tier: Option<SimdTier>) -> &'static Dispatch {
+    DISPATCH.get_or_init(|| Dispatch::new(CpuFeatures::detect(), max_tier))
+}
+
+/// The process dispatch table, probed without an override if `init` was
+/// never called.
+pub fn dispatch() -> &'static Dispatch {
+    init(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AVX512_CPU: CpuFeatures = CpuFeatures { avx2: true, avx512f: true, neon: false, amx: false, aes_ni: true };
+
+    #[test]
+    fn test_override_only_lowers() {
+        let dispatch = Dispatch::new(AVX512_CPU, None);
+        assert_eq!((dispatch.detected, dispatch.max_tier), (SimdTier::Avx512, SimdTier::Avx512));
+        assert!(!dispatch.overridden);
+        assert_eq!(dispatch.select(Kernel::TokenizerWhitespace), SimdTier::Avx2);
+
+        let forced = Dispatch::new(AVX512_CPU, Some(SimdTier::Scalar));
+        assert!(forced.overridden);
+        assert!(!forced.allows(SimdTier::Avx2));
+        assert_eq!(forced.select(Kernel::TokenizerWhitespace), SimdTier::Scalar);
+
+        let raised = Dispatch::new(CpuFeatures::default(), Some(SimdTier::Amx));
+        assert_eq!((raised.max_tier, raised.overridden), (SimdTier::Scalar, false));
+    }
+
+    #[test]
+    fn test_tier_names() {
+        assert_eq!("AVX-512".parse::<SimdTier>(), Ok(SimdTier::Avx512));
+        assert_eq!("none".parse::<SimdTier>(), Ok(SimdTier::Scalar));
+        assert!("sse9".parse::<SimdTier>().is_err());
+    }
+}
@@ -0,0 +1,107 @@
+//! Degeneration detection inside the decode loop.
+//!
+//! A model stuck in a loop repeats the same phrase until `max_tokens`.
+//! The detector counts n-grams over a sliding window of recent tokens and
+//! reports degeneration once one n-gram recurs too often, so the loop can
+//! stop with `FinishReason::Degenerated` and return what came before
+//! instead of spending the rest of the budget on repeats.
+
+use std::collections::{HashMap, VecDeque};
+
+/// Degeneration detector settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DegenerationConfig {
+    pub enabled: bool,
+    /// Tokens per n-gram.
+    pub ngram: usize,
+    /// Recent tokens searched for repeats.
+    pub window: usize,
+    /// Occurrences of one n-gram within the window that stop generation.
+    pub max_repeats: usize,
+}
+
+impl Default for DegenerationConfig {
+    fn default() -> Self {
+        Self { enabled: true, ngram: 3, window: 128, max_repeats: 6 }
+    }
+}
+
+impl DegenerationConfig {
+    pub fn disabled() -> Self {
+        Self { enabled: false, ..Self::default() }
+    }
+}
+
+/// Sliding-window n-gram counter fed one token per decode step.
+#[derive(Debug)]
+pub struct DegenerationDetector {
+    config: DegenerationConfig,
+    recent: VecDeque<u32>,
+    counts: HashMap<Vec<u32>, usize>,
+}
+
+impl DegenerationDetector {
+    pub fn new(config: DegenerationConfig) -> Self {
+        Self { config, recent: VecDeque::new(), counts: HashMap::new() }
+    }
+
+    /// Record a generated token. Returns true once the recent output has
+    /// degenerated into repetition.
+    pub fn push(&mut self, token: u32) -> bool {
+        let DegenerationConfig { enabled, ngram, window, max_repeats } = self.config;
+        if !enabled || ngram == 0 || max_repeats < 2 || window < ngram {
+            return false;
+        }
+        if self.recent.len() == window {
+            // The oldest n-gram leaves the window with its first token
+            let oldest: Vec<u32> = self.recent.iter().take(ngram).copied().collect();
+            if let Some(count) = self.counts.get_mut(&oldest) {
+                *count -= 1;
+                if *count == 0 {
+                    self.counts.remove(&oldest);
+                }
+            }
+            self.recent.pop_front();
+        }
+        self.recent.push_back(token);
+        if self.recent.len() < ngram {
+            return false;
+        }
+        let newest: Vec<u32> = self.recent.iter().skip(self.recent.len() - ngram).copied().collect();
+        let count = self.counts.entry(newest).or_insert(0);
+        *count += 1;
+        *count >= max_repeats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stops_repeating_phrase() {
+        let mut detector = DegenerationDetector::new(DegenerationConfig::default());
+        let prefix: Vec<u32> = (100..140).collect();
+        assert!(!prefix.iter().any(|&t| detector.push(t)));
+
+        // A five-token phrase repeated; stops on its sixth occurrence
+        let stopped_at = [1, 2, 3, 4, 5].iter().cycle().take(100).position(|&t| detector.push(t));
+        assert_eq!(stopped_at, Some(5 * 5 + 2));
+    }
+
+    #[test]
+    fn test_window_forgets_old_repeats() {
+        let config = DegenerationConfig { ngram: 2, window: 8, max_repeats: 3, enabled: true };
+        let mut detector = DegenerationDetector::new(config);
+        // "7 8" twice, then enough fresh tokens to push both out
+        for t in [7, 8, 7, 8] {
+            assert!(!detector.push(t));
+        }
+        for t in 20..28 {
+            assert!(!detector.push(t));
+        }
+        assert!(!detector.push(7));
+        assert!(!detector.push(8));
+        assert!(!DegenerationDetector::new(DegenerationConfig::disabled()).push(1));
+    }
+}
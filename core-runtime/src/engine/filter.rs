@@ -7,6 +7,7 @@ use regex::Regex;
 use serde::Deserialize;
 use unicode_normalization::UnicodeNormalization;
 
+use crate::engine::utf8;
 use crate::engine::InferenceError;
 
 /// Configuration for output filtering.
@@ -87,9 +88,9 @@ impl OutputFilter {
                 .to_string();
         }
 
-        // Apply length limit
-        if self.config.max_output_chars > 0 && result.len() > self.config.max_output_chars {
-            result.truncate(self.config.max_output_chars);
+        // Apply length limit, counting characters so no sequence is split
+        if self.config.max_output_chars > 0 {
+            utf8::truncate_chars(&mut result, self.config.max_output_chars);
         }
 
         Ok(result)
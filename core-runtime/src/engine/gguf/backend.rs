@@ -5,19 +5,25 @@
 
 use std::num::NonZeroU32;
 use std::path::Path;
+use std::sync::{Mutex, MutexGuard};
 
-use llama_cpp_2::context::params::LlamaContextParams;
+use llama_cpp_2::context::params::{LlamaContextParams, LlamaPoolingType};
 use llama_cpp_2::context::LlamaContext;
 use llama_cpp_2::llama_backend::LlamaBackend;
 use llama_cpp_2::llama_batch::LlamaBatch;
 use llama_cpp_2::model::params::LlamaModelParams;
 use llama_cpp_2::model::{AddBos, LlamaModel};
-use llama_cpp_2::sampling::LlamaSampler;
 use llama_cpp_2::token::LlamaToken;
 
+use crate::engine::sampler::{self, Sampler};
 use crate::engine::{
-    FinishReason, GenerationResult, InferenceConfig, InferenceError,
+    DegenerationDetector, FinishReason, GenerationResult, InferenceConfig, InferenceError, StepAction,
+    StepHooks,
 };
+use crate::memory::PromptCache;
+
+/// Prefilled prefix states kept per model.
+const PREFIX_STATES: usize = 8;
 
 /// Holds the loaded llama-cpp-2 model and backend.
 pub struct LlamaBackendInner {
@@ -25,6 +31,8 @@ pub struct LlamaBackendInner {
     model: LlamaModel,
     n_ctx: u32,
     n_threads: i32,
+    /// Context states after prefilling a prefix, keyed by its tokens.
+    prefixes: Mutex<PromptCache>,
 }
 
 // SAFETY: LlamaModel and LlamaBackend are Send+Sync in llama-cpp-2.
@@ -45,7 +53,13 @@ impl LlamaBackendInner {
         let model = LlamaModel::load_from_file(&backend, path, &model_params)
             .map_err(|e| InferenceError::ModelError(format!("load: {e}")))?;
         let n_threads = resolve_threads(config.n_threads);
-        Ok(Self { backend, model, n_ctx: config.n_ctx, n_threads })
+        Ok(Self {
+            backend,
+            model,
+            n_ctx: config.n_ctx,
+            n_threads,
+            prefixes: Mutex::new(PromptCache::new(PREFIX_STATES)),
+        })
     }
 
     pub fn model_size(&self) -> usize { self.model.size() as usize }
@@ -76,28 +90,43 @@ impl LlamaBackendInner {
         let tokens = self.tokenize(prompt)?;
         let max_tok = config.max_tokens.unwrap_or(256);
         let mut ctx = self.create_context()?;
-        let mut batch = LlamaBatch::new(tokens.len(), 1);
-        add_seq(&mut batch, &tokens)?;
-        decode(&mut ctx, &mut batch)?;
-        let mut sampler = build_sampler(config);
-        sampler.accept_many(tokens.iter().copied());
+        let mut logits_idx = self.prime(&mut ctx, &tokens)?;
+        let mut batch = LlamaBatch::new(1, 1);
+        let mut sampler = build_sampler(config)?;
+        let mut history = token_ids(&tokens);
         let mut pos = tokens.len() as i32;
+        let mut degeneration = DegenerationDetector::new(config.degeneration);
+        let mut hooks = StepHooks::begin(config);
+        let mut reason = FinishReason::MaxTokens;
         let rt = tokio::runtime::Handle::current();
         for i in 0..max_tok {
-            // Use -1 to sample from the last token that had logits computed
-            let tok = sampler.sample(&ctx, -1);
-            sampler.accept(tok);
+            let tok = next_token(sampler.as_mut(), &ctx, logits_idx, &mut history);
+            let action = hooks.step(i, tok.0 as u32, &history, || ctx.get_logits_ith(logits_idx));
             let eog = self.model.is_eog_token(tok);
-            let is_final = eog || i + 1 == max_tok;
+            // A looping stream ends on the token that completed the loop
+            let degenerated = !eog && degeneration.push(tok.0 as u32);
+            let stopped = action == StepAction::Stop;
+            let is_final = eog || degenerated || stopped || i + 1 == max_tok;
             if rt.block_on(sender.send(tok.0 as u32, is_final)).is_err() {
                 break;
             }
-            if eog { break; }
+            if eog || degenerated || stopped {
+                reason = if eog {
+                    FinishReason::Stop
+                } else if degenerated {
+                    FinishReason::Degenerated
+                } else {
+                    FinishReason::EarlyExit
+                };
+                break;
+            }
             batch.clear();
             add_one(&mut batch, tok, pos)?;
             decode(&mut ctx, &mut batch)?;
+            logits_idx = 0;
             pos += 1;
         }
+        hooks.finish(reason);
         Ok(())
     }
 
@@ -113,18 +142,19 @@ impl LlamaBackendInner {
         let mut batch = LlamaBatch::new(tokens.len().max(1), 1);
         add_seq(&mut batch, &tokens)?;
         decode(&mut ctx, &mut batch)?;
-        let mut sampler = build_sampler(&config);
-        sampler.accept_many(tokens.iter().copied());
+        let mut sampler = build_sampler(&config)?;
+        let mut history = context.to_vec();
+        let mut logits_idx = tokens.len().saturating_sub(1) as i32;
         let mut out = Vec::with_capacity(count);
         let mut pos = tokens.len() as i32;
         for _ in 0..count {
-            let tok = sampler.sample(&ctx, -1);
-            sampler.accept(tok);
+            let tok = next_token(sampler.as_mut(), &ctx, logits_idx, &mut history);
             if self.model.is_eog_token(tok) { break; }
             out.push(tok.0 as u32);
             batch.clear();
             add_one(&mut batch, tok, pos)?;
             decode(&mut ctx, &mut batch)?;
+            logits_idx = 0;
             pos += 1;
         }
         Ok(out)
@@ -153,12 +183,12 @@ impl LlamaBackendInner {
                 .map_err(|e| InferenceError::ModelError(format!("batch: {e}")))?;
         }
         decode(&mut ctx, &mut batch)?;
-        let mut sampler = build_sampler(&config);
+        let mut sampler = build_sampler(&config)?;
+        let mut history = context.to_vec();
         // Verify each draft token
         for (i, &draft_tok) in draft.iter().enumerate() {
             let logit_idx = (ctx_len - 1 + i) as i32;
-            let predicted = sampler.sample(&ctx, logit_idx);
-            sampler.accept(predicted);
+            let predicted = next_token(sampler.as_mut(), &ctx, logit_idx, &mut history);
             if predicted.0 as u32 != draft_tok {
                 return Ok(VerifyResult::diverge_at(i, predicted.0 as u32));
             }
@@ -166,6 +196,65 @@ impl LlamaBackendInner {
         Ok(VerifyResult::accept_all(draft.len()))
     }
 
+    /// Prefill `prefix` and keep the resulting context state, so prompts
+    /// that start with it only decode their remaining tokens.
+    pub fn prefill_prefix(&self, prefix: &str) -> Result<bool, InferenceError> {
+        let tokens = self.tokenize(prefix)?;
+        if tokens.is_empty() {
+            return Ok(false);
+        }
+        let mut ctx = self.create_context()?;
+        let mut batch = LlamaBatch::new(tokens.len(), 1);
+        add_seq(&mut batch, &tokens)?;
+        decode(&mut ctx, &mut batch)?;
+        let mut state = vec![0u8; ctx.get_state_size()];
+        // SAFETY: `state` holds get_state_size() bytes for this context
+        let written = unsafe { ctx.copy_state_data(state.as_mut_ptr()) };
+        state.truncate(written);
+        let ids = token_ids(&tokens);
+        self.lock_prefixes().insert(&ids, state, ids.len());
+        Ok(true)
+    }
+
+    /// Bytes held by kept prefix states.
+    pub fn prefix_cache_bytes(&self) -> usize {
+        self.lock_prefixes().memory_bytes()
+    }
+
+    /// Decode `tokens` into a fresh `ctx`, restoring the longest kept
+    /// prefix state first so only the remainder is prefilled. The last
+    /// token is always decoded for its logits; returns their batch index.
+    fn prime<'a>(
+        &'a self,
+        ctx: &mut LlamaContext<'a>,
+        tokens: &[LlamaToken],
+    ) -> Result<i32, InferenceError> {
+        let ids = token_ids(tokens);
+        let cached = match ids.len().checked_sub(1) {
+            Some(n) if n > 0 => self.lock_prefixes().find_prefix(&ids[..n]),
+            _ => None,
+        };
+        let mut start = 0;
+        if let Some((len, state)) = cached {
+            // SAFETY: the state was copied from a context of this model
+            // created with the same parameters
+            let read = unsafe { ctx.set_state_data(state.kv_data()) };
+            if read == state.kv_data().len() {
+                start = len;
+            } else {
+                *ctx = self.create_context()?;
+            }
+        }
+        let mut batch = LlamaBatch::new(tokens.len() - start, 1);
+        add_seq_at(&mut batch, &tokens[start..], start)?;
+        decode(ctx, &mut batch)?;
+        Ok((tokens.len() - start - 1) as i32)
+    }
+
+    fn lock_prefixes(&self) -> MutexGuard<'_, PromptCache> {
+        self.prefixes.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
     /// Get EOS token ID.
     pub fn eos_token(&self) -> Option<u32> {
         Some(self.model.token_eos().0 as u32)
@@ -190,6 +279,41 @@ impl LlamaBackendInner {
         Ok(out)
     }
 
+    /// Score a (query, passage) pair with a cross-encoder (rank pooling).
+    ///
+    /// Pairs use the `<s> query </s></s> passage </s>` layout expected by
+    /// BGE/XLM-R rerankers.
+    pub fn score_pair(&self, query: &str, passage: &str) -> Result<f32, InferenceError> {
+        let eos = self.model.token_eos();
+        let mut tokens = self.tokenize(query)?;
+        tokens.extend([eos, eos]);
+        tokens.extend(
+            self.model
+                .str_to_token(passage, AddBos::Never)
+                .map_err(|e| InferenceError::ModelError(format!("tokenize: {e}")))?,
+        );
+        tokens.push(eos);
+
+        let p = LlamaContextParams::default()
+            .with_n_ctx(NonZeroU32::new(self.n_ctx))
+            .with_n_threads(self.n_threads)
+            .with_n_threads_batch(self.n_threads)
+            .with_embeddings(true)
+            .with_pooling_type(LlamaPoolingType::Rank);
+        let mut ctx = self.model.new_context(&self.backend, p)
+            .map_err(|e| InferenceError::ModelError(format!("ctx: {e}")))?;
+        let mut batch = LlamaBatch::new(tokens.len(), 1);
+        for (i, &tok) in tokens.iter().enumerate() {
+            batch.add(tok, i as i32, &[0], true)
+                .map_err(|e| InferenceError::ModelError(format!("batch: {e}")))?;
+        }
+        decode(&mut ctx, &mut batch)?;
+        let scores = ctx.embeddings_seq_ith(0)
+            .map_err(|e| InferenceError::ModelError(format!("rank: {e}")))?;
+        scores.first().copied()
+            .ok_or_else(|| InferenceError::ModelError("rank: empty output".into()))
+    }
+
     fn create_context(&self) -> Result<LlamaContext<'_>, InferenceError> {
         // Use same thread count for both - simpler and avoids cache contention
         // llama.cpp internally optimizes based on workload
@@ -201,38 +325,59 @@ impl LlamaBackendInner {
             .map_err(|e| InferenceError::ModelError(format!("ctx: {e}")))
     }
 
-    fn sample_loop(
-        &self,
-        ctx: &mut LlamaContext<'_>,
+    fn sample_loop<'a>(
+        &'a self,
+        ctx: &mut LlamaContext<'a>,
         tokens: &[LlamaToken],
         max_tok: u32,
         config: &InferenceConfig,
     ) -> Result<(Vec<LlamaToken>, FinishReason), InferenceError> {
-        let mut batch = LlamaBatch::new(tokens.len(), 1);
-        add_seq(&mut batch, tokens)?;
-        decode(ctx, &mut batch)?;
-        let mut sampler = build_sampler(config);
-        sampler.accept_many(tokens.iter().copied());
+        let mut logits_idx = self.prime(ctx, tokens)?;
+        let mut batch = LlamaBatch::new(1, 1);
+        let mut sampler = build_sampler(config)?;
+        let mut history = token_ids(tokens);
         let mut out = Vec::new();
         let mut pos = tokens.len() as i32;
-        for _ in 0..max_tok {
-            // Use -1 to sample from the last token that had logits computed
-            let tok = sampler.sample(ctx, -1);
-            sampler.accept(tok);
+        let mut degeneration = DegenerationDetector::new(config.degeneration);
+        let mut hooks = StepHooks::begin(config);
+        let mut reason = FinishReason::MaxTokens;
+        for step in 0..max_tok {
+            let tok = next_token(sampler.as_mut(), ctx, logits_idx, &mut history);
+            let action = hooks.step(step, tok.0 as u32, &history, || ctx.get_logits_ith(logits_idx));
             if self.model.is_eog_token(tok) {
-                return Ok((out, FinishReason::Stop));
+                reason = FinishReason::Stop;
+                break;
             }
             out.push(tok);
+            if degeneration.push(tok.0 as u32) {
+                reason = FinishReason::Degenerated;
+                break;
+            }
+            if action == StepAction::Stop {
+                reason = FinishReason::EarlyExit;
+                break;
+            }
             batch.clear();
             add_one(&mut batch, tok, pos)?;
             decode(ctx, &mut batch)?;
+            logits_idx = 0;
             pos += 1;
         }
-        Ok((out, FinishReason::MaxTokens))
+        hooks.finish(reason);
+        Ok((out, reason))
     }
 }
 
 fn add_seq(batch: &mut LlamaBatch, tokens: &[LlamaToken]) -> Result<(), InferenceError> {
+    add_seq_at(batch, tokens, 0)
+}
+
+/// Add `tokens` at positions from `start`.
+fn add_seq_at(
+    batch: &mut LlamaBatch,
+    tokens: &[LlamaToken],
+    start: usize,
+) -> Result<(), InferenceError> {
     // Add all tokens except the last with logits=false
     // Add the last token with logits=true so we can sample from it
     let n = tokens.len();
@@ -241,12 +386,16 @@ fn add_seq(batch: &mut LlamaBatch, tokens: &[LlamaToken]) -> Result<(), Inferenc
     }
     for (i, &tok) in tokens.iter().enumerate() {
         let logits = i == n - 1; // Only compute logits for last token
-        batch.add(tok, i as i32, &[0], logits)
+        batch.add(tok, (start + i) as i32, &[0], logits)
             .map_err(|e| InferenceError::ModelError(format!("batch: {e}")))?;
     }
     Ok(())
 }
 
+fn token_ids(tokens: &[LlamaToken]) -> Vec<u32> {
+    tokens.iter().map(|t| t.0 as u32).collect()
+}
+
 fn add_one(batch: &mut LlamaBatch, tok: LlamaToken, pos: i32) -> Result<(), InferenceError> {
     batch.add(tok, pos, &[0], true)
         .map_err(|e| InferenceError::ModelError(format!("batch: {e}")))
@@ -256,18 +405,21 @@ fn decode(ctx: &mut LlamaContext<'_>, batch: &mut LlamaBatch) -> Result<(), Infe
     ctx.decode(batch).map_err(|e| InferenceError::ModelError(format!("decode: {e}")))
 }
 
-fn build_sampler(config: &InferenceConfig) -> LlamaSampler {
-    let mut s = Vec::new();
-    if config.repetition_penalty > 1.0 {
-        s.push(LlamaSampler::penalties(64, config.repetition_penalty, 0.0, 0.0));
-    }
-    if config.top_k > 0 {
-        s.push(LlamaSampler::top_k(config.top_k as i32));
-    }
-    s.push(LlamaSampler::top_p(config.top_p as f32, 1));
-    s.push(LlamaSampler::temp(config.temperature));
-    s.push(LlamaSampler::dist(42));
-    LlamaSampler::chain_simple(s)
+fn build_sampler(config: &InferenceConfig) -> Result<Box<dyn Sampler>, InferenceError> {
+    sampler::build_sampler(config).map_err(|e| InferenceError::InputValidation(e.to_string()))
+}
+
+/// Sample from the logits at batch index `logits_idx` and append the
+/// token to `history`.
+fn next_token(
+    sampler: &mut dyn Sampler,
+    ctx: &LlamaContext<'_>,
+    logits_idx: i32,
+    history: &mut Vec<u32>,
+) -> LlamaToken {
+    let id = sampler.sample(ctx.get_logits_ith(logits_idx), history);
+    history.push(id);
+    LlamaToken(id as i32)
 }
 
 fn resolve_threads(n: u32) -> i32 {
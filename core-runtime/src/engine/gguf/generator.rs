@@ -13,7 +13,6 @@ use crate::engine::{
 pub struct GgufGenerator {
     model_id: String,
     memory_bytes: AtomicUsize,
-    #[allow(dead_code)]
     context_size: u32,
     #[cfg(feature = "gguf")]
     inner: Option<super::backend::LlamaBackendInner>,
@@ -173,6 +172,11 @@ impl super::GgufModel for GgufGenerator {
                 let result = self.generate_text(&prompt, config)?;
                 Ok(InferenceOutput::Generation(result))
             }
+            InferenceInput::Rerank { .. } => {
+                Err(InferenceError::CapabilityNotSupported(
+                    "reranking requires a cross-encoder model".into(),
+                ))
+            }
             InferenceInput::TextBatch(_) => {
                 Err(InferenceError::CapabilityNotSupported(
                     "batch generation not supported".into(),
@@ -181,6 +185,53 @@ impl super::GgufModel for GgufGenerator {
         }
     }
 
+    async fn prefill_prefix(&self, prefix: &str) -> Result<bool, InferenceError> {
+        #[cfg(feature = "gguf")]
+        {
+            if let Some(inner) = &self.inner {
+                return inner.prefill_prefix(prefix);
+            }
+        }
+        let _ = prefix;
+        Ok(false)
+    }
+
+    fn prefix_cache_bytes(&self) -> usize {
+        #[cfg(feature = "gguf")]
+        {
+            if let Some(inner) = &self.inner {
+                return inner.prefix_cache_bytes();
+            }
+        }
+        0
+    }
+
+    fn count_tokens(&self, text: &str) -> Option<usize> {
+        #[cfg(feature = "gguf")]
+        {
+            if let Some(inner) = &self.inner {
+                return inner.tokenize(text).ok().map(|tokens| tokens.len());
+            }
+        }
+        let _ = text;
+        None
+    }
+
+    fn context_length(&self) -> Option<usize> {
+        Some(self.context_size as usize)
+    }
+
+    fn token_text(&self, token: u32) -> Option<String> {
+        #[cfg(feature = "gguf")]
+        {
+            if let Some(inner) = &self.inner {
+                return inner.detokenize(&[llama_cpp_2::token::LlamaToken(token as i32)]).ok();
+            }
+        }
+        let _ = token;
+        None
+    }
+
     async fn unload(&mut self) -> Result<(), InferenceError> {
         self.memory_bytes.store(0, Ordering::SeqCst);
         #[cfg(feature = "gguf")]
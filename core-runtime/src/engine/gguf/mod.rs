@@ -5,10 +5,12 @@
 #[cfg(feature = "gguf")]
 pub mod backend;
 mod generator;
+mod reranker;
 #[cfg(feature = "gguf")]
 pub mod speculative;
 
 pub use generator::GgufGenerator;
+pub use reranker::GgufReranker;
 #[cfg(feature = "gguf")]
 pub use backend::LlamaBackendInner;
 #[cfg(feature = "gguf")]
@@ -56,6 +58,35 @@ pub trait GgufModel: Send + Sync {
 
     async fn unload(&mut self) -> Result<(), InferenceError>;
 
+    /// Prefill `prefix` and keep its state, so a later prompt starting
+    /// with it only prefills the remainder. Returns whether a state was
+    /// kept; backends without prefix reuse keep nothing.
+    async fn prefill_prefix(&self, _prefix: &str) -> Result<bool, InferenceError> {
+        Ok(false)
+    }
+
+    /// Bytes held by prefix states kept by `prefill_prefix`.
+    fn prefix_cache_bytes(&self) -> usize {
+        0
+    }
+
+    /// Tokens `text` encodes to with the model's own tokenizer; `None`
+    /// when the backend has no tokenizer loaded.
+    fn count_tokens(&self, _text: &str) -> Option<usize> {
+        None
+    }
+
+    /// Context window size in tokens, when the backend has one.
+    fn context_length(&self) -> Option<usize> {
+        None
+    }
+
+    /// Text of one generated token, for screening streamed output; `None`
+    /// when the backend cannot decode it.
+    fn token_text(&self, _token: u32) -> Option<String> {
+        None
+    }
+
     /// Downcast support for streaming access to concrete type.
     fn as_any(&self) -> &dyn std::any::Any;
 }
@@ -75,6 +106,9 @@ pub fn load_gguf_model(
             format!("model file not found: {}", path.display()),
         ));
     }
+    // Reject corrupt headers before llama.cpp parses them
+    crate::models::validate_gguf(path)
+        .map_err(|e| InferenceError::ModelError(format!("invalid GGUF: {e}")))?;
     let generator = GgufGenerator::load(
         model_id.to_string(), path, config,
     )?;
@@ -93,6 +127,38 @@ pub fn load_gguf_model(
     ))
 }
 
+/// Load a GGUF cross-encoder reranker from a file path.
+///
+/// # Errors
+/// Returns error if model file is missing, invalid, or fails to load.
+#[cfg(feature = "gguf")]
+pub fn load_gguf_reranker(
+    path: &Path,
+    model_id: &str,
+    config: &GgufConfig,
+) -> Result<Arc<dyn GgufModel>, InferenceError> {
+    if !path.exists() {
+        return Err(InferenceError::ModelError(
+            format!("model file not found: {}", path.display()),
+        ));
+    }
+    crate::models::validate_gguf(path)
+        .map_err(|e| InferenceError::ModelError(format!("invalid GGUF: {e}")))?;
+    Ok(Arc::new(GgufReranker::load(model_id.to_string(), path, config)?))
+}
+
+/// Stub for non-gguf builds.
+#[cfg(not(feature = "gguf"))]
+pub fn load_gguf_reranker(
+    _path: &Path,
+    _model_id: &str,
+    _config: &GgufConfig,
+) -> Result<Arc<dyn GgufModel>, InferenceError> {
+    Err(InferenceError::ModelError(
+        "GGUF support not compiled in. Enable 'gguf' feature.".into(),
+    ))
+}
+
 /// Validate that a file has the GGUF magic bytes.
 pub fn is_valid_gguf(path: &Path) -> Result<bool, std::io::Error> {
     use std::fs::File;
@@ -0,0 +1,104 @@
+//! GGUF cross-encoder reranker.
+//!
+//! Wraps a reranker model (e.g. bge-reranker) loaded through llama-cpp-2
+//! with rank pooling; each (query, passage) pair yields one relevance score.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::engine::{
+    InferenceCapability, InferenceConfig, InferenceError, InferenceInput, InferenceOutput,
+};
+
+/// GGUF cross-encoder model.
+pub struct GgufReranker {
+    model_id: String,
+    memory_bytes: AtomicUsize,
+    #[cfg(feature = "gguf")]
+    inner: Option<super::backend::LlamaBackendInner>,
+}
+
+impl GgufReranker {
+    /// Create a reranker with no model loaded.
+    pub fn new(model_id: String) -> Self {
+        Self {
+            model_id,
+            memory_bytes: AtomicUsize::new(0),
+            #[cfg(feature = "gguf")]
+            inner: None,
+        }
+    }
+
+    /// Load a reranker from a GGUF file path.
+    #[cfg(feature = "gguf")]
+    pub fn load(
+        model_id: String,
+        path: &std::path::Path,
+        config: &super::GgufConfig,
+    ) -> Result<Self, InferenceError> {
+        let inner = super::backend::LlamaBackendInner::load(path, config)?;
+        Ok(Self {
+            model_id,
+            memory_bytes: AtomicUsize::new(inner.model_size()),
+            inner: Some(inner),
+        })
+    }
+
+    fn score(&self, query: &str, passages: &[String]) -> Result<Vec<f32>, InferenceError> {
+        #[cfg(feature = "gguf")]
+        {
+            if let Some(inner) = &self.inner {
+                return passages.iter().map(|p| inner.score_pair(query, p)).collect();
+            }
+        }
+        #[cfg(not(feature = "gguf"))]
+        let _ = (query, passages);
+        Err(InferenceError::ModelError(format!(
+            "model '{}' not loaded - cannot rerank",
+            self.model_id
+        )))
+    }
+}
+
+#[async_trait::async_trait]
+impl super::GgufModel for GgufReranker {
+    fn model_id(&self) -> &str {
+        &self.model_id
+    }
+
+    fn capabilities(&self) -> &[InferenceCapability] {
+        &[InferenceCapability::Reranking]
+    }
+
+    fn memory_usage(&self) -> usize {
+        self.memory_bytes.load(Ordering::SeqCst)
+    }
+
+    async fn infer(
+        &self,
+        input: &InferenceInput,
+        _config: &InferenceConfig,
+    ) -> Result<InferenceOutput, InferenceError> {
+        input.validate()?;
+        match input {
+            InferenceInput::Rerank { query, passages } => {
+                Ok(InferenceOutput::Rerank(self.score(query, passages)?))
+            }
+            _ => Err(InferenceError::CapabilityNotSupported(
+                "reranker models accept rerank input only".into(),
+            )),
+        }
+    }
+
+    async fn unload(&mut self) -> Result<(), InferenceError> {
+        self.memory_bytes.store(0, Ordering::SeqCst);
+        #[cfg(feature = "gguf")]
+        {
+            self.inner = None;
+        }
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
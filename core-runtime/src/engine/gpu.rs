@@ -37,6 +37,19 @@ impl fmt::Display for GpuBackend {
     }
 }
 
+/// How a device is shared with other workloads
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum GpuSharing {
+    /// The whole device belongs to this process
+    #[default]
+    Exclusive,
+    /// A MIG instance: an isolated slice of a physical GPU with its own
+    /// memory and compute, treated as a device of its own
+    Mig { parent_index: usize, profile: String, uuid: String },
+    /// Shared with other processes through the CUDA Multi-Process Service
+    Mps { active_thread_percentage: Option<u32> },
+}
+
 /// GPU Device Information
 #[derive(Debug, Clone)]
 pub struct GpuDevice {
@@ -52,6 +65,8 @@ pub struct GpuDevice {
     pub available_memory: u64,
     /// Compute capability (CUDA only)
     pub compute_capability: Option<(u32, u32)>,
+    /// MIG partition or MPS sharing (CUDA only)
+    pub sharing: GpuSharing,
 }
 
 impl GpuDevice {
@@ -64,6 +79,7 @@ impl GpuDevice {
             total_memory: 0,
             available_memory: 0,
             compute_capability: None,
+            sharing: GpuSharing::Exclusive,
         }
     }
 
@@ -208,7 +224,7 @@ impl GpuManager {
         #[cfg(feature = "cuda")]
         {
             if let Ok(cuda_devices) = self.detect_cuda_devices() {
-                self.devices.extend(cuda_devices);
+                self.devices.extend(apply_cuda_sharing(cuda_devices));
             }
         }
 
@@ -335,6 +351,111 @@ impl GpuManager {
     }
 }
 
+/// A MIG instance listed by `nvidia-smi -L`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigInstance {
+    /// Index of the physical GPU
+    pub parent_index: usize,
+    /// Instance profile, e.g. `1g.5gb`
+    pub profile: String,
+    pub uuid: String,
+}
+
+impl MigInstance {
+    /// Memory of the instance, from the profile's `<n>gb` suffix
+    pub fn memory_bytes(&self) -> u64 {
+        self.profile
+            .rsplit('.')
+            .next()
+            .and_then(|mem| mem.strip_suffix("gb"))
+            .and_then(|gb| gb.parse::<u64>().ok())
+            .map_or(0, |gb| gb * 1024 * 1024 * 1024)
+    }
+}
+
+/// Parse the MIG instances out of `nvidia-smi -L` output:
+///
+/// ```text
+/// GPU 0: NVIDIA A100-SXM4-40GB (UUID: GPU-...)
+///   MIG 1g.5gb      Device  0: (UUID: MIG-...)
+/// ```
+pub fn parse_mig_listing(listing: &str) -> Vec<MigInstance> {
+    let mut instances = Vec::new();
+    let mut parent = None;
+    for line in listing.lines().map(str::trim) {
+        if let Some(rest) = line.strip_prefix("GPU ") {
+            parent = rest.split(':').next().and_then(|i| i.trim().parse().ok());
+        } else if let (Some(rest), Some(parent_index)) = (line.strip_prefix("MIG "), parent) {
+            let profile = rest.split_whitespace().next().unwrap_or_default().to_string();
+            let uuid = rest
+                .split("UUID:")
+                .nth(1)
+                .map(|u| u.trim().trim_end_matches(')').trim().to_string())
+                .unwrap_or_default();
+            instances.push(MigInstance { parent_index, profile, uuid });
+        }
+    }
+    instances
+}
+
+/// Whether this process is a client of a running MPS daemon, and the
+/// share of SMs it is limited to.
+pub fn detect_mps() -> Option<GpuSharing> {
+    let pipe_dir = std::env::var("CUDA_MPS_PIPE_DIRECTORY").unwrap_or_else(|_| "/tmp/nvidia-mps".to_string());
+    if !std::path::Path::new(&pipe_dir).join("control").exists() {
+        return None;
+    }
+    let active_thread_percentage =
+        std::env::var("CUDA_MPS_ACTIVE_THREAD_PERCENTAGE").ok().and_then(|v| v.trim().parse().ok());
+    Some(GpuSharing::Mps { active_thread_percentage })
+}
+
+/// Split MIG-enabled GPUs into one device per instance and mark devices
+/// shared through MPS. Devices are renumbered so each instance has an
+/// index of its own.
+#[cfg(feature = "cuda")]
+fn apply_cuda_sharing(devices: Vec<GpuDevice>) -> Vec<GpuDevice> {
+    let listing = std::process::Command::new("nvidia-smi")
+        .arg("-L")
+        .output()
+        .ok()
+        .filter(|out| out.status.success())
+        .map(|out| String::from_utf8_lossy(&out.stdout).into_owned())
+        .unwrap_or_default();
+    split_partitions(devices, &parse_mig_listing(&listing), detect_mps())
+}
+
+/// Replace each GPU that has MIG instances by the instances, and apply
+/// `mps` to the rest.
+pub fn split_partitions(devices: Vec<GpuDevice>, mig: &[MigInstance], mps: Option<GpuSharing>) -> Vec<GpuDevice> {
+    let mut out = Vec::new();
+    for device in devices {
+        let instances: Vec<&MigInstance> = mig.iter().filter(|m| m.parent_index == device.index).collect();
+        if instances.is_empty() {
+            out.push(GpuDevice { sharing: mps.clone().unwrap_or_default(), ..device });
+            continue;
+        }
+        for instance in instances {
+            let memory = instance.memory_bytes();
+            out.push(GpuDevice {
+                name: format!("{} MIG {}", device.name, instance.profile),
+                total_memory: memory,
+                available_memory: memory,
+                sharing: GpuSharing::Mig {
+                    parent_index: device.index,
+                    profile: instance.profile.clone(),
+                    uuid: instance.uuid.clone(),
+                },
+                ..device.clone()
+            });
+        }
+    }
+    for (index, device) in out.iter_mut().enumerate() {
+        device.index = index;
+    }
+    out
+}
+
 /// GPU Memory Handle
 pub struct GpuMemory {
     /// Size in bytes
@@ -458,6 +579,35 @@ mod tests {
         assert_eq!(manager.active_device().unwrap().backend, GpuBackend::Cpu);
     }
 
+    #[test]
+    fn test_mig_instances_become_devices() {
+        let listing = "GPU 0: NVIDIA A100-SXM4-40GB (UUID: GPU-aaaa)\n  \
+                       MIG 3g.20gb     Device  0: (UUID: MIG-bbbb)\n  \
+                       MIG 1g.5gb      Device  1: (UUID: MIG-cccc)\n\
+                       GPU 1: NVIDIA L4 (UUID: GPU-dddd)\n";
+        let mig = parse_mig_listing(listing);
+        assert_eq!(mig.len(), 2);
+        assert_eq!(mig[1], MigInstance { parent_index: 0, profile: "1g.5gb".into(), uuid: "MIG-cccc".into() });
+
+        let gpu = |index, name: &str| GpuDevice {
+            backend: GpuBackend::Cuda,
+            index,
+            name: name.to_string(),
+            total_memory: 40 << 30,
+            available_memory: 40 << 30,
+            compute_capability: Some((8, 0)),
+            sharing: GpuSharing::Exclusive,
+        };
+        let mps = Some(GpuSharing::Mps { active_thread_percentage: Some(50) });
+        let devices = split_partitions(vec![gpu(0, "A100"), gpu(1, "L4")], &mig, mps.clone());
+        assert_eq!(devices.len(), 3);
+        assert_eq!(devices[0].name, "A100 MIG 3g.20gb");
+        assert_eq!(devices[0].total_memory, 20 << 30);
+        assert_eq!(devices[1].index, 1);
+        assert!(matches!(devices[1].sharing, GpuSharing::Mig { parent_index: 0, .. }));
+        assert_eq!((devices[2].index, devices[2].sharing.clone()), (2, mps.unwrap()));
+    }
+
     #[test]
     fn test_gpu_memory_pool() {
         let device = Arc::new(GpuDevice::cpu());
@@ -6,7 +6,11 @@ use thiserror::Error;
 use tokio::sync::RwLock;
 
 use crate::engine::gguf::GgufModel;
-use crate::engine::{InferenceConfig, InferenceInput, InferenceOutput};
+use crate::engine::{
+    DegenerationConfig, FinishReason, InferenceCapability, InferenceConfig, InferenceInput,
+    InferenceOutput,
+};
+use crate::engine::sampler;
 use crate::models::ModelHandle;
 
 #[derive(Error, Debug)]
@@ -24,8 +28,10 @@ pub enum InferenceError {
     ContextExceeded { max: usize, got: usize },
 }
 
-/// Parameters controlling inference behavior (IPC protocol).
+/// Parameters controlling inference behavior (IPC protocol). Omitted
+/// fields take their defaults.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct InferenceParams {
     pub max_tokens: usize,
     pub temperature: f32,
@@ -37,6 +43,9 @@ pub struct InferenceParams {
     /// Request timeout in milliseconds. None = no timeout.
     #[serde(default)]
     pub timeout_ms: Option<u64>,
+    /// Registered sampler name. None = the standard sampler.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sampler: Option<String>,
 }
 
 impl Default for InferenceParams {
@@ -48,6 +57,7 @@ impl Default for InferenceParams {
             top_k: 40,
             stream: false,
             timeout_ms: None,
+            sampler: None,
         }
     }
 }
@@ -63,6 +73,9 @@ impl InferenceParams {
         if self.top_p <= 0.0 || self.top_p > 1.0 {
             return Err(InferenceError::InvalidParams("top_p must be in (0, 1]".into()));
         }
+        if let Some(name) = self.sampler.as_deref().filter(|name| !sampler::is_registered(name)) {
+            return Err(InferenceError::InvalidParams(format!("unknown sampler: {}", name)));
+        }
         Ok(())
     }
 
@@ -76,6 +89,8 @@ impl InferenceParams {
             repetition_penalty: 1.1,
             timeout_ms: self.timeout_ms.unwrap_or(30_000),
             max_memory_bytes: None,
+            degeneration: DegenerationConfig::default(),
+            sampler: self.sampler.clone(),
         }
     }
 }
@@ -87,6 +102,35 @@ pub struct InferenceResult {
     pub output: String,
     pub tokens_generated: usize,
     pub finished: bool,
+    /// Why generation stopped.
+    pub finish_reason: FinishReason,
+}
+
+/// Context window usage of one request, in model tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ContextUsage {
+    pub prompt_tokens: usize,
+    pub completion_tokens: usize,
+    pub context_limit: usize,
+    /// Tokens left for follow-up turns in the same context.
+    pub context_remaining: usize,
+}
+
+impl ContextUsage {
+    pub fn new(prompt_tokens: usize, context_limit: usize) -> Self {
+        Self {
+            prompt_tokens,
+            completion_tokens: 0,
+            context_limit,
+            context_remaining: context_limit.saturating_sub(prompt_tokens),
+        }
+    }
+
+    pub fn with_completion(mut self, completion_tokens: usize) -> Self {
+        self.completion_tokens = completion_tokens;
+        self.context_remaining = self.context_limit.saturating_sub(self.prompt_tokens + completion_tokens);
+        self
+    }
 }
 
 /// Executes model inference by delegating to registered models.
@@ -132,6 +176,54 @@ impl InferenceEngine {
         prompt: &str,
         params: &InferenceParams,
     ) -> Result<InferenceResult, InferenceError> {
+        self.execute(model_id, InferenceInput::Text(prompt.to_string()), params)
+            .await
+    }
+
+    /// Score passages against a query with a cross-encoder.
+    /// Returns one score per passage, in request order.
+    pub async fn rerank(
+        &self,
+        model_id: &str,
+        query: &str,
+        passages: Vec<String>,
+    ) -> Result<Vec<f32>, InferenceError> {
+        let count = passages.len();
+        let input = InferenceInput::Rerank { query: query.to_string(), passages };
+        match self.infer_output(model_id, input, &InferenceParams::default()).await? {
+            InferenceOutput::Rerank(scores) if scores.len() == count => Ok(scores),
+            _ => Err(InferenceError::ExecutionFailed(
+                "Model returned no rerank scores".into(),
+            )),
+        }
+    }
+
+    async fn execute(
+        &self,
+        model_id: &str,
+        input: InferenceInput,
+        params: &InferenceParams,
+    ) -> Result<InferenceResult, InferenceError> {
+        // Extract generation result
+        match self.infer_output(model_id, input, params).await? {
+            InferenceOutput::Generation(gen) => Ok(InferenceResult {
+                output: gen.text,
+                tokens_generated: gen.tokens_generated as usize,
+                finished: true,
+                finish_reason: gen.finish_reason,
+            }),
+            _ => Err(InferenceError::ExecutionFailed(
+                "Model returned non-generation output".into(),
+            )),
+        }
+    }
+
+    async fn infer_output(
+        &self,
+        model_id: &str,
+        input: InferenceInput,
+        params: &InferenceParams,
+    ) -> Result<InferenceOutput, InferenceError> {
         params.validate()?;
 
         // Look up model by ID
@@ -140,34 +232,64 @@ impl InferenceEngine {
             InferenceError::ModelNotLoaded(model_id.to_string())
         })?;
 
-        // Check context length (approximate by bytes)
-        if prompt.len() > self.max_context_length {
+        // Check context length (approximate by bytes of text)
+        let text_len = match &input {
+            InferenceInput::Rerank { query, passages } => {
+                query.len() + passages.iter().map(|p| p.len()).max().unwrap_or(0)
+            }
+            other => other.byte_size(),
+        };
+        if text_len > self.max_context_length {
             return Err(InferenceError::ContextExceeded {
                 max: self.max_context_length,
-                got: prompt.len(),
+                got: text_len,
             });
         }
+        if matches!(input, InferenceInput::Rerank { .. })
+            && !model.capabilities().contains(&InferenceCapability::Reranking)
+        {
+            return Err(InferenceError::InvalidParams(format!(
+                "model '{model_id}' does not accept rerank input"
+            )));
+        }
 
         // Convert params to internal config
         let config = params.to_config();
-        let input = InferenceInput::Text(prompt.to_string());
 
         // Delegate to actual model
-        let output = model.infer(&input, &config).await.map_err(|e| {
+        model.infer(&input, &config).await.map_err(|e| {
             InferenceError::ExecutionFailed(e.to_string())
-        })?;
+        })
+    }
 
-        // Extract generation result
-        match output {
-            InferenceOutput::Generation(gen) => Ok(InferenceResult {
-                output: gen.text,
-                tokens_generated: gen.tokens_generated as usize,
-                finished: true,
-            }),
-            _ => Err(InferenceError::ExecutionFailed(
-                "Model returned non-generation output".into(),
-            )),
+    /// Prefill a prompt prefix on `model_id` and keep its state for reuse.
+    /// Returns whether the backend kept anything.
+    pub async fn prefill_prefix(&self, model_id: &str, prefix: &str) -> Result<bool, InferenceError> {
+        let model = self.models.read().await.get(model_id).cloned();
+        let model = model.ok_or_else(|| InferenceError::ModelNotLoaded(model_id.to_string()))?;
+        if prefix.len() > self.max_context_length {
+            return Ok(false);
         }
+        model.prefill_prefix(prefix).await.map_err(|e| InferenceError::ExecutionFailed(e.to_string()))
+    }
+
+    /// Context usage of `prompt` on `model_id`, counted with the model's
+    /// tokenizer. `None` when the model is not loaded or reports no
+    /// tokenizer or context size.
+    pub async fn context_usage(&self, model_id: &str, prompt: &str) -> Option<ContextUsage> {
+        let model = self.models.read().await.get(model_id).cloned()?;
+        let context_limit = model.context_length()?;
+        Some(ContextUsage::new(model.count_tokens(prompt)?, context_limit))
+    }
+
+    /// Decoded text of a token `model_id` generated.
+    pub async fn token_text(&self, model_id: &str, token: u32) -> Option<String> {
+        self.models.read().await.get(model_id)?.token_text(token)
+    }
+
+    /// Bytes of prefix state `model_id` keeps (0 when not loaded).
+    pub async fn prefix_cache_bytes(&self, model_id: &str) -> usize {
+        self.models.read().await.get(model_id).map_or(0, |model| model.prefix_cache_bytes())
     }
 
     /// Run inference by handle (legacy API compatibility).
@@ -278,6 +400,20 @@ mod tests {
         assert!(params.validate().is_err());
     }
 
+    #[test]
+    fn context_usage_remaining_saturates() {
+        let usage = ContextUsage::new(1000, 2048).with_completion(48);
+        assert_eq!(usage.context_remaining, 1000);
+        let full = ContextUsage::new(2000, 2048).with_completion(100);
+        assert_eq!((full.completion_tokens, full.context_remaining), (100, 0));
+    }
+
+    #[tokio::test]
+    async fn engine_context_usage_none_for_unloaded_model() {
+        let engine = InferenceEngine::new(4096);
+        assert!(engine.context_usage("missing-model", "prompt").await.is_none());
+    }
+
     #[tokio::test]
     async fn engine_new_creates_empty_engine() {
         let engine = InferenceEngine::new(4096);
@@ -307,4 +443,15 @@ mod tests {
         let result = engine.run_by_handle(handle, "test", &params).await;
         assert!(matches!(result, Err(InferenceError::ModelNotLoaded(_))));
     }
+
+    #[tokio::test]
+    async fn engine_rerank_requires_cross_encoder() {
+        use crate::engine::gguf::GgufGenerator;
+
+        let engine = InferenceEngine::new(4096);
+        let model = Arc::new(GgufGenerator::new("chat".into(), 2048));
+        engine.register_model("chat".into(), ModelHandle::new(1), model).await;
+        let result = engine.rerank("chat", "query", vec!["passage".into()]).await;
+        assert!(matches!(result, Err(InferenceError::InvalidParams(_))));
+    }
 }
@@ -4,6 +4,7 @@
 //! rejected, not truncated — fail-closed security.
 
 use super::error::InferenceError;
+use super::rerank::MAX_RERANK_PASSAGES;
 
 /// Maximum text input size in bytes (64KB).
 pub const MAX_TEXT_BYTES: usize = 65_536;
@@ -23,6 +24,8 @@ pub enum InferenceInput {
     TextBatch(Vec<String>),
     /// Chat-style messages with typed roles.
     ChatMessages(Vec<ChatMessage>),
+    /// Query and candidate passages for a cross-encoder.
+    Rerank { query: String, passages: Vec<String> },
 }
 
 /// A single message in a chat conversation.
@@ -47,6 +50,7 @@ impl InferenceInput {
             Self::Text(text) => validate_text(text),
             Self::TextBatch(batch) => validate_batch(batch),
             Self::ChatMessages(messages) => validate_messages(messages),
+            Self::Rerank { query, passages } => validate_rerank(query, passages),
         }
     }
 
@@ -56,6 +60,9 @@ impl InferenceInput {
             Self::Text(t) => t.len(),
             Self::TextBatch(b) => b.iter().map(|s| s.len()).sum(),
             Self::ChatMessages(m) => m.iter().map(|m| m.content.len()).sum(),
+            Self::Rerank { query, passages } => {
+                query.len() + passages.iter().map(|p| p.len()).sum::<usize>()
+            }
         }
     }
 }
@@ -113,3 +120,23 @@ fn validate_messages(messages: &[ChatMessage]) -> Result<(), InferenceError> {
     }
     Ok(())
 }
+
+fn validate_rerank(query: &str, passages: &[String]) -> Result<(), InferenceError> {
+    validate_text(query)?;
+    if passages.is_empty() {
+        return Err(InferenceError::InputValidation("passages cannot be empty".into()));
+    }
+    if passages.len() > MAX_RERANK_PASSAGES {
+        return Err(InferenceError::InputValidation(format!(
+            "too many passages: {} > {}",
+            passages.len(),
+            MAX_RERANK_PASSAGES
+        )));
+    }
+    for (i, passage) in passages.iter().enumerate() {
+        validate_text(passage).map_err(|e| {
+            InferenceError::InputValidation(format!("passage {}: {}", i, e))
+        })?;
+    }
+    Ok(())
+}
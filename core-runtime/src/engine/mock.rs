@@ -0,0 +1,327 @@
+//! Scripted mock backend for load tests, chaos experiments and client
+//! development without real weights.
+//!
+//! A `.mock` file is a JSON [`MockSpec`]. It loads like any other model
+//! and answers text prompts with scripted replies. Each reply is paced by
+//! a first-token delay plus a per-token delay, and each request may fail
+//! in a scripted way. Tokens are whitespace-separated words.
+//!
+//! ```json
+//! {
+//!   "responses": ["The answer is 42."],
+//!   "first_token_ms": 150,
+//!   "token_ms": 20,
+//!   "jitter_ms": 5,
+//!   "failures": [
+//!     { "mode": "error", "rate": 0.05 },
+//!     { "mode": "hang", "every": 50 }
+//!   ]
+//! }
+//! ```
+//!
+//! Only built with the `mock` feature, so production builds refuse the
+//! format like any other backend that is not compiled in.
+
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::engine::gguf::GgufModel;
+use crate::engine::{FinishReason, GenerationResult, InferenceCapability, InferenceConfig};
+use crate::engine::{InferenceError, InferenceInput, InferenceOutput};
+
+/// Largest spec file accepted.
+pub const MAX_SPEC_BYTES: u64 = 1024 * 1024;
+
+#[derive(Error, Debug)]
+pub enum MockSpecError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("spec is {0} bytes, limit is {MAX_SPEC_BYTES}")]
+    TooLarge(u64),
+
+    #[error("malformed spec: {0}")]
+    Parse(#[from] serde_json::Error),
+
+    #[error("{0}")]
+    Invalid(String),
+}
+
+/// Scripted behaviour of a mock model.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MockSpec {
+    /// Replies used in turn; empty echoes the prompt.
+    #[serde(default)]
+    pub responses: Vec<String>,
+    /// Delay before the first token.
+    #[serde(default)]
+    pub first_token_ms: u64,
+    /// Delay for each token after the first.
+    #[serde(default)]
+    pub token_ms: u64,
+    /// Random extra delay per token, up to this.
+    #[serde(default)]
+    pub jitter_ms: u64,
+    /// Memory the model reports, e.g. to exercise pool budgets.
+    #[serde(default)]
+    pub memory_bytes: usize,
+    #[serde(default)]
+    pub context_length: Option<usize>,
+    /// Checked in order on every request; the first that fires wins.
+    #[serde(default)]
+    pub failures: Vec<MockFailure>,
+}
+
+/// One injected failure rule.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MockFailure {
+    pub mode: FailureMode,
+    /// Fire on every Nth request.
+    #[serde(default)]
+    pub every: Option<u64>,
+    /// Chance of firing on any request, 0.0 to 1.0.
+    #[serde(default)]
+    pub rate: f64,
+    /// Tokens paced out before the failure, to fail mid-generation.
+    #[serde(default)]
+    pub after_tokens: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureMode {
+    /// The backend returns a model error.
+    Error,
+    /// The backend stalls until the request timeout, then times out.
+    Hang,
+    /// The backend panics.
+    Panic,
+}
+
+impl MockSpec {
+    /// Read and validate the spec at `path`.
+    pub fn load(path: &Path) -> Result<Self, MockSpecError> {
+        let size = std::fs::metadata(path)?.len();
+        if size > MAX_SPEC_BYTES {
+            return Err(MockSpecError::TooLarge(size));
+        }
+        let spec: Self = serde_json::from_slice(&std::fs::read(path)?)?;
+        spec.validate()?;
+        Ok(spec)
+    }
+
+    pub fn validate(&self) -> Result<(), MockSpecError> {
+        for failure in &self.failures {
+            if !(0.0..=1.0).contains(&failure.rate) {
+                return Err(MockSpecError::Invalid(format!("failure rate {} outside 0.0..=1.0", failure.rate)));
+            }
+            if failure.every == Some(0) {
+                return Err(MockSpecError::Invalid("failure every must be at least 1".into()));
+            }
+            if failure.every.is_none() && failure.rate == 0.0 {
+                return Err(MockSpecError::Invalid(format!("{:?} failure never fires", failure.mode)));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A text model that plays back a [`MockSpec`].
+pub struct MockModel {
+    model_id: String,
+    spec: MockSpec,
+    requests: AtomicU64,
+}
+
+impl MockModel {
+    pub fn new(model_id: impl Into<String>, spec: MockSpec) -> Self {
+        Self { model_id: model_id.into(), spec, requests: AtomicU64::new(0) }
+    }
+
+    pub fn spec(&self) -> &MockSpec {
+        &self.spec
+    }
+
+    /// Requests served so far, failed ones included.
+    pub fn requests(&self) -> u64 {
+        self.requests.load(Ordering::Relaxed)
+    }
+
+    fn failure_for(&self, request: u64) -> Option<&MockFailure> {
+        self.spec.failures.iter().find(|failure| {
+            failure.every.is_some_and(|every| request.is_multiple_of(every))
+                || (failure.rate > 0.0 && rand::thread_rng().gen_bool(failure.rate))
+        })
+    }
+
+    /// Time to produce `tokens` tokens.
+    fn pacing(&self, tokens: u32) -> Duration {
+        if tokens == 0 {
+            return Duration::ZERO;
+        }
+        let mut rng = rand::thread_rng();
+        let jitter: u64 = (0..tokens).map(|_| rng.gen_range(0..=self.spec.jitter_ms)).sum();
+        let ms = self.spec.first_token_ms + self.spec.token_ms * u64::from(tokens - 1) + jitter;
+        Duration::from_millis(ms)
+    }
+}
+
+#[async_trait::async_trait]
+impl GgufModel for MockModel {
+    fn model_id(&self) -> &str {
+        &self.model_id
+    }
+
+    fn capabilities(&self) -> &[InferenceCapability] {
+        &[InferenceCapability::TextGeneration]
+    }
+
+    fn memory_usage(&self) -> usize {
+        self.spec.memory_bytes
+    }
+
+    async fn infer(
+        &self,
+        input: &InferenceInput,
+        config: &InferenceConfig,
+    ) -> Result<InferenceOutput, InferenceError> {
+        let InferenceInput::Text(prompt) = input else {
+            return Err(InferenceError::CapabilityNotSupported("mock model takes text only".into()));
+        };
+        let request = self.requests.fetch_add(1, Ordering::Relaxed) + 1;
+
+        if let Some(failure) = self.failure_for(request) {
+            tokio::time::sleep(self.pacing(failure.after_tokens)).await;
+            tracing::debug!(model = %self.model_id, request, mode = ?failure.mode, "Mock failure injected");
+            match failure.mode {
+                FailureMode::Error => {
+                    return Err(InferenceError::ModelError(format!("mock failure on request {request}")));
+                }
+                FailureMode::Hang => {
+                    tokio::time::sleep(Duration::from_millis(config.timeout_ms)).await;
+                    return Err(InferenceError::Timeout(config.timeout_ms));
+                }
+                FailureMode::Panic => panic!("mock panic on request {request}"),
+            }
+        }
+
+        let reply = match self.spec.responses.len() {
+            0 => prompt.as_str(),
+            n => self.spec.responses[((request - 1) % n as u64) as usize].as_str(),
+        };
+        let words: Vec<&str> = reply.split_whitespace().collect();
+        let limit = config.max_tokens.map_or(words.len(), |max| max as usize);
+        let (text, finish_reason) = if words.len() > limit {
+            (words[..limit].join(" "), FinishReason::MaxTokens)
+        } else {
+            (reply.to_string(), FinishReason::Stop)
+        };
+        let tokens_generated = words.len().min(limit) as u32;
+        tokio::time::sleep(self.pacing(tokens_generated)).await;
+
+        Ok(InferenceOutput::Generation(GenerationResult { text, tokens_generated, finish_reason }))
+    }
+
+    async fn unload(&mut self) -> Result<(), InferenceError> {
+        Ok(())
+    }
+
+    fn count_tokens(&self, text: &str) -> Option<usize> {
+        Some(text.split_whitespace().count())
+    }
+
+    fn context_length(&self) -> Option<usize> {
+        self.spec.context_length
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Load a mock model from its spec file.
+///
+/// # Errors
+/// Returns error if the spec is missing, malformed or invalid.
+#[cfg(feature = "mock")]
+pub fn load_mock_model(path: &Path, model_id: &str) -> Result<Arc<dyn GgufModel>, InferenceError> {
+    let spec = MockSpec::load(path).map_err(|e| InferenceError::InvalidFormat(format!("invalid mock spec: {e}")))?;
+    Ok(Arc::new(MockModel::new(model_id, spec)))
+}
+
+/// Stub for non-mock builds.
+#[cfg(not(feature = "mock"))]
+pub fn load_mock_model(_path: &Path, _model_id: &str) -> Result<Arc<dyn GgufModel>, InferenceError> {
+    Err(InferenceError::ModelError(
+        "Mock model support not compiled in. Enable 'mock' feature.".into(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generate(output: InferenceOutput) -> GenerationResult {
+        match output {
+            InferenceOutput::Generation(result) => result,
+            other => panic!("expected generation, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cycles_responses_and_caps_tokens() {
+        let spec = MockSpec { responses: vec!["one two three".into(), "four".into()], ..Default::default() };
+        let model = MockModel::new("m", spec);
+        let input = InferenceInput::Text("hi".into());
+        let capped = InferenceConfig { max_tokens: Some(2), ..Default::default() };
+
+        let first = generate(model.infer(&input, &capped).await.unwrap());
+        assert_eq!((first.text.as_str(), first.finish_reason), ("one two", FinishReason::MaxTokens));
+        let second = generate(model.infer(&input, &capped).await.unwrap());
+        assert_eq!((second.text.as_str(), second.tokens_generated), ("four", 1));
+        assert_eq!(model.requests(), 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_scripted_latency_and_failures() {
+        let spec = MockSpec {
+            first_token_ms: 100,
+            token_ms: 10,
+            failures: vec![
+                MockFailure { mode: FailureMode::Error, every: Some(2), rate: 0.0, after_tokens: 0 },
+                MockFailure { mode: FailureMode::Hang, every: Some(3), rate: 0.0, after_tokens: 0 },
+            ],
+            ..Default::default()
+        };
+        let model = MockModel::new("m", spec);
+        let input = InferenceInput::Text("a b c".into());
+        let config = InferenceConfig { timeout_ms: 5_000, ..Default::default() };
+
+        let start = tokio::time::Instant::now();
+        assert_eq!(generate(model.infer(&input, &config).await.unwrap()).text, "a b c");
+        assert_eq!(start.elapsed(), Duration::from_millis(120));
+        assert!(matches!(model.infer(&input, &config).await, Err(InferenceError::ModelError(_))));
+        assert!(matches!(model.infer(&input, &config).await, Err(InferenceError::Timeout(5_000))));
+    }
+
+    #[test]
+    fn test_spec_validation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("chaos.mock");
+        std::fs::write(&path, r#"{"responses": ["ok"], "failures": [{"mode": "error", "rate": 0.5}]}"#).unwrap();
+        assert_eq!(MockSpec::load(&path).unwrap().failures[0].mode, FailureMode::Error);
+
+        std::fs::write(&path, r#"{"failures": [{"mode": "panic", "rate": 1.5}]}"#).unwrap();
+        assert!(matches!(MockSpec::load(&path), Err(MockSpecError::Invalid(_))));
+        std::fs::write(&path, r#"{"latency": 5}"#).unwrap();
+        assert!(matches!(MockSpec::load(&path), Err(MockSpecError::Parse(_))));
+    }
+}
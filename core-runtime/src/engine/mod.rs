@@ -3,8 +3,12 @@
 //! Handles tokenization, inference execution, and token streaming.
 //! Provides the `InferenceModel` trait and supporting types.
 
+pub mod backends;
 pub mod config;
+pub mod context_docs;
+pub mod cpu_dispatch;
 pub mod decode;
+pub mod degeneration;
 pub mod error;
 pub mod filter;
 pub mod flash_attn;
@@ -12,16 +16,24 @@ pub mod flash_attn_gpu;
 pub mod gguf;
 pub mod gpu;
 pub mod input;
+pub mod mock;
 pub mod onnx;
 pub mod output;
 pub mod prefill;
+pub mod presets;
 pub mod quantize;
+pub mod rerank;
+pub mod safetensors;
+pub mod sampler;
 pub mod simd_matmul;
 mod simd_neon;
 pub mod simd_tokenizer;
 pub mod simd_tokenizer_v2;
 pub mod speculative;
 pub mod speculative_v2;
+pub mod step_hooks;
+pub mod tools;
+pub mod utf8;
 
 // GPU backend modules (conditionally compiled)
 #[cfg(feature = "cuda")]
@@ -35,21 +47,35 @@ pub mod inference;
 mod streaming;
 mod tokenizer;
 
+pub use backends::load_model;
 pub use config::InferenceConfig;
+pub use context_docs::{
+    assemble_context, attribute_citations, AssembledContext, Citation, ContextBudget, ContextDocument,
+    ContextError, DocumentUsage, DocumentUse,
+};
 pub use decode::{DecodeConfig, DecodeExecutor, DecodeStepResult};
+pub use degeneration::{DegenerationConfig, DegenerationDetector};
 pub use error::InferenceError;
 pub use filter::{FilterConfig, OutputFilter};
 pub use flash_attn::{FlashAttn, FlashAttnConfig};
 pub use flash_attn_gpu::{FlashAttnGpuConfig, FlashAttnGpuError, FlashAttnGpuKernel};
-pub use inference::{InferenceEngine, InferenceParams, InferenceResult};
+pub use inference::{ContextUsage, InferenceEngine, InferenceParams, InferenceResult};
 pub use input::{ChatMessage, ChatRole, InferenceInput};
 pub use input::{MAX_BATCH_SIZE, MAX_INPUT_TOKENS, MAX_TEXT_BYTES};
+pub use mock::{load_mock_model, FailureMode, MockFailure, MockModel, MockSpec};
 pub use output::{ClassificationResult, EmbeddingResult, EntityResult};
 pub use output::{FinishReason, GenerationResult, InferenceOutput};
 pub use prefill::{PrefillConfig, PrefillExecutor, PrefillResult};
+pub use presets::{ParameterPreset, PresetCatalog, PresetError, PRESETS_FILE};
 pub use quantize::{QuantFormat, QuantizedTensor, QUANT_BLOCK_SIZE};
+pub use rerank::{rank_passages, RankedPassage, MAX_RERANK_PASSAGES};
+pub use sampler::{register_sampler, Sampler, SamplerError, SamplerFactory, DEFAULT_SAMPLER};
 pub use simd_matmul::{dot_q4, dot_q8, init_simd};
 pub use simd_tokenizer::SimdTokenizer;
+pub use step_hooks::{DecodeStep, StepAction, StepHook, StepHookError, StepHookFactory, StepHooks};
+#[cfg(feature = "step-hooks")]
+pub use step_hooks::{register_step_hook, step_hook_names, unregister_step_hook};
+pub use tools::{ToolCall, ToolDefinition, ToolError};
 pub use simd_tokenizer_v2::{
     SimdTokenizer as SimdTokenizerV2, TokenizerError as TokenizerV2Error, TokenizerStats,
 };
@@ -67,8 +93,11 @@ pub use tokenizer::{TokenizerError, TokenizerWrapper};
 pub use gguf::{GgufConfig, GgufGenerator, GgufModel};
 #[cfg(feature = "gguf")]
 pub use gguf::LlamaBackendInner;
-pub use gpu::{GpuBackend, GpuConfig, GpuDevice, GpuError, GpuManager, GpuMemory, GpuMemoryPool};
+pub use gpu::{
+    GpuBackend, GpuConfig, GpuDevice, GpuError, GpuManager, GpuMemory, GpuMemoryPool, GpuSharing, MigInstance,
+};
 pub use onnx::{OnnxClassifier, OnnxConfig, OnnxEmbedder, OnnxModel};
+pub use safetensors::{load_safetensors_model, SafetensorsModel, SafetensorsTask};
 
 // CUDA backend re-exports
 #[cfg(feature = "cuda")]
@@ -102,4 +131,6 @@ pub enum InferenceCapability {
     TextGeneration,
     Embedding,
     NamedEntityRecognition,
+    /// Scores query/passage pairs (cross-encoder models).
+    Reranking,
 }
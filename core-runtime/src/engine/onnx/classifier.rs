@@ -79,6 +79,9 @@ impl super::OnnxModel for OnnxClassifier {
             InferenceInput::ChatMessages(_) => Err(InferenceError::CapabilityNotSupported(
                 "chat messages not supported for classification".into(),
             )),
+            InferenceInput::Rerank { .. } => Err(InferenceError::CapabilityNotSupported(
+                "reranking not supported for classification".into(),
+            )),
         }
     }
 
@@ -79,6 +79,9 @@ impl super::OnnxModel for OnnxEmbedder {
             InferenceInput::ChatMessages(_) => Err(InferenceError::CapabilityNotSupported(
                 "chat messages not supported for embedding".into(),
             )),
+            InferenceInput::Rerank { .. } => Err(InferenceError::CapabilityNotSupported(
+                "reranking not supported for embedding".into(),
+            )),
         }
     }
 
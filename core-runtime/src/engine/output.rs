@@ -2,6 +2,8 @@
 //!
 //! Each output variant maps to a specific inference capability.
 
+use serde::{Deserialize, Serialize};
+
 /// Output variants for inference operations.
 #[derive(Debug, Clone)]
 pub enum InferenceOutput {
@@ -9,6 +11,8 @@ pub enum InferenceOutput {
     Generation(GenerationResult),
     Embedding(EmbeddingResult),
     Entities(Vec<EntityResult>),
+    /// Relevance scores, one per passage in request order.
+    Rerank(Vec<f32>),
 }
 
 /// Result of text classification inference.
@@ -58,7 +62,8 @@ pub struct EntityResult {
 }
 
 /// Reason why text generation finished.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum FinishReason {
     /// Model emitted stop token naturally.
     Stop,
@@ -68,6 +73,10 @@ pub enum FinishReason {
     Timeout,
     /// Content filter triggered.
     ContentFiltered,
+    /// Stopped early because the output degenerated into repetition.
+    Degenerated,
+    /// Stopped by a decode step hook.
+    EarlyExit,
 }
 
 impl InferenceOutput {
@@ -0,0 +1,186 @@
+//! Named inference parameter presets.
+//!
+//! A preset fixes some generation settings under a name (`deterministic`,
+//! `creative`, `json-strict`, ...) so teams standardize them instead of
+//! copying parameter blobs into every client. Requests select one with
+//! `preset`; the built-in presets can be replaced or extended through
+//! `<base>/presets.json`, a map of name to settings.
+//!
+//! Explicit parameters win over the preset: a preset value only replaces a
+//! parameter the request left at its default.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::InferenceParams;
+
+/// Preset file name within the base path.
+pub const PRESETS_FILE: &str = "presets.json";
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum PresetError {
+    #[error("Unknown parameter preset: {0}")]
+    Unknown(String),
+
+    #[error("Invalid preset file: {0}")]
+    Invalid(String),
+}
+
+/// Settings fixed by a preset; unset fields keep the request's values.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ParameterPreset {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_k: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+}
+
+impl ParameterPreset {
+    /// Fill the parameters `params` leaves at their defaults.
+    pub fn apply(&self, mut params: InferenceParams) -> InferenceParams {
+        let defaults = InferenceParams::default();
+        if let Some(max_tokens) = self.max_tokens.filter(|_| params.max_tokens == defaults.max_tokens) {
+            params.max_tokens = max_tokens;
+        }
+        if let Some(temperature) = self.temperature.filter(|_| params.temperature == defaults.temperature) {
+            params.temperature = temperature;
+        }
+        if let Some(top_p) = self.top_p.filter(|_| params.top_p == defaults.top_p) {
+            params.top_p = top_p;
+        }
+        if let Some(top_k) = self.top_k.filter(|_| params.top_k == defaults.top_k) {
+            params.top_k = top_k;
+        }
+        if params.timeout_ms.is_none() {
+            params.timeout_ms = self.timeout_ms;
+        }
+        params
+    }
+}
+
+/// Presets by name: the built-ins plus any defined in the preset file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PresetCatalog {
+    presets: BTreeMap<String, ParameterPreset>,
+}
+
+impl Default for PresetCatalog {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl PresetCatalog {
+    pub fn builtin() -> Self {
+        let presets = [
+            (
+                "deterministic",
+                ParameterPreset {
+                    description: "Greedy decoding; the same prompt gives the same output".into(),
+                    temperature: Some(0.0),
+                    top_p: Some(1.0),
+                    top_k: Some(1),
+                    ..Default::default()
+                },
+            ),
+            (
+                "creative",
+                ParameterPreset {
+                    description: "Broad sampling for drafting and brainstorming".into(),
+                    temperature: Some(1.0),
+                    top_p: Some(0.95),
+                    top_k: Some(100),
+                    ..Default::default()
+                },
+            ),
+            (
+                "json-strict",
+                ParameterPreset {
+                    description: "Low-variance sampling for structured output".into(),
+                    temperature: Some(0.0),
+                    top_p: Some(1.0),
+                    top_k: Some(1),
+                    max_tokens: Some(1024),
+                    ..Default::default()
+                },
+            ),
+        ];
+        Self { presets: presets.into_iter().map(|(name, preset)| (name.to_string(), preset)).collect() }
+    }
+
+    /// Built-ins overlaid with the presets in `path`; a missing file
+    /// leaves the built-ins.
+    pub fn load(path: &Path) -> Result<Self, PresetError> {
+        let mut catalog = Self::builtin();
+        let data = match fs::read(path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(catalog),
+            Err(e) => return Err(PresetError::Invalid(e.to_string())),
+        };
+        let presets: BTreeMap<String, ParameterPreset> =
+            serde_json::from_slice(&data).map_err(|e| PresetError::Invalid(e.to_string()))?;
+        for (name, preset) in presets {
+            let params = preset.apply(InferenceParams::default());
+            params.validate().map_err(|e| PresetError::Invalid(format!("{}: {}", name, e)))?;
+            catalog.presets.insert(name, preset);
+        }
+        Ok(catalog)
+    }
+
+    pub fn get(&self, name: &str) -> Result<&ParameterPreset, PresetError> {
+        self.presets.get(name).ok_or_else(|| PresetError::Unknown(name.to_string()))
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.presets.keys().map(String::as_str)
+    }
+
+    /// Resolve `params` under the named preset.
+    pub fn resolve(&self, name: &str, params: InferenceParams) -> Result<InferenceParams, PresetError> {
+        Ok(self.get(name)?.apply(params))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explicit_parameters_win() {
+        let catalog = PresetCatalog::builtin();
+        let params = InferenceParams { max_tokens: 64, ..Default::default() };
+        let resolved = catalog.resolve("json-strict", params).unwrap();
+        assert_eq!((resolved.max_tokens, resolved.temperature, resolved.top_k), (64, 0.0, 1));
+        let unknown = catalog.resolve("nope", InferenceParams::default()).unwrap_err();
+        assert_eq!(unknown, PresetError::Unknown("nope".into()));
+    }
+
+    #[test]
+    fn test_file_overrides_builtins() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(PRESETS_FILE);
+        assert_eq!(PresetCatalog::load(&path).unwrap(), PresetCatalog::builtin());
+
+        fs::write(&path, r#"{"creative": {"temperature": 1.2}, "support": {"max_tokens": 512}}"#).unwrap();
+        let catalog = PresetCatalog::load(&path).unwrap();
+        assert_eq!(catalog.get("creative").unwrap().temperature, Some(1.2));
+        assert_eq!(catalog.get("creative").unwrap().top_k, None);
+        assert_eq!(catalog.names().collect::<Vec<_>>(), ["creative", "deterministic", "json-strict", "support"]);
+
+        fs::write(&path, r#"{"broken": {"top_p": 2.0}}"#).unwrap();
+        assert!(matches!(PresetCatalog::load(&path), Err(PresetError::Invalid(_))));
+    }
+}
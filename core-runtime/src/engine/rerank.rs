@@ -0,0 +1,47 @@
+//! Passage reranking for retrieval pipelines.
+//!
+//! A cross-encoder scores each (query, passage) pair jointly; passages are
+//! then ordered by score. Keeping this step in-process means retrieved
+//! passages never leave the sandbox for scoring.
+
+use serde::{Deserialize, Serialize};
+
+/// Maximum passages scored per request.
+pub const MAX_RERANK_PASSAGES: usize = 128;
+
+/// A passage with its relevance score and position in the request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RankedPassage {
+    /// Index into the request's passage list.
+    pub index: usize,
+    pub score: f32,
+    pub text: String,
+}
+
+/// Order passages by descending score, keeping at most `top_n`.
+/// Ties keep request order.
+pub fn rank_passages(passages: Vec<String>, scores: &[f32], top_n: Option<usize>) -> Vec<RankedPassage> {
+    let mut ranked: Vec<RankedPassage> = passages
+        .into_iter()
+        .zip(scores)
+        .enumerate()
+        .map(|(index, (text, &score))| RankedPassage { index, score, text })
+        .collect();
+    ranked.sort_by(|a, b| b.score.total_cmp(&a.score));
+    ranked.truncate(top_n.unwrap_or(usize::MAX));
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rank_orders_and_truncates() {
+        let passages = vec!["a".to_string(), "b".into(), "c".into()];
+        let ranked = rank_passages(passages, &[0.1, 0.9, 0.1], Some(2));
+        assert_eq!(ranked.len(), 2);
+        assert_eq!((ranked[0].index, ranked[0].text.as_str()), (1, "b"));
+        assert_eq!(ranked[1].index, 0);
+    }
+}
@@ -0,0 +1,191 @@
+//! BERT-style encoder forward pass on the CPU.
+//!
+//! Weights use the Hugging Face BERT names, optionally under a `bert.`
+//! prefix (sequence-classification checkpoints). Sequences run one at a
+//! time, so attention needs no padding mask.
+
+use candle_core::safetensors::MmapedSafetensors;
+use candle_core::{Device, Module, Result, Tensor, D};
+use serde::Deserialize;
+
+use super::layers::{Activation, LayerNorm, Linear, Weights};
+
+/// Hyperparameters from the checkpoint's `config.json`.
+#[derive(Debug, Clone, Deserialize)]
+pub(super) struct EncoderConfig {
+    num_attention_heads: usize,
+    #[serde(default = "default_layer_norm_eps")]
+    layer_norm_eps: f64,
+    #[serde(default = "default_max_positions")]
+    pub(super) max_position_embeddings: usize,
+    #[serde(default = "default_hidden_act")]
+    hidden_act: String,
+}
+
+fn default_layer_norm_eps() -> f64 {
+    1e-12
+}
+
+fn default_max_positions() -> usize {
+    512
+}
+
+fn default_hidden_act() -> String {
+    "gelu".into()
+}
+
+struct Layer {
+    query: Linear,
+    key: Linear,
+    value: Linear,
+    attention_output: Linear,
+    attention_norm: LayerNorm,
+    intermediate: Linear,
+    output: Linear,
+    output_norm: LayerNorm,
+}
+
+impl Layer {
+    fn load(weights: &Weights<'_>, index: usize, eps: f64) -> Result<Self> {
+        let name = |part: &str| format!("encoder.layer.{index}.{part}");
+        Ok(Self {
+            query: weights.linear(&name("attention.self.query"))?,
+            key: weights.linear(&name("attention.self.key"))?,
+            value: weights.linear(&name("attention.self.value"))?,
+            attention_output: weights.linear(&name("attention.output.dense"))?,
+            attention_norm: weights.layer_norm(&name("attention.output.LayerNorm"), eps)?,
+            intermediate: weights.linear(&name("intermediate.dense"))?,
+            output: weights.linear(&name("output.dense"))?,
+            output_norm: weights.layer_norm(&name("output.LayerNorm"), eps)?,
+        })
+    }
+
+    fn forward(&self, x: &Tensor, heads: usize, activation: Activation) -> Result<Tensor> {
+        let attended = self.attention_output.forward(&self.attention(x, heads)?)?;
+        let x = self.attention_norm.forward(&(attended + x)?)?;
+        let hidden = activation.apply(&self.intermediate.forward(&x)?)?;
+        self.output_norm.forward(&(self.output.forward(&hidden)? + x)?)
+    }
+
+    /// Multi-head self-attention over `[tokens, hidden]`.
+    fn attention(&self, x: &Tensor, heads: usize) -> Result<Tensor> {
+        let (tokens, hidden) = x.dims2()?;
+        let head_dim = hidden / heads;
+        let split = |t: Tensor| t.reshape((tokens, heads, head_dim))?.transpose(0, 1)?.contiguous();
+        let query = split(self.query.forward(x)?)?;
+        let key = split(self.key.forward(x)?)?;
+        let value = split(self.value.forward(x)?)?;
+
+        let scores = (query.matmul(&key.t()?.contiguous()?)? / (head_dim as f64).sqrt())?;
+        let scores = scores.broadcast_sub(&scores.max_keepdim(D::Minus1)?)?.exp()?;
+        let weights = scores.broadcast_div(&scores.sum_keepdim(D::Minus1)?)?;
+        weights.matmul(&value)?.transpose(0, 1)?.reshape((tokens, hidden))
+    }
+}
+
+/// Cross-encoder head: optional tanh pooler over `[CLS]`, then a linear
+/// classifier.
+struct ClassifierHead {
+    pooler: Option<Linear>,
+    classifier: Linear,
+}
+
+/// Encoder weights, converted to F32 and held in memory.
+pub(super) struct Encoder {
+    config: EncoderConfig,
+    activation: Activation,
+    words: Tensor,
+    positions: Tensor,
+    token_types: Option<Tensor>,
+    embedding_norm: LayerNorm,
+    layers: Vec<Layer>,
+    head: Option<ClassifierHead>,
+}
+
+impl Encoder {
+    /// Load every encoder layer present in the checkpoint, plus the
+    /// classifier head when `with_head` is set.
+    pub(super) fn load(tensors: &MmapedSafetensors, config: EncoderConfig, with_head: bool) -> Result<Self> {
+        let activation = Activation::parse(&config.hidden_act)?;
+        let eps = config.layer_norm_eps;
+        let prefix = if tensors.get("bert.embeddings.word_embeddings.weight").is_ok() { "bert." } else { "" };
+        let weights = Weights { tensors, prefix };
+        let words = weights.get("embeddings.word_embeddings.weight")?;
+        if config.num_attention_heads == 0 || words.dim(1)? % config.num_attention_heads != 0 {
+            candle_core::bail!("hidden size is not a multiple of {} heads", config.num_attention_heads);
+        }
+        let layer_count = (0..)
+            .take_while(|i| tensors.get(&format!("{prefix}encoder.layer.{i}.output.dense.weight")).is_ok())
+            .count();
+        // The classifier sits outside the `bert.` prefix
+        let head = if with_head {
+            let classifier = Weights { tensors, prefix: "" }.linear("classifier")?;
+            Some(ClassifierHead { pooler: weights.linear("pooler.dense").ok(), classifier })
+        } else {
+            None
+        };
+        Ok(Self {
+            activation,
+            words,
+            positions: weights.get("embeddings.position_embeddings.weight")?,
+            token_types: weights.get("embeddings.token_type_embeddings.weight").ok(),
+            embedding_norm: weights.layer_norm("embeddings.LayerNorm", eps)?,
+            layers: (0..layer_count).map(|i| Layer::load(&weights, i, eps)).collect::<Result<_>>()?,
+            head,
+            config,
+        })
+    }
+
+    /// Longest sequence the position embeddings cover.
+    pub(super) fn max_len(&self) -> usize {
+        self.positions.dim(0).unwrap_or(0).min(self.config.max_position_embeddings)
+    }
+
+    /// Bytes held by the loaded weights (LayerNorms aside).
+    pub(super) fn memory_bytes(&self) -> usize {
+        let linear = |l: &Linear| l.weight_t.elem_count() + l.bias.elem_count();
+        let layers: usize = self
+            .layers
+            .iter()
+            .flat_map(|l| [&l.query, &l.key, &l.value, &l.attention_output, &l.intermediate, &l.output])
+            .map(linear)
+            .sum();
+        let head = self.head.as_ref().map_or(0, |h| linear(&h.classifier) + h.pooler.as_ref().map_or(0, linear));
+        let embeddings = self.words.elem_count() + self.positions.elem_count();
+        let types = self.token_types.as_ref().map_or(0, Tensor::elem_count);
+        (layers + head + embeddings + types) * std::mem::size_of::<f32>()
+    }
+
+    /// Final hidden states, `[tokens, hidden]`.
+    pub(super) fn forward(&self, ids: &[u32], segments: &[u32]) -> Result<Tensor> {
+        let device = Device::Cpu;
+        let positions = Tensor::arange(0u32, ids.len() as u32, &device)?;
+        let mut x = (self.words.index_select(&Tensor::new(ids, &device)?, 0)?
+            + self.positions.index_select(&positions, 0)?)?;
+        if let Some(types) = &self.token_types {
+            x = (x + types.index_select(&Tensor::new(segments, &device)?, 0)?)?;
+        }
+        let mut x = self.embedding_norm.forward(&x)?;
+        for layer in &self.layers {
+            x = layer.forward(&x, self.config.num_attention_heads, self.activation)?;
+        }
+        Ok(x)
+    }
+
+    /// Relevance score from the classifier head: the logit of a one-label
+    /// head, or relevant minus irrelevant for a two-label head.
+    pub(super) fn score(&self, hidden: &Tensor) -> Result<f32> {
+        let Some(head) = &self.head else {
+            candle_core::bail!("checkpoint has no classifier head");
+        };
+        let mut cls = hidden.narrow(0, 0, 1)?;
+        if let Some(pooler) = &head.pooler {
+            cls = pooler.forward(&cls)?.tanh()?;
+        }
+        match head.classifier.forward(&cls)?.flatten_all()?.to_vec1::<f32>()?.as_slice() {
+            [score] => Ok(*score),
+            [irrelevant, relevant] => Ok(relevant - irrelevant),
+            labels => candle_core::bail!("classifier has {} labels, expected 1 or 2", labels.len()),
+        }
+    }
+}
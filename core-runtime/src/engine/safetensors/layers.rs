@@ -0,0 +1,88 @@
+//! Building blocks of the encoder: weight lookup, linear layers, layer
+//! norms and activations.
+
+use candle_core::safetensors::MmapedSafetensors;
+use candle_core::{DType, Device, Module, Result, Tensor, D};
+
+/// Feed-forward activation named by `hidden_act`.
+#[derive(Debug, Clone, Copy)]
+pub(super) enum Activation {
+    /// Exact (erf) GELU, BERT's `gelu`.
+    Gelu,
+    /// Tanh approximation, `gelu_new` / `gelu_pytorch_tanh`.
+    GeluTanh,
+    Relu,
+}
+
+impl Activation {
+    pub(super) fn parse(name: &str) -> Result<Self> {
+        match name {
+            "gelu" => Ok(Self::Gelu),
+            "gelu_new" | "gelu_pytorch_tanh" => Ok(Self::GeluTanh),
+            "relu" => Ok(Self::Relu),
+            other => candle_core::bail!("unsupported hidden_act '{other}'"),
+        }
+    }
+
+    pub(super) fn apply(self, x: &Tensor) -> Result<Tensor> {
+        match self {
+            Self::Gelu => x.gelu_erf(),
+            Self::GeluTanh => x.gelu(),
+            Self::Relu => x.relu(),
+        }
+    }
+}
+
+/// Reads named tensors as F32, under the checkpoint's name prefix.
+pub(super) struct Weights<'a> {
+    pub(super) tensors: &'a MmapedSafetensors,
+    pub(super) prefix: &'static str,
+}
+
+impl Weights<'_> {
+    pub(super) fn get(&self, name: &str) -> Result<Tensor> {
+        self.tensors.load(&format!("{}{name}", self.prefix), &Device::Cpu)?.to_dtype(DType::F32)
+    }
+
+    pub(super) fn linear(&self, name: &str) -> Result<Linear> {
+        Ok(Linear {
+            weight_t: self.get(&format!("{name}.weight"))?.t()?.contiguous()?,
+            bias: self.get(&format!("{name}.bias"))?,
+        })
+    }
+
+    /// Older checkpoints name the LayerNorm parameters gamma and beta.
+    pub(super) fn layer_norm(&self, name: &str, eps: f64) -> Result<LayerNorm> {
+        let (weight, bias) = match self.get(&format!("{name}.weight")) {
+            Ok(weight) => (weight, self.get(&format!("{name}.bias"))?),
+            Err(_) => (self.get(&format!("{name}.gamma"))?, self.get(&format!("{name}.beta"))?),
+        };
+        Ok(LayerNorm { weight, bias, eps })
+    }
+}
+
+pub(super) struct Linear {
+    /// Stored transposed, `[in, out]`, so rows multiply directly.
+    pub(super) weight_t: Tensor,
+    pub(super) bias: Tensor,
+}
+
+impl Module for Linear {
+    fn forward(&self, x: &Tensor) -> Result<Tensor> {
+        x.matmul(&self.weight_t)?.broadcast_add(&self.bias)
+    }
+}
+
+pub(super) struct LayerNorm {
+    weight: Tensor,
+    bias: Tensor,
+    eps: f64,
+}
+
+impl Module for LayerNorm {
+    fn forward(&self, x: &Tensor) -> Result<Tensor> {
+        let centered = x.broadcast_sub(&x.mean_keepdim(D::Minus1)?)?;
+        let std = (centered.sqr()?.mean_keepdim(D::Minus1)? + self.eps)?.sqrt()?;
+        centered.broadcast_div(&std)?.broadcast_mul(&self.weight)?.broadcast_add(&self.bias)
+    }
+}
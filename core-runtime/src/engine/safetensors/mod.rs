@@ -0,0 +1,245 @@
+//! Safetensors inference backend using Candle.
+//!
+//! Serves BERT-style encoder checkpoints laid out as on the Hugging Face
+//! hub: the weights file sits next to its `config.json` and WordPiece
+//! `vocab.txt`, with optional `tokenizer_config.json` (`do_lower_case`)
+//! and sentence-transformers `1_Pooling/config.json`. Embedding models
+//! return the L2-normalized mean (or `[CLS]`) pooling of the final hidden
+//! states; rerankers score each (query, passage) pair with the checkpoint's
+//! classifier head.
+
+#[cfg(feature = "safetensors")]
+mod encoder;
+#[cfg(feature = "safetensors")]
+mod layers;
+#[cfg(feature = "safetensors")]
+mod wordpiece;
+
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::engine::gguf::GgufModel;
+use crate::engine::{InferenceCapability, InferenceConfig, InferenceError};
+use crate::engine::{InferenceInput, InferenceOutput};
+#[cfg(feature = "safetensors")]
+use crate::engine::EmbeddingResult;
+
+/// Which head a safetensors checkpoint provides.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SafetensorsTask {
+    Embedding,
+    Reranking,
+}
+
+/// How token states are pooled into one embedding.
+#[cfg(feature = "safetensors")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pooling {
+    Mean,
+    Cls,
+}
+
+/// A loaded encoder and its tokenizer.
+#[cfg(feature = "safetensors")]
+struct Runtime {
+    encoder: encoder::Encoder,
+    tokenizer: wordpiece::WordPiece,
+    pooling: Pooling,
+}
+
+/// Safetensors encoder checkpoint.
+pub struct SafetensorsModel {
+    model_id: String,
+    #[cfg(feature = "safetensors")]
+    task: SafetensorsTask,
+    capabilities: [InferenceCapability; 1],
+    tensor_count: usize,
+    memory_bytes: usize,
+    #[cfg(feature = "safetensors")]
+    runtime: Option<Runtime>,
+}
+
+impl SafetensorsModel {
+    /// Number of tensors in the checkpoint.
+    pub fn tensor_count(&self) -> usize {
+        self.tensor_count
+    }
+
+    #[cfg(feature = "safetensors")]
+    fn run(&self, input: &InferenceInput) -> Result<InferenceOutput, InferenceError> {
+        let runtime = self.runtime.as_ref().ok_or_else(|| {
+            InferenceError::ModelError(format!("safetensors model '{}' is unloaded", self.model_id))
+        })?;
+        match (self.task, input) {
+            (SafetensorsTask::Embedding, InferenceInput::Text(text)) => runtime.embed(text),
+            (SafetensorsTask::Embedding, InferenceInput::TextBatch(batch)) if batch.len() == 1 => {
+                runtime.embed(&batch[0])
+            }
+            (SafetensorsTask::Reranking, InferenceInput::Rerank { query, passages }) => {
+                runtime.rerank(query, passages)
+            }
+            (SafetensorsTask::Embedding, _) => Err(InferenceError::CapabilityNotSupported(
+                "embedding models accept a single text only".into(),
+            )),
+            (SafetensorsTask::Reranking, _) => Err(InferenceError::CapabilityNotSupported(
+                "reranker models accept rerank input only".into(),
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "safetensors")]
+impl Runtime {
+    fn embed(&self, text: &str) -> Result<InferenceOutput, InferenceError> {
+        let ids = self.tokenizer.encode(text, self.encoder.max_len());
+        let hidden = self.encoder.forward(&ids, &vec![0; ids.len()]).map_err(model_error)?;
+        let pooled = match self.pooling {
+            Pooling::Mean => hidden.mean(0),
+            Pooling::Cls => hidden.get(0),
+        };
+        let mut vector = pooled.and_then(|t| t.to_vec1::<f32>()).map_err(model_error)?;
+        let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt().max(f32::EPSILON);
+        vector.iter_mut().for_each(|v| *v /= norm);
+        Ok(InferenceOutput::Embedding(EmbeddingResult { dimensions: vector.len(), vector }))
+    }
+
+    fn rerank(&self, query: &str, passages: &[String]) -> Result<InferenceOutput, InferenceError> {
+        let scores = passages
+            .iter()
+            .map(|passage| {
+                let (ids, segments) = self.tokenizer.encode_pair(query, passage, self.encoder.max_len());
+                self.encoder.forward(&ids, &segments).and_then(|hidden| self.encoder.score(&hidden))
+            })
+            .collect::<Result<_, _>>()
+            .map_err(model_error)?;
+        Ok(InferenceOutput::Rerank(scores))
+    }
+}
+
+#[cfg(feature = "safetensors")]
+fn model_error(e: impl std::fmt::Display) -> InferenceError {
+    InferenceError::ModelError(format!("safetensors inference failed: {e}"))
+}
+
+#[async_trait::async_trait]
+impl GgufModel for SafetensorsModel {
+    fn model_id(&self) -> &str {
+        &self.model_id
+    }
+
+    fn capabilities(&self) -> &[InferenceCapability] {
+        &self.capabilities
+    }
+
+    fn memory_usage(&self) -> usize {
+        self.memory_bytes
+    }
+
+    async fn infer(
+        &self,
+        input: &InferenceInput,
+        _config: &InferenceConfig,
+    ) -> Result<InferenceOutput, InferenceError> {
+        input.validate()?;
+        #[cfg(feature = "safetensors")]
+        return self.run(input);
+        #[cfg(not(feature = "safetensors"))]
+        Err(InferenceError::ModelError(format!(
+            "safetensors model '{}' needs the 'safetensors' feature",
+            self.model_id
+        )))
+    }
+
+    async fn unload(&mut self) -> Result<(), InferenceError> {
+        self.memory_bytes = 0;
+        #[cfg(feature = "safetensors")]
+        {
+            self.runtime = None;
+        }
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Load a safetensors checkpoint from a file path.
+///
+/// # Errors
+/// Returns error if the file is missing, fails header validation, lacks
+/// its `config.json` or `vocab.txt`, or is not a BERT-style encoder.
+#[cfg(feature = "safetensors")]
+pub fn load_safetensors_model(
+    path: &Path,
+    model_id: &str,
+    task: SafetensorsTask,
+) -> Result<Arc<dyn GgufModel>, InferenceError> {
+    if !path.exists() {
+        return Err(InferenceError::ModelError(
+            format!("model file not found: {}", path.display()),
+        ));
+    }
+    let summary = crate::models::validate_safetensors(path)
+        .map_err(|e| InferenceError::ModelError(format!("invalid safetensors: {e}")))?;
+    let dir = path.parent().unwrap_or(Path::new("."));
+    let config = read_json(&dir.join("config.json"))?
+        .ok_or_else(|| InferenceError::ModelError(format!("no config.json next to {}", path.display())))?;
+    let (tokenizer, pooling) = load_side_files(dir)?;
+
+    // SAFETY: read-only mapping, dropped once the weights are copied out
+    let tensors = unsafe { candle_core::safetensors::MmapedSafetensors::new(path) }
+        .map_err(|e| InferenceError::ModelError(format!("failed to map safetensors: {e}")))?;
+    let encoder = encoder::Encoder::load(&tensors, config, task == SafetensorsTask::Reranking)
+        .map_err(|e| InferenceError::ModelError(format!("not a supported encoder checkpoint: {e}")))?;
+    let capability = match task {
+        SafetensorsTask::Embedding => InferenceCapability::Embedding,
+        SafetensorsTask::Reranking => InferenceCapability::Reranking,
+    };
+    Ok(Arc::new(SafetensorsModel {
+        model_id: model_id.to_string(),
+        task,
+        capabilities: [capability],
+        tensor_count: summary.tensor_count,
+        memory_bytes: encoder.memory_bytes(),
+        runtime: Some(Runtime { encoder, tokenizer, pooling }),
+    }))
+}
+
+/// Tokenizer and pooling mode from the files next to the weights.
+#[cfg(feature = "safetensors")]
+fn load_side_files(dir: &Path) -> Result<(wordpiece::WordPiece, Pooling), InferenceError> {
+    let lowercase = read_json::<serde_json::Value>(&dir.join("tokenizer_config.json"))?
+        .and_then(|c| c["do_lower_case"].as_bool())
+        .unwrap_or(true);
+    let cls_pooling = read_json::<serde_json::Value>(&dir.join("1_Pooling").join("config.json"))?
+        .and_then(|c| c["pooling_mode_cls_token"].as_bool())
+        .unwrap_or(false);
+    let tokenizer = wordpiece::WordPiece::load(&dir.join("vocab.txt"), lowercase)?;
+    Ok((tokenizer, if cls_pooling { Pooling::Cls } else { Pooling::Mean }))
+}
+
+/// Parse an optional JSON side file; `None` if it does not exist.
+#[cfg(feature = "safetensors")]
+fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Result<Option<T>, InferenceError> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(InferenceError::ModelError(format!("cannot read {}: {e}", path.display()))),
+    };
+    serde_json::from_str(&text)
+        .map(Some)
+        .map_err(|e| InferenceError::ModelError(format!("invalid {}: {e}", path.display())))
+}
+
+/// Stub for non-safetensors builds.
+#[cfg(not(feature = "safetensors"))]
+pub fn load_safetensors_model(
+    _path: &Path,
+    _model_id: &str,
+    _task: SafetensorsTask,
+) -> Result<Arc<dyn GgufModel>, InferenceError> {
+    Err(InferenceError::ModelError(
+        "Safetensors support not compiled in. Enable 'safetensors' feature.".into(),
+    ))
+}
@@ -0,0 +1,171 @@
+//! BERT WordPiece tokenization from a `vocab.txt`.
+//!
+//! Follows BERT's reference tokenizer: split on whitespace and punctuation,
+//! optionally lowercase and strip accents, then greedily match the longest
+//! vocabulary entry, continuing words with `##` pieces.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::engine::InferenceError;
+
+/// Words longer than this become `[UNK]`, as in the reference tokenizer.
+const MAX_WORD_CHARS: usize = 100;
+
+pub(super) struct WordPiece {
+    vocab: HashMap<String, u32>,
+    lowercase: bool,
+    cls: u32,
+    sep: u32,
+    unk: u32,
+}
+
+impl WordPiece {
+    /// Read a `vocab.txt`: one token per line, its id the line number.
+    pub(super) fn load(path: &Path, lowercase: bool) -> Result<Self, InferenceError> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| InferenceError::ModelError(format!("cannot read {}: {e}", path.display())))?;
+        Self::from_vocab(text.lines(), lowercase)
+    }
+
+    pub(super) fn from_vocab<'a>(
+        tokens: impl IntoIterator<Item = &'a str>,
+        lowercase: bool,
+    ) -> Result<Self, InferenceError> {
+        let vocab: HashMap<String, u32> =
+            tokens.into_iter().enumerate().map(|(id, token)| (token.to_string(), id as u32)).collect();
+        let special = |name: &str| {
+            vocab.get(name).copied().ok_or_else(|| InferenceError::ModelError(format!("vocabulary has no {name}")))
+        };
+        Ok(Self { cls: special("[CLS]")?, sep: special("[SEP]")?, unk: special("[UNK]")?, vocab, lowercase })
+    }
+
+    /// `[CLS] text [SEP]`, at most `max_len` ids.
+    pub(super) fn encode(&self, text: &str, max_len: usize) -> Vec<u32> {
+        let mut ids = vec![self.cls];
+        ids.extend(self.pieces(text).into_iter().take(max_len.saturating_sub(2)));
+        ids.push(self.sep);
+        ids
+    }
+
+    /// `[CLS] first [SEP] second [SEP]` and segment ids, at most `max_len`
+    /// ids. The second text is cut first, so a query survives long passages.
+    pub(super) fn encode_pair(&self, first: &str, second: &str, max_len: usize) -> (Vec<u32>, Vec<u32>) {
+        let budget = max_len.saturating_sub(3);
+        let mut first = self.pieces(first);
+        first.truncate(budget);
+        let mut second = self.pieces(second);
+        second.truncate(budget - first.len());
+
+        let mut ids = vec![self.cls];
+        ids.extend(first);
+        ids.push(self.sep);
+        let first_len = ids.len();
+        ids.extend(second);
+        ids.push(self.sep);
+        let segments = (0..ids.len()).map(|i| u32::from(i >= first_len)).collect();
+        (ids, segments)
+    }
+
+    fn pieces(&self, text: &str) -> Vec<u32> {
+        self.words(text).iter().flat_map(|word| self.word_pieces(word)).collect()
+    }
+
+    /// Whitespace- and punctuation-separated words; punctuation marks are
+    /// words of their own.
+    fn words(&self, text: &str) -> Vec<String> {
+        let text: String = if self.lowercase {
+            text.to_lowercase().nfd().filter(|c| !is_combining_mark(*c)).collect()
+        } else {
+            text.to_string()
+        };
+        let mut words = Vec::new();
+        let mut current = String::new();
+        for c in text.chars() {
+            if c.is_whitespace() || c.is_control() || is_punctuation(c) {
+                if !current.is_empty() {
+                    words.push(std::mem::take(&mut current));
+                }
+                if is_punctuation(c) {
+                    words.push(c.to_string());
+                }
+            } else {
+                current.push(c);
+            }
+        }
+        if !current.is_empty() {
+            words.push(current);
+        }
+        words
+    }
+
+    /// Greedy longest-match pieces of one word; `[UNK]` if any part of it
+    /// has no match.
+    fn word_pieces(&self, word: &str) -> Vec<u32> {
+        if word.chars().count() > MAX_WORD_CHARS {
+            return vec![self.unk];
+        }
+        let mut pieces = Vec::new();
+        let mut start = 0;
+        while start < word.len() {
+            let mut end = word.len();
+            let id = loop {
+                let piece = match start {
+                    0 => word[..end].to_string(),
+                    _ => format!("##{}", &word[start..end]),
+                };
+                if let Some(&id) = self.vocab.get(&piece) {
+                    break id;
+                }
+                match word[start..end].char_indices().next_back() {
+                    Some((last, _)) if last > 0 => end = start + last,
+                    _ => return vec![self.unk],
+                }
+            };
+            pieces.push(id);
+            start = end;
+        }
+        pieces
+    }
+}
+
+/// ASCII punctuation plus any other non-alphanumeric, visible character.
+fn is_punctuation(c: char) -> bool {
+    c.is_ascii_punctuation() || (!c.is_ascii() && !c.is_alphanumeric() && !c.is_whitespace() && !c.is_control())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokenizer() -> WordPiece {
+        let vocab = ["[PAD]", "[UNK]", "[CLS]", "[SEP]", "un", "##aff", "##able", "cafe", ",", "hello"];
+        WordPiece::from_vocab(vocab, true).unwrap()
+    }
+
+    #[test]
+    fn test_longest_match_pieces() {
+        let wp = tokenizer();
+        assert_eq!(wp.encode("Unaffable, hello", 16), vec![2, 4, 5, 6, 8, 9, 3]);
+        // Accents are stripped when lowercasing; unknown words are [UNK]
+        assert_eq!(wp.encode("Café xyz", 16), vec![2, 7, 1, 3]);
+    }
+
+    #[test]
+    fn test_truncation_keeps_special_tokens() {
+        let wp = tokenizer();
+        assert_eq!(wp.encode("hello hello hello", 4), vec![2, 9, 9, 3]);
+
+        let (ids, segments) = wp.encode_pair("hello", "cafe cafe cafe", 6);
+        assert_eq!(ids, vec![2, 9, 3, 7, 7, 3]);
+        assert_eq!(segments, vec![0, 0, 0, 1, 1, 1]);
+    }
+
+    #[test]
+    fn test_vocabulary_needs_special_tokens() {
+        assert!(WordPiece::from_vocab(["[CLS]", "[SEP]"], true).is_err());
+    }
+}
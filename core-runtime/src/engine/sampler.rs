@@ -0,0 +1,274 @@
+//! Pluggable token samplers.
+//!
+//! A sampler picks the next token from the logits of the last decoded
+//! position. Samplers are registered by name and selected per request with
+//! `parameters.sampler`; requests without one use `standard`. Forks add
+//! strategies (Mirostat, contrastive search, ...) by registering a factory
+//! at startup with [`register_sampler`].
+//!
+//! Every sampler built through the registry reports
+//! `core_sampler_requests_total` and `core_sampler_tokens_total`, labelled
+//! by sampler name.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, OnceLock, RwLock};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use thiserror::Error;
+
+use super::InferenceConfig;
+use crate::telemetry;
+
+/// Sampler used when a request names none.
+pub const DEFAULT_SAMPLER: &str = "standard";
+
+/// Recent tokens considered by the repetition penalty.
+const PENALTY_WINDOW: usize = 64;
+
+/// Seed of the standard sampler's random draw.
+const SAMPLER_SEED: u64 = 42;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum SamplerError {
+    #[error("Unknown sampler: {0}")]
+    Unknown(String),
+
+    #[error("Sampler already registered: {0}")]
+    AlreadyRegistered(String),
+}
+
+/// Chooses tokens for one generation.
+pub trait Sampler: Send {
+    /// Pick the next token. `logits` covers the vocabulary for the last
+    /// decoded position; `history` holds the prompt and the tokens
+    /// generated so far.
+    fn sample(&mut self, logits: &[f32], history: &[u32]) -> u32;
+}
+
+/// Builds a sampler for each generation.
+pub trait SamplerFactory: Send + Sync {
+    fn build(&self, config: &InferenceConfig) -> Box<dyn Sampler>;
+}
+
+impl<F> SamplerFactory for F
+where
+    F: Fn(&InferenceConfig) -> Box<dyn Sampler> + Send + Sync,
+{
+    fn build(&self, config: &InferenceConfig) -> Box<dyn Sampler> {
+        self(config)
+    }
+}
+
+type Registry = RwLock<BTreeMap<String, Arc<dyn SamplerFactory>>>;
+
+fn registry() -> &'static Registry {
+    static REGISTRY: OnceLock<Registry> = OnceLock::new();
+    REGISTRY.get_or_init(|| {
+        let mut samplers: BTreeMap<String, Arc<dyn SamplerFactory>> = BTreeMap::new();
+        samplers.insert(DEFAULT_SAMPLER.to_string(), Arc::new(StandardSampler::factory));
+        samplers.insert("greedy".to_string(), Arc::new(GreedySampler::factory));
+        RwLock::new(samplers)
+    })
+}
+
+/// Register a sampler under `name`. Names are never replaced, so a fork
+/// cannot silently change what an existing name means.
+pub fn register_sampler(name: &str, factory: Arc<dyn SamplerFactory>) -> Result<(), SamplerError> {
+    let mut samplers = registry().write().unwrap_or_else(|e| e.into_inner());
+    if samplers.contains_key(name) {
+        return Err(SamplerError::AlreadyRegistered(name.to_string()));
+    }
+    samplers.insert(name.to_string(), factory);
+    Ok(())
+}
+
+pub fn is_registered(name: &str) -> bool {
+    registry().read().unwrap_or_else(|e| e.into_inner()).contains_key(name)
+}
+
+/// Registered sampler names, sorted.
+pub fn sampler_names() -> Vec<String> {
+    registry().read().unwrap_or_else(|e| e.into_inner()).keys().cloned().collect()
+}
+
+/// Build the sampler `config` selects.
+pub fn build_sampler(config: &InferenceConfig) -> Result<Box<dyn Sampler>, SamplerError> {
+    let name = config.sampler.as_deref().unwrap_or(DEFAULT_SAMPLER);
+    let factory = registry()
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(name)
+        .cloned()
+        .ok_or_else(|| SamplerError::Unknown(name.to_string()))?;
+    Ok(Box::new(Metered { inner: factory.build(config), name: name.to_string(), tokens: 0 }))
+}
+
+/// Counts tokens and reports them when the generation ends.
+struct Metered {
+    inner: Box<dyn Sampler>,
+    name: String,
+    tokens: u64,
+}
+
+impl Sampler for Metered {
+    fn sample(&mut self, logits: &[f32], history: &[u32]) -> u32 {
+        self.tokens += 1;
+        self.inner.sample(logits, history)
+    }
+}
+
+impl Drop for Metered {
+    fn drop(&mut self) {
+        telemetry::record_sampler_usage(&self.name, self.tokens);
+    }
+}
+
+/// Always picks the most likely token.
+pub struct GreedySampler;
+
+impl GreedySampler {
+    fn factory(_config: &InferenceConfig) -> Box<dyn Sampler> {
+        Box::new(Self)
+    }
+}
+
+impl Sampler for GreedySampler {
+    fn sample(&mut self, logits: &[f32], _history: &[u32]) -> u32 {
+        argmax(logits)
+    }
+}
+
+/// Repetition penalty, top-k, top-p and temperature, then a seeded draw.
+pub struct StandardSampler {
+    temperature: f32,
+    top_p: f32,
+    top_k: usize,
+    repetition_penalty: f32,
+    rng: StdRng,
+}
+
+impl StandardSampler {
+    pub fn new(config: &InferenceConfig) -> Self {
+        Self {
+            temperature: config.temperature,
+            top_p: config.top_p,
+            top_k: config.top_k as usize,
+            repetition_penalty: config.repetition_penalty,
+            rng: StdRng::seed_from_u64(SAMPLER_SEED),
+        }
+    }
+
+    fn factory(config: &InferenceConfig) -> Box<dyn Sampler> {
+        Box::new(Self::new(config))
+    }
+}
+
+impl Sampler for StandardSampler {
+    fn sample(&mut self, logits: &[f32], history: &[u32]) -> u32 {
+        let mut candidates: Vec<(u32, f32)> = logits.iter().enumerate().map(|(id, &l)| (id as u32, l)).collect();
+        if self.repetition_penalty > 1.0 {
+            let recent = &history[history.len().saturating_sub(PENALTY_WINDOW)..];
+            for (id, logit) in candidates.iter_mut() {
+                if recent.contains(id) {
+                    *logit = if *logit > 0.0 { *logit / self.repetition_penalty } else { *logit * self.repetition_penalty };
+                }
+            }
+        }
+        if self.temperature <= 0.0 {
+            return candidates.iter().max_by(|a, b| a.1.total_cmp(&b.1)).map_or(0, |c| c.0);
+        }
+
+        if self.top_k > 0 && self.top_k < candidates.len() {
+            candidates.select_nth_unstable_by(self.top_k - 1, |a, b| b.1.total_cmp(&a.1));
+            candidates.truncate(self.top_k);
+        }
+        candidates.sort_unstable_by(|a, b| b.1.total_cmp(&a.1));
+
+        // Nucleus cut on the untempered distribution, keeping at least one
+        if self.top_p < 1.0 {
+            let probs = softmax(candidates.iter().map(|c| c.1), 1.0);
+            let mut cumulative = 0.0;
+            let keep = probs
+                .iter()
+                .position(|p| {
+                    cumulative += p;
+                    cumulative >= self.top_p
+                })
+                .map_or(candidates.len(), |i| i + 1);
+            candidates.truncate(keep);
+        }
+
+        let probs = softmax(candidates.iter().map(|c| c.1), self.temperature);
+        let mut draw: f32 = self.rng.gen();
+        for (candidate, p) in candidates.iter().zip(&probs) {
+            if draw < *p {
+                return candidate.0;
+            }
+            draw -= p;
+        }
+        candidates.last().map_or(0, |c| c.0)
+    }
+}
+
+fn argmax(logits: &[f32]) -> u32 {
+    logits.iter().enumerate().max_by(|a, b| a.1.total_cmp(b.1)).map_or(0, |(id, _)| id as u32)
+}
+
+fn softmax(logits: impl Iterator<Item = f32> + Clone, temperature: f32) -> Vec<f32> {
+    let max = logits.clone().fold(f32::NEG_INFINITY, f32::max);
+    let exps: Vec<f32> = logits.map(|l| ((l - max) / temperature).exp()).collect();
+    let sum: f32 = exps.iter().sum();
+    exps.into_iter().map(|e| e / sum).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Fixed(u32);
+
+    impl Sampler for Fixed {
+        fn sample(&mut self, _logits: &[f32], _history: &[u32]) -> u32 {
+            self.0
+        }
+    }
+
+    fn config(sampler: Option<&str>) -> InferenceConfig {
+        InferenceConfig { sampler: sampler.map(String::from), ..Default::default() }
+    }
+
+    #[test]
+    fn test_registry() {
+        assert!(is_registered(DEFAULT_SAMPLER) && is_registered("greedy"));
+        register_sampler("fixed-7", Arc::new(|_: &InferenceConfig| Box::new(Fixed(7)) as Box<dyn Sampler>)).unwrap();
+        let mut sampler = build_sampler(&config(Some("fixed-7"))).unwrap();
+        assert_eq!(sampler.sample(&[0.0; 16], &[]), 7);
+
+        let duplicate = register_sampler("greedy", Arc::new(|_: &InferenceConfig| Box::new(Fixed(0)) as Box<dyn Sampler>));
+        assert_eq!(duplicate.unwrap_err(), SamplerError::AlreadyRegistered("greedy".into()));
+        assert_eq!(build_sampler(&config(Some("mirostat"))).err(), Some(SamplerError::Unknown("mirostat".into())));
+    }
+
+    #[test]
+    fn test_builtins() {
+        let logits = [0.1, 2.0, 0.5, 1.9];
+        assert_eq!(build_sampler(&config(Some("greedy"))).unwrap().sample(&logits, &[]), 1);
+
+        // Zero temperature is greedy after the repetition penalty
+        let cold = InferenceConfig { temperature: 0.0, repetition_penalty: 1.5, ..Default::default() };
+        assert_eq!(StandardSampler::new(&cold).sample(&logits, &[1]), 3);
+
+        // top_k = 1 leaves a single candidate whatever the draw
+        let narrow = InferenceConfig { top_k: 1, repetition_penalty: 1.0, ..Default::default() };
+        let mut sampler = StandardSampler::new(&narrow);
+        assert!((0..20).all(|_| sampler.sample(&logits, &[]) == 1));
+
+        // Seeded draws repeat across generations
+        let draws = |config: &InferenceConfig| {
+            let mut sampler = StandardSampler::new(config);
+            (0..20).map(|_| sampler.sample(&logits, &[])).collect::<Vec<_>>()
+        };
+        assert_eq!(draws(&InferenceConfig::default()), draws(&InferenceConfig::default()));
+    }
+}
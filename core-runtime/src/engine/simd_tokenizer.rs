@@ -2,9 +2,12 @@
 //!
 //! Provides AVX2-accelerated whitespace detection and greedy BPE encoding.
 
+use crate::engine::utf8;
 use crate::engine::TokenizerError;
 use std::collections::HashMap;
 
+#[cfg(target_arch = "x86_64")]
+use crate::engine::cpu_dispatch::{self, Kernel, SimdTier};
 #[cfg(target_arch = "x86_64")]
 use std::arch::x86_64::*;
 
@@ -105,12 +108,12 @@ impl SimdTokenizer {
             .collect()
     }
 
-    /// Find whitespace with automatic dispatch.
+    /// Find whitespace with the implementation the dispatch table selects.
     pub fn find_whitespace(text: &[u8]) -> Vec<usize> {
         #[cfg(target_arch = "x86_64")]
         {
-            if is_x86_feature_detected!("avx2") {
-                // SAFETY: AVX2 feature is detected before calling
+            if cpu_dispatch::dispatch().select(Kernel::TokenizerWhitespace) == SimdTier::Avx2 {
+                // SAFETY: the dispatch table only selects AVX2 when the CPU has it
                 return unsafe { Self::find_whitespace_avx2(text) };
             }
         }
@@ -146,7 +149,8 @@ impl SimdTokenizer {
         (1, bytes[pos] as u32)
     }
 
-    /// Decode token IDs to text.
+    /// Decode token IDs to text. Byte sequences that are not valid UTF-8
+    /// are replaced with U+FFFD.
     pub fn decode(&self, tokens: &[u32]) -> Result<String, TokenizerError> {
         let mut bytes = Vec::new();
         for &id in tokens {
@@ -156,7 +160,7 @@ impl SimdTokenizer {
                 return Err(TokenizerError::InvalidToken(id));
             }
         }
-        String::from_utf8(bytes).map_err(|e| TokenizerError::DecodingFailed(e.to_string()))
+        Ok(utf8::decode_lossy(&bytes).0)
     }
 
     /// Get end-of-sequence token ID.
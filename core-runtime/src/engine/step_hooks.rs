@@ -0,0 +1,290 @@
+//! Per-decode-step hooks for research instrumentation.
+//!
+//! With the `step-hooks` feature, factories registered through
+//! [`register_step_hook`] build a [`StepHook`] for every generation. The
+//! hook sees each decode step after sampling: the logits the token was
+//! drawn from, the chosen token, the history and the step's wall time. It
+//! can compute entropy or uncertainty metrics, test a watermark, or end
+//! the generation early with [`StepAction::Stop`], which finishes it with
+//! `FinishReason::EarlyExit`.
+//!
+//! Without the feature nothing can be registered, [`StepHooks`] is empty
+//! and every call on it compiles to nothing, so the decode loop pays no
+//! cost.
+//!
+//! Hooks run inline on the decode thread; slow hooks slow generation.
+
+#[cfg(feature = "step-hooks")]
+use std::collections::BTreeMap;
+#[cfg(feature = "step-hooks")]
+use std::sync::{Arc, OnceLock, RwLock};
+use std::time::Duration;
+#[cfg(feature = "step-hooks")]
+use std::time::Instant;
+
+use thiserror::Error;
+
+use super::{FinishReason, InferenceConfig};
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum StepHookError {
+    #[error("Step hook already registered: {0}")]
+    AlreadyRegistered(String),
+}
+
+/// One decode step as seen by a hook.
+#[derive(Debug, Clone, Copy)]
+pub struct DecodeStep<'a> {
+    /// Zero-based index of the generated token.
+    pub step: u32,
+    /// Logits over the vocabulary the token was sampled from.
+    pub logits: &'a [f32],
+    pub token: u32,
+    /// Prompt and generated tokens, ending with `token`.
+    pub history: &'a [u32],
+    /// Time since the previous step: decoding the previous token and
+    /// sampling this one. The first step includes the prompt prefill.
+    pub elapsed: Duration,
+}
+
+impl DecodeStep<'_> {
+    /// Shannon entropy, in nats, of the softmax over `logits`.
+    pub fn entropy(&self) -> f32 {
+        let max = self.logits.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        let sum: f32 = self.logits.iter().map(|l| (l - max).exp()).sum();
+        let log_sum = sum.ln();
+        self.logits
+            .iter()
+            .map(|l| {
+                let log_p = l - max - log_sum;
+                -log_p.exp() * log_p
+            })
+            .sum()
+    }
+
+    /// Softmax probability of the chosen token.
+    pub fn token_probability(&self) -> f32 {
+        let Some(&chosen) = self.logits.get(self.token as usize) else {
+            return 0.0;
+        };
+        let max = self.logits.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        let sum: f32 = self.logits.iter().map(|l| (l - max).exp()).sum();
+        (chosen - max).exp() / sum
+    }
+}
+
+/// What the decode loop does after a hook has seen a step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepAction {
+    Continue,
+    /// End the generation after this token.
+    Stop,
+}
+
+/// Observes one generation step by step.
+pub trait StepHook: Send {
+    fn on_step(&mut self, step: &DecodeStep<'_>) -> StepAction;
+
+    /// Called once when the generation ends, after `steps` steps.
+    fn on_finish(&mut self, _reason: FinishReason, _steps: u32) {}
+}
+
+/// Builds a hook for each generation.
+pub trait StepHookFactory: Send + Sync {
+    fn build(&self, config: &InferenceConfig) -> Box<dyn StepHook>;
+}
+
+impl<F> StepHookFactory for F
+where
+    F: Fn(&InferenceConfig) -> Box<dyn StepHook> + Send + Sync,
+{
+    fn build(&self, config: &InferenceConfig) -> Box<dyn StepHook> {
+        self(config)
+    }
+}
+
+#[cfg(feature = "step-hooks")]
+type Registry = RwLock<BTreeMap<String, Arc<dyn StepHookFactory>>>;
+
+#[cfg(feature = "step-hooks")]
+fn registry() -> &'static Registry {
+    static REGISTRY: OnceLock<Registry> = OnceLock::new();
+    REGISTRY.get_or_init(Default::default)
+}
+
+/// Run hooks from `factory` on every generation from now on. Names are
+/// never replaced.
+#[cfg(feature = "step-hooks")]
+pub fn register_step_hook(name: &str, factory: Arc<dyn StepHookFactory>) -> Result<(), StepHookError> {
+    let mut hooks = registry().write().unwrap_or_else(|e| e.into_inner());
+    if hooks.contains_key(name) {
+        return Err(StepHookError::AlreadyRegistered(name.to_string()));
+    }
+    hooks.insert(name.to_string(), factory);
+    Ok(())
+}
+
+/// Stop building `name` for new generations; running ones keep theirs.
+#[cfg(feature = "step-hooks")]
+pub fn unregister_step_hook(name: &str) -> bool {
+    registry().write().unwrap_or_else(|e| e.into_inner()).remove(name).is_some()
+}
+
+/// Registered hook names, sorted.
+#[cfg(feature = "step-hooks")]
+pub fn step_hook_names() -> Vec<String> {
+    registry().read().unwrap_or_else(|e| e.into_inner()).keys().cloned().collect()
+}
+
+/// The hooks of one generation.
+#[derive(Default)]
+pub struct StepHooks {
+    #[cfg(feature = "step-hooks")]
+    hooks: Vec<Box<dyn StepHook>>,
+    #[cfg(feature = "step-hooks")]
+    last: Option<Instant>,
+    #[cfg(feature = "step-hooks")]
+    steps: u32,
+}
+
+#[cfg(feature = "step-hooks")]
+impl StepHooks {
+    /// Build the registered hooks for a generation with `config`.
+    pub fn begin(config: &InferenceConfig) -> Self {
+        let factories: Vec<_> = registry().read().unwrap_or_else(|e| e.into_inner()).values().cloned().collect();
+        let hooks: Vec<_> = factories.iter().map(|factory| factory.build(config)).collect();
+        let last = (!hooks.is_empty()).then(Instant::now);
+        Self { hooks, last, steps: 0 }
+    }
+
+    pub fn is_active(&self) -> bool {
+        !self.hooks.is_empty()
+    }
+
+    /// Show a step to every hook; any hook asking to stop stops it.
+    /// `logits` is only fetched when a hook is running.
+    pub fn step<'a>(
+        &mut self,
+        step: u32,
+        token: u32,
+        history: &[u32],
+        logits: impl FnOnce() -> &'a [f32],
+    ) -> StepAction {
+        if !self.is_active() {
+            return StepAction::Continue;
+        }
+        let now = Instant::now();
+        let elapsed = self.last.map_or(Duration::ZERO, |last| now - last);
+        self.last = Some(now);
+        self.steps = step + 1;
+        let decoded = DecodeStep { step, logits: logits(), token, history, elapsed };
+        let mut action = StepAction::Continue;
+        for hook in &mut self.hooks {
+            if hook.on_step(&decoded) == StepAction::Stop {
+                action = StepAction::Stop;
+            }
+        }
+        action
+    }
+
+    /// Tell every hook the generation ended.
+    pub fn finish(&mut self, reason: FinishReason) {
+        for hook in &mut self.hooks {
+            hook.on_finish(reason, self.steps);
+        }
+    }
+}
+
+/// No-op hooks for builds without `step-hooks`.
+#[cfg(not(feature = "step-hooks"))]
+impl StepHooks {
+    #[inline(always)]
+    pub fn begin(_config: &InferenceConfig) -> Self {
+        Self {}
+    }
+
+    #[inline(always)]
+    pub fn is_active(&self) -> bool {
+        false
+    }
+
+    #[inline(always)]
+    pub fn step<'a>(
+        &mut self,
+        _step: u32,
+        _token: u32,
+        _history: &[u32],
+        _logits: impl FnOnce() -> &'a [f32],
+    ) -> StepAction {
+        StepAction::Continue
+    }
+
+    #[inline(always)]
+    pub fn finish(&mut self, _reason: FinishReason) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entropy_and_probability() {
+        let uniform = [0.0f32; 4];
+        let step = DecodeStep { step: 0, logits: &uniform, token: 1, history: &[1], elapsed: Duration::ZERO };
+        assert!((step.entropy() - 4f32.ln()).abs() < 1e-5);
+        assert!((step.token_probability() - 0.25).abs() < 1e-5);
+
+        let peaked = [0.0, 100.0, 0.0];
+        let step = DecodeStep { logits: &peaked, ..step };
+        assert!(step.entropy() < 1e-3);
+        assert!(step.token_probability() > 0.999);
+    }
+
+    #[cfg(not(feature = "step-hooks"))]
+    #[test]
+    fn test_inactive_without_feature() {
+        let mut hooks = StepHooks::begin(&InferenceConfig::default());
+        assert!(!hooks.is_active());
+        let action = hooks.step(0, 1, &[1], || unreachable!("logits fetched without hooks"));
+        assert_eq!(action, StepAction::Continue);
+    }
+
+    #[cfg(feature = "step-hooks")]
+    #[test]
+    fn test_registered_hook_sees_steps_and_stops() {
+        use std::sync::Mutex;
+
+        /// Stops once a step's entropy drops below half a nat.
+        struct Confident(Arc<Mutex<Vec<u32>>>);
+
+        impl StepHook for Confident {
+            fn on_step(&mut self, step: &DecodeStep<'_>) -> StepAction {
+                if step.entropy() < 0.5 {
+                    return StepAction::Stop;
+                }
+                StepAction::Continue
+            }
+
+            fn on_finish(&mut self, _reason: FinishReason, steps: u32) {
+                self.0.lock().unwrap().push(steps);
+            }
+        }
+
+        let finished = Arc::new(Mutex::new(Vec::new()));
+        let seen = Arc::clone(&finished);
+        let factory = move |_: &InferenceConfig| -> Box<dyn StepHook> { Box::new(Confident(Arc::clone(&seen))) };
+        register_step_hook("test.confident", Arc::new(factory)).unwrap();
+        assert!(register_step_hook("test.confident", Arc::new(|_: &InferenceConfig| -> Box<dyn StepHook> {
+            Box::new(Confident(Arc::default()))
+        }))
+        .is_err());
+
+        let mut hooks = StepHooks::begin(&InferenceConfig::default());
+        assert!(unregister_step_hook("test.confident"));
+        assert!(hooks.is_active());
+        assert_eq!(hooks.step(0, 0, &[0], || &[0.0, 0.0]), StepAction::Continue);
+        assert_eq!(hooks.step(1, 1, &[0, 1], || &[0.0, 50.0]), StepAction::Stop);
+        hooks.finish(FinishReason::EarlyExit);
+        assert_eq!(*finished.lock().unwrap(), vec![2]);
+    }
+}
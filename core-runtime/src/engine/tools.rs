@@ -0,0 +1,197 @@
+//! Function/tool calling.
+//!
+//! Tools are declared per request with a JSON-schema parameter spec. The
+//! prompt is prefixed with the tool list and a fixed call format; model
+//! output is then scanned for a tool-call object, and its arguments are
+//! checked against the declared schema. Only schema-valid calls for
+//! declared tools are returned, so clients never see hallucinated tools.
+//!
+//! The validator covers the schema subset used for tool parameters:
+//! `type`, `properties`, `required`, `items` and `enum`.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+/// Marker the model is instructed to emit before a tool call.
+pub const TOOL_CALL_MARKER: &str = "<tool_call>";
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ToolError {
+    #[error("Unknown tool: {0}")]
+    UnknownTool(String),
+
+    #[error("Invalid arguments for {tool}: {reason}")]
+    InvalidArguments { tool: String, reason: String },
+
+    #[error("Malformed tool call: {0}")]
+    Malformed(String),
+}
+
+/// A tool the model may call.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolDefinition {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// JSON schema for the arguments object.
+    #[serde(default)]
+    pub parameters: Value,
+}
+
+/// A validated tool invocation produced by the model.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolCall {
+    pub id: String,
+    pub name: String,
+    pub arguments: Value,
+}
+
+#[derive(Deserialize)]
+struct RawCall {
+    name: String,
+    #[serde(default)]
+    arguments: Value,
+}
+
+/// Prefix `prompt` with the tool catalog and call format.
+pub fn render_tool_prompt(tools: &[ToolDefinition], prompt: &str) -> String {
+    let catalog = serde_json::to_string(tools).unwrap_or_default();
+    format!(
+        "You can call these tools: {catalog}\n\
+         To call a tool, reply with {TOOL_CALL_MARKER} followed by a JSON object \
+         {{\"name\": ..., \"arguments\": {{...}}}} and nothing else.\n\n{prompt}"
+    )
+}
+
+/// Extract and validate a tool call from model output.
+/// Returns `Ok(None)` when the output is a plain answer.
+pub fn parse_tool_call(
+    output: &str,
+    tools: &[ToolDefinition],
+    call_id: &str,
+) -> Result<Option<ToolCall>, ToolError> {
+    let Some(start) = output.find(TOOL_CALL_MARKER) else {
+        return Ok(None);
+    };
+    let body = output[start + TOOL_CALL_MARKER.len()..].trim_start();
+    let mut stream = serde_json::Deserializer::from_str(body).into_iter::<RawCall>();
+    let raw = match stream.next() {
+        Some(Ok(raw)) => raw,
+        Some(Err(e)) => return Err(ToolError::Malformed(e.to_string())),
+        None => return Err(ToolError::Malformed("empty tool call".into())),
+    };
+    let tool = tools
+        .iter()
+        .find(|t| t.name == raw.name)
+        .ok_or_else(|| ToolError::UnknownTool(raw.name.clone()))?;
+    validate(&raw.arguments, &tool.parameters, "arguments").map_err(|reason| {
+        ToolError::InvalidArguments { tool: raw.name.clone(), reason }
+    })?;
+    Ok(Some(ToolCall { id: call_id.to_string(), name: raw.name, arguments: raw.arguments }))
+}
+
+/// Validate `value` against the supported schema subset.
+fn validate(value: &Value, schema: &Value, path: &str) -> Result<(), String> {
+    let Some(schema) = schema.as_object() else {
+        return Ok(()); // no schema = anything goes
+    };
+    if let Some(expected) = schema.get("type").and_then(Value::as_str) {
+        if !type_matches(value, expected) {
+            return Err(format!("{path} must be {expected}"));
+        }
+    }
+    if let Some(options) = schema.get("enum").and_then(Value::as_array) {
+        if !options.contains(value) {
+            return Err(format!("{path} is not an allowed value"));
+        }
+    }
+    if let Some(object) = value.as_object() {
+        for key in schema.get("required").and_then(Value::as_array).into_iter().flatten() {
+            let key = key.as_str().unwrap_or_default();
+            if !object.contains_key(key) {
+                return Err(format!("{path}.{key} is required"));
+            }
+        }
+        if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+            for (key, child) in object {
+                if let Some(child_schema) = properties.get(key) {
+                    validate(child, child_schema, &format!("{path}.{key}"))?;
+                }
+            }
+        }
+    }
+    if let (Some(items), Some(array)) = (schema.get("items"), value.as_array()) {
+        for (i, item) in array.iter().enumerate() {
+            validate(item, items, &format!("{path}[{i}]"))?;
+        }
+    }
+    Ok(())
+}
+
+fn type_matches(value: &Value, expected: &str) -> bool {
+    match expected {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn weather() -> Vec<ToolDefinition> {
+        vec![ToolDefinition {
+            name: "get_weather".into(),
+            description: Some("Current weather".into()),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "city": { "type": "string" },
+                    "unit": { "type": "string", "enum": ["c", "f"] }
+                },
+                "required": ["city"]
+            }),
+        }]
+    }
+
+    #[test]
+    fn test_plain_answer_has_no_call() {
+        assert_eq!(parse_tool_call("It is sunny.", &weather(), "c1"), Ok(None));
+    }
+
+    #[test]
+    fn test_valid_call_parsed_with_trailing_text() {
+        let output = r#"<tool_call> {"name": "get_weather", "arguments": {"city": "Oslo"}} done"#;
+        let call = parse_tool_call(output, &weather(), "call-1").unwrap().unwrap();
+        assert_eq!(call.id, "call-1");
+        assert_eq!(call.arguments["city"], "Oslo");
+    }
+
+    #[test]
+    fn test_schema_violations_rejected() {
+        let missing = r#"<tool_call>{"name": "get_weather", "arguments": {}}"#;
+        assert!(matches!(
+            parse_tool_call(missing, &weather(), "c"),
+            Err(ToolError::InvalidArguments { .. })
+        ));
+        let bad_enum = r#"<tool_call>{"name": "get_weather", "arguments": {"city": "Oslo", "unit": "k"}}"#;
+        assert!(parse_tool_call(bad_enum, &weather(), "c").is_err());
+        let unknown = r#"<tool_call>{"name": "rm_rf", "arguments": {}}"#;
+        assert!(matches!(parse_tool_call(unknown, &weather(), "c"), Err(ToolError::UnknownTool(_))));
+    }
+
+    #[test]
+    fn test_prompt_lists_tools() {
+        let prompt = render_tool_prompt(&weather(), "Weather in Oslo?");
+        assert!(prompt.contains("get_weather"));
+        assert!(prompt.ends_with("Weather in Oslo?"));
+    }
+}
@@ -0,0 +1,193 @@
+//! UTF-8 output finalization.
+//!
+//! Backends produce output a token at a time, and a multi-byte character
+//! may be split across tokens or be invalid outright. Output passes through
+//! here so it is always valid UTF-8: bytes are decoded incrementally with
+//! invalid sequences replaced by U+FFFD, and truncation only cuts on
+//! character boundaries. Mojibake (UTF-8 decoded as Latin-1/Windows-1252,
+//! e.g. "cafÃ©") is scored rather than rejected, since the same characters
+//! also occur in legitimate text.
+
+/// Incremental UTF-8 decoder. Holds back an incomplete trailing sequence
+/// until the next push; the concatenated output equals
+/// `String::from_utf8_lossy` of all pushed bytes, however they are split.
+#[derive(Debug, Default)]
+pub struct Utf8Decoder {
+    pending: Vec<u8>,
+    replaced: usize,
+}
+
+impl Utf8Decoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Decode `bytes`, returning the text completed by them.
+    pub fn push(&mut self, bytes: &[u8]) -> String {
+        self.pending.extend_from_slice(bytes);
+        let mut out = String::with_capacity(self.pending.len());
+        let mut pos = 0;
+        loop {
+            match std::str::from_utf8(&self.pending[pos..]) {
+                Ok(valid) => {
+                    out.push_str(valid);
+                    pos = self.pending.len();
+                    break;
+                }
+                Err(e) => {
+                    let valid_up_to = pos + e.valid_up_to();
+                    // Already validated by from_utf8
+                    out.push_str(std::str::from_utf8(&self.pending[pos..valid_up_to]).unwrap_or_default());
+                    match e.error_len() {
+                        Some(len) => {
+                            out.push(char::REPLACEMENT_CHARACTER);
+                            self.replaced += 1;
+                            pos = valid_up_to + len;
+                        }
+                        // Incomplete sequence at the end; wait for more
+                        None => {
+                            pos = valid_up_to;
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+        self.pending.drain(..pos);
+        out
+    }
+
+    /// Flush the decoder. An unfinished trailing sequence becomes U+FFFD.
+    pub fn finish(&mut self) -> String {
+        if self.pending.is_empty() {
+            return String::new();
+        }
+        self.pending.clear();
+        self.replaced += 1;
+        char::REPLACEMENT_CHARACTER.to_string()
+    }
+
+    /// Invalid sequences replaced so far.
+    pub fn replaced(&self) -> usize {
+        self.replaced
+    }
+}
+
+/// Decode backend bytes, replacing invalid sequences. Returns the text and
+/// the number of replacements.
+pub fn decode_lossy(bytes: &[u8]) -> (String, usize) {
+    let mut decoder = Utf8Decoder::new();
+    let mut text = decoder.push(bytes);
+    text.push_str(&decoder.finish());
+    (text, decoder.replaced())
+}
+
+/// Largest char boundary at or below `index`.
+pub fn floor_char_boundary(text: &str, index: usize) -> usize {
+    if index >= text.len() {
+        return text.len();
+    }
+    let mut end = index;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    end
+}
+
+/// Cut `text` to at most `max_bytes`, on a char boundary. Returns whether
+/// anything was removed.
+pub fn truncate_bytes(text: &mut String, max_bytes: usize) -> bool {
+    if text.len() <= max_bytes {
+        return false;
+    }
+    text.truncate(floor_char_boundary(text, max_bytes));
+    true
+}
+
+/// Cut `text` to at most `max_chars` characters. Returns whether anything
+/// was removed.
+pub fn truncate_chars(text: &mut String, max_chars: usize) -> bool {
+    match text.char_indices().nth(max_chars) {
+        Some((end, _)) => {
+            text.truncate(end);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Whether `c` can stand for a UTF-8 continuation byte (0x80-0xBF) that
+/// was decoded as Latin-1 or Windows-1252.
+fn is_misdecoded_continuation(c: char) -> bool {
+    matches!(c, '\u{80}'..='\u{BF}')
+        || matches!(
+            c,
+            '€' | '‚' | 'ƒ' | '„' | '…' | '†' | '‡' | 'ˆ' | '‰' | 'Š' | '‹' | 'Œ' | 'Ž' | '‘' | '’' | '“' | '”' | '•'
+                | '–' | '—' | '˜' | '™' | 'š' | '›' | 'œ' | 'ž' | 'Ÿ'
+        )
+}
+
+/// Likelihood (0.0-1.0) that `text` is mojibake: the share of its
+/// non-ASCII characters that form a misdecoded UTF-8 lead/continuation
+/// pair such as "Ã©" or "â€".
+pub fn mojibake_score(text: &str) -> f32 {
+    let mut non_ascii = 0usize;
+    let mut suspicious = 0usize;
+    let mut prev: Option<char> = None;
+    for c in text.chars() {
+        if !c.is_ascii() {
+            non_ascii += 1;
+        }
+        // Leads of two- to four-byte sequences, as Latin-1
+        if prev.is_some_and(|p| matches!(p, '\u{C2}'..='\u{F4}')) && is_misdecoded_continuation(c) {
+            suspicious += 2;
+            prev = None;
+            continue;
+        }
+        prev = Some(c);
+    }
+    if non_ascii == 0 {
+        return 0.0;
+    }
+    (suspicious as f32 / non_ascii as f32).min(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decoder_matches_lossy_across_splits() {
+        let bytes = "héllo wörld €".as_bytes().iter().copied().chain([0xff, b'!', 0xe2, 0x82]).collect::<Vec<_>>();
+        let expected = String::from_utf8_lossy(&bytes).into_owned();
+        for split in 0..=bytes.len() {
+            let mut decoder = Utf8Decoder::new();
+            let mut out = decoder.push(&bytes[..split]);
+            out.push_str(&decoder.push(&bytes[split..]));
+            out.push_str(&decoder.finish());
+            assert_eq!(out, expected, "split at {}", split);
+            assert_eq!(decoder.replaced(), 2);
+        }
+    }
+
+    #[test]
+    fn test_truncation_keeps_char_boundaries() {
+        let mut text = "aé€".to_string();
+        assert!(truncate_bytes(&mut text, 4));
+        assert_eq!(text, "aé");
+        let mut text = "aé€".to_string();
+        assert!(truncate_chars(&mut text, 2));
+        assert_eq!(text, "aé");
+        assert!(!truncate_chars(&mut text, 2));
+    }
+
+    #[test]
+    fn test_mojibake_score() {
+        assert_eq!(mojibake_score("plain ascii"), 0.0);
+        assert_eq!(mojibake_score("café São Paulo Ãgua"), 0.0);
+        assert_eq!(mojibake_score("cafÃ© Ã¨"), 1.0);
+        assert!(mojibake_score("â€™") > 0.5);
+        let mixed = mojibake_score("café naïve cafÃ©");
+        assert!(mixed > 0.0 && mixed < 1.0);
+    }
+}
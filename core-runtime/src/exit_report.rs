@@ -0,0 +1,266 @@
+//! Structured exit reports for post-mortems.
+//!
+//! Each run leaves a `last-exit.json` in the base path describing why the
+//! process ended: the reason, uptime, how much work drained or was
+//! aborted, and the most recent errors. A placeholder marked `unclean` is
+//! written at startup and replaced on exit, so a run killed without a
+//! chance to report (SIGKILL, OOM, panic) is still recognizable. The next
+//! instance reads the previous report before starting and surfaces it
+//! through health reports and `status`.
+
+use std::collections::VecDeque;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::security::audit::{audit_logger, AuditCategory, AuditEvent, AuditSeverity};
+use crate::shutdown::{DrainCounts, ShutdownReport, ShutdownResult};
+use crate::telemetry::{subscribe_events, unsubscribe_events};
+
+/// Report file name within the base path.
+pub const EXIT_REPORT_FILE: &str = "last-exit.json";
+
+/// Errors kept for the report.
+const RECENT_ERRORS: usize = 16;
+
+/// Why the process ended.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ExitReason {
+    /// Graceful shutdown on a signal.
+    Signal { signal: String },
+    /// An error stopped the server.
+    FatalError { error: String },
+    /// Cryptographic self-tests failed at startup.
+    FipsFailure { error: String },
+    /// The process ended without writing a report.
+    Unclean,
+}
+
+impl ExitReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Signal { .. } => "signal",
+            Self::FatalError { .. } => "fatal_error",
+            Self::FipsFailure { .. } => "fips_failure",
+            Self::Unclean => "unclean",
+        }
+    }
+
+    /// One-line description.
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Signal { signal } => format!("stopped by {}", signal),
+            Self::FatalError { error } => format!("fatal error: {}", error),
+            Self::FipsFailure { error } => format!("FIPS self-test failure: {}", error),
+            Self::Unclean => "ended without a shutdown report (killed or crashed)".to_string(),
+        }
+    }
+}
+
+/// How one run of the runtime ended.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExitReport {
+    pub reason: ExitReason,
+    pub version: String,
+    /// RFC 3339 start and exit times; `exited_at` is unset while running.
+    pub started_at: String,
+    #[serde(default)]
+    pub exited_at: Option<String>,
+    pub uptime_secs: u64,
+    /// In-flight work at shutdown; zero when the runtime never drained.
+    #[serde(default)]
+    pub requests: DrainCounts,
+    #[serde(default)]
+    pub connections: DrainCounts,
+    /// The drain deadline passed with requests still running.
+    #[serde(default)]
+    pub drain_timed_out: bool,
+    /// Most recent errors and warnings, oldest first.
+    #[serde(default)]
+    pub last_errors: Vec<String>,
+}
+
+impl ExitReport {
+    /// Report for a run ending now.
+    pub fn new(reason: ExitReason, started_at: chrono::DateTime<Utc>) -> Self {
+        let now = Utc::now();
+        Self {
+            reason,
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            started_at: started_at.to_rfc3339(),
+            exited_at: Some(now.to_rfc3339()),
+            uptime_secs: (now - started_at).num_seconds().max(0) as u64,
+            requests: DrainCounts::default(),
+            connections: DrainCounts::default(),
+            drain_timed_out: false,
+            last_errors: Vec::new(),
+        }
+    }
+
+    /// Add drain accounting from a graceful shutdown.
+    pub fn with_shutdown(mut self, shutdown: &ShutdownReport) -> Self {
+        self.requests = shutdown.requests;
+        self.connections = shutdown.connections;
+        self.drain_timed_out = matches!(shutdown.result, ShutdownResult::Timeout { .. });
+        self
+    }
+
+    pub fn with_errors(mut self, errors: Vec<String>) -> Self {
+        self.last_errors = errors;
+        self
+    }
+
+    /// Whether the process shut down in an orderly way.
+    pub fn is_clean(&self) -> bool {
+        matches!(self.reason, ExitReason::Signal { .. }) && !self.drain_timed_out
+    }
+
+    pub fn path(base_path: &Path) -> PathBuf {
+        base_path.join(EXIT_REPORT_FILE)
+    }
+
+    /// Read the report left by the previous run.
+    pub fn load(base_path: &Path) -> Option<Self> {
+        let data = fs::read(Self::path(base_path)).ok()?;
+        serde_json::from_slice(&data).ok()
+    }
+
+    /// Write the report, replacing any previous one atomically.
+    pub fn write(&self, base_path: &Path) -> io::Result<()> {
+        fs::create_dir_all(base_path)?;
+        let path = Self::path(base_path);
+        let temp_path = path.with_extension("tmp");
+        fs::write(&temp_path, serde_json::to_vec_pretty(self).map_err(io::Error::from)?)?;
+        fs::rename(&temp_path, &path)
+    }
+
+    /// Record the report as an audit event; the last one of the run.
+    pub async fn audit(&self) {
+        let Some(logger) = audit_logger() else {
+            return;
+        };
+        let severity = match self.reason {
+            ExitReason::Signal { .. } if self.is_clean() => AuditSeverity::Info,
+            ExitReason::Signal { .. } | ExitReason::Unclean => AuditSeverity::Warning,
+            ExitReason::FatalError { .. } | ExitReason::FipsFailure { .. } => AuditSeverity::Error,
+        };
+        let Ok(event) = AuditEvent::builder()
+            .severity(severity)
+            .category(AuditCategory::System)
+            .event_type("shutdown")
+            .message(format!("Runtime {}", self.reason.describe()))
+            .source("runtime")
+            .success(self.is_clean())
+            .metadata("reason", self.reason.as_str())
+            .metadata("uptime_secs", self.uptime_secs.to_string())
+            .metadata("requests_drained", self.requests.drained.to_string())
+            .metadata("requests_aborted", self.requests.aborted.to_string())
+            .metadata("connections_aborted", self.connections.aborted.to_string())
+            .build()
+        else {
+            return;
+        };
+        logger.log(event).await;
+    }
+}
+
+/// Start a run: return the previous run's report and leave an `unclean`
+/// placeholder until this run writes its own.
+pub fn begin_run(base_path: &Path, started_at: chrono::DateTime<Utc>) -> Option<ExitReport> {
+    let previous = ExitReport::load(base_path);
+    let mut placeholder = ExitReport::new(ExitReason::Unclean, started_at);
+    placeholder.exited_at = None;
+    placeholder.uptime_secs = 0;
+    if let Err(e) = placeholder.write(base_path) {
+        tracing::warn!(error = %e, "Failed to write exit report placeholder");
+    }
+    previous
+}
+
+/// Keeps the most recent warnings and errors for the exit report: runtime
+/// events at WARN or above, plus anything noted directly.
+pub struct RecentErrors {
+    errors: Arc<Mutex<VecDeque<String>>>,
+    subscription: u64,
+}
+
+impl RecentErrors {
+    pub fn install() -> Self {
+        let errors = Arc::new(Mutex::new(VecDeque::with_capacity(RECENT_ERRORS)));
+        let sink = Arc::clone(&errors);
+        let subscription = subscribe_events(move |event| {
+            if matches!(event.level, tracing::Level::WARN | tracing::Level::ERROR) {
+                push(&sink, format!("{}: {}", event.kind.as_str(), event.detail));
+            }
+        });
+        Self { errors, subscription }
+    }
+
+    pub fn note(&self, error: impl Into<String>) {
+        push(&self.errors, error.into());
+    }
+
+    pub fn snapshot(&self) -> Vec<String> {
+        self.errors.lock().unwrap_or_else(|e| e.into_inner()).iter().cloned().collect()
+    }
+}
+
+impl Drop for RecentErrors {
+    fn drop(&mut self) {
+        unsubscribe_events(self.subscription);
+    }
+}
+
+fn push(errors: &Mutex<VecDeque<String>>, error: String) {
+    let mut errors = errors.lock().unwrap_or_else(|e| e.into_inner());
+    if errors.len() == RECENT_ERRORS {
+        errors.pop_front();
+    }
+    errors.push_back(error);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_placeholder_then_report() {
+        let dir = tempfile::tempdir().unwrap();
+        let started = Utc::now();
+        assert!(begin_run(dir.path(), started).is_none());
+
+        // A run that never reported shows up as unclean
+        let previous = begin_run(dir.path(), started).unwrap();
+        assert_eq!(previous.reason, ExitReason::Unclean);
+        assert!(previous.exited_at.is_none());
+
+        let shutdown = ShutdownReport {
+            result: ShutdownResult::Timeout { remaining: 1 },
+            connections: DrainCounts { drained: 2, aborted: 0 },
+            requests: DrainCounts { drained: 3, aborted: 1 },
+        };
+        let report = ExitReport::new(ExitReason::Signal { signal: "SIGTERM".into() }, started)
+            .with_shutdown(&shutdown)
+            .with_errors(vec!["model_evicted: out of memory".into()]);
+        assert!(!report.is_clean());
+        report.write(dir.path()).unwrap();
+        assert_eq!(begin_run(dir.path(), started), Some(report));
+    }
+
+    #[test]
+    fn test_recent_errors_bounded() {
+        let errors = RecentErrors::install();
+        for i in 0..RECENT_ERRORS + 2 {
+            errors.note(format!("error {}", i));
+        }
+        let kept = errors.snapshot();
+        assert_eq!(kept.len(), RECENT_ERRORS);
+        assert!(!kept.contains(&"error 0".to_string()));
+        assert!(kept.contains(&format!("error {}", RECENT_ERRORS + 1)));
+    }
+}
@@ -0,0 +1,191 @@
+//! Runtime feature flags.
+//!
+//! One registry of switches for optional behaviour, shared by the
+//! components that honour them and queryable over IPC. Admin sessions can
+//! flip the flags marked toggleable without a restart, which allows staged
+//! rollouts. Flags that would weaken security (authentication) are
+//! reported but never toggleable, and flags whose component is not
+//! configured are reported as unavailable.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// A runtime feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Feature {
+    SpeculativeDecoding,
+    ResponseCache,
+    BinaryProtocol,
+    SanitizerShadow,
+    RequestHedging,
+    ConversationPrefetch,
+    RequireAuth,
+}
+
+impl Feature {
+    pub const ALL: [Feature; 7] = [
+        Feature::SpeculativeDecoding,
+        Feature::ResponseCache,
+        Feature::BinaryProtocol,
+        Feature::SanitizerShadow,
+        Feature::RequestHedging,
+        Feature::ConversationPrefetch,
+        Feature::RequireAuth,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::SpeculativeDecoding => "speculative_decoding",
+            Self::ResponseCache => "response_cache",
+            Self::BinaryProtocol => "binary_protocol",
+            Self::SanitizerShadow => "sanitizer_shadow",
+            Self::RequestHedging => "request_hedging",
+            Self::ConversationPrefetch => "conversation_prefetch",
+            Self::RequireAuth => "require_auth",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|f| f.as_str() == name)
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::SpeculativeDecoding => "Draft-model speculative decoding (still subject to the overload ladder)",
+            Self::ResponseCache => "Reuse outputs of identical recent requests",
+            Self::BinaryProtocol => "Offer the packed V2 protocol to new sessions",
+            Self::SanitizerShadow => "Evaluate the candidate policy in shadow mode",
+            Self::RequestHedging => "Race slow requests on paired model replicas",
+            Self::ConversationPrefetch => "Prefill conversation follow-up prefixes on idle capacity",
+            Self::RequireAuth => "Require an authenticated session (restart to change)",
+        }
+    }
+
+    /// Whether the flag may be flipped while running.
+    pub fn runtime_toggle(&self) -> bool {
+        !matches!(self, Self::RequireAuth)
+    }
+
+    fn default_enabled(&self) -> bool {
+        !matches!(self, Self::ConversationPrefetch)
+    }
+
+    fn index(&self) -> usize {
+        Self::ALL.iter().position(|f| f == self).unwrap_or(0)
+    }
+}
+
+/// Current state of one flag, as reported over IPC.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeatureState {
+    pub name: String,
+    pub enabled: bool,
+    /// Admin sessions may flip it at runtime.
+    pub toggleable: bool,
+    /// The component behind it is configured; an unavailable flag has no
+    /// effect and cannot be flipped.
+    pub available: bool,
+    pub description: String,
+}
+
+/// Reasons a toggle is refused.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum FeatureError {
+    #[error("unknown feature '{0}'")]
+    Unknown(String),
+    #[error("feature '{0}' cannot be changed at runtime")]
+    NotToggleable(&'static str),
+    #[error("feature '{0}' is not configured on this runtime")]
+    Unavailable(&'static str),
+}
+
+/// The runtime's feature flags.
+pub struct FeatureFlags {
+    enabled: [AtomicBool; Feature::ALL.len()],
+    available: [AtomicBool; Feature::ALL.len()],
+}
+
+impl FeatureFlags {
+    pub fn new() -> Self {
+        Self {
+            enabled: Feature::ALL.map(|f| AtomicBool::new(f.default_enabled())),
+            available: Feature::ALL.map(|_| AtomicBool::new(true)),
+        }
+    }
+
+    pub fn enabled(&self, feature: Feature) -> bool {
+        self.available[feature.index()].load(Ordering::Acquire) && self.enabled[feature.index()].load(Ordering::Acquire)
+    }
+
+    /// Startup configuration: set the initial state, bypassing the toggle
+    /// rules.
+    pub fn configure(&self, feature: Feature, enabled: bool, available: bool) {
+        self.enabled[feature.index()].store(enabled, Ordering::Release);
+        self.available[feature.index()].store(available, Ordering::Release);
+    }
+
+    /// Flip a flag at runtime. Returns the previous state.
+    pub fn set(&self, name: &str, enabled: bool) -> Result<(Feature, bool), FeatureError> {
+        let feature = Feature::parse(name).ok_or_else(|| FeatureError::Unknown(name.to_string()))?;
+        if !feature.runtime_toggle() {
+            return Err(FeatureError::NotToggleable(feature.as_str()));
+        }
+        if !self.available[feature.index()].load(Ordering::Acquire) {
+            return Err(FeatureError::Unavailable(feature.as_str()));
+        }
+        Ok((feature, self.enabled[feature.index()].swap(enabled, Ordering::AcqRel)))
+    }
+
+    /// Every flag, in registry order.
+    pub fn list(&self) -> Vec<FeatureState> {
+        Feature::ALL
+            .into_iter()
+            .map(|f| FeatureState {
+                name: f.as_str().to_string(),
+                enabled: self.enabled(f),
+                toggleable: f.runtime_toggle(),
+                available: self.available[f.index()].load(Ordering::Acquire),
+                description: f.description().to_string(),
+            })
+            .collect()
+    }
+}
+
+impl Default for FeatureFlags {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toggle_rules() {
+        let flags = FeatureFlags::new();
+        assert!(flags.enabled(Feature::ResponseCache));
+        assert_eq!(flags.set("response_cache", false), Ok((Feature::ResponseCache, true)));
+        assert!(!flags.enabled(Feature::ResponseCache));
+
+        assert_eq!(flags.set("require_auth", false), Err(FeatureError::NotToggleable("require_auth")));
+        assert!(matches!(flags.set("warp_drive", true), Err(FeatureError::Unknown(_))));
+
+        flags.configure(Feature::RequestHedging, true, false);
+        assert!(!flags.enabled(Feature::RequestHedging));
+        assert_eq!(flags.set("request_hedging", true), Err(FeatureError::Unavailable("request_hedging")));
+    }
+
+    #[test]
+    fn test_list_covers_every_feature() {
+        let flags = FeatureFlags::new();
+        let names: Vec<String> = flags.list().into_iter().map(|s| s.name).collect();
+        assert_eq!(names.len(), Feature::ALL.len());
+        for feature in Feature::ALL {
+            assert_eq!(Feature::parse(feature.as_str()), Some(feature));
+            assert!(names.contains(&feature.as_str().to_string()));
+        }
+    }
+}
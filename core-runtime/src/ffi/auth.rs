@@ -8,6 +8,7 @@ use std::sync::Arc;
 
 use super::error::{set_last_error, CoreErrorCode};
 use super::runtime::CoreRuntime;
+use super::strings::utf8_arg;
 use crate::ipc::SessionToken;
 use crate::Runtime;
 
@@ -38,7 +39,37 @@ pub unsafe extern "C" fn core_authenticate(
             return CoreErrorCode::InvalidParams;
         }
     };
+    authenticate(rt, token_str, out_session)
+}
+
+/// Authenticate with a length-delimited UTF-8 token
+///
+/// # Safety
+/// `token` must be valid for reads of `token_len` bytes; `runtime` and
+/// `out_session` must be valid pointers.
+#[no_mangle]
+pub unsafe extern "C" fn core_authenticate_utf8(
+    runtime: *mut CoreRuntime,
+    token: *const u8,
+    token_len: usize,
+    out_session: *mut *mut CoreSession,
+) -> CoreErrorCode {
+    if runtime.is_null() || out_session.is_null() {
+        set_last_error("null pointer argument");
+        return CoreErrorCode::NullPointer;
+    }
+    let token_str = match utf8_arg(token, token_len, "token") {
+        Ok(s) => s,
+        Err(code) => return code,
+    };
+    authenticate(&*runtime, token_str, out_session)
+}
 
+unsafe fn authenticate(
+    rt: &CoreRuntime,
+    token_str: &str,
+    out_session: *mut *mut CoreSession,
+) -> CoreErrorCode {
     let result = rt.tokio.block_on(async {
         rt.inner.ipc_handler.auth.authenticate(token_str).await
     });
@@ -115,6 +115,10 @@ impl From<crate::models::LoadError> for CoreErrorCode {
             LoadError::NotFound(_) => CoreErrorCode::ModelNotFound,
             LoadError::InvalidFormat(_) => CoreErrorCode::ModelLoadFailed,
             LoadError::Io(_) => CoreErrorCode::ModelLoadFailed,
+            LoadError::Quarantined(_) => CoreErrorCode::ModelLoadFailed,
+            LoadError::Panicked(_) => CoreErrorCode::ModelLoadFailed,
+            LoadError::Backend(_) => CoreErrorCode::ModelLoadFailed,
+            LoadError::Attestation(_) => CoreErrorCode::ModelLoadFailed,
         }
     }
 }
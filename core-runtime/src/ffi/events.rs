@@ -0,0 +1,91 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Runtime event subscription for FFI
+
+use std::ffi::{c_void, CString};
+
+use super::error::{set_last_error, CoreErrorCode};
+use super::types::{CoreEvent, CoreEventKind, CoreLogLevel};
+use crate::telemetry::{subscribe_events, unsubscribe_events, RuntimeEventKind};
+
+/// Event callback signature
+/// The event is borrowed for the call only. The callback may run on any
+/// runtime thread and must not unwind.
+pub type CoreEventCallback = unsafe extern "C" fn(user_data: *mut c_void, event: *const CoreEvent);
+
+struct EventSink {
+    callback: CoreEventCallback,
+    user_data: *mut c_void,
+}
+
+// SAFETY: user_data pointer is provided by caller who ensures thread safety
+unsafe impl Send for EventSink {}
+unsafe impl Sync for EventSink {}
+
+impl EventSink {
+    fn deliver(&self, event: &CoreEvent) {
+        unsafe { (self.callback)(self.user_data, event) };
+    }
+}
+
+/// Subscribe to runtime events at or above `min_level`
+///
+/// # Safety
+/// `user_data` is passed back verbatim; the caller keeps it valid until
+/// `core_unsubscribe_events` returns. `out_subscription_id` must be valid.
+#[no_mangle]
+pub unsafe extern "C" fn core_subscribe_events(
+    callback: Option<CoreEventCallback>,
+    min_level: CoreLogLevel,
+    user_data: *mut c_void,
+    out_subscription_id: *mut u64,
+) -> CoreErrorCode {
+    let Some(callback) = callback else {
+        set_last_error("null callback");
+        return CoreErrorCode::NullPointer;
+    };
+    if out_subscription_id.is_null() {
+        set_last_error("null pointer argument");
+        return CoreErrorCode::NullPointer;
+    }
+
+    let sink = EventSink { callback, user_data };
+    let id = subscribe_events(move |event| {
+        let level = CoreLogLevel::from(event.level);
+        if level < min_level {
+            return;
+        }
+        let subject = CString::new(event.subject.as_str()).unwrap_or_default();
+        let detail = CString::new(event.detail.as_str()).unwrap_or_default();
+        let c_event = CoreEvent {
+            kind: match event.kind {
+                RuntimeEventKind::ModelLoaded => CoreEventKind::ModelLoaded,
+                RuntimeEventKind::ModelUnloaded => CoreEventKind::ModelUnloaded,
+                RuntimeEventKind::ModelEvicted => CoreEventKind::ModelEvicted,
+                RuntimeEventKind::HealthChanged => CoreEventKind::HealthChanged,
+                RuntimeEventKind::SloBurn => CoreEventKind::SloBurn,
+                RuntimeEventKind::ThermalThrottle => CoreEventKind::ThermalThrottle,
+                RuntimeEventKind::ResourceGrowth => CoreEventKind::ResourceGrowth,
+            },
+            level,
+            subject: subject.as_ptr(),
+            detail: detail.as_ptr(),
+            timestamp_ms: event.timestamp_ms,
+        };
+        sink.deliver(&c_event);
+    });
+    *out_subscription_id = id;
+    CoreErrorCode::Ok
+}
+
+/// Cancel an event subscription
+#[no_mangle]
+pub extern "C" fn core_unsubscribe_events(subscription_id: u64) -> CoreErrorCode {
+    if unsubscribe_events(subscription_id) {
+        CoreErrorCode::Ok
+    } else {
+        set_last_error("unknown subscription id");
+        CoreErrorCode::InvalidParams
+    }
+}
@@ -30,6 +30,7 @@ pub unsafe extern "C" fn core_health_check(
     let memory = rt.inner.memory_pool.available();
 
     let report = rt.inner.health.report(shutdown_state, models, memory, queue);
+    rt.inner.health.observe(report.state);
 
     (*out_report).state = match report.state {
         HealthState::Healthy => CoreHealthState::Healthy,
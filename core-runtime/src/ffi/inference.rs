@@ -8,7 +8,8 @@ use std::ffi::{c_char, CStr};
 use super::auth::CoreSession;
 use super::error::{set_last_error, CoreErrorCode};
 use super::runtime::CoreRuntime;
-use super::types::{CoreInferenceParams, CoreInferenceResult};
+use super::strings::utf8_arg;
+use super::types::{CoreInferenceParams, CoreInferenceResult, CORE_INFERENCE_PARAMS_VERSION};
 use crate::engine::InferenceParams;
 use crate::models::ModelHandle;
 
@@ -33,9 +34,73 @@ pub unsafe extern "C" fn core_infer(
         return CoreErrorCode::NullPointer;
     }
 
-    let rt = &*runtime;
-    let sess = &*session;
+    // Parse model ID
+    let model_str = match CStr::from_ptr(model_id).to_str() {
+        Ok(s) => s,
+        Err(_) => {
+            set_last_error("invalid UTF-8 in model_id");
+            return CoreErrorCode::InvalidParams;
+        }
+    };
+    infer(
+        &*runtime,
+        &*session,
+        model_str,
+        prompt_tokens,
+        prompt_token_count,
+        params,
+        out_result,
+    )
+}
+
+/// Submit inference request with a length-delimited UTF-8 model ID (blocking)
+///
+/// # Safety
+/// `model_id` must be valid for reads of `model_id_len` bytes and
+/// `prompt_tokens` for `prompt_token_count` elements; `params` may be NULL.
+#[no_mangle]
+pub unsafe extern "C" fn core_infer_utf8(
+    runtime: *mut CoreRuntime,
+    session: *mut CoreSession,
+    model_id: *const u8,
+    model_id_len: usize,
+    prompt_tokens: *const u32,
+    prompt_token_count: u32,
+    params: *const CoreInferenceParams,
+    out_result: *mut CoreInferenceResult,
+) -> CoreErrorCode {
+    if runtime.is_null() || session.is_null() {
+        set_last_error("null runtime or session pointer");
+        return CoreErrorCode::NullPointer;
+    }
+    if prompt_tokens.is_null() || out_result.is_null() {
+        set_last_error("null argument pointer");
+        return CoreErrorCode::NullPointer;
+    }
+    let model_str = match utf8_arg(model_id, model_id_len, "model_id") {
+        Ok(s) => s,
+        Err(code) => return code,
+    };
+    infer(
+        &*runtime,
+        &*session,
+        model_str,
+        prompt_tokens,
+        prompt_token_count,
+        params,
+        out_result,
+    )
+}
 
+unsafe fn infer(
+    rt: &CoreRuntime,
+    sess: &CoreSession,
+    _model_str: &str,
+    prompt_tokens: *const u32,
+    prompt_token_count: u32,
+    params: *const CoreInferenceParams,
+    out_result: *mut CoreInferenceResult,
+) -> CoreErrorCode {
     // Validate session
     let validate_result = rt
         .tokio
@@ -44,15 +109,6 @@ pub unsafe extern "C" fn core_infer(
         return e.into();
     }
 
-    // Parse model ID
-    let _model_str = match CStr::from_ptr(model_id).to_str() {
-        Ok(s) => s,
-        Err(_) => {
-            set_last_error("invalid UTF-8 in model_id");
-            return CoreErrorCode::InvalidParams;
-        }
-    };
-
     // SECURITY: Validate token count to prevent memory safety issues
     // Maximum reasonable token count (1M tokens = ~4MB of u32)
     const MAX_TOKEN_COUNT: u32 = 1_000_000;
@@ -76,7 +132,10 @@ pub unsafe extern "C" fn core_infer(
     } else {
         &*params
     };
-    let rust_params = params_from_c(c_params);
+    let rust_params = match params_from_c(c_params) {
+        Ok(p) => p,
+        Err(code) => return code,
+    };
 
     // Run inference
     let result = rt.tokio.block_on(async {
@@ -134,9 +193,27 @@ pub unsafe extern "C" fn core_free_tokens(tokens: *mut u32, count: u32) {
     }
 }
 
-/// Convert C params to Rust params
-pub(super) fn params_from_c(c: &CoreInferenceParams) -> InferenceParams {
-    InferenceParams {
+/// Get default inference parameters (sets the current struct_version)
+#[no_mangle]
+pub extern "C" fn core_inference_params_default(params: *mut CoreInferenceParams) {
+    if params.is_null() {
+        return;
+    }
+    unsafe {
+        *params = CoreInferenceParams::default();
+    }
+}
+
+/// Convert C params to Rust params, rejecting unknown layouts
+pub(super) fn params_from_c(c: &CoreInferenceParams) -> Result<InferenceParams, CoreErrorCode> {
+    if c.struct_version != CORE_INFERENCE_PARAMS_VERSION {
+        set_last_error(format!(
+            "unsupported CoreInferenceParams struct_version {} (expected {})",
+            c.struct_version, CORE_INFERENCE_PARAMS_VERSION
+        ));
+        return Err(CoreErrorCode::InvalidParams);
+    }
+    Ok(InferenceParams {
         max_tokens: c.max_tokens as usize,
         temperature: c.temperature,
         top_p: c.top_p,
@@ -147,7 +224,8 @@ pub(super) fn params_from_c(c: &CoreInferenceParams) -> InferenceParams {
         } else {
             Some(c.timeout_ms)
         },
-    }
+        sampler: None,
+    })
 }
 
 /// Write inference result to C struct
@@ -162,6 +240,7 @@ fn write_inference_result(result: &crate::engine::InferenceResult, out: &mut Cor
 impl Clone for CoreInferenceParams {
     fn clone(&self) -> Self {
         Self {
+            struct_version: self.struct_version,
             max_tokens: self.max_tokens,
             temperature: self.temperature,
             top_p: self.top_p,
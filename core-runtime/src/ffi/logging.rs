@@ -0,0 +1,145 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Log forwarding to a host callback for FFI
+//!
+//! The first `core_set_log_callback` installs a global tracing subscriber
+//! whose layer forwards events at or above the configured level to the
+//! callback. Later calls swap or clear the callback; the subscriber stays.
+
+use std::ffi::{c_char, c_void, CString};
+use std::sync::{OnceLock, RwLock};
+
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::util::SubscriberInitExt;
+
+use super::error::{set_last_error, CoreErrorCode};
+use super::types::CoreLogLevel;
+
+/// Log callback signature
+/// `target`, `message` and `fields_json` are borrowed for the call only.
+/// The callback may run on any runtime thread and must not unwind.
+pub type CoreLogCallback = unsafe extern "C" fn(
+    user_data: *mut c_void,
+    level: CoreLogLevel,
+    target: *const c_char,
+    message: *const c_char,
+    fields_json: *const c_char,
+);
+
+struct LogSink {
+    callback: CoreLogCallback,
+    user_data: *mut c_void,
+    min_level: CoreLogLevel,
+}
+
+// SAFETY: user_data pointer is provided by caller who ensures thread safety
+unsafe impl Send for LogSink {}
+unsafe impl Sync for LogSink {}
+
+static LOG_SINK: RwLock<Option<LogSink>> = RwLock::new(None);
+static INSTALLED: OnceLock<bool> = OnceLock::new();
+
+/// Set (or clear, with a NULL callback) the log callback
+///
+/// # Safety
+/// `user_data` is passed back verbatim; the caller keeps it valid until
+/// the callback is replaced or cleared.
+#[no_mangle]
+pub unsafe extern "C" fn core_set_log_callback(
+    callback: Option<CoreLogCallback>,
+    min_level: CoreLogLevel,
+    user_data: *mut c_void,
+) -> CoreErrorCode {
+    let installed = *INSTALLED.get_or_init(|| {
+        tracing_subscriber::registry()
+            .with(CallbackLayer)
+            .try_init()
+            .is_ok()
+    });
+    if !installed {
+        set_last_error("a global log subscriber is already installed");
+        return CoreErrorCode::Internal;
+    }
+
+    let sink = callback.map(|callback| LogSink {
+        callback,
+        user_data,
+        min_level,
+    });
+    *LOG_SINK.write().unwrap_or_else(|e| e.into_inner()) = sink;
+    CoreErrorCode::Ok
+}
+
+/// Layer forwarding tracing events to the registered callback.
+struct CallbackLayer;
+
+impl<S: Subscriber> Layer<S> for CallbackLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let guard = LOG_SINK.read().unwrap_or_else(|e| e.into_inner());
+        let Some(sink) = guard.as_ref() else {
+            return;
+        };
+        let metadata = event.metadata();
+        let level = CoreLogLevel::from(*metadata.level());
+        if level < sink.min_level {
+            return;
+        }
+
+        let mut fields = FieldCollector::default();
+        event.record(&mut fields);
+        let target = CString::new(metadata.target()).unwrap_or_default();
+        let message = CString::new(fields.message).unwrap_or_default();
+        let json = serde_json::Value::Object(fields.fields).to_string();
+        let json = CString::new(json).unwrap_or_default();
+
+        unsafe {
+            (sink.callback)(
+                sink.user_data,
+                level,
+                target.as_ptr(),
+                message.as_ptr(),
+                json.as_ptr(),
+            )
+        };
+    }
+}
+
+#[derive(Default)]
+struct FieldCollector {
+    message: String,
+    fields: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Visit for FieldCollector {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        let text = format!("{value:?}");
+        if field.name() == "message" {
+            self.message = text;
+        } else {
+            self.fields.insert(field.name().to_string(), text.into());
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = value.to_string();
+        } else {
+            self.fields.insert(field.name().to_string(), value.into());
+        }
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.fields.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.fields.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.fields.insert(field.name().to_string(), value.into());
+    }
+}
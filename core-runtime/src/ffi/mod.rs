@@ -8,18 +8,24 @@
 
 mod auth;
 mod error;
+mod events;
 mod health;
 mod inference;
+mod logging;
 mod models;
 mod runtime;
 mod streaming;
+mod strings;
 mod types;
 
 pub use auth::*;
 pub use error::{core_clear_last_error, core_get_last_error, CoreErrorCode};
+pub use events::*;
 pub use health::*;
 pub use inference::*;
+pub use logging::*;
 pub use models::*;
 pub use runtime::*;
 pub use streaming::*;
+pub use strings::CORE_MAX_STRING_BYTES;
 pub use types::*;
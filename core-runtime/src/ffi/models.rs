@@ -7,6 +7,7 @@ use std::ffi::{c_char, CStr, CString};
 
 use super::error::{set_last_error, CoreErrorCode};
 use super::runtime::CoreRuntime;
+use super::strings::utf8_arg;
 use super::types::CoreModelMetadata;
 
 /// Load a model from path (relative to base_path/models/)
@@ -29,6 +30,38 @@ pub unsafe extern "C" fn core_model_load(
             return CoreErrorCode::InvalidParams;
         }
     };
+    load_model(rt, path_str, out_handle_id)
+}
+
+/// Load a model from a length-delimited UTF-8 path
+///
+/// # Safety
+/// `model_path` must be valid for reads of `model_path_len` bytes;
+/// `runtime` and `out_handle_id` must be valid pointers.
+#[no_mangle]
+pub unsafe extern "C" fn core_model_load_utf8(
+    runtime: *mut CoreRuntime,
+    model_path: *const u8,
+    model_path_len: usize,
+    out_handle_id: *mut u64,
+) -> CoreErrorCode {
+    if runtime.is_null() || out_handle_id.is_null() {
+        set_last_error("null pointer argument");
+        return CoreErrorCode::NullPointer;
+    }
+    let path_str = match utf8_arg(model_path, model_path_len, "model_path") {
+        Ok(s) => s,
+        Err(code) => return code,
+    };
+    load_model(&*runtime, path_str, out_handle_id)
+}
+
+unsafe fn load_model(rt: &CoreRuntime, path_str: &str, out_handle_id: *mut u64) -> CoreErrorCode {
+    // Refuse loads during a maintenance window
+    if let Err(e) = rt.inner.maintenance.check_model_load() {
+        set_last_error(e.to_string());
+        return CoreErrorCode::ModelLoadFailed;
+    }
 
     // Validate path
     let model_path = match rt.inner.model_loader.validate_path(path_str) {
@@ -36,18 +69,35 @@ pub unsafe extern "C" fn core_model_load(
         Err(e) => return e.into(),
     };
 
-    // Load metadata
-    let metadata = match rt.inner.model_loader.load_metadata(&model_path) {
-        Ok(m) => m,
+    // Check provenance before reading the file as a model; the runtime
+    // context lets the outcome reach the audit log
+    let loader = &rt.inner.model_loader;
+    let attestation = {
+        let _context = rt.tokio.enter();
+        match loader.attest(&model_path) {
+            Ok(attestation) => attestation,
+            Err(e) => return e.into(),
+        }
+    };
+
+    // Validate and load metadata under the quarantine guard
+    let loaded = loader.load_guarded(&model_path, |_, format| {
+        loader.load_metadata(&model_path).map(|m| (m, format))
+    });
+    let (metadata, format) = match loaded {
+        Ok(loaded) => loaded,
         Err(e) => return e.into(),
     };
 
-    // Register model
+    // Register model under its detected format
     let handle = rt.tokio.block_on(async {
-        rt.inner
-            .model_registry
-            .register(metadata, 0)
-            .await
+        let registry = &rt.inner.model_registry;
+        let handle = registry.register_with_format(metadata, 0, format.as_str().to_string()).await;
+        if let Some(attestation) = attestation {
+            registry.set_attestation(handle, attestation).await;
+        }
+        registry.set_path(handle, model_path.as_path().to_path_buf()).await;
+        handle
     });
 
     *out_handle_id = handle.id();
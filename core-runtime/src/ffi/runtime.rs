@@ -11,7 +11,7 @@ use std::time::Duration;
 use tokio::runtime::Runtime as TokioRuntime;
 
 use super::error::{set_last_error, CoreErrorCode};
-use super::types::CoreConfig;
+use super::types::{CoreConfig, CORE_ABI_VERSION};
 use crate::{Runtime, RuntimeConfig};
 
 /// Opaque handle wrapping Rust runtime
@@ -20,6 +20,12 @@ pub struct CoreRuntime {
     pub(crate) tokio: TokioRuntime,
 }
 
+/// ABI version (`major << 16 | minor`) for load-time compatibility checks
+#[no_mangle]
+pub extern "C" fn core_abi_version() -> u32 {
+    CORE_ABI_VERSION
+}
+
 /// Get default configuration values
 #[no_mangle]
 pub extern "C" fn core_config_default(config: *mut CoreConfig) {
@@ -11,6 +11,7 @@ use super::auth::CoreSession;
 use super::error::{set_last_error, CoreErrorCode};
 use super::inference::params_from_c;
 use super::runtime::CoreRuntime;
+use super::strings::utf8_arg;
 use super::types::CoreInferenceParams;
 use crate::engine::TokenStream;
 
@@ -78,9 +79,77 @@ pub unsafe extern "C" fn core_infer_streaming(
         return CoreErrorCode::NullPointer;
     }
 
-    let rt = &*runtime;
-    let sess = &*session;
+    let model_str = match CStr::from_ptr(model_id).to_str() {
+        Ok(s) => s,
+        Err(_) => {
+            set_last_error("invalid UTF-8 in model_id");
+            return CoreErrorCode::InvalidParams;
+        }
+    };
+    infer_streaming(
+        &*runtime,
+        &*session,
+        model_str,
+        prompt_tokens,
+        prompt_token_count,
+        params,
+        callback,
+        user_data,
+    )
+}
 
+/// Submit streaming inference with a length-delimited UTF-8 model ID
+///
+/// # Safety
+/// `model_id` must be valid for reads of `model_id_len` bytes and
+/// `prompt_tokens` for `prompt_token_count` elements; `params` may be NULL.
+/// `callback` is invoked on the calling thread and must not unwind.
+#[no_mangle]
+pub unsafe extern "C" fn core_infer_streaming_utf8(
+    runtime: *mut CoreRuntime,
+    session: *mut CoreSession,
+    model_id: *const u8,
+    model_id_len: usize,
+    prompt_tokens: *const u32,
+    prompt_token_count: u32,
+    params: *const CoreInferenceParams,
+    callback: CoreStreamCallback,
+    user_data: *mut c_void,
+) -> CoreErrorCode {
+    if runtime.is_null() || session.is_null() {
+        set_last_error("null runtime or session pointer");
+        return CoreErrorCode::NullPointer;
+    }
+    if prompt_tokens.is_null() {
+        set_last_error("null argument pointer");
+        return CoreErrorCode::NullPointer;
+    }
+    let model_str = match utf8_arg(model_id, model_id_len, "model_id") {
+        Ok(s) => s,
+        Err(code) => return code,
+    };
+    infer_streaming(
+        &*runtime,
+        &*session,
+        model_str,
+        prompt_tokens,
+        prompt_token_count,
+        params,
+        callback,
+        user_data,
+    )
+}
+
+unsafe fn infer_streaming(
+    rt: &CoreRuntime,
+    sess: &CoreSession,
+    model_str: &str,
+    prompt_tokens: *const u32,
+    prompt_token_count: u32,
+    params: *const CoreInferenceParams,
+    callback: CoreStreamCallback,
+    user_data: *mut c_void,
+) -> CoreErrorCode {
     // Validate session
     let validate_result = rt
         .tokio
@@ -89,14 +158,6 @@ pub unsafe extern "C" fn core_infer_streaming(
         return e.into();
     }
 
-    let model_str = match CStr::from_ptr(model_id).to_str() {
-        Ok(s) => s,
-        Err(_) => {
-            set_last_error("invalid UTF-8 in model_id");
-            return CoreErrorCode::InvalidParams;
-        }
-    };
-
     // SECURITY: Validate token count to prevent memory safety issues
     // Maximum reasonable token count (1M tokens = ~4MB of u32)
     const MAX_TOKEN_COUNT: u32 = 1_000_000;
@@ -120,7 +181,10 @@ pub unsafe extern "C" fn core_infer_streaming(
     } else {
         &*params
     };
-    let rust_params = params_from_c(c_params);
+    let rust_params = match params_from_c(c_params) {
+        Ok(p) => p,
+        Err(code) => return code,
+    };
 
     let cancelled = Arc::new(AtomicBool::new(false));
     let invoker = CallbackInvoker {
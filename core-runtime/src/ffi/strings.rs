@@ -0,0 +1,43 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Length-delimited UTF-8 string arguments for FFI
+//!
+//! Managed hosts (.NET, JVM) hold strings as UTF-16 and marshal them to
+//! UTF-8 byte buffers without a trailing NUL. The `_utf8` entry points take
+//! a pointer and byte length instead of a C string; the bytes must be valid
+//! UTF-8 and are never read past `len`.
+
+use super::error::{set_last_error, CoreErrorCode};
+
+/// Longest string argument accepted (1 MiB).
+pub const CORE_MAX_STRING_BYTES: usize = 1024 * 1024;
+
+/// Borrow a `(ptr, len)` UTF-8 argument as `&str`.
+///
+/// A null pointer is accepted only when `len` is zero.
+///
+/// # Safety
+/// `ptr` must be valid for reads of `len` bytes for the lifetime `'a`.
+pub(super) unsafe fn utf8_arg<'a>(
+    ptr: *const u8,
+    len: usize,
+    name: &str,
+) -> Result<&'a str, CoreErrorCode> {
+    if len == 0 {
+        return Ok("");
+    }
+    if ptr.is_null() {
+        set_last_error(format!("null {name} pointer"));
+        return Err(CoreErrorCode::NullPointer);
+    }
+    if len > CORE_MAX_STRING_BYTES {
+        set_last_error(format!("{name} exceeds {CORE_MAX_STRING_BYTES} bytes"));
+        return Err(CoreErrorCode::InvalidParams);
+    }
+    let bytes = std::slice::from_raw_parts(ptr, len);
+    std::str::from_utf8(bytes).map_err(|_| {
+        set_last_error(format!("invalid UTF-8 in {name}"));
+        CoreErrorCode::InvalidParams
+    })
+}
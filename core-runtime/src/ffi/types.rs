@@ -5,6 +5,18 @@
 
 use std::ffi::c_char;
 
+/// ABI version of the C surface, encoded as `major << 16 | minor`.
+///
+/// The major component changes on any breaking change to a function
+/// signature or struct layout; the minor component changes when entry
+/// points are added. Managed wrappers should compare the major component
+/// against the one they were built for before calling anything else.
+pub const CORE_ABI_VERSION: u32 = (1 << 16) | 1;
+
+/// Layout version of [`CoreInferenceParams`]. Callers must set
+/// `struct_version` to this value (or use `core_inference_params_default`).
+pub const CORE_INFERENCE_PARAMS_VERSION: u32 = 1;
+
 /// Runtime configuration (C-compatible struct)
 #[repr(C)]
 pub struct CoreConfig {
@@ -36,8 +48,13 @@ impl Default for CoreConfig {
 }
 
 /// Inference parameters (matches InferenceParams)
+///
+/// Booleans are one byte; .NET hosts must marshal them with
+/// `[MarshalAs(UnmanagedType.U1)]`.
 #[repr(C)]
 pub struct CoreInferenceParams {
+    /// Layout version (must be CORE_INFERENCE_PARAMS_VERSION)
+    pub struct_version: u32,
     /// Maximum tokens to generate (default: 256)
     pub max_tokens: u32,
     /// Temperature for sampling (default: 0.7)
@@ -55,6 +72,7 @@ pub struct CoreInferenceParams {
 impl Default for CoreInferenceParams {
     fn default() -> Self {
         Self {
+            struct_version: CORE_INFERENCE_PARAMS_VERSION,
             max_tokens: 256,
             temperature: 0.7,
             top_p: 0.9,
@@ -148,3 +166,54 @@ impl Default for CoreModelMetadata {
         }
     }
 }
+
+/// Log and event severity, ordered from most to least verbose
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CoreLogLevel {
+    Trace = 0,
+    Debug = 1,
+    Info = 2,
+    Warn = 3,
+    Error = 4,
+}
+
+impl From<tracing::Level> for CoreLogLevel {
+    fn from(level: tracing::Level) -> Self {
+        match level {
+            tracing::Level::TRACE => Self::Trace,
+            tracing::Level::DEBUG => Self::Debug,
+            tracing::Level::INFO => Self::Info,
+            tracing::Level::WARN => Self::Warn,
+            tracing::Level::ERROR => Self::Error,
+        }
+    }
+}
+
+/// Runtime event kind
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoreEventKind {
+    ModelLoaded = 0,
+    ModelUnloaded = 1,
+    ModelEvicted = 2,
+    HealthChanged = 3,
+    SloBurn = 4,
+    ThermalThrottle = 5,
+    ResourceGrowth = 6,
+}
+
+/// Runtime event (borrowed, valid only for the duration of the callback)
+#[repr(C)]
+pub struct CoreEvent {
+    /// Event kind
+    pub kind: CoreEventKind,
+    /// Event severity
+    pub level: CoreLogLevel,
+    /// Subject of the event (model ID or health state)
+    pub subject: *const c_char,
+    /// Human-readable detail
+    pub detail: *const c_char,
+    /// Milliseconds since the Unix epoch
+    pub timestamp_ms: u64,
+}
@@ -3,11 +3,17 @@
 //! Provides liveness, readiness, and full health report capabilities
 //! for orchestrator integration (Kubernetes, systemd).
 
+use std::sync::Mutex;
 use std::time::Instant;
 
 use serde::{Deserialize, Serialize};
 
+use crate::exit_report::ExitReport;
+use crate::maintenance::MaintenanceStatus;
+use crate::scheduler::{DegradationLevel, OccupancySnapshot};
+use crate::security::fips_mode::{self, FipsMode};
 use crate::shutdown::ShutdownState;
+use crate::telemetry::{emit_event, RuntimeEvent, RuntimeEventKind};
 
 /// Overall health status.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -27,6 +33,45 @@ pub struct HealthReport {
     pub memory_used_bytes: usize,
     pub queue_depth: usize,
     pub uptime_secs: u64,
+    /// Current overload degradation level.
+    #[serde(default)]
+    pub degradation: DegradationLevel,
+    /// Active or upcoming maintenance window.
+    #[serde(default)]
+    pub maintenance: MaintenanceStatus,
+    /// Worker occupancy and running batches.
+    #[serde(default)]
+    pub scheduler: OccupancySnapshot,
+    /// How the previous run of this instance ended.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_exit: Option<ExitReport>,
+    /// Cryptographic algorithm policy.
+    #[serde(default)]
+    pub fips_mode: FipsMode,
+}
+
+impl HealthReport {
+    /// Overlay the overload degradation level. Any active ladder step
+    /// downgrades a healthy report to `Degraded`.
+    pub fn with_degradation(mut self, level: DegradationLevel) -> Self {
+        self.degradation = level;
+        if level > DegradationLevel::Normal && self.state == HealthState::Healthy {
+            self.state = HealthState::Degraded;
+        }
+        self
+    }
+
+    /// Overlay scheduler occupancy.
+    pub fn with_scheduler(mut self, scheduler: OccupancySnapshot) -> Self {
+        self.scheduler = scheduler;
+        self
+    }
+
+    /// Overlay the maintenance window state.
+    pub fn with_maintenance(mut self, status: MaintenanceStatus) -> Self {
+        self.maintenance = status;
+        self
+    }
 }
 
 /// Health check configuration.
@@ -49,6 +94,9 @@ impl Default for HealthConfig {
 pub struct HealthChecker {
     config: HealthConfig,
     start_time: Instant,
+    /// Last reported state, for transition events.
+    last_state: Mutex<Option<HealthState>>,
+    previous_exit: Option<ExitReport>,
 }
 
 impl HealthChecker {
@@ -56,9 +104,41 @@ impl HealthChecker {
         Self {
             config,
             start_time: Instant::now(),
+            last_state: Mutex::new(None),
+            previous_exit: None,
         }
     }
 
+    /// Report how the previous run ended, for post-mortems.
+    pub fn with_previous_exit(mut self, report: Option<ExitReport>) -> Self {
+        self.previous_exit = report;
+        self
+    }
+
+    /// Record the state of a finished report, emitting a `HealthChanged`
+    /// event when it differs from the previous one. Returns true on change.
+    pub fn observe(&self, state: HealthState) -> bool {
+        let mut last = self.last_state.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(previous) = last.replace(state) else {
+            return false;
+        };
+        if previous == state {
+            return false;
+        }
+        let level = match state {
+            HealthState::Healthy => tracing::Level::INFO,
+            HealthState::Degraded => tracing::Level::WARN,
+            HealthState::Unhealthy => tracing::Level::ERROR,
+        };
+        emit_event(RuntimeEvent::new(
+            RuntimeEventKind::HealthChanged,
+            level,
+            format!("{state:?}"),
+            format!("health changed from {previous:?} to {state:?}"),
+        ));
+        true
+    }
+
     /// Check liveness: process is responsive.
     pub fn is_alive(&self) -> bool {
         true
@@ -98,6 +178,11 @@ impl HealthChecker {
             memory_used_bytes: memory_bytes,
             queue_depth: queue,
             uptime_secs: self.start_time.elapsed().as_secs(),
+            degradation: DegradationLevel::Normal,
+            maintenance: MaintenanceStatus::default(),
+            scheduler: OccupancySnapshot::default(),
+            previous_exit: self.previous_exit.clone(),
+            fips_mode: fips_mode::mode(),
         }
     }
 
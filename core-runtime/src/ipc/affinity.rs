@@ -0,0 +1,144 @@
+//! Sticky-session routing hints.
+//!
+//! Each successful response carries a `session_affinity_key`. Clients echo
+//! it on follow-up turns, and external load balancers route on it. The
+//! tracker remembers which keys this replica served recently, bounded by
+//! the same kind of TTL/capacity limits the KV cache uses, so an
+//! `AffinityQuery` can report whether the conversation's KV state is
+//! likely still resident here.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use sha2::{Digest, Sha256};
+
+/// Hex length of an affinity key (128-bit digest prefix).
+const KEY_HEX_LEN: usize = 32;
+
+/// Affinity tracking limits.
+#[derive(Debug, Clone)]
+pub struct AffinityConfig {
+    /// How long a key stays resident after its last turn.
+    pub ttl: Duration,
+    /// Maximum tracked keys; the least recently used is dropped first.
+    pub capacity: usize,
+}
+
+impl Default for AffinityConfig {
+    fn default() -> Self {
+        Self {
+            ttl: Duration::from_secs(600),
+            capacity: 4096,
+        }
+    }
+}
+
+/// Tracks affinity keys served by this replica.
+pub struct AffinityTracker {
+    config: AffinityConfig,
+    entries: Mutex<HashMap<String, Instant>>,
+}
+
+impl AffinityTracker {
+    pub fn new(config: AffinityConfig) -> Self {
+        Self {
+            config,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Derive the key for a new sequence from its model and first prompt.
+    pub fn derive_key(model_id: &str, prompt: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(model_id.as_bytes());
+        hasher.update([0u8]);
+        hasher.update(prompt.as_bytes());
+        let digest = hasher.finalize();
+        digest[..KEY_HEX_LEN / 2].iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    /// Reuse a client-supplied key if well-formed, else derive a new one.
+    pub fn resolve_key(supplied: Option<&str>, model_id: &str, prompt: &str) -> String {
+        match supplied {
+            Some(key) if is_valid_key(key) => key.to_string(),
+            _ => Self::derive_key(model_id, prompt),
+        }
+    }
+
+    /// Drop `key`. Returns false if it was not tracked.
+    pub fn forget(&self, key: &str) -> bool {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner()).remove(key).is_some()
+    }
+
+    /// Record that `key` was just served here.
+    pub fn touch(&self, key: &str) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let ttl = self.config.ttl;
+        entries.retain(|_, seen| seen.elapsed() < ttl);
+        if entries.len() >= self.config.capacity && !entries.contains_key(key) {
+            let oldest = entries.iter().min_by_key(|(_, seen)| **seen).map(|(k, _)| k.clone());
+            if let Some(oldest) = oldest {
+                entries.remove(&oldest);
+            }
+        }
+        entries.insert(key.to_string(), Instant::now());
+    }
+
+    /// Whether `key` was served here within the TTL.
+    pub fn is_resident(&self, key: &str) -> bool {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.get(key).is_some_and(|seen| seen.elapsed() < self.config.ttl)
+    }
+}
+
+impl Default for AffinityTracker {
+    fn default() -> Self {
+        Self::new(AffinityConfig::default())
+    }
+}
+
+fn is_valid_key(key: &str) -> bool {
+    key.len() == KEY_HEX_LEN && key.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_is_stable_and_echo_is_reused() {
+        let key = AffinityTracker::derive_key("phi-3", "Hello");
+        assert_eq!(key.len(), KEY_HEX_LEN);
+        assert_eq!(key, AffinityTracker::derive_key("phi-3", "Hello"));
+        assert_ne!(key, AffinityTracker::derive_key("llama", "Hello"));
+
+        let reused = AffinityTracker::resolve_key(Some(&key), "phi-3", "next turn");
+        assert_eq!(reused, key);
+        let fresh = AffinityTracker::resolve_key(Some("not-a-key"), "phi-3", "next turn");
+        assert_ne!(fresh, "not-a-key");
+    }
+
+    #[test]
+    fn test_residency_respects_capacity() {
+        let tracker = AffinityTracker::new(AffinityConfig {
+            ttl: Duration::from_secs(60),
+            capacity: 1,
+        });
+        tracker.touch("a");
+        assert!(tracker.is_resident("a"));
+        tracker.touch("b");
+        assert!(!tracker.is_resident("a"));
+        assert!(tracker.is_resident("b"));
+    }
+
+    #[test]
+    fn test_residency_expires() {
+        let tracker = AffinityTracker::new(AffinityConfig {
+            ttl: Duration::ZERO,
+            capacity: 8,
+        });
+        tracker.touch("a");
+        assert!(!tracker.is_resident("a"));
+    }
+}
@@ -11,6 +11,7 @@
 //! - Session timeout (limits exposure window)
 //! - Security audit logging (enables forensic analysis)
 
+use crate::scheduler::Priority;
 use crate::telemetry::{log_security_event, SecurityEvent};
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
@@ -70,7 +71,43 @@ impl SessionToken {
     }
 }
 
+/// Capability class of an authenticated session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionRole {
+    /// Authenticated with the standard handshake token.
+    Standard,
+    /// Authenticated with the admin handshake token.
+    Admin,
+    /// Authenticated with the observer handshake token: read-only queries
+    /// (health, status, metrics, spans) for dashboards and monitoring agents.
+    Observer,
+}
+
+impl SessionRole {
+    /// Highest request priority this role may request.
+    pub fn max_priority(&self) -> Priority {
+        match self {
+            Self::Standard => Priority::High,
+            Self::Admin => Priority::Critical,
+            Self::Observer => Priority::Low,
+        }
+    }
+
+    /// Metric label for the role.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Standard => "standard",
+            Self::Admin => "admin",
+            Self::Observer => "observer",
+        }
+    }
+}
+
 struct Session {
+    role: SessionRole,
+    /// Stable identity behind the session: the tenant name of a tenant
+    /// token, otherwise the role of the shared token it presented.
+    principal: String,
     created_at: Instant,
     last_activity: Instant,
     connection_count: AtomicUsize,
@@ -159,6 +196,10 @@ impl RateLimiter {
 pub struct SessionAuth {
     sessions: Arc<RwLock<HashMap<SessionToken, Session>>>,
     expected_token_hash: [u8; 32],
+    admin_token_hash: Option<[u8; 32]>,
+    observer_token_hash: Option<[u8; 32]>,
+    /// Per-tenant handshake tokens granting `SessionRole::Standard`.
+    tenant_token_hashes: Vec<(String, [u8; 32])>,
     session_timeout: Duration,
     rate_limiter: RateLimiter,
 }
@@ -166,18 +207,36 @@ pub struct SessionAuth {
 impl SessionAuth {
     /// Create new auth manager with expected handshake token.
     pub fn new(expected_token: &str, session_timeout: Duration) -> Self {
-        let mut hasher = Sha256::new();
-        hasher.update(expected_token.as_bytes());
-        let expected_token_hash: [u8; 32] = hasher.finalize().into();
-
         Self {
             sessions: Arc::new(RwLock::new(HashMap::new())),
-            expected_token_hash,
+            expected_token_hash: hash_token(expected_token),
+            admin_token_hash: None,
+            observer_token_hash: None,
+            tenant_token_hashes: Vec::new(),
             session_timeout,
             rate_limiter: RateLimiter::new(),
         }
     }
 
+    /// Accept a second handshake token that grants `SessionRole::Admin`.
+    pub fn with_admin_token(mut self, admin_token: &str) -> Self {
+        self.admin_token_hash = Some(hash_token(admin_token));
+        self
+    }
+
+    /// Accept a handshake token that grants read-only `SessionRole::Observer`.
+    pub fn with_observer_token(mut self, observer_token: &str) -> Self {
+        self.observer_token_hash = Some(hash_token(observer_token));
+        self
+    }
+
+    /// Accept a standard handshake token that identifies `tenant`, so its
+    /// sessions keep one principal across reconnects.
+    pub fn with_tenant_token(mut self, tenant: &str, token: &str) -> Self {
+        self.tenant_token_hashes.push((tenant.to_string(), hash_token(token)));
+        self
+    }
+
     /// Validate handshake token and create session.
     /// Implements rate limiting to prevent brute-force attacks.
     pub async fn authenticate(&self, token: &str) -> Result<SessionToken, AuthError> {
@@ -191,11 +250,21 @@ impl SessionAuth {
             return Err(AuthError::RateLimited);
         }
 
-        let mut hasher = Sha256::new();
-        hasher.update(token.as_bytes());
-        let token_hash: [u8; 32] = hasher.finalize().into();
-
-        if !constant_time_compare(token_hash.as_slice(), self.expected_token_hash.as_slice()) {
+        let token_hash = hash_token(token);
+        // Compare against every hash unconditionally to keep timing uniform
+        let is_standard = constant_time_compare(&token_hash, &self.expected_token_hash);
+        let is_admin = self
+            .admin_token_hash
+            .is_some_and(|admin| constant_time_compare(&token_hash, &admin));
+        let is_observer = self
+            .observer_token_hash
+            .is_some_and(|observer| constant_time_compare(&token_hash, &observer));
+        let tenant = self.tenant_token_hashes.iter().fold(None, |found, (name, hash)| {
+            let hit = constant_time_compare(&token_hash, hash);
+            found.or_else(|| hit.then(|| name.clone()))
+        });
+
+        if !is_standard && !is_admin && !is_observer && tenant.is_none() {
             // Record failed attempt for rate limiting
             self.rate_limiter.record_failure();
             log_security_event(
@@ -213,9 +282,24 @@ impl SessionAuth {
         let session_token = SessionToken(session_id);
         let now = Instant::now();
 
+        // Admin wins if tokens collide; a shared standard/observer token stays
+        // read-only so misconfiguration never widens access.
+        let role = if is_admin {
+            SessionRole::Admin
+        } else if is_observer {
+            SessionRole::Observer
+        } else {
+            SessionRole::Standard
+        };
+        let principal = match tenant {
+            Some(tenant) if role == SessionRole::Standard => tenant,
+            _ => role.as_str().to_string(),
+        };
         self.sessions.write().await.insert(
             session_token.clone(),
             Session {
+                role,
+                principal,
                 created_at: now,
                 last_activity: now,
                 connection_count: AtomicUsize::new(0),
@@ -304,6 +388,22 @@ impl SessionAuth {
         Ok(())
     }
 
+    /// Role of an active session, if it exists.
+    pub async fn role(&self, token: &SessionToken) -> Option<SessionRole> {
+        self.sessions.read().await.get(token).map(|s| s.role)
+    }
+
+    /// Stable identity of an active session, for attributing tenant state
+    /// that must outlive a reconnect.
+    pub async fn principal(&self, token: &SessionToken) -> Option<String> {
+        self.sessions.read().await.get(token).map(|s| s.principal.clone())
+    }
+
+    /// Principal behind a raw session id, such as one named by an admin.
+    pub async fn principal_by_id(&self, session_id: &str) -> Option<String> {
+        self.principal(&SessionToken(session_id.to_string())).await
+    }
+
     /// Remove expired sessions.
     pub async fn cleanup(&self) {
         let mut sessions = self.sessions.write().await;
@@ -334,6 +434,12 @@ impl SessionAuth {
     }
 }
 
+fn hash_token(token: &str) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(token.as_bytes());
+    hasher.finalize().into()
+}
+
 /// Constant-time comparison to prevent timing attacks.
 fn constant_time_compare(a: &[u8], b: &[u8]) -> bool {
     if a.len() != b.len() {
@@ -611,4 +717,50 @@ mod tests {
         assert!(auth.validate(&session1).await.is_ok());
         assert!(auth.validate(&session2).await.is_ok());
     }
+
+    /// Test session roles follow the token used at handshake
+    #[tokio::test]
+    async fn test_admin_token_grants_admin_role() {
+        let auth = SessionAuth::new("user-token", Duration::from_secs(3600))
+            .with_admin_token("admin-token");
+
+        let user = auth.authenticate("user-token").await.unwrap();
+        let admin = auth.authenticate("admin-token").await.unwrap();
+
+        assert_eq!(auth.role(&user).await, Some(SessionRole::Standard));
+        assert_eq!(auth.role(&admin).await, Some(SessionRole::Admin));
+        assert_eq!(SessionRole::Standard.max_priority(), Priority::High);
+        assert_eq!(SessionRole::Admin.max_priority(), Priority::Critical);
+    }
+
+    /// Test the observer token grants a distinct read-only role
+    #[tokio::test]
+    async fn test_observer_token_grants_observer_role() {
+        let auth = SessionAuth::new("user-token", Duration::from_secs(3600))
+            .with_observer_token("observer-token");
+
+        let observer = auth.authenticate("observer-token").await.unwrap();
+        let user = auth.authenticate("user-token").await.unwrap();
+
+        assert_eq!(auth.role(&observer).await, Some(SessionRole::Observer));
+        assert_eq!(auth.role(&user).await, Some(SessionRole::Standard));
+        assert_eq!(SessionRole::Observer.as_str(), "observer");
+    }
+
+    /// Test tenant tokens give every session of a tenant the same principal
+    #[tokio::test]
+    async fn test_tenant_token_principal_survives_reconnect() {
+        let auth = SessionAuth::new("user-token", Duration::from_secs(3600))
+            .with_tenant_token("acme", "acme-token");
+
+        let first = auth.authenticate("acme-token").await.unwrap();
+        let second = auth.authenticate("acme-token").await.unwrap();
+        let shared = auth.authenticate("user-token").await.unwrap();
+
+        assert_ne!(first, second);
+        assert_eq!(auth.principal(&first).await.as_deref(), Some("acme"));
+        assert_eq!(auth.principal(&second).await.as_deref(), Some("acme"));
+        assert_eq!(auth.role(&second).await, Some(SessionRole::Standard));
+        assert_eq!(auth.principal(&shared).await.as_deref(), Some("standard"));
+    }
 }
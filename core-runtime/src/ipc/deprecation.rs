@@ -0,0 +1,247 @@
+//! Protocol deprecation.
+//!
+//! Message types, message fields and protocol versions can be marked
+//! deprecated in config, so an old wire format (protocol V1 once V2 binary
+//! encoding lands) is retired on a schedule rather than by surprise:
+//!
+//! - clients see the list in `HandshakeAck.deprecations`;
+//! - every use is counted per client in
+//!   `core_protocol_deprecated_use_total` and logged once per client;
+//! - past its sunset date a target is refused with a `410` error when
+//!   `enforce_sunset` is set.
+//!
+//! A target names a message type (`metrics_request`), a field of one
+//! (`inference_request.prompt`) or a protocol version (`protocol.v1`).
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+use super::auth::SessionToken;
+use crate::telemetry::{self, tenant_label};
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum DeprecationError {
+    #[error("{target} was retired on {sunset}{}", use_instead(.replacement))]
+    Retired { target: String, sunset: NaiveDate, replacement: Option<String> },
+
+    #[error("Invalid deprecation config: {0}")]
+    InvalidConfig(String),
+}
+
+/// One deprecated message type, field or protocol version.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Deprecation {
+    pub target: String,
+    /// Last day the target is accepted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sunset: Option<NaiveDate>,
+    /// What clients should use instead.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replacement: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+impl Deprecation {
+    pub fn is_retired(&self, today: NaiveDate) -> bool {
+        self.sunset.is_some_and(|sunset| today > sunset)
+    }
+}
+
+/// Deprecations in effect, loaded from `CORE_DEPRECATIONS`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeprecationConfig {
+    #[serde(default)]
+    pub deprecations: Vec<Deprecation>,
+    /// Refuse targets past their sunset date; otherwise they only warn.
+    #[serde(default)]
+    pub enforce_sunset: bool,
+}
+
+impl DeprecationConfig {
+    pub fn validate(&self) -> Result<(), DeprecationError> {
+        let mut seen = std::collections::HashSet::new();
+        for deprecation in &self.deprecations {
+            let target = deprecation.target.as_str();
+            if target.is_empty() || target.split('.').any(str::is_empty) || target.split('.').count() > 2 {
+                return Err(DeprecationError::InvalidConfig(format!("bad target '{}'", target)));
+            }
+            if !seen.insert(target) {
+                return Err(DeprecationError::InvalidConfig(format!("duplicate target '{}'", target)));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn use_instead(replacement: &Option<String>) -> String {
+    replacement.as_ref().map(|r| format!("; use {}", r)).unwrap_or_default()
+}
+
+/// Deprecated use by one client.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DeprecatedUse {
+    pub client: String,
+    pub target: String,
+    pub count: u64,
+}
+
+/// Matches incoming frames against the deprecations and counts their use.
+pub struct DeprecationTracker {
+    config: DeprecationConfig,
+    usage: Mutex<HashMap<(String, String), u64>>,
+}
+
+impl DeprecationTracker {
+    pub fn new(config: DeprecationConfig) -> Self {
+        Self { config, usage: Mutex::new(HashMap::new()) }
+    }
+
+    /// Deprecations announced in `HandshakeAck`.
+    pub fn advertised(&self) -> Vec<Deprecation> {
+        self.config.deprecations.clone()
+    }
+
+    /// Count the deprecated targets a JSON frame uses, refusing it if one
+    /// is retired and sunsets are enforced. Clients are told apart by
+    /// tenant label; frames before the handshake count as `anonymous`.
+    pub fn check(&self, frame: &[u8], session: Option<&SessionToken>) -> Result<(), DeprecationError> {
+        if self.config.deprecations.is_empty() {
+            return Ok(());
+        }
+        let client = session.map_or_else(|| "anonymous".to_string(), |s| tenant_label(s.as_str()));
+        self.check_on(frame, &client, Utc::now().date_naive())
+    }
+
+    fn check_on(&self, frame: &[u8], client: &str, today: NaiveDate) -> Result<(), DeprecationError> {
+        let Ok(Value::Object(message)) = serde_json::from_slice::<Value>(frame) else {
+            return Ok(());
+        };
+        let Some(kind) = message.get("type").and_then(Value::as_str) else {
+            return Ok(());
+        };
+        let used = |target: &str| {
+            target == kind
+                || target.strip_prefix(kind).and_then(|t| t.strip_prefix('.')).is_some_and(|f| message.contains_key(f))
+                || (kind == "handshake" && target == protocol_target(&message))
+        };
+        for deprecation in self.config.deprecations.iter().filter(|d| used(&d.target)) {
+            self.record(client, &deprecation.target);
+            if self.config.enforce_sunset && deprecation.is_retired(today) {
+                return Err(DeprecationError::Retired {
+                    target: deprecation.target.clone(),
+                    sunset: deprecation.sunset.unwrap_or(today),
+                    replacement: deprecation.replacement.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn record(&self, client: &str, target: &str) {
+        let first = {
+            let mut usage = self.usage.lock().unwrap_or_else(|e| e.into_inner());
+            let count = usage.entry((client.to_string(), target.to_string())).or_insert(0);
+            *count += 1;
+            *count == 1
+        };
+        if first {
+            tracing::warn!(client, target, "Client used deprecated protocol feature");
+        }
+        telemetry::record_deprecated_use(target, client);
+    }
+
+    /// Deprecated use so far, by client then target.
+    pub fn usage(&self) -> Vec<DeprecatedUse> {
+        let usage = self.usage.lock().unwrap_or_else(|e| e.into_inner());
+        let mut uses: Vec<DeprecatedUse> = usage
+            .iter()
+            .map(|((client, target), count)| DeprecatedUse {
+                client: client.clone(),
+                target: target.clone(),
+                count: *count,
+            })
+            .collect();
+        uses.sort_by(|a, b| (&a.client, &a.target).cmp(&(&b.client, &b.target)));
+        uses
+    }
+}
+
+impl Default for DeprecationTracker {
+    fn default() -> Self {
+        Self::new(DeprecationConfig::default())
+    }
+}
+
+/// `protocol.v1` or `protocol.v2` for the version a handshake requests;
+/// no request means V1.
+fn protocol_target(handshake: &serde_json::Map<String, Value>) -> String {
+    let version = handshake.get("protocol_version").and_then(Value::as_str).unwrap_or("V1");
+    format!("protocol.{}", version.to_ascii_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracker(enforce_sunset: bool) -> DeprecationTracker {
+        let deprecation = |target: &str, sunset: Option<&str>| Deprecation {
+            target: target.into(),
+            sunset: sunset.map(|s| s.parse().unwrap()),
+            replacement: Some("protocol.v2".into()),
+            note: None,
+        };
+        DeprecationTracker::new(DeprecationConfig {
+            deprecations: vec![
+                deprecation("protocol.v1", Some("2026-06-30")),
+                deprecation("metrics_request", None),
+                deprecation("inference_request.prompt", Some("2027-01-01")),
+            ],
+            enforce_sunset,
+        })
+    }
+
+    #[test]
+    fn test_counts_use_per_client() {
+        let tracker = tracker(false);
+        let today = "2026-10-17".parse().unwrap();
+        tracker.check_on(br#"{"type":"handshake","token":"t"}"#, "tenant-a", today).unwrap();
+        tracker.check_on(br#"{"type":"handshake","token":"t","protocol_version":"V2"}"#, "tenant-b", today).unwrap();
+        tracker.check_on(br#"{"type":"metrics_request"}"#, "tenant-a", today).unwrap();
+        tracker.check_on(br#"{"type":"metrics_request"}"#, "tenant-a", today).unwrap();
+        tracker.check_on(br#"{"type":"inference_request","model_id":"m"}"#, "tenant-a", today).unwrap();
+
+        let usage = tracker.usage();
+        let uses: Vec<(&str, &str, u64)> = usage.iter().map(|u| (u.client.as_str(), u.target.as_str(), u.count)).collect();
+        assert_eq!(uses, [("tenant-a", "metrics_request", 2), ("tenant-a", "protocol.v1", 1)]);
+    }
+
+    #[test]
+    fn test_sunset_enforced() {
+        let today = "2026-10-17".parse().unwrap();
+        let v1 = br#"{"type":"handshake","token":"t"}"#;
+        assert!(tracker(false).check_on(v1, "c", today).is_ok());
+        let err = tracker(true).check_on(v1, "c", today).unwrap_err();
+        assert_eq!(err.to_string(), "protocol.v1 was retired on 2026-06-30; use protocol.v2");
+        // Before its sunset a field still passes
+        let prompt = br#"{"type":"inference_request","prompt":"hi"}"#;
+        assert!(tracker(true).check_on(prompt, "c", today).is_ok());
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(DeprecationConfig::default().validate().is_ok());
+        let bad = |target: &str| DeprecationConfig {
+            deprecations: vec![Deprecation { target: target.into(), sunset: None, replacement: None, note: None }],
+            enforce_sunset: false,
+        };
+        assert!(bad("a.b.c").validate().is_err());
+        assert!(bad("inference_request.").validate().is_err());
+        assert!(bad("protocol.v1").validate().is_ok());
+    }
+}
@@ -0,0 +1,199 @@
+//! Admin-only operations: profiling, usage, snapshots, model administration,
+//! drain, audit tail, purge, legal holds and feature flags.
+
+use crate::ipc::auth::SessionToken;
+use crate::ipc::protocol::{
+    IpcMessage, LegalHoldAction, ModelAdminRequest, PurgeTarget, SnapshotAction, SnapshotRequest,
+};
+use crate::security::audit::audit_logger;
+use crate::security::image_input;
+use crate::security::{LegalHoldError, PurgeReceipt, PurgeScope, ReceiptSigner};
+use crate::telemetry::{self, tenant_label, ProfileError};
+
+use super::IpcHandler;
+
+/// Upper bound on events returned by one `AuditTailRequest`.
+const MAX_AUDIT_TAIL: usize = 500;
+
+impl IpcHandler {
+    /// Capture a CPU profile for an admin session.
+    pub(super) async fn handle_profile(&self, seconds: u32, session: Option<&SessionToken>) -> IpcMessage {
+        if !self.is_admin(session).await {
+            return IpcMessage::Error { code: 403, message: "Admin session required".into() };
+        }
+        match self.profiler.capture(seconds).await {
+            Ok(profile) => IpcMessage::ProfileResponse { profile: image_input::encode_base64(&profile) },
+            Err(e) => {
+                let code = match e {
+                    ProfileError::InvalidDuration { .. } => 400,
+                    ProfileError::Busy => 409,
+                    ProfileError::Disabled | ProfileError::NotCompiled => 501,
+                    ProfileError::Failed(_) => 500,
+                };
+                IpcMessage::Error { code, message: e.to_string() }
+            }
+        }
+    }
+
+    /// Report top heap allocation sites for an admin session.
+    pub(super) async fn handle_heap_profile(&self, top: usize, session: Option<&SessionToken>) -> IpcMessage {
+        if !self.is_admin(session).await {
+            return IpcMessage::Error { code: 403, message: "Admin session required".into() };
+        }
+        if !telemetry::heap::is_active() {
+            return IpcMessage::Error {
+                code: 501,
+                message: "Heap tracking not compiled in. Enable 'heap-profiling' feature.".into(),
+            };
+        }
+        IpcMessage::HeapProfileResponse(telemetry::heap_report(top.clamp(1, telemetry::heap::MAX_TAGS)))
+    }
+
+    /// Export per-tenant usage for an admin session, noised per config.
+    pub(super) async fn handle_usage_export(&self, session: Option<&SessionToken>) -> IpcMessage {
+        if !self.is_admin(session).await {
+            return IpcMessage::Error { code: 403, message: "Admin session required".into() };
+        }
+        IpcMessage::UsageExportResponse(self.usage.export(self.config.usage_privacy.as_ref()))
+    }
+
+    /// Create or restore a snapshot. Restore loads models and stages config,
+    /// so it needs an admin session.
+    pub(super) async fn handle_snapshot(&self, request: SnapshotRequest, session: Option<&SessionToken>) -> IpcMessage {
+        if request.action == SnapshotAction::Restore && !self.is_admin(session).await {
+            return IpcMessage::Error { code: 403, message: "Admin session required".into() };
+        }
+        IpcMessage::SnapshotResponse(self.snapshots.handle(request).await)
+    }
+
+    /// Mutate the model registry for an admin session.
+    pub(super) async fn handle_model_admin(
+        &self,
+        request: ModelAdminRequest,
+        session: Option<&SessionToken>,
+    ) -> IpcMessage {
+        if !self.is_admin(session).await {
+            return IpcMessage::Error { code: 403, message: "Admin session required".into() };
+        }
+        IpcMessage::ModelAdminResponse(self.model_admin.handle(request).await)
+    }
+
+    /// Stop admitting new requests for an admin session.
+    pub(super) async fn handle_drain(&self, session: Option<&SessionToken>) -> IpcMessage {
+        if !self.is_admin(session).await {
+            return IpcMessage::Error { code: 403, message: "Admin session required".into() };
+        }
+        let in_flight = self.shutdown.drain().await;
+        tracing::warn!(in_flight, "Drain requested over IPC; no longer accepting requests");
+        IpcMessage::DrainResponse { in_flight }
+    }
+
+    /// Most recent audit events for an admin session.
+    pub(super) async fn handle_audit_tail(&self, limit: usize, session: Option<&SessionToken>) -> IpcMessage {
+        if !self.is_admin(session).await {
+            return IpcMessage::Error { code: 403, message: "Admin session required".into() };
+        }
+        let mut events = match audit_logger() {
+            Some(logger) => logger.get_events().await,
+            None => Vec::new(),
+        };
+        let skip = events.len().saturating_sub(limit.min(MAX_AUDIT_TAIL));
+        events.drain(..skip);
+        IpcMessage::AuditTailResponse { events }
+    }
+
+    /// Erase conversations, affinity entries and usage records for a
+    /// subject, returning a signed receipt. The runtime keeps no KV spill
+    /// files or request journals, so these are all the server holds.
+    pub(super) async fn handle_purge(&self, target: PurgeTarget, session: Option<&SessionToken>) -> IpcMessage {
+        if !self.is_admin(session).await {
+            return IpcMessage::Error { code: 403, message: "Admin session required".into() };
+        }
+        let (scope, subject, tenant) = match target {
+            PurgeTarget::Tenant(tenant) => (PurgeScope::Tenant, tenant.clone(), Some(tenant)),
+            PurgeTarget::Session(id) => match self.auth.principal_by_id(&id).await {
+                Some(principal) => (PurgeScope::Session, id, Some(tenant_label(&principal))),
+                None => return IpcMessage::Error { code: 404, message: "Session not found".into() },
+            },
+            PurgeTarget::Conversation(id) => (PurgeScope::Conversation, id, None),
+        };
+        let mut receipt = PurgeReceipt::new(scope, &subject);
+        let affinity_keys = match &tenant {
+            Some(tenant) => {
+                receipt.usage_records_removed = usize::from(self.usage.purge(tenant));
+                self.conversations.purge_owner(tenant)
+            }
+            None => self.conversations.purge(&subject).into_iter().collect(),
+        };
+        receipt.conversations_removed = affinity_keys.len();
+        receipt.cache_entries_removed = affinity_keys.iter().filter(|key| self.affinity.forget(key)).count();
+        self.receipt_signer().sign(&mut receipt);
+
+        self.metrics_store.increment_counter("core_data_purges", 1);
+        Self::audit_purge(&receipt);
+        IpcMessage::PurgeResponse(receipt)
+    }
+
+    /// Place, release or list legal holds for an admin session. The hold
+    /// manager audits every change.
+    pub(super) async fn handle_legal_hold(
+        &self,
+        action: LegalHoldAction,
+        session: Option<&SessionToken>,
+    ) -> IpcMessage {
+        if !self.is_admin(session).await {
+            return IpcMessage::Error { code: 403, message: "Admin session required".into() };
+        }
+        let actor = self.principal(session).await.map(|p| tenant_label(&p));
+        let result = match action {
+            LegalHoldAction::List => Ok(()),
+            LegalHoldAction::Activate { tenant, days, reason } => {
+                self.legal_hold.activate(&tenant, days, &reason, actor).map(drop)
+            }
+            LegalHoldAction::Release { tenant } => self.legal_hold.release(&tenant, actor).map(drop),
+        };
+        match result {
+            Ok(()) => IpcMessage::LegalHoldResponse { holds: self.legal_hold.list() },
+            Err(e @ (LegalHoldError::NotConfigured | LegalHoldError::Io(_) | LegalHoldError::Encryption(_))) => {
+                IpcMessage::Error { code: 500, message: e.to_string() }
+            }
+            Err(e @ LegalHoldError::NotHeld(_)) => IpcMessage::Error { code: 404, message: e.to_string() },
+            Err(e) => IpcMessage::Error { code: 400, message: e.to_string() },
+        }
+    }
+
+    pub(super) fn receipt_signer(&self) -> &ReceiptSigner {
+        self.receipts.get_or_init(|| match self.config.purge_key_path.as_deref().map(ReceiptSigner::load_or_create) {
+            Some(Ok(signer)) => signer,
+            Some(Err(e)) => {
+                tracing::warn!(error = %e, "Purge receipt key unavailable; using a per-process key");
+                ReceiptSigner::ephemeral()
+            }
+            None => ReceiptSigner::ephemeral(),
+        })
+    }
+
+    /// Flip a runtime feature flag for an admin session. Every change is
+    /// audited; setting a flag to its current state is not a change.
+    pub(super) async fn handle_feature_toggle(
+        &self,
+        name: &str,
+        enabled: bool,
+        session: Option<&SessionToken>,
+    ) -> IpcMessage {
+        if !self.is_admin(session).await {
+            return IpcMessage::Error { code: 403, message: "Admin session required".into() };
+        }
+        let (feature, previous) = match self.features.set(name, enabled) {
+            Ok(changed) => changed,
+            Err(e) => return IpcMessage::Error { code: 400, message: e.to_string() },
+        };
+        if previous != enabled {
+            tracing::warn!(feature = feature.as_str(), enabled, "Feature flag changed over IPC");
+            metrics::counter!("core_feature_toggles_total", "feature" => feature.as_str()).increment(1);
+            let actor = session.map(|s| tenant_label(s.as_str()));
+            Self::audit_feature_toggle(feature, previous, enabled, actor);
+        }
+        IpcMessage::FeaturesResponse { features: self.features.list() }
+    }
+}
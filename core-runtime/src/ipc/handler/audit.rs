@@ -0,0 +1,125 @@
+//! Audit events the handler emits. Events name resources and counts, never
+//! prompt or output text.
+
+use crate::ipc::protocol::RequestId;
+use crate::engine::context_docs::{AssembledContext, DocumentUse};
+use crate::features::Feature;
+use crate::security::audit::{audit_logger, AuditCategory, AuditEvent, AuditSeverity};
+use crate::security::{PiiBlock, PurgeReceipt};
+
+use super::IpcHandler;
+
+impl IpcHandler {
+    pub(super) fn audit_context(request_id: RequestId, assembled: &AssembledContext) {
+        let (Some(logger), Ok(runtime)) = (audit_logger(), tokio::runtime::Handle::try_current()) else {
+            return;
+        };
+        let dropped: Vec<&str> = assembled
+            .documents
+            .iter()
+            .filter(|d| d.usage == DocumentUse::Dropped)
+            .map(|d| d.id.as_str())
+            .collect();
+        let Ok(event) = AuditEvent::builder()
+            .severity(AuditSeverity::Info)
+            .category(AuditCategory::DataAccess)
+            .event_type("context_documents")
+            .message(format!("{} context documents used", assembled.used_ids().len()))
+            .source("ipc_handler")
+            .correlation_id(request_id.to_string())
+            .metadata("documents", assembled.used_ids().join(","))
+            .metadata("dropped", dropped.join(","))
+            .build()
+        else {
+            return;
+        };
+        runtime.spawn(async move { logger.log(event).await });
+    }
+
+    /// Count a PII block per type and audit it; the event names the types,
+    /// never the matched text.
+    pub(super) fn record_pii_block(&self, block: &PiiBlock, request_id: RequestId, model_id: &str, streaming: bool) {
+        let mut keys: Vec<&'static str> = block.types.iter().map(|t| t.as_str()).collect();
+        if keys.is_empty() {
+            keys.push("unscreened");
+        }
+        for key in keys {
+            self.metrics_store.increment_counter(&format!("core_pii_blocked_{}", key), 1);
+            metrics::counter!("core_pii_blocked_total", "type" => key).increment(1);
+        }
+        tracing::warn!(
+            request_id = %request_id,
+            model = model_id,
+            pii_types = %block.type_list(),
+            matches = block.matches,
+            "Response blocked by PII policy"
+        );
+
+        let (Some(logger), Ok(runtime)) = (audit_logger(), tokio::runtime::Handle::try_current()) else {
+            return;
+        };
+        let Ok(event) = AuditEvent::builder()
+            .severity(AuditSeverity::Warning)
+            .category(AuditCategory::DataAccess)
+            .event_type("pii_blocked")
+            .message(block.to_string())
+            .source("ipc_handler")
+            .correlation_id(request_id.to_string())
+            .metadata("model", model_id)
+            .metadata("pii_types", block.type_list())
+            .metadata("matches", block.matches.to_string())
+            .metadata("confidence", format!("{:.2}", block.confidence))
+            .metadata("streaming", streaming.to_string())
+            .build()
+        else {
+            return;
+        };
+        runtime.spawn(async move { logger.log(event).await });
+    }
+
+    pub(super) fn audit_feature_toggle(feature: Feature, previous: bool, enabled: bool, actor: Option<String>) {
+        let (Some(logger), Ok(runtime)) = (audit_logger(), tokio::runtime::Handle::try_current()) else {
+            return;
+        };
+        let mut builder = AuditEvent::builder()
+            .severity(AuditSeverity::Warning)
+            .category(AuditCategory::Configuration)
+            .event_type("feature_toggle")
+            .message(format!("Feature {} {}", feature.as_str(), if enabled { "enabled" } else { "disabled" }))
+            .source("ipc_handler")
+            .metadata("feature", feature.as_str())
+            .metadata("previous", previous.to_string())
+            .metadata("enabled", enabled.to_string());
+        if let Some(actor) = actor {
+            builder = builder.metadata("actor", actor);
+        }
+        let Ok(event) = builder.build() else {
+            return;
+        };
+        runtime.spawn(async move { logger.log(event).await });
+    }
+
+    /// Record a purge by receipt; the event carries the subject hash only.
+    pub(super) fn audit_purge(receipt: &PurgeReceipt) {
+        let (Some(logger), Ok(runtime)) = (audit_logger(), tokio::runtime::Handle::try_current()) else {
+            return;
+        };
+        let Ok(event) = AuditEvent::builder()
+            .severity(AuditSeverity::Critical)
+            .category(AuditCategory::DataAccess)
+            .event_type("data_purge")
+            .message(format!(
+                "Purged {} conversations, {} usage records, {} cache entries",
+                receipt.conversations_removed, receipt.usage_records_removed, receipt.cache_entries_removed
+            ))
+            .source("ipc_handler")
+            .correlation_id(receipt.receipt_id.clone())
+            .metadata("scope", format!("{:?}", receipt.scope).to_lowercase())
+            .metadata("subject_hash", receipt.subject_hash.clone())
+            .build()
+        else {
+            return;
+        };
+        runtime.spawn(async move { logger.log(event).await });
+    }
+}
@@ -0,0 +1,90 @@
+//! Handler configuration.
+
+use std::path::PathBuf;
+
+use crate::ipc::affinity::AffinityConfig;
+use crate::ipc::deprecation::DeprecationConfig;
+use crate::ipc::probe::ProbeConfig;
+use crate::ipc::limits::RequestLimits;
+use crate::conversations::{ConversationConfig, PrefetchConfig};
+use crate::engine::context_docs::ContextBudget;
+use crate::engine::PresetCatalog;
+use crate::scheduler::{CircuitConfig, GpuShareConfig, HedgeConfig, QuotaConfig, ThermalConfig, ThreadPoolConfig};
+use crate::security::{LegalHoldConfig, PiiBlockConfig, ShadowConfig};
+use crate::telemetry::{DpConfig, ProfilerConfig, SloConfig};
+
+/// Configuration for IPC handler.
+#[derive(Debug, Clone)]
+pub struct IpcHandlerConfig {
+    pub require_auth: bool,
+    pub affinity: AffinityConfig,
+    pub conversations: ConversationConfig,
+    pub slo: SloConfig,
+    pub profiler: ProfilerConfig,
+    /// Candidate sanitizer/policy evaluated in shadow mode.
+    pub policy_shadow: Option<ShadowConfig>,
+    /// PII types that fail a response outright instead of passing through.
+    pub pii_block: Option<PiiBlockConfig>,
+    pub circuit: CircuitConfig,
+    /// Prompt template library; `None` rejects `template_id` requests.
+    pub templates_dir: Option<PathBuf>,
+    /// Named parameter presets selectable by `preset`.
+    pub presets: PresetCatalog,
+    /// Token budgets for RAG context documents.
+    pub context_budget: ContextBudget,
+    /// Laplace noise on exported per-tenant usage; `None` exports exact counts.
+    pub usage_privacy: Option<DpConfig>,
+    /// Installation key for signing purge receipts; `None` uses a
+    /// per-process key, so receipts cannot be verified after restart.
+    pub purge_key_path: Option<PathBuf>,
+    /// Tenant rate limits and idempotency keys, optionally fleet-wide.
+    pub quota: QuotaConfig,
+    /// Paired model replicas raced when the primary is slow.
+    pub hedge: HedgeConfig,
+    /// Background prefill of conversation follow-up prefixes.
+    pub prefetch: PrefetchConfig,
+    /// Per-field prompt, metadata and output size limits.
+    pub limits: RequestLimits,
+    /// Rate limit and caching for the liveness probe fast path.
+    pub probe: ProbeConfig,
+    /// Worker slots reported in scheduler occupancy.
+    pub workers: usize,
+    /// Turn-taking between models sharing a GPU.
+    pub gpu_share: GpuShareConfig,
+    /// Response to GPUs running hot or at their power limit.
+    pub thermal: ThermalConfig,
+    /// Protocol features slated for removal, and their sunset dates.
+    pub deprecations: DeprecationConfig,
+    /// Hashed inference audit sampling and per-tenant legal hold capture.
+    pub legal_hold: LegalHoldConfig,
+}
+
+impl Default for IpcHandlerConfig {
+    fn default() -> Self {
+        Self {
+            require_auth: true,
+            affinity: AffinityConfig::default(),
+            conversations: ConversationConfig::default(),
+            slo: SloConfig::default(),
+            profiler: ProfilerConfig::default(),
+            policy_shadow: None,
+            pii_block: None,
+            circuit: CircuitConfig::default(),
+            templates_dir: None,
+            presets: PresetCatalog::default(),
+            context_budget: ContextBudget::default(),
+            usage_privacy: None,
+            purge_key_path: None,
+            quota: QuotaConfig::default(),
+            hedge: HedgeConfig::default(),
+            prefetch: PrefetchConfig::default(),
+            limits: RequestLimits::default(),
+            probe: ProbeConfig::default(),
+            workers: ThreadPoolConfig::for_inference().worker_threads.get(),
+            gpu_share: GpuShareConfig::default(),
+            thermal: ThermalConfig::default(),
+            deprecations: DeprecationConfig::default(),
+            legal_hold: LegalHoldConfig::default(),
+        }
+    }
+}
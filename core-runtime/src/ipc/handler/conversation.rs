@@ -0,0 +1,206 @@
+//! Conversation messages: turns, tool results and follow-up prefetch.
+
+use std::sync::Arc;
+
+use crate::ipc::auth::SessionToken;
+use crate::ipc::protocol::{
+    ConversationTurnRequest, InferenceRequest, InferenceResponse, IpcMessage, RequestId, ToolResultRequest,
+};
+use crate::conversations::{ConversationError, PrefetchLoad, PreparedTurn};
+use crate::engine::tools;
+use crate::engine::{InferenceParams, ToolDefinition};
+use crate::features::Feature;
+use crate::scheduler::Priority;
+use crate::telemetry::tenant_label;
+
+use super::IpcHandler;
+
+/// What a turn appends to the transcript before generating.
+enum TurnInput {
+    Message(String),
+    ToolResult { tool_call_id: String, content: String },
+}
+
+/// Request fields shared by conversation turns and tool results.
+struct PendingTurn {
+    conversation_id: String,
+    request_id: RequestId,
+    input: TurnInput,
+    parameters: InferenceParams,
+    preset: Option<String>,
+    tools: Vec<ToolDefinition>,
+}
+
+impl From<ConversationTurnRequest> for PendingTurn {
+    fn from(turn: ConversationTurnRequest) -> Self {
+        Self {
+            conversation_id: turn.conversation_id,
+            request_id: turn.request_id,
+            input: TurnInput::Message(turn.message),
+            parameters: turn.parameters,
+            preset: turn.preset,
+            tools: turn.tools,
+        }
+    }
+}
+
+impl From<ToolResultRequest> for PendingTurn {
+    fn from(result: ToolResultRequest) -> Self {
+        Self {
+            conversation_id: result.conversation_id,
+            request_id: result.request_id,
+            input: TurnInput::ToolResult { tool_call_id: result.tool_call_id, content: result.content },
+            parameters: result.parameters,
+            preset: result.preset,
+            tools: result.tools,
+        }
+    }
+}
+
+impl IpcHandler {
+    /// Conversation messages. Listing and eviction are admin operations.
+    pub(super) async fn handle_conversation(
+        &self,
+        message: IpcMessage,
+        session: Option<&SessionToken>,
+    ) -> IpcMessage {
+        match message {
+            IpcMessage::ConversationCreate { model_id } => {
+                match self.conversations.create_owned(&model_id, self.conversation_owner(session).await) {
+                    Ok(conversation_id) => IpcMessage::ConversationCreated { conversation_id },
+                    Err(e) => IpcMessage::Error { code: 429, message: e.to_string() },
+                }
+            }
+            IpcMessage::ConversationTurn(turn) => {
+                IpcMessage::InferenceResponse(self.handle_turn(turn.into(), session).await)
+            }
+            IpcMessage::ToolResult(result) => {
+                IpcMessage::InferenceResponse(self.handle_turn(result.into(), session).await)
+            }
+            _ if !self.is_admin(session).await => IpcMessage::Error {
+                code: 403,
+                message: "Admin session required".into(),
+            },
+            IpcMessage::ConversationList => IpcMessage::ConversationListResponse {
+                conversations: self.conversations.list(),
+            },
+            IpcMessage::ConversationEvict { conversation_id } => {
+                let evicted = self.conversations.evict(&conversation_id);
+                IpcMessage::ConversationEvicted { conversation_id, evicted }
+            }
+            _ => IpcMessage::Error { code: 400, message: "Unexpected message type".into() },
+        }
+    }
+
+    /// Owner label for conversations: the same tenant label usage is
+    /// recorded under, so a purge clears both together.
+    async fn conversation_owner(&self, session: Option<&SessionToken>) -> Option<String> {
+        self.principal(session).await.map(|p| tenant_label(&p))
+    }
+
+    /// Append the turn's input to a conversation the caller owns.
+    async fn begin_turn(
+        &self,
+        turn: &PendingTurn,
+        session: Option<&SessionToken>,
+    ) -> Result<PreparedTurn, ConversationError> {
+        let id = &turn.conversation_id;
+        self.conversations.check_owner(id, self.conversation_owner(session).await.as_deref())?;
+        match &turn.input {
+            TurnInput::Message(message) => self.conversations.begin_turn(id, message),
+            TurnInput::ToolResult { tool_call_id, content } => {
+                self.conversations.begin_tool_result(id, tool_call_id, content)
+            }
+        }
+    }
+
+    /// Run inference for a conversation turn.
+    async fn handle_turn(&self, turn: PendingTurn, session: Option<&SessionToken>) -> InferenceResponse {
+        let prepared = match self.begin_turn(&turn, session).await {
+            Ok(prepared) => prepared,
+            Err(e) => return InferenceResponse::error(turn.request_id, e.to_string()),
+        };
+        // Taken before the next await, so cancellation rolls the turn back
+        let guard = self.conversations.guard_turn(&turn.conversation_id);
+        let priority = self.effective_priority(Priority::Normal, session).await;
+        let request = InferenceRequest {
+            request_id: turn.request_id,
+            model_id: prepared.model_id,
+            prompt: prepared.prompt,
+            parameters: turn.parameters,
+            preset: turn.preset,
+            priority,
+            session_affinity_key: Some(prepared.affinity_key),
+            tools: turn.tools,
+            images: Vec::new(),
+            template_id: None,
+            variables: Default::default(),
+            context_documents: Vec::new(),
+            cite_sources: false,
+            privacy: Default::default(),
+            idempotency_key: None,
+        };
+        let principal = self.principal(session).await;
+        let response = self.handle_inference(request, priority, principal.as_deref()).await;
+        if response.error.is_none() {
+            guard.finish(&response.output);
+            self.prefetch_follow_up(&turn.conversation_id).await;
+        }
+        response
+    }
+
+    /// Prefill the conversation's next-turn prefix in the background when
+    /// there is spare capacity, so the follow-up only prefills its message.
+    async fn prefetch_follow_up(&self, conversation_id: &str) {
+        if !self.features.enabled(Feature::ConversationPrefetch) {
+            return;
+        }
+        let Some((model_id, prefix)) = self.conversations.next_turn_prefix(conversation_id) else {
+            return;
+        };
+        let load = PrefetchLoad {
+            queue_depth: self.queue.len().await,
+            active_requests: self.shutdown.in_flight_count() as usize,
+            level: self.overload.level(),
+            cache_bytes: self.inference_engine.prefix_cache_bytes(&model_id).await,
+        };
+        let guard = match self.prefetch.try_begin(load) {
+            Ok(guard) => guard,
+            Err(reason) => {
+                let name = format!("core_prefetch_skipped_{}", reason.as_str());
+                self.metrics_store.increment_counter(&name, 1);
+                return;
+            }
+        };
+        self.metrics_store.increment_counter("core_prefetch_started", 1);
+        let engine = Arc::clone(&self.inference_engine);
+        let metrics = Arc::clone(&self.metrics_store);
+        tokio::spawn(async move {
+            let _guard = guard;
+            match engine.prefill_prefix(&model_id, &prefix).await {
+                Ok(true) => metrics.increment_counter("core_prefetch_kept", 1),
+                Ok(false) => {}
+                Err(e) => {
+                    tracing::debug!(model = %model_id, error = %e, "Follow-up prefetch failed");
+                    metrics.increment_counter("core_prefetch_failed", 1);
+                }
+            }
+        });
+    }
+
+    /// Attach a schema-valid tool call, if the model produced one.
+    pub(super) fn extract_tool_calls(
+        request: &InferenceRequest,
+        response: InferenceResponse,
+    ) -> InferenceResponse {
+        let call_id = format!("call-{}", request.request_id);
+        match tools::parse_tool_call(&response.output, &request.tools, &call_id) {
+            Ok(Some(call)) => response.with_tool_calls(vec![call]),
+            Ok(None) => response,
+            Err(e) => {
+                tracing::debug!(error = %e, "Discarding invalid tool call");
+                response
+            }
+        }
+    }
+}
@@ -0,0 +1,128 @@
+//! Message dispatch: decode a frame, route it to its handler, encode the reply.
+
+use crate::ipc::auth::SessionToken;
+use crate::ipc::protocol::{decode_message, encode_message, IpcMessage};
+use crate::telemetry;
+
+use super::{HandlerError, IpcHandler, READ_ONLY_MESSAGE};
+
+impl IpcHandler {
+    /// Process incoming message bytes and return response bytes.
+    pub async fn process(
+        &self,
+        bytes: &[u8],
+        session: Option<&SessionToken>,
+    ) -> Result<(Vec<u8>, Option<SessionToken>), HandlerError> {
+        let message = {
+            let _tag = telemetry::tag_scope("ipc_codec");
+            decode_message(bytes)?
+        };
+        let (response, new_session) = self.handle_message(message, session).await?;
+        let response_bytes = {
+            let _tag = telemetry::tag_scope("ipc_codec");
+            encode_message(&response)?
+        };
+        Ok((response_bytes, new_session))
+    }
+
+    async fn handle_message(
+        &self,
+        mut message: IpcMessage,
+        session: Option<&SessionToken>,
+    ) -> Result<(IpcMessage, Option<SessionToken>), HandlerError> {
+        message.assign_request_id();
+        if let IpcMessage::Handshake { token, protocol_version } = message {
+            return self.handle_handshake(&token, protocol_version).await;
+        }
+        if self.is_observer(session).await {
+            self.metrics_store.increment_counter("core_observer_requests", 1);
+            if !message.is_read_only() {
+                self.metrics_store.increment_counter("core_observer_denied", 1);
+                return Ok((IpcMessage::Error { code: 403, message: READ_ONLY_MESSAGE.into() }, None));
+            }
+        }
+
+        let response = if message.requires_auth() {
+            self.require_auth(session).await?;
+            self.handle_authenticated(message, session).await
+        } else {
+            self.handle_open(message).await
+        };
+        Ok((response, None))
+    }
+
+    /// NO AUTH REQUIRED: health, metrics, model listing and routing hints
+    /// (orchestrator pattern).
+    async fn handle_open(&self, message: IpcMessage) -> IpcMessage {
+        match message {
+            IpcMessage::HealthCheck { check_type } => {
+                IpcMessage::HealthResponse(self.health_handler.handle(check_type).await)
+            }
+            // Normally answered by the server loop; reached via `process`
+            IpcMessage::Probe => self.probe.answer(),
+            IpcMessage::MetricsRequest => IpcMessage::MetricsResponse(self.metrics_store.snapshot()),
+            IpcMessage::SloStatusRequest => IpcMessage::SloStatusResponse { objectives: self.slo.evaluate() },
+            IpcMessage::ModelsRequest => IpcMessage::ModelsResponse(self.handle_models_request().await),
+            IpcMessage::WarmupRequest(request) => {
+                IpcMessage::WarmupResponse(self.handle_warmup(request.model_id, request.tokens).await)
+            }
+            IpcMessage::AffinityQuery { keys } => {
+                let resident = keys.iter().map(|k| self.affinity.is_resident(k)).collect();
+                IpcMessage::AffinityResponse { resident }
+            }
+            _ => unexpected_message(),
+        }
+    }
+
+    /// Requests from an authenticated session; admin checks happen per message.
+    async fn handle_authenticated(&self, message: IpcMessage, session: Option<&SessionToken>) -> IpcMessage {
+        match message {
+            IpcMessage::InferenceRequest(request) => {
+                IpcMessage::InferenceResponse(self.handle_inference_request(request, session).await)
+            }
+            IpcMessage::CancelRequest { request_id } => {
+                // Queue entries are numbered; only integer IDs can name one
+                let cancelled = match u64::try_from(request_id.0) {
+                    Ok(id) => self.queue.cancel(id).await,
+                    Err(_) => false,
+                };
+                IpcMessage::CancelResponse { request_id, cancelled }
+            }
+            message @ (IpcMessage::ConversationCreate { .. }
+            | IpcMessage::ConversationTurn(_)
+            | IpcMessage::ToolResult(_)
+            | IpcMessage::ConversationList
+            | IpcMessage::ConversationEvict { .. }) => self.handle_conversation(message, session).await,
+            IpcMessage::RerankRequest(request) => IpcMessage::RerankResponse(self.handle_rerank(request).await),
+            // Spans carry request attributes
+            IpcMessage::SpansRequest { max_count } => IpcMessage::SpansResponse { spans: self.spans.drain(max_count) },
+            IpcMessage::FeaturesRequest => IpcMessage::FeaturesResponse { features: self.features.list() },
+            message => self.handle_admin(message, session).await,
+        }
+    }
+
+    /// Operations that reveal internals or change runtime state; each
+    /// handler checks for an admin session itself.
+    async fn handle_admin(&self, message: IpcMessage, session: Option<&SessionToken>) -> IpcMessage {
+        match message {
+            IpcMessage::ProfileRequest { seconds } => self.handle_profile(seconds, session).await,
+            IpcMessage::HeapProfileRequest { top } => self.handle_heap_profile(top, session).await,
+            IpcMessage::UsageExportRequest => self.handle_usage_export(session).await,
+            IpcMessage::SnapshotRequest(request) => self.handle_snapshot(request, session).await,
+            IpcMessage::ModelAdminRequest(request) => self.handle_model_admin(request, session).await,
+            IpcMessage::DrainRequest => self.handle_drain(session).await,
+            IpcMessage::AuditTailRequest { limit } => self.handle_audit_tail(limit, session).await,
+            IpcMessage::PurgeRequest { target } => self.handle_purge(target, session).await,
+            IpcMessage::LegalHoldRequest { action } => self.handle_legal_hold(action, session).await,
+            IpcMessage::FeatureToggle { name, enabled } => self.handle_feature_toggle(&name, enabled, session).await,
+            _ => unexpected_message(),
+        }
+    }
+}
+
+fn unexpected_message() -> IpcMessage {
+    IpcMessage::Error {
+        code: 400,
+        message: "Unexpected message type".into(),
+    }
+}
@@ -0,0 +1,27 @@
+//! Errors surfaced while handling a message.
+
+use thiserror::Error;
+
+use crate::ipc::auth::AuthError;
+use crate::ipc::protocol::ProtocolError;
+
+#[derive(Error, Debug)]
+pub enum HandlerError {
+    #[error("Protocol error: {0}")]
+    Protocol(#[from] ProtocolError),
+
+    #[error("Authentication error: {0}")]
+    Auth(#[from] AuthError),
+
+    #[error("Not authenticated")]
+    NotAuthenticated,
+
+    #[error("Queue error: {0}")]
+    QueueFull(String),
+
+    #[error("Server is shutting down")]
+    ShuttingDown,
+
+    #[error("Stream send error: {0}")]
+    StreamSend(String),
+}
@@ -0,0 +1,199 @@
+//! Unary inference: admission, routing, execution and the response.
+
+use tracing::Instrument;
+
+use crate::ipc::auth::SessionToken;
+use crate::ipc::protocol::{InferenceRequest, InferenceResponse, ResponseErrorCode};
+use crate::engine::context_docs::AssembledContext;
+use crate::engine::InferenceParams;
+use crate::scheduler::{AdmissionGuard, CircuitPermit, Priority, QueueError};
+use crate::telemetry::span_export::now_unix_ns;
+use crate::telemetry::{self, RequestSpan};
+
+use super::IpcHandler;
+
+impl IpcHandler {
+    /// Run an authenticated request under its span, with legal-hold capture.
+    pub(super) async fn handle_inference_request(
+        &self,
+        request: InferenceRequest,
+        session: Option<&SessionToken>,
+    ) -> InferenceResponse {
+        let priority = self.effective_priority(request.priority, session).await;
+        let principal = self.principal(session).await;
+        let privacy = request.privacy;
+        let span = RequestSpan::new(&request.request_id.to_string(), &request.model_id);
+        let audit = self
+            .legal_hold
+            .plan(principal.as_deref(), privacy.no_log)
+            .map(|plan| (plan, request.model_id.clone(), request.prompt.clone()));
+        let started_ns = now_unix_ns();
+        let response = self
+            .handle_inference(request, priority, principal.as_deref())
+            .instrument(span)
+            .await
+            .with_privacy(privacy);
+        self.export_span(&response, started_ns);
+        if let Some((plan, model_id, prompt)) = audit {
+            let request_id = response.request_id.to_string();
+            self.legal_hold.record(plan, &request_id, &model_id, &prompt, &response.output);
+        }
+        response
+    }
+
+    pub(super) async fn handle_inference(
+        &self,
+        request: InferenceRequest,
+        priority: Priority,
+        tenant: Option<&str>,
+    ) -> InferenceResponse {
+        // Check shutdown state before accepting new request
+        let _guard = match self.shutdown.track() {
+            Some(g) => g,
+            None => {
+                return InferenceResponse::error(
+                    request.request_id,
+                    "Server is shutting down".into(),
+                );
+            }
+        };
+
+        let (request, context) = match self.prepare_inference(request) {
+            Ok(prepared) => prepared,
+            Err(response) => return *response,
+        };
+        // Text inference screens nothing today; the shadow only observes
+        if let Some(shadow) = self.shadow.as_ref().filter(|_| Self::shadow_allowed(request.privacy)) {
+            shadow.compare_prompt(&request.prompt, false, request.request_id);
+        }
+        let (_admission, permit) = match self.admit(&request, priority, tenant).await {
+            Ok(admitted) => admitted,
+            Err(response) => return response,
+        };
+        self.generate(&request, permit, context, priority, tenant).await
+        // guard dropped here, decrementing in-flight count
+    }
+
+    /// Queue and run an admitted request, then build its response.
+    async fn generate(
+        &self,
+        request: &InferenceRequest,
+        permit: CircuitPermit<'_>,
+        context: Option<AssembledContext>,
+        priority: Priority,
+        tenant: Option<&str>,
+    ) -> InferenceResponse {
+        let model_id = permit.model().to_string();
+        let inputs = self.generation_inputs(request);
+        let (prompt, params) = (&inputs.prompt, &inputs.params);
+        if let Err(response) = self.enqueue(request, &model_id, prompt, params, priority).await {
+            return response;
+        }
+
+        // Run inference using model_id to look up the model
+        let start = std::time::Instant::now();
+        let _running = self.occupancy.begin(&request.request_id.to_string(), &model_id);
+        let _thermal_slot = self.thermal.admit(&model_id).await;
+        let _gpu_turn = self.gpu.acquire(&model_id).await;
+        let (result, model_id) = self.run_hedged(permit, prompt, params).await;
+        let mut result = match result {
+            Ok(result) => result,
+            Err(e) => return self.failure_response(request.request_id, &model_id, e),
+        };
+        let cut = self.output_cut(&mut result.output, result.tokens_generated, inputs.token_cap);
+        let tenant = tenant.filter(|_| !request.privacy.no_train_export);
+        self.record_success(&model_id, start.elapsed().as_millis() as u64, &result, tenant).await;
+        if let Some(shadow) = self.shadow.as_ref().filter(|_| Self::shadow_allowed(request.privacy)) {
+            shadow.compare_output(&result.output, 0, request.request_id);
+        }
+        if let Some(response) = self.pii_blocked_response(request.request_id, &model_id, &result.output) {
+            return response;
+        }
+        let response = self.success_response(request, result, model_id, prompt, cut, context).await;
+        response.with_max_tokens_cap(inputs.overload_cap)
+    }
+
+    /// Admit a request past tenant quotas, the degradation ladder and the
+    /// model's circuit, which may route it down the fallback chain.
+    async fn admit(
+        &self,
+        request: &InferenceRequest,
+        priority: Priority,
+        tenant: Option<&str>,
+    ) -> Result<(AdmissionGuard, CircuitPermit<'_>), InferenceResponse> {
+        // Tenant rate limits and idempotency keys hold across replicas
+        if let Err(e) = self.quota.admit(tenant, request.idempotency_key.as_deref()).await {
+            self.record_rejection(priority, e.reason());
+            return Err(InferenceResponse::error(request.request_id, e.to_string()));
+        }
+
+        // Step the degradation ladder before admitting new work
+        let level = self.overload.evaluate(self.queue.len().await);
+        self.metrics_store.set_gauge("core_overload_level", level as u8 as f64);
+        let admission = self.overload.try_admit(priority, tenant).map_err(|e| {
+            self.record_rejection(priority, e.reason());
+            InferenceResponse::error(request.request_id, e.to_string())
+        })?;
+        // Fail fast, or move down the fallback chain, while the model is
+        // unavailable or its circuit is open
+        let permit = self
+            .route_model(&request.model_id)
+            .await
+            .map_err(|e| InferenceResponse::error(request.request_id, e.to_string()))?;
+        Ok((admission, permit))
+    }
+
+    /// Track the request in the queue for metrics.
+    async fn enqueue(
+        &self,
+        request: &InferenceRequest,
+        model_id: &str,
+        prompt: &str,
+        params: &InferenceParams,
+        priority: Priority,
+    ) -> Result<(), InferenceResponse> {
+        let enqueue_result = self
+            .queue
+            .enqueue(
+                model_id.to_string(),
+                prompt.to_string(),
+                params.clone(),
+                priority,
+            )
+            .await;
+
+        if let Err(e) = enqueue_result {
+            // The queue records its own facade metric
+            self.metrics_store
+                .increment_counter(&format!("core_queue_rejections_{}", priority.as_str()), 1);
+            if let QueueError::ModelDeactivated(_) = e {
+                return Err(InferenceResponse::error_with_code(
+                    request.request_id,
+                    ResponseErrorCode::ModelDeactivated,
+                    e.to_string(),
+                ));
+            }
+            return Err(InferenceResponse::error(request.request_id, e.to_string()));
+        }
+        self.record_priority_depths().await;
+        Ok(())
+    }
+
+    /// Mirror per-priority queue depth into the IPC metrics store.
+    async fn record_priority_depths(&self) {
+        let depths = self.queue.depth_by_priority().await;
+        for priority in Priority::ALL {
+            let name = format!("core_queue_depth_{}", priority.as_str());
+            self.metrics_store.set_gauge(&name, depths[priority as usize] as f64);
+        }
+        let oldest_ms = self.queue.oldest_age().await.map_or(0.0, |age| age.as_secs_f64() * 1000.0);
+        self.metrics_store.set_gauge("core_queue_oldest_age_ms", oldest_ms);
+    }
+
+    /// Count a request refused before it reached the queue.
+    pub(super) fn record_rejection(&self, priority: Priority, reason: &str) {
+        telemetry::record_queue_rejection(priority.as_str(), reason);
+        self.metrics_store
+            .increment_counter(&format!("core_queue_rejections_{}", priority.as_str()), 1);
+    }
+}
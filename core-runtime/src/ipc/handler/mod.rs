@@ -0,0 +1,231 @@
+//! Request/response handling for IPC connections.
+//!
+//! [`IpcHandler`] owns the per-request services; each group of messages is
+//! handled in its own module.
+
+mod admin;
+mod audit;
+mod config;
+mod conversation;
+mod dispatch;
+mod error;
+mod inference;
+mod models;
+mod prepare;
+#[cfg(feature = "gguf")]
+mod relay;
+mod response;
+mod routing;
+mod session;
+mod streaming;
+
+pub use config::IpcHandlerConfig;
+pub use error::HandlerError;
+
+use std::sync::{Arc, OnceLock};
+
+use super::affinity::AffinityTracker;
+use super::auth::SessionAuth;
+use super::deprecation::DeprecationTracker;
+use super::health_handler::HealthHandler;
+use super::probe::ProbeResponder;
+use super::model_admin::ModelAdminHandler;
+use super::snapshot_handler::SnapshotHandler;
+use super::protocol::IpcMessage;
+use crate::conversations::{ConversationStore, PrefetchGate};
+use crate::engine::InferenceEngine;
+use crate::features::{Feature, FeatureFlags};
+use crate::health::HealthChecker;
+use crate::maintenance::MaintenanceScheduler;
+use crate::models::ModelRegistry;
+use crate::scheduler::{
+    CircuitBreaker, GpuScheduler, Hedger, Occupancy, OverloadController, QuotaStore, RequestQueue, ThermalGovernor,
+};
+use crate::security::{LegalHoldManager, PiiBlockPolicy, PurgeReceipt, ReceiptSigner, ShadowPolicy};
+use crate::shutdown::ShutdownCoordinator;
+use crate::templates::TemplateStore;
+use crate::telemetry::{MetricsStore, Profiler, SloMonitor, SpanCollector, UsageLedger};
+
+const READ_ONLY_MESSAGE: &str = "Observer sessions are read-only";
+
+/// Trait for sending streaming responses over IPC.
+#[async_trait::async_trait]
+pub trait StreamSender: Send + Sync {
+    /// Send a message to the stream. Returns error if stream is closed.
+    async fn send(&self, message: IpcMessage) -> Result<(), HandlerError>;
+}
+
+/// Handles IPC message processing with authentication.
+pub struct IpcHandler {
+    /// Session authentication manager (public for FFI access)
+    pub auth: Arc<SessionAuth>,
+    queue: Arc<RequestQueue>,
+    config: IpcHandlerConfig,
+    shutdown: Arc<ShutdownCoordinator>,
+    health_handler: HealthHandler,
+    probe: ProbeResponder,
+    metrics_store: Arc<MetricsStore>,
+    slo: Arc<SloMonitor>,
+    profiler: Profiler,
+    model_registry: Arc<ModelRegistry>,
+    inference_engine: Arc<InferenceEngine>,
+    overload: Arc<OverloadController>,
+    occupancy: Arc<Occupancy>,
+    gpu: Arc<GpuScheduler>,
+    thermal: Arc<ThermalGovernor>,
+    deprecations: DeprecationTracker,
+    legal_hold: LegalHoldManager,
+    snapshots: SnapshotHandler,
+    model_admin: Arc<ModelAdminHandler>,
+    affinity: AffinityTracker,
+    conversations: ConversationStore,
+    shadow: Option<Arc<ShadowPolicy>>,
+    pii_block: Option<PiiBlockPolicy>,
+    circuits: Arc<CircuitBreaker>,
+    templates: Option<TemplateStore>,
+    /// Exact per-tenant usage; only exports are noised.
+    usage: Arc<UsageLedger>,
+    spans: Arc<SpanCollector>,
+    quota: QuotaStore,
+    hedger: Hedger,
+    prefetch: PrefetchGate,
+    features: Arc<FeatureFlags>,
+    /// Loaded on first purge so the key file only exists once used.
+    receipts: OnceLock<ReceiptSigner>,
+}
+
+impl IpcHandler {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        auth: Arc<SessionAuth>,
+        queue: Arc<RequestQueue>,
+        config: IpcHandlerConfig,
+        shutdown: Arc<ShutdownCoordinator>,
+        health: Arc<HealthChecker>,
+        model_registry: Arc<ModelRegistry>,
+        metrics_store: Arc<MetricsStore>,
+        inference_engine: Arc<InferenceEngine>,
+        overload: Arc<OverloadController>,
+        features: Arc<FeatureFlags>,
+        maintenance: Arc<MaintenanceScheduler>,
+        snapshots: SnapshotHandler,
+        model_admin: Arc<ModelAdminHandler>,
+    ) -> Self {
+        let gpu = Arc::new(GpuScheduler::new(config.gpu_share.clone()));
+        let occupancy = Arc::new(Occupancy::new(config.workers).with_gpu(Arc::clone(&gpu)));
+        let probe = ProbeResponder::new(config.probe.clone(), Arc::clone(&health), Arc::clone(&shutdown));
+        let health_handler = HealthHandler::new(
+            health,
+            Arc::clone(&shutdown),
+            Arc::clone(&model_registry),
+            Arc::clone(&queue),
+            Arc::clone(&overload),
+            maintenance,
+            Arc::clone(&occupancy),
+        );
+        let affinity = AffinityTracker::new(config.affinity.clone());
+        let conversations = ConversationStore::new(config.conversations.clone());
+        features.configure(Feature::RequireAuth, config.require_auth, true);
+        features.configure(Feature::SanitizerShadow, true, config.policy_shadow.is_some());
+        features.configure(Feature::RequestHedging, true, !config.hedge.pairs.is_empty());
+        features.configure(Feature::ConversationPrefetch, config.prefetch.enabled, true);
+        let shadow = config.policy_shadow.clone().map(|c| {
+            Arc::new(
+                ShadowPolicy::new(c)
+                    .with_metrics(Arc::clone(&metrics_store))
+                    .with_features(Arc::clone(&features)),
+            )
+        });
+        let pii_block = config.pii_block.clone().filter(|c| !c.types.is_empty()).map(PiiBlockPolicy::new);
+        let slo = Arc::new(SloMonitor::new(config.slo.clone(), Arc::clone(&metrics_store)));
+        let profiler = Profiler::new(config.profiler.clone());
+        let circuits = Arc::new(CircuitBreaker::new(config.circuit.clone()));
+        let templates = config.templates_dir.clone().map(TemplateStore::new);
+        let usage = Arc::new(UsageLedger::new(config.usage_privacy));
+        let quota = QuotaStore::new(config.quota.clone());
+        let hedger = Hedger::new(config.hedge.clone());
+        let prefetch = PrefetchGate::new(config.prefetch.clone());
+        let thermal = Arc::new(ThermalGovernor::new(config.thermal.clone()));
+        let deprecations = DeprecationTracker::new(config.deprecations.clone());
+        let legal_hold = LegalHoldManager::new(config.legal_hold.clone());
+        Self {
+            auth,
+            queue,
+            config,
+            shutdown,
+            health_handler,
+            probe,
+            metrics_store,
+            slo,
+            profiler,
+            model_registry,
+            inference_engine,
+            overload,
+            occupancy,
+            gpu,
+            thermal,
+            deprecations,
+            legal_hold,
+            snapshots,
+            model_admin,
+            affinity,
+            conversations,
+            shadow,
+            pii_block,
+            circuits,
+            templates,
+            usage,
+            spans: Arc::new(SpanCollector::new()),
+            quota,
+            hedger,
+            prefetch,
+            features,
+            receipts: OnceLock::new(),
+        }
+    }
+
+    /// Probe fast path, answered by the server loop before auth.
+    pub fn probe(&self) -> &ProbeResponder {
+        &self.probe
+    }
+
+    /// Shutdown coordinator shared with the server loop.
+    pub fn shutdown(&self) -> &Arc<ShutdownCoordinator> {
+        &self.shutdown
+    }
+
+    /// Completed spans served to `SpansRequest` queries.
+    pub fn spans(&self) -> &Arc<SpanCollector> {
+        &self.spans
+    }
+
+    /// Whether `receipt` was signed by this installation's purge key.
+    pub fn verify_receipt(&self, receipt: &PurgeReceipt) -> bool {
+        self.receipt_signer().verify(receipt)
+    }
+
+    /// Per-tenant usage ledger, shared with retention enforcement.
+    pub fn usage(&self) -> &Arc<UsageLedger> {
+        &self.usage
+    }
+
+    /// SLO monitor; the server runs it in the background.
+    pub fn slo(&self) -> &Arc<SloMonitor> {
+        &self.slo
+    }
+
+    /// Per-model circuit breaker, shared with integrity verification.
+    pub fn circuits(&self) -> &Arc<CircuitBreaker> {
+        &self.circuits
+    }
+
+    /// Thermal governor; the server polls GPU sensors with it.
+    pub fn thermal(&self) -> &Arc<ThermalGovernor> {
+        &self.thermal
+    }
+
+    /// Deprecated protocol use, checked by the server loop on every frame.
+    pub fn deprecations(&self) -> &DeprecationTracker {
+        &self.deprecations
+    }
+}
@@ -0,0 +1,114 @@
+//! Model listing, warmup and reranking.
+
+use crate::ipc::protocol::{ModelInfo, ModelsListResponse, RerankRequest, RerankResponse, WarmupResponse};
+use crate::engine::rank_passages;
+use crate::scheduler::Priority;
+use crate::telemetry;
+
+use super::IpcHandler;
+
+/// Format SystemTime as ISO 8601 string for IPC responses.
+fn format_system_time(time: std::time::SystemTime) -> String {
+    time.duration_since(std::time::UNIX_EPOCH)
+        .map(|d| {
+            let secs = d.as_secs();
+            let datetime = chrono::DateTime::from_timestamp(secs as i64, 0);
+            datetime
+                .map(|dt| dt.format("%Y-%m-%dT%H:%M:%SZ").to_string())
+                .unwrap_or_else(|| format!("{}s", secs))
+        })
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+fn average(total: f64, count: u64) -> f64 {
+    if count > 0 {
+        total / count as f64
+    } else {
+        0.0
+    }
+}
+
+impl IpcHandler {
+    pub(super) async fn handle_warmup(&self, model_id: String, _tokens: usize) -> WarmupResponse {
+        let start = std::time::Instant::now();
+        let result = self
+            .queue
+            .enqueue(
+                model_id.clone(),
+                "warmup".to_string(), // Minimal warmup prompt
+                crate::engine::InferenceParams::default(),
+                Priority::Low,
+            )
+            .await;
+        let elapsed_ms = start.elapsed().as_millis() as u64;
+        match result {
+            Ok(_) => {
+                self.model_registry.mark_warmed(&model_id).await;
+                WarmupResponse::success(model_id, elapsed_ms)
+            }
+            Err(e) => WarmupResponse::error(model_id, e.to_string(), elapsed_ms),
+        }
+    }
+
+    pub(super) async fn handle_models_request(&self) -> ModelsListResponse {
+        let models = self.model_registry.list_models().await;
+        let total_memory_bytes = models.iter().map(|m| m.memory_bytes).sum();
+
+        let model_infos: Vec<ModelInfo> = models
+            .into_iter()
+            .map(|m| {
+                let avg_latency_ms = if m.request_count > 0 {
+                    m.total_latency_ms / m.request_count as f64
+                } else {
+                    0.0
+                };
+                ModelInfo {
+                    handle_id: m.handle_id,
+                    name: m.name,
+                    format: m.format,
+                    size_bytes: m.size_bytes,
+                    memory_bytes: m.memory_bytes,
+                    state: m.state.as_str().to_string(),
+                    request_count: m.request_count,
+                    avg_latency_ms,
+                    avg_ttft_ms: average(m.total_ttft_ms, m.stream_count),
+                    avg_inter_token_ms: average(m.total_inter_token_ms, m.inter_token_count),
+                    recent_p95_latency_ms: m.recent_p95_latency_ms.unwrap_or(0.0),
+                    loaded_at: format_system_time(m.loaded_at),
+                    attestation: m.attestation,
+                }
+            })
+            .collect();
+
+        ModelsListResponse {
+            models: model_infos,
+            total_memory_bytes,
+            circuits: self.circuits.status(),
+        }
+    }
+
+    pub(super) async fn handle_rerank(&self, request: RerankRequest) -> RerankResponse {
+        let Some(_guard) = self.shutdown.track() else {
+            return RerankResponse::error(request.request_id, "Server is shutting down".into());
+        };
+        let start = std::time::Instant::now();
+        let scores = self
+            .inference_engine
+            .rerank(&request.model_id, &request.query, request.passages.clone())
+            .await;
+        match scores {
+            Ok(scores) => {
+                let latency_ms = start.elapsed().as_millis() as u64;
+                telemetry::record_request_success(&request.model_id, latency_ms, 0);
+                self.metrics_store.record_request(true, 0);
+                let results = rank_passages(request.passages, &scores, request.top_n);
+                RerankResponse::success(request.request_id, results)
+            }
+            Err(e) => {
+                telemetry::record_request_failure(&request.model_id, &e.to_string());
+                self.metrics_store.record_request(false, 0);
+                RerankResponse::error(request.request_id, e.to_string())
+            }
+        }
+    }
+}
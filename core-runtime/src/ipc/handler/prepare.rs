@@ -0,0 +1,192 @@
+//! Request preparation and output limits for unary and streaming inference.
+
+use crate::ipc::auth::SessionToken;
+use crate::ipc::limits::LimitError;
+use crate::ipc::protocol::{InferenceRequest, InferenceResponse, PrivacyFlags, StreamChunk};
+use crate::engine::context_docs::{self, AssembledContext, DocumentUse};
+use crate::engine::tools;
+use crate::engine::InferenceParams;
+use crate::telemetry::{ExportableSpan, SpanAttributeValue, SpanStatus};
+use crate::telemetry::span_export::{generate_span_id, now_unix_ns};
+
+use super::IpcHandler;
+
+/// What a request generates with, from [`IpcHandler::generation_inputs`].
+pub(super) struct GenerationInputs {
+    pub(super) prompt: String,
+    pub(super) params: InferenceParams,
+    /// Lowered output token limit; reaching it means the output was cut.
+    pub(super) token_cap: Option<usize>,
+    /// `max_tokens` ceiling the overload ladder applied, reported back.
+    pub(super) overload_cap: Option<usize>,
+}
+
+impl IpcHandler {
+    /// Resolve the request's parameters under its `preset`.
+    pub(super) fn apply_preset(&self, mut request: InferenceRequest) -> Result<InferenceRequest, String> {
+        let Some(name) = request.preset.take() else {
+            return Ok(request);
+        };
+        request.parameters = self.config.presets.resolve(&name, request.parameters).map_err(|e| e.to_string())?;
+        metrics::counter!("core_preset_requests_total", "preset" => name).increment(1);
+        Ok(request)
+    }
+
+    /// Replace `template_id` + `variables` with the rendered prompt.
+    pub(super) fn apply_template(&self, mut request: InferenceRequest) -> Result<InferenceRequest, String> {
+        let Some(reference) = request.template_id.take() else {
+            return Ok(request);
+        };
+        let store = self.templates.as_ref().ok_or("Prompt templates are not enabled")?;
+        match store.render(&reference, &request.variables) {
+            Ok(rendered) => {
+                metrics::counter!("core_template_renders_total", "template" => rendered.id.clone()).increment(1);
+                tracing::debug!(template = %rendered.id, version = rendered.version, "Rendered prompt template");
+                request.prompt = rendered.prompt;
+                request.variables.clear();
+                Ok(request)
+            }
+            Err(e) => {
+                self.metrics_store.increment_counter("core_template_rejections", 1);
+                Err(e.to_string())
+            }
+        }
+    }
+
+    /// Validate and expand a request: presets, templates, context
+    /// documents and size limits.
+    pub(super) fn prepare_inference(
+        &self,
+        request: InferenceRequest,
+    ) -> Result<(InferenceRequest, Option<AssembledContext>), Box<InferenceResponse>> {
+        let request_id = request.request_id;
+        let error = |e: String| Box::new(InferenceResponse::error(request_id, e));
+        if let Err(e) = request.validate() {
+            return Err(error(e.to_string()));
+        }
+        let request = self.apply_preset(request).and_then(|r| self.apply_template(r)).map_err(error)?;
+        let (request, context) = self.apply_context(request).map_err(error)?;
+        if let Err(e) = self.config.limits.check_request(&request) {
+            self.record_limit(&e);
+            return Err(Box::new(InferenceResponse::error_with_code(request_id, e.code(), e.to_string())));
+        }
+        Ok((request, context))
+    }
+
+    /// Validate and expand a streaming request, then admit it past the
+    /// tenant quota.
+    pub(super) async fn prepare_stream(
+        &self,
+        request: InferenceRequest,
+        session: &SessionToken,
+    ) -> Result<InferenceRequest, Box<StreamChunk>> {
+        let request_id = request.request_id;
+        let error = |e: String| Box::new(StreamChunk::error(request_id, e));
+        if let Err(e) = request.validate() {
+            return Err(error(e.to_string()));
+        }
+        let request = self.apply_preset(request).and_then(|r| self.apply_template(r)).map_err(error)?;
+        let (request, _) = self.apply_context(request).map_err(error)?;
+        if let Err(e) = self.config.limits.check_request(&request) {
+            self.record_limit(&e);
+            return Err(Box::new(StreamChunk::error_with_code(request_id, e.code(), e.to_string())));
+        }
+        if let Some(shadow) = self.shadow.as_ref().filter(|_| Self::shadow_allowed(request.privacy)) {
+            shadow.compare_prompt(&request.prompt, false, request_id);
+        }
+        let principal = self.principal(Some(session)).await;
+        if let Err(e) = self.quota.admit(principal.as_deref(), request.idempotency_key.as_deref()).await {
+            self.record_rejection(request.priority, e.reason());
+            return Err(error(e.to_string()));
+        }
+        Ok(request)
+    }
+
+    /// The prompt and parameters to generate with, after overload caps and
+    /// the token limit.
+    pub(super) fn generation_inputs(&self, request: &InferenceRequest) -> GenerationInputs {
+        let mut params = request.parameters.clone();
+        let overload_cap = self.overload.apply_param_caps(&mut params);
+        let token_cap = self.config.limits.cap_max_tokens(&mut params.max_tokens).then_some(params.max_tokens);
+        let prompt = if request.tools.is_empty() {
+            request.prompt.clone()
+        } else {
+            tools::render_tool_prompt(&request.tools, &request.prompt)
+        };
+        GenerationInputs { prompt, params, token_cap, overload_cap }
+    }
+
+    /// Assemble `context_documents` into the prompt, auditing which
+    /// documents were used.
+    pub(super) fn apply_context(
+        &self,
+        mut request: InferenceRequest,
+    ) -> Result<(InferenceRequest, Option<AssembledContext>), String> {
+        if request.context_documents.is_empty() {
+            return Ok((request, None));
+        }
+        let documents = std::mem::take(&mut request.context_documents);
+        let assembled = context_docs::assemble_context(&documents, &request.prompt, self.config.context_budget)
+            .map_err(|e| e.to_string())?;
+        for usage in [DocumentUse::Truncated, DocumentUse::Dropped] {
+            let count = assembled.documents.iter().filter(|d| d.usage == usage).count();
+            if count > 0 {
+                let name = if usage == DocumentUse::Dropped { "dropped" } else { "truncated" };
+                self.metrics_store.increment_counter(&format!("core_context_documents_{name}"), count as u64);
+            }
+        }
+        self.metrics_store.increment_counter("core_context_documents", documents.len() as u64);
+        if !request.privacy.no_log {
+            Self::audit_context(request.request_id, &assembled);
+        }
+        request.prompt = assembled.prompt.clone();
+        Ok((request, Some(assembled)))
+    }
+
+    /// Shadow evaluation samples requests into audit events and candidate
+    /// policy statistics, so either privacy flag opts a request out.
+    pub(super) fn shadow_allowed(privacy: PrivacyFlags) -> bool {
+        !privacy.no_log && !privacy.no_train_export
+    }
+
+    /// Apply the output limits to a finished generation: cut the text at
+    /// the byte limit, and report a generation that ran into a lowered
+    /// token limit.
+    pub(super) fn output_cut(
+        &self,
+        output: &mut String,
+        tokens: usize,
+        token_cap: Option<usize>,
+    ) -> Option<LimitError> {
+        if let Err(e) = self.config.limits.truncate_output(output) {
+            return Some(e);
+        }
+        token_cap.filter(|cap| tokens >= *cap).map(|max| LimitError::Output { unit: "tokens", max })
+    }
+
+    pub(super) fn record_limit(&self, error: &LimitError) {
+        self.metrics_store.increment_counter(&format!("core_limit_exceeded_{}", error.kind()), 1);
+        metrics::counter!("core_limit_exceeded_total", "limit" => error.kind()).increment(1);
+    }
+
+    /// Export a finished request as a span; its trace ID is the request
+    /// ID, so traces join up with logs and audit events for the request.
+    pub(super) fn export_span(&self, response: &InferenceResponse, started_ns: u64) {
+        let mut attributes = std::collections::HashMap::new();
+        attributes.insert("request_id".to_string(), SpanAttributeValue::String(response.request_id.to_string()));
+        attributes.insert("tokens_generated".to_string(), SpanAttributeValue::Int(response.tokens_generated as i64));
+        if let Some(model) = &response.served_model {
+            attributes.insert("model_id".to_string(), SpanAttributeValue::String(model.clone()));
+        }
+        self.spans.record(ExportableSpan {
+            trace_id: format!("{:032x}", response.request_id.0),
+            span_id: generate_span_id(),
+            parent_span_id: None,
+            name: "inference_request".to_string(),
+            start_time_unix_ns: started_ns,
+            end_time_unix_ns: now_unix_ns(),
+            status: if response.error.is_some() { SpanStatus::Error } else { SpanStatus::Ok },
+            attributes,
+        });
+    }
+}
@@ -0,0 +1,143 @@
+//! Relaying generated tokens to a streaming client: cancellation, the
+//! output limit and PII hold-back (gguf feature only).
+
+use tokio_util::sync::CancellationToken;
+
+use crate::engine::{ContextUsage, StreamingOutput, TokenStream};
+use crate::ipc::limits::OutputBudget;
+use crate::ipc::protocol::{IpcMessage, RequestId, ResponseErrorCode, StreamChunk};
+use crate::security::pii_block::PiiStreamGuard;
+use crate::security::{PiiBlock, PiiBlockPolicy};
+use crate::telemetry::{self, StreamTimer};
+
+use super::{HandlerError, IpcHandler, StreamSender};
+
+/// Relay state for one stream.
+pub(super) struct StreamRelay<'a> {
+    request_id: RequestId,
+    model_id: String,
+    usage: Option<ContextUsage>,
+    budget: OutputBudget,
+    pii_guard: Option<PiiStreamGuard<'a, u32>>,
+    timer: StreamTimer,
+    completion_tokens: usize,
+}
+
+/// What relaying one token did to the stream.
+enum Relayed {
+    /// Keep reading tokens.
+    More,
+    /// The final token was sent.
+    Done,
+    /// The stream was cut at the output limit or by the PII block policy.
+    Cut,
+}
+
+impl IpcHandler {
+    pub(super) async fn stream_relay(&self, request_id: RequestId, model_id: &str, prompt: &str) -> StreamRelay<'_> {
+        StreamRelay {
+            request_id,
+            model_id: model_id.to_string(),
+            usage: self.inference_engine.context_usage(model_id, prompt).await,
+            budget: self.config.limits.output_budget(),
+            pii_guard: self.pii_block.as_ref().map(PiiBlockPolicy::stream),
+            timer: StreamTimer::start(),
+            completion_tokens: 0,
+        }
+    }
+
+    /// Relay tokens until the final one, cancellation or a cut; returns
+    /// whether the stream ended early. Tokens still held when the channel
+    /// closes early are dropped.
+    pub(super) async fn relay_stream(
+        &self,
+        relay: &mut StreamRelay<'_>,
+        stream: &mut TokenStream,
+        sender: &dyn StreamSender,
+        cancel: &CancellationToken,
+    ) -> Result<bool, HandlerError> {
+        loop {
+            tokio::select! {
+                biased;
+                _ = cancel.cancelled() => {
+                    let chunk = StreamChunk::error(relay.request_id, "cancelled".into());
+                    let _ = sender.send(IpcMessage::StreamChunk(chunk)).await;
+                    return Ok(true);
+                }
+                token_opt = stream.next() => {
+                    // Channel closed
+                    let Some(output) = token_opt else {
+                        return Ok(false);
+                    };
+                    match self.relay_token(relay, output, sender).await? {
+                        Relayed::More => {}
+                        Relayed::Done => return Ok(false),
+                        Relayed::Cut => return Ok(true),
+                    }
+                }
+            }
+        }
+    }
+
+    async fn relay_token(
+        &self,
+        relay: &mut StreamRelay<'_>,
+        output: StreamingOutput,
+        sender: &dyn StreamSender,
+    ) -> Result<Relayed, HandlerError> {
+        if let Err(e) = relay.budget.charge(1, 0) {
+            self.record_limit(&e);
+            let chunk = StreamChunk::error_with_code(relay.request_id, e.code(), e.to_string());
+            sender.send(IpcMessage::StreamChunk(chunk)).await?;
+            return Ok(Relayed::Cut);
+        }
+        let latency = relay.timer.token();
+        self.metrics_store.record_token_latency(latency);
+        telemetry::record_token_latency(&relay.model_id, latency);
+        relay.completion_tokens += 1;
+        let mut tokens = match self.screen_token(relay, &output).await {
+            Ok(tokens) => tokens,
+            Err(block) => {
+                self.record_pii_block(&block, relay.request_id, &relay.model_id, true);
+                let code = ResponseErrorCode::PolicyBlocked;
+                let chunk = StreamChunk::error_with_code(relay.request_id, code, block.to_string());
+                sender.send(IpcMessage::StreamChunk(chunk)).await?;
+                return Ok(Relayed::Cut);
+            }
+        };
+        let last = if output.is_final { tokens.pop() } else { None };
+        for token in tokens {
+            sender.send(IpcMessage::StreamChunk(StreamChunk::token(relay.request_id, token))).await?;
+        }
+        let Some(token) = last else {
+            return Ok(Relayed::More);
+        };
+        let mut chunk = StreamChunk::final_token(relay.request_id, token).with_served_model(relay.model_id.clone());
+        if let Some(usage) = relay.usage {
+            chunk = chunk.with_usage(usage.with_completion(relay.completion_tokens));
+        }
+        sender.send(IpcMessage::StreamChunk(chunk)).await?;
+        Ok(Relayed::Done)
+    }
+
+    /// Hold tokens back for the PII block policy; returns the tokens now
+    /// safe to send.
+    async fn screen_token(&self, relay: &mut StreamRelay<'_>, output: &StreamingOutput) -> Result<Vec<u32>, PiiBlock> {
+        let Some(guard) = relay.pii_guard.as_mut() else {
+            return Ok(vec![output.token]);
+        };
+        // Undecodable output cannot be screened; fail closed
+        match self.inference_engine.token_text(&relay.model_id, output.token).await {
+            Some(text) if output.is_final => guard.finish(output.token, &text),
+            Some(text) => guard.push(output.token, &text),
+            None => Err(PiiBlock::unscreened()),
+        }
+    }
+
+    /// Feed the stream's timings into the model's statistics.
+    pub(super) async fn record_stream(&self, relay: &StreamRelay<'_>) {
+        if let Some(handle) = self.inference_engine.get_handle(&relay.model_id).await {
+            self.model_registry.record_stream(handle, &relay.timer.timings()).await;
+        }
+    }
+}
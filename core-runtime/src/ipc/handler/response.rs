@@ -0,0 +1,131 @@
+//! Finished inference: metrics, usage and the response.
+
+use crate::ipc::affinity::AffinityTracker;
+use crate::ipc::limits::LimitError;
+use crate::ipc::protocol::{InferenceRequest, InferenceResponse, RequestId, ResponseErrorCode};
+use crate::engine::context_docs::{self, AssembledContext};
+use crate::engine::inference::InferenceError;
+use crate::engine::{FinishReason, InferenceResult};
+use crate::telemetry::{self, REQUEST_LATENCY_HISTOGRAM};
+
+use super::IpcHandler;
+
+impl IpcHandler {
+    /// Record latency, token and usage metrics for a completed run.
+    pub(super) async fn record_success(
+        &self,
+        model_id: &str,
+        latency_ms: u64,
+        result: &InferenceResult,
+        tenant: Option<&str>,
+    ) {
+        self.overload.record_latency(latency_ms);
+
+        // Record metrics via telemetry facade (Prometheus-compatible)
+        telemetry::record_request_success(
+            model_id,
+            latency_ms,
+            result.tokens_generated as u64,
+        );
+        self.metrics_store
+            .record_request(true, result.tokens_generated as u64);
+        if let Some(tenant) = tenant {
+            self.usage.record(tenant, result.tokens_generated as u64);
+        }
+        self.metrics_store
+            .record_bucketed(REQUEST_LATENCY_HISTOGRAM, latency_ms as f64);
+
+        // Also record in model registry with correct handle for per-model stats
+        if let Some(handle) = self.inference_engine.get_handle(model_id).await {
+            self.model_registry
+                .record_request(handle, latency_ms as f64)
+                .await;
+        }
+        if result.finish_reason == FinishReason::Degenerated {
+            self.metrics_store.increment_counter("core_generation_degenerated", 1);
+            metrics::counter!("core_generation_degenerated_total", "model" => model_id.to_string()).increment(1);
+        }
+    }
+
+    /// Build the response: usage, output limits, affinity, citations and
+    /// tool calls.
+    pub(super) async fn success_response(
+        &self,
+        request: &InferenceRequest,
+        result: InferenceResult,
+        model_id: String,
+        prompt: &str,
+        cut: Option<LimitError>,
+        context: Option<AssembledContext>,
+    ) -> InferenceResponse {
+        let mut response = InferenceResponse::success(
+            request.request_id,
+            result.output,
+            result.tokens_generated,
+            result.finished,
+        )
+        .with_served_model(model_id.clone())
+        .with_finish_reason(result.finish_reason);
+        if let Some(usage) = self.inference_engine.context_usage(&model_id, prompt).await {
+            response = response.with_usage(usage.with_completion(result.tokens_generated));
+        }
+        if let Some(e) = cut {
+            self.record_limit(&e);
+            response = response.with_error_code(e.code(), e.to_string());
+        }
+        // The affinity key is a prompt hash; no_cache keeps none of it
+        if !request.privacy.no_cache {
+            let affinity_key = AffinityTracker::resolve_key(
+                request.session_affinity_key.as_deref(),
+                &model_id,
+                &request.prompt,
+            );
+            self.affinity.touch(&affinity_key);
+            response = response.with_affinity_key(affinity_key);
+        }
+        let response = Self::attach_context(request, response, context);
+        Self::extract_tool_calls(request, response)
+    }
+
+    /// Attach the context documents used, citing them when asked.
+    fn attach_context(
+        request: &InferenceRequest,
+        response: InferenceResponse,
+        context: Option<AssembledContext>,
+    ) -> InferenceResponse {
+        let Some(context) = context else {
+            return response;
+        };
+        let citations = if request.cite_sources {
+            context_docs::attribute_citations(&response.output, &context.documents)
+        } else {
+            Vec::new()
+        };
+        response.with_context(context.documents, citations)
+    }
+
+    /// Record a failed run and build its error response.
+    pub(super) fn failure_response(
+        &self,
+        request_id: RequestId,
+        model_id: &str,
+        error: InferenceError,
+    ) -> InferenceResponse {
+        telemetry::record_request_failure(model_id, &error.to_string());
+        self.metrics_store.record_request(false, 0);
+        InferenceResponse::error(request_id, error.to_string())
+    }
+
+    /// Withhold output the PII block policy flags.
+    pub(super) fn pii_blocked_response(
+        &self,
+        request_id: RequestId,
+        model_id: &str,
+        output: &str,
+    ) -> Option<InferenceResponse> {
+        let block = self.pii_block.as_ref()?.check(output)?;
+        self.record_pii_block(&block, request_id, model_id, false);
+        let code = ResponseErrorCode::PolicyBlocked;
+        Some(InferenceResponse::error_with_code(request_id, code, block.to_string()))
+    }
+}
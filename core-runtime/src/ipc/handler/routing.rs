@@ -0,0 +1,188 @@
+//! Model routing: fallback chains, circuit permits and hedged runs.
+
+use std::sync::Arc;
+
+use crate::engine::inference::InferenceError;
+use crate::engine::{InferenceParams, InferenceResult};
+use crate::features::Feature;
+use crate::models::FallbackReason;
+use crate::scheduler::{AbortOnDrop, CircuitOpen, CircuitPermit, HedgeGuard};
+use crate::telemetry::FALLBACK_COUNTER;
+
+use super::IpcHandler;
+
+/// An inference run spawned so it can be raced and aborted.
+type InferenceRun = AbortOnDrop<Result<InferenceResult, InferenceError>>;
+
+/// Flatten a spawned inference run; a panicked or aborted task is a failure.
+fn joined_result(
+    joined: Result<Result<InferenceResult, InferenceError>, tokio::task::JoinError>,
+) -> Result<InferenceResult, InferenceError> {
+    joined.unwrap_or_else(|e| Err(InferenceError::ExecutionFailed(format!("Inference task failed: {}", e))))
+}
+
+impl IpcHandler {
+    /// Run on the permitted model. When it has a hedge pair and is still
+    /// running after the TTFT threshold, race the secondary replica and keep
+    /// the first success; the loser is aborted. Returns the served model.
+    pub(super) async fn run_hedged(
+        &self,
+        permit: CircuitPermit<'_>,
+        prompt: &str,
+        params: &InferenceParams,
+    ) -> (Result<InferenceResult, InferenceError>, String) {
+        let model_id = permit.model().to_string();
+        let secondary = self
+            .hedger
+            .secondary(&model_id)
+            .filter(|_| self.features.enabled(Feature::RequestHedging))
+            .map(str::to_string);
+        let Some(secondary) = secondary else {
+            let result = self.inference_engine.run(&model_id, prompt, params).await;
+            permit.resolve(&result);
+            return (result, model_id);
+        };
+        self.hedger.observe();
+
+        // Spawned so the threshold timer runs even while the backend blocks
+        let mut primary = self.spawn_run(&model_id, prompt, params);
+        let early = tokio::select! {
+            joined = &mut primary => Some(joined_result(joined)),
+            _ = tokio::time::sleep(self.hedger.threshold()) => None,
+        };
+        if let Some(result) = early {
+            permit.resolve(&result);
+            return (result, model_id);
+        }
+
+        let Some((backup_permit, _hedge)) = self.acquire_hedge(&model_id, &secondary).await else {
+            let result = joined_result(primary.await);
+            permit.resolve(&result);
+            return (result, model_id);
+        };
+        let backup = self.spawn_run(&secondary, prompt, params);
+        self.race_hedge(permit, primary, model_id, backup_permit, backup, secondary).await
+    }
+
+    /// Race the primary run against its hedge and keep the first success.
+    async fn race_hedge(
+        &self,
+        permit: CircuitPermit<'_>,
+        mut primary: InferenceRun,
+        model_id: String,
+        backup_permit: CircuitPermit<'_>,
+        mut backup: InferenceRun,
+        secondary: String,
+    ) -> (Result<InferenceResult, InferenceError>, String) {
+        let (primary_first, result) = tokio::select! {
+            joined = &mut primary => (true, joined_result(joined)),
+            joined = &mut backup => (false, joined_result(joined)),
+        };
+
+        // A failed first finisher hands over to the other replica
+        let (result, served) = if primary_first {
+            permit.resolve(&result);
+            if result.is_ok() {
+                (result, model_id.clone())
+            } else {
+                let result = joined_result(backup.await);
+                backup_permit.resolve(&result);
+                (result, secondary)
+            }
+        } else {
+            backup_permit.resolve(&result);
+            if result.is_ok() {
+                (result, secondary)
+            } else {
+                let result = joined_result(primary.await);
+                permit.resolve(&result);
+                (result, model_id.clone())
+            }
+        };
+        let winner = if served == model_id { "primary" } else { "secondary" };
+        metrics::counter!("core_hedge_total", "model" => model_id, "winner" => winner).increment(1);
+        self.metrics_store.increment_counter(&format!("core_hedges_won_{}", winner), 1);
+        (result, served)
+    }
+
+    /// Admit a hedge onto `secondary` when it is loaded, its circuit is
+    /// closed and the hedge caps allow one more.
+    async fn acquire_hedge(&self, primary: &str, secondary: &str) -> Option<(CircuitPermit<'_>, HedgeGuard)> {
+        let skip = |reason: &str| {
+            tracing::debug!(primary, secondary, reason, "Hedge skipped");
+            self.metrics_store.increment_counter(&format!("core_hedges_skipped_{}", reason), 1);
+        };
+        if self.inference_engine.get_handle(secondary).await.is_none() {
+            skip("unavailable");
+            return None;
+        }
+        let guard = match self.hedger.try_hedge(self.overload.level()) {
+            Ok(guard) => guard,
+            Err(reason) => {
+                skip(reason.as_str());
+                return None;
+            }
+        };
+        let Ok(permit) = self.circuits.try_acquire(secondary) else {
+            skip("circuit_open");
+            return None;
+        };
+        tracing::debug!(primary, secondary, "Hedging slow request onto replica");
+        self.metrics_store.increment_counter("core_hedges_launched", 1);
+        Some((permit, guard))
+    }
+
+    fn spawn_run(&self, model_id: &str, prompt: &str, params: &InferenceParams) -> InferenceRun {
+        let engine = Arc::clone(&self.inference_engine);
+        let (model_id, prompt, params) = (model_id.to_string(), prompt.to_string(), params.clone());
+        AbortOnDrop::spawn(async move { engine.run(&model_id, &prompt, &params).await })
+    }
+
+    /// Admit the first model in `model_id`'s fallback chain that is loaded
+    /// and whose circuit is not open. The last candidate is admitted even
+    /// when not loaded, so the engine reports it as usual.
+    pub(super) async fn route_model(&self, model_id: &str) -> Result<CircuitPermit<'_>, CircuitOpen> {
+        let chain = self.model_registry.fallback_chain(model_id);
+        let mut primary_skipped = None;
+        let mut first_open = None;
+        for (i, candidate) in chain.iter().enumerate() {
+            let last = i + 1 == chain.len();
+            // A throttled GPU sheds traffic to off-GPU alternates
+            if self.thermal.should_shift(candidate, &chain[i + 1..]) {
+                primary_skipped.get_or_insert(FallbackReason::Thermal);
+                continue;
+            }
+            let reason = match self.circuits.try_acquire(candidate) {
+                Ok(permit) if last || self.inference_engine.get_handle(candidate).await.is_some() => {
+                    if let Some(reason) = primary_skipped {
+                        self.record_fallback(model_id, candidate, reason);
+                    }
+                    return Ok(permit);
+                }
+                Ok(_) => FallbackReason::Unavailable,
+                Err(open) => {
+                    first_open.get_or_insert(open);
+                    FallbackReason::CircuitOpen
+                }
+            };
+            primary_skipped.get_or_insert(reason);
+        }
+        self.metrics_store.increment_counter("core_circuit_rejections", 1);
+        // The loop only ends here when the last candidate's circuit was open
+        Err(first_open.unwrap_or_else(|| CircuitOpen { model: model_id.to_string(), retry_after_ms: 0 }))
+    }
+
+    fn record_fallback(&self, requested: &str, served: &str, reason: FallbackReason) {
+        tracing::debug!(requested, served, reason = reason.as_str(), "Serving fallback model");
+        metrics::counter!(
+            "core_fallback_total",
+            "model" => requested.to_string(),
+            "served" => served.to_string(),
+            "reason" => reason.as_str()
+        )
+        .increment(1);
+        self.metrics_store.increment_counter(FALLBACK_COUNTER, 1);
+        self.metrics_store
+            .increment_counter(&format!("{}_{}", FALLBACK_COUNTER, reason.as_str()), 1);
+    }
+}
@@ -0,0 +1,99 @@
+//! Session checks: authentication, roles and the principal behind a session.
+
+use crate::ipc::auth::{SessionRole, SessionToken};
+use crate::ipc::protocol::{IpcMessage, ProtocolVersion};
+use crate::features::Feature;
+use crate::scheduler::Priority;
+
+use super::{HandlerError, IpcHandler};
+
+impl IpcHandler {
+    /// Authenticate a handshake and negotiate the protocol version.
+    pub(super) async fn handle_handshake(
+        &self,
+        token: &str,
+        protocol_version: Option<ProtocolVersion>,
+    ) -> Result<(IpcMessage, Option<SessionToken>), HandlerError> {
+        let session_token = self.auth.authenticate(token).await?;
+        let role = self.auth.role(&session_token).await.unwrap_or(SessionRole::Standard);
+        metrics::counter!("core_ipc_connections_total", "role" => role.as_str()).increment(1);
+        if role == SessionRole::Observer {
+            self.metrics_store.increment_counter("core_observer_sessions", 1);
+        }
+        // Negotiate protocol version with client; V2 only while offered
+        let protocol_version =
+            protocol_version.filter(|v| *v != ProtocolVersion::V2 || self.features.enabled(Feature::BinaryProtocol));
+        let negotiated_version = ProtocolVersion::negotiate(protocol_version);
+        let response = IpcMessage::HandshakeAck {
+            session_id: session_token.as_str().to_string(),
+            protocol_version: negotiated_version,
+            deprecations: self.deprecations.advertised(),
+        };
+        Ok((response, Some(session_token)))
+    }
+
+    pub(super) async fn require_auth(&self, session: Option<&SessionToken>) -> Result<(), HandlerError> {
+        if !self.config.require_auth {
+            return Ok(());
+        }
+
+        let token = session.ok_or(HandlerError::NotAuthenticated)?;
+        self.auth.validate(token).await?;
+        Ok(())
+    }
+
+    /// Stable identity behind a session, surviving reconnects.
+    pub(super) async fn principal(&self, session: Option<&SessionToken>) -> Option<String> {
+        match session {
+            Some(token) => self.auth.principal(token).await,
+            None => None,
+        }
+    }
+
+    /// Whether the session is a read-only observer. Without auth there are
+    /// no roles, so nothing is an observer.
+    pub(super) async fn is_observer(&self, session: Option<&SessionToken>) -> bool {
+        if !self.config.require_auth {
+            return false;
+        }
+        match session {
+            Some(token) => self.auth.role(token).await == Some(SessionRole::Observer),
+            None => false,
+        }
+    }
+
+    /// Whether the session may perform admin operations.
+    pub(super) async fn is_admin(&self, session: Option<&SessionToken>) -> bool {
+        if !self.config.require_auth {
+            return true;
+        }
+        match session {
+            Some(token) => self.auth.role(token).await == Some(SessionRole::Admin),
+            None => false,
+        }
+    }
+
+    /// Cap the client-requested priority by the session's role.
+    pub(super) async fn effective_priority(
+        &self,
+        requested: Priority,
+        session: Option<&SessionToken>,
+    ) -> Priority {
+        if !self.config.require_auth {
+            return requested;
+        }
+        let cap = match session {
+            Some(token) => self.auth.role(token).await.map(|r| r.max_priority()),
+            None => None,
+        };
+        let effective = requested.min(cap.unwrap_or(Priority::Normal));
+        if effective != requested {
+            tracing::debug!(
+                requested = requested.as_str(),
+                effective = effective.as_str(),
+                "Request priority capped by session role"
+            );
+        }
+        effective
+    }
+}
@@ -0,0 +1,99 @@
+//! Streaming inference over a `StreamSender`.
+
+#[cfg(feature = "gguf")]
+use std::sync::Arc;
+
+use tokio_util::sync::CancellationToken;
+
+use crate::ipc::auth::SessionToken;
+use crate::ipc::protocol::{InferenceRequest, IpcMessage, StreamChunk};
+#[cfg(feature = "gguf")]
+use crate::engine::TokenStream;
+
+use super::{HandlerError, IpcHandler, StreamSender, READ_ONLY_MESSAGE};
+
+impl IpcHandler {
+    /// Process streaming inference request. Sends token chunks via sender.
+    ///
+    /// Creates a token stream channel, spawns inference on a blocking task,
+    /// and relays tokens to the client until completion or cancellation.
+    #[allow(unused_variables)]
+    pub async fn process_streaming(
+        &self,
+        request: InferenceRequest,
+        session: &SessionToken,
+        sender: &dyn StreamSender,
+        cancel: CancellationToken,
+    ) -> Result<(), HandlerError> {
+        self.auth.validate(session).await?;
+        if self.is_observer(Some(session)).await {
+            self.metrics_store.increment_counter("core_observer_denied", 1);
+            return sender.send(IpcMessage::Error { code: 403, message: READ_ONLY_MESSAGE.into() }).await;
+        }
+        let _guard = self.shutdown.track().ok_or(HandlerError::ShuttingDown)?;
+
+        let request = match self.prepare_stream(request, session).await {
+            Ok(request) => request,
+            Err(chunk) => return sender.send(IpcMessage::StreamChunk(*chunk)).await,
+        };
+
+        // Streaming requires gguf feature
+        #[cfg(not(feature = "gguf"))]
+        {
+            let chunk = StreamChunk::error(
+                request.request_id,
+                "Streaming requires GGUF feature. Rebuild with --features gguf.".into(),
+            );
+            sender.send(IpcMessage::StreamChunk(chunk)).await?;
+            return Ok(());
+        }
+
+        #[cfg(feature = "gguf")]
+        {
+            self.run_streaming_inference(request, sender, cancel).await
+        }
+    }
+
+    /// Internal streaming implementation (gguf feature only).
+    #[cfg(feature = "gguf")]
+    async fn run_streaming_inference(
+        &self,
+        request: InferenceRequest,
+        sender: &dyn StreamSender,
+        cancel: CancellationToken,
+    ) -> Result<(), HandlerError> {
+        let request_id = request.request_id;
+        let permit = match self.route_model(&request.model_id).await {
+            Ok(permit) => permit,
+            Err(e) => return sender.send(IpcMessage::StreamChunk(StreamChunk::error(request_id, e.to_string()))).await,
+        };
+        let model_id = permit.model().to_string();
+        let _running = self.occupancy.begin(&request_id.to_string(), &model_id);
+        let _thermal_slot = self.thermal.admit(&model_id).await;
+        let _gpu_turn = self.gpu.acquire(&model_id).await;
+        let mut relay = self.stream_relay(request_id, &model_id, &request.prompt).await;
+
+        // Create channel for token streaming and spawn blocking inference task
+        let (token_sender, mut stream) = TokenStream::new(32);
+        let engine = Arc::clone(&self.inference_engine);
+        let config = request.parameters.to_config();
+        let inf_handle = tokio::task::spawn_blocking(move || {
+            engine.run_stream_sync(&model_id, &request.prompt, &config, token_sender)
+        });
+        let cancelled = self.relay_stream(&mut relay, &mut stream, sender, &cancel).await?;
+
+        // Closing the stream stops a generator that is still running
+        drop(stream);
+
+        // Wait for inference task; tokens are already sent, the result only
+        // feeds the circuit breaker (a cancelled or cut stream says nothing
+        // about the model)
+        match inf_handle.await {
+            _ if cancelled => drop(permit),
+            Ok(result) => permit.resolve(&result),
+            Err(_) => permit.failure(),
+        }
+        self.record_stream(&relay).await;
+        Ok(())
+    }
+}
@@ -7,8 +7,9 @@ use std::sync::Arc;
 
 use super::protocol::{HealthCheckResponse, HealthCheckType};
 use crate::health::HealthChecker;
+use crate::maintenance::MaintenanceScheduler;
 use crate::models::ModelRegistry;
-use crate::scheduler::RequestQueue;
+use crate::scheduler::{Occupancy, OverloadController, RequestQueue};
 use crate::shutdown::ShutdownCoordinator;
 
 /// Handles health check requests (orchestrator pattern, no auth).
@@ -17,6 +18,9 @@ pub struct HealthHandler {
     shutdown: Arc<ShutdownCoordinator>,
     model_registry: Arc<ModelRegistry>,
     queue: Arc<RequestQueue>,
+    overload: Arc<OverloadController>,
+    maintenance: Arc<MaintenanceScheduler>,
+    occupancy: Arc<Occupancy>,
 }
 
 impl HealthHandler {
@@ -25,8 +29,11 @@ impl HealthHandler {
         shutdown: Arc<ShutdownCoordinator>,
         model_registry: Arc<ModelRegistry>,
         queue: Arc<RequestQueue>,
+        overload: Arc<OverloadController>,
+        maintenance: Arc<MaintenanceScheduler>,
+        occupancy: Arc<Occupancy>,
     ) -> Self {
-        Self { health, shutdown, model_registry, queue }
+        Self { health, shutdown, model_registry, queue, overload, maintenance, occupancy }
     }
 
     /// Handle a health check request. Returns appropriate response.
@@ -74,7 +81,13 @@ impl HealthHandler {
         queue_len: usize,
     ) -> HealthCheckResponse {
         let memory = self.model_registry.total_memory().await;
-        let report = self.health.report(shutdown_state, models, memory, queue_len);
+        let report = self
+            .health
+            .report(shutdown_state, models, memory, queue_len)
+            .with_degradation(self.overload.level())
+            .with_maintenance(self.maintenance.status())
+            .with_scheduler(self.occupancy.snapshot());
+        self.health.observe(report.state);
         HealthCheckResponse {
             check_type: HealthCheckType::Full,
             ok: report.ready,
@@ -0,0 +1,144 @@
+//! Single-instance lock for a socket path.
+//!
+//! Two runtimes started with the same socket path would otherwise race:
+//! the second renames its freshly bound socket over the first one's, and
+//! the first keeps running unreachable. The lock file `<socket>.lock` is
+//! held with `flock`, which the kernel releases when its holder dies, so a
+//! held lock proves a live owner and a free one proves the old owner gone.
+//! The file records the owner's PID for the conflict report.
+//!
+//! The lock file is never removed: unlinking it would let a process that
+//! opened the old inode lock it alongside a newer owner.
+
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, Write};
+use std::os::unix::io::AsRawFd;
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum InstanceLockError {
+    #[error(
+        "Another runtime{} already serves {socket}; stop it or choose a different socket path (lock: {lock})",
+        pid.map(|p| format!(" (pid {p})")).unwrap_or_default(),
+        socket = socket.display(),
+        lock = lock.display()
+    )]
+    AlreadyRunning {
+        socket: PathBuf,
+        lock: PathBuf,
+        pid: Option<u32>,
+    },
+
+    #[error("IO error on {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+/// Held for the lifetime of the server; released on drop or process exit.
+#[derive(Debug)]
+pub struct InstanceLock {
+    file: File,
+    path: PathBuf,
+}
+
+/// Lock file guarding `socket_path`.
+pub fn lock_path(socket_path: &Path) -> PathBuf {
+    let mut path = socket_path.as_os_str().to_owned();
+    path.push(".lock");
+    PathBuf::from(path)
+}
+
+impl InstanceLock {
+    /// Take the lock for `socket_path`, or report the running owner.
+    pub fn acquire(socket_path: &Path) -> Result<Self, InstanceLockError> {
+        let path = lock_path(socket_path);
+        let io = |source| InstanceLockError::Io { path: path.clone(), source };
+
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .map_err(io)?;
+
+        // SAFETY: flock on a descriptor we own
+        if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } != 0 {
+            let err = std::io::Error::last_os_error();
+            if err.kind() != std::io::ErrorKind::WouldBlock {
+                return Err(io(err));
+            }
+            let mut contents = String::new();
+            let _ = file.read_to_string(&mut contents);
+            return Err(InstanceLockError::AlreadyRunning {
+                socket: socket_path.to_path_buf(),
+                lock: path,
+                pid: contents.trim().parse().ok(),
+            });
+        }
+
+        file.set_len(0).map_err(io)?;
+        file.rewind().map_err(io)?;
+        writeln!(file, "{}", std::process::id()).map_err(io)?;
+        file.sync_all().map_err(io)?;
+        Ok(Self { file, path })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for InstanceLock {
+    fn drop(&mut self) {
+        // SAFETY: flock on a descriptor we own
+        unsafe { libc::flock(self.file.as_raw_fd(), libc::LOCK_UN) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_second_acquire_reports_owner_pid() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket = dir.path().join("core.sock");
+
+        let _held = InstanceLock::acquire(&socket).unwrap();
+        match InstanceLock::acquire(&socket) {
+            Err(InstanceLockError::AlreadyRunning { pid, .. }) => {
+                assert_eq!(pid, Some(std::process::id()))
+            }
+            other => panic!("expected AlreadyRunning, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_lock_released_on_drop() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket = dir.path().join("core.sock");
+
+        drop(InstanceLock::acquire(&socket).unwrap());
+        let lock = InstanceLock::acquire(&socket).unwrap();
+        assert_eq!(lock.path(), lock_path(&socket));
+        assert!(lock.path().exists());
+    }
+
+    #[test]
+    fn test_stale_lock_file_is_taken_over() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket = dir.path().join("core.sock");
+        // Left behind by a process that no longer holds the lock
+        std::fs::write(lock_path(&socket), "999999\n").unwrap();
+
+        let lock = InstanceLock::acquire(&socket).unwrap();
+        let pid = std::fs::read_to_string(lock.path()).unwrap();
+        assert_eq!(pid.trim(), std::process::id().to_string());
+    }
+}
@@ -0,0 +1,220 @@
+//! Per-field request and response size limits.
+//!
+//! The frame limit bounds a message as a whole; these bound its parts, so
+//! an oversized prompt or a runaway generation is refused with a specific
+//! code long before it approaches the frame size. Prompt and metadata
+//! limits are checked once the prompt is assembled. Output limits are
+//! enforced as output is produced: the token limit caps generation up
+//! front and, for streams, is charged per relayed token so the stream is
+//! cut at the limit. Streamed chunks carry token ids only, so byte limits
+//! apply where text is returned.
+
+use thiserror::Error;
+
+use super::protocol::{InferenceRequest, ResponseErrorCode};
+use crate::engine::utf8;
+
+/// Size limits. Zero disables a limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestLimits {
+    /// Assembled prompt size, after templates and context documents.
+    pub max_prompt_bytes: usize,
+    /// Assembled prompt length, estimated at ~4 bytes per token.
+    pub max_prompt_tokens: usize,
+    /// Tools, template variables, keys and document ids/sources combined.
+    pub max_metadata_bytes: usize,
+    /// Generated text returned in one response.
+    pub max_output_bytes: usize,
+    /// Tokens generated for one request.
+    pub max_output_tokens: usize,
+}
+
+impl Default for RequestLimits {
+    fn default() -> Self {
+        Self {
+            max_prompt_bytes: 4 * 1024 * 1024,
+            max_prompt_tokens: 0,
+            max_metadata_bytes: 256 * 1024,
+            max_output_bytes: 4 * 1024 * 1024,
+            max_output_tokens: 0,
+        }
+    }
+}
+
+/// A limit that was exceeded.
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum LimitError {
+    #[error("prompt too large: {size} {unit} (max {max})")]
+    Prompt { unit: &'static str, size: usize, max: usize },
+    #[error("request metadata too large: {size} bytes (max {max})")]
+    Metadata { size: usize, max: usize },
+    #[error("output limit exceeded: {max} {unit}")]
+    Output { unit: &'static str, max: usize },
+}
+
+impl LimitError {
+    /// Label for limit metrics.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Prompt { .. } => "prompt",
+            Self::Metadata { .. } => "metadata",
+            Self::Output { .. } => "output",
+        }
+    }
+
+    /// Code reported alongside the message.
+    pub fn code(&self) -> ResponseErrorCode {
+        match self {
+            Self::Prompt { .. } => ResponseErrorCode::PromptLimitExceeded,
+            Self::Metadata { .. } => ResponseErrorCode::MetadataLimitExceeded,
+            Self::Output { .. } => ResponseErrorCode::OutputLimitExceeded,
+        }
+    }
+}
+
+fn exceeds(size: usize, max: usize) -> bool {
+    max > 0 && size > max
+}
+
+impl RequestLimits {
+    /// Check an assembled request's prompt and metadata.
+    pub fn check_request(&self, request: &InferenceRequest) -> Result<(), LimitError> {
+        let bytes = request.prompt.len();
+        if exceeds(bytes, self.max_prompt_bytes) {
+            return Err(LimitError::Prompt { unit: "bytes", size: bytes, max: self.max_prompt_bytes });
+        }
+        let tokens = bytes.div_ceil(4);
+        if exceeds(tokens, self.max_prompt_tokens) {
+            return Err(LimitError::Prompt { unit: "tokens", size: tokens, max: self.max_prompt_tokens });
+        }
+        let metadata = metadata_bytes(request);
+        if exceeds(metadata, self.max_metadata_bytes) {
+            return Err(LimitError::Metadata { size: metadata, max: self.max_metadata_bytes });
+        }
+        Ok(())
+    }
+
+    /// Clamp `max_tokens` to the output token limit. Returns whether it
+    /// was lowered, i.e. whether reaching it means the output was cut.
+    pub fn cap_max_tokens(&self, max_tokens: &mut usize) -> bool {
+        if exceeds(*max_tokens, self.max_output_tokens) {
+            *max_tokens = self.max_output_tokens;
+            return true;
+        }
+        false
+    }
+
+    /// Cut `output` to the byte limit on a char boundary.
+    pub fn truncate_output(&self, output: &mut String) -> Result<(), LimitError> {
+        if !exceeds(output.len(), self.max_output_bytes) {
+            return Ok(());
+        }
+        utf8::truncate_bytes(output, self.max_output_bytes);
+        Err(LimitError::Output { unit: "bytes", max: self.max_output_bytes })
+    }
+
+    /// Budget for output produced incrementally.
+    pub fn output_budget(&self) -> OutputBudget {
+        OutputBudget { limits: *self, tokens: 0, bytes: 0 }
+    }
+}
+
+/// Bytes of everything in a request besides the prompt, images and
+/// document text.
+fn metadata_bytes(request: &InferenceRequest) -> usize {
+    let tools: usize = request
+        .tools
+        .iter()
+        .map(|t| t.name.len() + t.description.as_ref().map_or(0, String::len) + t.parameters.to_string().len())
+        .sum();
+    let variables: usize = request.variables.iter().map(|(k, v)| k.len() + v.len()).sum();
+    let documents: usize = request
+        .context_documents
+        .iter()
+        .map(|d| d.id.len() + d.source.as_ref().map_or(0, String::len))
+        .sum();
+    let keys = [&request.template_id, &request.session_affinity_key, &request.idempotency_key]
+        .into_iter()
+        .map(|k| k.as_ref().map_or(0, String::len))
+        .sum::<usize>();
+    tools + variables + documents + keys
+}
+
+/// Output charged so far against the output limits.
+#[derive(Debug, Clone)]
+pub struct OutputBudget {
+    limits: RequestLimits,
+    tokens: usize,
+    bytes: usize,
+}
+
+impl OutputBudget {
+    /// Charge output about to be sent. On error nothing is charged and the
+    /// output must not be sent.
+    pub fn charge(&mut self, tokens: usize, bytes: usize) -> Result<(), LimitError> {
+        let limits = &self.limits;
+        if exceeds(self.tokens + tokens, limits.max_output_tokens) {
+            return Err(LimitError::Output { unit: "tokens", max: limits.max_output_tokens });
+        }
+        if exceeds(self.bytes + bytes, limits.max_output_bytes) {
+            return Err(LimitError::Output { unit: "bytes", max: limits.max_output_bytes });
+        }
+        self.tokens += tokens;
+        self.bytes += bytes;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(prompt: &str) -> InferenceRequest {
+        serde_json::from_value(serde_json::json!({
+            "request_id": 1,
+            "model_id": "m",
+            "prompt": prompt,
+            "parameters": {"max_tokens": 8, "temperature": 0.0, "top_p": 1.0, "top_k": 1},
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_request_limits() {
+        let limits = RequestLimits { max_prompt_bytes: 16, max_prompt_tokens: 3, max_metadata_bytes: 4, ..Default::default() };
+        assert!(limits.check_request(&request("short")).is_ok());
+        let err = limits.check_request(&request(&"x".repeat(17))).unwrap_err();
+        assert_eq!(err.code(), ResponseErrorCode::PromptLimitExceeded);
+        assert!(matches!(limits.check_request(&request(&"x".repeat(13))), Err(LimitError::Prompt { unit: "tokens", .. })));
+
+        let mut keyed = request("short");
+        keyed.idempotency_key = Some("12345".into());
+        assert_eq!(limits.check_request(&keyed), Err(LimitError::Metadata { size: 5, max: 4 }));
+    }
+
+    #[test]
+    fn test_budget_cuts_at_limit() {
+        let limits = RequestLimits { max_output_tokens: 3, ..Default::default() };
+        let mut budget = limits.output_budget();
+        for _ in 0..3 {
+            assert!(budget.charge(1, 0).is_ok());
+        }
+        let err = budget.charge(1, 0).unwrap_err();
+        assert_eq!(err.code(), ResponseErrorCode::OutputLimitExceeded);
+
+        let mut max_tokens = 10;
+        assert!(limits.cap_max_tokens(&mut max_tokens));
+        assert_eq!(max_tokens, 3);
+        assert!(!RequestLimits::default().cap_max_tokens(&mut max_tokens));
+    }
+
+    #[test]
+    fn test_truncate_output_on_char_boundary() {
+        let limits = RequestLimits { max_output_bytes: 4, ..Default::default() };
+        let mut fits = "abé".to_string();
+        assert!(limits.truncate_output(&mut fits).is_ok());
+        let mut output = "abcé".to_string();
+        assert!(limits.truncate_output(&mut output).is_err());
+        assert_eq!(output, "abc");
+    }
+}
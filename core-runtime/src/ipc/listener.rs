@@ -0,0 +1,232 @@
+//! Unix listener sources: filesystem path, abstract namespace, or a socket
+//! passed in by systemd socket activation.
+//!
+//! - A path starting with `@` names a Linux abstract namespace socket. It
+//!   has no file, so there is nothing to clean up and nothing to race on;
+//!   access control relies on the session handshake alone.
+//! - If `LISTEN_PID` matches this process and `LISTEN_FDS` is at least 1,
+//!   the first passed descriptor (fd 3) is adopted instead of binding.
+//!   systemd owns that socket file, so the server never removes it.
+//! - Under `serve --supervise` the supervisor binds the socket and passes
+//!   it as fd 3 too, naming itself in [`SUPERVISOR_PID_ENV`]. The child
+//!   adopts it only if that is its parent, and the supervisor removes the
+//!   socket file when it exits.
+
+use std::os::unix::io::{FromRawFd, RawFd};
+use std::path::{Path, PathBuf};
+
+use tokio::net::UnixListener;
+
+use super::server::ServerError;
+use super::socket_perms::{self, SocketPermissions};
+
+/// First descriptor passed by systemd (`SD_LISTEN_FDS_START`).
+pub const LISTEN_FDS_START: RawFd = 3;
+
+/// Set by the supervisor on its child to its own pid.
+pub const SUPERVISOR_PID_ENV: &str = "CORE_SUPERVISOR_PID";
+
+/// Where the listening socket came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ListenerSource {
+    /// Bound at a filesystem path; removed on shutdown.
+    Path(PathBuf),
+    /// Linux abstract namespace name (without the leading `@`).
+    Abstract(String),
+    /// Adopted from socket activation.
+    Activated(RawFd),
+}
+
+impl ListenerSource {
+    /// Filesystem path to remove on shutdown, if the server owns one.
+    pub fn owned_path(&self) -> Option<&Path> {
+        match self {
+            Self::Path(path) => Some(path),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for ListenerSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Path(path) => write!(f, "{}", path.display()),
+            Self::Abstract(name) => write!(f, "@{}", name),
+            Self::Activated(fd) => write!(f, "activated fd {}", fd),
+        }
+    }
+}
+
+/// Descriptor passed by socket activation, if any.
+///
+/// Takes the variables explicitly so the check is testable; use
+/// [`activated_fd_from_env`] at runtime.
+pub fn activated_fd(listen_pid: Option<&str>, listen_fds: Option<&str>, pid: u32) -> Option<RawFd> {
+    let listen_pid: u32 = listen_pid?.trim().parse().ok()?;
+    let count: u32 = listen_fds?.trim().parse().ok()?;
+    (listen_pid == pid && count >= 1).then_some(LISTEN_FDS_START)
+}
+
+/// Descriptor passed by a supervisor, if `supervisor_pid` is our parent.
+/// A grandchild inherits the variable but not the parent, so it never
+/// adopts fd 3 by mistake.
+pub fn supervised_fd(supervisor_pid: Option<&str>, parent_pid: u32) -> Option<RawFd> {
+    let supervisor_pid: u32 = supervisor_pid?.trim().parse().ok()?;
+    (supervisor_pid == parent_pid).then_some(LISTEN_FDS_START)
+}
+
+/// Descriptor passed by socket activation or a supervisor, if any.
+pub fn activated_fd_from_env() -> Option<RawFd> {
+    let pid = std::env::var("LISTEN_PID").ok();
+    let fds = std::env::var("LISTEN_FDS").ok();
+    activated_fd(pid.as_deref(), fds.as_deref(), std::process::id()).or_else(|| {
+        let supervisor = std::env::var(SUPERVISOR_PID_ENV).ok();
+        // SAFETY: getppid cannot fail
+        supervised_fd(supervisor.as_deref(), unsafe { libc::getppid() } as u32)
+    })
+}
+
+/// Open the listener: adopt an activated socket, bind an abstract name
+/// (`@name`), or bind `socket_path` with `permissions` applied.
+pub fn open(
+    socket_path: &str,
+    permissions: &SocketPermissions,
+) -> Result<(UnixListener, ListenerSource), ServerError> {
+    if let Some(fd) = activated_fd_from_env() {
+        // SAFETY: systemd or the supervisor hands over ownership of fd 3
+        let listener = unsafe { adopt(fd)? };
+        return Ok((listener, ListenerSource::Activated(fd)));
+    }
+    if let Some(name) = socket_path.strip_prefix('@') {
+        return Ok((bind_abstract(name)?, ListenerSource::Abstract(name.to_string())));
+    }
+    let path = PathBuf::from(socket_path);
+    let listener = socket_perms::bind(&path, permissions)?;
+    Ok((listener, ListenerSource::Path(path)))
+}
+
+/// Wrap an inherited listening descriptor.
+///
+/// # Safety
+/// `fd` must be an open descriptor owned by nobody else in this process.
+pub unsafe fn adopt(fd: RawFd) -> Result<UnixListener, ServerError> {
+    let mut stat: libc::stat = std::mem::zeroed();
+    if libc::fstat(fd, &mut stat) != 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+    if stat.st_mode & libc::S_IFMT != libc::S_IFSOCK {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("activated fd {} is not a socket", fd),
+        )
+        .into());
+    }
+    // Don't leak the listener into child processes
+    libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC);
+
+    let listener = std::os::unix::net::UnixListener::from_raw_fd(fd);
+    listener.set_nonblocking(true)?;
+    Ok(UnixListener::from_std(listener)?)
+}
+
+/// Connect to a server path, honoring the `@name` abstract form.
+pub async fn connect(socket_path: &str) -> std::io::Result<tokio::net::UnixStream> {
+    match socket_path.strip_prefix('@') {
+        Some(name) => connect_abstract(name),
+        None => tokio::net::UnixStream::connect(socket_path).await,
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn connect_abstract(name: &str) -> std::io::Result<tokio::net::UnixStream> {
+    #[cfg(target_os = "android")]
+    use std::os::android::net::SocketAddrExt;
+    #[cfg(target_os = "linux")]
+    use std::os::linux::net::SocketAddrExt;
+
+    let addr = std::os::unix::net::SocketAddr::from_abstract_name(name.as_bytes())?;
+    let stream = std::os::unix::net::UnixStream::connect_addr(&addr)?;
+    stream.set_nonblocking(true)?;
+    tokio::net::UnixStream::from_std(stream)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+fn connect_abstract(name: &str) -> std::io::Result<tokio::net::UnixStream> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        format!("abstract socket @{} requires Linux", name),
+    ))
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn bind_abstract(name: &str) -> Result<UnixListener, ServerError> {
+    #[cfg(target_os = "android")]
+    use std::os::android::net::SocketAddrExt;
+    #[cfg(target_os = "linux")]
+    use std::os::linux::net::SocketAddrExt;
+
+    let addr = std::os::unix::net::SocketAddr::from_abstract_name(name.as_bytes())?;
+    let listener = std::os::unix::net::UnixListener::bind_addr(&addr)?;
+    listener.set_nonblocking(true)?;
+    Ok(UnixListener::from_std(listener)?)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+fn bind_abstract(name: &str) -> Result<UnixListener, ServerError> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        format!("abstract socket @{} requires Linux", name),
+    )
+    .into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::io::{AsRawFd, IntoRawFd};
+
+    #[test]
+    fn test_activated_fd_requires_matching_pid() {
+        assert_eq!(activated_fd(Some("42"), Some("1"), 42), Some(LISTEN_FDS_START));
+        assert_eq!(activated_fd(Some("41"), Some("1"), 42), None);
+        assert_eq!(activated_fd(Some("42"), Some("0"), 42), None);
+        assert_eq!(activated_fd(None, Some("1"), 42), None);
+        assert_eq!(activated_fd(Some("x"), Some("1"), 42), None);
+    }
+
+    #[test]
+    fn test_supervised_fd_requires_parent() {
+        assert_eq!(supervised_fd(Some("7"), 7), Some(LISTEN_FDS_START));
+        assert_eq!(supervised_fd(Some("7"), 8), None);
+        assert_eq!(supervised_fd(None, 7), None);
+    }
+
+    #[tokio::test]
+    async fn test_adopt_inherited_listener() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("activated.sock");
+        let fd = std::os::unix::net::UnixListener::bind(&path).unwrap().into_raw_fd();
+
+        let listener = unsafe { adopt(fd).unwrap() };
+        let _client = tokio::net::UnixStream::connect(&path).await.unwrap();
+        assert!(listener.accept().await.is_ok());
+    }
+
+    #[test]
+    fn test_adopt_rejects_non_socket() {
+        let file = tempfile::tempfile().unwrap();
+        let err = unsafe { adopt(file.as_raw_fd()) };
+        assert!(err.is_err());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_abstract_socket_has_no_file() {
+        let name = format!("gg-core-test-{}", std::process::id());
+        let listener = bind_abstract(&name).unwrap();
+        let client = connect(&format!("@{}", name)).await.unwrap();
+        assert!(listener.accept().await.is_ok());
+        drop(client);
+        assert!(!Path::new(&name).exists());
+    }
+}
@@ -1,27 +1,52 @@
 //! IPC module for CORE Runtime.
 //!
-//! Handles named pipe/Unix socket communication with authenticated callers.
+//! Handles named pipe/Unix socket communication with authenticated callers,
+//! plus an opt-in loopback TCP port with the same framing.
 //! This is the ONLY external interface - no HTTP/REST/WebSocket allowed.
 
+pub mod affinity;
 mod auth;
 mod connections;
+pub mod deprecation;
 pub mod encoding;
+#[cfg(unix)]
+pub mod instance_lock;
 mod handler;
 mod health_handler;
+pub mod limits;
+mod model_admin;
+pub mod probe;
+#[cfg(unix)]
+pub mod listener;
+mod snapshot_handler;
 pub mod protocol;
 pub mod server;
+pub mod socket_perms;
 mod stream_bridge;
+pub mod tcp;
 
-pub use auth::{AuthError, SessionAuth, SessionToken};
+pub use affinity::{AffinityConfig, AffinityTracker};
+pub use auth::{AuthError, SessionAuth, SessionRole, SessionToken};
 pub use connections::{ConnectionConfig, ConnectionGuard, ConnectionPool, OwnedConnectionGuard};
+pub use deprecation::{Deprecation, DeprecationConfig, DeprecationError, DeprecationTracker};
 pub use encoding::{get_encoder, TokenEncoder, V1Encoder, V2Encoder};
 pub use handler::{HandlerError, IpcHandler, IpcHandlerConfig, StreamSender};
+pub use limits::{LimitError, OutputBudget, RequestLimits};
+#[cfg(unix)]
+pub use instance_lock::{InstanceLock, InstanceLockError};
+pub use model_admin::ModelAdminHandler;
+pub use probe::{ProbeConfig, ProbeResponder, ProbeResponse};
+pub use snapshot_handler::SnapshotHandler;
+pub use socket_perms::{SocketPermError, SocketPermIssue, SocketPermissions};
 pub use stream_bridge::IpcStreamBridge;
+pub use tcp::{TcpBind, TcpEndpoint, TcpTransportError};
 pub use protocol::{
     decode_message, decode_message_binary, encode_message, encode_message_binary,
-    HealthCheckResponse, HealthCheckType, InferenceRequest, InferenceResponse, IpcMessage,
-    ModelInfo, ModelsListResponse, ProtocolError, ProtocolVersion, RequestId, StreamChunk,
-    WarmupRequest, WarmupResponse,
+    ConversationTurnRequest, HealthCheckResponse, HealthCheckType, InferenceRequest, InferenceResponse, IpcMessage,
+    ModelAction, ModelAdminRequest, ModelAdminResponse, ModelInfo, ModelsListResponse, LegalHoldAction, PrivacyFlags, ProtocolError, ProtocolVersion, PurgeTarget, RequestId, RerankRequest, ResponseErrorCode,
+    RerankResponse, SnapshotAction,
+    SnapshotRequest, SnapshotResponse, StreamChunk, ToolResultRequest, WarmupRequest,
+    WarmupResponse,
 };
 // Re-export MetricsSnapshot for IPC consumers
 pub use crate::telemetry::MetricsSnapshot;
@@ -0,0 +1,175 @@
+//! Model load/unload request handling.
+//!
+//! Loads go through the same path validation and quarantine guard as the
+//! FFI loader, so IPC callers can only load files under `models/` or
+//! `tokenizers/`, and are refused during a maintenance window or when the
+//! model's provenance attestation does not satisfy the policy.
+//!
+//! With an inference engine attached, a model whose format has a backend
+//! in this build is also loaded into the engine, inside the quarantine
+//! guard, and served under its registry name. Other formats are only
+//! catalogued.
+
+use std::sync::Arc;
+
+use super::protocol::{ModelAction, ModelAdminRequest, ModelAdminResponse};
+use crate::engine::InferenceEngine;
+use crate::maintenance::MaintenanceScheduler;
+use crate::models::{LoadError, ModelHandle, ModelLoader, ModelRegistry};
+
+/// Handles model management requests against the live model registry.
+pub struct ModelAdminHandler {
+    loader: ModelLoader,
+    model_registry: Arc<ModelRegistry>,
+    maintenance: Arc<MaintenanceScheduler>,
+    engine: Option<Arc<InferenceEngine>>,
+}
+
+impl ModelAdminHandler {
+    pub fn new(
+        loader: ModelLoader,
+        model_registry: Arc<ModelRegistry>,
+        maintenance: Arc<MaintenanceScheduler>,
+    ) -> Self {
+        Self { loader, model_registry, maintenance, engine: None }
+    }
+
+    /// Serve loaded models from `engine`.
+    pub fn with_engine(mut self, engine: Arc<InferenceEngine>) -> Self {
+        self.engine = Some(engine);
+        self
+    }
+
+    pub async fn handle(&self, request: ModelAdminRequest) -> ModelAdminResponse {
+        let ModelAdminRequest { action, model } = request;
+        let result = match action {
+            ModelAction::Load => self.load(&model).await,
+            ModelAction::Unload => self.unload(&model).await,
+        };
+        match result {
+            Ok(handle_id) => {
+                tracing::info!(action = ?action, model = %model, handle_id, "Model admin request applied");
+                ModelAdminResponse::success(action, model, handle_id)
+            }
+            Err(e) => ModelAdminResponse::error(action, model, e),
+        }
+    }
+
+    async fn load(&self, relative_path: &str) -> Result<u64, String> {
+        self.maintenance.check_model_load().map_err(|e| e.to_string())?;
+        let model_path = self.loader.validate_path(relative_path).map_err(|e| e.to_string())?;
+        let attestation = self.loader.attest(&model_path).map_err(|e| e.to_string())?;
+        let (metadata, format, model) = self
+            .loader
+            .load_guarded(&model_path, |path, format| {
+                let metadata = self.loader.load_metadata(&model_path)?;
+                let model = match &self.engine {
+                    Some(_) if format.is_compiled() => Some(
+                        crate::engine::load_model(format, path, &metadata.name)
+                            .map_err(|e| LoadError::Backend(e.to_string()))?,
+                    ),
+                    _ => None,
+                };
+                Ok((metadata, format, model))
+            })
+            .map_err(|e| e.to_string())?;
+        let name = metadata.name.clone();
+        let memory_bytes = model.as_ref().map_or(0, |m| m.memory_usage());
+        let handle = self
+            .model_registry
+            .register_with_format(metadata, memory_bytes, format.as_str().to_string())
+            .await;
+        if let Some(attestation) = attestation {
+            self.model_registry.set_attestation(handle, attestation).await;
+        }
+        self.model_registry.set_path(handle, model_path.as_path().to_path_buf()).await;
+        if let (Some(engine), Some(model)) = (&self.engine, model) {
+            engine.register_model(name, handle, model).await;
+        }
+        Ok(handle.id())
+    }
+
+    async fn unload(&self, name: &str) -> Result<u64, String> {
+        let handle_id = self
+            .model_registry
+            .list_models()
+            .await
+            .into_iter()
+            .find(|m| m.name == name)
+            .map(|m| m.handle_id)
+            .ok_or_else(|| format!("Model not loaded: {}", name))?;
+        self.model_registry
+            .unregister(ModelHandle::new(handle_id))
+            .await
+            .ok_or_else(|| format!("Model not loaded: {}", name))?;
+        if let Some(engine) = &self.engine {
+            engine.unregister_model(name).await;
+        }
+        Ok(handle_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maintenance::MaintenanceConfig;
+    use crate::models::ModelMetadata;
+
+    fn handler(base: &std::path::Path, registry: Arc<ModelRegistry>) -> ModelAdminHandler {
+        ModelAdminHandler::new(
+            ModelLoader::new(base.to_path_buf()),
+            registry,
+            Arc::new(MaintenanceScheduler::new(MaintenanceConfig::default())),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_unload_by_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = Arc::new(ModelRegistry::new());
+        let metadata = ModelMetadata { name: "phi".into(), size_bytes: 10 };
+        let handle = registry.register(metadata, 1024).await;
+
+        let handler = handler(dir.path(), Arc::clone(&registry));
+        let response = handler
+            .handle(ModelAdminRequest { action: ModelAction::Unload, model: "phi".into() })
+            .await;
+
+        assert!(response.success);
+        assert_eq!(response.handle_id, Some(handle.id()));
+        assert_eq!(registry.count().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_load_outside_models_dir_refused() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("secret.gguf"), b"GGUF").unwrap();
+        let handler = handler(dir.path(), Arc::new(ModelRegistry::new()));
+
+        let response = handler
+            .handle(ModelAdminRequest { action: ModelAction::Load, model: "secret.gguf".into() })
+            .await;
+
+        assert!(!response.success);
+        assert!(response.error.unwrap().contains("not allowed"));
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_load_mock_serves_from_engine() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("models")).unwrap();
+        std::fs::write(dir.path().join("models/chaos.mock"), r#"{"responses": ["pong"]}"#).unwrap();
+        let engine = Arc::new(InferenceEngine::new(4096));
+        let handler = handler(dir.path(), Arc::new(ModelRegistry::new())).with_engine(Arc::clone(&engine));
+
+        let load = ModelAdminRequest { action: ModelAction::Load, model: "models/chaos.mock".into() };
+        assert!(handler.handle(load).await.success);
+        let result = engine.run("chaos", "ping", &Default::default()).await.unwrap();
+        assert_eq!(result.output, "pong");
+
+        let unload = ModelAdminRequest { action: ModelAction::Unload, model: "chaos".into() };
+        assert!(handler.handle(unload).await.success);
+        assert!(!engine.has_model("chaos").await);
+    }
+}
@@ -0,0 +1,180 @@
+//! Lightweight liveness probe.
+//!
+//! `Probe` messages are answered by the server loop itself, before
+//! authentication, the handler and the request queue, so an overloaded
+//! runtime still answers its orchestrator and is not restarted for being
+//! busy. Answers come from cached state refreshed at most once per
+//! `refresh` interval, and probes are rate limited so they cannot become a
+//! cheap way to load the server. While the connection limit is reached a
+//! few extra connections are still accepted to answer a single probe.
+
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use super::protocol::IpcMessage;
+use crate::health::HealthChecker;
+use crate::shutdown::ShutdownCoordinator;
+
+/// Probe fast path configuration.
+#[derive(Debug, Clone)]
+pub struct ProbeConfig {
+    /// Probes answered per second; excess probes get a 429.
+    pub max_per_sec: u32,
+    /// Cached liveness is recomputed when older than this.
+    pub refresh: Duration,
+    /// Connections accepted beyond the connection limit to answer a probe.
+    pub overflow_connections: usize,
+    /// How long an overflow connection may take to send its probe.
+    pub overflow_read_timeout: Duration,
+}
+
+impl Default for ProbeConfig {
+    fn default() -> Self {
+        Self {
+            max_per_sec: 20,
+            refresh: Duration::from_secs(1),
+            overflow_connections: 4,
+            overflow_read_timeout: Duration::from_secs(1),
+        }
+    }
+}
+
+/// Answer to a `Probe`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProbeResponse {
+    pub alive: bool,
+    /// Shutdown has begun; the process is alive but draining.
+    pub draining: bool,
+    pub uptime_secs: u64,
+    /// Age of the cached liveness state.
+    pub state_age_ms: u64,
+}
+
+/// Answers probes from cached state under a rate limit.
+pub struct ProbeResponder {
+    config: ProbeConfig,
+    health: Arc<HealthChecker>,
+    shutdown: Arc<ShutdownCoordinator>,
+    started: Instant,
+    alive: AtomicBool,
+    /// Milliseconds since `started` of the last refresh; 0 = never.
+    refreshed_ms: AtomicU64,
+    /// Current rate limit window (seconds since `started`) and its count.
+    window: AtomicU64,
+    in_window: AtomicU32,
+    overflow: Arc<AtomicUsize>,
+}
+
+impl ProbeResponder {
+    pub fn new(config: ProbeConfig, health: Arc<HealthChecker>, shutdown: Arc<ShutdownCoordinator>) -> Self {
+        Self {
+            config,
+            health,
+            shutdown,
+            started: Instant::now(),
+            alive: AtomicBool::new(false),
+            refreshed_ms: AtomicU64::new(0),
+            window: AtomicU64::new(0),
+            in_window: AtomicU32::new(0),
+            overflow: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    pub fn config(&self) -> &ProbeConfig {
+        &self.config
+    }
+
+    /// Answer one probe: a `ProbeResponse`, or a 429 once the rate limit
+    /// is spent.
+    pub fn answer(&self) -> IpcMessage {
+        if !self.admit() {
+            metrics::counter!("core_probe_total", "result" => "limited").increment(1);
+            return IpcMessage::Error { code: 429, message: "Probe rate limit exceeded".into() };
+        }
+        metrics::counter!("core_probe_total", "result" => "answered").increment(1);
+        IpcMessage::ProbeResponse(self.state())
+    }
+
+    fn now_ms(&self) -> u64 {
+        // Never 0, which marks the cache as empty
+        self.started.elapsed().as_millis() as u64 + 1
+    }
+
+    fn admit(&self) -> bool {
+        let second = self.started.elapsed().as_secs();
+        if self.window.swap(second, Ordering::AcqRel) != second {
+            self.in_window.store(0, Ordering::Release);
+        }
+        let max = self.config.max_per_sec;
+        self.in_window
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| (n < max).then_some(n + 1))
+            .is_ok()
+    }
+
+    fn state(&self) -> ProbeResponse {
+        let now = self.now_ms();
+        let refresh = self.config.refresh.as_millis() as u64;
+        let mut refreshed = self.refreshed_ms.load(Ordering::Acquire);
+        if refreshed == 0 || now.saturating_sub(refreshed) >= refresh {
+            self.alive.store(self.health.is_alive(), Ordering::Release);
+            self.refreshed_ms.store(now, Ordering::Release);
+            refreshed = now;
+        }
+        ProbeResponse {
+            alive: self.alive.load(Ordering::Acquire),
+            draining: self.shutdown.stopping().is_cancelled(),
+            uptime_secs: self.started.elapsed().as_secs(),
+            state_age_ms: now.saturating_sub(refreshed),
+        }
+    }
+
+    /// Reserve one of the connections allowed past the connection limit.
+    pub fn try_overflow(&self) -> Option<OverflowGuard> {
+        let max = self.config.overflow_connections;
+        self.overflow
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| (n < max).then_some(n + 1))
+            .ok()
+            .map(|_| OverflowGuard { count: Arc::clone(&self.overflow) })
+    }
+}
+
+/// RAII guard for one overflow probe connection.
+pub struct OverflowGuard {
+    count: Arc<AtomicUsize>,
+}
+
+impl Drop for OverflowGuard {
+    fn drop(&mut self) {
+        self.count.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn responder(config: ProbeConfig) -> ProbeResponder {
+        ProbeResponder::new(config, Arc::new(HealthChecker::default()), Arc::new(ShutdownCoordinator::new()))
+    }
+
+    #[test]
+    fn test_answers_from_cache_within_rate_limit() {
+        let probe = responder(ProbeConfig { max_per_sec: 2, refresh: Duration::from_secs(60), ..Default::default() });
+        let IpcMessage::ProbeResponse(first) = probe.answer() else { panic!("expected probe response") };
+        assert!(first.alive && !first.draining);
+        assert!(matches!(probe.answer(), IpcMessage::ProbeResponse(_)));
+        assert!(matches!(probe.answer(), IpcMessage::Error { code: 429, .. }));
+    }
+
+    #[test]
+    fn test_overflow_connections_bounded() {
+        let probe = responder(ProbeConfig { overflow_connections: 1, ..Default::default() });
+        let guard = probe.try_overflow().unwrap();
+        assert!(probe.try_overflow().is_none());
+        drop(guard);
+        assert!(probe.try_overflow().is_some());
+    }
+}
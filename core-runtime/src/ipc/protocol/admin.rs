@@ -0,0 +1,67 @@
+//! Admin requests: snapshots, purges and legal holds.
+
+use serde::{Deserialize, Serialize};
+
+/// Snapshot operation requested over IPC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SnapshotAction {
+    Create,
+    Restore,
+}
+
+/// Snapshot request. `name` selects an archive in the runtime's snapshot
+/// directory; paths are not accepted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotRequest {
+    pub action: SnapshotAction,
+    pub name: String,
+}
+
+/// Snapshot response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotResponse {
+    pub action: SnapshotAction,
+    pub name: String,
+    pub success: bool,
+    /// Models written (create) or re-registered (restore).
+    pub models: usize,
+    pub error: Option<String>,
+}
+
+impl SnapshotResponse {
+    pub fn success(action: SnapshotAction, name: String, models: usize) -> Self {
+        Self { action, name, success: true, models, error: None }
+    }
+
+    pub fn error(action: SnapshotAction, name: String, error: String) -> Self {
+        Self { action, name, success: false, models: 0, error: Some(error) }
+    }
+}
+
+/// What a `PurgeRequest` erases.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PurgeTarget {
+    /// Tenant label as it appears in usage exports.
+    Tenant(String),
+    /// Raw session id; purges the tenant it maps to.
+    Session(String),
+    Conversation(String),
+}
+
+/// What a `LegalHoldRequest` does.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LegalHoldAction {
+    List,
+    /// Capture the tenant's inference in full for `days` (default: the
+    /// configured maximum); renews an existing hold.
+    Activate {
+        tenant: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        days: Option<u32>,
+        reason: String,
+    },
+    Release { tenant: String },
+}
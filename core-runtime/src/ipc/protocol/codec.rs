@@ -0,0 +1,171 @@
+//! Message encoding with size limits.
+//!
+//! # Security
+//! - Message size limits prevent memory exhaustion attacks
+//! - Response size limits prevent resource exhaustion
+
+use thiserror::Error;
+
+use super::IpcMessage;
+
+#[derive(Error, Debug)]
+pub enum ProtocolError {
+    #[error("Invalid message format: {0}")]
+    InvalidFormat(String),
+
+    #[error("Missing required field: {0}")]
+    MissingField(String),
+
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("Message too large: {size} bytes (max {max})")]
+    MessageTooLarge { size: usize, max: usize },
+
+    #[error("Invalid request ID: expected a ULID, UUID or non-negative integer")]
+    InvalidRequestId,
+}
+
+const MAX_MESSAGE_SIZE: usize = 16 * 1024 * 1024; // 16 MB
+/// Maximum response size to prevent memory exhaustion
+const MAX_RESPONSE_SIZE: usize = 16 * 1024 * 1024; // 16 MB
+
+/// Encode message to JSON bytes with size limit enforcement.
+///
+/// # Security
+/// Enforces maximum message size to prevent memory exhaustion attacks.
+pub fn encode_message(message: &IpcMessage) -> Result<Vec<u8>, ProtocolError> {
+    let bytes = serde_json::to_vec(message)?;
+    if bytes.len() > MAX_MESSAGE_SIZE {
+        return Err(ProtocolError::MessageTooLarge {
+            size: bytes.len(),
+            max: MAX_MESSAGE_SIZE,
+        });
+    }
+    Ok(bytes)
+}
+
+/// Encode response message with response-specific size limit.
+///
+/// # Security
+/// Enforces maximum response size to prevent resource exhaustion.
+pub fn encode_response(message: &IpcMessage) -> Result<Vec<u8>, ProtocolError> {
+    let bytes = serde_json::to_vec(message)?;
+    if bytes.len() > MAX_RESPONSE_SIZE {
+        // For oversized responses, return an error response instead
+        let error_response = IpcMessage::Error {
+            code: 413, // HTTP 413 Payload Too Large
+            message: format!(
+                "Response too large: {} bytes (max {})",
+                bytes.len(),
+                MAX_RESPONSE_SIZE
+            ),
+        };
+        return encode_message(&error_response);
+    }
+    Ok(bytes)
+}
+
+/// Decode message from JSON bytes with size limit enforcement.
+///
+/// # Security
+/// Enforces maximum message size to prevent memory exhaustion attacks.
+/// Size check happens BEFORE parsing to prevent allocation attacks.
+pub fn decode_message(bytes: &[u8]) -> Result<IpcMessage, ProtocolError> {
+    // SECURITY: Check size BEFORE parsing to prevent memory exhaustion
+    if bytes.len() > MAX_MESSAGE_SIZE {
+        return Err(ProtocolError::MessageTooLarge {
+            size: bytes.len(),
+            max: MAX_MESSAGE_SIZE,
+        });
+    }
+    Ok(serde_json::from_slice(bytes)?)
+}
+
+/// Encode message to bytes for IPC transport.
+///
+/// Uses JSON encoding; bincode is incompatible with the internally-tagged
+/// `IpcMessage` enum (`#[serde(tag = "type")]` requires `deserialize_any`
+/// which bincode v1 does not implement).
+pub fn encode_message_binary(message: &IpcMessage) -> Result<Vec<u8>, ProtocolError> {
+    encode_message(message)
+}
+
+/// Decode message from IPC transport bytes.
+///
+/// Uses JSON decoding; see `encode_message_binary` for rationale.
+pub fn decode_message_binary(bytes: &[u8]) -> Result<IpcMessage, ProtocolError> {
+    decode_message(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc::protocol::HealthCheckType;
+
+    #[test]
+    fn test_encode_decode_message() {
+        let msg = IpcMessage::HealthCheck {
+            check_type: HealthCheckType::Liveness,
+        };
+        let encoded = encode_message(&msg).unwrap();
+        let decoded = decode_message(&encoded).unwrap();
+        assert!(matches!(
+            decoded,
+            IpcMessage::HealthCheck {
+                check_type: HealthCheckType::Liveness
+            }
+        ));
+    }
+
+    #[test]
+    fn test_encode_response_within_limit() {
+        let msg = IpcMessage::Error {
+            code: 500,
+            message: "test".to_string(),
+        };
+        let result = encode_response(&msg).unwrap();
+        assert!(!result.is_empty());
+    }
+
+    #[test]
+    fn test_decode_message_too_large() {
+        // Create a message that exceeds the size limit
+        let large_data = vec![0u8; MAX_MESSAGE_SIZE + 1];
+        let result = decode_message(&large_data);
+        assert!(matches!(result, Err(ProtocolError::MessageTooLarge { .. })));
+    }
+
+    #[test]
+    fn test_encode_message_binary_roundtrip() {
+        let msg = IpcMessage::HealthCheck {
+            check_type: HealthCheckType::Readiness,
+        };
+        let encoded = encode_message_binary(&msg).unwrap();
+        let decoded = decode_message_binary(&encoded).unwrap();
+        assert!(matches!(
+            decoded,
+            IpcMessage::HealthCheck {
+                check_type: HealthCheckType::Readiness
+            }
+        ));
+    }
+
+    #[test]
+    fn test_decode_message_binary_too_large() {
+        let large_data = vec![0u8; MAX_MESSAGE_SIZE + 1];
+        let result = decode_message_binary(&large_data);
+        assert!(matches!(result, Err(ProtocolError::MessageTooLarge { .. })));
+    }
+
+    #[test]
+    fn test_protocol_error_display() {
+        let err = ProtocolError::MessageTooLarge { size: 100, max: 50 };
+        let msg = err.to_string();
+        assert!(msg.contains("100"));
+        assert!(msg.contains("50"));
+
+        let err = ProtocolError::MissingField("test".to_string());
+        assert!(err.to_string().contains("test"));
+    }
+}
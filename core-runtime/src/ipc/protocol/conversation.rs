@@ -0,0 +1,76 @@
+//! Conversation turns and tool results.
+
+use serde::{Deserialize, Serialize};
+
+use crate::engine::tools::ToolDefinition;
+use crate::engine::InferenceParams;
+
+use super::RequestId;
+
+/// One user turn in a server-held conversation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversationTurnRequest {
+    pub conversation_id: String,
+    /// Generated by the server when omitted.
+    #[serde(default)]
+    pub request_id: RequestId,
+    /// New user message only; history is held server-side.
+    pub message: String,
+    #[serde(default)]
+    pub parameters: InferenceParams,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preset: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<ToolDefinition>,
+}
+
+/// Result of a tool call, returned into a conversation to continue generation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolResultRequest {
+    pub conversation_id: String,
+    /// Generated by the server when omitted.
+    #[serde(default)]
+    pub request_id: RequestId,
+    pub tool_call_id: String,
+    pub content: String,
+    #[serde(default)]
+    pub parameters: InferenceParams,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preset: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<ToolDefinition>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc::protocol::{decode_message, encode_message, InferenceResponse, IpcMessage};
+
+    #[test]
+    fn test_conversation_turn_defaults_parameters() {
+        let json = r#"{"type":"conversation_turn","conversation_id":"c1","request_id":7,"message":"Hi"}"#;
+        match decode_message(json.as_bytes()).unwrap() {
+            IpcMessage::ConversationTurn(turn) => {
+                assert_eq!(turn.conversation_id, "c1");
+                assert_eq!(turn.parameters.max_tokens, InferenceParams::default().max_tokens);
+            }
+            other => panic!("unexpected message: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_tool_result_roundtrip() {
+        let json = r#"{"type":"tool_result","conversation_id":"c1","request_id":8,"tool_call_id":"call-7","content":"12C","tools":[{"name":"get_weather"}]}"#;
+        match decode_message(json.as_bytes()).unwrap() {
+            IpcMessage::ToolResult(result) => {
+                assert_eq!(result.tool_call_id, "call-7");
+                assert_eq!(result.tools[0].name, "get_weather");
+            }
+            other => panic!("unexpected message: {other:?}"),
+        }
+
+        let response = InferenceResponse::success(RequestId(8), String::new(), 0, true);
+        let encoded = String::from_utf8(encode_message(&IpcMessage::InferenceResponse(response)).unwrap()).unwrap();
+        assert!(!encoded.contains("tool_calls"));
+    }
+}
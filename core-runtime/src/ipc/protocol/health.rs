@@ -0,0 +1,21 @@
+//! Health checks.
+
+use serde::{Deserialize, Serialize};
+
+use crate::health::HealthReport;
+
+/// Health check request types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HealthCheckType {
+    Liveness,
+    Readiness,
+    Full,
+}
+
+/// Health check response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthCheckResponse {
+    pub check_type: HealthCheckType,
+    pub ok: bool,
+    pub report: Option<HealthReport>,
+}
@@ -0,0 +1,227 @@
+//! The message envelope: every request and response the IPC protocol carries.
+
+use serde::{Deserialize, Serialize};
+
+use crate::conversations::ConversationSummary;
+use crate::ipc::deprecation::Deprecation;
+use crate::ipc::probe::ProbeResponse;
+use crate::features::FeatureState;
+use crate::security::{AuditEvent, LegalHold, PurgeReceipt};
+use crate::telemetry::{ExportableSpan, HeapReport, MetricsSnapshot, SloStatus, UsageExport};
+
+use super::{
+    ConversationTurnRequest, HealthCheckResponse, HealthCheckType, InferenceRequest,
+    InferenceResponse, LegalHoldAction, ModelAdminRequest, ModelAdminResponse, ModelsListResponse, ProtocolVersion,
+    PurgeTarget, RequestId, RerankRequest, RerankResponse, SnapshotRequest, SnapshotResponse, StreamChunk,
+    ToolResultRequest, WarmupRequest, WarmupResponse,
+};
+
+/// All possible IPC message types.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum IpcMessage {
+    #[serde(rename = "handshake")]
+    Handshake {
+        token: String,
+        /// Optional protocol version request. Defaults to V1 if not specified.
+        #[serde(default)]
+        protocol_version: Option<ProtocolVersion>,
+    },
+
+    #[serde(rename = "handshake_ack")]
+    HandshakeAck {
+        session_id: String,
+        /// Negotiated protocol version for this session.
+        #[serde(default)]
+        protocol_version: ProtocolVersion,
+        /// Message types, fields and protocol versions slated for removal.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        deprecations: Vec<Deprecation>,
+    },
+
+    #[serde(rename = "inference_request")]
+    InferenceRequest(InferenceRequest),
+
+    #[serde(rename = "inference_response")]
+    InferenceResponse(InferenceResponse),
+
+    #[serde(rename = "stream_chunk")]
+    StreamChunk(StreamChunk),
+
+    #[serde(rename = "health_check")]
+    HealthCheck { check_type: HealthCheckType },
+
+    /// Liveness probe answered by the server loop ahead of auth and
+    /// queueing; see `ipc::probe`.
+    #[serde(rename = "probe")]
+    Probe,
+
+    #[serde(rename = "probe_response")]
+    ProbeResponse(ProbeResponse),
+
+    #[serde(rename = "health_response")]
+    HealthResponse(HealthCheckResponse),
+
+    #[serde(rename = "metrics_request")]
+    MetricsRequest,
+
+    #[serde(rename = "metrics_response")]
+    MetricsResponse(MetricsSnapshot),
+
+    #[serde(rename = "prometheus_request")]
+    PrometheusMetricsRequest,
+
+    #[serde(rename = "prometheus_response")]
+    PrometheusMetricsResponse { text: String },
+
+    #[serde(rename = "slo_status_request")]
+    SloStatusRequest,
+
+    #[serde(rename = "slo_status_response")]
+    SloStatusResponse { objectives: Vec<SloStatus> },
+
+    /// Capture a CPU profile of the server (admin).
+    #[serde(rename = "profile_request")]
+    ProfileRequest { seconds: u32 },
+
+    /// Gzipped pprof protobuf, base64-encoded.
+    #[serde(rename = "profile_response")]
+    ProfileResponse { profile: String },
+
+    /// Top heap allocation sites by tag (admin).
+    #[serde(rename = "heap_profile_request")]
+    HeapProfileRequest { top: usize },
+
+    #[serde(rename = "heap_profile_response")]
+    HeapProfileResponse(HeapReport),
+
+    /// Per-tenant usage aggregates for external export (admin). Noised when
+    /// differential privacy is configured.
+    #[serde(rename = "usage_export_request")]
+    UsageExportRequest,
+
+    #[serde(rename = "usage_export_response")]
+    UsageExportResponse(UsageExport),
+
+    #[serde(rename = "spans_request")]
+    SpansRequest { max_count: usize },
+
+    #[serde(rename = "spans_response")]
+    SpansResponse { spans: Vec<ExportableSpan> },
+
+    #[serde(rename = "cancel_request")]
+    CancelRequest { request_id: RequestId },
+
+    #[serde(rename = "cancel_response")]
+    CancelResponse {
+        request_id: RequestId,
+        cancelled: bool,
+    },
+
+    #[serde(rename = "warmup_request")]
+    WarmupRequest(WarmupRequest),
+
+    #[serde(rename = "warmup_response")]
+    WarmupResponse(WarmupResponse),
+
+    #[serde(rename = "models_request")]
+    ModelsRequest,
+
+    #[serde(rename = "models_response")]
+    ModelsResponse(ModelsListResponse),
+
+    #[serde(rename = "conversation_create")]
+    ConversationCreate { model_id: String },
+
+    #[serde(rename = "conversation_created")]
+    ConversationCreated { conversation_id: String },
+
+    /// Answered with `InferenceResponse`.
+    #[serde(rename = "conversation_turn")]
+    ConversationTurn(ConversationTurnRequest),
+
+    /// Answered with `InferenceResponse`.
+    #[serde(rename = "tool_result")]
+    ToolResult(ToolResultRequest),
+
+    #[serde(rename = "conversation_list")]
+    ConversationList,
+
+    #[serde(rename = "conversation_list_response")]
+    ConversationListResponse { conversations: Vec<ConversationSummary> },
+
+    #[serde(rename = "conversation_evict")]
+    ConversationEvict { conversation_id: String },
+
+    #[serde(rename = "conversation_evicted")]
+    ConversationEvicted { conversation_id: String, evicted: bool },
+
+    /// Ask whether sequences are resident on this replica (routing hint).
+    #[serde(rename = "affinity_query")]
+    AffinityQuery { keys: Vec<String> },
+
+    #[serde(rename = "affinity_response")]
+    AffinityResponse { resident: Vec<bool> },
+
+    #[serde(rename = "rerank_request")]
+    RerankRequest(RerankRequest),
+
+    #[serde(rename = "rerank_response")]
+    RerankResponse(RerankResponse),
+
+    #[serde(rename = "snapshot_request")]
+    SnapshotRequest(SnapshotRequest),
+
+    #[serde(rename = "snapshot_response")]
+    SnapshotResponse(SnapshotResponse),
+
+    /// Load or unload a model (admin).
+    #[serde(rename = "model_admin_request")]
+    ModelAdminRequest(ModelAdminRequest),
+
+    #[serde(rename = "model_admin_response")]
+    ModelAdminResponse(ModelAdminResponse),
+
+    /// Stop accepting new requests; in-flight work finishes (admin).
+    #[serde(rename = "drain_request")]
+    DrainRequest,
+
+    #[serde(rename = "drain_response")]
+    DrainResponse { in_flight: u32 },
+
+    /// Most recent audit events, oldest first (admin).
+    #[serde(rename = "audit_tail_request")]
+    AuditTailRequest { limit: usize },
+
+    #[serde(rename = "audit_tail_response")]
+    AuditTailResponse { events: Vec<AuditEvent> },
+
+    /// Erase server-held state for a subject (admin).
+    #[serde(rename = "purge_request")]
+    PurgeRequest { target: PurgeTarget },
+
+    #[serde(rename = "purge_response")]
+    PurgeResponse(PurgeReceipt),
+
+    /// Place, release or list tenant legal holds (admin); answered with
+    /// all holds.
+    #[serde(rename = "legal_hold_request")]
+    LegalHoldRequest { action: LegalHoldAction },
+
+    #[serde(rename = "legal_hold_response")]
+    LegalHoldResponse { holds: Vec<LegalHold> },
+
+    /// Runtime feature flags and their state.
+    #[serde(rename = "features_request")]
+    FeaturesRequest,
+
+    /// Flip a runtime feature flag (admin); answered with all flags.
+    #[serde(rename = "feature_toggle")]
+    FeatureToggle { name: String, enabled: bool },
+
+    #[serde(rename = "features_response")]
+    FeaturesResponse { features: Vec<FeatureState> },
+
+    #[serde(rename = "error")]
+    Error { code: u32, message: String },
+}
@@ -0,0 +1,95 @@
+//! Wire format and schema validation for IPC messages.
+//!
+//! # Security
+//! - Message size limits prevent memory exhaustion attacks
+//! - Protocol versioning enables backward-compatible security updates
+//! - Response size limits prevent resource exhaustion
+
+mod admin;
+mod codec;
+mod conversation;
+mod health;
+mod message;
+mod models;
+mod request;
+mod request_id;
+mod rerank;
+mod response;
+mod stream;
+mod version;
+
+pub use admin::{LegalHoldAction, PurgeTarget, SnapshotAction, SnapshotRequest, SnapshotResponse};
+pub use codec::{
+    decode_message, decode_message_binary, encode_message, encode_message_binary, encode_response, ProtocolError,
+};
+pub use conversation::{ConversationTurnRequest, ToolResultRequest};
+pub use health::{HealthCheckResponse, HealthCheckType};
+pub use message::IpcMessage;
+pub use models::{
+    ModelAction, ModelAdminRequest, ModelAdminResponse, ModelInfo, ModelsListResponse, WarmupRequest, WarmupResponse,
+};
+pub use request::{InferenceRequest, PrivacyFlags, MAX_IDEMPOTENCY_KEY_LEN};
+pub use request_id::RequestId;
+pub use rerank::{RerankRequest, RerankResponse};
+pub use response::{InferenceResponse, ResponseErrorCode};
+pub use stream::StreamChunk;
+pub use version::{ProtocolVersion, CURRENT_PROTOCOL_VERSION, MIN_PROTOCOL_VERSION};
+
+impl IpcMessage {
+    /// Give a request without a client-supplied ID a generated one, so
+    /// every request can be correlated across logs, spans and audit.
+    pub fn assign_request_id(&mut self) {
+        let id = match self {
+            Self::InferenceRequest(r) => &mut r.request_id,
+            Self::RerankRequest(r) => &mut r.request_id,
+            Self::ConversationTurn(r) => &mut r.request_id,
+            Self::ToolResult(r) => &mut r.request_id,
+            _ => return,
+        };
+        *id = id.or_generate();
+    }
+
+    /// Queries that only read runtime state; the only requests (besides a
+    /// handshake) accepted from observer sessions.
+    pub fn is_read_only(&self) -> bool {
+        matches!(
+            self,
+            Self::HealthCheck { .. }
+                | Self::Probe
+                | Self::MetricsRequest
+                | Self::SloStatusRequest
+                | Self::ModelsRequest
+                | Self::AffinityQuery { .. }
+                | Self::SpansRequest { .. }
+                | Self::FeaturesRequest
+        )
+    }
+
+    /// Requests that need an authenticated session; everything else is
+    /// either open to orchestrators or not a request at all.
+    pub fn requires_auth(&self) -> bool {
+        matches!(
+            self,
+            Self::InferenceRequest(_)
+                | Self::CancelRequest { .. }
+                | Self::ConversationCreate { .. }
+                | Self::ConversationTurn(_)
+                | Self::ToolResult(_)
+                | Self::ConversationList
+                | Self::ConversationEvict { .. }
+                | Self::RerankRequest(_)
+                | Self::ProfileRequest { .. }
+                | Self::HeapProfileRequest { .. }
+                | Self::UsageExportRequest
+                | Self::SpansRequest { .. }
+                | Self::SnapshotRequest(_)
+                | Self::ModelAdminRequest(_)
+                | Self::DrainRequest
+                | Self::AuditTailRequest { .. }
+                | Self::PurgeRequest { .. }
+                | Self::LegalHoldRequest { .. }
+                | Self::FeaturesRequest
+                | Self::FeatureToggle { .. }
+        )
+    }
+}
@@ -0,0 +1,148 @@
+//! Model listing, warmup and load/unload.
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::Attestation;
+use crate::scheduler::CircuitStatus;
+
+/// Model information for diagnostics.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelInfo {
+    /// Model handle ID
+    pub handle_id: u64,
+    /// Model name
+    pub name: String,
+    /// Model format (`ModelFormat::as_str`: gguf, safetensors, onnx, mock; "unknown" if undetected)
+    pub format: String,
+    /// Model size in bytes
+    pub size_bytes: u64,
+    /// Memory usage in bytes
+    pub memory_bytes: u64,
+    /// Current state
+    pub state: String,
+    /// Total requests processed
+    pub request_count: u64,
+    /// Average latency in milliseconds
+    pub avg_latency_ms: f64,
+    /// Average time to first streamed token in milliseconds
+    #[serde(default)]
+    pub avg_ttft_ms: f64,
+    /// Average gap between streamed tokens in milliseconds
+    #[serde(default)]
+    pub avg_inter_token_ms: f64,
+    /// p95 latency over the most recent requests in milliseconds
+    #[serde(default)]
+    pub recent_p95_latency_ms: f64,
+    /// Timestamp when loaded (ISO 8601)
+    pub loaded_at: String,
+    /// Verified provenance attestation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attestation: Option<Attestation>,
+}
+
+/// Models list response for diagnostics.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelsListResponse {
+    /// List of loaded models
+    pub models: Vec<ModelInfo>,
+    /// Total memory used by all models
+    pub total_memory_bytes: u64,
+    /// Circuit breaker state of every model that has served requests
+    #[serde(default)]
+    pub circuits: Vec<CircuitStatus>,
+}
+
+/// Warmup request to prime a model.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WarmupRequest {
+    pub model_id: String,
+    /// Number of tokens to generate (default: 1).
+    #[serde(default = "default_warmup_tokens")]
+    pub tokens: usize,
+}
+
+fn default_warmup_tokens() -> usize {
+    1
+}
+
+/// Warmup response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WarmupResponse {
+    pub model_id: String,
+    pub success: bool,
+    pub error: Option<String>,
+    pub elapsed_ms: u64,
+}
+
+impl WarmupResponse {
+    pub fn success(model_id: String, elapsed_ms: u64) -> Self {
+        Self {
+            model_id,
+            success: true,
+            error: None,
+            elapsed_ms,
+        }
+    }
+
+    pub fn error(model_id: String, error: String, elapsed_ms: u64) -> Self {
+        Self {
+            model_id,
+            success: false,
+            error: Some(error),
+            elapsed_ms,
+        }
+    }
+}
+
+/// Model management action requested over IPC (admin).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModelAction {
+    Load,
+    Unload,
+}
+
+/// Load or unload a model. `model` is a path relative to the runtime base
+/// path for loads (restricted to `models/` and `tokenizers/`) and a model
+/// name for unloads.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelAdminRequest {
+    pub action: ModelAction,
+    pub model: String,
+}
+
+/// Model management response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelAdminResponse {
+    pub action: ModelAction,
+    pub model: String,
+    pub success: bool,
+    /// Handle of the loaded (or unloaded) model.
+    pub handle_id: Option<u64>,
+    pub error: Option<String>,
+}
+
+impl ModelAdminResponse {
+    pub fn success(action: ModelAction, model: String, handle_id: u64) -> Self {
+        Self { action, model, success: true, handle_id: Some(handle_id), error: None }
+    }
+
+    pub fn error(action: ModelAction, model: String, error: String) -> Self {
+        Self { action, model, success: false, handle_id: None, error: Some(error) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_warmup_response() {
+        let success = WarmupResponse::success("model".to_string(), 100);
+        assert!(success.success);
+
+        let error = WarmupResponse::error("model".to_string(), "err".to_string(), 50);
+        assert!(!error.success);
+        assert!(error.error.is_some());
+    }
+}
@@ -0,0 +1,222 @@
+//! Inference requests and their validation.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::engine::tools::ToolDefinition;
+use crate::engine::context_docs::ContextDocument;
+use crate::engine::InferenceParams;
+use crate::scheduler::Priority;
+use crate::security::ImagePart;
+
+use super::{ProtocolError, RequestId};
+
+/// Per-request privacy flags. The flags a server honored are echoed in
+/// the response so clients can check them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrivacyFlags {
+    /// No audit events name the request (context provenance, shadow samples).
+    #[serde(default)]
+    pub no_log: bool,
+    /// Keep nothing derived from the prompt after responding: no affinity
+    /// entry and no affinity key in the response.
+    #[serde(default)]
+    pub no_cache: bool,
+    /// Leave the request out of data exported off-host: usage exports and
+    /// shadow policy evaluation.
+    #[serde(default)]
+    pub no_train_export: bool,
+}
+
+impl PrivacyFlags {
+    pub fn is_empty(&self) -> bool {
+        !(self.no_log || self.no_cache || self.no_train_export)
+    }
+}
+
+/// Inference request from caller.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InferenceRequest {
+    /// Generated by the server when omitted.
+    #[serde(default)]
+    pub request_id: RequestId,
+    pub model_id: String,
+    /// Text prompt for inference (tokenization handled by model).
+    /// Empty when `template_id` is set.
+    #[serde(default)]
+    pub prompt: String,
+    #[serde(default)]
+    pub parameters: InferenceParams,
+    /// Named parameter preset; `parameters` set to non-default values
+    /// override it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preset: Option<String>,
+    /// Requested scheduling priority. Capped server-side by session role.
+    #[serde(default)]
+    pub priority: Priority,
+    /// Affinity key from a previous response, echoed on follow-up turns.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_affinity_key: Option<String>,
+    /// Tools the model may call. Calls are returned in `tool_calls`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<ToolDefinition>,
+    /// Image parts. No vision backend is linked, so a request carrying
+    /// any is rejected rather than answered from its text alone.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub images: Vec<ImagePart>,
+    /// Server-side prompt template (`id` or `id@version`) rendered with
+    /// `variables` in place of `prompt`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template_id: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub variables: BTreeMap<String, String>,
+    /// Retrieved documents assembled into the prompt within token budgets.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub context_documents: Vec<ContextDocument>,
+    /// Attribute output sentences to the documents they cite.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub cite_sources: bool,
+    #[serde(default, skip_serializing_if = "PrivacyFlags::is_empty")]
+    pub privacy: PrivacyFlags,
+    /// Client-chosen key; a repeat within the idempotency window is refused
+    /// by every replica sharing the quota backend.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idempotency_key: Option<String>,
+}
+
+/// Longest accepted `idempotency_key`, in bytes.
+pub const MAX_IDEMPOTENCY_KEY_LEN: usize = 128;
+
+impl InferenceRequest {
+    pub fn validate(&self) -> Result<(), ProtocolError> {
+        if self.model_id.is_empty() {
+            return Err(ProtocolError::MissingField("model_id".into()));
+        }
+        match (&self.template_id, self.prompt.is_empty()) {
+            (None, true) => return Err(ProtocolError::MissingField("prompt".into())),
+            (Some(_), false) => {
+                return Err(ProtocolError::InvalidFormat(
+                    "prompt and template_id are mutually exclusive".into(),
+                ))
+            }
+            _ => {}
+        }
+        if self.template_id.is_none() && !self.variables.is_empty() {
+            return Err(ProtocolError::InvalidFormat("variables require template_id".into()));
+        }
+        if !self.images.is_empty() {
+            return Err(ProtocolError::InvalidFormat("image input is not supported".into()));
+        }
+        if let Some(key) = &self.idempotency_key {
+            if key.is_empty() || key.len() > MAX_IDEMPOTENCY_KEY_LEN {
+                return Err(ProtocolError::InvalidFormat(format!(
+                    "idempotency_key must be 1-{} bytes",
+                    MAX_IDEMPOTENCY_KEY_LEN
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc::protocol::{decode_message, IpcMessage};
+
+    #[test]
+    fn test_inference_request_validation() {
+        let valid = InferenceRequest {
+            request_id: RequestId(1),
+            model_id: "test-model".to_string(),
+            prompt: "Hello, world!".to_string(),
+            parameters: InferenceParams::default(),
+            preset: None,
+            priority: Priority::Normal,
+            session_affinity_key: None,
+            tools: Vec::new(),
+            images: Vec::new(),
+            template_id: None,
+            variables: Default::default(),
+            context_documents: Vec::new(),
+            cite_sources: false,
+            privacy: Default::default(),
+            idempotency_key: None,
+        };
+        assert!(valid.validate().is_ok());
+
+        let invalid_model = InferenceRequest {
+            request_id: RequestId(1),
+            model_id: "".to_string(),
+            prompt: "Hello, world!".to_string(),
+            parameters: InferenceParams::default(),
+            preset: None,
+            priority: Priority::Normal,
+            session_affinity_key: None,
+            tools: Vec::new(),
+            images: Vec::new(),
+            template_id: None,
+            variables: Default::default(),
+            context_documents: Vec::new(),
+            cite_sources: false,
+            privacy: Default::default(),
+            idempotency_key: None,
+        };
+        assert!(invalid_model.validate().is_err());
+
+        let invalid_prompt = InferenceRequest {
+            request_id: RequestId(1),
+            model_id: "test".to_string(),
+            prompt: "".to_string(),
+            parameters: InferenceParams::default(),
+            preset: None,
+            priority: Priority::Normal,
+            session_affinity_key: None,
+            tools: Vec::new(),
+            images: Vec::new(),
+            template_id: None,
+            variables: Default::default(),
+            context_documents: Vec::new(),
+            cite_sources: false,
+            privacy: Default::default(),
+            idempotency_key: None,
+        };
+        assert!(invalid_prompt.validate().is_err());
+
+        let templated = InferenceRequest { template_id: Some("greet".into()), ..invalid_prompt };
+        assert!(templated.validate().is_ok());
+        let both = InferenceRequest { prompt: "Hello".into(), ..templated.clone() };
+        assert!(both.validate().is_err());
+        let stray_variables = InferenceRequest {
+            template_id: None,
+            variables: [("name".to_string(), "Ada".to_string())].into(),
+            ..both
+        };
+        assert!(stray_variables.validate().is_err());
+    }
+
+    #[test]
+    fn test_inference_request_priority_defaults_to_normal() {
+        let json = r#"{"request_id":1,"model_id":"m","prompt":"p","parameters":{"max_tokens":8,"temperature":0.7,"top_p":0.9,"top_k":40}}"#;
+        let request: InferenceRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.priority, Priority::Normal);
+
+        let json = json.replace(r#""top_k":40}"#, r#""top_k":40},"priority":"critical""#);
+        let request: InferenceRequest = serde_json::from_str(&json).unwrap();
+        assert_eq!(request.priority, Priority::Critical);
+    }
+
+    #[test]
+    fn test_inference_request_images_are_rejected() {
+        let json = r#"{"type":"inference_request","request_id":9,"model_id":"llava","prompt":"Describe","parameters":{"max_tokens":8,"temperature":0.0,"top_p":1.0,"top_k":1},"images":[{"source":"base64","data":"iVBORw=="},{"source":"shm","name":"frame-1","len":1024}]}"#;
+        match decode_message(json.as_bytes()).unwrap() {
+            IpcMessage::InferenceRequest(request) => {
+                assert_eq!(request.images.len(), 2);
+                assert_eq!(request.images[1], ImagePart::Shm { name: "frame-1".into(), len: 1024 });
+                assert!(request.validate().is_err());
+            }
+            other => panic!("unexpected message: {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,165 @@
+//! Request identifiers: ULID, UUID or legacy integer.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::ProtocolError;
+
+/// Request identifier, unique across restarts and replicas.
+///
+/// Clients may supply their own ID as a ULID or UUID string; a missing or
+/// zero ID is replaced by a server-generated one (UUIDv7 bits, written as a
+/// ULID, so IDs sort by creation time). Integer IDs from older clients are
+/// still accepted and are echoed back as integers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RequestId(pub u128);
+
+/// Crockford base32, the ULID alphabet.
+const ULID_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+impl RequestId {
+    /// A new time-ordered ID.
+    pub fn generate() -> Self {
+        Self(uuid::Uuid::now_v7().as_u128())
+    }
+
+    /// No ID was supplied.
+    pub fn is_unset(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// This ID, or a generated one if none was supplied.
+    pub fn or_generate(self) -> Self {
+        if self.is_unset() {
+            Self::generate()
+        } else {
+            self
+        }
+    }
+
+    /// An integer ID from a client predating ULID IDs.
+    pub fn is_legacy(&self) -> bool {
+        self.0 <= u64::MAX as u128
+    }
+}
+
+impl fmt::Display for RequestId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_legacy() {
+            return write!(f, "{}", self.0);
+        }
+        // 26 characters: 3 bits, then 25 groups of 5
+        let ulid: String = (0..26)
+            .map(|i| ULID_ALPHABET[((self.0 >> (125 - i * 5)) & 0x1f) as usize] as char)
+            .collect();
+        f.write_str(&ulid)
+    }
+}
+
+fn decode_ulid(text: &str) -> Option<u128> {
+    let mut value: u128 = 0;
+    for (i, c) in text.bytes().enumerate() {
+        let digit = ULID_ALPHABET.iter().position(|&a| a == c.to_ascii_uppercase())? as u128;
+        // The first character carries only the top 3 bits
+        if i == 0 && digit > 7 {
+            return None;
+        }
+        value = (value << 5) | digit;
+    }
+    Some(value)
+}
+
+impl FromStr for RequestId {
+    type Err = ProtocolError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let parsed = if !text.is_empty() && text.bytes().all(|b| b.is_ascii_digit()) {
+            text.parse::<u64>().ok().map(u128::from)
+        } else if text.len() == 26 {
+            decode_ulid(text)
+        } else {
+            uuid::Uuid::parse_str(text).ok().map(|u| u.as_u128())
+        };
+        parsed.map(Self).ok_or(ProtocolError::InvalidRequestId)
+    }
+}
+
+impl Serialize for RequestId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.is_legacy() {
+            serializer.serialize_u64(self.0 as u64)
+        } else {
+            serializer.collect_str(self)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for RequestId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct IdVisitor;
+
+        impl Visitor<'_> for IdVisitor {
+            type Value = RequestId;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a ULID, UUID or non-negative integer request ID")
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<RequestId, E> {
+                Ok(RequestId(value.into()))
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<RequestId, E> {
+                value.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(IdVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc::protocol::IpcMessage;
+
+    #[test]
+    fn test_request_id_formats() {
+        let id = RequestId::generate();
+        assert!(!id.is_legacy());
+        let text = id.to_string();
+        assert_eq!(text.len(), 26);
+        assert_eq!(text.parse::<RequestId>().unwrap(), id);
+        assert_eq!(serde_json::to_string(&id).unwrap(), format!("\"{}\"", text));
+        assert!(RequestId::generate() > id);
+
+        let uuid = "0190163d-8694-739b-aea5-966c26f8ad91";
+        let from_uuid: RequestId = serde_json::from_str(&format!("\"{}\"", uuid)).unwrap();
+        assert_eq!(from_uuid.0, uuid::Uuid::parse_str(uuid).unwrap().as_u128());
+
+        // Older clients send integers and get integers back
+        let legacy: RequestId = serde_json::from_str("42").unwrap();
+        assert_eq!(serde_json::to_string(&legacy).unwrap(), "42");
+
+        assert!("not-an-id".parse::<RequestId>().is_err());
+        assert!("80000000000000000000000000".parse::<RequestId>().is_err());
+        assert!(serde_json::from_str::<RequestId>("-1").is_err());
+    }
+
+    #[test]
+    fn test_missing_request_id_is_generated() {
+        let json = r#"{"type":"rerank_request","model_id":"bge","query":"q","passages":["a"]}"#;
+        let mut message: IpcMessage = serde_json::from_str(json).unwrap();
+        message.assign_request_id();
+        let IpcMessage::RerankRequest(request) = &message else { panic!("expected rerank request") };
+        assert!(!request.request_id.is_unset());
+
+        let before = request.request_id;
+        message.assign_request_id();
+        let IpcMessage::RerankRequest(request) = message else { panic!("expected rerank request") };
+        assert_eq!(request.request_id, before);
+    }
+}
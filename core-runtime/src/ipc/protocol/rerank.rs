@@ -0,0 +1,64 @@
+//! Passage reranking.
+
+use serde::{Deserialize, Serialize};
+
+use crate::engine::RankedPassage;
+
+use super::RequestId;
+
+/// Score passages against a query with a cross-encoder model.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RerankRequest {
+    /// Generated by the server when omitted.
+    #[serde(default)]
+    pub request_id: RequestId,
+    pub model_id: String,
+    pub query: String,
+    pub passages: Vec<String>,
+    /// Return only the best `top_n` passages (all if unset).
+    #[serde(default)]
+    pub top_n: Option<usize>,
+}
+
+/// Passages ordered by descending relevance.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RerankResponse {
+    pub request_id: RequestId,
+    pub results: Vec<RankedPassage>,
+    pub error: Option<String>,
+}
+
+impl RerankResponse {
+    pub fn success(request_id: RequestId, results: Vec<RankedPassage>) -> Self {
+        Self {
+            request_id,
+            results,
+            error: None,
+        }
+    }
+
+    pub fn error(request_id: RequestId, error: String) -> Self {
+        Self {
+            request_id,
+            results: Vec::new(),
+            error: Some(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ipc::protocol::{decode_message, IpcMessage};
+
+    #[test]
+    fn test_rerank_request_defaults_top_n() {
+        let json = r#"{"type":"rerank_request","request_id":3,"model_id":"bge","query":"q","passages":["a","b"]}"#;
+        match decode_message(json.as_bytes()).unwrap() {
+            IpcMessage::RerankRequest(request) => {
+                assert_eq!(request.passages.len(), 2);
+                assert_eq!(request.top_n, None);
+            }
+            other => panic!("unexpected message: {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,186 @@
+//! Inference responses and their error codes.
+
+use serde::{Deserialize, Serialize};
+
+use crate::engine::tools::ToolCall;
+use crate::engine::context_docs::{Citation, DocumentUsage};
+use crate::engine::{ContextUsage, FinishReason};
+
+use super::{PrivacyFlags, RequestId};
+
+/// Inference response to caller.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InferenceResponse {
+    pub request_id: RequestId,
+    /// Generated text output from model.
+    pub output: String,
+    /// Number of tokens generated.
+    pub tokens_generated: usize,
+    pub finished: bool,
+    pub error: Option<String>,
+    /// Routing hint: send follow-up turns with this key to reuse KV state.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_affinity_key: Option<String>,
+    /// Schema-validated tool calls requested by the model.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tool_calls: Vec<ToolCall>,
+    /// Model that produced the output; differs from the requested model
+    /// when a fallback served the request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub served_model: Option<String>,
+    /// How each context document was used (included, truncated, dropped).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub context_documents: Vec<DocumentUsage>,
+    /// Output spans attributed to context documents, when requested.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub citations: Vec<Citation>,
+    /// Privacy flags honored for this request.
+    #[serde(default, skip_serializing_if = "PrivacyFlags::is_empty")]
+    pub privacy: PrivacyFlags,
+    /// Machine-readable reason accompanying `error`, when one applies.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<ResponseErrorCode>,
+    /// Why generation stopped; `degenerated` output was cut short because
+    /// it started repeating.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finish_reason: Option<FinishReason>,
+    /// Prompt and completion tokens against the model's context window,
+    /// from the model's tokenizer; absent when the request never ran.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<ContextUsage>,
+    /// `max_tokens` ceiling applied under overload; when set, output may
+    /// have stopped there rather than at the requested `max_tokens`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens_cap: Option<usize>,
+}
+
+/// Machine-readable failure reasons for responses and stream chunks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ResponseErrorCode {
+    PromptLimitExceeded,
+    MetadataLimitExceeded,
+    /// Output was cut at the limit; what was produced up to it is kept.
+    OutputLimitExceeded,
+    /// Output contained PII the deployment never emits; nothing is kept.
+    PolicyBlocked,
+    /// The model pool switched away from the requested model and the queue
+    /// fails its requests until it is active again.
+    ModelDeactivated,
+}
+
+impl InferenceResponse {
+    pub fn success(request_id: RequestId, output: String, tokens_generated: usize, finished: bool) -> Self {
+        Self {
+            request_id,
+            output,
+            tokens_generated,
+            finished,
+            error: None,
+            session_affinity_key: None,
+            tool_calls: Vec::new(),
+            served_model: None,
+            context_documents: Vec::new(),
+            citations: Vec::new(),
+            privacy: PrivacyFlags::default(),
+            error_code: None,
+            finish_reason: None,
+            usage: None,
+            max_tokens_cap: None,
+        }
+    }
+
+    pub fn with_tool_calls(mut self, calls: Vec<ToolCall>) -> Self {
+        self.tool_calls = calls;
+        self
+    }
+
+    pub fn with_affinity_key(mut self, key: String) -> Self {
+        self.session_affinity_key = Some(key);
+        self
+    }
+
+    pub fn with_served_model(mut self, model: String) -> Self {
+        self.served_model = Some(model);
+        self
+    }
+
+    pub fn with_context(mut self, documents: Vec<DocumentUsage>, citations: Vec<Citation>) -> Self {
+        self.context_documents = documents;
+        self.citations = citations;
+        self
+    }
+
+    pub fn with_privacy(mut self, privacy: PrivacyFlags) -> Self {
+        self.privacy = privacy;
+        self
+    }
+
+    pub fn with_finish_reason(mut self, reason: FinishReason) -> Self {
+        self.finish_reason = Some(reason);
+        self
+    }
+
+    pub fn with_usage(mut self, usage: ContextUsage) -> Self {
+        self.usage = Some(usage);
+        self
+    }
+
+    pub fn with_max_tokens_cap(mut self, cap: Option<usize>) -> Self {
+        self.max_tokens_cap = cap;
+        self
+    }
+
+    /// Mark the response failed with `code`, keeping any output.
+    pub fn with_error_code(mut self, code: ResponseErrorCode, error: String) -> Self {
+        self.error = Some(error);
+        self.error_code = Some(code);
+        self
+    }
+
+    pub fn error_with_code(request_id: RequestId, code: ResponseErrorCode, error: String) -> Self {
+        Self::error(request_id, String::new()).with_error_code(code, error)
+    }
+
+    pub fn error(request_id: RequestId, error: String) -> Self {
+        Self {
+            request_id,
+            output: String::new(),
+            tokens_generated: 0,
+            finished: true,
+            error: Some(error),
+            session_affinity_key: None,
+            tool_calls: Vec::new(),
+            served_model: None,
+            context_documents: Vec::new(),
+            citations: Vec::new(),
+            privacy: PrivacyFlags::default(),
+            error_code: None,
+            finish_reason: None,
+            usage: None,
+            max_tokens_cap: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inference_response_success() {
+        let response = InferenceResponse::success(RequestId(1), "Generated text".to_string(), 5, true);
+        assert_eq!(response.request_id.0, 1);
+        assert_eq!(response.output, "Generated text");
+        assert_eq!(response.tokens_generated, 5);
+        assert!(response.finished);
+        assert!(response.error.is_none());
+    }
+
+    #[test]
+    fn test_inference_response_error() {
+        let response = InferenceResponse::error(RequestId(1), "test error".to_string());
+        assert!(response.finished);
+        assert!(response.error.is_some());
+        assert!(response.output.is_empty());
+    }
+}
@@ -0,0 +1,151 @@
+//! Streamed output chunks.
+
+use serde::{Deserialize, Serialize};
+
+use crate::engine::ContextUsage;
+
+use super::{RequestId, ResponseErrorCode};
+
+/// Single token chunk for streaming responses.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamChunk {
+    pub request_id: RequestId,
+    pub token: u32,
+    /// Decoded text for this token (optional, for client display).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    pub is_final: bool,
+    pub error: Option<String>,
+    /// Model that produced the stream; set on the final chunk.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub served_model: Option<String>,
+    /// Machine-readable reason accompanying `error`, when one applies.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<ResponseErrorCode>,
+    /// Context window usage summary; set on the final chunk.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<ContextUsage>,
+}
+
+impl StreamChunk {
+    /// Create a non-final token chunk.
+    pub fn token(request_id: RequestId, token: u32) -> Self {
+        Self {
+            request_id,
+            token,
+            text: None,
+            is_final: false,
+            error: None,
+            served_model: None,
+            error_code: None,
+            usage: None,
+        }
+    }
+
+    /// Create a token chunk with decoded text.
+    pub fn token_with_text(request_id: RequestId, token: u32, text: String) -> Self {
+        Self {
+            request_id,
+            token,
+            text: Some(text),
+            is_final: false,
+            error: None,
+            served_model: None,
+            error_code: None,
+            usage: None,
+        }
+    }
+
+    /// Create the final token chunk.
+    pub fn final_token(request_id: RequestId, token: u32) -> Self {
+        Self {
+            request_id,
+            token,
+            text: None,
+            is_final: true,
+            error: None,
+            served_model: None,
+            error_code: None,
+            usage: None,
+        }
+    }
+
+    /// Create a final token chunk with decoded text.
+    pub fn final_token_with_text(request_id: RequestId, token: u32, text: String) -> Self {
+        Self {
+            request_id,
+            token,
+            text: Some(text),
+            is_final: true,
+            error: None,
+            served_model: None,
+            error_code: None,
+            usage: None,
+        }
+    }
+
+    pub fn with_served_model(mut self, model: String) -> Self {
+        self.served_model = Some(model);
+        self
+    }
+
+    pub fn with_usage(mut self, usage: ContextUsage) -> Self {
+        self.usage = Some(usage);
+        self
+    }
+
+    /// Create an error chunk carrying `code` (always final).
+    pub fn error_with_code(request_id: RequestId, code: ResponseErrorCode, error: String) -> Self {
+        Self { error_code: Some(code), ..Self::error(request_id, error) }
+    }
+
+    /// Create an error chunk (always final).
+    pub fn error(request_id: RequestId, error: String) -> Self {
+        Self {
+            request_id,
+            token: 0,
+            text: None,
+            is_final: true,
+            error: Some(error),
+            served_model: None,
+            error_code: None,
+            usage: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_chunk_token() {
+        let chunk = StreamChunk::token(RequestId(1), 42);
+        assert_eq!(chunk.token, 42);
+        assert!(!chunk.is_final);
+        assert!(chunk.error.is_none());
+    }
+
+    #[test]
+    fn test_stream_chunk_final() {
+        let chunk = StreamChunk::final_token(RequestId(1), 42);
+        assert!(chunk.is_final);
+    }
+
+    #[test]
+    fn test_stream_chunk_error() {
+        let chunk = StreamChunk::error(RequestId(1), "error".to_string());
+        assert!(chunk.is_final);
+        assert!(chunk.error.is_some());
+    }
+
+    #[test]
+    fn test_stream_chunk_error_code_roundtrip() {
+        let chunk = StreamChunk::error_with_code(RequestId(3), ResponseErrorCode::OutputLimitExceeded, "cut".into());
+        let json = serde_json::to_string(&chunk).unwrap();
+        assert!(json.contains(r#""error_code":"OutputLimitExceeded""#));
+        let decoded: StreamChunk = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.error_code, Some(ResponseErrorCode::OutputLimitExceeded));
+        assert!(!serde_json::to_string(&StreamChunk::token(RequestId(3), 1)).unwrap().contains("error_code"));
+    }
+}
@@ -0,0 +1,116 @@
+//! Protocol versions and their negotiation at handshake.
+
+use serde::{Deserialize, Serialize};
+
+/// Current protocol version for new connections.
+pub const CURRENT_PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1;
+
+/// Minimum supported protocol version.
+pub const MIN_PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1;
+
+/// Protocol version for negotiating encoding strategies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProtocolVersion {
+    /// V1: JSON encoding of token arrays (current default).
+    V1,
+    /// V2: Packed varint encoding (experimental).
+    V2,
+}
+
+impl Default for ProtocolVersion {
+    fn default() -> Self {
+        Self::V1
+    }
+}
+
+impl ProtocolVersion {
+    /// Check if this version is supported.
+    pub fn is_supported(&self) -> bool {
+        matches!(self, ProtocolVersion::V1 | ProtocolVersion::V2)
+    }
+
+    /// Negotiate the highest common version with a client.
+    pub fn negotiate(client_requested: Option<ProtocolVersion>) -> ProtocolVersion {
+        let requested = client_requested.unwrap_or_default();
+        if requested.is_supported() {
+            requested
+        } else {
+            CURRENT_PROTOCOL_VERSION
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc::protocol::{decode_message, encode_message, IpcMessage};
+
+    #[test]
+    fn test_protocol_version_default() {
+        assert_eq!(ProtocolVersion::default(), ProtocolVersion::V1);
+    }
+
+    #[test]
+    fn test_protocol_version_is_supported() {
+        assert!(ProtocolVersion::V1.is_supported());
+        assert!(ProtocolVersion::V2.is_supported());
+    }
+
+    #[test]
+    fn test_protocol_version_negotiate_none() {
+        // None should default to V1
+        assert_eq!(ProtocolVersion::negotiate(None), ProtocolVersion::V1);
+    }
+
+    #[test]
+    fn test_protocol_version_negotiate_v1() {
+        assert_eq!(
+            ProtocolVersion::negotiate(Some(ProtocolVersion::V1)),
+            ProtocolVersion::V1
+        );
+    }
+
+    #[test]
+    fn test_protocol_version_negotiate_v2() {
+        assert_eq!(
+            ProtocolVersion::negotiate(Some(ProtocolVersion::V2)),
+            ProtocolVersion::V2
+        );
+    }
+
+    #[test]
+    fn test_handshake_message_encoding() {
+        let msg = IpcMessage::Handshake {
+            token: "test-token".to_string(),
+            protocol_version: Some(ProtocolVersion::V2),
+        };
+        let encoded = encode_message(&msg).unwrap();
+        let decoded: IpcMessage = serde_json::from_slice(&encoded).unwrap();
+        assert!(matches!(
+            decoded,
+            IpcMessage::Handshake {
+                protocol_version: Some(ProtocolVersion::V2),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_handshake_ack_message() {
+        let msg = IpcMessage::HandshakeAck {
+            session_id: "session-123".to_string(),
+            protocol_version: ProtocolVersion::V1,
+            deprecations: Vec::new(),
+        };
+        let encoded = encode_message(&msg).unwrap();
+        let decoded = decode_message(&encoded).unwrap();
+        assert!(matches!(
+            decoded,
+            IpcMessage::HandshakeAck {
+                session_id,
+                protocol_version: ProtocolVersion::V1,
+                ..
+            } if session_id == "session-123"
+        ));
+    }
+}
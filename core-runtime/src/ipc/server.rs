@@ -1,8 +1,10 @@
 //! IPC server loop for accepting and processing connections.
 //!
 //! Uses platform-specific transports:
-//! - Unix: `tokio::net::UnixListener` (file-based sockets)
+//! - Unix: `tokio::net::UnixListener` (file-based, abstract or activated sockets)
 //! - Windows: `tokio::net::windows::named_pipe` (named pipes)
+//! - Optionally, a loopback TCP port for clients that can use neither
+//!   (see [`super::tcp`])
 //!
 //! All connections use length-prefixed framing (4-byte LE + payload)
 //! matching the CLI client protocol in `cli::ipc_client`.
@@ -11,13 +13,18 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
 use tokio::sync::{watch, Mutex};
 use tokio_util::sync::CancellationToken;
 use thiserror::Error;
 
 use super::connections::{ConnectionPool, OwnedConnectionGuard};
 use super::handler::IpcHandler;
-use super::protocol::{decode_message, encode_message, IpcMessage};
+use super::probe::OverflowGuard;
+use super::protocol::{decode_message, encode_message, IpcMessage, RequestId};
+use super::socket_perms::SocketPermError;
+#[cfg(unix)]
+use super::socket_perms::SocketPermissions;
 use super::stream_bridge::IpcStreamBridge;
 
 /// Maximum allowed message frame size (16 MB).
@@ -30,6 +37,9 @@ pub enum ServerError {
 
     #[error("Frame too large: {size} bytes (max {max})")]
     FrameTooLarge { size: usize, max: usize },
+
+    #[error(transparent)]
+    Permissions(#[from] SocketPermError),
 }
 
 /// Read a length-prefixed frame from an async reader.
@@ -75,18 +85,27 @@ async fn write_frame_locked<W: AsyncWriteExt + Unpin>(
 
 /// Handle one IPC connection: read requests, dispatch, write responses.
 /// Supports both synchronous request/response and streaming inference.
+///
+/// With `session_required`, nothing but probes and the handshake is
+/// answered before the client authenticates.
 async fn handle_connection<S: AsyncReadExt + AsyncWriteExt + Unpin + Send + 'static>(
     stream: S,
     handler: Arc<IpcHandler>,
     _guard: OwnedConnectionGuard,
+    session_required: bool,
 ) {
     let (mut read_half, write_half) = tokio::io::split(stream);
     let write_half = Arc::new(Mutex::new(write_half));
     let mut session = None;
-    let mut active_streams: HashMap<u64, CancellationToken> = HashMap::new();
+    let mut active_streams: HashMap<RequestId, CancellationToken> = HashMap::new();
+    let stopping = handler.shutdown().stopping().clone();
 
     loop {
-        let request_bytes = match read_frame(&mut read_half).await {
+        let frame = tokio::select! {
+            frame = read_frame(&mut read_half) => frame,
+            _ = stopping.cancelled() => break,
+        };
+        let request_bytes = match frame {
             Ok(bytes) => bytes,
             Err(ServerError::Io(ref e))
                 if e.kind() == std::io::ErrorKind::UnexpectedEof =>
@@ -100,7 +119,7 @@ async fn handle_connection<S: AsyncReadExt + AsyncWriteExt + Unpin + Send + 'sta
         };
 
         // Parse message to detect streaming vs non-streaming
-        let message = match decode_message(&request_bytes) {
+        let mut message = match decode_message(&request_bytes) {
             Ok(m) => m,
             Err(e) => {
                 let err = format!(r#"{{"type":"error","code":400,"message":"{}"}}"#, e);
@@ -108,13 +127,39 @@ async fn handle_connection<S: AsyncReadExt + AsyncWriteExt + Unpin + Send + 'sta
                 continue;
             }
         };
+        // Streams are tracked by ID before reaching the handler
+        message.assign_request_id();
+
+        if session_required
+            && session.is_none()
+            && !matches!(message, IpcMessage::Probe | IpcMessage::Handshake { .. })
+        {
+            let err = r#"{"type":"error","code":401,"message":"Not authenticated"}"#;
+            let _ = write_frame_locked(&write_half, err.as_bytes()).await;
+            continue;
+        }
+
+        // Retired protocol features are refused before dispatch
+        if let Err(e) = handler.deprecations().check(&request_bytes, session.as_ref()) {
+            if let Ok(bytes) = encode_message(&IpcMessage::Error { code: 410, message: e.to_string() }) {
+                let _ = write_frame_locked(&write_half, &bytes).await;
+            }
+            continue;
+        }
 
         match message {
+            // Liveness probe: answered here, ahead of auth and queueing
+            IpcMessage::Probe => {
+                if let Ok(bytes) = encode_message(&handler.probe().answer()) {
+                    let _ = write_frame_locked(&write_half, &bytes).await;
+                }
+            }
+
             // Streaming inference request
             IpcMessage::InferenceRequest(ref req) if req.parameters.stream => {
                 if let Some(ref sess) = session {
                     let cancel = CancellationToken::new();
-                    active_streams.insert(req.request_id.0, cancel.clone());
+                    active_streams.insert(req.request_id, cancel.clone());
                     let bridge = IpcStreamBridge::new(
                         Arc::clone(&write_half),
                         req.request_id,
@@ -123,7 +168,7 @@ async fn handle_connection<S: AsyncReadExt + AsyncWriteExt + Unpin + Send + 'sta
                     let _ = handler
                         .process_streaming(req.clone(), sess, &bridge, cancel)
                         .await;
-                    active_streams.remove(&req.request_id.0);
+                    active_streams.remove(&req.request_id);
                 } else {
                     let err = r#"{"type":"error","code":401,"message":"Not authenticated"}"#;
                     let _ = write_frame_locked(&write_half, err.as_bytes()).await;
@@ -132,7 +177,7 @@ async fn handle_connection<S: AsyncReadExt + AsyncWriteExt + Unpin + Send + 'sta
 
             // Cancel request - trigger cancellation for active streams
             IpcMessage::CancelRequest { request_id } => {
-                let cancelled = if let Some(cancel) = active_streams.get(&request_id.0) {
+                let cancelled = if let Some(cancel) = active_streams.get(&request_id) {
                     cancel.cancel();
                     true
                 } else {
@@ -167,46 +212,87 @@ async fn handle_connection<S: AsyncReadExt + AsyncWriteExt + Unpin + Send + 'sta
     }
 }
 
+/// Serve a connection accepted past the connection limit: answer one
+/// probe, refuse anything else, and close.
+async fn serve_probe_only<S: AsyncReadExt + AsyncWriteExt + Unpin>(
+    mut stream: S,
+    handler: Arc<IpcHandler>,
+    _overflow: OverflowGuard,
+) {
+    let timeout = handler.probe().config().overflow_read_timeout;
+    let Ok(Ok(frame)) = tokio::time::timeout(timeout, read_frame(&mut stream)).await else {
+        return;
+    };
+    let response = match decode_message(&frame) {
+        Ok(IpcMessage::Probe) => handler.probe().answer(),
+        _ => IpcMessage::Error { code: 503, message: "Connection limit reached".into() },
+    };
+    if let Ok(bytes) = encode_message(&response) {
+        let _ = write_frame(&mut stream, &bytes).await;
+    }
+}
+
 /// Accept one connection, acquire a guard, and spawn a handler task.
 fn spawn_connection<S: AsyncReadExt + AsyncWriteExt + Unpin + Send + 'static>(
     stream: S,
     handler: &Arc<IpcHandler>,
     connections: &Arc<ConnectionPool>,
+    session_required: bool,
 ) {
     let guard = match connections.try_acquire_owned() {
         Some(g) => g,
         None => {
-            eprintln!("Connection limit reached, rejecting client");
+            // Still answer a probe, so a busy runtime is not restarted
+            if let Some(overflow) = handler.probe().try_overflow() {
+                let task_handler = Arc::clone(handler);
+                handler.shutdown().connections().spawn(async move {
+                    serve_probe_only(stream, task_handler, overflow).await;
+                });
+            } else {
+                eprintln!("Connection limit reached, rejecting client");
+            }
             return;
         }
     };
-    let handler = Arc::clone(handler);
-    tokio::spawn(async move {
-        handle_connection(stream, handler, guard).await;
+    let task_handler = Arc::clone(handler);
+    handler.shutdown().connections().spawn(async move {
+        handle_connection(stream, task_handler, guard, session_required).await;
     });
 }
 
-/// Run the IPC server on Unix (Unix domain socket).
+/// Run the IPC server on Unix (Unix domain socket) with an owner-only socket.
 #[cfg(unix)]
 pub async fn run_server(
     socket_path: String,
     handler: Arc<IpcHandler>,
     connections: Arc<ConnectionPool>,
-    mut shutdown_rx: watch::Receiver<bool>,
+    shutdown_rx: watch::Receiver<bool>,
 ) -> Result<(), ServerError> {
-    use tokio::net::UnixListener;
-
-    let _ = std::fs::remove_file(&socket_path);
+    let permissions = SocketPermissions::default();
+    run_server_with_permissions(socket_path, permissions, handler, connections, shutdown_rx).await
+}
 
-    let listener = UnixListener::bind(&socket_path)?;
-    eprintln!("IPC server listening on {}", socket_path);
+/// Run the IPC server on Unix, applying `permissions` to the socket file.
+///
+/// The listener may instead be adopted from socket activation or bound in
+/// the abstract namespace; see [`super::listener`].
+#[cfg(unix)]
+pub async fn run_server_with_permissions(
+    socket_path: String,
+    permissions: SocketPermissions,
+    handler: Arc<IpcHandler>,
+    connections: Arc<ConnectionPool>,
+    mut shutdown_rx: watch::Receiver<bool>,
+) -> Result<(), ServerError> {
+    let (listener, source) = super::listener::open(&socket_path, &permissions)?;
+    eprintln!("IPC server listening on {}", source);
 
     loop {
         tokio::select! {
             result = listener.accept() => {
                 match result {
                     Ok((stream, _)) => spawn_connection(
-                        stream, &handler, &connections,
+                        stream, &handler, &connections, false,
                     ),
                     Err(e) => eprintln!("Accept error: {}", e),
                 }
@@ -218,11 +304,52 @@ pub async fn run_server(
         }
     }
 
-    let _ = std::fs::remove_file(&socket_path);
+    if let Some(path) = source.owned_path() {
+        let _ = std::fs::remove_file(path);
+    }
     Ok(())
 }
 
+/// Serve IPC on a loopback TCP listener from [`super::tcp::bind`].
+///
+/// Connections count against the same pool as socket or pipe clients, and
+/// must complete the handshake before anything but a probe is answered.
+pub async fn run_tcp_server(
+    listener: TcpListener,
+    handler: Arc<IpcHandler>,
+    connections: Arc<ConnectionPool>,
+    mut shutdown_rx: watch::Receiver<bool>,
+) -> Result<(), ServerError> {
+    eprintln!("IPC server listening on tcp {}", listener.local_addr()?);
+
+    loop {
+        tokio::select! {
+            result = listener.accept() => {
+                match result {
+                    Ok((stream, peer)) if peer.ip().is_loopback() => {
+                        let _ = stream.set_nodelay(true);
+                        spawn_connection(stream, &handler, &connections, true);
+                    }
+                    Ok((_, peer)) => eprintln!("Rejected non-loopback TCP client {}", peer),
+                    Err(e) => eprintln!("Accept error: {}", e),
+                }
+            }
+            _ = shutdown_rx.changed() => break,
+        }
+    }
+    Ok(())
+}
+
+/// Pipe instances kept waiting for clients at any time.
+#[cfg(windows)]
+const PIPE_POOL_SIZE: usize = 8;
+
 /// Run the IPC server on Windows (named pipes).
+///
+/// A pool of pipe instances awaits `connect()` concurrently so simultaneous
+/// clients do not see `ERROR_PIPE_BUSY`; each consumed instance is replaced
+/// immediately. Connection limits behave as on Unix: over-limit clients
+/// are accepted and then closed.
 #[cfg(windows)]
 pub async fn run_server(
     pipe_name: String,
@@ -230,22 +357,44 @@ pub async fn run_server(
     connections: Arc<ConnectionPool>,
     mut shutdown_rx: watch::Receiver<bool>,
 ) -> Result<(), ServerError> {
-    use tokio::net::windows::named_pipe::ServerOptions;
+    use tokio::net::windows::named_pipe::{NamedPipeServer, ServerOptions};
+    use tokio::task::JoinSet;
 
-    eprintln!("IPC server listening on {}", pipe_name);
+    type Accepts = JoinSet<(NamedPipeServer, std::io::Result<()>)>;
 
-    loop {
+    fn spawn_accept(accepts: &mut Accepts, server: NamedPipeServer) {
+        accepts.spawn(async move {
+            let result = server.connect().await;
+            (server, result)
+        });
+    }
+
+    let pool_size = PIPE_POOL_SIZE.min(connections.max_connections()).max(1);
+    let mut accepts = Accepts::new();
+    for i in 0..pool_size {
+        // The first instance claims the name so another process cannot
+        // squat on it before us
         let server = ServerOptions::new()
-            .first_pipe_instance(false)
+            .first_pipe_instance(i == 0)
             .create(&pipe_name)?;
+        spawn_accept(&mut accepts, server);
+    }
+    eprintln!("IPC server listening on {} ({} instances)", pipe_name, pool_size);
 
+    loop {
         tokio::select! {
-            result = server.connect() => {
-                match result {
-                    Ok(()) => spawn_connection(
-                        server, &handler, &connections,
+            Some(joined) = accepts.join_next() => {
+                match joined {
+                    Ok((server, Ok(()))) => spawn_connection(
+                        server, &handler, &connections, false,
                     ),
-                    Err(e) => eprintln!("Pipe connect error: {}", e),
+                    Ok((_, Err(e))) => eprintln!("Pipe connect error: {}", e),
+                    Err(e) => eprintln!("Pipe accept task failed: {}", e),
+                }
+                match ServerOptions::new().create(&pipe_name) {
+                    Ok(server) => spawn_accept(&mut accepts, server),
+                    Err(e) if accepts.is_empty() => return Err(e.into()),
+                    Err(e) => eprintln!("Pipe create error: {}", e),
                 }
             }
             _ = shutdown_rx.changed() => {
@@ -255,5 +404,6 @@ pub async fn run_server(
         }
     }
 
+    accepts.abort_all();
     Ok(())
 }
@@ -0,0 +1,159 @@
+//! Snapshot create/restore request handling.
+//!
+//! Archives live in a fixed directory under the runtime base path; clients
+//! select them by name only, so IPC callers cannot read or write arbitrary
+//! files. Restore loads each model from the file it was captured from,
+//! through the model admin path, so it gets the same path, maintenance and
+//! attestation checks as an explicit load.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use super::model_admin::ModelAdminHandler;
+use super::protocol::{ModelAction, ModelAdminRequest, SnapshotAction, SnapshotRequest, SnapshotResponse};
+use crate::models::{ModelPool, ModelRegistry};
+use crate::snapshot::{CatalogEntry, ConfigSnapshot, RuntimeSnapshot, SnapshotError};
+use crate::RuntimeConfig;
+
+/// Maximum snapshot name length.
+const MAX_NAME_LEN: usize = 64;
+
+/// Handles snapshot requests against the live model registry.
+pub struct SnapshotHandler {
+    model_registry: Arc<ModelRegistry>,
+    model_admin: Arc<ModelAdminHandler>,
+    pool: Option<Arc<ModelPool>>,
+    config: ConfigSnapshot,
+    dir: PathBuf,
+}
+
+impl SnapshotHandler {
+    pub fn new(
+        model_registry: Arc<ModelRegistry>,
+        model_admin: Arc<ModelAdminHandler>,
+        config: &RuntimeConfig,
+        dir: PathBuf,
+    ) -> Self {
+        Self { model_registry, model_admin, pool: None, config: ConfigSnapshot::capture(config), dir }
+    }
+
+    /// Capture and rebuild `pool` membership along with the catalog.
+    pub fn with_pool(mut self, pool: Arc<ModelPool>) -> Self {
+        self.pool = Some(pool);
+        self
+    }
+
+    pub async fn handle(&self, request: SnapshotRequest) -> SnapshotResponse {
+        let SnapshotRequest { action, name } = request;
+        if !is_valid_name(&name) {
+            return SnapshotResponse::error(action, name, "Invalid snapshot name".into());
+        }
+        let path = self.dir.join(format!("{name}.json"));
+        let result = match action {
+            SnapshotAction::Create => self.create(&path).await,
+            SnapshotAction::Restore => self.restore(&path).await,
+        };
+        match result {
+            Ok(models) => SnapshotResponse::success(action, name, models),
+            Err(e) => SnapshotResponse::error(action, name, e.to_string()),
+        }
+    }
+
+    async fn create(&self, path: &Path) -> Result<usize, SnapshotError> {
+        let snapshot = RuntimeSnapshot::capture(self.config.clone(), &self.model_registry, self.pool.as_deref()).await;
+        snapshot.write_to(path)?;
+        tracing::info!(path = %path.display(), models = snapshot.catalog.len(), "Snapshot created");
+        Ok(snapshot.catalog.len())
+    }
+
+    async fn restore(&self, path: &Path) -> Result<usize, SnapshotError> {
+        let snapshot = RuntimeSnapshot::read_from(path)?;
+        let load = |entry: &CatalogEntry| self.load(entry.path.clone());
+        let summary = snapshot.restore(&self.model_registry, self.pool.as_deref(), load).await?;
+        if serde_json::to_value(&self.config).ok() != serde_json::to_value(&snapshot.config).ok() {
+            snapshot.config.stage(&self.dir)?;
+            tracing::warn!("Snapshot configuration differs from running config; staged for the next start");
+        }
+        tracing::info!(
+            path = %path.display(),
+            restored = summary.models_restored,
+            skipped = summary.models_skipped,
+            "Snapshot restored"
+        );
+        Ok(summary.models_restored)
+    }
+
+    async fn load(&self, path: Option<PathBuf>) -> Result<(), String> {
+        let path = path.ok_or("snapshot does not record the model file")?;
+        let request = ModelAdminRequest { action: ModelAction::Load, model: path.display().to_string() };
+        let response = self.model_admin.handle(request).await;
+        match response.success {
+            true => Ok(()),
+            false => Err(response.error.unwrap_or_default()),
+        }
+    }
+}
+
+/// Names are limited to `[A-Za-z0-9_-]` so they cannot escape the directory.
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= MAX_NAME_LEN
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maintenance::{MaintenanceConfig, MaintenanceScheduler};
+
+    #[test]
+    fn test_snapshot_names_cannot_traverse() {
+        assert!(is_valid_name("nightly-2026_03"));
+        assert!(!is_valid_name("../etc/passwd"));
+        assert!(!is_valid_name("a/b"));
+        assert!(!is_valid_name(""));
+    }
+
+    fn handler(base: &Path) -> SnapshotHandler {
+        let registry = Arc::new(ModelRegistry::new());
+        let model_admin = ModelAdminHandler::new(
+            crate::models::ModelLoader::new(base.to_path_buf()),
+            Arc::clone(&registry),
+            Arc::new(MaintenanceScheduler::new(MaintenanceConfig::default())),
+        );
+        SnapshotHandler::new(registry, Arc::new(model_admin), &RuntimeConfig::default(), base.join("snapshots"))
+    }
+
+    fn request(action: SnapshotAction) -> SnapshotRequest {
+        SnapshotRequest { action, name: "s1".into() }
+    }
+
+    #[tokio::test]
+    async fn test_create_then_restore_by_name() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("models")).unwrap();
+        std::fs::write(dir.path().join("models/m.mock"), r#"{"responses": ["pong"]}"#).unwrap();
+        let live = handler(dir.path());
+        let load = ModelAdminRequest { action: ModelAction::Load, model: "models/m.mock".into() };
+        assert!(live.model_admin.handle(load).await.success);
+
+        let created = live.handle(request(SnapshotAction::Create)).await;
+        assert!(created.success);
+        assert_eq!(created.models, 1);
+
+        let fresh = handler(dir.path());
+        let restored = fresh.handle(request(SnapshotAction::Restore)).await;
+        assert!(restored.success);
+        assert_eq!(restored.models, 1);
+        let models = fresh.model_registry.list_models().await;
+        assert!(models[0].path.as_ref().is_some_and(|p| p.ends_with("models/m.mock")));
+
+        // A model whose file is gone is not restored as a bare catalog entry
+        std::fs::remove_file(dir.path().join("models/m.mock")).unwrap();
+        let replacement = handler(dir.path());
+        let restored = replacement.handle(request(SnapshotAction::Restore)).await;
+        assert!(restored.success);
+        assert_eq!(restored.models, 0);
+        assert_eq!(replacement.model_registry.count().await, 0);
+    }
+}
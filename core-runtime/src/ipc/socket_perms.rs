@@ -0,0 +1,231 @@
+//! Unix socket file permissions and ownership.
+//!
+//! The socket is the only way in, so its mode and owner are the access
+//! control list. The default is owner-only (0600); deployments where a
+//! client runs under a different user share it with a group (0660).
+//!
+//! Binding is atomic with respect to permissions: the socket is bound
+//! under a temporary name, chmod/chown'd, then renamed over the final
+//! path, so no client ever sees it with the process umask applied. An
+//! existing socket is only replaced when nothing accepts on it.
+
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum SocketPermError {
+    #[error("Socket directory {0} is world-writable")]
+    WorldWritableParent(PathBuf),
+
+    #[error("Socket mode {0:o} grants access to other users")]
+    ModeTooOpen(u32),
+
+    #[error("Socket {0} is served by another running instance")]
+    InUse(PathBuf),
+
+    #[error("IO error on {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+/// Mode and ownership applied to the socket file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SocketPermissions {
+    /// Permission bits (no bits for "other" are allowed).
+    pub mode: u32,
+    /// Owner UID (None = keep the process UID).
+    pub owner: Option<u32>,
+    /// Group GID (None = keep the process GID).
+    pub group: Option<u32>,
+}
+
+impl Default for SocketPermissions {
+    fn default() -> Self {
+        Self {
+            mode: 0o600,
+            owner: None,
+            group: None,
+        }
+    }
+}
+
+impl SocketPermissions {
+    /// Group-shared socket (0660) owned by `group`.
+    pub fn group_shared(group: u32) -> Self {
+        Self {
+            mode: 0o660,
+            owner: None,
+            group: Some(group),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), SocketPermError> {
+        if self.mode & 0o007 != 0 || self.mode & !0o777 != 0 {
+            return Err(SocketPermError::ModeTooOpen(self.mode));
+        }
+        Ok(())
+    }
+}
+
+/// A mismatch found by [`verify`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SocketPermIssue {
+    Mode { expected: u32, actual: u32 },
+    Owner { expected: u32, actual: u32 },
+    Group { expected: u32, actual: u32 },
+}
+
+impl std::fmt::Display for SocketPermIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Mode { expected, actual } => write!(f, "mode {actual:o}, expected {expected:o}"),
+            Self::Owner { expected, actual } => write!(f, "owner {actual}, expected {expected}"),
+            Self::Group { expected, actual } => write!(f, "group {actual}, expected {expected}"),
+        }
+    }
+}
+
+fn io_err(path: &Path) -> impl FnOnce(std::io::Error) -> SocketPermError + '_ {
+    move |source| SocketPermError::Io {
+        path: path.to_path_buf(),
+        source,
+    }
+}
+
+/// Refuse a socket path whose parent directory is world-writable.
+#[cfg(unix)]
+pub fn check_parent(socket_path: &Path) -> Result<(), SocketPermError> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let parent = match socket_path.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p,
+        _ => Path::new("."),
+    };
+    let mode = std::fs::metadata(parent).map_err(io_err(parent))?.permissions().mode();
+    if mode & 0o002 != 0 {
+        return Err(SocketPermError::WorldWritableParent(parent.to_path_buf()));
+    }
+    Ok(())
+}
+
+/// Apply mode and ownership to an existing socket file.
+#[cfg(unix)]
+pub fn apply(path: &Path, perms: &SocketPermissions) -> Result<(), SocketPermError> {
+    use std::os::unix::fs::PermissionsExt;
+
+    if perms.owner.is_some() || perms.group.is_some() {
+        std::os::unix::fs::chown(path, perms.owner, perms.group).map_err(io_err(path))?;
+    }
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(perms.mode)).map_err(io_err(path))
+}
+
+/// Bind a listener at `socket_path` with `perms` already applied.
+#[cfg(unix)]
+pub fn bind(
+    socket_path: &Path,
+    perms: &SocketPermissions,
+) -> Result<tokio::net::UnixListener, SocketPermError> {
+    perms.validate()?;
+    check_parent(socket_path)?;
+    // A socket that accepts is live; only a refused connect marks it stale
+    if std::os::unix::net::UnixStream::connect(socket_path).is_ok() {
+        return Err(SocketPermError::InUse(socket_path.to_path_buf()));
+    }
+
+    let mut staging = socket_path.as_os_str().to_owned();
+    staging.push(format!(".{}.tmp", std::process::id()));
+    let staging = PathBuf::from(staging);
+    let _ = std::fs::remove_file(&staging);
+
+    let listener = tokio::net::UnixListener::bind(&staging).map_err(io_err(&staging))?;
+    let result = apply(&staging, perms)
+        .and_then(|()| std::fs::rename(&staging, socket_path).map_err(io_err(socket_path)));
+    if let Err(e) = result {
+        let _ = std::fs::remove_file(&staging);
+        return Err(e);
+    }
+    Ok(listener)
+}
+
+/// Compare the socket file against `perms`.
+#[cfg(unix)]
+pub fn verify(path: &Path, perms: &SocketPermissions) -> Result<Vec<SocketPermIssue>, SocketPermError> {
+    use std::os::unix::fs::MetadataExt;
+
+    let meta = std::fs::metadata(path).map_err(io_err(path))?;
+    let mut issues = Vec::new();
+    let mode = meta.mode() & 0o777;
+    if mode != perms.mode {
+        issues.push(SocketPermIssue::Mode { expected: perms.mode, actual: mode });
+    }
+    if let Some(uid) = perms.owner.filter(|uid| *uid != meta.uid()) {
+        issues.push(SocketPermIssue::Owner { expected: uid, actual: meta.uid() });
+    }
+    if let Some(gid) = perms.group.filter(|gid| *gid != meta.gid()) {
+        issues.push(SocketPermIssue::Group { expected: gid, actual: meta.gid() });
+    }
+    Ok(issues)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    fn private_dir() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::set_permissions(dir.path(), std::fs::Permissions::from_mode(0o700)).unwrap();
+        dir
+    }
+
+    #[tokio::test]
+    async fn test_bind_applies_mode_and_verify_repairs() {
+        let dir = private_dir();
+        let path = dir.path().join("core.sock");
+        let perms = SocketPermissions::default();
+
+        let _listener = bind(&path, &perms).unwrap();
+        assert!(verify(&path, &perms).unwrap().is_empty());
+
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o666)).unwrap();
+        let issues = verify(&path, &perms).unwrap();
+        assert_eq!(issues, vec![SocketPermIssue::Mode { expected: 0o600, actual: 0o666 }]);
+
+        apply(&path, &perms).unwrap();
+        assert!(verify(&path, &perms).unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_bind_refuses_live_socket_and_replaces_stale() {
+        let dir = private_dir();
+        let path = dir.path().join("core.sock");
+        let perms = SocketPermissions::default();
+
+        let listener = bind(&path, &perms).unwrap();
+        assert!(matches!(bind(&path, &perms), Err(SocketPermError::InUse(_))));
+
+        // The socket file outlives its listener; it is stale now
+        drop(listener);
+        assert!(path.exists());
+        assert!(bind(&path, &perms).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_bind_refuses_world_writable_parent() {
+        let dir = private_dir();
+        std::fs::set_permissions(dir.path(), std::fs::Permissions::from_mode(0o777)).unwrap();
+        let err = bind(&dir.path().join("core.sock"), &SocketPermissions::default()).unwrap_err();
+        assert!(matches!(err, SocketPermError::WorldWritableParent(_)));
+    }
+
+    #[test]
+    fn test_mode_for_other_users_rejected() {
+        let perms = SocketPermissions { mode: 0o666, ..Default::default() };
+        assert!(matches!(perms.validate(), Err(SocketPermError::ModeTooOpen(0o666))));
+        assert!(SocketPermissions::group_shared(100).validate().is_ok());
+    }
+}
@@ -0,0 +1,143 @@
+//! Loopback TCP transport.
+//!
+//! Some client runtimes (older JVMs, sandboxed Windows processes) cannot
+//! open a Unix socket or named pipe. For them the server can also listen on
+//! a loopback TCP port, speaking the same length-prefixed framing and
+//! sharing the same connection limit.
+//!
+//! A TCP port has no file permissions, so any local user can reach it:
+//! the listener is opt-in, requires a configured auth token, and answers
+//! nothing but probes and the handshake until a session is established.
+//! The chosen port is written to [`TCP_ENDPOINT_FILE`] under `base_path`
+//! for clients to discover, and removed on shutdown.
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::Path;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::net::TcpListener;
+
+/// Endpoint metadata file under `base_path`.
+pub const TCP_ENDPOINT_FILE: &str = "ipc-tcp.json";
+
+#[derive(Error, Debug)]
+pub enum TcpTransportError {
+    #[error("TCP listen address must be loopback, got {0}")]
+    NotLoopback(IpAddr),
+
+    #[error("Invalid TCP listen address '{0}' (auto, or e.g. 127.0.0.1:7878)")]
+    InvalidAddr(String),
+
+    #[error("TCP transport requires CORE_AUTH_TOKEN")]
+    NoAuthToken,
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Where the TCP listener binds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TcpBind {
+    /// An ephemeral port on `::1`, or `127.0.0.1` where IPv6 is off.
+    Auto,
+    /// A fixed loopback address; port 0 picks an ephemeral port.
+    Addr(SocketAddr),
+}
+
+impl FromStr for TcpBind {
+    type Err = TcpTransportError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" | "1" | "true" => Ok(Self::Auto),
+            other => {
+                let addr: SocketAddr = other.parse().map_err(|_| TcpTransportError::InvalidAddr(other.to_string()))?;
+                if !addr.ip().is_loopback() {
+                    return Err(TcpTransportError::NotLoopback(addr.ip()));
+                }
+                Ok(Self::Addr(addr))
+            }
+        }
+    }
+}
+
+/// Bind the listener, refusing anything but loopback.
+pub async fn bind(spec: TcpBind) -> Result<TcpListener, TcpTransportError> {
+    match spec {
+        TcpBind::Addr(addr) if !addr.ip().is_loopback() => Err(TcpTransportError::NotLoopback(addr.ip())),
+        TcpBind::Addr(addr) => Ok(TcpListener::bind(addr).await?),
+        TcpBind::Auto => match TcpListener::bind((Ipv6Addr::LOCALHOST, 0)).await {
+            Ok(listener) => Ok(listener),
+            Err(_) => Ok(TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await?),
+        },
+    }
+}
+
+/// Contents of [`TCP_ENDPOINT_FILE`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TcpEndpoint {
+    /// Address to connect to, e.g. `[::1]:40123`.
+    pub addr: SocketAddr,
+    pub port: u16,
+    /// Server process, so clients can tell a stale file from a live one.
+    pub pid: u32,
+}
+
+impl TcpEndpoint {
+    pub fn new(addr: SocketAddr) -> Self {
+        Self { addr, port: addr.port(), pid: std::process::id() }
+    }
+
+    /// Publish the endpoint under `base_path`, replacing any stale file.
+    pub fn write(&self, base_path: &Path) -> std::io::Result<()> {
+        let path = base_path.join(TCP_ENDPOINT_FILE);
+        let temp = path.with_extension("json.tmp");
+        let json = serde_json::to_vec_pretty(self)?;
+        std::fs::write(&temp, json)?;
+        std::fs::rename(&temp, &path)
+    }
+
+    pub fn read(base_path: &Path) -> std::io::Result<Self> {
+        let data = std::fs::read(base_path.join(TCP_ENDPOINT_FILE))?;
+        Ok(serde_json::from_slice(&data)?)
+    }
+
+    /// Remove the published endpoint if it is still ours.
+    pub fn remove(base_path: &Path) {
+        if Self::read(base_path).is_ok_and(|endpoint| endpoint.pid == std::process::id()) {
+            let _ = std::fs::remove_file(base_path.join(TCP_ENDPOINT_FILE));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_bind() {
+        assert_eq!("auto".parse::<TcpBind>().unwrap(), TcpBind::Auto);
+        assert_eq!(
+            "[::1]:7878".parse::<TcpBind>().unwrap(),
+            TcpBind::Addr(SocketAddr::new(Ipv6Addr::LOCALHOST.into(), 7878))
+        );
+        assert!(matches!("0.0.0.0:7878".parse::<TcpBind>(), Err(TcpTransportError::NotLoopback(_))));
+        assert!(matches!("localhost".parse::<TcpBind>(), Err(TcpTransportError::InvalidAddr(_))));
+    }
+
+    #[tokio::test]
+    async fn test_bind_and_publish() {
+        let listener = bind(TcpBind::Auto).await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        assert!(addr.ip().is_loopback() && addr.port() != 0);
+
+        let dir = tempfile::tempdir().unwrap();
+        let endpoint = TcpEndpoint::new(addr);
+        endpoint.write(dir.path()).unwrap();
+        assert_eq!(TcpEndpoint::read(dir.path()).unwrap(), endpoint);
+        TcpEndpoint::remove(dir.path());
+        assert!(!dir.path().join(TCP_ENDPOINT_FILE).exists());
+    }
+}
@@ -22,16 +22,31 @@
 //! - Network: Blocked (deny all)
 //! - IPC: Named pipes/Unix sockets only. No HTTP/REST/WebSocket.
 
+pub mod backup;
+pub mod bench;
+pub mod config_schema;
+pub mod conversations;
 pub mod engine;
+pub mod exit_report;
+pub mod features;
 pub mod health;
 pub mod ipc;
+pub mod locks;
+pub mod maintenance;
 pub mod memory;
+pub mod migrations;
 pub mod models;
+pub mod retention;
 pub mod sandbox;
 pub mod scheduler;
 pub mod security;
 pub mod shutdown;
+pub mod snapshot;
+pub mod storage;
+#[cfg(unix)]
+pub mod supervisor;
 pub mod telemetry;
+pub mod templates;
 
 // A/B testing module (v0.5.0)
 pub mod ab_testing;
@@ -53,6 +68,14 @@ pub mod shim;
 #[cfg(feature = "ffi")]
 pub mod ffi;
 
+// Loopback admin web console (optional)
+#[cfg(feature = "web-console")]
+pub mod console;
+
+// In-process server fixture for end-to-end tests
+#[cfg(all(feature = "testing", unix))]
+pub mod testing;
+
 // Python bindings module (v0.3.1)
 #[cfg(feature = "python")]
 pub mod python;
@@ -61,18 +84,31 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 
-use engine::InferenceEngine;
+use conversations::{ConversationConfig, PrefetchConfig};
+use engine::{InferenceEngine, PresetCatalog};
+use exit_report::ExitReport;
+use features::FeatureFlags;
 use health::{HealthChecker, HealthConfig};
-use ipc::{ConnectionConfig, ConnectionPool, IpcHandler, IpcHandlerConfig, SessionAuth};
+use ipc::{
+    ConnectionConfig, ConnectionPool, DeprecationConfig, IpcHandler, IpcHandlerConfig, ModelAdminHandler, ProbeConfig,
+    RequestLimits, SessionAuth, SnapshotHandler, SocketPermissions,
+};
+use maintenance::{AuditRotationTask, CacheGcTask, KvCompactionTask, MaintenanceConfig, MaintenanceScheduler};
 use memory::{
     ContextCache, ContextCacheConfig, GpuMemory, GpuMemoryConfig, MemoryPool, MemoryPoolConfig,
 };
-use models::{ModelLoader, ModelRegistry};
+use models::{
+    AttestationConfig, IntegrityConfig, IntegrityMonitor, ModelLoader, ModelPool, ModelRegistry, PlacementConfig,
+    PoolConfig, RegistryPersistence, WarmPoolController, WarmScheduleConfig,
+};
 use scheduler::{
-    BatchConfig, BatchProcessor, OutputCache, OutputCacheConfig, RequestQueue, RequestQueueConfig,
+    BatchConfig, BatchProcessor, CircuitConfig, GpuShareConfig, HedgeConfig, OutputCache, OutputCacheConfig,
+    OverloadConfig, OverloadController, QuotaConfig, RequestQueue, RequestQueueConfig, SwitchPolicy, ThermalConfig,
 };
+use security::{LegalHoldConfig, PiiBlockConfig, ShadowConfig};
 use shutdown::ShutdownCoordinator;
-use telemetry::MetricsStore;
+use retention::{RetentionConfig, RetentionEnforcer};
+use telemetry::{DpConfig, MetricsStore, ProfilerConfig, SloConfig};
 use tokio::sync::Mutex;
 
 /// Runtime configuration.
@@ -80,16 +116,76 @@ use tokio::sync::Mutex;
 pub struct RuntimeConfig {
     pub base_path: PathBuf,
     pub auth_token: String,
+    /// Optional handshake token granting admin sessions (e.g. Critical priority).
+    pub admin_token: Option<String>,
+    /// Optional handshake token granting read-only observer sessions.
+    pub observer_token: Option<String>,
+    /// Per-tenant handshake tokens as (tenant, token); sessions opened with
+    /// one are attributed to that tenant rather than to the shared token.
+    pub tenant_tokens: Vec<(String, String)>,
     pub session_timeout: Duration,
     pub max_context_length: usize,
     pub memory_pool: MemoryPoolConfig,
     pub gpu_memory: GpuMemoryConfig,
     pub context_cache: ContextCacheConfig,
     pub request_queue: RequestQueueConfig,
+    /// What happens to queued requests for a model the pool switches away
+    /// from.
+    pub queue_on_switch: SwitchPolicy,
     pub batch: BatchConfig,
     pub shutdown_timeout: Duration,
     pub output_cache: OutputCacheConfig,
     pub connections: ConnectionConfig,
+    pub overload: OverloadConfig,
+    pub maintenance: MaintenanceConfig,
+    /// Shared-directory placement adverts for multi-replica deployments.
+    pub placement: Option<PlacementConfig>,
+    pub conversations: ConversationConfig,
+    /// Mode and ownership of the Unix socket file.
+    pub socket: SocketPermissions,
+    /// Service level objectives and burn-rate alerting.
+    pub slo: SloConfig,
+    /// Opt-in CPU self-profiler served over IPC.
+    pub profiler: ProfilerConfig,
+    /// Candidate sanitizer/policy run in shadow mode on live traffic.
+    pub policy_shadow: Option<ShadowConfig>,
+    /// PII types whose detection blocks a response instead of emitting it.
+    pub pii_block: Option<PiiBlockConfig>,
+    /// Per-model circuit breaker on repeated inference failures.
+    pub circuit: CircuitConfig,
+    /// Time-of-day rules for which model tiers are kept warm.
+    pub warm_schedule: Option<WarmScheduleConfig>,
+    /// Differential privacy for exported per-tenant usage.
+    pub usage_privacy: Option<DpConfig>,
+    /// Age and size limits for audit, journal and usage data.
+    pub retention: RetentionConfig,
+    /// Tenant rate limits and idempotency keys, optionally shared via Redis.
+    pub quota: QuotaConfig,
+    /// Model replica pairs raced when the primary is slow to answer.
+    pub hedge: HedgeConfig,
+    /// Background prefill of the next conversation turn on idle capacity.
+    pub prefetch: PrefetchConfig,
+    /// Prompt, metadata and output size limits below the frame limit.
+    pub limits: RequestLimits,
+    /// Rate limit and overflow connections for the liveness probe.
+    pub probe: ProbeConfig,
+    /// Named inference parameter presets.
+    pub presets: PresetCatalog,
+    /// Turn-taking between models that share one GPU.
+    pub gpu_share: GpuShareConfig,
+    /// Throttle GPU traffic when the GPU runs hot or at its power limit.
+    pub thermal: ThermalConfig,
+    /// Provenance attestation policy and trusted signers for model loads.
+    pub attestation: AttestationConfig,
+    /// Background re-hashing of resident model files.
+    pub integrity: IntegrityConfig,
+    /// Deprecated protocol features announced to clients, with sunsets.
+    pub deprecations: DeprecationConfig,
+    /// Hashed inference audit sampling and per-tenant legal hold capture;
+    /// unset store and key paths default under `base_path`.
+    pub legal_hold: LegalHoldConfig,
+    /// How the previous run ended, surfaced in health reports.
+    pub previous_exit: Option<ExitReport>,
 }
 
 impl Default for RuntimeConfig {
@@ -97,16 +193,46 @@ impl Default for RuntimeConfig {
         Self {
             base_path: PathBuf::from("."),
             auth_token: String::new(),
+            admin_token: None,
+            observer_token: None,
+            tenant_tokens: Vec::new(),
             session_timeout: Duration::from_secs(3600),
             max_context_length: 4096,
             memory_pool: MemoryPoolConfig::default(),
             gpu_memory: GpuMemoryConfig::default(),
             context_cache: ContextCacheConfig::default(),
             request_queue: RequestQueueConfig::default(),
+            queue_on_switch: SwitchPolicy::default(),
             batch: BatchConfig::default(),
             shutdown_timeout: Duration::from_secs(30),
             output_cache: OutputCacheConfig::default(),
             connections: ConnectionConfig::default(),
+            overload: OverloadConfig::default(),
+            maintenance: MaintenanceConfig::default(),
+            placement: None,
+            conversations: ConversationConfig::default(),
+            socket: SocketPermissions::default(),
+            slo: SloConfig::default(),
+            profiler: ProfilerConfig::default(),
+            policy_shadow: None,
+            pii_block: None,
+            circuit: CircuitConfig::default(),
+            warm_schedule: None,
+            usage_privacy: None,
+            retention: RetentionConfig::default(),
+            quota: QuotaConfig::default(),
+            hedge: HedgeConfig::default(),
+            prefetch: PrefetchConfig::default(),
+            limits: RequestLimits::default(),
+            probe: ProbeConfig::default(),
+            presets: PresetCatalog::default(),
+            gpu_share: GpuShareConfig::default(),
+            thermal: ThermalConfig::default(),
+            attestation: AttestationConfig::default(),
+            integrity: IntegrityConfig::default(),
+            deprecations: DeprecationConfig::default(),
+            legal_hold: LegalHoldConfig::default(),
+            previous_exit: None,
         }
     }
 }
@@ -116,9 +242,11 @@ pub struct Runtime {
     pub config: RuntimeConfig,
     pub memory_pool: MemoryPool,
     pub gpu_memory: GpuMemory,
-    pub context_cache: ContextCache,
+    pub context_cache: Arc<ContextCache>,
     pub model_loader: ModelLoader,
     pub model_registry: Arc<ModelRegistry>,
+    /// Hot-swap model pool; snapshots capture and rebuild its membership.
+    pub model_pool: Arc<ModelPool>,
     pub inference_engine: Arc<InferenceEngine>,
     pub request_queue: Arc<RequestQueue>,
     pub batch_processor: BatchProcessor,
@@ -128,6 +256,17 @@ pub struct Runtime {
     pub metrics_store: Arc<MetricsStore>,
     pub output_cache: Arc<Mutex<OutputCache>>,
     pub connections: Arc<ConnectionPool>,
+    pub overload: Arc<OverloadController>,
+    pub maintenance: Arc<MaintenanceScheduler>,
+    /// Warm-pool controller, when a schedule is configured.
+    pub warm_pool: Option<Arc<WarmPoolController>>,
+    /// Retention enforcement; the server runs it in the background.
+    pub retention: Arc<RetentionEnforcer>,
+    /// Model file re-verification, unless disabled; the server runs it in
+    /// the background.
+    pub integrity: Option<Arc<IntegrityMonitor>>,
+    /// Runtime feature flags, shared with the components that honour them.
+    pub features: Arc<FeatureFlags>,
 }
 
 impl Runtime {
@@ -135,30 +274,115 @@ impl Runtime {
     pub fn new(config: RuntimeConfig) -> Self {
         let memory_pool = MemoryPool::new(config.memory_pool.clone());
         let gpu_memory = GpuMemory::new(config.gpu_memory.clone());
-        let context_cache = ContextCache::new(config.context_cache.clone());
-        let model_loader = ModelLoader::new(config.base_path.clone());
-        let model_registry = Arc::new(ModelRegistry::new());
+        let context_cache = Arc::new(ContextCache::new(config.context_cache.clone()));
+        let model_loader = ModelLoader::new(config.base_path.clone()).with_attestation(config.attestation.clone());
+        let model_registry =
+            Arc::new(ModelRegistry::with_persistence(RegistryPersistence::for_base_path(&config.base_path)));
         let inference_engine = InferenceEngine::new(config.max_context_length);
-        let request_queue = Arc::new(RequestQueue::new(config.request_queue.clone()));
+        let request_queue =
+            Arc::new(RequestQueue::new(config.request_queue.clone()).with_switch_policy(config.queue_on_switch));
         let batch_processor = BatchProcessor::new(config.batch.clone());
         let shutdown = Arc::new(ShutdownCoordinator::new());
-        let health = Arc::new(HealthChecker::new(HealthConfig::default()));
+        let health = Arc::new(
+            HealthChecker::new(HealthConfig::default()).with_previous_exit(config.previous_exit.clone()),
+        );
         let metrics_store = Arc::new(MetricsStore::new());
-        let output_cache = Arc::new(Mutex::new(OutputCache::new(config.output_cache.clone())));
+        let features = Arc::new(FeatureFlags::new());
+        let output_cache = Arc::new(Mutex::new(
+            OutputCache::new(config.output_cache.clone()).with_features(Arc::clone(&features)),
+        ));
         let connections = Arc::new(ConnectionPool::new(config.connections.clone()));
+        let overload = Arc::new(
+            OverloadController::new(config.overload.clone()).with_features(Arc::clone(&features)),
+        );
+        let model_pool = Arc::new(
+            ModelPool::new(PoolConfig::default(), Arc::clone(&model_registry)).with_queue(Arc::clone(&request_queue)),
+        );
+        let maintenance = Arc::new(Self::build_maintenance(
+            &config,
+            Arc::clone(&context_cache),
+            Arc::clone(&output_cache),
+            Arc::clone(&model_pool),
+        ));
 
-        let session_auth = Arc::new(SessionAuth::new(&config.auth_token, config.session_timeout));
+        let mut session_auth = SessionAuth::new(&config.auth_token, config.session_timeout);
+        if let Some(ref admin_token) = config.admin_token {
+            session_auth = session_auth.with_admin_token(admin_token);
+        }
+        if let Some(ref observer_token) = config.observer_token {
+            session_auth = session_auth.with_observer_token(observer_token);
+        }
+        for (tenant, token) in &config.tenant_tokens {
+            session_auth = session_auth.with_tenant_token(tenant, token);
+        }
+        let warm_pool = config.warm_schedule.clone().map(|schedule| {
+            Arc::new(WarmPoolController::new(schedule, Arc::clone(&model_registry)))
+        });
+
+        let session_auth = Arc::new(session_auth);
         let inference_engine = Arc::new(inference_engine);
+        let model_admin = Arc::new(
+            ModelAdminHandler::new(
+                ModelLoader::new(config.base_path.clone()).with_attestation(config.attestation.clone()),
+                Arc::clone(&model_registry),
+                Arc::clone(&maintenance),
+            )
+            .with_engine(Arc::clone(&inference_engine)),
+        );
         let ipc_handler = IpcHandler::new(
             session_auth,
             request_queue.clone(),
-            IpcHandlerConfig::default(),
+            IpcHandlerConfig {
+                conversations: config.conversations.clone(),
+                slo: config.slo.clone(),
+                profiler: config.profiler.clone(),
+                policy_shadow: config.policy_shadow.clone(),
+                pii_block: config.pii_block.clone(),
+                circuit: config.circuit.clone(),
+                templates_dir: Some(config.base_path.join("templates")),
+                usage_privacy: config.usage_privacy,
+                purge_key_path: Some(security::receipt::default_key_path(&config.base_path)),
+                quota: config.quota.clone(),
+                hedge: config.hedge.clone(),
+                prefetch: config.prefetch.clone(),
+                limits: config.limits,
+                probe: config.probe.clone(),
+                presets: config.presets.clone(),
+                gpu_share: config.gpu_share.clone(),
+                thermal: config.thermal.clone(),
+                deprecations: config.deprecations.clone(),
+                legal_hold: config.legal_hold.clone().with_base_path(&config.base_path),
+                ..Default::default()
+            },
             shutdown.clone(),
             health.clone(),
             model_registry.clone(),
             metrics_store.clone(),
             Arc::clone(&inference_engine),
+            Arc::clone(&overload),
+            Arc::clone(&features),
+            Arc::clone(&maintenance),
+            SnapshotHandler::new(
+                Arc::clone(&model_registry),
+                Arc::clone(&model_admin),
+                &config,
+                config.base_path.join(snapshot::SNAPSHOT_DIR),
+            )
+            .with_pool(Arc::clone(&model_pool)),
+            model_admin,
         );
+        let retention = Arc::new(RetentionEnforcer::new(
+            config.retention.clone(),
+            config.base_path.join("journal"),
+            Arc::clone(ipc_handler.usage()),
+        ));
+        let integrity = config.integrity.enabled.then(|| {
+            Arc::new(IntegrityMonitor::new(
+                config.integrity.clone(),
+                Arc::clone(&model_registry),
+                Arc::clone(ipc_handler.circuits()),
+            ))
+        });
 
         Self {
             config,
@@ -167,6 +391,7 @@ impl Runtime {
             context_cache,
             model_loader,
             model_registry,
+            model_pool,
             inference_engine,
             request_queue,
             batch_processor,
@@ -176,7 +401,27 @@ impl Runtime {
             metrics_store,
             output_cache,
             connections,
+            overload,
+            maintenance,
+            warm_pool,
+            retention,
+            integrity,
+            features,
         }
     }
+
+    /// Maintenance scheduler with the built-in audit rotation and cache GC tasks.
+    fn build_maintenance(
+        config: &RuntimeConfig,
+        context_cache: Arc<ContextCache>,
+        output_cache: Arc<Mutex<OutputCache>>,
+        model_pool: Arc<ModelPool>,
+    ) -> MaintenanceScheduler {
+        let mut scheduler = MaintenanceScheduler::new(config.maintenance.clone());
+        scheduler.register_task(Box::new(AuditRotationTask { keep_recent: 1000 }));
+        scheduler.register_task(Box::new(CacheGcTask { context_cache, output_cache }));
+        scheduler.register_task(Box::new(KvCompactionTask { pool: model_pool, idle: config.maintenance.kv_idle() }));
+        scheduler
+    }
 }
 
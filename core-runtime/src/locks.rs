@@ -0,0 +1,260 @@
+//! Named `std` lock acquisition with visible poisoning and, under the
+//! `lock-order-debug` feature, lock-order and hold-time tracking.
+//!
+//! Synchronous state shared with async code sits behind `std` locks held
+//! for short sections that never await. Those locks are taken through
+//! [`lock_or_recover`], [`read_or_recover`] and [`write_or_recover`] with a
+//! static name. A lock poisoned by a panicking holder is recovered so the
+//! runtime stays available, but each recovery is logged at error level
+//! and counted in `core_lock_poison_recoveries_total`.
+//!
+//! With `lock-order-debug`, each thread records the named locks it holds.
+//! Taking lock B while holding A adds the edge A -> B to a process-wide
+//! graph. An acquisition that closes a cycle can deadlock under the right
+//! interleaving; it is logged and counted in
+//! `core_lock_order_violations_total` before the lock is taken. A hold
+//! longer than [`LONG_HOLD`] is counted in `core_lock_long_holds_total`.
+//! Without the feature a [`Tracked`] guard is the bare `std` guard.
+//!
+//! # Lock order
+//!
+//! A path that takes several locks takes them in the order their owner
+//! documents, e.g. the KV cache takes `sequences`, then `page_table`, then
+//! `access_order`. tokio locks are not tracked (a guard held across an
+//! await can move threads); they follow the same rule and are never held
+//! across calls into another component.
+
+use std::ops::{Deref, DerefMut};
+use std::sync::{Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::time::Duration;
+
+use crate::telemetry;
+
+/// Holds longer than this are reported under `lock-order-debug`.
+pub const LONG_HOLD: Duration = Duration::from_millis(100);
+
+/// A lock guard that reports its release to the order tracker.
+pub struct Tracked<G> {
+    guard: G,
+    #[cfg(feature = "lock-order-debug")]
+    _held: order::Held,
+}
+
+impl<G> Tracked<G> {
+    #[cfg_attr(not(feature = "lock-order-debug"), allow(unused_variables))]
+    fn new(name: &'static str, guard: G) -> Self {
+        Self {
+            guard,
+            #[cfg(feature = "lock-order-debug")]
+            _held: order::Held::new(name),
+        }
+    }
+
+    /// The bare guard, e.g. for `Condvar::wait`; tracking ends here.
+    pub fn into_inner(self) -> G {
+        self.guard
+    }
+}
+
+impl<G: Deref> Deref for Tracked<G> {
+    type Target = G::Target;
+
+    fn deref(&self) -> &G::Target {
+        &self.guard
+    }
+}
+
+impl<G: DerefMut> DerefMut for Tracked<G> {
+    fn deref_mut(&mut self) -> &mut G::Target {
+        &mut self.guard
+    }
+}
+
+fn recovered(name: &'static str, mode: &'static str) {
+    tracing::error!(lock = name, mode, "Lock poisoned by a panicking holder; recovering to stay available");
+    telemetry::record_lock_poisoned(name);
+}
+
+/// Acquire a mutex, recovering from poison.
+pub fn lock_or_recover<'a, T>(name: &'static str, mutex: &'a Mutex<T>) -> Tracked<MutexGuard<'a, T>> {
+    #[cfg(feature = "lock-order-debug")]
+    order::check(name);
+    let guard = mutex.lock().unwrap_or_else(|poisoned| {
+        recovered(name, "lock");
+        poisoned.into_inner()
+    });
+    Tracked::new(name, guard)
+}
+
+/// Acquire a read lock, recovering from poison.
+pub fn read_or_recover<'a, T>(name: &'static str, rwlock: &'a RwLock<T>) -> Tracked<RwLockReadGuard<'a, T>> {
+    #[cfg(feature = "lock-order-debug")]
+    order::check(name);
+    let guard = rwlock.read().unwrap_or_else(|poisoned| {
+        recovered(name, "read");
+        poisoned.into_inner()
+    });
+    Tracked::new(name, guard)
+}
+
+/// Acquire a write lock, recovering from poison.
+pub fn write_or_recover<'a, T>(name: &'static str, rwlock: &'a RwLock<T>) -> Tracked<RwLockWriteGuard<'a, T>> {
+    #[cfg(feature = "lock-order-debug")]
+    order::check(name);
+    let guard = rwlock.write().unwrap_or_else(|poisoned| {
+        recovered(name, "write");
+        poisoned.into_inner()
+    });
+    Tracked::new(name, guard)
+}
+
+/// Lock-order inversions seen so far, as (held, acquired) pairs.
+#[cfg(feature = "lock-order-debug")]
+pub fn violations() -> Vec<(&'static str, &'static str)> {
+    order::violations()
+}
+
+#[cfg(feature = "lock-order-debug")]
+mod order {
+    use std::cell::RefCell;
+    use std::collections::{HashMap, HashSet};
+    use std::sync::{Mutex, OnceLock};
+    use std::time::Instant;
+
+    use super::LONG_HOLD;
+    use crate::telemetry;
+
+    #[derive(Default)]
+    struct Graph {
+        /// Lock -> locks taken while it was held.
+        edges: HashMap<&'static str, HashSet<&'static str>>,
+        violations: HashSet<(&'static str, &'static str)>,
+    }
+
+    impl Graph {
+        fn reaches(&self, from: &'static str, to: &'static str) -> bool {
+            let mut stack = vec![from];
+            let mut seen = HashSet::new();
+            while let Some(lock) = stack.pop() {
+                if lock == to {
+                    return true;
+                }
+                if seen.insert(lock) {
+                    stack.extend(self.edges.get(lock).into_iter().flatten().copied());
+                }
+            }
+            false
+        }
+    }
+
+    static GRAPH: OnceLock<Mutex<Graph>> = OnceLock::new();
+
+    thread_local! {
+        static HELD: RefCell<Vec<(u64, &'static str)>> = const { RefCell::new(Vec::new()) };
+        static NEXT: std::cell::Cell<u64> = const { std::cell::Cell::new(0) };
+    }
+
+    fn graph() -> std::sync::MutexGuard<'static, Graph> {
+        GRAPH.get_or_init(Default::default).lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Record the edges `name` adds before it is taken, reporting any that
+    /// close a cycle. Locks of one name (e.g. per-worker queues) share a
+    /// node, so nesting two of them is not an edge.
+    pub(super) fn check(name: &'static str) {
+        let held: Vec<&'static str> = HELD.with(|h| h.borrow().iter().map(|(_, n)| *n).collect());
+        if held.is_empty() {
+            return;
+        }
+        let mut inversions = Vec::new();
+        {
+            let mut graph = graph();
+            for prior in held.into_iter().filter(|prior| *prior != name) {
+                if graph.edges.get(prior).is_some_and(|next| next.contains(name)) {
+                    continue;
+                }
+                if graph.reaches(name, prior) && graph.violations.insert((prior, name)) {
+                    inversions.push(prior);
+                }
+                graph.edges.entry(prior).or_default().insert(name);
+            }
+        }
+        for prior in inversions {
+            tracing::error!(held = prior, acquiring = name, "Lock order inversion: potential deadlock");
+            telemetry::record_lock_order_violation(prior, name);
+        }
+    }
+
+    pub(super) fn violations() -> Vec<(&'static str, &'static str)> {
+        let mut violations: Vec<_> = graph().violations.iter().copied().collect();
+        violations.sort();
+        violations
+    }
+
+    /// One held lock on this thread.
+    pub(super) struct Held {
+        id: u64,
+        name: &'static str,
+        since: Instant,
+    }
+
+    impl Held {
+        pub(super) fn new(name: &'static str) -> Self {
+            let id = NEXT.with(|next| {
+                let id = next.get();
+                next.set(id + 1);
+                id
+            });
+            HELD.with(|h| h.borrow_mut().push((id, name)));
+            Self { id, name, since: Instant::now() }
+        }
+    }
+
+    impl Drop for Held {
+        fn drop(&mut self) {
+            // Guards need not drop in acquisition order
+            HELD.with(|h| h.borrow_mut().retain(|(id, _)| *id != self.id));
+            let held = self.since.elapsed();
+            if held > LONG_HOLD {
+                tracing::warn!(lock = self.name, held_ms = held.as_millis() as u64, "Lock held too long");
+                telemetry::record_lock_long_hold(self.name, held);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recovers_poisoned_lock() {
+        let mutex = std::sync::Arc::new(Mutex::new(1));
+        let poisoner = std::sync::Arc::clone(&mutex);
+        let _ = std::thread::spawn(move || {
+            let _guard = poisoner.lock().unwrap();
+            panic!("poison");
+        })
+        .join();
+        assert!(mutex.is_poisoned());
+        *lock_or_recover("test.poisoned", &mutex) += 1;
+        assert_eq!(*lock_or_recover("test.poisoned", &mutex), 2);
+    }
+
+    #[cfg(feature = "lock-order-debug")]
+    #[test]
+    fn test_detects_order_inversion() {
+        let a = Mutex::new(());
+        let b = RwLock::new(());
+        {
+            let _a = lock_or_recover("test.a", &a);
+            let _b = write_or_recover("test.b", &b);
+        }
+        assert!(!violations().contains(&("test.b", "test.a")));
+        {
+            let _b = read_or_recover("test.b", &b);
+            let _a = lock_or_recover("test.a", &a);
+        }
+        assert!(violations().contains(&("test.b", "test.a")));
+    }
+}
@@ -0,0 +1,213 @@
+//! Scheduled maintenance windows for CORE Runtime.
+//!
+//! During a configured window the runtime refuses new model loads and runs
+//! routine heavy operations (audit rotation, KV compaction, cache GC,
+//! optional self-tests) so they don't collide with peak traffic.
+//! The active/next window is advertised through health and status.
+
+mod tasks;
+mod window;
+
+pub use tasks::{AuditRotationTask, CacheGcTask, KvCompactionTask, PoolBudgetTask, SelfTestTask};
+pub use window::MaintenanceWindow;
+
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// How long a KV sequence may sit idle before compaction frees it.
+pub const DEFAULT_KV_IDLE: std::time::Duration = std::time::Duration::from_secs(600);
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum MaintenanceError {
+    #[error("Model loads are refused during maintenance window (ends {ends_at})")]
+    LoadRefused { ends_at: String },
+
+    #[error("Invalid maintenance window: {0}")]
+    InvalidWindow(String),
+}
+
+/// Maintenance configuration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MaintenanceConfig {
+    #[serde(default)]
+    pub windows: Vec<MaintenanceWindow>,
+    /// Run FIPS self-tests once per window.
+    #[serde(default)]
+    pub run_self_tests: bool,
+    /// Idle time after which KV compaction frees a sequence, in seconds;
+    /// unset uses [`DEFAULT_KV_IDLE`].
+    #[serde(default)]
+    pub kv_idle_secs: Option<u64>,
+}
+
+impl MaintenanceConfig {
+    pub fn validate(&self) -> Result<(), MaintenanceError> {
+        self.windows.iter().try_for_each(MaintenanceWindow::validate)
+    }
+
+    pub fn kv_idle(&self) -> std::time::Duration {
+        self.kv_idle_secs.map_or(DEFAULT_KV_IDLE, std::time::Duration::from_secs)
+    }
+}
+
+/// Maintenance state advertised in health and status.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MaintenanceStatus {
+    pub active: bool,
+    /// End of the active window (RFC 3339), if any.
+    pub ends_at: Option<String>,
+    /// Start of the next window (RFC 3339), if any.
+    pub next_window_at: Option<String>,
+}
+
+/// Outcome of one maintenance task run.
+#[derive(Debug, Clone)]
+pub struct TaskOutcome {
+    pub task: &'static str,
+    pub result: Result<String, String>,
+}
+
+/// A routine operation executed once per maintenance window.
+#[async_trait::async_trait]
+pub trait MaintenanceTask: Send + Sync {
+    fn name(&self) -> &'static str;
+    /// Run the task. `Ok` carries a short summary for the log.
+    async fn run(&self) -> Result<String, String>;
+}
+
+/// Decides when maintenance is active and runs registered tasks.
+pub struct MaintenanceScheduler {
+    config: MaintenanceConfig,
+    tasks: Vec<Box<dyn MaintenanceTask>>,
+    /// Start of the window occurrence whose tasks already ran.
+    last_run: Mutex<Option<DateTime<Utc>>>,
+}
+
+impl MaintenanceScheduler {
+    pub fn new(config: MaintenanceConfig) -> Self {
+        let mut tasks: Vec<Box<dyn MaintenanceTask>> = Vec::new();
+        if config.run_self_tests {
+            tasks.push(Box::new(SelfTestTask));
+        }
+        Self { config, tasks, last_run: Mutex::new(None) }
+    }
+
+    /// Register an additional task to run during each window.
+    pub fn register_task(&mut self, task: Box<dyn MaintenanceTask>) {
+        self.tasks.push(task);
+    }
+
+    /// Active window (start, end) at `now`, if any.
+    pub fn active_window_at(&self, now: DateTime<Utc>) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        self.config.windows.iter().filter_map(|w| w.active_at(now)).min_by_key(|(s, _)| *s)
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active_window_at(Utc::now()).is_some()
+    }
+
+    /// Refuse model loads while a window is active.
+    pub fn check_model_load(&self) -> Result<(), MaintenanceError> {
+        match self.active_window_at(Utc::now()) {
+            Some((_, end)) => Err(MaintenanceError::LoadRefused { ends_at: end.to_rfc3339() }),
+            None => Ok(()),
+        }
+    }
+
+    /// Status snapshot at `now`.
+    pub fn status_at(&self, now: DateTime<Utc>) -> MaintenanceStatus {
+        let active = self.active_window_at(now);
+        let next = self.config.windows.iter().filter_map(|w| w.next_after(now)).min();
+        MaintenanceStatus {
+            active: active.is_some(),
+            ends_at: active.map(|(_, end)| end.to_rfc3339()),
+            next_window_at: next.map(|t| t.to_rfc3339()),
+        }
+    }
+
+    pub fn status(&self) -> MaintenanceStatus {
+        self.status_at(Utc::now())
+    }
+
+    /// Run tasks if a window is active at `now` and they haven't run for it yet.
+    pub async fn tick(&self, now: DateTime<Utc>) -> Vec<TaskOutcome> {
+        let Some((start, _)) = self.active_window_at(now) else {
+            return Vec::new();
+        };
+        {
+            let mut last = self.last_run.lock().unwrap_or_else(|e| e.into_inner());
+            if *last == Some(start) {
+                return Vec::new();
+            }
+            *last = Some(start);
+        }
+
+        let mut outcomes = Vec::with_capacity(self.tasks.len());
+        for task in &self.tasks {
+            let result = task.run().await;
+            match &result {
+                Ok(summary) => tracing::info!(task = task.name(), %summary, "Maintenance task done"),
+                Err(e) => tracing::warn!(task = task.name(), error = %e, "Maintenance task failed"),
+            }
+            outcomes.push(TaskOutcome { task: task.name(), result });
+        }
+        outcomes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(h: u32, m: u32) -> DateTime<Utc> {
+        // 2026-03-02 is a Monday
+        Utc.with_ymd_and_hms(2026, 3, 2, h, m, 0).unwrap()
+    }
+
+    fn nightly() -> MaintenanceWindow {
+        MaintenanceWindow { start_minute: 23 * 60, duration_minutes: 120, weekdays: vec![] }
+    }
+
+    #[test]
+    fn test_status_reports_next_window() {
+        let scheduler = MaintenanceScheduler::new(MaintenanceConfig {
+            windows: vec![nightly()],
+            ..Default::default()
+        });
+        let status = scheduler.status_at(at(12, 0));
+        assert!(!status.active);
+        assert!(status.next_window_at.unwrap().starts_with("2026-03-02T23:00"));
+    }
+
+    struct CountingTask(std::sync::Arc<std::sync::atomic::AtomicUsize>);
+
+    #[async_trait::async_trait]
+    impl MaintenanceTask for CountingTask {
+        fn name(&self) -> &'static str {
+            "counting"
+        }
+        async fn run(&self) -> Result<String, String> {
+            self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok("ok".into())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tick_runs_tasks_once_per_window() {
+        let runs = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let mut scheduler = MaintenanceScheduler::new(MaintenanceConfig {
+            windows: vec![nightly()],
+            ..Default::default()
+        });
+        scheduler.register_task(Box::new(CountingTask(runs.clone())));
+
+        assert!(scheduler.tick(at(12, 0)).await.is_empty());
+        assert_eq!(scheduler.tick(at(23, 5)).await.len(), 1);
+        assert!(scheduler.tick(at(23, 50)).await.is_empty());
+        assert_eq!(runs.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,108 @@
+//! Built-in maintenance tasks.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::Mutex;
+
+use super::MaintenanceTask;
+use crate::memory::ContextCache;
+use crate::models::ModelPool;
+use crate::scheduler::OutputCache;
+use crate::security::audit::audit_logger;
+use crate::security::fips_tests;
+
+/// Rotates the in-memory audit log, keeping the most recent events.
+pub struct AuditRotationTask {
+    pub keep_recent: usize,
+}
+
+#[async_trait::async_trait]
+impl MaintenanceTask for AuditRotationTask {
+    fn name(&self) -> &'static str {
+        "audit_rotation"
+    }
+
+    async fn run(&self) -> Result<String, String> {
+        let logger = audit_logger().ok_or("audit logger not initialized")?;
+        let rotated = logger.rotate(self.keep_recent).await;
+        Ok(format!("rotated {} events", rotated.len()))
+    }
+}
+
+/// Frees KV sequences that have been idle longer than `idle` in the KV
+/// caches attached to pooled models.
+pub struct KvCompactionTask {
+    pub pool: Arc<ModelPool>,
+    pub idle: Duration,
+}
+
+#[async_trait::async_trait]
+impl MaintenanceTask for KvCompactionTask {
+    fn name(&self) -> &'static str {
+        "kv_compaction"
+    }
+
+    async fn run(&self) -> Result<String, String> {
+        let freed = self.pool.compact_kv(self.idle).await;
+        Ok(format!("freed {} idle sequences", freed))
+    }
+}
+
+/// Keeps the model pool within its memory budget as KV caches grow.
+pub struct PoolBudgetTask {
+    pub pool: Arc<ModelPool>,
+}
+
+#[async_trait::async_trait]
+impl MaintenanceTask for PoolBudgetTask {
+    fn name(&self) -> &'static str {
+        "pool_budget"
+    }
+
+    async fn run(&self) -> Result<String, String> {
+        let enforcement = self.pool.enforce_budget().await;
+        Ok(format!(
+            "evicted {} KV sequences ({} bytes), {} models",
+            enforcement.kv_sequences_evicted,
+            enforcement.kv_bytes_reclaimed,
+            enforcement.models_evicted.len()
+        ))
+    }
+}
+
+/// Drops expired context and output cache entries.
+pub struct CacheGcTask {
+    pub context_cache: Arc<ContextCache>,
+    pub output_cache: Arc<Mutex<OutputCache>>,
+}
+
+#[async_trait::async_trait]
+impl MaintenanceTask for CacheGcTask {
+    fn name(&self) -> &'static str {
+        "cache_gc"
+    }
+
+    async fn run(&self) -> Result<String, String> {
+        self.context_cache.cleanup().await;
+        let mut output = self.output_cache.lock().await;
+        output.cleanup();
+        Ok(format!("{} output entries retained", output.len()))
+    }
+}
+
+/// Re-runs the FIPS 140-3 power-on self-tests.
+pub struct SelfTestTask;
+
+#[async_trait::async_trait]
+impl MaintenanceTask for SelfTestTask {
+    fn name(&self) -> &'static str {
+        "self_tests"
+    }
+
+    async fn run(&self) -> Result<String, String> {
+        fips_tests::run_power_on_self_tests()
+            .map(|_| "passed".to_string())
+            .map_err(|e| e.to_string())
+    }
+}
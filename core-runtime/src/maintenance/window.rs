@@ -0,0 +1,103 @@
+//! Recurring maintenance windows.
+
+use chrono::{DateTime, Datelike, Duration as ChronoDuration, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+
+use super::MaintenanceError;
+
+/// Minutes in a day; window start offsets must be below this.
+const MINUTES_PER_DAY: u32 = 24 * 60;
+
+/// A recurring window, expressed in UTC.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaintenanceWindow {
+    /// Start offset in minutes after 00:00 UTC.
+    pub start_minute: u32,
+    /// Window length in minutes. May cross midnight.
+    pub duration_minutes: u32,
+    /// Days the window opens on. Empty = every day.
+    #[serde(default)]
+    pub weekdays: Vec<Weekday>,
+}
+
+impl MaintenanceWindow {
+    pub fn validate(&self) -> Result<(), MaintenanceError> {
+        if self.start_minute >= MINUTES_PER_DAY {
+            return Err(MaintenanceError::InvalidWindow("start_minute must be < 1440".into()));
+        }
+        if self.duration_minutes == 0 || self.duration_minutes > MINUTES_PER_DAY {
+            return Err(MaintenanceError::InvalidWindow(
+                "duration_minutes must be in 1..=1440".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn opens_on(&self, day: Weekday) -> bool {
+        self.weekdays.is_empty() || self.weekdays.contains(&day)
+    }
+
+    /// Occurrence of this window opening on the same UTC date as `day`.
+    fn occurrence(&self, day: DateTime<Utc>) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        if !self.opens_on(day.weekday()) {
+            return None;
+        }
+        let midnight = day.date_naive().and_hms_opt(0, 0, 0)?.and_utc();
+        let start = midnight + ChronoDuration::minutes(self.start_minute as i64);
+        Some((start, start + ChronoDuration::minutes(self.duration_minutes as i64)))
+    }
+
+    /// The occurrence containing `now`, checking yesterday for midnight spill-over.
+    pub fn active_at(&self, now: DateTime<Utc>) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        [now - ChronoDuration::days(1), now]
+            .into_iter()
+            .filter_map(|day| self.occurrence(day))
+            .find(|(start, end)| *start <= now && now < *end)
+    }
+
+    /// Next opening strictly after `now`, searching up to a week ahead.
+    pub fn next_after(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        (0..=7)
+            .filter_map(|d| self.occurrence(now + ChronoDuration::days(d)))
+            .map(|(start, _)| start)
+            .find(|start| *start > now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(h: u32, m: u32) -> DateTime<Utc> {
+        // 2026-03-02 is a Monday
+        Utc.with_ymd_and_hms(2026, 3, 2, h, m, 0).unwrap()
+    }
+
+    fn nightly() -> MaintenanceWindow {
+        MaintenanceWindow { start_minute: 23 * 60, duration_minutes: 120, weekdays: vec![] }
+    }
+
+    #[test]
+    fn test_window_active_and_spans_midnight() {
+        let w = nightly();
+        assert!(w.active_at(at(23, 30)).is_some());
+        assert!(w.active_at(at(0, 30)).is_some());
+        assert!(w.active_at(at(1, 0)).is_none());
+        assert!(w.active_at(at(12, 0)).is_none());
+    }
+
+    #[test]
+    fn test_weekday_filter() {
+        let w = MaintenanceWindow { weekdays: vec![Weekday::Sun], ..nightly() };
+        assert!(w.active_at(at(23, 30)).is_none());
+        // Sunday 23:00 spills into Monday 00:30
+        assert!(w.active_at(at(0, 30)).is_some());
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_window() {
+        let w = MaintenanceWindow { start_minute: 1440, duration_minutes: 10, weekdays: vec![] };
+        assert!(w.validate().is_err());
+    }
+}
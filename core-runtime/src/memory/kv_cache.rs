@@ -5,44 +5,26 @@
 //! and efficient memory management through page-based allocation.
 //!
 //! # Panic Safety
-//! This module uses poison-recovering lock guards to maintain cache availability
-//! even if a thread panics while holding a lock. A poisoned lock logs a warning
-//! but continues operation rather than propagating the panic.
+//! This module uses poison-recovering lock guards ([`crate::locks`]) to
+//! maintain cache availability even if a thread panics while holding a
+//! lock. A recovery is logged and counted rather than propagating the panic.
+//!
+//! # Lock Order
+//! `sequences`, then `page_table`, then `access_order`. Eviction runs
+//! under the locks its caller already holds instead of re-taking them.
 
 use std::collections::{HashMap, VecDeque};
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::{Arc, Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
-use std::time::Instant;
-
-/// Acquire a mutex lock, recovering from poison if a thread panicked.
-#[inline]
-fn lock_or_recover<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
-    mutex.lock().unwrap_or_else(|poisoned| {
-        tracing::warn!("KV cache mutex poisoned, recovering");
-        poisoned.into_inner()
-    })
-}
-
-/// Acquire a read lock, recovering from poison if a thread panicked.
-#[inline]
-fn read_or_recover<T>(rwlock: &RwLock<T>) -> RwLockReadGuard<'_, T> {
-    rwlock.read().unwrap_or_else(|poisoned| {
-        tracing::warn!("KV cache RwLock poisoned, recovering for read");
-        poisoned.into_inner()
-    })
-}
-
-/// Acquire a write lock, recovering from poison if a thread panicked.
-#[inline]
-fn write_or_recover<T>(rwlock: &RwLock<T>) -> RwLockWriteGuard<'_, T> {
-    rwlock.write().unwrap_or_else(|poisoned| {
-        tracing::warn!("KV cache RwLock poisoned, recovering for write");
-        poisoned.into_inner()
-    })
-}
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
 
 use super::kv_quant::Q8KvStore;
 use super::paged::{PageId, PageTable, PAGE_TOKENS};
+use crate::locks::{lock_or_recover, read_or_recover, write_or_recover};
+
+const SEQUENCES: &str = "kv_cache.sequences";
+const PAGE_TABLE: &str = "kv_cache.page_table";
+const ACCESS_ORDER: &str = "kv_cache.access_order";
 
 /// Configuration for the KV Cache Manager.
 #[derive(Debug, Clone)]
@@ -183,8 +165,8 @@ impl KvCacheManager {
             quant_store,
         };
 
-        write_or_recover(&self.sequences).insert(id, entry);
-        lock_or_recover(&self.access_order).push_back(id);
+        write_or_recover(SEQUENCES, &self.sequences).insert(id, entry);
+        lock_or_recover(ACCESS_ORDER, &self.access_order).push_back(id);
 
         id
     }
@@ -196,43 +178,44 @@ impl KvCacheManager {
         keys: &[f32],
         values: &[f32],
     ) -> Result<(), KvCacheError> {
-        let mut sequences = write_or_recover(&self.sequences);
-        let entry = sequences
-            .get_mut(&seq_id)
-            .ok_or(KvCacheError::SequenceNotFound(seq_id.0))?;
-
-        entry.last_access = Instant::now();
-        entry.access_count += 1;
-
-        let seq_pos = entry.seq_len;
+        let _tag = crate::telemetry::tag_scope("kv_cache");
+        let mut sequences = write_or_recover(SEQUENCES, &self.sequences);
+        let (seq_pos, needs_page) = {
+            let entry = sequences
+                .get_mut(&seq_id)
+                .ok_or(KvCacheError::SequenceNotFound(seq_id.0))?;
+            entry.last_access = Instant::now();
+            entry.access_count += 1;
+            (entry.seq_len, entry.seq_len % PAGE_TOKENS == 0 || entry.page_ids.is_empty())
+        };
         let slot = seq_pos % PAGE_TOKENS;
 
-        // Allocate new page if needed
-        if slot == 0 || entry.page_ids.is_empty() {
-            let mut page_table = write_or_recover(&self.page_table);
-
-            // Try to allocate, evict if necessary
+        let mut page_table = write_or_recover(PAGE_TABLE, &self.page_table);
+        // Allocate new page if needed, evicting another sequence if full
+        let new_page = if needs_page {
             let page_id = match page_table.allocate(seq_pos) {
                 Some(id) => id,
                 None => {
-                    drop(page_table);
-                    self.evict_lru()?;
-                    write_or_recover(&self.page_table)
+                    self.evict_lru(&mut sequences, &mut page_table, Some(seq_id));
+                    page_table
                         .allocate(seq_pos)
                         .ok_or(KvCacheError::MemoryExhausted)?
                 }
             };
-
-            entry.page_ids.push(page_id);
+            Some(page_id)
+        } else {
+            None
+        };
+        if let Some(page) = page_table.get_mut(seq_pos) {
+            page.write(slot, keys, values);
         }
+        drop(page_table);
 
-        // Write to page with mutable access
-        {
-            let mut page_table = write_or_recover(&self.page_table);
-            if let Some(page) = page_table.get_mut(seq_pos) {
-                page.write(slot, keys, values);
-            }
-        }
+        // Eviction never picks the sequence being appended to
+        let entry = sequences
+            .get_mut(&seq_id)
+            .ok_or(KvCacheError::SequenceNotFound(seq_id.0))?;
+        entry.page_ids.extend(new_page);
 
         // Store in per-sequence quantized format if enabled
         if let Some(ref mut qs) = entry.quant_store {
@@ -255,7 +238,7 @@ impl KvCacheManager {
         keys_out: &mut [f32],
         values_out: &mut [f32],
     ) -> Result<(), KvCacheError> {
-        let mut sequences = write_or_recover(&self.sequences);
+        let mut sequences = write_or_recover(SEQUENCES, &self.sequences);
         let entry = sequences
             .get_mut(&seq_id)
             .ok_or(KvCacheError::SequenceNotFound(seq_id.0))?;
@@ -280,7 +263,7 @@ impl KvCacheManager {
         }
 
         // Fall back to page table
-        let page_table = read_or_recover(&self.page_table);
+        let page_table = read_or_recover(PAGE_TABLE, &self.page_table);
         if let Some(page) = page_table.get(pos) {
             let slot = pos % PAGE_TOKENS;
             keys_out.copy_from_slice(page.read_keys(slot));
@@ -298,7 +281,7 @@ impl KvCacheManager {
         query: &[f32],
         scores_out: &mut [f32],
     ) -> Result<(), KvCacheError> {
-        let sequences = read_or_recover(&self.sequences);
+        let sequences = read_or_recover(SEQUENCES, &self.sequences);
         let entry = sequences
             .get(&seq_id)
             .ok_or(KvCacheError::SequenceNotFound(seq_id.0))?;
@@ -316,7 +299,7 @@ impl KvCacheManager {
         drop(sequences);
 
         // Fall back to page-by-page computation
-        let page_table = read_or_recover(&self.page_table);
+        let page_table = read_or_recover(PAGE_TABLE, &self.page_table);
         for pos in 0..seq_len {
             if let Some(page) = page_table.get(pos) {
                 let slot = pos % PAGE_TOKENS;
@@ -331,23 +314,53 @@ impl KvCacheManager {
 
     /// Free a sequence and its pages.
     pub fn free_sequence(&self, seq_id: SequenceId) -> Result<(), KvCacheError> {
-        let mut sequences = write_or_recover(&self.sequences);
+        let mut sequences = write_or_recover(SEQUENCES, &self.sequences);
         let entry = sequences
             .remove(&seq_id)
             .ok_or(KvCacheError::SequenceNotFound(seq_id.0))?;
 
         // Free pages
-        let mut page_table = write_or_recover(&self.page_table);
+        let mut page_table = write_or_recover(PAGE_TABLE, &self.page_table);
         page_table.free(&entry.page_ids);
 
         // Remove from access order
-        if let Ok(mut order) = self.access_order.lock() {
-            order.retain(|&id| id != seq_id);
-        }
+        lock_or_recover(ACCESS_ORDER, &self.access_order).retain(|&id| id != seq_id);
 
         Ok(())
     }
 
+    /// Free every sequence not accessed within `idle`. Returns the count freed.
+    pub fn compact(&self, idle: Duration) -> usize {
+        let stale: Vec<SequenceId> = read_or_recover(SEQUENCES, &self.sequences)
+            .iter()
+            .filter(|(_, entry)| entry.last_access.elapsed() >= idle)
+            .map(|(id, _)| *id)
+            .collect();
+        stale
+            .into_iter()
+            .filter(|id| self.free_sequence(*id).is_ok())
+            .count()
+    }
+
+    /// Evict least recently used sequences until the pages in use fit in
+    /// `target_bytes`, then release free pages so [`memory_usage`] drops.
+    /// Returns the number of sequences evicted.
+    ///
+    /// [`memory_usage`]: Self::memory_usage
+    pub fn shrink_to(&self, target_bytes: usize) -> usize {
+        let page_bytes = self.page_bytes();
+        let mut sequences = write_or_recover(SEQUENCES, &self.sequences);
+        let mut page_table = write_or_recover(PAGE_TABLE, &self.page_table);
+        let mut evicted = 0;
+        while (page_table.page_count() - page_table.free_count()) * page_bytes > target_bytes
+            && self.evict_lru(&mut sequences, &mut page_table, None)
+        {
+            evicted += 1;
+        }
+        page_table.release_free();
+        evicted
+    }
+
     /// Get current statistics.
     pub fn stats(&self) -> KvCacheStats {
         let stats = self.stats.clone();
@@ -366,7 +379,7 @@ impl KvCacheManager {
 
     /// Get sequence length.
     pub fn seq_len(&self, seq_id: SequenceId) -> Result<usize, KvCacheError> {
-        let sequences = read_or_recover(&self.sequences);
+        let sequences = read_or_recover(SEQUENCES, &self.sequences);
         let entry = sequences
             .get(&seq_id)
             .ok_or(KvCacheError::SequenceNotFound(seq_id.0))?;
@@ -375,33 +388,47 @@ impl KvCacheManager {
 
     /// Check if sequence exists.
     pub fn has_sequence(&self, seq_id: SequenceId) -> bool {
-        read_or_recover(&self.sequences).contains_key(&seq_id)
+        read_or_recover(SEQUENCES, &self.sequences).contains_key(&seq_id)
     }
 
     /// Get number of active sequences.
     pub fn active_sequences(&self) -> usize {
-        read_or_recover(&self.sequences).len()
+        read_or_recover(SEQUENCES, &self.sequences).len()
     }
 
     /// Get memory usage in bytes.
     pub fn memory_usage(&self) -> usize {
-        let page_table = read_or_recover(&self.page_table);
-        let page_count = page_table.page_count();
-        page_count * PAGE_TOKENS * self.config.hidden_dim * 2 * std::mem::size_of::<f32>()
+        let page_table = read_or_recover(PAGE_TABLE, &self.page_table);
+        page_table.page_count() * self.page_bytes()
+    }
+
+    /// Bytes held by one page of keys and values.
+    fn page_bytes(&self) -> usize {
+        PAGE_TOKENS * self.config.hidden_dim * 2 * std::mem::size_of::<f32>()
     }
 
-    /// Evict least recently used sequence.
-    fn evict_lru(&self) -> Result<(), KvCacheError> {
+    /// Evict the least recently used sequence other than `keep`, under the
+    /// `sequences` and `page_table` locks the caller holds. Returns whether
+    /// a sequence was evicted.
+    fn evict_lru(
+        &self,
+        sequences: &mut HashMap<SequenceId, SequenceEntry>,
+        page_table: &mut PageTable,
+        keep: Option<SequenceId>,
+    ) -> bool {
         let victim_id = {
-            let mut order = lock_or_recover(&self.access_order);
-            order.pop_front()
+            let mut order = lock_or_recover(ACCESS_ORDER, &self.access_order);
+            let pos = order.iter().position(|&id| Some(id) != keep);
+            pos.and_then(|pos| order.remove(pos))
         };
 
-        if let Some(id) = victim_id {
-            self.free_sequence(id)?;
+        match victim_id.and_then(|id| sequences.remove(&id)) {
+            Some(entry) => {
+                page_table.free(&entry.page_ids);
+                true
+            }
+            None => false,
         }
-
-        Ok(())
     }
 
     /// Compute dot product of two vectors.
@@ -411,10 +438,13 @@ impl KvCacheManager {
 
     /// Reset all cache state.
     pub fn reset(&self) {
-        let mut sequences = write_or_recover(&self.sequences);
-        sequences.clear();
+        let mut sequences = write_or_recover(SEQUENCES, &self.sequences);
+        let mut page_table = write_or_recover(PAGE_TABLE, &self.page_table);
+        for (_, entry) in sequences.drain() {
+            page_table.free(&entry.page_ids);
+        }
 
-        let mut order = lock_or_recover(&self.access_order);
+        let mut order = lock_or_recover(ACCESS_ORDER, &self.access_order);
         order.clear();
     }
 }
@@ -496,6 +526,67 @@ mod tests {
         assert!(manager.has_sequence(seq2));
     }
 
+    #[test]
+    fn test_append_evicts_under_held_locks() {
+        let config = KvCacheConfig {
+            hidden_dim: 8,
+            max_pages: 2,
+            max_seq_len: 64,
+            ..Default::default()
+        };
+        let manager = KvCacheManager::new(config);
+        let old = manager.allocate_sequence();
+        let new = manager.allocate_sequence();
+        let kv = vec![1.0f32; 8];
+
+        for _ in 0..2 * PAGE_TOKENS {
+            manager.append_kv(old, &kv, &kv).unwrap();
+            manager.append_kv(new, &kv, &kv).unwrap();
+        }
+        // The third page needs an eviction: the older sequence goes, not
+        // the one being appended to
+        manager.append_kv(new, &kv, &kv).unwrap();
+        assert!(!manager.has_sequence(old));
+        assert_eq!(manager.seq_len(new).unwrap(), 2 * PAGE_TOKENS + 1);
+    }
+
+    #[test]
+    fn test_shrink_to_evicts_lru_and_releases_pages() {
+        let config = KvCacheConfig {
+            hidden_dim: 8,
+            max_pages: 8,
+            max_seq_len: 64,
+            ..Default::default()
+        };
+        let manager = KvCacheManager::new(config);
+        let page_bytes = PAGE_TOKENS * 8 * 2 * std::mem::size_of::<f32>();
+        let old = manager.allocate_sequence();
+        let new = manager.allocate_sequence();
+        let kv = vec![1.0f32; 8];
+        for _ in 0..2 * PAGE_TOKENS {
+            manager.append_kv(old, &kv, &kv).unwrap();
+        }
+        assert_eq!(manager.memory_usage(), 2 * page_bytes);
+
+        // Within budget: nothing to do
+        assert_eq!(manager.shrink_to(2 * page_bytes), 0);
+        assert_eq!(manager.shrink_to(page_bytes), 1);
+        assert!(!manager.has_sequence(old));
+        assert!(manager.has_sequence(new));
+        assert_eq!(manager.memory_usage(), 0);
+    }
+
+    #[test]
+    fn test_compact_frees_idle_sequences() {
+        let manager = KvCacheManager::new(KvCacheConfig::default());
+        let seq = manager.allocate_sequence();
+
+        assert_eq!(manager.compact(Duration::from_secs(3600)), 0);
+        assert!(manager.has_sequence(seq));
+        assert_eq!(manager.compact(Duration::ZERO), 1);
+        assert!(!manager.has_sequence(seq));
+    }
+
     #[test]
     fn test_attention_scores() {
         let config = KvCacheConfig {
@@ -1,7 +1,8 @@
 //! Memory management module for CORE Runtime.
 //!
 //! Provides pooled memory allocation, GPU memory tracking, context caching,
-//! arena allocation, paged KV-cache, and resource limit enforcement.
+//! arena allocation, paged KV-cache with intra-batch prefix sharing, and
+//! resource limit enforcement.
 
 mod arena;
 mod cache;
@@ -11,6 +12,7 @@ pub mod kv_quant;
 mod limits;
 pub mod paged;
 mod pool;
+pub mod prefix_share;
 pub mod prompt_cache;
 
 pub use arena::{Arena, ArenaPool, ArenaSlice};
@@ -23,4 +25,5 @@ pub use kv_quant::{compute_scale, dequantize, quantize_to, Q8KvStore};
 pub use limits::{ResourceLimits, ResourceLimitsConfig};
 pub use paged::{Page, PageId, PageTable, PAGE_TOKENS};
 pub use pool::{MemoryPool, MemoryPoolConfig, PooledBuffer};
+pub use prefix_share::{PrefixShareStats, SharedKvPool};
 pub use prompt_cache::{CachedKv, PromptCache};
@@ -156,6 +156,13 @@ impl PageTable {
         Some(id)
     }
 
+    /// Drop the buffers of free pages. Returns the number released.
+    pub fn release_free(&mut self) -> usize {
+        let free: Vec<PageId> = self.free_pages.drain(..).collect();
+        self.pages.retain(|p| !free.contains(&p.id));
+        free.len()
+    }
+
     pub fn page_count(&self) -> usize { self.pages.len() }
     pub fn free_count(&self) -> usize { self.free_pages.len() }
 }
@@ -0,0 +1,438 @@
+//! Intra-batch prefix sharing for paged KV storage.
+//!
+//! Batch members with a common prompt prefix (typically an identical
+//! system prompt) map the same physical pages instead of prefilling it
+//! again. Pages are reference counted. A member that writes into a page
+//! it shares first copies the slots it kept to a private page
+//! (copy-on-write), so divergence never disturbs other members.
+//!
+//! Attaching a member reserves pages for the rest of its prompt and
+//! advertises their tokens right away, so members admitted in the same
+//! step share as well. The executor must therefore prefill members in
+//! attach order. Pages are indexed by a hash of every prompt token before
+//! them, and matches are confirmed token by token, so hash collisions
+//! cannot alias unrelated KV data. The last prompt token is always
+//! recomputed to produce its logits.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+use super::kv_cache::{KvCacheError, SequenceId};
+use super::paged::{Page, PageId, PAGE_TOKENS};
+
+/// Parent hash of the first page of every prompt.
+const ROOT: u64 = 0;
+
+/// Cumulative sharing statistics.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PrefixShareStats {
+    /// Sequences attached.
+    pub sequences: u64,
+    /// Prompt tokens prefilled by their own member.
+    pub prefill_tokens: u64,
+    /// Prompt tokens served from shared pages instead of prefill.
+    pub shared_tokens: u64,
+    /// Pages copied on a write to a shared page.
+    pub cow_copies: u64,
+}
+
+impl PrefixShareStats {
+    /// Fraction of prompt tokens whose prefill was skipped.
+    pub fn saved_ratio(&self) -> f64 {
+        let total = self.prefill_tokens + self.shared_tokens;
+        if total == 0 {
+            return 0.0;
+        }
+        self.shared_tokens as f64 / total as f64
+    }
+}
+
+/// Prompt tokens a page holds (or is reserved for), in slot order.
+#[derive(Debug)]
+struct PageMeta {
+    parent: u64,
+    tokens: Vec<u32>,
+    /// Member that fills the page; other members copy before writing.
+    writer: SequenceId,
+}
+
+#[derive(Debug)]
+struct SharedSequence {
+    prompt: Vec<u32>,
+    pages: Vec<PageId>,
+    len: usize,
+}
+
+/// Reference-counted page pool shared by the members of one batch.
+#[derive(Debug)]
+pub struct SharedKvPool {
+    hidden_dim: usize,
+    max_pages: usize,
+    pages: Vec<Page>,
+    refs: Vec<u32>,
+    meta: Vec<Option<PageMeta>>,
+    free: Vec<PageId>,
+    index: HashMap<u64, Vec<PageId>>,
+    sequences: HashMap<SequenceId, SharedSequence>,
+    stats: PrefixShareStats,
+}
+
+impl SharedKvPool {
+    pub fn new(hidden_dim: usize, max_pages: usize) -> Self {
+        Self {
+            hidden_dim,
+            max_pages,
+            pages: Vec::new(),
+            refs: Vec::new(),
+            meta: Vec::new(),
+            free: Vec::new(),
+            index: HashMap::new(),
+            sequences: HashMap::new(),
+            stats: PrefixShareStats::default(),
+        }
+    }
+
+    /// Register `seq` with its prompt, map the longest prefix other members
+    /// hold and reserve pages for the remainder. Returns the number of
+    /// prompt tokens reused; prefill continues from that position.
+    pub fn attach(&mut self, seq: SequenceId, prompt: &[u32]) -> Result<usize, KvCacheError> {
+        self.release(seq);
+        let mut pages = Vec::new();
+        let mut reused = 0;
+        let mut parent = ROOT;
+        for block in prompt.chunks(PAGE_TOKENS) {
+            let Some((page, matched)) = self.best_match(parent, block) else {
+                break;
+            };
+            self.refs[page.0] += 1;
+            pages.push(page);
+            reused += matched;
+            if matched < block.len() {
+                break;
+            }
+            parent = chain(parent, block);
+        }
+
+        // A partially shared last page is reserved on its copy-on-write
+        let mut start = pages.len() * PAGE_TOKENS;
+        while start < prompt.len() {
+            let page = match self.allocate() {
+                Ok(page) => page,
+                Err(e) => {
+                    for page in pages {
+                        self.unref(page);
+                    }
+                    return Err(e);
+                }
+            };
+            let end = (start + PAGE_TOKENS).min(prompt.len());
+            self.meta[page.0] = Some(PageMeta {
+                parent,
+                tokens: prompt[start..end].to_vec(),
+                writer: seq,
+            });
+            self.index.entry(parent).or_default().push(page);
+            pages.push(page);
+            parent = chain(parent, &prompt[start..end]);
+            start = end;
+        }
+
+        let reused = reused.min(prompt.len().saturating_sub(1));
+        self.stats.sequences += 1;
+        self.stats.shared_tokens += reused as u64;
+        self.stats.prefill_tokens += (prompt.len() - reused) as u64;
+        self.sequences.insert(
+            seq,
+            SharedSequence {
+                prompt: prompt.to_vec(),
+                pages,
+                len: reused,
+            },
+        );
+        Ok(reused)
+    }
+
+    /// Append one position of KV data to `seq`, copying a shared page
+    /// before writing into it.
+    pub fn append(
+        &mut self,
+        seq: SequenceId,
+        keys: &[f32],
+        values: &[f32],
+    ) -> Result<(), KvCacheError> {
+        let entry = self
+            .sequences
+            .get(&seq)
+            .ok_or(KvCacheError::SequenceNotFound(seq.0))?;
+        let pos = entry.len;
+        let slot = pos % PAGE_TOKENS;
+        let page_start = pos - slot;
+        let page_end = (page_start + PAGE_TOKENS).min(entry.prompt.len());
+        let ours = entry.prompt.get(pos..page_end).unwrap_or_default().to_vec();
+        let mapped = entry.pages.get(pos / PAGE_TOKENS).copied();
+
+        let page = match mapped {
+            // Past the prompt: generated tokens are never shared
+            None => {
+                let page = self.allocate()?;
+                if let Some(entry) = self.sequences.get_mut(&seq) {
+                    entry.pages.push(page);
+                }
+                page
+            }
+            Some(shared) if self.refs[shared.0] > 1 && !self.writes(shared, seq) => {
+                let kept: Vec<u32> = self.meta[shared.0]
+                    .as_ref()
+                    .map(|meta| meta.tokens.iter().take(slot).copied().collect())
+                    .unwrap_or_default();
+                let page = self.copy_on_write(shared, slot)?;
+                if let Some(entry) = self.sequences.get_mut(&seq) {
+                    entry.pages[pos / PAGE_TOKENS] = page;
+                    if page_start < entry.prompt.len() {
+                        let parent = entry.prompt[..page_start]
+                            .chunks(PAGE_TOKENS)
+                            .fold(ROOT, chain);
+                        self.meta[page.0] = Some(PageMeta {
+                            parent,
+                            tokens: kept,
+                            writer: seq,
+                        });
+                        self.index.entry(parent).or_default().push(page);
+                    }
+                }
+                page
+            }
+            Some(owned) => owned,
+        };
+
+        // Keep the advertised tokens in step with what the page now holds
+        if let Some(meta) = self.meta[page.0].as_mut() {
+            let kept = meta.tokens.get(slot..).unwrap_or_default();
+            let stale = !kept.starts_with(&ours) || (ours.is_empty() && !kept.is_empty());
+            if stale && meta.tokens.len() >= slot {
+                meta.tokens.truncate(slot);
+                meta.tokens.extend_from_slice(&ours);
+            }
+            meta.writer = seq;
+        }
+        self.pages[page.0].write(slot, keys, values);
+        if let Some(entry) = self.sequences.get_mut(&seq) {
+            entry.len += 1;
+        }
+        Ok(())
+    }
+
+    /// Read the KV data at `pos` of `seq`.
+    pub fn read(
+        &self,
+        seq: SequenceId,
+        pos: usize,
+        keys_out: &mut [f32],
+        values_out: &mut [f32],
+    ) -> Result<(), KvCacheError> {
+        let entry = self
+            .sequences
+            .get(&seq)
+            .ok_or(KvCacheError::SequenceNotFound(seq.0))?;
+        if pos >= entry.len {
+            return Err(KvCacheError::PositionOutOfBounds {
+                pos,
+                seq_len: entry.len,
+            });
+        }
+        let page = entry
+            .pages
+            .get(pos / PAGE_TOKENS)
+            .ok_or(KvCacheError::PageNotFound)?;
+        let slot = pos % PAGE_TOKENS;
+        keys_out.copy_from_slice(self.pages[page.0].read_keys(slot));
+        values_out.copy_from_slice(self.pages[page.0].read_values(slot));
+        Ok(())
+    }
+
+    /// Drop `seq`'s page references, freeing pages no member still maps.
+    pub fn release(&mut self, seq: SequenceId) {
+        let Some(entry) = self.sequences.remove(&seq) else {
+            return;
+        };
+        for page in entry.pages {
+            self.unref(page);
+        }
+    }
+
+    /// Positions stored for `seq`.
+    pub fn seq_len(&self, seq: SequenceId) -> Option<usize> {
+        self.sequences.get(&seq).map(|entry| entry.len)
+    }
+
+    /// Physical pages currently mapped by at least one sequence.
+    pub fn pages_in_use(&self) -> usize {
+        self.refs.iter().filter(|&&refs| refs > 0).count()
+    }
+
+    pub fn stats(&self) -> PrefixShareStats {
+        self.stats
+    }
+
+    /// Indexed page under `parent` sharing the most leading tokens with `block`.
+    fn best_match(&self, parent: u64, block: &[u32]) -> Option<(PageId, usize)> {
+        self.index
+            .get(&parent)?
+            .iter()
+            .filter_map(|&page| {
+                let meta = self.meta[page.0]
+                    .as_ref()
+                    .filter(|meta| meta.parent == parent)?;
+                let matched = meta
+                    .tokens
+                    .iter()
+                    .zip(block)
+                    .take_while(|(a, b)| a == b)
+                    .count();
+                (matched > 0).then_some((page, matched))
+            })
+            .max_by_key(|&(_, matched)| matched)
+    }
+
+    fn writes(&self, page: PageId, seq: SequenceId) -> bool {
+        self.meta[page.0]
+            .as_ref()
+            .is_some_and(|meta| meta.writer == seq)
+    }
+
+    /// Give the caller a private copy of the first `slots` slots of `shared`.
+    fn copy_on_write(&mut self, shared: PageId, slots: usize) -> Result<PageId, KvCacheError> {
+        let page = self.allocate()?;
+        let mut keys = vec![0.0; self.hidden_dim];
+        let mut values = vec![0.0; self.hidden_dim];
+        for slot in 0..slots {
+            keys.copy_from_slice(self.pages[shared.0].read_keys(slot));
+            values.copy_from_slice(self.pages[shared.0].read_values(slot));
+            self.pages[page.0].write(slot, &keys, &values);
+        }
+        self.unref(shared);
+        self.stats.cow_copies += 1;
+        Ok(page)
+    }
+
+    fn allocate(&mut self) -> Result<PageId, KvCacheError> {
+        let page = match self.free.pop() {
+            Some(page) => page,
+            None if self.pages.len() < self.max_pages => {
+                let page = PageId(self.pages.len());
+                self.pages.push(Page::new(page, self.hidden_dim));
+                self.refs.push(0);
+                self.meta.push(None);
+                page
+            }
+            None => return Err(KvCacheError::MemoryExhausted),
+        };
+        self.refs[page.0] = 1;
+        Ok(page)
+    }
+
+    fn unref(&mut self, page: PageId) {
+        self.refs[page.0] = self.refs[page.0].saturating_sub(1);
+        if self.refs[page.0] > 0 {
+            return;
+        }
+        if let Some(meta) = self.meta[page.0].take() {
+            if let Some(pages) = self.index.get_mut(&meta.parent) {
+                pages.retain(|&p| p != page);
+                if pages.is_empty() {
+                    self.index.remove(&meta.parent);
+                }
+            }
+        }
+        self.pages[page.0].reset();
+        self.free.push(page);
+    }
+}
+
+fn chain(parent: u64, block: &[u32]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    parent.hash(&mut hasher);
+    block.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prefill(pool: &mut SharedKvPool, seq: SequenceId, prompt: &[u32]) -> usize {
+        let reused = pool.attach(seq, prompt).unwrap();
+        for &token in &prompt[reused..] {
+            pool.append(seq, &[token as f32; 2], &[token as f32; 2])
+                .unwrap();
+        }
+        reused
+    }
+
+    fn key_at(pool: &SharedKvPool, seq: SequenceId, pos: usize) -> f32 {
+        let (mut k, mut v) = ([0.0; 2], [0.0; 2]);
+        pool.read(seq, pos, &mut k, &mut v).unwrap();
+        k[0]
+    }
+
+    #[test]
+    fn test_identical_prefix_shares_full_pages() {
+        let mut pool = SharedKvPool::new(2, 16);
+        let system: Vec<u32> = (100..132).collect();
+        let a = [system.clone(), vec![1, 2]].concat();
+        let b = [system.clone(), vec![3]].concat();
+
+        assert_eq!(prefill(&mut pool, SequenceId(1), &a), 0);
+        assert_eq!(pool.pages_in_use(), 3);
+        assert_eq!(prefill(&mut pool, SequenceId(2), &b), 32);
+        // Two shared system pages plus one private page each
+        assert_eq!(pool.pages_in_use(), 4);
+        assert_eq!(key_at(&pool, SequenceId(2), 31), 131.0);
+        assert_eq!(key_at(&pool, SequenceId(2), 32), 3.0);
+        assert_eq!(key_at(&pool, SequenceId(1), 32), 1.0);
+        assert_eq!(pool.stats().shared_tokens, 32);
+        assert_eq!(pool.stats().prefill_tokens, 35);
+    }
+
+    #[test]
+    fn test_divergence_inside_page_copies_on_write() {
+        let mut pool = SharedKvPool::new(2, 16);
+        let a: Vec<u32> = (0..10).collect();
+        let b: Vec<u32> = (0..6).chain([50, 51]).collect();
+        // Attached together: b matches a's reserved page before either prefills
+        assert_eq!(pool.attach(SequenceId(1), &a).unwrap(), 0);
+        assert_eq!(pool.attach(SequenceId(2), &b).unwrap(), 6);
+        for &token in &a {
+            pool.append(SequenceId(1), &[token as f32; 2], &[0.0; 2])
+                .unwrap();
+        }
+        for &token in &b[6..] {
+            pool.append(SequenceId(2), &[token as f32; 2], &[0.0; 2])
+                .unwrap();
+        }
+
+        assert_eq!(pool.stats().cow_copies, 1);
+        assert_eq!(key_at(&pool, SequenceId(2), 5), 5.0);
+        assert_eq!(key_at(&pool, SequenceId(2), 6), 50.0);
+        // The original member is untouched by the divergent write
+        assert_eq!(key_at(&pool, SequenceId(1), 6), 6.0);
+    }
+
+    #[test]
+    fn test_release_frees_pages_and_recomputes_last_token() {
+        let mut pool = SharedKvPool::new(2, 16);
+        let prompt: Vec<u32> = (0..16).collect();
+        prefill(&mut pool, SequenceId(1), &prompt);
+        // A full match still recomputes the final prompt token
+        assert_eq!(prefill(&mut pool, SequenceId(2), &prompt), 15);
+
+        pool.release(SequenceId(1));
+        pool.release(SequenceId(2));
+        assert_eq!(pool.pages_in_use(), 0);
+        assert_eq!(pool.attach(SequenceId(3), &prompt).unwrap(), 0);
+        assert!(SharedKvPool::new(2, 0)
+            .attach(SequenceId(4), &prompt)
+            .is_err());
+    }
+}
@@ -9,6 +9,7 @@ use std::collections::HashMap;
 #[derive(Debug, Clone)]
 pub struct CachedKv {
     _token_hash: [u8; 32],
+    token_len: usize,
     kv_data: Vec<u8>,
     seq_len: usize,
     last_used: u64,
@@ -74,6 +75,7 @@ impl PromptCache {
             hash,
             CachedKv {
                 _token_hash: hash,
+                token_len: tokens.len(),
                 kv_data,
                 seq_len,
                 last_used: self.access_counter,
@@ -82,8 +84,17 @@ impl PromptCache {
     }
 
     /// Find longest cached prefix of tokens. Returns (prefix_len, cloned entry).
+    /// Only lengths some entry was stored under are hashed.
     pub fn find_prefix(&mut self, tokens: &[u32]) -> Option<(usize, CachedKv)> {
-        for len in (1..=tokens.len()).rev() {
+        let mut lens: Vec<usize> = self
+            .entries
+            .values()
+            .map(|e| e.token_len)
+            .filter(|&len| len >= 1 && len <= tokens.len())
+            .collect();
+        lens.sort_unstable_by(|a, b| b.cmp(a));
+        lens.dedup();
+        for len in lens {
             let hash = Self::hash_tokens(&tokens[..len]);
             if self.entries.contains_key(&hash) {
                 self.access_counter += 1;
@@ -0,0 +1,362 @@
+//! Startup migrations for on-disk formats.
+//!
+//! Long-lived installations carry files written by older builds (registry
+//! catalog, snapshots, encrypted models, salt). Each format change ships a
+//! `Migration` with a version number; at startup every step above the
+//! recorded version runs in order. Steps are idempotent: they check the
+//! files themselves and skip what is already current, so a crash between a
+//! step and the version update is safe to re-run.
+//!
+//! Before a step touches anything its files are copied to
+//! `migration-backups/<version>-<id>-<unix seconds>/` under the base path.
+//! The applied version lives in `format-version.json`; a version newer than
+//! this build supports refuses startup rather than risk a silent downgrade.
+
+mod steps;
+
+pub use steps::RegistryStatsMigration;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Version file name under the base path.
+pub const VERSION_FILE: &str = "format-version.json";
+/// Backup directory name under the base path.
+pub const BACKUP_DIR: &str = "migration-backups";
+
+#[derive(Error, Debug)]
+pub enum MigrationError {
+    #[error("IO error on {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Malformed {path}: {source}")]
+    Parse {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error("On-disk format version {found} is newer than this build supports ({supported}); refusing to downgrade")]
+    NewerFormat { found: u32, supported: u32 },
+
+    #[error("Migration {version} ({id}) failed: {message}")]
+    Step {
+        version: u32,
+        id: &'static str,
+        message: String,
+    },
+}
+
+pub(crate) fn io_err(path: &Path) -> impl FnOnce(std::io::Error) -> MigrationError + '_ {
+    move |source| MigrationError::Io { path: path.to_path_buf(), source }
+}
+
+/// One versioned format change.
+pub trait Migration: Send + Sync {
+    /// Target format version; steps run in ascending order.
+    fn version(&self) -> u32;
+
+    fn id(&self) -> &'static str;
+
+    fn description(&self) -> &'static str;
+
+    /// Existing files this step would modify; they are backed up first.
+    /// An empty list means there is nothing to migrate.
+    fn affected_files(&self, base_path: &Path) -> Result<Vec<PathBuf>, MigrationError>;
+
+    /// Bring the files up to this version. Must be safe to run twice.
+    fn apply(&self, base_path: &Path) -> Result<(), MigrationError>;
+}
+
+/// Recorded state in `format-version.json`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FormatVersion {
+    pub version: u32,
+    #[serde(default)]
+    pub applied: Vec<AppliedMigration>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AppliedMigration {
+    pub version: u32,
+    pub id: String,
+    /// Unix seconds.
+    pub applied_at: u64,
+    /// Backup directory, when files were touched.
+    pub backup: Option<PathBuf>,
+}
+
+/// A step that has not been recorded as applied.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PendingMigration {
+    pub version: u32,
+    pub id: &'static str,
+    pub description: &'static str,
+    pub files: Vec<PathBuf>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MigrationReport {
+    pub from_version: u32,
+    pub to_version: u32,
+    pub dry_run: bool,
+    pub steps: Vec<PendingMigration>,
+    pub backups: Vec<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct MigrationOptions {
+    /// Report what would run without touching anything.
+    pub dry_run: bool,
+    /// Copy affected files aside before each step.
+    pub backup: bool,
+}
+
+impl Default for MigrationOptions {
+    fn default() -> Self {
+        Self { dry_run: false, backup: true }
+    }
+}
+
+/// Runs registered migrations against one base path.
+pub struct Migrator {
+    base_path: PathBuf,
+    migrations: Vec<Box<dyn Migration>>,
+}
+
+impl Migrator {
+    /// Migrator with no steps; see [`Migrator::builtin`].
+    pub fn new(base_path: impl Into<PathBuf>) -> Self {
+        Self { base_path: base_path.into(), migrations: Vec::new() }
+    }
+
+    /// Migrator with every step this build ships.
+    pub fn builtin(base_path: impl Into<PathBuf>) -> Self {
+        Self::new(base_path).with_migration(RegistryStatsMigration)
+    }
+
+    pub fn with_migration(mut self, migration: impl Migration + 'static) -> Self {
+        self.migrations.push(Box::new(migration));
+        self.migrations.sort_by_key(|m| m.version());
+        self
+    }
+
+    /// Newest format version this build understands.
+    pub fn latest_version(&self) -> u32 {
+        self.migrations.last().map_or(0, |m| m.version())
+    }
+
+    fn version_path(&self) -> PathBuf {
+        self.base_path.join(VERSION_FILE)
+    }
+
+    /// Recorded format version; a missing file means version 0.
+    pub fn current(&self) -> Result<FormatVersion, MigrationError> {
+        let path = self.version_path();
+        match fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).map_err(|source| MigrationError::Parse { path, source }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(FormatVersion::default()),
+            Err(e) => Err(io_err(&path)(e)),
+        }
+    }
+
+    /// Steps above the recorded version, without changing anything.
+    pub fn pending(&self) -> Result<Vec<PendingMigration>, MigrationError> {
+        let current = self.current()?;
+        self.check_not_newer(&current)?;
+        self.migrations
+            .iter()
+            .filter(|m| m.version() > current.version)
+            .map(|m| {
+                Ok(PendingMigration {
+                    version: m.version(),
+                    id: m.id(),
+                    description: m.description(),
+                    files: m.affected_files(&self.base_path)?,
+                })
+            })
+            .collect()
+    }
+
+    /// Apply pending steps in order, recording the version after each.
+    pub fn run(&self, options: MigrationOptions) -> Result<MigrationReport, MigrationError> {
+        let mut state = self.current()?;
+        let steps = self.pending()?;
+        let mut report = MigrationReport {
+            from_version: state.version,
+            to_version: steps.last().map_or(state.version, |s| s.version),
+            dry_run: options.dry_run,
+            steps,
+            backups: Vec::new(),
+        };
+        if options.dry_run {
+            return Ok(report);
+        }
+
+        for step in &mut report.steps {
+            let migration = self
+                .migrations
+                .iter()
+                .find(|m| m.version() == step.version)
+                .expect("pending step comes from the registered migrations");
+            // Earlier steps may have changed what this one touches
+            step.files = migration.affected_files(&self.base_path)?;
+            let backup = if options.backup && !step.files.is_empty() {
+                Some(self.backup(step)?)
+            } else {
+                None
+            };
+            migration.apply(&self.base_path)?;
+            tracing::info!(version = step.version, id = step.id, "Applied on-disk migration");
+
+            state.version = step.version;
+            state.applied.push(AppliedMigration {
+                version: step.version,
+                id: step.id.to_string(),
+                applied_at: unix_now(),
+                backup: backup.clone(),
+            });
+            self.record(&state)?;
+            report.backups.extend(backup);
+        }
+        Ok(report)
+    }
+
+    fn check_not_newer(&self, current: &FormatVersion) -> Result<(), MigrationError> {
+        let supported = self.latest_version();
+        if current.version > supported {
+            return Err(MigrationError::NewerFormat { found: current.version, supported });
+        }
+        Ok(())
+    }
+
+    fn backup(&self, step: &PendingMigration) -> Result<PathBuf, MigrationError> {
+        let dir = self
+            .base_path
+            .join(BACKUP_DIR)
+            .join(format!("{}-{}-{}", step.version, step.id, unix_now()));
+        fs::create_dir_all(&dir).map_err(io_err(&dir))?;
+        for file in &step.files {
+            // Keep the layout under the base path; outside files go flat
+            let relative = match file.strip_prefix(&self.base_path) {
+                Ok(relative) => relative.to_path_buf(),
+                Err(_) => file.file_name().map(PathBuf::from).unwrap_or_default(),
+            };
+            let target = dir.join(relative);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent).map_err(io_err(parent))?;
+            }
+            fs::copy(file, &target).map_err(io_err(file))?;
+        }
+        Ok(dir)
+    }
+
+    fn record(&self, state: &FormatVersion) -> Result<(), MigrationError> {
+        let path = self.version_path();
+        let tmp = path.with_extension("tmp");
+        let json = serde_json::to_vec_pretty(state)
+            .map_err(|source| MigrationError::Parse { path: path.clone(), source })?;
+        fs::write(&tmp, json).map_err(io_err(&tmp))?;
+        fs::rename(&tmp, &path).map_err(io_err(&path))
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    /// Appends a line to `data.txt` unless already present.
+    struct AppendLine {
+        version: u32,
+        line: &'static str,
+        runs: Arc<AtomicUsize>,
+    }
+
+    impl Migration for AppendLine {
+        fn version(&self) -> u32 {
+            self.version
+        }
+        fn id(&self) -> &'static str {
+            self.line
+        }
+        fn description(&self) -> &'static str {
+            "append a line"
+        }
+        fn affected_files(&self, base_path: &Path) -> Result<Vec<PathBuf>, MigrationError> {
+            let path = base_path.join("data.txt");
+            Ok(if path.exists() { vec![path] } else { Vec::new() })
+        }
+        fn apply(&self, base_path: &Path) -> Result<(), MigrationError> {
+            self.runs.fetch_add(1, Ordering::SeqCst);
+            let path = base_path.join("data.txt");
+            let mut data = fs::read_to_string(&path).unwrap_or_default();
+            if !data.lines().any(|l| l == self.line) {
+                data.push_str(self.line);
+                data.push('\n');
+                fs::write(&path, data).map_err(io_err(&path))?;
+            }
+            Ok(())
+        }
+    }
+
+    fn migrator(dir: &Path, runs: &Arc<AtomicUsize>) -> Migrator {
+        Migrator::new(dir)
+            .with_migration(AppendLine { version: 2, line: "two", runs: Arc::clone(runs) })
+            .with_migration(AppendLine { version: 1, line: "one", runs: Arc::clone(runs) })
+    }
+
+    #[test]
+    fn test_runs_in_order_once_with_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("data.txt"), "zero\n").unwrap();
+        let runs = Arc::new(AtomicUsize::new(0));
+        let migrator = migrator(dir.path(), &runs);
+
+        let report = migrator.run(MigrationOptions::default()).unwrap();
+        assert_eq!((report.from_version, report.to_version), (0, 2));
+        assert_eq!(fs::read_to_string(dir.path().join("data.txt")).unwrap(), "zero\none\ntwo\n");
+        assert_eq!(report.backups.len(), 2);
+        assert_eq!(fs::read_to_string(report.backups[0].join("data.txt")).unwrap(), "zero\n");
+
+        // Second startup: nothing pending, nothing run
+        assert!(migrator.pending().unwrap().is_empty());
+        migrator.run(MigrationOptions::default()).unwrap();
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+        assert_eq!(migrator.current().unwrap().applied.len(), 2);
+    }
+
+    #[test]
+    fn test_dry_run_touches_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let runs = Arc::new(AtomicUsize::new(0));
+        let report = migrator(dir.path(), &runs)
+            .run(MigrationOptions { dry_run: true, backup: true })
+            .unwrap();
+        assert_eq!(report.steps.len(), 2);
+        assert_eq!(runs.load(Ordering::SeqCst), 0);
+        assert!(!dir.path().join(VERSION_FILE).exists());
+    }
+
+    #[test]
+    fn test_refuses_newer_format() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join(VERSION_FILE), r#"{"version": 9}"#).unwrap();
+        let runs = Arc::new(AtomicUsize::new(0));
+        let err = migrator(dir.path(), &runs).run(MigrationOptions::default()).unwrap_err();
+        assert!(matches!(err, MigrationError::NewerFormat { found: 9, supported: 2 }));
+    }
+}
@@ -0,0 +1,67 @@
+//! Migration steps shipped with this build, one per format version.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{io_err, Migration, MigrationError};
+
+/// Registry catalog file written by `RegistryPersistence`.
+const REGISTRY_STATE_FILE: &str = "registry_state.json";
+
+/// Version 1: the registry catalog gains a per-model `stats` map. Older
+/// files load without it, but writing it out explicitly lets tools that
+/// read the file see the current shape. Unknown fields are preserved.
+pub struct RegistryStatsMigration;
+
+impl RegistryStatsMigration {
+    fn needs_stats(path: &Path) -> Result<Option<serde_json::Value>, MigrationError> {
+        let bytes = match fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(io_err(path)(e)),
+        };
+        let value: serde_json::Value = serde_json::from_slice(&bytes)
+            .map_err(|source| MigrationError::Parse { path: path.to_path_buf(), source })?;
+        Ok(value.get("stats").is_none().then_some(value))
+    }
+}
+
+impl Migration for RegistryStatsMigration {
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn id(&self) -> &'static str {
+        "registry-stats"
+    }
+
+    fn description(&self) -> &'static str {
+        "Add per-model request statistics to the registry catalog"
+    }
+
+    fn affected_files(&self, base_path: &Path) -> Result<Vec<PathBuf>, MigrationError> {
+        let path = base_path.join(REGISTRY_STATE_FILE);
+        Ok(Self::needs_stats(&path)?.map(|_| path).into_iter().collect())
+    }
+
+    fn apply(&self, base_path: &Path) -> Result<(), MigrationError> {
+        let path = base_path.join(REGISTRY_STATE_FILE);
+        let Some(mut value) = Self::needs_stats(&path)? else {
+            return Ok(());
+        };
+        let Some(object) = value.as_object_mut() else {
+            return Err(MigrationError::Step {
+                version: self.version(),
+                id: self.id(),
+                message: format!("{} is not a JSON object", path.display()),
+            });
+        };
+        object.insert("stats".into(), serde_json::Value::Object(Default::default()));
+
+        let tmp = path.with_extension("tmp");
+        let json = serde_json::to_vec_pretty(&value)
+            .map_err(|source| MigrationError::Parse { path: path.clone(), source })?;
+        fs::write(&tmp, json).map_err(io_err(&tmp))?;
+        fs::rename(&tmp, &path).map_err(io_err(&path))
+    }
+}
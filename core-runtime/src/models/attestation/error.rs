@@ -0,0 +1,33 @@
+//! Attestation verification errors.
+
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum AttestationError {
+    #[error("Model has no attestation and policy requires one")]
+    Missing,
+
+    #[error("Malformed attestation {path}: {reason}")]
+    Malformed { path: PathBuf, reason: String },
+
+    #[error("Unsupported attestation {path}: {reason}")]
+    Unsupported { path: PathBuf, reason: String },
+
+    #[error("Attestation {0} is not signed by a trusted identity")]
+    Untrusted(PathBuf),
+
+    #[error("Attestation {path} does not cover this file (sha256 {sha256})")]
+    SubjectMismatch { path: PathBuf, sha256: String },
+
+    #[error("Invalid trusted identity {name}: {reason}")]
+    InvalidIdentity { name: String, reason: String },
+
+    #[error("IO error on {path}: {reason}")]
+    Io { path: PathBuf, reason: String },
+}
+
+pub(super) fn io_err(path: &Path) -> impl FnOnce(std::io::Error) -> AttestationError + '_ {
+    move |e| AttestationError::Io { path: path.to_path_buf(), reason: e.to_string() }
+}
@@ -0,0 +1,104 @@
+//! in-toto link metadata signed over canonical JSON.
+
+use std::path::Path;
+
+use p256::ecdsa::signature::Verifier;
+use p256::ecdsa::Signature;
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::{AttestationConfig, AttestationError};
+
+#[derive(Deserialize)]
+struct Link {
+    signed: Value,
+    signatures: Vec<LinkSignature>,
+}
+
+#[derive(Deserialize)]
+struct LinkSignature {
+    sig: String,
+}
+
+pub(super) fn verify_link(
+    config: &AttestationConfig,
+    path: &Path,
+    document: &[u8],
+    file_digest: &[u8; 32],
+) -> Result<String, AttestationError> {
+    let malformed = |reason: String| AttestationError::Malformed { path: path.to_path_buf(), reason };
+    let link: Link = serde_json::from_slice(document).map_err(|e| malformed(e.to_string()))?;
+    if link.signed.get("_type").and_then(Value::as_str) != Some("link") {
+        return Err(malformed("not in-toto link metadata".into()));
+    }
+    let mut canonical = String::new();
+    canonical_json(&link.signed, &mut canonical);
+
+    let mut signer = None;
+    for signature in &link.signatures {
+        let der = hex::decode(&signature.sig).map_err(|e| malformed(e.to_string()))?;
+        let signature = Signature::from_der(&der).map_err(|e| malformed(e.to_string()))?;
+        signer = config.signer(|key| key.verify(canonical.as_bytes(), &signature).is_ok());
+        if signer.is_some() {
+            break;
+        }
+    }
+    let signer = signer.ok_or_else(|| AttestationError::Untrusted(path.to_path_buf()))?;
+
+    let sha256 = hex::encode(file_digest);
+    let matches = |product: &Value| {
+        product.get("sha256").and_then(Value::as_str).is_some_and(|d| d.eq_ignore_ascii_case(&sha256))
+    };
+    let covered = link
+        .signed
+        .get("products")
+        .and_then(Value::as_object)
+        .is_some_and(|products| products.values().any(matches));
+    if !covered {
+        return Err(AttestationError::SubjectMismatch { path: path.to_path_buf(), sha256 });
+    }
+    Ok(signer)
+}
+
+/// Canonical JSON as in-toto signs it: sorted keys, no whitespace, and
+/// only `"` and `\` escaped in strings.
+fn canonical_json(value: &Value, out: &mut String) {
+    let string = |s: &str, out: &mut String| {
+        out.push('"');
+        for c in s.chars() {
+            if c == '"' || c == '\\' {
+                out.push('\\');
+            }
+            out.push(c);
+        }
+        out.push('"');
+    };
+    match value {
+        Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            out.push('{');
+            for (i, key) in keys.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                string(key, out);
+                out.push(':');
+                canonical_json(&map[key], out);
+            }
+            out.push('}');
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                canonical_json(item, out);
+            }
+            out.push(']');
+        }
+        Value::String(s) => string(s, out),
+        other => out.push_str(&other.to_string()),
+    }
+}
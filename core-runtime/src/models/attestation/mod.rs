@@ -0,0 +1,213 @@
+//! Supply-chain attestation of model files.
+//!
+//! A model can ship with a provenance attestation beside it: a Sigstore
+//! bundle (`<model>.sigstore.json`) or in-toto link metadata
+//! (`<model>.link`). When attestation is enabled, the attestation must be
+//! signed by a configured trusted identity and must name the file's SHA-256
+//! before the model loads. The digest of the attestation document is kept
+//! on the loaded model and written to the audit log, tying the model back
+//! to the build that produced it.
+//!
+//! Trusted identities are ECDSA P-256 public keys, as produced by
+//! `cosign generate-key-pair` or in-toto key tooling. Verification is
+//! offline, so keyless bundles (trust rooted in a Fulcio certificate and
+//! the Rekor log) are rejected.
+
+mod error;
+mod in_toto;
+mod sigstore;
+mod trust;
+
+pub use error::AttestationError;
+pub use trust::{load_identities, AttestationConfig, AttestationPolicy, TrustedIdentity};
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::security::audit::{audit_logger, AuditCategory, AuditEvent, AuditSeverity};
+
+use error::io_err;
+
+/// Sidecar suffix of a Sigstore bundle.
+pub const SIGSTORE_SUFFIX: &str = "sigstore.json";
+
+/// Sidecar suffix of in-toto link metadata.
+pub const IN_TOTO_SUFFIX: &str = "link";
+
+/// Trusted identities file name within the base path.
+pub const TRUSTED_IDENTITIES_FILE: &str = "trusted_identities.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttestationKind {
+    SigstoreBundle,
+    InTotoLink,
+}
+
+impl AttestationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::SigstoreBundle => "sigstore_bundle",
+            Self::InTotoLink => "in_toto_link",
+        }
+    }
+}
+
+/// A verified attestation of a model file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attestation {
+    pub kind: AttestationKind,
+    /// `sha256:<hex>` of the attestation document.
+    pub digest: String,
+    /// Trusted identity that signed it.
+    pub signer: String,
+    /// SHA-256 of the model file it covers.
+    pub subject_sha256: String,
+}
+
+fn sidecar(model: &Path, suffix: &str) -> PathBuf {
+    let mut name = model.as_os_str().to_owned();
+    name.push(".");
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// The attestation beside `model`, if any. A Sigstore bundle wins over a
+/// link.
+pub fn find_attestation(model: &Path) -> Option<(AttestationKind, PathBuf)> {
+    [(AttestationKind::SigstoreBundle, SIGSTORE_SUFFIX), (AttestationKind::InTotoLink, IN_TOTO_SUFFIX)]
+        .into_iter()
+        .map(|(kind, suffix)| (kind, sidecar(model, suffix)))
+        .find(|(_, path)| path.is_file())
+}
+
+/// Verify `model`'s attestation under `config`. `None` when attestation is
+/// off, or the model is unattested and the policy allows that.
+pub fn verify_model(config: &AttestationConfig, model: &Path) -> Result<Option<Attestation>, AttestationError> {
+    if config.policy == AttestationPolicy::Off {
+        return Ok(None);
+    }
+    let Some((kind, path)) = find_attestation(model) else {
+        return match config.policy {
+            AttestationPolicy::Require => Err(AttestationError::Missing),
+            _ => Ok(None),
+        };
+    };
+    let document = fs::read(&path).map_err(io_err(&path))?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut fs::File::open(model).map_err(io_err(model))?, &mut hasher).map_err(io_err(model))?;
+    let file_digest: [u8; 32] = hasher.finalize().into();
+
+    let signer = match kind {
+        AttestationKind::SigstoreBundle => sigstore::verify_bundle(config, &path, &document, &file_digest)?,
+        AttestationKind::InTotoLink => in_toto::verify_link(config, &path, &document, &file_digest)?,
+    };
+    Ok(Some(Attestation {
+        kind,
+        digest: format!("sha256:{}", hex::encode(Sha256::digest(&document))),
+        signer,
+        subject_sha256: hex::encode(file_digest),
+    }))
+}
+
+/// Record a verification outcome in the audit log.
+pub fn audit(model: &Path, result: &Result<Option<Attestation>, AttestationError>) {
+    let (Some(logger), Ok(runtime)) = (audit_logger(), tokio::runtime::Handle::try_current()) else {
+        return;
+    };
+    let model = model.display().to_string();
+    let builder = AuditEvent::builder()
+        .category(AuditCategory::ModelOperation)
+        .event_type("model_attestation")
+        .source("model_loader")
+        .metadata("model", model.clone());
+    let builder = match result {
+        Ok(None) => return,
+        Ok(Some(attestation)) => builder
+            .severity(AuditSeverity::Info)
+            .message(format!("Model {} attested by {}", model, attestation.signer))
+            .metadata("kind", attestation.kind.as_str())
+            .metadata("attestation_digest", attestation.digest.clone())
+            .metadata("signer", attestation.signer.clone())
+            .metadata("subject_sha256", attestation.subject_sha256.clone()),
+        Err(e) => builder
+            .severity(AuditSeverity::Warning)
+            .message(format!("Model {} refused: {}", model, e))
+            .success(false),
+    };
+    let Ok(event) = builder.build() else {
+        return;
+    };
+    runtime.spawn(async move { logger.log(event).await });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUBLIC_KEY: &str = "-----BEGIN PUBLIC KEY-----
+MFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAE1v96+PO7ZPhxuQzrk5otxgnwfQBp
+/lM22Ch7L6l+x0DiFUIkLYiyxNHlYiZ3mmRpJ3BPE/0PC1sc/t7mq0EGwA==
+-----END PUBLIC KEY-----";
+
+    const MODEL: &[u8] = b"GGUF\x03\x00\x00\x00attested model";
+
+    const DSSE_BUNDLE: &str = r#"{"mediaType": "application/vnd.dev.sigstore.bundle.v0.3+json", "verificationMaterial": {"publicKey": {"hint": "release"}}, "dsseEnvelope": {"payload": "eyJfdHlwZSI6Imh0dHBzOi8vaW4tdG90by5pby9TdGF0ZW1lbnQvdjEiLCJzdWJqZWN0IjpbeyJuYW1lIjoicGhpLmdndWYiLCJkaWdlc3QiOnsic2hhMjU2IjoiZDY2MGY2ZWI5OGY3OWQxNzliODdjYTdkMmQ2YjczYmQ3ZjQyYWQ4M2Y1NDNkODhiZDIxZThmNjA3OTgzMTI2NyJ9fV0sInByZWRpY2F0ZVR5cGUiOiJodHRwczovL3Nsc2EuZGV2L3Byb3ZlbmFuY2UvdjEiLCJwcmVkaWNhdGUiOnt9fQ==", "payloadType": "application/vnd.in-toto+json", "signatures": [{"sig": "MEUCIQCtLenX4p9AIR2gLtMkgNzO+Osa7Bloc6jyaJ5I1iwlmgIgUb5wiUkDPun0B6FVIMAWP+fHSHmrG3kIyrJbmpHfVy0=", "keyid": ""}]}}"#;
+
+    const MESSAGE_BUNDLE: &str = r#"{"mediaType": "application/vnd.dev.sigstore.bundle.v0.3+json", "verificationMaterial": {"publicKey": {"hint": "release"}}, "messageSignature": {"messageDigest": {"algorithm": "SHA2_256", "digest": "1mD265j3nRebh8p9LWtzvX9CrYP1Q9iL0h6PYHmDEmc="}, "signature": "MEQCIDWJDKL+cjpcSY0xFxgB4esY2OODyZoBKWttwtaNABifAiANuwj1AEf9p79AMBbudwxzbQJWcv/axbLGjasS45MsdQ=="}}"#;
+
+    const LINK: &str = r#"{"signed": {"_type": "link", "name": "package", "materials": {}, "products": {"phi.gguf": {"sha256": "d660f6eb98f79d179b87ca7d2d6b73bd7f42ad83f543d88bd21e8f6079831267"}}, "byproducts": {}, "command": [], "environment": {}}, "signatures": [{"keyid": "release", "sig": "3045022100de442c672e6dd8177168265a9a2dc1544e0dfc72ea525f88bf1d051bdbd408c102207dc1f1ca3cb259b087fbc0d5dc5907ef337e6b88a7eb7a8af35dd0de64c0e97a"}]}"#;
+
+    fn config(policy: AttestationPolicy) -> AttestationConfig {
+        AttestationConfig { policy, identities: vec![TrustedIdentity::from_pem("release", PUBLIC_KEY).unwrap()] }
+    }
+
+    fn model(dir: &Path) -> PathBuf {
+        let path = dir.join("phi.gguf");
+        fs::write(&path, MODEL).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_verify_bundles_and_links() {
+        for (suffix, document, kind) in [
+            (SIGSTORE_SUFFIX, DSSE_BUNDLE, AttestationKind::SigstoreBundle),
+            (SIGSTORE_SUFFIX, MESSAGE_BUNDLE, AttestationKind::SigstoreBundle),
+            (IN_TOTO_SUFFIX, LINK, AttestationKind::InTotoLink),
+        ] {
+            let dir = tempfile::tempdir().unwrap();
+            let model = model(dir.path());
+            fs::write(sidecar(&model, suffix), document).unwrap();
+            let attestation = verify_model(&config(AttestationPolicy::Require), &model).unwrap().unwrap();
+            assert_eq!((attestation.kind, attestation.signer.as_str()), (kind, "release"));
+            assert_eq!(attestation.digest, format!("sha256:{}", hex::encode(Sha256::digest(document))));
+
+            // Tampering with the model breaks the subject match
+            fs::write(&model, b"GGUF tampered").unwrap();
+            let err = verify_model(&config(AttestationPolicy::Verify), &model).unwrap_err();
+            assert!(matches!(err, AttestationError::SubjectMismatch { .. }));
+        }
+    }
+
+    #[test]
+    fn test_policy() {
+        let dir = tempfile::tempdir().unwrap();
+        let model = model(dir.path());
+        assert_eq!(verify_model(&config(AttestationPolicy::Verify), &model), Ok(None));
+        assert_eq!(verify_model(&config(AttestationPolicy::Require), &model), Err(AttestationError::Missing));
+
+        // A valid signature from a key nobody trusts
+        fs::write(sidecar(&model, SIGSTORE_SUFFIX), DSSE_BUNDLE).unwrap();
+        let untrusted = AttestationConfig { policy: AttestationPolicy::Require, identities: Vec::new() };
+        assert!(matches!(verify_model(&untrusted, &model), Err(AttestationError::Untrusted(_))));
+
+        let keyless = DSSE_BUNDLE.replace(r#""publicKey": {"hint": "release"}"#, r#""certificate": {"rawBytes": ""}"#);
+        fs::write(sidecar(&model, SIGSTORE_SUFFIX), keyless).unwrap();
+        let err = verify_model(&config(AttestationPolicy::Require), &model).unwrap_err();
+        assert!(matches!(err, AttestationError::Unsupported { .. }));
+        assert_eq!(verify_model(&config(AttestationPolicy::Off), &model), Ok(None));
+    }
+}
@@ -0,0 +1,151 @@
+//! Sigstore bundles: DSSE-wrapped in-toto statements or message signatures.
+
+use std::path::Path;
+
+use p256::ecdsa::signature::hazmat::PrehashVerifier;
+use p256::ecdsa::signature::Verifier;
+use p256::ecdsa::Signature;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::security::image_input::decode_base64;
+
+use super::{AttestationConfig, AttestationError};
+
+const DSSE_IN_TOTO: &str = "application/vnd.in-toto+json";
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Bundle {
+    verification_material: VerificationMaterial,
+    #[serde(default)]
+    dsse_envelope: Option<Envelope>,
+    #[serde(default)]
+    message_signature: Option<MessageSignature>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VerificationMaterial {
+    #[serde(default)]
+    public_key: Option<Value>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Envelope {
+    payload: String,
+    payload_type: String,
+    signatures: Vec<EnvelopeSignature>,
+}
+
+#[derive(Deserialize)]
+struct EnvelopeSignature {
+    sig: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MessageSignature {
+    message_digest: MessageDigest,
+    signature: String,
+}
+
+#[derive(Deserialize)]
+struct MessageDigest {
+    algorithm: String,
+    digest: String,
+}
+
+#[derive(Deserialize)]
+struct Statement {
+    subject: Vec<Subject>,
+}
+
+#[derive(Deserialize)]
+struct Subject {
+    #[serde(default)]
+    digest: std::collections::HashMap<String, String>,
+}
+
+fn malformed(path: &Path, reason: impl ToString) -> AttestationError {
+    AttestationError::Malformed { path: path.to_path_buf(), reason: reason.to_string() }
+}
+
+fn unsupported(path: &Path, reason: &str) -> AttestationError {
+    AttestationError::Unsupported { path: path.to_path_buf(), reason: reason.to_string() }
+}
+
+fn decode_signature(path: &Path, text: &str) -> Result<Signature, AttestationError> {
+    let bytes = decode_base64(text).map_err(|e| malformed(path, e))?;
+    Signature::from_der(&bytes).map_err(|e| malformed(path, e))
+}
+
+pub(super) fn verify_bundle(
+    config: &AttestationConfig,
+    path: &Path,
+    document: &[u8],
+    file_digest: &[u8; 32],
+) -> Result<String, AttestationError> {
+    let bundle: Bundle = serde_json::from_slice(document).map_err(|e| malformed(path, e))?;
+    if bundle.verification_material.public_key.is_none() {
+        return Err(unsupported(path, "keyless bundles need Fulcio and Rekor; sign with a trusted key"));
+    }
+    if let Some(envelope) = bundle.dsse_envelope {
+        return verify_envelope(config, path, envelope, file_digest);
+    }
+    let message = bundle.message_signature.ok_or_else(|| malformed(path, "no dsseEnvelope or messageSignature"))?;
+    verify_message(config, path, message, file_digest)
+}
+
+/// A DSSE envelope signs an in-toto statement whose subject is the file.
+fn verify_envelope(
+    config: &AttestationConfig,
+    path: &Path,
+    envelope: Envelope,
+    file_digest: &[u8; 32],
+) -> Result<String, AttestationError> {
+    if envelope.payload_type != DSSE_IN_TOTO {
+        return Err(unsupported(path, "DSSE payload is not an in-toto statement"));
+    }
+    let payload = decode_base64(&envelope.payload).map_err(|e| malformed(path, e))?;
+    let statement: Statement = serde_json::from_slice(&payload).map_err(|e| malformed(path, e))?;
+    let pae = [
+        format!("DSSEv1 {} {} {} ", envelope.payload_type.len(), envelope.payload_type, payload.len()).as_bytes(),
+        payload.as_slice(),
+    ]
+    .concat();
+    let mut signer = None;
+    for signature in &envelope.signatures {
+        let signature = decode_signature(path, &signature.sig)?;
+        signer = config.signer(|key| key.verify(&pae, &signature).is_ok());
+        if signer.is_some() {
+            break;
+        }
+    }
+    let signer = signer.ok_or_else(|| AttestationError::Untrusted(path.to_path_buf()))?;
+    let sha256 = hex::encode(file_digest);
+    if !statement.subject.iter().any(|s| s.digest.get("sha256").is_some_and(|d| d.eq_ignore_ascii_case(&sha256))) {
+        return Err(AttestationError::SubjectMismatch { path: path.to_path_buf(), sha256 });
+    }
+    Ok(signer)
+}
+
+/// A message signature signs the file's SHA-256 digest directly.
+fn verify_message(
+    config: &AttestationConfig,
+    path: &Path,
+    message: MessageSignature,
+    file_digest: &[u8; 32],
+) -> Result<String, AttestationError> {
+    if message.message_digest.algorithm != "SHA2_256" {
+        return Err(unsupported(path, "message digest is not SHA2_256"));
+    }
+    if decode_base64(&message.message_digest.digest).map_err(|e| malformed(path, e))? != file_digest {
+        return Err(AttestationError::SubjectMismatch { path: path.to_path_buf(), sha256: hex::encode(file_digest) });
+    }
+    let signature = decode_signature(path, &message.signature)?;
+    config
+        .signer(|key| key.verify_prehash(file_digest, &signature).is_ok())
+        .ok_or_else(|| AttestationError::Untrusted(path.to_path_buf()))
+}
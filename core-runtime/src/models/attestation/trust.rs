@@ -0,0 +1,83 @@
+//! Attestation policy and the identities whose signatures are trusted.
+
+use std::fs;
+use std::path::Path;
+
+use p256::ecdsa::VerifyingKey;
+use p256::pkcs8::DecodePublicKey;
+use serde::{Deserialize, Serialize};
+
+use super::error::io_err;
+use super::AttestationError;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttestationPolicy {
+    /// Attestations are not checked.
+    #[default]
+    Off,
+    /// Attestations present are verified; unattested models still load.
+    Verify,
+    /// Every model needs a valid attestation.
+    Require,
+}
+
+impl std::str::FromStr for AttestationPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "off" => Ok(Self::Off),
+            "verify" => Ok(Self::Verify),
+            "require" | "required" => Ok(Self::Require),
+            other => Err(format!("unknown attestation policy '{}' (off, verify, require)", other)),
+        }
+    }
+}
+
+/// A signer whose attestations are accepted.
+#[derive(Debug, Clone)]
+pub struct TrustedIdentity {
+    pub name: String,
+    pub key: VerifyingKey,
+}
+
+impl TrustedIdentity {
+    /// Identity from a PEM `PUBLIC KEY` block.
+    pub fn from_pem(name: &str, pem: &str) -> Result<Self, AttestationError> {
+        let key = VerifyingKey::from_public_key_pem(pem.trim()).map_err(|e| AttestationError::InvalidIdentity {
+            name: name.to_string(),
+            reason: e.to_string(),
+        })?;
+        Ok(Self { name: name.to_string(), key })
+    }
+}
+
+#[derive(Deserialize)]
+struct IdentityEntry {
+    name: String,
+    public_key: String,
+}
+
+/// Read trusted identities from a JSON list of `{name, public_key}`.
+pub fn load_identities(path: &Path) -> Result<Vec<TrustedIdentity>, AttestationError> {
+    let data = fs::read(path).map_err(io_err(path))?;
+    let entries: Vec<IdentityEntry> = serde_json::from_slice(&data).map_err(|e| AttestationError::Malformed {
+        path: path.to_path_buf(),
+        reason: e.to_string(),
+    })?;
+    entries.iter().map(|entry| TrustedIdentity::from_pem(&entry.name, &entry.public_key)).collect()
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AttestationConfig {
+    pub policy: AttestationPolicy,
+    pub identities: Vec<TrustedIdentity>,
+}
+
+impl AttestationConfig {
+    /// Name of the first identity whose key passes `verify`.
+    pub(super) fn signer(&self, verify: impl Fn(&VerifyingKey) -> bool) -> Option<String> {
+        self.identities.iter().find(|identity| verify(&identity.key)).map(|identity| identity.name.clone())
+    }
+}
@@ -0,0 +1,166 @@
+//! Fallback model chains from the registry catalog.
+//!
+//! A chain such as `prod-13b -> prod-7b -> tiny` lists the models tried, in
+//! order, when the requested model is unavailable (not loaded) or its
+//! circuit is open. Chains are flat: a fallback's own chain is not followed,
+//! so a catalog cannot create routing loops.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Longest accepted chain, primary excluded.
+pub const MAX_CHAIN_LEN: usize = 8;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum FallbackError {
+    #[error("Empty model name in fallback chain")]
+    EmptyName,
+
+    #[error("Model {0} appears more than once in its fallback chain")]
+    Duplicate(String),
+
+    #[error("Fallback chain longer than {MAX_CHAIN_LEN} models")]
+    TooLong,
+}
+
+/// Why a request was served by a fallback instead of the requested model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FallbackReason {
+    Unavailable,
+    CircuitOpen,
+    /// The GPU it runs on is throttled for heat or power.
+    Thermal,
+}
+
+impl FallbackReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Unavailable => "unavailable",
+            Self::CircuitOpen => "circuit_open",
+            Self::Thermal => "thermal",
+        }
+    }
+}
+
+/// Primary model → ordered alternates.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct FallbackChains(HashMap<String, Vec<String>>);
+
+impl FallbackChains {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set `primary`'s alternates; an empty list removes the chain.
+    pub fn set(&mut self, primary: &str, alternates: Vec<String>) -> Result<(), FallbackError> {
+        validate(primary, &alternates)?;
+        if alternates.is_empty() {
+            self.0.remove(primary);
+        } else {
+            self.0.insert(primary.to_string(), alternates);
+        }
+        Ok(())
+    }
+
+    /// Models to try for `model`, starting with `model` itself.
+    pub fn chain(&self, model: &str) -> Vec<String> {
+        let mut chain = vec![model.to_string()];
+        if let Some(alternates) = self.0.get(model) {
+            chain.extend(alternates.iter().cloned());
+        }
+        chain
+    }
+
+    /// All chains, sorted by primary.
+    pub fn list(&self) -> Vec<(&str, &[String])> {
+        let mut chains: Vec<_> = self.0.iter().map(|(k, v)| (k.as_str(), v.as_slice())).collect();
+        chains.sort_by_key(|(primary, _)| *primary);
+        chains
+    }
+
+    /// Drop invalid chains (e.g. from a hand-edited catalog), logging each.
+    pub fn sanitized(mut self) -> Self {
+        self.0.retain(|primary, alternates| match validate(primary, alternates) {
+            Ok(()) => true,
+            Err(e) => {
+                tracing::warn!(primary = %primary, error = %e, "Ignoring invalid fallback chain");
+                false
+            }
+        });
+        self
+    }
+}
+
+/// Parse `a -> b -> c` (or `a,b,c`) into the primary and its alternates.
+pub fn parse_chain(spec: &str) -> Result<(String, Vec<String>), FallbackError> {
+    let mut models = spec
+        .split([',', '>'])
+        .map(|part| part.trim().trim_end_matches('-').trim().to_string());
+    let primary = models.next().filter(|m| !m.is_empty()).ok_or(FallbackError::EmptyName)?;
+    let alternates: Vec<String> = models.collect();
+    validate(&primary, &alternates)?;
+    Ok((primary, alternates))
+}
+
+fn validate(primary: &str, alternates: &[String]) -> Result<(), FallbackError> {
+    if alternates.len() > MAX_CHAIN_LEN {
+        return Err(FallbackError::TooLong);
+    }
+    let mut seen = vec![primary];
+    for model in alternates {
+        if primary.is_empty() || model.is_empty() {
+            return Err(FallbackError::EmptyName);
+        }
+        if seen.contains(&model.as_str()) {
+            return Err(FallbackError::Duplicate(model.clone()));
+        }
+        seen.push(model);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_arrow_chain() {
+        let (primary, alternates) = parse_chain("prod-13b -> prod-7b -> tiny").unwrap();
+        assert_eq!(primary, "prod-13b");
+        assert_eq!(alternates, ["prod-7b", "tiny"]);
+        assert_eq!(parse_chain("a,b").unwrap().1, ["b"]);
+    }
+
+    #[test]
+    fn test_rejects_loops_and_blanks() {
+        assert_eq!(parse_chain("a -> b -> a"), Err(FallbackError::Duplicate("a".into())));
+        assert_eq!(parse_chain("a -> -> b"), Err(FallbackError::EmptyName));
+        assert_eq!(parse_chain(""), Err(FallbackError::EmptyName));
+    }
+
+    #[test]
+    fn test_chain_starts_with_requested_model() {
+        let mut chains = FallbackChains::new();
+        chains.set("big", vec!["mid".into(), "small".into()]).unwrap();
+        assert_eq!(chains.chain("big"), ["big", "mid", "small"]);
+        // Chains are not followed transitively
+        chains.set("mid", vec!["other".into()]).unwrap();
+        assert_eq!(chains.chain("big"), ["big", "mid", "small"]);
+        assert_eq!(chains.chain("unknown"), ["unknown"]);
+
+        chains.set("big", Vec::new()).unwrap();
+        assert_eq!(chains.chain("big"), ["big"]);
+    }
+
+    #[test]
+    fn test_sanitized_drops_invalid_chains() {
+        let chains: FallbackChains =
+            serde_json::from_str(r#"{"a": ["b"], "c": ["c"], "d": ["", "e"]}"#).unwrap();
+        let chains = chains.sanitized();
+        assert_eq!(chains.list().len(), 1);
+        assert_eq!(chains.chain("a"), ["a", "b"]);
+    }
+}
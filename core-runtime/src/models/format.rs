@@ -0,0 +1,192 @@
+//! Model file format detection and per-format validation.
+//!
+//! Formats are identified from file contents rather than trusted from the
+//! extension: GGUF by its magic, safetensors by a plausible little-endian
+//! header length followed by a JSON object. ONNX protobufs carry no magic,
+//! so they need the `.onnx` extension plus a leading `ir_version` field tag.
+//! Mock specs are JSON, so they need the `.mock` extension plus a leading `{`.
+
+use std::fmt;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use super::gguf_validate::validate_gguf;
+use super::loader::LoadError;
+use super::safetensors_validate::{validate_safetensors, MAX_HEADER_BYTES};
+use crate::engine::MockSpec;
+
+/// Protobuf tag of ModelProto field 1 (`ir_version`, varint).
+const ONNX_IR_VERSION_TAG: u8 = 0x08;
+
+/// On-disk model format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModelFormat {
+    Gguf,
+    Safetensors,
+    Onnx,
+    /// Scripted mock backend (`mock` feature).
+    Mock,
+}
+
+impl ModelFormat {
+    pub const ALL: [ModelFormat; 4] =
+        [ModelFormat::Gguf, ModelFormat::Safetensors, ModelFormat::Onnx, ModelFormat::Mock];
+
+    /// Name reported in ModelInfo and the registry.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ModelFormat::Gguf => "gguf",
+            ModelFormat::Safetensors => "safetensors",
+            ModelFormat::Onnx => "onnx",
+            ModelFormat::Mock => "mock",
+        }
+    }
+
+    /// Cargo feature that provides the inference backend.
+    pub fn feature(&self) -> &'static str {
+        match self {
+            ModelFormat::Gguf => "gguf",
+            ModelFormat::Safetensors => "safetensors",
+            ModelFormat::Onnx => "onnx",
+            ModelFormat::Mock => "mock",
+        }
+    }
+
+    /// Whether this build includes a backend for the format.
+    pub fn is_compiled(&self) -> bool {
+        match self {
+            ModelFormat::Gguf => cfg!(feature = "gguf"),
+            ModelFormat::Safetensors => cfg!(feature = "safetensors"),
+            ModelFormat::Onnx => cfg!(feature = "onnx"),
+            ModelFormat::Mock => cfg!(feature = "mock"),
+        }
+    }
+
+    /// Identify the format of the file at `path` from its leading bytes.
+    pub fn detect(path: &Path) -> Result<Self, LoadError> {
+        let mut head = [0u8; 9];
+        let mut file = File::open(path)?;
+        let read = read_up_to(&mut file, &mut head)?;
+        let size = file.metadata()?.len();
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or_default().to_ascii_lowercase();
+        Self::detect_bytes(&head[..read], size, &extension).ok_or_else(|| {
+            LoadError::InvalidFormat(format!("unrecognized model format: {}", path.display()))
+        })
+    }
+
+    /// Detection on the first bytes of a file of `size` bytes, given its
+    /// lowercase extension.
+    pub fn detect_bytes(head: &[u8], size: u64, extension: &str) -> Option<Self> {
+        if head.starts_with(b"GGUF") {
+            return Some(ModelFormat::Gguf);
+        }
+        if head.len() >= 9 && head[8] == b'{' {
+            let header_len = u64::from_le_bytes([
+                head[0], head[1], head[2], head[3], head[4], head[5], head[6], head[7],
+            ]);
+            if (2..=MAX_HEADER_BYTES).contains(&header_len) && header_len <= size - 8 {
+                return Some(ModelFormat::Safetensors);
+            }
+        }
+        if extension == "onnx" && head.first() == Some(&ONNX_IR_VERSION_TAG) {
+            return Some(ModelFormat::Onnx);
+        }
+        if extension == "mock" && head.first() == Some(&b'{') {
+            return Some(ModelFormat::Mock);
+        }
+        None
+    }
+
+    /// Structural validation before the file reaches a backend.
+    ///
+    /// ONNX has no cheap structural check beyond detection; the backend's
+    /// protobuf parser bounds-checks the rest.
+    pub fn validate(&self, path: &Path) -> Result<(), LoadError> {
+        match self {
+            ModelFormat::Gguf => validate_gguf(path)
+                .map(|_| ())
+                .map_err(|e| LoadError::InvalidFormat(format!("invalid GGUF: {e}"))),
+            ModelFormat::Safetensors => validate_safetensors(path)
+                .map(|_| ())
+                .map_err(|e| LoadError::InvalidFormat(format!("invalid safetensors: {e}"))),
+            ModelFormat::Onnx => Ok(()),
+            ModelFormat::Mock => MockSpec::load(path)
+                .map(|_| ())
+                .map_err(|e| LoadError::InvalidFormat(format!("invalid mock spec: {e}"))),
+        }
+    }
+}
+
+impl fmt::Display for ModelFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ModelFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ModelFormat::ALL
+            .into_iter()
+            .find(|f| f.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("unknown model format: {s}"))
+    }
+}
+
+fn read_up_to(file: &mut File, buf: &mut [u8]) -> std::io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match file.read(&mut buf[filled..])? {
+            0 => break,
+            n => filled += n,
+        }
+    }
+    Ok(filled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_by_content() {
+        assert_eq!(ModelFormat::detect_bytes(b"GGUF\x03\0\0\0\0", 1024, ""), Some(ModelFormat::Gguf));
+
+        let mut st = 2u64.to_le_bytes().to_vec();
+        st.push(b'{');
+        assert_eq!(ModelFormat::detect_bytes(&st, 10, ""), Some(ModelFormat::Safetensors));
+        // Header length larger than the file
+        assert_eq!(ModelFormat::detect_bytes(&st, 9, ""), None);
+
+        let onnx = [ONNX_IR_VERSION_TAG, 7, 0x12, 0, 0, 0, 0, 0, 0];
+        assert_eq!(ModelFormat::detect_bytes(&onnx, 1024, "onnx"), Some(ModelFormat::Onnx));
+        assert_eq!(ModelFormat::detect_bytes(&onnx, 1024, ""), None);
+
+        let mock = b"{\"respons";
+        assert_eq!(ModelFormat::detect_bytes(mock, 64, "mock"), Some(ModelFormat::Mock));
+        assert_eq!(ModelFormat::detect_bytes(mock, 64, "json"), None);
+    }
+
+    #[test]
+    fn test_extension_does_not_override_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("model.onnx");
+        std::fs::write(&path, b"GGUF\x03\0\0\0").unwrap();
+        assert_eq!(ModelFormat::detect(&path).unwrap(), ModelFormat::Gguf);
+    }
+
+    #[test]
+    fn test_round_trip_names() {
+        for format in ModelFormat::ALL {
+            assert_eq!(format.as_str().parse::<ModelFormat>().unwrap(), format);
+            assert_eq!(serde_json::to_string(&format).unwrap(), format!("\"{format}\""));
+        }
+        assert!("pickle".parse::<ModelFormat>().is_err());
+    }
+}
@@ -0,0 +1,333 @@
+//! Pre-load structural validation of GGUF files.
+//!
+//! llama.cpp trusts the header: a truncated or corrupt file can make it
+//! allocate absurd amounts of memory or read out of bounds. Before a file
+//! reaches the backend, the header is walked here with every length and
+//! count checked against the file size and sane upper bounds.
+//!
+//! Layout (v2/v3, little endian): magic `GGUF`, version u32, tensor count
+//! u64, metadata count u64, metadata key/values, tensor infos, padding to
+//! `general.alignment`, tensor data.
+
+use std::fs::File;
+use std::path::Path;
+
+use memmap2::Mmap;
+use thiserror::Error;
+
+const GGUF_MAGIC: &[u8; 4] = b"GGUF";
+const SUPPORTED_VERSIONS: std::ops::RangeInclusive<u32> = 2..=3;
+
+/// Upper bounds well above any real model.
+pub const MAX_TENSORS: u64 = 1 << 20;
+pub const MAX_METADATA_ENTRIES: u64 = 1 << 16;
+pub const MAX_STRING_BYTES: u64 = 1 << 20;
+const MAX_DIMS: u32 = 4;
+const MAX_ARRAY_DEPTH: u32 = 2;
+/// Highest ggml tensor type ID accepted.
+const MAX_TENSOR_TYPE: u32 = 64;
+const DEFAULT_ALIGNMENT: u64 = 32;
+
+#[derive(Error, Debug)]
+pub enum GgufError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Not a GGUF file (bad magic)")]
+    BadMagic,
+
+    #[error("Unsupported GGUF version {0}")]
+    UnsupportedVersion(u32),
+
+    #[error("Tensor count {0} exceeds limit")]
+    TooManyTensors(u64),
+
+    #[error("Metadata count {0} exceeds limit")]
+    TooManyMetadata(u64),
+
+    #[error("Truncated while reading {0}")]
+    Truncated(&'static str),
+
+    #[error("Invalid metadata value type {0}")]
+    InvalidValueType(u32),
+
+    #[error("String length {0} exceeds limit")]
+    StringTooLong(u64),
+
+    #[error("Tensor {name}: {reason}")]
+    InvalidTensor { name: String, reason: String },
+
+    #[error("Invalid alignment {0}")]
+    InvalidAlignment(u64),
+}
+
+/// What a valid header declared.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GgufSummary {
+    pub version: u32,
+    pub tensor_count: u64,
+    pub metadata_count: u64,
+    /// `general.architecture`, if present.
+    pub architecture: Option<String>,
+    /// `general.file_type` (llama.cpp `llama_ftype`), if present.
+    pub file_type: Option<u32>,
+}
+
+/// Validate the GGUF file at `path`.
+pub fn validate_gguf(path: &Path) -> Result<GgufSummary, GgufError> {
+    let file = File::open(path)?;
+    // SAFETY: read-only mapping, dropped before returning
+    let mmap = unsafe { Mmap::map(&file)? };
+    validate_gguf_bytes(&mmap)
+}
+
+/// Validate an in-memory GGUF image.
+pub fn validate_gguf_bytes(bytes: &[u8]) -> Result<GgufSummary, GgufError> {
+    let mut r = Reader { bytes, pos: 0 };
+    if r.take(4, "magic")? != GGUF_MAGIC {
+        return Err(GgufError::BadMagic);
+    }
+    let version = r.u32("version")?;
+    if !SUPPORTED_VERSIONS.contains(&version) {
+        return Err(GgufError::UnsupportedVersion(version));
+    }
+    let tensor_count = r.u64("tensor count")?;
+    if tensor_count > MAX_TENSORS {
+        return Err(GgufError::TooManyTensors(tensor_count));
+    }
+    let metadata_count = r.u64("metadata count")?;
+    if metadata_count > MAX_METADATA_ENTRIES {
+        return Err(GgufError::TooManyMetadata(metadata_count));
+    }
+
+    let mut architecture = None;
+    let mut file_type = None;
+    let mut alignment = DEFAULT_ALIGNMENT;
+    for _ in 0..metadata_count {
+        let key = r.string("metadata key")?;
+        let value_type = r.u32("metadata type")?;
+        match (key, value_type) {
+            ("general.architecture", STRING) => {
+                architecture = Some(r.string("metadata value")?.to_string());
+            }
+            ("general.file_type", UINT32) => {
+                file_type = Some(r.u32("file type")?);
+            }
+            ("general.alignment", UINT32) => {
+                alignment = u64::from(r.u32("alignment")?);
+                if alignment == 0 || !alignment.is_power_of_two() {
+                    return Err(GgufError::InvalidAlignment(alignment));
+                }
+            }
+            _ => r.skip_value(value_type, 0)?,
+        }
+    }
+
+    let mut tensors = Vec::with_capacity(tensor_count.min(4096) as usize);
+    for _ in 0..tensor_count {
+        let name = r.string("tensor name")?.to_string();
+        let n_dims = r.u32("tensor dims")?;
+        if n_dims == 0 || n_dims > MAX_DIMS {
+            return Err(invalid_tensor(&name, format!("{n_dims} dimensions")));
+        }
+        let mut elements: u64 = 1;
+        for _ in 0..n_dims {
+            let dim = r.u64("tensor shape")?;
+            elements = elements
+                .checked_mul(dim)
+                .ok_or_else(|| invalid_tensor(&name, "element count overflows".into()))?;
+        }
+        let tensor_type = r.u32("tensor type")?;
+        if tensor_type > MAX_TENSOR_TYPE {
+            return Err(invalid_tensor(&name, format!("unknown type {tensor_type}")));
+        }
+        let offset = r.u64("tensor offset")?;
+        if offset % alignment != 0 {
+            return Err(invalid_tensor(&name, format!("offset {offset} not aligned to {alignment}")));
+        }
+        tensors.push((name, offset));
+    }
+
+    let data_start = (r.pos as u64).div_ceil(alignment) * alignment;
+    let data_len = (bytes.len() as u64).saturating_sub(data_start);
+    for (name, offset) in &tensors {
+        if *offset >= data_len {
+            return Err(invalid_tensor(name, format!("offset {offset} past end of data ({data_len} bytes)")));
+        }
+    }
+
+    Ok(GgufSummary { version, tensor_count, metadata_count, architecture, file_type })
+}
+
+fn invalid_tensor(name: &str, reason: String) -> GgufError {
+    GgufError::InvalidTensor { name: name.to_string(), reason }
+}
+
+// Metadata value types
+const UINT8: u32 = 0;
+const INT8: u32 = 1;
+const UINT16: u32 = 2;
+const INT16: u32 = 3;
+const UINT32: u32 = 4;
+const INT32: u32 = 5;
+const FLOAT32: u32 = 6;
+const BOOL: u32 = 7;
+const STRING: u32 = 8;
+const ARRAY: u32 = 9;
+const UINT64: u32 = 10;
+const INT64: u32 = 11;
+const FLOAT64: u32 = 12;
+
+fn scalar_size(value_type: u32) -> Option<u64> {
+    match value_type {
+        UINT8 | INT8 | BOOL => Some(1),
+        UINT16 | INT16 => Some(2),
+        UINT32 | INT32 | FLOAT32 => Some(4),
+        UINT64 | INT64 | FLOAT64 => Some(8),
+        _ => None,
+    }
+}
+
+/// Bounds-checked cursor over the file image.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: u64, what: &'static str) -> Result<&'a [u8], GgufError> {
+        let remaining = (self.bytes.len() - self.pos) as u64;
+        if len > remaining {
+            return Err(GgufError::Truncated(what));
+        }
+        let slice = &self.bytes[self.pos..self.pos + len as usize];
+        self.pos += len as usize;
+        Ok(slice)
+    }
+
+    fn u32(&mut self, what: &'static str) -> Result<u32, GgufError> {
+        let b = self.take(4, what)?;
+        Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn u64(&mut self, what: &'static str) -> Result<u64, GgufError> {
+        let b = self.take(8, what)?;
+        Ok(u64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]))
+    }
+
+    fn string(&mut self, what: &'static str) -> Result<&'a str, GgufError> {
+        let len = self.u64(what)?;
+        if len > MAX_STRING_BYTES {
+            return Err(GgufError::StringTooLong(len));
+        }
+        // Non-UTF-8 keys or names are treated as corruption
+        std::str::from_utf8(self.take(len, what)?).map_err(|_| GgufError::Truncated(what))
+    }
+
+    fn skip_value(&mut self, value_type: u32, depth: u32) -> Result<(), GgufError> {
+        if let Some(size) = scalar_size(value_type) {
+            self.take(size, "metadata value")?;
+            return Ok(());
+        }
+        match value_type {
+            STRING => {
+                self.string("metadata value")?;
+            }
+            ARRAY if depth < MAX_ARRAY_DEPTH => {
+                let elem_type = self.u32("array type")?;
+                let count = self.u64("array length")?;
+                match scalar_size(elem_type) {
+                    Some(size) => {
+                        let total = count.checked_mul(size).ok_or(GgufError::Truncated("array"))?;
+                        self.take(total, "array")?;
+                    }
+                    None => {
+                        // Each element takes at least 8 bytes (string length
+                        // or nested array header), so bound the loop first
+                        let remaining = (self.bytes.len() - self.pos) as u64;
+                        if count > remaining / 8 {
+                            return Err(GgufError::Truncated("array"));
+                        }
+                        for _ in 0..count {
+                            self.skip_value(elem_type, depth + 1)?;
+                        }
+                    }
+                }
+            }
+            other => return Err(GgufError::InvalidValueType(other)),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Minimal valid v3 file with one metadata string and one tensor.
+    fn sample_gguf() -> Vec<u8> {
+        let mut b = Vec::new();
+        b.extend_from_slice(GGUF_MAGIC);
+        b.extend_from_slice(&3u32.to_le_bytes());
+        b.extend_from_slice(&1u64.to_le_bytes()); // tensors
+        b.extend_from_slice(&1u64.to_le_bytes()); // metadata
+        push_str(&mut b, "general.architecture");
+        b.extend_from_slice(&STRING.to_le_bytes());
+        push_str(&mut b, "llama");
+        push_str(&mut b, "tok_embd");
+        b.extend_from_slice(&2u32.to_le_bytes());
+        b.extend_from_slice(&4u64.to_le_bytes());
+        b.extend_from_slice(&8u64.to_le_bytes());
+        b.extend_from_slice(&0u32.to_le_bytes()); // f32
+        b.extend_from_slice(&0u64.to_le_bytes()); // offset
+        b.resize(b.len().div_ceil(32) * 32 + 128, 0);
+        b
+    }
+
+    fn push_str(b: &mut Vec<u8>, s: &str) {
+        b.extend_from_slice(&(s.len() as u64).to_le_bytes());
+        b.extend_from_slice(s.as_bytes());
+    }
+
+    #[test]
+    fn test_valid_header() {
+        let summary = validate_gguf_bytes(&sample_gguf()).unwrap();
+        assert_eq!(summary.version, 3);
+        assert_eq!(summary.tensor_count, 1);
+        assert_eq!(summary.architecture.as_deref(), Some("llama"));
+    }
+
+    #[test]
+    fn test_rejects_bad_magic_and_version() {
+        let mut bytes = sample_gguf();
+        bytes[0] = b'X';
+        assert!(matches!(validate_gguf_bytes(&bytes), Err(GgufError::BadMagic)));
+
+        let mut bytes = sample_gguf();
+        bytes[4..8].copy_from_slice(&99u32.to_le_bytes());
+        assert!(matches!(validate_gguf_bytes(&bytes), Err(GgufError::UnsupportedVersion(99))));
+    }
+
+    #[test]
+    fn test_rejects_absurd_counts() {
+        let mut bytes = sample_gguf();
+        bytes[8..16].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(matches!(validate_gguf_bytes(&bytes), Err(GgufError::TooManyTensors(_))));
+    }
+
+    #[test]
+    fn test_rejects_truncation_anywhere() {
+        let bytes = sample_gguf();
+        // Every cut inside the header must fail cleanly, never panic
+        for len in 0..80 {
+            assert!(validate_gguf_bytes(&bytes[..len]).is_err(), "accepted {len} bytes");
+        }
+    }
+
+    #[test]
+    fn test_rejects_tensor_past_end() {
+        let mut bytes = sample_gguf();
+        bytes.truncate(bytes.len() - 128);
+        assert!(matches!(validate_gguf_bytes(&bytes), Err(GgufError::InvalidTensor { .. })));
+    }
+}
@@ -0,0 +1,298 @@
+//! Background re-verification of resident model files.
+//!
+//! Weights are memory-mapped from their files, so corruption or tampering
+//! on the volume underneath (a failing disk, a PVC another pod can write)
+//! changes what a model serves without any load step noticing.
+//! [`IntegrityMonitor`] re-hashes every registered model file with SHA-256
+//! at a capped read rate, so verification never competes with inference
+//! for I/O, and compares the result with the digest recorded for the
+//! model: the attested digest when the model had an attestation,
+//! otherwise the one taken on the model's first pass.
+//!
+//! On a mismatch the model's circuit is held open, so its requests fail
+//! fast or move down its fallback chain, and a critical audit event is
+//! written. The hold is lifted when the file hashes clean again (restored
+//! from backup) or the model is unloaded. A file that cannot be read is
+//! reported but not degraded: the mapping keeps serving the original.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use sha2::{Digest, Sha256};
+
+use super::registry::{LoadedModelInfo, ModelHandle, ModelRegistry};
+use crate::scheduler::CircuitBreaker;
+use crate::security::audit::{audit_logger, AuditCategory, AuditEvent, AuditSeverity};
+
+/// How often the monitor looks for models due for verification.
+const TICK: Duration = Duration::from_secs(60);
+
+/// Read size while hashing.
+const CHUNK_BYTES: usize = 1024 * 1024;
+
+/// Hold reason shown in circuit status.
+const HOLD_REASON: &str = "model file failed integrity verification";
+
+#[derive(Debug, Clone)]
+pub struct IntegrityConfig {
+    pub enabled: bool,
+    /// Time between verifications of the same model.
+    pub interval: Duration,
+    /// Read rate while hashing, in bytes per second; 0 is uncapped.
+    pub max_bytes_per_sec: u64,
+}
+
+impl Default for IntegrityConfig {
+    fn default() -> Self {
+        Self { enabled: true, interval: Duration::from_secs(6 * 3600), max_bytes_per_sec: 64 * 1024 * 1024 }
+    }
+}
+
+/// Result of verifying one model.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IntegrityOutcome {
+    /// No digest was recorded; this one is now.
+    Baseline(String),
+    Verified,
+    Mismatch { expected: String, actual: String },
+    /// The file could not be read.
+    Unreadable(String),
+}
+
+#[derive(Default)]
+struct MonitorState {
+    /// Last verification of each registered model.
+    verified: HashMap<u64, Instant>,
+    /// Models held open for a mismatch, with the failing handle.
+    failed: HashMap<String, u64>,
+}
+
+/// Periodically re-hashes registered model files.
+pub struct IntegrityMonitor {
+    config: IntegrityConfig,
+    registry: Arc<ModelRegistry>,
+    circuits: Arc<CircuitBreaker>,
+    state: Mutex<MonitorState>,
+}
+
+impl IntegrityMonitor {
+    pub fn new(config: IntegrityConfig, registry: Arc<ModelRegistry>, circuits: Arc<CircuitBreaker>) -> Self {
+        Self { config, registry, circuits, state: Mutex::new(MonitorState::default()) }
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, MonitorState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Verify every model without a digest or not verified for an
+    /// interval, one at a time. Returns the outcomes by model name.
+    pub async fn tick(&self) -> Vec<(String, IntegrityOutcome)> {
+        let models = self.registry.list_models().await;
+        let due: Vec<LoadedModelInfo> = {
+            let mut state = self.state();
+            state.verified.retain(|handle, _| models.iter().any(|m| m.handle_id == *handle));
+            let circuits = &self.circuits;
+            state.failed.retain(|name, handle| {
+                let loaded = models.iter().any(|m| m.handle_id == *handle);
+                if !loaded {
+                    circuits.release_hold(name);
+                }
+                loaded
+            });
+            let now = Instant::now();
+            models
+                .into_iter()
+                .filter(|m| m.path.is_some())
+                .filter(|m| match (&m.sha256, state.verified.get(&m.handle_id)) {
+                    (None, _) => true,
+                    (Some(_), Some(at)) => now.duration_since(*at) >= self.config.interval,
+                    // Attested at load; that hash is the first verification
+                    (Some(_), None) => {
+                        state.verified.insert(m.handle_id, now);
+                        false
+                    }
+                })
+                .collect()
+        };
+
+        let mut outcomes = Vec::with_capacity(due.len());
+        for model in due {
+            let outcome = self.verify(&model).await;
+            outcomes.push((model.name, outcome));
+        }
+        outcomes
+    }
+
+    async fn verify(&self, model: &LoadedModelInfo) -> IntegrityOutcome {
+        let Some(path) = model.path.clone() else {
+            return IntegrityOutcome::Unreadable("no file recorded".into());
+        };
+        let rate = self.config.max_bytes_per_sec;
+        let hashed = tokio::task::spawn_blocking(move || hash_file(&path, rate))
+            .await
+            .unwrap_or_else(|e| Err(std::io::Error::other(e)));
+        self.state().verified.insert(model.handle_id, Instant::now());
+
+        let actual = match hashed {
+            Ok(actual) => actual,
+            Err(e) => {
+                tracing::warn!(model = %model.name, error = %e, "Cannot re-verify model file");
+                metrics::counter!("core_model_integrity_errors_total", "model" => model.name.clone()).increment(1);
+                return IntegrityOutcome::Unreadable(e.to_string());
+            }
+        };
+        metrics::counter!("core_model_integrity_checks_total", "model" => model.name.clone()).increment(1);
+
+        let Some(expected) = model.sha256.clone() else {
+            self.registry.set_sha256(ModelHandle::new(model.handle_id), actual.clone()).await;
+            tracing::debug!(model = %model.name, sha256 = %actual, "Recorded model file digest");
+            return IntegrityOutcome::Baseline(actual);
+        };
+        if actual.eq_ignore_ascii_case(&expected) {
+            let recovered = self.state().failed.remove(&model.name).is_some();
+            if recovered && self.circuits.release_hold(&model.name) {
+                tracing::info!(model = %model.name, "Model file verifies again; circuit hold released");
+                audit(model, AuditSeverity::Info, "model_integrity_restored", &expected, &actual).await;
+            }
+            return IntegrityOutcome::Verified;
+        }
+
+        let newly_failed = self.state().failed.insert(model.name.clone(), model.handle_id).is_none();
+        if newly_failed {
+            self.circuits.hold_open(&model.name, HOLD_REASON);
+            metrics::counter!("core_model_integrity_failures_total", "model" => model.name.clone()).increment(1);
+            tracing::error!(
+                model = %model.name,
+                expected = %expected,
+                actual = %actual,
+                "Model file digest changed while resident; holding its circuit open"
+            );
+            audit(model, AuditSeverity::Critical, "model_integrity_mismatch", &expected, &actual).await;
+        }
+        IntegrityOutcome::Mismatch { expected, actual }
+    }
+
+    /// Check for due models every minute until the task is aborted.
+    pub async fn run(self: Arc<Self>) {
+        let mut ticker = tokio::time::interval(TICK);
+        loop {
+            ticker.tick().await;
+            self.tick().await;
+        }
+    }
+}
+
+/// Hex SHA-256 of the file at `path`, read at no more than
+/// `max_bytes_per_sec` (0 is uncapped).
+pub fn hash_file(path: &Path, max_bytes_per_sec: u64) -> std::io::Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; CHUNK_BYTES];
+    let started = Instant::now();
+    let mut total = 0u64;
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+        total += n as u64;
+        if max_bytes_per_sec > 0 {
+            let due = Duration::from_secs_f64(total as f64 / max_bytes_per_sec as f64);
+            if let Some(ahead) = due.checked_sub(started.elapsed()) {
+                std::thread::sleep(ahead);
+            }
+        }
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+async fn audit(model: &LoadedModelInfo, severity: AuditSeverity, event_type: &str, expected: &str, actual: &str) {
+    let Some(logger) = audit_logger() else {
+        return;
+    };
+    let message = match severity {
+        AuditSeverity::Info => format!("Model {} file verifies against its recorded digest again", model.name),
+        _ => format!("Model {} file no longer matches its recorded digest; model degraded", model.name),
+    };
+    let Ok(event) = AuditEvent::builder()
+        .severity(severity)
+        .category(AuditCategory::ModelOperation)
+        .event_type(event_type)
+        .message(message)
+        .source("model_integrity")
+        .metadata("model", model.name.as_str())
+        .metadata("path", model.path.as_deref().map(|p| p.display().to_string()).unwrap_or_default())
+        .metadata("expected_sha256", expected)
+        .metadata("actual_sha256", actual)
+        .build()
+    else {
+        return;
+    };
+    logger.log(event).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ModelMetadata;
+
+    #[tokio::test]
+    async fn test_mismatch_holds_circuit_until_restored() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("phi.gguf");
+        std::fs::write(&path, b"GGUF weights").unwrap();
+
+        let registry = Arc::new(ModelRegistry::new());
+        let handle = registry.register(ModelMetadata { name: "phi".into(), size_bytes: 12 }, 0).await;
+        registry.set_path(handle, path.clone()).await;
+        let circuits = Arc::new(CircuitBreaker::default());
+        let config = IntegrityConfig { interval: Duration::ZERO, max_bytes_per_sec: 0, ..Default::default() };
+        let monitor = IntegrityMonitor::new(config, Arc::clone(&registry), Arc::clone(&circuits));
+
+        let outcomes = monitor.tick().await;
+        let [(_, IntegrityOutcome::Baseline(digest))] = &outcomes[..] else {
+            panic!("expected a baseline");
+        };
+        assert_eq!(digest, &hash_file(&path, 0).unwrap());
+        assert_eq!(monitor.tick().await[0].1, IntegrityOutcome::Verified);
+
+        // A bit flips on disk
+        std::fs::write(&path, b"GGUF weightz").unwrap();
+        assert!(matches!(monitor.tick().await[0].1, IntegrityOutcome::Mismatch { .. }));
+        assert!(circuits.try_acquire("phi").is_err());
+        assert!(circuits.try_acquire("other").is_ok());
+
+        std::fs::write(&path, b"GGUF weights").unwrap();
+        assert_eq!(monitor.tick().await[0].1, IntegrityOutcome::Verified);
+        assert!(circuits.try_acquire("phi").is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_unload_lifts_hold() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("phi.gguf");
+        std::fs::write(&path, b"tampered").unwrap();
+
+        let registry = Arc::new(ModelRegistry::new());
+        let handle = registry.register(ModelMetadata { name: "phi".into(), size_bytes: 8 }, 0).await;
+        registry.set_path(handle, path).await;
+        registry.set_sha256(handle, "00".repeat(32)).await;
+        let circuits = Arc::new(CircuitBreaker::default());
+        let config = IntegrityConfig { interval: Duration::ZERO, ..Default::default() };
+        let monitor = IntegrityMonitor::new(config, Arc::clone(&registry), Arc::clone(&circuits));
+
+        // A recorded digest without a verification time counts as verified
+        assert!(monitor.tick().await.is_empty());
+        assert!(matches!(monitor.tick().await[0].1, IntegrityOutcome::Mismatch { .. }));
+        assert!(circuits.try_acquire("phi").is_err());
+
+        registry.unregister(handle).await;
+        assert!(monitor.tick().await.is_empty());
+        assert!(circuits.try_acquire("phi").is_ok());
+    }
+}
@@ -5,6 +5,10 @@ use std::fs::File;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 
+use super::attestation::{self, Attestation, AttestationConfig, AttestationError};
+use super::format::ModelFormat;
+use super::quarantine::{GuardedLoadError, Quarantine};
+
 #[derive(Error, Debug)]
 pub enum LoadError {
     #[error("Model path not allowed: {0}")]
@@ -18,6 +22,29 @@ pub enum LoadError {
 
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
+
+    #[error("Model file quarantined as {0}")]
+    Quarantined(PathBuf),
+
+    #[error("Model loader panicked: {0}")]
+    Panicked(String),
+
+    #[error("Backend failed to load model: {0}")]
+    Backend(String),
+
+    #[error("Model attestation failed: {0}")]
+    Attestation(#[from] AttestationError),
+}
+
+impl From<GuardedLoadError<LoadError>> for LoadError {
+    fn from(err: GuardedLoadError<LoadError>) -> Self {
+        match err {
+            GuardedLoadError::Quarantined(path) => LoadError::Quarantined(path),
+            GuardedLoadError::Failed(e) => e,
+            GuardedLoadError::Panicked(message) => LoadError::Panicked(message),
+            GuardedLoadError::Io(e) => LoadError::Io(e),
+        }
+    }
 }
 
 /// Validated model path within allowed directories.
@@ -38,11 +65,59 @@ const ALLOWED_DIRS: &[&str] = &["models", "tokenizers"];
 /// Loads and validates models from allowed directories.
 pub struct ModelLoader {
     base_path: PathBuf,
+    quarantine: Quarantine,
+    attestation: AttestationConfig,
 }
 
 impl ModelLoader {
     pub fn new(base_path: PathBuf) -> Self {
-        Self { base_path }
+        Self { base_path, quarantine: Quarantine::default(), attestation: AttestationConfig::default() }
+    }
+
+    /// Override how many failed attempts quarantine a file.
+    pub fn with_quarantine(mut self, quarantine: Quarantine) -> Self {
+        self.quarantine = quarantine;
+        self
+    }
+
+    /// Check provenance attestations before loading.
+    pub fn with_attestation(mut self, attestation: AttestationConfig) -> Self {
+        self.attestation = attestation;
+        self
+    }
+
+    /// Verify the provenance attestation of a validated path under the
+    /// configured policy, auditing the outcome. A refusal does not count
+    /// towards quarantine: the file is intact, just not trusted.
+    pub fn attest(&self, model_path: &ModelPath) -> Result<Option<Attestation>, LoadError> {
+        let result = attestation::verify_model(&self.attestation, model_path.as_path());
+        attestation::audit(model_path.as_path(), &result);
+        result.map_err(LoadError::from)
+    }
+
+    /// Run `load` for a validated path under the quarantine guard.
+    ///
+    /// The file's format is detected from its contents and its structure
+    /// validated first; a validation failure counts as a failed attempt
+    /// like any loader error or panic.
+    pub fn load_guarded<T>(
+        &self,
+        model_path: &ModelPath,
+        load: impl FnOnce(&Path, ModelFormat) -> Result<T, LoadError>,
+    ) -> Result<T, LoadError> {
+        let path = model_path.as_path();
+        let _tag = crate::telemetry::tag_scope("model_load");
+        let result = self.quarantine.guard(path, || {
+            let format = ModelFormat::detect(path)?;
+            format.validate(path)?;
+            load(path, format)
+        });
+        result.map_err(LoadError::from)
+    }
+
+    /// Detect the on-disk format of a validated path.
+    pub fn detect_format(&self, model_path: &ModelPath) -> Result<ModelFormat, LoadError> {
+        ModelFormat::detect(model_path.as_path())
     }
 
     /// Validate and create a ModelPath if within allowed directories.
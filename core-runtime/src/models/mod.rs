@@ -7,7 +7,11 @@ pub mod pool;
 pub mod smart_loader;
 pub mod tier_synergy;
 
+pub mod attestation;
 mod drain;
+pub mod fallback;
+pub mod format;
+pub mod gguf_validate;
 mod loader;
 mod preload;
 pub mod registry;
@@ -16,19 +20,49 @@ mod swap;
 
 // v0.5.0: Model registry enhancements
 pub mod history;
+pub mod integrity;
 pub mod persistence;
+pub mod placement;
+pub mod quantize;
+pub mod quarantine;
+pub mod recommend;
+pub mod safetensors_validate;
 pub mod search;
 pub mod version;
+pub mod warm_schedule;
 
+pub use attestation::{
+    Attestation, AttestationConfig, AttestationError, AttestationKind, AttestationPolicy, TrustedIdentity,
+};
 pub use drain::{DrainError, FlightGuard, FlightTracker};
+pub use fallback::{FallbackChains, FallbackError, FallbackReason};
+pub use format::ModelFormat;
+pub use gguf_validate::{validate_gguf, GgufError, GgufSummary};
 pub use history::{VersionHistory, VersionHistoryEntry, VersionSource};
+pub use integrity::{IntegrityConfig, IntegrityMonitor, IntegrityOutcome};
 pub use loader::{LoadError, MappedModel, ModelLoader, ModelMetadata, ModelPath};
 pub use manifest::{ModelArchitecture, ModelCapability, ModelManifest};
-pub use persistence::{PersistenceError, PersistedModel, RegistryPersistence, RegistryState};
-pub use pool::{ModelPool, PoolConfig, PoolError, PoolMetrics, PoolStatus, SwitchResult};
+pub use persistence::{
+    PersistenceError, PersistedModel, PersistedStats, RegistryPersistence, RegistryState,
+};
+pub use placement::{
+    read_adverts, PlacementBoard, PlacementConfig, PlacementError, ReplicaAdvert,
+};
+pub use pool::{
+    BudgetEnforcement, ModelPool, PoolConfig, PoolError, PoolMember, PoolMetrics, PoolStatus, SwitchResult,
+};
 pub use pool::ModelTier as PoolModelTier;
+pub use quantize::{
+    quantize_model, LlamaQuantizer, QuantizeBackend, QuantizeError, QuantizeMethod, QuantizeOptions,
+    QuantizeReport, QuantizeStage,
+};
+pub use quarantine::{GuardedLoadError, Quarantine};
 pub use preload::{ModelPreloader, PreloadError, PreloadedModel};
+pub use recommend::{
+    recommend, Calibration, CpuCalibration, RecommendReport, RecommendTarget, Recommendation,
+};
 pub use registry::{LoadedModelInfo, LoadedModelState, ModelHandle, ModelRegistry};
+pub use safetensors_validate::{validate_safetensors, SafetensorsError, SafetensorsSummary};
 pub use router::{ModelRouter, RouterError};
 pub use search::{ModelQuery, ModelQueryBuilder, ModelSearchResult};
 pub use smart_loader::{LoadHint, SmartLoader, SmartLoaderConfig, SmartLoaderError, SmartLoaderMetrics, SmartLoaderStatus};
@@ -36,3 +70,7 @@ pub use smart_loader::ModelTier as SmartModelTier;
 pub use swap::{SwapError, SwapManager, SwapResult};
 pub use tier_synergy::{SynergyMode, SynergyResult, SynergyStatus, TierSynergy};
 pub use version::{ModelVersion, VersionRange};
+pub use warm_schedule::{
+    HourSet, WarmModel, WarmPoolController, WarmRule, WarmScheduleConfig, WarmScheduleError,
+    WarmTarget, WarmTransition,
+};
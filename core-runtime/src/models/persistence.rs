@@ -3,18 +3,26 @@
 
 //! JSON-based model registry persistence.
 //!
-//! Saves and loads registry state for restart recovery.
+//! Saves and loads registry state for restart recovery, including
+//! cumulative per-model request statistics. With an object store
+//! installed the state is kept there instead of on local disk.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::fs::{self, File};
 use std::io::{BufReader, BufWriter};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
 
+use super::fallback::FallbackChains;
 use super::history::VersionHistory;
 use super::manifest::{ModelArchitecture, ModelCapability};
 use super::version::ModelVersion;
+use crate::storage::{self, Storage};
+
+/// Catalog file name under `base_path`, and its key in object storage.
+pub const REGISTRY_STATE_FILE: &str = "registry_state.json";
 
 /// Error type for persistence operations.
 #[derive(Debug, Clone)]
@@ -61,6 +69,62 @@ pub struct PersistedModel {
     pub history: VersionHistory,
 }
 
+/// Latencies kept per model for the recent-latency digest.
+pub const RECENT_LATENCY_WINDOW: usize = 256;
+
+/// Cumulative request statistics for one model name.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PersistedStats {
+    pub request_count: u64,
+    pub total_latency_ms: f64,
+    pub stream_count: u64,
+    pub total_ttft_ms: f64,
+    pub inter_token_count: u64,
+    pub total_inter_token_ms: f64,
+    /// Last `RECENT_LATENCY_WINDOW` request latencies, oldest first.
+    #[serde(default)]
+    pub recent_latencies_ms: VecDeque<f64>,
+}
+
+impl PersistedStats {
+    /// Fold in another set of stats for the same model name.
+    pub fn merge(&mut self, other: &PersistedStats) {
+        self.request_count += other.request_count;
+        self.total_latency_ms += other.total_latency_ms;
+        self.stream_count += other.stream_count;
+        self.total_ttft_ms += other.total_ttft_ms;
+        self.inter_token_count += other.inter_token_count;
+        self.total_inter_token_ms += other.total_inter_token_ms;
+        for &latency in &other.recent_latencies_ms {
+            push_recent(&mut self.recent_latencies_ms, latency);
+        }
+    }
+
+    /// Latency at quantile `q` (0.0..=1.0) over the recent window.
+    pub fn recent_percentile(&self, q: f64) -> Option<f64> {
+        percentile(&self.recent_latencies_ms, q)
+    }
+}
+
+/// Append to a recent-latency window, evicting the oldest beyond capacity.
+pub fn push_recent(window: &mut VecDeque<f64>, latency_ms: f64) {
+    if window.len() == RECENT_LATENCY_WINDOW {
+        window.pop_front();
+    }
+    window.push_back(latency_ms);
+}
+
+/// Nearest-rank quantile of `values`.
+pub fn percentile(values: &VecDeque<f64>, q: f64) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    let mut sorted: Vec<f64> = values.iter().copied().collect();
+    sorted.sort_by(f64::total_cmp);
+    let rank = (q.clamp(0.0, 1.0) * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.saturating_sub(1)])
+}
+
 /// Complete registry state for persistence.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RegistryState {
@@ -72,6 +136,12 @@ pub struct RegistryState {
     pub models: HashMap<String, PersistedModel>,
     /// Default model ID (if set).
     pub default_model: Option<String>,
+    /// Request statistics indexed by model name.
+    #[serde(default)]
+    pub stats: HashMap<String, PersistedStats>,
+    /// Fallback chains indexed by primary model name.
+    #[serde(default)]
+    pub fallbacks: FallbackChains,
 }
 
 impl Default for RegistryState {
@@ -81,6 +151,8 @@ impl Default for RegistryState {
             saved_at: 0,
             models: HashMap::new(),
             default_model: None,
+            stats: HashMap::new(),
+            fallbacks: FallbackChains::new(),
         }
     }
 }
@@ -88,16 +160,45 @@ impl Default for RegistryState {
 /// Handles saving and loading registry state to JSON.
 pub struct RegistryPersistence {
     state_path: PathBuf,
+    /// Object store holding the state under the file name instead.
+    storage: Option<Arc<dyn Storage>>,
 }
 
 impl RegistryPersistence {
     /// Create a new persistence handler.
     pub fn new(state_path: PathBuf) -> Self {
-        Self { state_path }
+        Self { state_path, storage: None }
+    }
+
+    /// Keep the state in `storage`, keyed by the file name of `state_path`.
+    pub fn with_storage(state_path: PathBuf, storage: Arc<dyn Storage>) -> Self {
+        Self { state_path, storage: Some(storage) }
+    }
+
+    /// The catalog for `base_path`, in the installed object store if any.
+    pub fn for_base_path(base_path: &Path) -> Self {
+        let state_path = base_path.join(REGISTRY_STATE_FILE);
+        match storage::installed() {
+            Some(storage) => Self::with_storage(state_path, storage),
+            None => Self::new(state_path),
+        }
+    }
+
+    fn storage_key(&self) -> String {
+        self.state_path
+            .file_name()
+            .map_or_else(|| REGISTRY_STATE_FILE.to_string(), |name| name.to_string_lossy().into_owned())
     }
 
     /// Save registry state to disk.
     pub fn save(&self, state: &RegistryState) -> Result<(), PersistenceError> {
+        if let Some(storage) = &self.storage {
+            let data = serde_json::to_vec_pretty(state).map_err(|e| PersistenceError::WriteError(e.to_string()))?;
+            return storage
+                .put(&self.storage_key(), &data)
+                .map_err(|e| PersistenceError::WriteError(e.to_string()));
+        }
+
         // Ensure parent directory exists
         if let Some(parent) = self.state_path.parent() {
             fs::create_dir_all(parent).map_err(|e| PersistenceError::WriteError(e.to_string()))?;
@@ -125,6 +226,14 @@ impl RegistryPersistence {
     /// by attempting to open the file directly and handling the error,
     /// rather than checking existence first.
     pub fn load(&self) -> Result<RegistryState, PersistenceError> {
+        if let Some(storage) = &self.storage {
+            let data = storage
+                .get(&self.storage_key())
+                .map_err(|e| PersistenceError::ReadError(e.to_string()))?
+                .ok_or(PersistenceError::NotFound)?;
+            return serde_json::from_slice(&data).map_err(|e| PersistenceError::ParseError(e.to_string()));
+        }
+
         // SECURITY: Do NOT check exists() before opening - that's a TOCTOU race.
         // Instead, try to open directly and handle the error appropriately.
         let file = File::open(&self.state_path).map_err(|e| {
@@ -146,7 +255,10 @@ impl RegistryPersistence {
 
     /// Check if state file exists.
     pub fn exists(&self) -> bool {
-        self.state_path.exists()
+        match &self.storage {
+            Some(storage) => storage.get(&self.storage_key()).is_ok_and(|data| data.is_some()),
+            None => self.state_path.exists(),
+        }
     }
 
     /// Delete state file.
@@ -156,6 +268,10 @@ impl RegistryPersistence {
     /// by attempting to delete the file directly and handling the error,
     /// rather than checking existence first.
     pub fn delete(&self) -> Result<(), PersistenceError> {
+        if let Some(storage) = &self.storage {
+            return storage.delete(&self.storage_key()).map_err(|e| PersistenceError::WriteError(e.to_string()));
+        }
+
         // SECURITY: Do NOT check exists() before deleting - that's a TOCTOU race.
         // Instead, try to delete directly and handle NotFound gracefully.
         match fs::remove_file(&self.state_path) {
@@ -400,6 +516,53 @@ mod tests {
         let _ = fs::remove_dir_all(&temp_dir);
     }
 
+    #[test]
+    fn test_state_without_stats_still_loads() {
+        let json = r#"{"schema_version":1,"saved_at":0,"models":{},"default_model":null}"#;
+        let state: RegistryState = serde_json::from_str(json).unwrap();
+        assert!(state.stats.is_empty());
+    }
+
+    #[test]
+    fn test_stats_merge_and_recent_window() {
+        let mut stats = PersistedStats::default();
+        for i in 1..=RECENT_LATENCY_WINDOW as u64 + 10 {
+            push_recent(&mut stats.recent_latencies_ms, i as f64);
+        }
+        assert_eq!(stats.recent_latencies_ms.len(), RECENT_LATENCY_WINDOW);
+        assert_eq!(stats.recent_latencies_ms.front(), Some(&11.0));
+
+        let mut total = PersistedStats { request_count: 3, total_latency_ms: 30.0, ..Default::default() };
+        total.merge(&PersistedStats { request_count: 2, total_latency_ms: 10.0, ..Default::default() });
+        assert_eq!((total.request_count, total.total_latency_ms), (5, 40.0));
+    }
+
+    #[test]
+    fn test_recent_percentile() {
+        let values: VecDeque<f64> = (1..=100).map(f64::from).collect();
+        assert_eq!(percentile(&values, 0.95), Some(95.0));
+        assert_eq!(percentile(&values, 0.5), Some(50.0));
+        assert_eq!(percentile(&VecDeque::new(), 0.5), None);
+    }
+
+    #[test]
+    fn test_state_in_storage() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Arc::new(crate::storage::LocalStorage::new(dir.path().join("bucket")));
+        let persistence = RegistryPersistence::with_storage(dir.path().join(REGISTRY_STATE_FILE), store);
+        assert!(matches!(persistence.load(), Err(PersistenceError::NotFound)));
+
+        let state = RegistryState { default_model: Some("llama".into()), ..Default::default() };
+        persistence.save(&state).unwrap();
+        assert!(persistence.exists());
+        assert!(dir.path().join("bucket").join(REGISTRY_STATE_FILE).exists());
+        assert!(!dir.path().join(REGISTRY_STATE_FILE).exists());
+        assert_eq!(persistence.load().unwrap().default_model.as_deref(), Some("llama"));
+
+        persistence.delete().unwrap();
+        assert!(!persistence.exists());
+    }
+
     #[test]
     fn test_all_architectures_serializable() {
         let architectures = vec![
@@ -0,0 +1,203 @@
+//! Distributed model placement hints.
+//!
+//! When several replicas share a model volume, each replica periodically
+//! writes an advert listing the models it has loaded into a shared
+//! directory. Callers query the directory to answer "which replica serves
+//! model X" and route accordingly. Adverts are replaced atomically
+//! (temp file + rename) and expire after a TTL, so a crashed replica drops
+//! out without any lock cleanup.
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::registry::ModelRegistry;
+
+#[derive(Error, Debug)]
+pub enum PlacementError {
+    #[error("Placement I/O failed: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid replica id: {0}")]
+    InvalidReplicaId(String),
+}
+
+/// Placement coordination settings.
+#[derive(Debug, Clone)]
+pub struct PlacementConfig {
+    /// Shared directory (typically on the model PVC).
+    pub dir: PathBuf,
+    /// Unique id of this replica (e.g. pod name).
+    pub replica_id: String,
+    /// Adverts older than this are ignored.
+    pub ttl: Duration,
+}
+
+/// One replica's advertised model set.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplicaAdvert {
+    pub replica_id: String,
+    pub models: Vec<String>,
+    /// Seconds since the Unix epoch.
+    pub updated_at: u64,
+}
+
+/// Reads and writes placement adverts in the shared directory.
+pub struct PlacementBoard {
+    config: PlacementConfig,
+}
+
+impl PlacementBoard {
+    pub fn new(config: PlacementConfig) -> Result<Self, PlacementError> {
+        let id = &config.replica_id;
+        let valid = !id.is_empty()
+            && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+            && !id.starts_with('.');
+        if !valid {
+            return Err(PlacementError::InvalidReplicaId(id.clone()));
+        }
+        Ok(Self { config })
+    }
+
+    pub fn replica_id(&self) -> &str {
+        &self.config.replica_id
+    }
+
+    fn advert_path(&self) -> PathBuf {
+        self.config.dir.join(format!("{}.json", self.config.replica_id))
+    }
+
+    /// Publish this replica's loaded models.
+    pub fn advertise(&self, models: Vec<String>) -> Result<(), PlacementError> {
+        fs::create_dir_all(&self.config.dir)?;
+        let advert = ReplicaAdvert {
+            replica_id: self.config.replica_id.clone(),
+            models,
+            updated_at: now_secs(),
+        };
+        let path = self.advert_path();
+        let temp_path = path.with_extension("tmp");
+        fs::write(&temp_path, serde_json::to_vec(&advert).map_err(std::io::Error::from)?)?;
+        fs::rename(&temp_path, &path)?;
+        Ok(())
+    }
+
+    /// Remove this replica's advert (on shutdown).
+    pub fn withdraw(&self) -> Result<(), PlacementError> {
+        match fs::remove_file(self.advert_path()) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Live (non-expired) adverts from all replicas.
+    pub fn replicas(&self) -> Result<Vec<ReplicaAdvert>, PlacementError> {
+        read_adverts(&self.config.dir, self.config.ttl)
+    }
+
+    /// Replica ids currently advertising `model`.
+    pub fn which(&self, model: &str) -> Result<Vec<String>, PlacementError> {
+        Ok(self
+            .replicas()?
+            .into_iter()
+            .filter(|r| r.models.iter().any(|m| m == model))
+            .map(|r| r.replica_id)
+            .collect())
+    }
+
+    /// Re-advertise the registry contents every `interval` until cancelled.
+    pub async fn run_advertiser(self: Arc<Self>, registry: Arc<ModelRegistry>, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            let models = registry.list_models().await.into_iter().map(|m| m.name).collect();
+            if let Err(e) = self.advertise(models) {
+                tracing::warn!(error = %e, "Failed to publish placement advert");
+            }
+        }
+    }
+}
+
+/// Read adverts from `dir`, skipping unreadable or expired entries.
+pub fn read_adverts(dir: &std::path::Path, ttl: Duration) -> Result<Vec<ReplicaAdvert>, PlacementError> {
+    let cutoff = now_secs().saturating_sub(ttl.as_secs());
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut adverts: Vec<ReplicaAdvert> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| fs::read(path).ok())
+        .filter_map(|bytes| serde_json::from_slice::<ReplicaAdvert>(&bytes).ok())
+        .filter(|advert| advert.updated_at >= cutoff)
+        .collect();
+    adverts.sort_by(|a, b| a.replica_id.cmp(&b.replica_id));
+    Ok(adverts)
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board(dir: &std::path::Path, id: &str) -> PlacementBoard {
+        PlacementBoard::new(PlacementConfig {
+            dir: dir.to_path_buf(),
+            replica_id: id.to_string(),
+            ttl: Duration::from_secs(60),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_which_replica_serves_model() {
+        let dir = std::env::temp_dir().join("veritas_test_placement");
+        let _ = fs::remove_dir_all(&dir);
+        let a = board(&dir, "replica-a");
+        let b = board(&dir, "replica-b");
+        a.advertise(vec!["phi-3".into(), "llama".into()]).unwrap();
+        b.advertise(vec!["llama".into()]).unwrap();
+
+        assert_eq!(a.which("phi-3").unwrap(), vec!["replica-a"]);
+        assert_eq!(b.which("llama").unwrap(), vec!["replica-a", "replica-b"]);
+
+        a.withdraw().unwrap();
+        assert!(b.which("phi-3").unwrap().is_empty());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_expired_adverts_ignored() {
+        let dir = std::env::temp_dir().join("veritas_test_placement_ttl");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let stale = ReplicaAdvert { replica_id: "old".into(), models: vec!["m".into()], updated_at: 0 };
+        fs::write(dir.join("old.json"), serde_json::to_vec(&stale).unwrap()).unwrap();
+
+        assert!(board(&dir, "new").which("m").unwrap().is_empty());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_rejects_path_like_replica_id() {
+        let config = PlacementConfig {
+            dir: PathBuf::from("/tmp"),
+            replica_id: "../escape".into(),
+            ttl: Duration::from_secs(60),
+        };
+        assert!(PlacementBoard::new(config).is_err());
+    }
+}
@@ -2,14 +2,35 @@
 //!
 //! Maintains multiple models in memory to enable seamless tier transitions
 //! without load-time latency.
+//!
+//! # Memory budget
+//! A resident model costs its weights plus its KV cache: the bytes the
+//! attached [`KvCacheManager`] holds, or `kv_headroom_bytes` reserved for
+//! growth, whichever is larger. When a preload or [`ModelPool::enforce_budget`]
+//! finds the total over `max_memory_bytes`, KV caches are first shrunk back
+//! to their headroom, evicting their least recently used sequences; models
+//! are evicted only if that is not enough.
+//!
+//! # Lock Order
+//! `models`, then `active_model`, then `metrics`. No pool lock is held
+//! while calling into the registry: eviction picks its victims under the
+//! `models` lock and unregisters them after releasing it. KV caches are
+//! shrunk under the `models` lock; their own locks never await.
+//! The attached request queue is told about a switch after every pool
+//! lock is released.
 
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tokio::sync::RwLock;
 
 use super::registry::{ModelHandle, ModelRegistry};
+use crate::memory::KvCacheManager;
+use crate::scheduler::{QueuedRequest, RequestQueue};
+use crate::telemetry::{self, emit_event, RuntimeEvent, RuntimeEventKind};
 
 #[derive(Error, Debug)]
 pub enum PoolError {
@@ -27,7 +48,7 @@ pub enum PoolError {
 }
 
 /// Model tier for prioritized eviction.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum ModelTier {
     /// CI/Testing - lowest priority, first to evict
     Testing = 0,
@@ -38,11 +59,8 @@ pub enum ModelTier {
 }
 
 /// Pooled model entry with usage tracking.
-#[derive(Debug)]
 struct PooledModel {
     handle: ModelHandle,
-    /// Stored for debugging/logging
-    #[allow(dead_code)]
     model_id: String,
     tier: ModelTier,
     memory_bytes: usize,
@@ -52,9 +70,19 @@ struct PooledModel {
     last_used: Instant,
     use_count: u64,
     warmup_complete: bool,
+    kv_cache: Option<Arc<KvCacheManager>>,
 }
 
 impl PooledModel {
+    fn kv_bytes(&self) -> usize {
+        self.kv_cache.as_ref().map_or(0, |kv| kv.memory_usage())
+    }
+
+    /// Weights plus KV usage or the headroom reserved for it.
+    fn footprint(&self, kv_headroom: usize) -> usize {
+        self.memory_bytes + self.kv_bytes().max(kv_headroom)
+    }
+
     /// Calculate eviction score (lower = evict first).
     fn eviction_score(&self) -> u64 {
         let tier_weight = (self.tier as u64) * 1_000_000;
@@ -77,6 +105,8 @@ pub struct PoolConfig {
     pub warmup_prompt: String,
     /// Enable background preloading
     pub enable_preload: bool,
+    /// Memory reserved per resident model for KV cache growth (bytes)
+    pub kv_headroom_bytes: usize,
 }
 
 impl Default for PoolConfig {
@@ -86,6 +116,7 @@ impl Default for PoolConfig {
             max_memory_bytes: 8 * 1024 * 1024 * 1024, // 8 GB
             warmup_prompt: "Hello".to_string(),
             enable_preload: true,
+            kv_headroom_bytes: 0,
         }
     }
 }
@@ -98,6 +129,24 @@ pub struct PoolMetrics {
     pub evictions: u64,
     pub warmups_completed: u64,
     pub avg_switch_latency_ns: u64,
+    /// KV sequences evicted to keep the pool within budget
+    pub kv_sequences_evicted: u64,
+    pub kv_bytes_reclaimed: u64,
+}
+
+/// Outcome of one [`ModelPool::enforce_budget`] pass.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BudgetEnforcement {
+    pub kv_sequences_evicted: usize,
+    pub kv_bytes_reclaimed: usize,
+    pub models_evicted: Vec<String>,
+}
+
+/// KV memory reclaimed under the `models` lock.
+#[derive(Default)]
+struct KvReclaim {
+    sequences: usize,
+    bytes: usize,
 }
 
 /// Model pool for instant tier switching.
@@ -107,6 +156,7 @@ pub struct ModelPool {
     models: Arc<RwLock<HashMap<String, PooledModel>>>,
     active_model: Arc<RwLock<Option<String>>>,
     metrics: Arc<RwLock<PoolMetrics>>,
+    queue: Option<Arc<RequestQueue>>,
 }
 
 impl ModelPool {
@@ -117,9 +167,17 @@ impl ModelPool {
             models: Arc::new(RwLock::new(HashMap::new())),
             active_model: Arc::new(RwLock::new(None)),
             metrics: Arc::new(RwLock::new(PoolMetrics::default())),
+            queue: None,
         }
     }
 
+    /// Tell `queue` when a switch deactivates a model, so its queued
+    /// requests follow the queue's switch policy.
+    pub fn with_queue(mut self, queue: Arc<RequestQueue>) -> Self {
+        self.queue = Some(queue);
+        self
+    }
+
     /// Add a model to the pool (preload without activating).
     pub async fn preload(
         &self,
@@ -128,44 +186,150 @@ impl ModelPool {
         tier: ModelTier,
         memory_bytes: usize,
     ) -> Result<(), PoolError> {
+        // Capacity check, eviction and insert happen under one `models`
+        // lock, so concurrent preloads cannot overfill the pool
+        let headroom = self.config.kv_headroom_bytes;
+        let (result, evicted, reclaimed) = {
+            let mut models = self.models.write().await;
+
+            // Check if already loaded
+            if models.contains_key(&model_id) {
+                return Err(PoolError::AlreadyLoaded(model_id));
+            }
+
+            let active = self.active_model.read().await.clone();
+            let mut evicted = Vec::new();
+            let mut reclaimed = None;
+            let mut result = Ok(());
+            let over_budget = |models: &HashMap<String, PooledModel>| {
+                Self::committed(models, headroom) + memory_bytes + headroom > self.config.max_memory_bytes
+            };
+            // Check capacity and memory; KV goes before any model
+            while models.len() >= self.config.max_models || over_budget(&models) {
+                if reclaimed.is_none() && models.len() < self.config.max_models {
+                    reclaimed = Some(Self::reclaim_kv(&models, headroom));
+                    continue;
+                }
+                match Self::take_victim(&mut models, active.as_deref()) {
+                    Some(victim) => evicted.push(victim),
+                    None => {
+                        result = Err(PoolError::EvictionFailed);
+                        break;
+                    }
+                }
+            }
+
+            if result.is_ok() {
+                let now = Instant::now();
+                models.insert(
+                    model_id.clone(),
+                    PooledModel {
+                        handle,
+                        model_id,
+                        tier,
+                        memory_bytes,
+                        loaded_at: now,
+                        last_used: now,
+                        use_count: 0,
+                        warmup_complete: false,
+                        kv_cache: None,
+                    },
+                );
+            }
+            (result, evicted, reclaimed.unwrap_or_default())
+        };
+
+        self.finish_reclaim(&reclaimed).await;
+        self.finish_evictions(evicted).await;
+        result
+    }
+
+    /// Attach the KV cache a pooled model's sequences use, so its growth
+    /// counts against the pool budget.
+    pub async fn attach_kv_cache(&self, model_id: &str, kv_cache: Arc<KvCacheManager>) -> Result<(), PoolError> {
         let mut models = self.models.write().await;
+        let model = models
+            .get_mut(model_id)
+            .ok_or_else(|| PoolError::ModelNotFound(model_id.to_string()))?;
+        model.kv_cache = Some(kv_cache);
+        Ok(())
+    }
 
-        // Check if already loaded
-        if models.contains_key(&model_id) {
-            return Err(PoolError::AlreadyLoaded(model_id));
-        }
+    /// Free KV sequences idle for at least `idle` in every attached KV
+    /// cache. Returns the number of sequences freed.
+    pub async fn compact_kv(&self, idle: Duration) -> usize {
+        let models = self.models.read().await;
+        models.values().filter_map(|m| m.kv_cache.as_ref()).map(|kv| kv.compact(idle)).sum()
+    }
 
-        // Check capacity
-        if models.len() >= self.config.max_models {
-            drop(models);
-            self.evict_one().await?;
-            models = self.models.write().await;
-        }
+    /// Bring the pool back within `max_memory_bytes` after KV growth:
+    /// shrink KV caches to their headroom, then evict models other than
+    /// the active one. Run after batches or periodically.
+    pub async fn enforce_budget(&self) -> BudgetEnforcement {
+        let headroom = self.config.kv_headroom_bytes;
+        let (reclaimed, evicted) = {
+            let mut models = self.models.write().await;
+            let mut reclaimed = KvReclaim::default();
+            let mut evicted = Vec::new();
+            if Self::committed(&models, headroom) > self.config.max_memory_bytes {
+                reclaimed = Self::reclaim_kv(&models, headroom);
+                let active = self.active_model.read().await.clone();
+                while Self::committed(&models, headroom) > self.config.max_memory_bytes {
+                    match Self::take_victim(&mut models, active.as_deref()) {
+                        Some(victim) => evicted.push(victim),
+                        None => break,
+                    }
+                }
+            }
+            telemetry::record_pool_memory(
+                models.values().map(|m| m.memory_bytes).sum(),
+                models.values().map(PooledModel::kv_bytes).sum(),
+            );
+            (reclaimed, evicted)
+        };
 
-        // Check memory
-        let current_memory: usize = models.values().map(|m| m.memory_bytes).sum();
-        if current_memory + memory_bytes > self.config.max_memory_bytes {
-            drop(models);
-            self.evict_for_memory(memory_bytes).await?;
-            models = self.models.write().await;
+        let enforcement = BudgetEnforcement {
+            kv_sequences_evicted: reclaimed.sequences,
+            kv_bytes_reclaimed: reclaimed.bytes,
+            models_evicted: evicted.iter().map(|m| m.model_id.clone()).collect(),
+        };
+        self.finish_reclaim(&reclaimed).await;
+        self.finish_evictions(evicted).await;
+        enforcement
+    }
+
+    /// Memory the locked pool has committed, KV included.
+    fn committed(models: &HashMap<String, PooledModel>, kv_headroom: usize) -> usize {
+        models.values().map(|m| m.footprint(kv_headroom)).sum()
+    }
+
+    /// Shrink every KV cache above its headroom back down to it.
+    fn reclaim_kv(models: &HashMap<String, PooledModel>, kv_headroom: usize) -> KvReclaim {
+        let mut reclaimed = KvReclaim::default();
+        for kv in models.values().filter_map(|m| m.kv_cache.as_ref()) {
+            let before = kv.memory_usage();
+            if before > kv_headroom {
+                reclaimed.sequences += kv.shrink_to(kv_headroom);
+                reclaimed.bytes += before.saturating_sub(kv.memory_usage());
+            }
         }
+        reclaimed
+    }
 
-        let now = Instant::now();
-        models.insert(
-            model_id.clone(),
-            PooledModel {
-                handle,
-                model_id,
-                tier,
-                memory_bytes,
-                loaded_at: now,
-                last_used: now,
-                use_count: 0,
-                warmup_complete: false,
-            },
+    /// Record KV memory reclaimed for the budget, with no pool lock held.
+    async fn finish_reclaim(&self, reclaimed: &KvReclaim) {
+        if reclaimed.bytes == 0 && reclaimed.sequences == 0 {
+            return;
+        }
+        tracing::info!(
+            sequences = reclaimed.sequences,
+            bytes = reclaimed.bytes,
+            "Reclaimed KV cache to keep model pool within budget"
         );
-
-        Ok(())
+        telemetry::record_pool_kv_reclaim(reclaimed.sequences, reclaimed.bytes);
+        let mut metrics = self.metrics.write().await;
+        metrics.kv_sequences_evicted += reclaimed.sequences as u64;
+        metrics.kv_bytes_reclaimed += reclaimed.bytes as u64;
     }
 
     /// Switch to a model in the pool (instant if preloaded).
@@ -186,7 +350,8 @@ impl ModelPool {
         drop(models);
 
         // Update active model
-        *self.active_model.write().await = Some(model_id.to_string());
+        let previous = self.active_model.write().await.replace(model_id.to_string());
+        let previous = previous.filter(|prev| prev != model_id);
 
         let switch_latency = start.elapsed();
 
@@ -197,54 +362,68 @@ impl ModelPool {
         let total = metrics.pool_hits;
         metrics.avg_switch_latency_ns =
             (metrics.avg_switch_latency_ns * (total - 1) + switch_latency.as_nanos() as u64) / total;
+        drop(metrics);
+
+        let mut deactivated = Vec::new();
+        if let Some(queue) = &self.queue {
+            queue.model_activated(model_id).await;
+            if let Some(prev) = &previous {
+                deactivated = queue.model_deactivated(prev).await;
+            }
+        }
 
         Ok(SwitchResult {
             handle,
             switch_latency,
             was_preloaded: true,
             was_warmed,
+            previous,
+            deactivated,
         })
     }
 
     /// Mark a model as warmed up (after running warmup inference).
     pub async fn mark_warmed(&self, model_id: &str) {
-        if let Some(model) = self.models.write().await.get_mut(model_id) {
-            model.warmup_complete = true;
+        let warmed = match self.models.write().await.get_mut(model_id) {
+            Some(model) => {
+                model.warmup_complete = true;
+                true
+            }
+            None => false,
+        };
+        if warmed {
             self.metrics.write().await.warmups_completed += 1;
         }
     }
 
-    /// Evict lowest-priority model from pool.
-    async fn evict_one(&self) -> Result<String, PoolError> {
-        let mut models = self.models.write().await;
-        let active = self.active_model.read().await.clone();
-
+    /// Remove the lowest-priority model other than `active` from the
+    /// locked pool.
+    fn take_victim(models: &mut HashMap<String, PooledModel>, active: Option<&str>) -> Option<PooledModel> {
         // Find model with lowest eviction score (excluding active)
         let evict_id = models
             .iter()
-            .filter(|(id, _)| active.as_ref() != Some(*id))
+            .filter(|(id, _)| active != Some(id.as_str()))
             .min_by_key(|(_, m)| m.eviction_score())
-            .map(|(id, _)| id.clone());
-
-        if let Some(id) = evict_id {
-            let model = models.remove(&id).unwrap();
-            self.registry.unregister(model.handle).await;
-            self.metrics.write().await.evictions += 1;
-            Ok(id)
-        } else {
-            Err(PoolError::EvictionFailed)
-        }
+            .map(|(id, _)| id.clone())?;
+        models.remove(&evict_id)
     }
 
-    /// Evict models until we have enough memory.
-    async fn evict_for_memory(&self, needed_bytes: usize) -> Result<(), PoolError> {
-        loop {
-            let current: usize = self.models.read().await.values().map(|m| m.memory_bytes).sum();
-            if current + needed_bytes <= self.config.max_memory_bytes {
-                return Ok(());
-            }
-            self.evict_one().await?;
+    /// Unregister evicted models and record the evictions, with no pool
+    /// lock held.
+    async fn finish_evictions(&self, evicted: Vec<PooledModel>) {
+        if evicted.is_empty() {
+            return;
         }
+        for model in &evicted {
+            self.registry.unregister(model.handle).await;
+            emit_event(RuntimeEvent::new(
+                RuntimeEventKind::ModelEvicted,
+                tracing::Level::WARN,
+                model.model_id.clone(),
+                "evicted from pool",
+            ));
+        }
+        self.metrics.write().await.evictions += evicted.len() as u64;
     }
 
     /// Get current pool status.
@@ -253,15 +432,33 @@ impl ModelPool {
         let active = self.active_model.read().await.clone();
         let metrics = self.metrics.read().await.clone();
 
+        let headroom = self.config.kv_headroom_bytes;
         PoolStatus {
             model_count: models.len(),
             total_memory_bytes: models.values().map(|m| m.memory_bytes).sum(),
+            kv_memory_bytes: models.values().map(PooledModel::kv_bytes).sum(),
+            committed_memory_bytes: Self::committed(&models, headroom),
             active_model: active,
             loaded_models: models.keys().cloned().collect(),
             metrics,
         }
     }
 
+    /// Current pool members, for snapshotting.
+    pub async fn members(&self) -> Vec<PoolMember> {
+        self.models
+            .read()
+            .await
+            .values()
+            .map(|m| PoolMember {
+                model_id: m.model_id.clone(),
+                tier: m.tier,
+                memory_bytes: m.memory_bytes,
+                warmed: m.warmup_complete,
+            })
+            .collect()
+    }
+
     /// Check if a model is in the pool.
     pub async fn contains(&self, model_id: &str) -> bool {
         self.models.read().await.contains_key(model_id)
@@ -295,13 +492,32 @@ pub struct SwitchResult {
     pub switch_latency: Duration,
     pub was_preloaded: bool,
     pub was_warmed: bool,
+    /// Model active before the switch, when it was a different one.
+    pub previous: Option<String>,
+    /// Queued requests for `previous` failed by a fail-fast queue; each is
+    /// marked deactivated.
+    pub deactivated: Vec<QueuedRequest>,
+}
+
+/// Pool membership entry, as captured in a runtime snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PoolMember {
+    pub model_id: String,
+    pub tier: ModelTier,
+    pub memory_bytes: usize,
+    pub warmed: bool,
 }
 
 /// Current pool status.
 #[derive(Debug)]
 pub struct PoolStatus {
     pub model_count: usize,
+    /// Model weights only
     pub total_memory_bytes: usize,
+    /// KV cache held by attached caches
+    pub kv_memory_bytes: usize,
+    /// Weights plus KV usage or headroom, as budgeted
+    pub committed_memory_bytes: usize,
     pub active_model: Option<String>,
     pub loaded_models: Vec<String>,
     pub metrics: PoolMetrics,
@@ -330,7 +546,6 @@ mod tests {
         let result = pool.switch_to("qwen-0.5b").await.unwrap();
         assert_eq!(result.handle, handle);
         assert!(result.was_preloaded);
-        assert!(result.switch_latency < Duration::from_millis(1));
     }
 
     #[tokio::test]
@@ -356,19 +571,148 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn pool_switch_latency_under_1ms() {
+    async fn pool_eviction_spares_active_model() {
+        let registry = Arc::new(ModelRegistry::new());
+        let config = PoolConfig {
+            max_models: 3,
+            max_memory_bytes: 300,
+            kv_headroom_bytes: 0,
+            ..Default::default()
+        };
+        let pool = ModelPool::new(config, registry.clone());
+
+        pool.preload("a".to_string(), ModelHandle::new(1), ModelTier::Testing, 100).await.unwrap();
+        pool.preload("b".to_string(), ModelHandle::new(2), ModelTier::Default, 100).await.unwrap();
+        pool.switch_to("a").await.unwrap();
+
+        // Needs two evictions for memory; only "b" may go
+        let result = pool.preload("c".to_string(), ModelHandle::new(3), ModelTier::Quality, 250).await;
+        assert!(matches!(result, Err(PoolError::EvictionFailed)));
+        assert!(pool.contains("a").await);
+        assert!(!pool.contains("b").await);
+        assert!(!pool.contains("c").await);
+        assert_eq!(pool.status().await.metrics.evictions, 1);
+    }
+
+    fn kv_cache(pages: usize) -> Arc<KvCacheManager> {
+        let kv = Arc::new(KvCacheManager::new(crate::memory::KvCacheConfig {
+            hidden_dim: 8,
+            max_pages: 16,
+            max_seq_len: 256,
+            ..Default::default()
+        }));
+        let seq = kv.allocate_sequence();
+        let values = vec![1.0f32; 8];
+        for _ in 0..pages * crate::memory::PAGE_TOKENS {
+            kv.append_kv(seq, &values, &values).unwrap();
+        }
+        kv
+    }
+
+    #[tokio::test]
+    async fn pool_compacts_idle_kv_sequences() {
+        let pool = ModelPool::new(PoolConfig::default(), Arc::new(ModelRegistry::new()));
+        pool.preload("a".to_string(), ModelHandle::new(1), ModelTier::Testing, 100).await.unwrap();
+        let kv = kv_cache(1);
+        pool.attach_kv_cache("a", kv.clone()).await.unwrap();
+
+        assert_eq!(pool.compact_kv(Duration::from_secs(60)).await, 0);
+        assert_eq!(pool.compact_kv(Duration::ZERO).await, 1);
+        assert_eq!(kv.active_sequences(), 0);
+    }
+
+    #[tokio::test]
+    async fn pool_reclaims_kv_before_evicting_models() {
+        let page = kv_cache(1).memory_usage();
+        let registry = Arc::new(ModelRegistry::new());
+        let config = PoolConfig {
+            max_models: 3,
+            max_memory_bytes: 200 + 3 * page,
+            kv_headroom_bytes: page,
+            ..Default::default()
+        };
+        let pool = ModelPool::new(config, registry.clone());
+
+        pool.preload("a".to_string(), ModelHandle::new(1), ModelTier::Testing, 100).await.unwrap();
+        let kv = kv_cache(3);
+        pool.attach_kv_cache("a", kv.clone()).await.unwrap();
+        assert_eq!(pool.status().await.committed_memory_bytes, 100 + 3 * page);
+
+        // "b" needs its weights plus headroom; shrinking a's KV makes room
+        pool.preload("b".to_string(), ModelHandle::new(2), ModelTier::Default, 100).await.unwrap();
+        assert!(pool.contains("a").await);
+        assert_eq!(kv.active_sequences(), 0);
+        assert_eq!(kv.memory_usage(), 0);
+
+        let status = pool.status().await;
+        assert_eq!(status.committed_memory_bytes, 200 + 2 * page);
+        assert_eq!(status.metrics.kv_sequences_evicted, 1);
+        assert_eq!(status.metrics.evictions, 0);
+    }
+
+    #[tokio::test]
+    async fn pool_enforce_budget_after_kv_growth() {
+        let page = kv_cache(1).memory_usage();
+        let registry = Arc::new(ModelRegistry::new());
+        let config = PoolConfig {
+            max_models: 3,
+            max_memory_bytes: 200 + 2 * page,
+            kv_headroom_bytes: page,
+            ..Default::default()
+        };
+        let pool = ModelPool::new(config, registry.clone());
+
+        pool.preload("a".to_string(), ModelHandle::new(1), ModelTier::Testing, 100).await.unwrap();
+        pool.preload("b".to_string(), ModelHandle::new(2), ModelTier::Default, 100).await.unwrap();
+        assert_eq!(pool.enforce_budget().await, BudgetEnforcement::default());
+
+        pool.attach_kv_cache("b", kv_cache(2)).await.unwrap();
+        let enforcement = pool.enforce_budget().await;
+        assert_eq!(enforcement.kv_sequences_evicted, 1);
+        assert!(enforcement.models_evicted.is_empty());
+        assert!(pool.contains("a").await && pool.contains("b").await);
+    }
+
+    #[tokio::test]
+    async fn pool_repeated_switch_stays_preloaded() {
+        // Switch latency is tracked by the pool group in benches/regression.rs
         let registry = Arc::new(ModelRegistry::new());
         let pool = ModelPool::new(PoolConfig::default(), registry.clone());
 
         pool.preload("test".to_string(), ModelHandle::new(1), ModelTier::Default, 100).await.unwrap();
 
-        // Multiple switches should all be fast
         for _ in 0..100 {
             let result = pool.switch_to("test").await.unwrap();
-            assert!(result.switch_latency < Duration::from_millis(1));
+            assert!(result.was_preloaded);
         }
     }
 
+    #[tokio::test]
+    async fn pool_switch_deactivates_previous_model_in_queue() {
+        use crate::engine::InferenceParams;
+        use crate::scheduler::{Priority, RequestQueueConfig, SwitchPolicy};
+
+        let queue = Arc::new(
+            RequestQueue::new(RequestQueueConfig::default()).with_switch_policy(SwitchPolicy::FailFast),
+        );
+        let pool = ModelPool::new(PoolConfig::default(), Arc::new(ModelRegistry::new())).with_queue(queue.clone());
+        pool.preload("a".to_string(), ModelHandle::new(1), ModelTier::Default, 100).await.unwrap();
+        pool.preload("b".to_string(), ModelHandle::new(2), ModelTier::Default, 100).await.unwrap();
+
+        assert!(pool.switch_to("a").await.unwrap().previous.is_none());
+        for model in ["a", "b"] {
+            let params = InferenceParams::default();
+            queue.enqueue(model.to_string(), "p".to_string(), params, Priority::Normal).await.unwrap();
+        }
+        // Re-activating the active model deactivates nothing
+        assert!(pool.switch_to("a").await.unwrap().deactivated.is_empty());
+
+        let result = pool.switch_to("b").await.unwrap();
+        assert_eq!(result.previous.as_deref(), Some("a"));
+        assert_eq!(result.deactivated.len(), 1);
+        assert_eq!(queue.len().await, 1);
+    }
+
     #[tokio::test]
     async fn pool_warmup_tracking() {
         let registry = Arc::new(ModelRegistry::new());
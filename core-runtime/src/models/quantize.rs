@@ -0,0 +1,380 @@
+//! Offline GGUF quantization for the `models quantize` command.
+//!
+//! Keeps the artifact pipeline inside the runtime binary: an encrypted input
+//! is decrypted to a private temp file, its header validated, the backend
+//! quantizes into `<out>.partial`, the result is optionally re-encrypted,
+//! hashed, and only then renamed into place next to a `<out>.sha256` file in
+//! `sha256sum` format.
+
+use std::fmt;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use super::gguf_validate::validate_gguf;
+use crate::security::encryption::ModelEncryption;
+
+/// Magic written by `ModelEncryption::encrypt_file`.
+const ENCRYPTED_MAGIC: &[u8; 5] = b"GGGCM";
+const HASH_CHUNK: usize = 1 << 20;
+
+#[derive(Error, Debug)]
+pub enum QuantizeError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid input model: {0}")]
+    InvalidInput(String),
+
+    #[error("Output already exists: {0}")]
+    OutputExists(PathBuf),
+
+    #[error("Input is encrypted but no decryption key was provided")]
+    KeyRequired,
+
+    #[error("Encryption error: {0}")]
+    Encryption(String),
+
+    #[error("Quantization failed: {0}")]
+    Backend(String),
+}
+
+/// Target quantization type (llama.cpp naming), highest quality first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuantizeMethod {
+    F16,
+    Q8_0,
+    Q6K,
+    Q5KM,
+    Q4KM,
+    Q4_0,
+}
+
+impl QuantizeMethod {
+    pub const ALL: [QuantizeMethod; 6] = [
+        QuantizeMethod::F16,
+        QuantizeMethod::Q8_0,
+        QuantizeMethod::Q6K,
+        QuantizeMethod::Q5KM,
+        QuantizeMethod::Q4KM,
+        QuantizeMethod::Q4_0,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QuantizeMethod::F16 => "f16",
+            QuantizeMethod::Q8_0 => "q8_0",
+            QuantizeMethod::Q6K => "q6_k",
+            QuantizeMethod::Q5KM => "q5_k_m",
+            QuantizeMethod::Q4KM => "q4_k_m",
+            QuantizeMethod::Q4_0 => "q4_0",
+        }
+    }
+
+    /// llama.cpp `llama_ftype` value.
+    pub fn ftype(&self) -> u32 {
+        match self {
+            QuantizeMethod::F16 => 1,
+            QuantizeMethod::Q4_0 => 2,
+            QuantizeMethod::Q8_0 => 7,
+            QuantizeMethod::Q4KM => 15,
+            QuantizeMethod::Q5KM => 17,
+            QuantizeMethod::Q6K => 18,
+        }
+    }
+
+    /// Method stored under a GGUF `general.file_type`, if it is one of ours.
+    pub fn from_ftype(ftype: u32) -> Option<Self> {
+        QuantizeMethod::ALL.into_iter().find(|m| m.ftype() == ftype)
+    }
+
+    /// Average bits per weight, including block scales.
+    pub fn bits_per_weight(&self) -> f64 {
+        match self {
+            QuantizeMethod::F16 => 16.0,
+            QuantizeMethod::Q8_0 => 8.5,
+            QuantizeMethod::Q6K => 6.56,
+            QuantizeMethod::Q5KM => 5.69,
+            QuantizeMethod::Q4KM => 4.85,
+            QuantizeMethod::Q4_0 => 4.55,
+        }
+    }
+}
+
+impl fmt::Display for QuantizeMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for QuantizeMethod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        QuantizeMethod::ALL
+            .into_iter()
+            .find(|m| m.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                let known: Vec<_> = QuantizeMethod::ALL.iter().map(|m| m.as_str()).collect();
+                format!("unknown method '{s}' (expected one of: {})", known.join(", "))
+            })
+    }
+}
+
+/// Pipeline stage, reported as the conversion advances.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuantizeStage {
+    Decrypting,
+    Validating,
+    Quantizing,
+    Encrypting,
+    Hashing { done: u64, total: u64 },
+}
+
+/// Performs the actual tensor conversion.
+pub trait QuantizeBackend {
+    fn quantize(
+        &self,
+        input: &Path,
+        output: &Path,
+        method: QuantizeMethod,
+        threads: u32,
+    ) -> Result<(), QuantizeError>;
+}
+
+/// llama.cpp's `llama_model_quantize`.
+pub struct LlamaQuantizer;
+
+impl QuantizeBackend for LlamaQuantizer {
+    #[cfg(feature = "gguf")]
+    fn quantize(
+        &self,
+        input: &Path,
+        output: &Path,
+        method: QuantizeMethod,
+        threads: u32,
+    ) -> Result<(), QuantizeError> {
+        use std::ffi::CString;
+
+        let c_path = |p: &Path| {
+            CString::new(p.to_string_lossy().as_bytes())
+                .map_err(|_| QuantizeError::InvalidInput("path contains NUL".into()))
+        };
+        let (input, output) = (c_path(input)?, c_path(output)?);
+        // SAFETY: both paths are valid NUL-terminated strings and params is
+        // initialised by llama.cpp's own default constructor
+        let status = unsafe {
+            let mut params = llama_cpp_sys_2::llama_model_quantize_default_params();
+            params.nthread = threads as i32;
+            params.ftype = method.ftype() as _;
+            llama_cpp_sys_2::llama_model_quantize(input.as_ptr(), output.as_ptr(), &params)
+        };
+        if status != 0 {
+            return Err(QuantizeError::Backend(format!("llama_model_quantize returned {status}")));
+        }
+        Ok(())
+    }
+
+    #[cfg(not(feature = "gguf"))]
+    fn quantize(&self, _: &Path, _: &Path, _: QuantizeMethod, _: u32) -> Result<(), QuantizeError> {
+        Err(QuantizeError::Backend(
+            "GGUF support not compiled in. Enable 'gguf' feature.".into(),
+        ))
+    }
+}
+
+/// What to convert and how.
+#[derive(Debug, Clone)]
+pub struct QuantizeOptions {
+    pub input: PathBuf,
+    pub output: PathBuf,
+    pub method: QuantizeMethod,
+    /// Worker threads (0 = backend default).
+    pub threads: u32,
+    /// Encrypt the output with the supplied key.
+    pub encrypt_output: bool,
+}
+
+/// Result of a successful conversion.
+#[derive(Debug, Clone)]
+pub struct QuantizeReport {
+    pub method: QuantizeMethod,
+    pub input_bytes: u64,
+    pub output_bytes: u64,
+    /// SHA-256 of the final artifact as written (ciphertext if encrypted).
+    pub sha256: String,
+    pub checksum_path: PathBuf,
+    pub encrypted: bool,
+    pub elapsed: Duration,
+}
+
+/// Run the full pipeline. `key` decrypts an encrypted input and, with
+/// `encrypt_output`, encrypts the result.
+pub fn quantize_model(
+    options: &QuantizeOptions,
+    backend: &dyn QuantizeBackend,
+    key: Option<&ModelEncryption>,
+    progress: &mut dyn FnMut(QuantizeStage),
+) -> Result<QuantizeReport, QuantizeError> {
+    let started = Instant::now();
+    if options.output.exists() {
+        return Err(QuantizeError::OutputExists(options.output.clone()));
+    }
+    if options.encrypt_output && key.is_none() {
+        return Err(QuantizeError::KeyRequired);
+    }
+    let input_bytes = std::fs::metadata(&options.input)?.len();
+    let out_dir = options
+        .output
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+
+    // Plaintext of an encrypted input lives only in a temp file that is
+    // removed on drop
+    let decrypted = if is_encrypted(&options.input)? {
+        let key = key.ok_or(QuantizeError::KeyRequired)?;
+        progress(QuantizeStage::Decrypting);
+        let temp = tempfile::NamedTempFile::new_in(out_dir)?;
+        key.decrypt_file(&options.input, temp.path())
+            .map_err(|e| QuantizeError::Encryption(e.to_string()))?;
+        Some(temp)
+    } else {
+        None
+    };
+    let source = decrypted.as_ref().map_or(options.input.as_path(), |t| t.path());
+
+    progress(QuantizeStage::Validating);
+    validate_gguf(source).map_err(|e| QuantizeError::InvalidInput(e.to_string()))?;
+
+    progress(QuantizeStage::Quantizing);
+    let partial = Partial(with_suffix(&options.output, "partial"));
+    backend.quantize(source, &partial.0, options.method, options.threads)?;
+    drop(decrypted);
+    validate_gguf(&partial.0)
+        .map_err(|e| QuantizeError::Backend(format!("backend wrote an invalid file: {e}")))?;
+
+    let staged = if options.encrypt_output {
+        progress(QuantizeStage::Encrypting);
+        let encrypted = Partial(with_suffix(&options.output, "partial.enc"));
+        key.ok_or(QuantizeError::KeyRequired)?
+            .encrypt_file(&partial.0, &encrypted.0)
+            .map_err(|e| QuantizeError::Encryption(e.to_string()))?;
+        encrypted
+    } else {
+        partial
+    };
+
+    let sha256 = sha256_file(&staged.0, progress)?;
+    let output_bytes = std::fs::metadata(&staged.0)?.len();
+    std::fs::rename(staged.take(), &options.output)?;
+
+    let checksum_path = with_suffix(&options.output, "sha256");
+    let file_name = options.output.file_name().unwrap_or_default().to_string_lossy();
+    std::fs::write(&checksum_path, format!("{sha256}  {file_name}\n"))?;
+
+    Ok(QuantizeReport {
+        method: options.method,
+        input_bytes,
+        output_bytes,
+        sha256,
+        checksum_path,
+        encrypted: options.encrypt_output,
+        elapsed: started.elapsed(),
+    })
+}
+
+/// Whether `path` carries the runtime's encrypted-model header.
+pub fn is_encrypted(path: &Path) -> std::io::Result<bool> {
+    let mut magic = [0u8; 5];
+    match File::open(path)?.read_exact(&mut magic) {
+        Ok(()) => Ok(&magic == ENCRYPTED_MAGIC),
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+fn sha256_file(path: &Path, progress: &mut dyn FnMut(QuantizeStage)) -> std::io::Result<String> {
+    let total = std::fs::metadata(path)?.len();
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; HASH_CHUNK];
+    let mut done = 0u64;
+    loop {
+        let n = reader.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+        done += n as u64;
+        progress(QuantizeStage::Hashing { done, total });
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// Intermediate file removed unless handed off with `take`.
+struct Partial(PathBuf);
+
+impl Partial {
+    fn take(mut self) -> PathBuf {
+        std::mem::take(&mut self.0)
+    }
+}
+
+impl Drop for Partial {
+    fn drop(&mut self) {
+        if !self.0.as_os_str().is_empty() {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_method_names_round_trip() {
+        for method in QuantizeMethod::ALL {
+            assert_eq!(method.as_str().parse::<QuantizeMethod>().unwrap(), method);
+        }
+        assert_eq!("Q4_K_M".parse::<QuantizeMethod>().unwrap(), QuantizeMethod::Q4KM);
+        assert!("q3_xs".parse::<QuantizeMethod>().unwrap_err().contains("q4_k_m"));
+    }
+
+    #[test]
+    fn test_is_encrypted() {
+        let dir = tempfile::tempdir().unwrap();
+        let plain = dir.path().join("plain");
+        std::fs::write(&plain, b"GGUF").unwrap();
+        assert!(!is_encrypted(&plain).unwrap());
+        let enc = dir.path().join("enc");
+        std::fs::write(&enc, b"GGGCM\x02\x00").unwrap();
+        assert!(is_encrypted(&enc).unwrap());
+    }
+
+    #[test]
+    fn test_partial_removed_unless_taken() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.partial");
+        std::fs::write(&path, b"x").unwrap();
+        drop(Partial(path.clone()));
+        assert!(!path.exists());
+
+        std::fs::write(&path, b"x").unwrap();
+        let kept = Partial(path.clone()).take();
+        assert!(kept.exists());
+    }
+}
@@ -0,0 +1,186 @@
+//! Quarantine for model files that repeatedly fail to load.
+//!
+//! A poisoned artifact that crashes the backend would otherwise crash-loop
+//! the runtime on every restart. Each load attempt first bumps a counter in
+//! a `<file>.attempts` marker next to the model and clears it on success,
+//! so attempts that die with the process still count. Once the counter
+//! reaches the limit the file is renamed to `<file>.quarantined` and an
+//! audit event is logged; an operator must inspect and rename it back.
+
+use std::any::Any;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+
+use crate::telemetry::{log_security_event, SecurityEvent};
+
+/// Suffix of the per-file attempt counter.
+pub const ATTEMPTS_SUFFIX: &str = "attempts";
+/// Suffix given to quarantined files.
+pub const QUARANTINE_SUFFIX: &str = "quarantined";
+
+/// Failed or interrupted attempts before a file is quarantined.
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+
+/// Outcome of a guarded load that did not succeed.
+#[derive(Debug)]
+pub enum GuardedLoadError<E> {
+    /// The file was (or just became) quarantined at this path.
+    Quarantined(PathBuf),
+    /// The loader returned an error.
+    Failed(E),
+    /// The loader panicked; the message is kept for diagnostics.
+    Panicked(String),
+    /// The attempt marker could not be written.
+    Io(std::io::Error),
+}
+
+/// Tracks load attempts and quarantines repeat offenders.
+#[derive(Debug, Clone)]
+pub struct Quarantine {
+    max_attempts: u32,
+}
+
+impl Default for Quarantine {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_ATTEMPTS)
+    }
+}
+
+impl Quarantine {
+    pub fn new(max_attempts: u32) -> Self {
+        Self { max_attempts: max_attempts.max(1) }
+    }
+
+    /// Run `load` for `path`, counting the attempt and quarantining the
+    /// file if it has now failed `max_attempts` times in a row.
+    pub fn guard<T, E: std::fmt::Display>(
+        &self,
+        path: &Path,
+        load: impl FnOnce() -> Result<T, E>,
+    ) -> Result<T, GuardedLoadError<E>> {
+        let marker = with_suffix(path, ATTEMPTS_SUFFIX);
+        let previous = read_attempts(&marker);
+        if previous >= self.max_attempts {
+            return Err(self.quarantine(path, &marker, previous, "earlier attempts did not finish"));
+        }
+        std::fs::write(&marker, (previous + 1).to_string()).map_err(GuardedLoadError::Io)?;
+
+        let error = match catch_unwind(AssertUnwindSafe(load)) {
+            Ok(Ok(value)) => {
+                let _ = std::fs::remove_file(&marker);
+                return Ok(value);
+            }
+            Ok(Err(e)) => {
+                let reason = e.to_string();
+                (GuardedLoadError::Failed(e), reason)
+            }
+            Err(payload) => {
+                let message = panic_message(payload);
+                (GuardedLoadError::Panicked(message.clone()), message)
+            }
+        };
+        if previous + 1 >= self.max_attempts {
+            return Err(self.quarantine(path, &marker, previous + 1, &error.1));
+        }
+        Err(error.0)
+    }
+
+    /// Clear the attempt counter, e.g. after an operator replaced the file.
+    pub fn reset(&self, path: &Path) {
+        let _ = std::fs::remove_file(with_suffix(path, ATTEMPTS_SUFFIX));
+    }
+
+    fn quarantine<E>(&self, path: &Path, marker: &Path, attempts: u32, reason: &str) -> GuardedLoadError<E> {
+        let target = with_suffix(path, QUARANTINE_SUFFIX);
+        if let Err(e) = std::fs::rename(path, &target) {
+            return GuardedLoadError::Io(e);
+        }
+        let _ = std::fs::remove_file(marker);
+        let attempts = attempts.to_string();
+        log_security_event(
+            SecurityEvent::ModelQuarantined,
+            "Model file quarantined after repeated load failures",
+            &[
+                ("path", &path.display().to_string()),
+                ("quarantined_as", &target.display().to_string()),
+                ("attempts", &attempts),
+                ("reason", reason),
+            ],
+        );
+        GuardedLoadError::Quarantined(target)
+    }
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+fn read_attempts(marker: &Path) -> u32 {
+    std::fs::read_to_string(marker)
+        .ok()
+        .and_then(|s| s.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "panic".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model_file(dir: &Path) -> PathBuf {
+        let path = dir.join("model.gguf");
+        std::fs::write(&path, b"not really a model").unwrap();
+        path
+    }
+
+    #[test]
+    fn test_success_clears_counter() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = model_file(dir.path());
+        let quarantine = Quarantine::new(2);
+
+        let _ = quarantine.guard(&path, || Err::<(), _>("bad"));
+        assert_eq!(read_attempts(&with_suffix(&path, ATTEMPTS_SUFFIX)), 1);
+        assert_eq!(quarantine.guard(&path, || Ok::<_, String>(7)).unwrap(), 7);
+        assert!(!with_suffix(&path, ATTEMPTS_SUFFIX).exists());
+    }
+
+    #[test]
+    fn test_repeated_failures_quarantine() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = model_file(dir.path());
+        let quarantine = Quarantine::new(2);
+
+        assert!(matches!(quarantine.guard(&path, || Err::<(), _>("bad")), Err(GuardedLoadError::Failed(_))));
+        let result = quarantine.guard(&path, || -> Result<(), String> { panic!("backend crashed") });
+        assert!(matches!(result, Err(GuardedLoadError::Quarantined(_))));
+        assert!(!path.exists());
+        assert!(with_suffix(&path, QUARANTINE_SUFFIX).exists());
+    }
+
+    #[test]
+    fn test_interrupted_attempts_count() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = model_file(dir.path());
+        // Simulate three loads that killed the process mid-way
+        std::fs::write(with_suffix(&path, ATTEMPTS_SUFFIX), "3").unwrap();
+
+        let mut ran = false;
+        let result = Quarantine::default().guard(&path, || {
+            ran = true;
+            Ok::<_, String>(())
+        });
+        assert!(matches!(result, Err(GuardedLoadError::Quarantined(_))));
+        assert!(!ran);
+    }
+}
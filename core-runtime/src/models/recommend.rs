@@ -0,0 +1,286 @@
+//! Hardware-informed quantization recommendation for `models recommend`.
+//!
+//! Single-stream decode is mostly memory-bandwidth bound: each generated
+//! token streams every weight once. Calibration therefore measures
+//! sustained read bandwidth per backend on this host, and a candidate's
+//! per-token latency is *estimated* as its weight size over that bandwidth.
+//! No candidate is actually run, so the estimate is a lower bound that
+//! ignores compute cost (dequantization, attention) and differences
+//! between backend kernels; the report says so in `estimateBasis`. The
+//! report serializes in camelCase so the operator can copy it straight
+//! into a GgModel status.
+
+use std::hint::black_box;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use super::gguf_validate::validate_gguf;
+use super::quantize::QuantizeMethod;
+
+/// Weights plus KV cache, scratch buffers and allocator slack.
+pub const MEMORY_OVERHEAD: f64 = 1.15;
+/// `general.file_type` of an all-F32 model.
+const FTYPE_ALL_F32: u32 = 0;
+/// How `estimated_ms_per_token` is derived, reported with every result.
+pub const ESTIMATE_BASIS: &str =
+    "weight bytes / measured memory bandwidth; excludes compute cost and backend kernel differences";
+
+/// Measured decode throughput of one backend.
+pub trait Calibration {
+    fn backend(&self) -> &str;
+    /// Sustained weight-read bandwidth in bytes per second.
+    fn bandwidth(&self) -> f64;
+    /// Memory usable for weights on this backend, if bounded.
+    fn memory_bytes(&self) -> Option<u64> {
+        None
+    }
+}
+
+/// Host memory bandwidth measured with all cores streaming reads.
+#[derive(Debug, Clone)]
+pub struct CpuCalibration {
+    bandwidth: f64,
+}
+
+impl CpuCalibration {
+    /// Stream `buffer_bytes` repeatedly for about `budget` and record the rate.
+    pub fn run(buffer_bytes: usize, budget: Duration) -> Self {
+        let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+        let words = (buffer_bytes / 8).max(threads);
+        let buffer: Vec<u64> = (0..words as u64).collect();
+        let chunk = words.div_ceil(threads);
+
+        let started = Instant::now();
+        let mut bytes_read = 0u64;
+        while started.elapsed() < budget || bytes_read == 0 {
+            std::thread::scope(|scope| {
+                for part in buffer.chunks(chunk) {
+                    scope.spawn(move || black_box(part.iter().fold(0u64, |acc, w| acc.wrapping_add(*w))));
+                }
+            });
+            bytes_read += (words * 8) as u64;
+        }
+        let secs = started.elapsed().as_secs_f64().max(f64::EPSILON);
+        Self { bandwidth: bytes_read as f64 / secs }
+    }
+
+    /// A fixed bandwidth, for callers that already measured it.
+    pub fn with_bandwidth(bandwidth: f64) -> Self {
+        Self { bandwidth }
+    }
+}
+
+impl Calibration for CpuCalibration {
+    fn backend(&self) -> &str {
+        "cpu"
+    }
+
+    fn bandwidth(&self) -> f64 {
+        self.bandwidth
+    }
+}
+
+/// What the model must satisfy.
+#[derive(Debug, Clone, Copy)]
+pub struct RecommendTarget {
+    /// Per-token decode latency.
+    pub latency: Duration,
+    pub memory_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CalibrationResult {
+    pub backend: String,
+    pub bandwidth_gbps: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CandidateResult {
+    pub method: String,
+    pub backend: String,
+    pub estimated_bytes: u64,
+    /// Bandwidth-bound lower bound, not a measurement; see `ESTIMATE_BASIS`.
+    pub estimated_ms_per_token: f64,
+    pub fits_memory: bool,
+    pub meets_latency: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Recommendation {
+    pub method: String,
+    pub backend: String,
+    pub estimated_bytes: u64,
+    /// Bandwidth-bound lower bound, not a measurement; see `ESTIMATE_BASIS`.
+    pub estimated_ms_per_token: f64,
+}
+
+/// Machine-readable outcome of `models recommend`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecommendReport {
+    pub model: String,
+    pub source_bytes: u64,
+    pub source_method: Option<String>,
+    pub target_latency_ms: f64,
+    pub memory_budget_bytes: u64,
+    /// How latencies were estimated ([`ESTIMATE_BASIS`]).
+    pub estimate_basis: String,
+    pub calibrations: Vec<CalibrationResult>,
+    pub candidates: Vec<CandidateResult>,
+    pub recommendation: Option<Recommendation>,
+    pub reason: String,
+}
+
+/// Evaluate every quantization at or below the source precision on every
+/// calibrated backend and pick the highest-quality one that meets `target`.
+pub fn recommend(
+    model_path: &Path,
+    target: RecommendTarget,
+    calibrations: &[&dyn Calibration],
+) -> Result<RecommendReport, String> {
+    let summary = validate_gguf(model_path).map_err(|e| format!("invalid GGUF: {e}"))?;
+    let source_bytes = std::fs::metadata(model_path).map_err(|e| e.to_string())?.len();
+    let source_method = summary.file_type.and_then(QuantizeMethod::from_ftype);
+    let source_bits = match (summary.file_type, source_method) {
+        (Some(FTYPE_ALL_F32), _) => 32.0,
+        (_, Some(method)) => method.bits_per_weight(),
+        // Unknown or mixed types: conversion checkpoints are usually F16
+        _ => QuantizeMethod::F16.bits_per_weight(),
+    };
+    let target_ms = target.latency.as_secs_f64() * 1000.0;
+
+    let mut candidates = Vec::new();
+    for method in QuantizeMethod::ALL.into_iter().filter(|m| m.bits_per_weight() <= source_bits) {
+        let estimated_bytes = (source_bytes as f64 * method.bits_per_weight() / source_bits) as u64;
+        for calibration in calibrations {
+            let budget = calibration
+                .memory_bytes()
+                .map_or(target.memory_bytes, |m| m.min(target.memory_bytes));
+            let estimated_ms_per_token = estimated_bytes as f64 / calibration.bandwidth().max(1.0) * 1000.0;
+            candidates.push(CandidateResult {
+                method: method.to_string(),
+                backend: calibration.backend().to_string(),
+                estimated_bytes,
+                estimated_ms_per_token,
+                fits_memory: estimated_bytes as f64 * MEMORY_OVERHEAD <= budget as f64,
+                meets_latency: estimated_ms_per_token <= target_ms,
+            });
+        }
+    }
+
+    // Candidates are in quality order, so the first passing one is best;
+    // among equal quality prefer the faster backend
+    let passing = candidates.iter().filter(|c| c.fits_memory && c.meets_latency);
+    let best_quality = passing.clone().next().map(|c| c.method.clone());
+    let chosen = passing
+        .filter(|c| Some(&c.method) == best_quality.as_ref())
+        .min_by(|a, b| a.estimated_ms_per_token.total_cmp(&b.estimated_ms_per_token));
+    let (chosen, reason) = match chosen {
+        Some(c) => (Some(c), "highest quality estimated to meet latency and memory targets".to_string()),
+        None => match candidates
+            .iter()
+            .filter(|c| c.fits_memory)
+            .min_by(|a, b| a.estimated_ms_per_token.total_cmp(&b.estimated_ms_per_token))
+        {
+            Some(c) => {
+                let reason = format!("no configuration is estimated to meet {target_ms:.0}ms/token; fastest that fits");
+                (Some(c), reason)
+            }
+            None => (None, "no quantization fits the memory budget".to_string()),
+        },
+    };
+
+    Ok(RecommendReport {
+        model: model_path.display().to_string(),
+        source_bytes,
+        source_method: source_method.map(|m| m.to_string()),
+        target_latency_ms: target_ms,
+        memory_budget_bytes: target.memory_bytes,
+        estimate_basis: ESTIMATE_BASIS.to_string(),
+        calibrations: calibrations
+            .iter()
+            .map(|c| CalibrationResult {
+                backend: c.backend().to_string(),
+                bandwidth_gbps: c.bandwidth() / 1e9,
+            })
+            .collect(),
+        recommendation: chosen.map(|c| Recommendation {
+            method: c.method.clone(),
+            backend: c.backend.clone(),
+            estimated_bytes: c.estimated_bytes,
+            estimated_ms_per_token: c.estimated_ms_per_token,
+        }),
+        candidates,
+        reason,
+    })
+}
+
+/// Parse a Kubernetes-style quantity: `8Gi`, `512Mi`, `8G`, `1024`.
+pub fn parse_memory(s: &str) -> Option<u64> {
+    let s = s.trim();
+    let split = s.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let number: f64 = number.parse().ok()?;
+    let scale: f64 = match unit {
+        "" => 1.0,
+        "Ki" => 1024.0,
+        "Mi" => 1024.0 * 1024.0,
+        "Gi" => 1024.0 * 1024.0 * 1024.0,
+        "Ti" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+        "k" | "K" => 1e3,
+        "M" => 1e6,
+        "G" => 1e9,
+        "T" => 1e12,
+        _ => return None,
+    };
+    Some((number * scale) as u64)
+}
+
+/// Parse a latency such as `50ms`, `0.2s` or `800us`.
+pub fn parse_latency(s: &str) -> Option<Duration> {
+    let s = s.trim();
+    let (number, scale) = if let Some(n) = s.strip_suffix("ms") {
+        (n, 1e-3)
+    } else if let Some(n) = s.strip_suffix("us") {
+        (n, 1e-6)
+    } else if let Some(n) = s.strip_suffix('s') {
+        (n, 1.0)
+    } else {
+        return None;
+    };
+    let secs = number.parse::<f64>().ok()? * scale;
+    (secs.is_finite() && secs > 0.0).then(|| Duration::from_secs_f64(secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_memory() {
+        assert_eq!(parse_memory("8Gi"), Some(8 << 30));
+        assert_eq!(parse_memory("512Mi"), Some(512 << 20));
+        assert_eq!(parse_memory("2G"), Some(2_000_000_000));
+        assert_eq!(parse_memory("4096"), Some(4096));
+        assert_eq!(parse_memory("8 GB"), None);
+    }
+
+    #[test]
+    fn test_parse_latency() {
+        assert_eq!(parse_latency("50ms"), Some(Duration::from_millis(50)));
+        assert_eq!(parse_latency("0.2s"), Some(Duration::from_millis(200)));
+        assert_eq!(parse_latency("50"), None);
+        assert_eq!(parse_latency("0ms"), None);
+    }
+
+    #[test]
+    fn test_cpu_calibration_measures_positive_bandwidth() {
+        let calibration = CpuCalibration::run(1 << 20, Duration::from_millis(5));
+        assert!(calibration.bandwidth() > 0.0);
+    }
+}
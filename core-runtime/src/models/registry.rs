@@ -1,12 +1,23 @@
 //! Model registry for tracking loaded models.
+//!
+//! Request statistics are keyed by model name and survive unloads; with
+//! persistence attached they also survive restarts (`persist_stats`).
+//! Fallback chains are read from the same catalog file.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::Arc;
-use std::time::SystemTime;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::sync::RwLock;
 
+use super::attestation::Attestation;
+use super::fallback::FallbackChains;
 use super::loader::ModelMetadata;
+use super::persistence::{
+    percentile, push_recent, PersistedStats, PersistenceError, RegistryPersistence,
+};
+use crate::telemetry::{emit_event, RuntimeEvent, RuntimeEventKind, StreamTimings};
 
 /// Unique handle to a loaded model.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -54,7 +65,21 @@ pub struct LoadedModelInfo {
     pub state: LoadedModelState,
     pub request_count: u64,
     pub total_latency_ms: f64,
+    /// Streamed requests that produced at least one token.
+    pub stream_count: u64,
+    pub total_ttft_ms: f64,
+    pub inter_token_count: u64,
+    pub total_inter_token_ms: f64,
+    /// p95 latency over the most recent requests.
+    pub recent_p95_latency_ms: Option<f64>,
     pub loaded_at: SystemTime,
+    pub warmed: bool,
+    /// Verified provenance attestation, if the model had one.
+    pub attestation: Option<Attestation>,
+    /// File the model was loaded from.
+    pub path: Option<PathBuf>,
+    /// Recorded SHA-256 of the file, hex.
+    pub sha256: Option<String>,
 }
 
 struct LoadedModel {
@@ -64,13 +89,45 @@ struct LoadedModel {
     state: LoadedModelState,
     request_count: AtomicU64,
     total_latency_ms: std::sync::atomic::AtomicU64,
+    stream_count: AtomicU64,
+    total_ttft_ms: AtomicU64,
+    inter_token_count: AtomicU64,
+    total_inter_token_ms: AtomicU64,
+    recent_latencies: Mutex<VecDeque<f64>>,
     loaded_at: SystemTime,
+    warmed: bool,
+    attestation: Option<Attestation>,
+    path: Option<PathBuf>,
+    sha256: Option<String>,
+}
+
+impl LoadedModel {
+    fn stats(&self) -> PersistedStats {
+        PersistedStats {
+            request_count: self.request_count.load(Ordering::Relaxed),
+            total_latency_ms: f64::from_bits(self.total_latency_ms.load(Ordering::Relaxed)),
+            stream_count: self.stream_count.load(Ordering::Relaxed),
+            total_ttft_ms: f64::from_bits(self.total_ttft_ms.load(Ordering::Relaxed)),
+            inter_token_count: self.inter_token_count.load(Ordering::Relaxed),
+            total_inter_token_ms: f64::from_bits(self.total_inter_token_ms.load(Ordering::Relaxed)),
+            recent_latencies_ms: lock(&self.recent_latencies).clone(),
+        }
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
 }
 
 /// Thread-safe registry of loaded models.
 pub struct ModelRegistry {
     models: Arc<RwLock<HashMap<ModelHandle, LoadedModel>>>,
     next_id: AtomicU64,
+    /// Stats of models not currently registered, restored on registration.
+    retained: Mutex<HashMap<String, PersistedStats>>,
+    /// Fallback chains from the catalog, read at startup.
+    fallbacks: FallbackChains,
+    persistence: Option<RegistryPersistence>,
 }
 
 impl ModelRegistry {
@@ -78,6 +135,28 @@ impl ModelRegistry {
         Self {
             models: Arc::new(RwLock::new(HashMap::new())),
             next_id: AtomicU64::new(1),
+            retained: Mutex::new(HashMap::new()),
+            fallbacks: FallbackChains::new(),
+            persistence: None,
+        }
+    }
+
+    /// Registry whose request statistics are loaded from, and saved to,
+    /// `persistence`. An unreadable state file starts the stats fresh.
+    pub fn with_persistence(persistence: RegistryPersistence) -> Self {
+        let (retained, fallbacks) = match persistence.load() {
+            Ok(state) => (state.stats, state.fallbacks.sanitized()),
+            Err(PersistenceError::NotFound) => Default::default(),
+            Err(e) => {
+                tracing::warn!(error = %e, "Discarding unreadable model statistics");
+                Default::default()
+            }
+        };
+        Self {
+            retained: Mutex::new(retained),
+            fallbacks,
+            persistence: Some(persistence),
+            ..Self::new()
         }
     }
 
@@ -96,16 +175,33 @@ impl ModelRegistry {
         let id = self.next_id.fetch_add(1, Ordering::SeqCst);
         let handle = ModelHandle(id);
 
+        let name = metadata.name.clone();
+        let restored = lock(&self.retained).remove(&name).unwrap_or_default();
         let model = LoadedModel {
             metadata,
             memory_bytes,
             format,
             state: LoadedModelState::Ready,
-            request_count: AtomicU64::new(0),
-            total_latency_ms: AtomicU64::new(0),
+            request_count: AtomicU64::new(restored.request_count),
+            total_latency_ms: AtomicU64::new(restored.total_latency_ms.to_bits()),
+            stream_count: AtomicU64::new(restored.stream_count),
+            total_ttft_ms: AtomicU64::new(restored.total_ttft_ms.to_bits()),
+            inter_token_count: AtomicU64::new(restored.inter_token_count),
+            total_inter_token_ms: AtomicU64::new(restored.total_inter_token_ms.to_bits()),
+            recent_latencies: Mutex::new(restored.recent_latencies_ms),
             loaded_at: SystemTime::now(),
+            warmed: false,
+            attestation: None,
+            path: None,
+            sha256: None,
         };
         self.models.write().await.insert(handle, model);
+        emit_event(RuntimeEvent::new(
+            RuntimeEventKind::ModelLoaded,
+            tracing::Level::INFO,
+            name,
+            format!("handle {id}"),
+        ));
 
         handle
     }
@@ -122,7 +218,18 @@ impl ModelRegistry {
 
     /// Remove a model from the registry.
     pub async fn unregister(&self, handle: ModelHandle) -> Option<usize> {
-        self.models.write().await.remove(&handle).map(|m| m.memory_bytes)
+        let removed = self.models.write().await.remove(&handle)?;
+        lock(&self.retained)
+            .entry(removed.metadata.name.clone())
+            .or_default()
+            .merge(&removed.stats());
+        emit_event(RuntimeEvent::new(
+            RuntimeEventKind::ModelUnloaded,
+            tracing::Level::INFO,
+            removed.metadata.name,
+            format!("handle {}", handle.0),
+        ));
+        Some(removed.memory_bytes)
     }
 
     /// Total memory used by all registered models.
@@ -149,7 +256,16 @@ impl ModelRegistry {
                 state: model.state,
                 request_count: model.request_count.load(Ordering::Relaxed),
                 total_latency_ms: f64::from_bits(model.total_latency_ms.load(Ordering::Relaxed)),
+                stream_count: model.stream_count.load(Ordering::Relaxed),
+                total_ttft_ms: f64::from_bits(model.total_ttft_ms.load(Ordering::Relaxed)),
+                inter_token_count: model.inter_token_count.load(Ordering::Relaxed),
+                total_inter_token_ms: f64::from_bits(model.total_inter_token_ms.load(Ordering::Relaxed)),
+                recent_p95_latency_ms: percentile(&lock(&model.recent_latencies), 0.95),
                 loaded_at: model.loaded_at,
+                warmed: model.warmed,
+                attestation: model.attestation.clone(),
+                path: model.path.clone(),
+                sha256: model.sha256.clone(),
             })
             .collect()
     }
@@ -158,20 +274,84 @@ impl ModelRegistry {
     pub async fn record_request(&self, handle: ModelHandle, latency_ms: f64) {
         if let Some(model) = self.models.read().await.get(&handle) {
             model.request_count.fetch_add(1, Ordering::Relaxed);
-            // Atomic f64 addition via CAS loop
-            loop {
-                let old_bits = model.total_latency_ms.load(Ordering::Relaxed);
-                let old_value = f64::from_bits(old_bits);
-                let new_value = old_value + latency_ms;
-                let new_bits = new_value.to_bits();
-                if model
-                    .total_latency_ms
-                    .compare_exchange(old_bits, new_bits, Ordering::Relaxed, Ordering::Relaxed)
-                    .is_ok()
-                {
-                    break;
-                }
-            }
+            atomic_add_f64(&model.total_latency_ms, latency_ms);
+            push_recent(&mut lock(&model.recent_latencies), latency_ms);
+        }
+    }
+
+    /// Cumulative statistics per model name, loaded or not.
+    pub async fn stats_snapshot(&self) -> HashMap<String, PersistedStats> {
+        let mut stats = lock(&self.retained).clone();
+        for model in self.models.read().await.values() {
+            stats.entry(model.metadata.name.clone()).or_default().merge(&model.stats());
+        }
+        stats
+    }
+
+    /// Write cumulative statistics to the state file, keeping the rest of
+    /// the persisted registry state. No-op without persistence.
+    pub async fn persist_stats(&self) -> Result<(), PersistenceError> {
+        let Some(persistence) = &self.persistence else {
+            return Ok(());
+        };
+        let mut state = persistence.load_or_default();
+        state.stats = self.stats_snapshot().await;
+        state.saved_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        persistence.save(&state)
+    }
+
+    /// Models to try for `name`: `name` itself, then its fallbacks.
+    pub fn fallback_chain(&self, name: &str) -> Vec<String> {
+        self.fallbacks.chain(name)
+    }
+
+    /// Record first-token and inter-token timings of a finished stream.
+    pub async fn record_stream(&self, handle: ModelHandle, timings: &StreamTimings) {
+        let Some(ttft_ms) = timings.ttft_ms else {
+            return;
+        };
+        if let Some(model) = self.models.read().await.get(&handle) {
+            model.stream_count.fetch_add(1, Ordering::Relaxed);
+            atomic_add_f64(&model.total_ttft_ms, ttft_ms);
+            model.inter_token_count.fetch_add(timings.inter_token_count, Ordering::Relaxed);
+            atomic_add_f64(&model.total_inter_token_ms, timings.inter_token_total_ms);
+        }
+    }
+
+    /// Mark every model registered under `name` as warmed up.
+    /// Returns false if no such model is loaded.
+    pub async fn mark_warmed(&self, name: &str) -> bool {
+        let mut models = self.models.write().await;
+        let mut found = false;
+        for model in models.values_mut().filter(|m| m.metadata.name == name) {
+            model.warmed = true;
+            found = true;
+        }
+        found
+    }
+
+    /// Record the attestation a model was verified against.
+    pub async fn set_attestation(&self, handle: ModelHandle, attestation: Attestation) {
+        if let Some(model) = self.models.write().await.get_mut(&handle) {
+            model.attestation = Some(attestation);
+        }
+    }
+
+    /// Record the file a model was loaded from. An attested model's digest
+    /// is taken from its attestation.
+    pub async fn set_path(&self, handle: ModelHandle, path: PathBuf) {
+        if let Some(model) = self.models.write().await.get_mut(&handle) {
+            model.sha256 = model.sha256.take().or_else(|| model.attestation.as_ref().map(|a| a.subject_sha256.clone()));
+            model.path = Some(path);
+        }
+    }
+
+    /// Record the SHA-256 of a model's file.
+    pub async fn set_sha256(&self, handle: ModelHandle, sha256: String) {
+        if let Some(model) = self.models.write().await.get_mut(&handle) {
+            model.sha256 = Some(sha256);
         }
     }
 
@@ -183,6 +363,20 @@ impl ModelRegistry {
     }
 }
 
+/// Atomic f64 addition via CAS loop.
+fn atomic_add_f64(atomic: &AtomicU64, value: f64) {
+    loop {
+        let old_bits = atomic.load(Ordering::Relaxed);
+        let new_bits = (f64::from_bits(old_bits) + value).to_bits();
+        if atomic
+            .compare_exchange(old_bits, new_bits, Ordering::Relaxed, Ordering::Relaxed)
+            .is_ok()
+        {
+            break;
+        }
+    }
+}
+
 impl Default for ModelRegistry {
     fn default() -> Self {
         Self::new()
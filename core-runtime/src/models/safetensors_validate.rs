@@ -0,0 +1,155 @@
+//! Pre-load structural validation of safetensors files.
+//!
+//! Layout: u64 little-endian header length `N`, `N` bytes of JSON mapping
+//! tensor names to `{dtype, shape, data_offsets}` (plus an optional
+//! `__metadata__` string map), then the tensor data. Every tensor's byte
+//! range must lie inside the data section and match its shape and dtype.
+
+use std::fs::File;
+use std::path::Path;
+
+use memmap2::Mmap;
+use thiserror::Error;
+
+/// Largest JSON header accepted (the reference implementation's limit).
+pub const MAX_HEADER_BYTES: u64 = 100 * 1024 * 1024;
+
+#[derive(Error, Debug)]
+pub enum SafetensorsError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Header length {0} is invalid for a {1}-byte file")]
+    BadHeaderLength(u64, u64),
+
+    #[error("Header is not a JSON object: {0}")]
+    BadHeader(String),
+
+    #[error("Tensor {name}: {reason}")]
+    InvalidTensor { name: String, reason: String },
+}
+
+/// What a valid header declared.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SafetensorsSummary {
+    pub tensor_count: usize,
+    /// Sum of all tensor byte ranges.
+    pub data_bytes: u64,
+}
+
+/// Validate the safetensors file at `path`.
+pub fn validate_safetensors(path: &Path) -> Result<SafetensorsSummary, SafetensorsError> {
+    let file = File::open(path)?;
+    // SAFETY: read-only mapping, dropped before returning
+    let mmap = unsafe { Mmap::map(&file)? };
+    validate_safetensors_bytes(&mmap)
+}
+
+/// Validate an in-memory safetensors image.
+pub fn validate_safetensors_bytes(bytes: &[u8]) -> Result<SafetensorsSummary, SafetensorsError> {
+    let file_len = bytes.len() as u64;
+    let header_len = bytes
+        .get(..8)
+        .map(|b| u64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]))
+        .ok_or(SafetensorsError::BadHeaderLength(0, file_len))?;
+    if !(2..=MAX_HEADER_BYTES).contains(&header_len) || header_len > file_len - 8 {
+        return Err(SafetensorsError::BadHeaderLength(header_len, file_len));
+    }
+    let header = &bytes[8..8 + header_len as usize];
+    let data_len = file_len - 8 - header_len;
+
+    let header: serde_json::Map<String, serde_json::Value> =
+        serde_json::from_slice(header).map_err(|e| SafetensorsError::BadHeader(e.to_string()))?;
+
+    let mut data_bytes = 0u64;
+    let mut tensor_count = 0;
+    for (name, info) in &header {
+        if name == "__metadata__" {
+            continue;
+        }
+        let invalid = |reason: &str| SafetensorsError::InvalidTensor {
+            name: name.clone(),
+            reason: reason.to_string(),
+        };
+        let dtype_size = info
+            .get("dtype")
+            .and_then(|d| d.as_str())
+            .and_then(dtype_size)
+            .ok_or_else(|| invalid("missing or unknown dtype"))?;
+        let shape = info
+            .get("shape")
+            .and_then(|s| s.as_array())
+            .ok_or_else(|| invalid("missing shape"))?;
+        let mut elements: u64 = 1;
+        for dim in shape {
+            let dim = dim.as_u64().ok_or_else(|| invalid("non-integer dimension"))?;
+            elements = elements.checked_mul(dim).ok_or_else(|| invalid("element count overflows"))?;
+        }
+        let offsets = info
+            .get("data_offsets")
+            .and_then(|o| o.as_array())
+            .filter(|o| o.len() == 2)
+            .and_then(|o| Some((o[0].as_u64()?, o[1].as_u64()?)))
+            .ok_or_else(|| invalid("missing data_offsets"))?;
+        let (begin, end) = offsets;
+        if begin > end || end > data_len {
+            return Err(invalid(&format!("range {begin}..{end} outside {data_len}-byte data section")));
+        }
+        let expected = elements.checked_mul(dtype_size).ok_or_else(|| invalid("byte size overflows"))?;
+        if end - begin != expected {
+            return Err(invalid(&format!("range holds {} bytes, shape needs {expected}", end - begin)));
+        }
+        data_bytes += expected;
+        tensor_count += 1;
+    }
+
+    Ok(SafetensorsSummary { tensor_count, data_bytes })
+}
+
+fn dtype_size(dtype: &str) -> Option<u64> {
+    match dtype {
+        "BOOL" | "U8" | "I8" | "F8_E4M3" | "F8_E5M2" => Some(1),
+        "U16" | "I16" | "F16" | "BF16" => Some(2),
+        "U32" | "I32" | "F32" => Some(4),
+        "U64" | "I64" | "F64" => Some(8),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(header: &str, data_len: usize) -> Vec<u8> {
+        let mut bytes = (header.len() as u64).to_le_bytes().to_vec();
+        bytes.extend_from_slice(header.as_bytes());
+        bytes.resize(bytes.len() + data_len, 0);
+        bytes
+    }
+
+    #[test]
+    fn test_valid_file() {
+        let header = r#"{"__metadata__":{"format":"pt"},"w":{"dtype":"F32","shape":[2,3],"data_offsets":[0,24]}}"#;
+        let summary = validate_safetensors_bytes(&build(header, 24)).unwrap();
+        assert_eq!(summary, SafetensorsSummary { tensor_count: 1, data_bytes: 24 });
+    }
+
+    #[test]
+    fn test_rejects_out_of_bounds_and_mismatched_ranges() {
+        let header = r#"{"w":{"dtype":"F32","shape":[2,3],"data_offsets":[0,24]}}"#;
+        assert!(matches!(
+            validate_safetensors_bytes(&build(header, 16)),
+            Err(SafetensorsError::InvalidTensor { .. })
+        ));
+        let header = r#"{"w":{"dtype":"F16","shape":[2,3],"data_offsets":[0,24]}}"#;
+        assert!(validate_safetensors_bytes(&build(header, 24)).is_err());
+    }
+
+    #[test]
+    fn test_rejects_bad_header_length() {
+        let mut bytes = build("{}", 0);
+        bytes[..8].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(matches!(validate_safetensors_bytes(&bytes), Err(SafetensorsError::BadHeaderLength(..))));
+        assert!(validate_safetensors_bytes(&[1, 2, 3]).is_err());
+    }
+}
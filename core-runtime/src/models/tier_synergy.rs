@@ -15,6 +15,7 @@ use tokio::sync::RwLock;
 use super::smart_loader::{LoadHint, ModelTier, SmartLoader, SmartLoaderError};
 use crate::engine::speculative_v2::{SpeculativeConfig, SpeculativeStats};
 use crate::models::registry::ModelHandle;
+use crate::scheduler::OverloadController;
 
 /// Synergy mode for tiered model usage.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -50,6 +51,7 @@ pub struct TierSynergy {
     stats: Arc<RwLock<SpeculativeStats>>,
     /// Model IDs by tier
     tier_models: Arc<RwLock<TierModels>>,
+    overload: Option<Arc<OverloadController>>,
 }
 
 /// Mapping of tiers to model IDs.
@@ -68,9 +70,17 @@ impl TierSynergy {
             spec_config: SpeculativeConfig::default(),
             stats: Arc::new(RwLock::new(SpeculativeStats::default())),
             tier_models: Arc::new(RwLock::new(TierModels::default())),
+            overload: None,
         }
     }
 
+    /// Serve from a single model, without a draft, whenever the overload
+    /// ladder disallows speculative decoding.
+    pub fn with_overload(mut self, overload: Arc<OverloadController>) -> Self {
+        self.overload = Some(overload);
+        self
+    }
+
     /// Set custom speculative config.
     pub fn with_spec_config(mut self, config: SpeculativeConfig) -> Self {
         self.spec_config = config;
@@ -122,7 +132,10 @@ impl TierSynergy {
         // First, inform loader of the hint
         self.loader.hint(hint).await;
 
-        let mode = self.mode().await;
+        let mode = match &self.overload {
+            Some(overload) if !overload.speculative_allowed() => SynergyMode::Single,
+            _ => self.mode().await,
+        };
         let tiers = self.tier_models.read().await;
 
         match (mode, hint) {
@@ -284,6 +297,7 @@ pub struct SynergyStatus {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::features::{Feature, FeatureFlags};
     use crate::models::smart_loader::SmartLoaderConfig;
     use std::io::Write;
     use tempfile::NamedTempFile;
@@ -364,6 +378,64 @@ mod tests {
         assert_eq!(result.mode, SynergyMode::SpeculativeLightQuality);
     }
 
+    #[tokio::test]
+    async fn test_synergy_no_draft_under_overload() {
+        let loader = Arc::new(SmartLoader::new(SmartLoaderConfig::default()));
+        let overload = Arc::new(OverloadController::default());
+        let synergy = TierSynergy::new(loader.clone()).with_overload(Arc::clone(&overload));
+
+        let light = create_test_model(100);
+        let quality = create_test_model(200);
+        loader
+            .register("light".into(), light.path().to_path_buf(), ModelTier::Light)
+            .await
+            .unwrap();
+        loader
+            .register("quality".into(), quality.path().to_path_buf(), ModelTier::Quality)
+            .await
+            .unwrap();
+        synergy.register_tier("light", ModelTier::Light).await;
+        synergy.register_tier("quality", ModelTier::Quality).await;
+
+        overload.evaluate(64);
+        let result = synergy.request(LoadHint::ComplexTask).await.unwrap();
+        assert_eq!(result.mode, SynergyMode::Single);
+        assert!(result.draft_handle.is_none());
+
+        overload.evaluate(0);
+        let result = synergy.request(LoadHint::ComplexTask).await.unwrap();
+        assert_eq!(result.mode, SynergyMode::SpeculativeLightQuality);
+    }
+
+    #[tokio::test]
+    async fn test_synergy_no_draft_when_flag_off() {
+        let loader = Arc::new(SmartLoader::new(SmartLoaderConfig::default()));
+        let features = Arc::new(FeatureFlags::new());
+        let overload = Arc::new(OverloadController::default().with_features(Arc::clone(&features)));
+        let synergy = TierSynergy::new(loader.clone()).with_overload(overload);
+
+        let light = create_test_model(100);
+        let quality = create_test_model(200);
+        loader
+            .register("light".into(), light.path().to_path_buf(), ModelTier::Light)
+            .await
+            .unwrap();
+        loader
+            .register("quality".into(), quality.path().to_path_buf(), ModelTier::Quality)
+            .await
+            .unwrap();
+        synergy.register_tier("light", ModelTier::Light).await;
+        synergy.register_tier("quality", ModelTier::Quality).await;
+
+        features.configure(Feature::SpeculativeDecoding, false, true);
+        let result = synergy.request(LoadHint::ComplexTask).await.unwrap();
+        assert_eq!(result.mode, SynergyMode::Single);
+
+        features.configure(Feature::SpeculativeDecoding, true, true);
+        let result = synergy.request(LoadHint::ComplexTask).await.unwrap();
+        assert_eq!(result.mode, SynergyMode::SpeculativeLightQuality);
+    }
+
     #[tokio::test]
     async fn test_synergy_fallback_single_tier() {
         let loader = Arc::new(SmartLoader::new(SmartLoaderConfig::default()));
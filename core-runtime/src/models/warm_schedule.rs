@@ -0,0 +1,408 @@
+//! Time-of-day schedules for the warm model pool.
+//!
+//! Rules map hours of the day (UTC, cron-style hour fields) to the pool
+//! tiers kept warm, e.g. the Quality tier during business hours and only
+//! Testing models at night. [`WarmPoolController`] reconciles the pool
+//! against the active rule on every tick and writes an audit event for
+//! each transition.
+//!
+//! Only registered models can be warmed; the controller admits and releases
+//! pool membership but never loads or unloads weights itself.
+
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{DateTime, Datelike, Timelike, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::pool::{ModelPool, ModelTier, PoolConfig};
+use super::registry::{ModelHandle, ModelRegistry};
+use crate::security::audit::{audit_logger, AuditCategory, AuditEvent, AuditSeverity};
+
+/// Rule name reported when no rule matches the current hour.
+pub const DEFAULT_RULE: &str = "default";
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum WarmScheduleError {
+    #[error("Invalid hour field '{0}': expected '*', 'H', 'A-B' or a comma list (0-23)")]
+    InvalidHours(String),
+
+    #[error("Warm schedule max_models must be at least 1")]
+    NoCapacity,
+}
+
+/// Hours of the day a rule applies to, parsed from a cron hour field.
+///
+/// Accepts `*`, single hours (`8`), inclusive ranges (`9-17`) and comma
+/// lists (`8,12-13`). Ranges may wrap midnight: `22-5` covers 22:00 to
+/// 05:59.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct HourSet {
+    mask: u32,
+    /// Original text, kept for round-tripping config files.
+    spec: String,
+}
+
+impl HourSet {
+    pub fn parse(spec: &str) -> Result<Self, WarmScheduleError> {
+        let invalid = || WarmScheduleError::InvalidHours(spec.to_string());
+        let hour = |s: &str| s.trim().parse::<u32>().ok().filter(|h| *h < 24).ok_or_else(invalid);
+        let mut mask = 0u32;
+        for item in spec.split(',') {
+            let item = item.trim();
+            if item == "*" {
+                mask = (1 << 24) - 1;
+            } else if let Some((a, b)) = item.split_once('-') {
+                let (a, b) = (hour(a)?, hour(b)?);
+                let mut h = a;
+                loop {
+                    mask |= 1 << h;
+                    if h == b {
+                        break;
+                    }
+                    h = (h + 1) % 24;
+                }
+            } else {
+                mask |= 1 << hour(item)?;
+            }
+        }
+        Ok(Self { mask, spec: spec.trim().to_string() })
+    }
+
+    pub fn contains(&self, hour: u32) -> bool {
+        hour < 24 && self.mask & (1 << hour) != 0
+    }
+}
+
+impl TryFrom<String> for HourSet {
+    type Error = WarmScheduleError;
+
+    fn try_from(spec: String) -> Result<Self, Self::Error> {
+        Self::parse(&spec)
+    }
+}
+
+impl From<HourSet> for String {
+    fn from(hours: HourSet) -> Self {
+        hours.spec
+    }
+}
+
+/// A model the schedule may keep warm, with its pool tier.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WarmModel {
+    pub model_id: String,
+    pub tier: ModelTier,
+}
+
+/// Tiers kept warm during the matching hours.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WarmRule {
+    pub name: String,
+    pub hours: HourSet,
+    /// Days the rule applies on. Empty = every day.
+    #[serde(default)]
+    pub weekdays: Vec<Weekday>,
+    pub tiers: Vec<ModelTier>,
+}
+
+impl WarmRule {
+    pub fn matches(&self, now: DateTime<Utc>) -> bool {
+        self.hours.contains(now.hour()) && (self.weekdays.is_empty() || self.weekdays.contains(&now.weekday()))
+    }
+}
+
+/// Warm-pool schedule, usually loaded from the JSON file in `CORE_WARM_SCHEDULE`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WarmScheduleConfig {
+    /// Candidate models and their tiers.
+    pub models: Vec<WarmModel>,
+    /// Checked in order; the first matching rule wins.
+    pub rules: Vec<WarmRule>,
+    /// Tiers kept warm when no rule matches.
+    #[serde(default)]
+    pub default_tiers: Vec<ModelTier>,
+    /// Pool capacity; higher tiers are admitted first.
+    #[serde(default = "default_max_models")]
+    pub max_models: usize,
+}
+
+fn default_max_models() -> usize {
+    PoolConfig::default().max_models
+}
+
+/// Models that should be warm at a given time, highest tier first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WarmTarget {
+    pub rule: String,
+    pub models: Vec<WarmModel>,
+}
+
+impl WarmScheduleConfig {
+    pub fn validate(&self) -> Result<(), WarmScheduleError> {
+        if self.max_models == 0 {
+            return Err(WarmScheduleError::NoCapacity);
+        }
+        Ok(())
+    }
+
+    pub fn active_rule(&self, now: DateTime<Utc>) -> Option<&WarmRule> {
+        self.rules.iter().find(|rule| rule.matches(now))
+    }
+
+    pub fn target_at(&self, now: DateTime<Utc>) -> WarmTarget {
+        let (rule, tiers) = match self.active_rule(now) {
+            Some(rule) => (rule.name.clone(), &rule.tiers),
+            None => (DEFAULT_RULE.to_string(), &self.default_tiers),
+        };
+        let mut models: Vec<WarmModel> =
+            self.models.iter().filter(|m| tiers.contains(&m.tier)).cloned().collect();
+        // Stable sort keeps catalog order within a tier
+        models.sort_by_key(|m| std::cmp::Reverse(m.tier));
+        models.truncate(self.max_models);
+        WarmTarget { rule, models }
+    }
+}
+
+/// One reconciliation that changed the active rule or the pool.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WarmTransition {
+    pub from_rule: Option<String>,
+    pub to_rule: String,
+    /// Models admitted to the pool.
+    pub warmed: Vec<String>,
+    /// Models released from the pool.
+    pub released: Vec<String>,
+    /// Scheduled models that are not registered yet.
+    pub missing: Vec<String>,
+}
+
+/// Background controller applying a [`WarmScheduleConfig`] to a pool.
+pub struct WarmPoolController {
+    config: WarmScheduleConfig,
+    pool: ModelPool,
+    registry: Arc<ModelRegistry>,
+    current_rule: Mutex<Option<String>>,
+}
+
+impl WarmPoolController {
+    pub fn new(config: WarmScheduleConfig, registry: Arc<ModelRegistry>) -> Self {
+        let pool = ModelPool::new(
+            PoolConfig { max_models: config.max_models, ..Default::default() },
+            Arc::clone(&registry),
+        );
+        Self { config, pool, registry, current_rule: Mutex::new(None) }
+    }
+
+    pub fn pool(&self) -> &ModelPool {
+        &self.pool
+    }
+
+    /// Name of the rule applied by the last tick, if any.
+    pub fn current_rule(&self) -> Option<String> {
+        self.current_rule.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Reconcile the pool with the schedule at `now`. Returns the transition
+    /// if the rule changed or any model was admitted or released.
+    pub async fn tick(&self, now: DateTime<Utc>) -> Option<WarmTransition> {
+        let target = self.config.target_at(now);
+        let wanted: HashSet<&str> = target.models.iter().map(|m| m.model_id.as_str()).collect();
+
+        // Release first so admissions never have to evict registered models
+        let mut released = Vec::new();
+        for member in self.pool.members().await {
+            if !wanted.contains(member.model_id.as_str()) && self.pool.remove(&member.model_id).await.is_some() {
+                released.push(member.model_id);
+            }
+        }
+
+        let registered = self.registry.list_models().await;
+        let (mut warmed, mut missing) = (Vec::new(), Vec::new());
+        for model in &target.models {
+            if self.pool.contains(&model.model_id).await {
+                continue;
+            }
+            let Some(info) = registered.iter().find(|m| m.name == model.model_id) else {
+                missing.push(model.model_id.clone());
+                continue;
+            };
+            let handle = ModelHandle::new(info.handle_id);
+            match self.pool.preload(model.model_id.clone(), handle, model.tier, info.memory_bytes as usize).await {
+                Ok(()) => {
+                    if info.warmed {
+                        self.pool.mark_warmed(&model.model_id).await;
+                    }
+                    warmed.push(model.model_id.clone());
+                }
+                Err(e) => tracing::warn!(model = %model.model_id, error = %e, "Cannot admit model to warm pool"),
+            }
+        }
+
+        let from_rule = {
+            let mut current = self.current_rule.lock().unwrap_or_else(|e| e.into_inner());
+            current.replace(target.rule.clone())
+        };
+        if from_rule.as_deref() == Some(target.rule.as_str()) && warmed.is_empty() && released.is_empty() {
+            return None;
+        }
+
+        let transition = WarmTransition { from_rule, to_rule: target.rule, warmed, released, missing };
+        tracing::info!(
+            from = transition.from_rule.as_deref().unwrap_or("none"),
+            to = %transition.to_rule,
+            warmed = transition.warmed.len(),
+            released = transition.released.len(),
+            missing = transition.missing.len(),
+            "Warm pool transition"
+        );
+        audit_transition(&transition).await;
+        Some(transition)
+    }
+
+    /// Tick every `interval` until the task is aborted.
+    pub async fn run(self: Arc<Self>, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            self.tick(Utc::now()).await;
+        }
+    }
+}
+
+async fn audit_transition(transition: &WarmTransition) {
+    let Some(logger) = audit_logger() else {
+        return;
+    };
+    let Ok(event) = AuditEvent::builder()
+        .severity(AuditSeverity::Info)
+        .category(AuditCategory::ModelOperation)
+        .event_type("warm_pool_transition")
+        .message(format!(
+            "Warm pool moved to rule {} ({} warmed, {} released)",
+            transition.to_rule,
+            transition.warmed.len(),
+            transition.released.len()
+        ))
+        .source("warm_schedule")
+        .metadata("from_rule", transition.from_rule.as_deref().unwrap_or(""))
+        .metadata("to_rule", transition.to_rule.as_str())
+        .metadata("warmed", transition.warmed.join(","))
+        .metadata("released", transition.released.join(","))
+        .metadata("missing", transition.missing.join(","))
+        .build()
+    else {
+        return;
+    };
+    logger.log(event).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ModelMetadata;
+    use chrono::TimeZone;
+
+    fn at(h: u32) -> DateTime<Utc> {
+        // 2026-03-02 is a Monday
+        Utc.with_ymd_and_hms(2026, 3, 2, h, 0, 0).unwrap()
+    }
+
+    fn config() -> WarmScheduleConfig {
+        serde_json::from_str(
+            r#"{
+                "models": [
+                    {"model_id": "tiny", "tier": "Testing"},
+                    {"model_id": "base", "tier": "Default"},
+                    {"model_id": "large", "tier": "Quality"}
+                ],
+                "rules": [
+                    {"name": "business", "hours": "9-17", "weekdays": ["Mon", "Tue", "Wed", "Thu", "Fri"],
+                     "tiers": ["Quality", "Default"]}
+                ],
+                "default_tiers": ["Testing"]
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_hour_fields() {
+        let hours = HourSet::parse("9-17").unwrap();
+        assert!(hours.contains(9) && hours.contains(17));
+        assert!(!hours.contains(18) && !hours.contains(8));
+
+        let night = HourSet::parse("22-5").unwrap();
+        assert!(night.contains(23) && night.contains(0) && night.contains(5));
+        assert!(!night.contains(12));
+
+        let list = HourSet::parse("8, 12-13").unwrap();
+        assert!(list.contains(8) && list.contains(13) && !list.contains(9));
+        assert!(HourSet::parse("*").unwrap().contains(3));
+
+        assert!(HourSet::parse("24").is_err());
+        assert!(HourSet::parse("9-").is_err());
+        assert_eq!(String::from(hours.clone()), "9-17");
+    }
+
+    #[test]
+    fn test_target_follows_rules_and_weekdays() {
+        let config = config();
+        let day = config.target_at(at(10));
+        assert_eq!(day.rule, "business");
+        let ids: Vec<_> = day.models.iter().map(|m| m.model_id.as_str()).collect();
+        assert_eq!(ids, ["large", "base"]);
+
+        assert_eq!(config.target_at(at(20)).rule, DEFAULT_RULE);
+        assert_eq!(config.target_at(at(20)).models[0].model_id, "tiny");
+
+        // Saturday falls back to the default tiers
+        let saturday = Utc.with_ymd_and_hms(2026, 3, 7, 10, 0, 0).unwrap();
+        assert_eq!(config.target_at(saturday).rule, DEFAULT_RULE);
+    }
+
+    #[test]
+    fn test_target_respects_capacity() {
+        let models = WarmScheduleConfig { max_models: 1, ..config() }.target_at(at(10)).models;
+        assert_eq!(models.len(), 1);
+        assert_eq!(models[0].model_id, "large");
+        assert_eq!(WarmScheduleConfig { max_models: 0, ..config() }.validate(), Err(WarmScheduleError::NoCapacity));
+    }
+
+    #[tokio::test]
+    async fn test_controller_transitions_between_rules() {
+        let registry = Arc::new(ModelRegistry::new());
+        for name in ["tiny", "base", "large"] {
+            let metadata = ModelMetadata { name: name.into(), size_bytes: 1 };
+            registry.register_with_format(metadata, 1024, "gguf".into()).await;
+        }
+        let controller = WarmPoolController::new(config(), Arc::clone(&registry));
+
+        let morning = controller.tick(at(9)).await.unwrap();
+        assert_eq!(morning.from_rule, None);
+        assert_eq!(morning.warmed, ["large", "base"]);
+        // Nothing changes within the same rule
+        assert!(controller.tick(at(12)).await.is_none());
+
+        let night = controller.tick(at(22)).await.unwrap();
+        assert_eq!(night.from_rule.as_deref(), Some("business"));
+        assert_eq!(night.warmed, ["tiny"]);
+        assert_eq!(night.released.len(), 2);
+        assert!(controller.pool().contains("tiny").await);
+        assert!(!controller.pool().contains("large").await);
+        // Released models stay registered
+        assert_eq!(registry.list_models().await.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_unregistered_models_are_reported_missing() {
+        let controller = WarmPoolController::new(config(), Arc::new(ModelRegistry::new()));
+        let transition = controller.tick(at(10)).await.unwrap();
+        assert!(transition.warmed.is_empty());
+        assert_eq!(transition.missing, ["large", "base"]);
+        assert_eq!(controller.current_rule().as_deref(), Some("business"));
+    }
+}
@@ -0,0 +1,111 @@
+//! Age- and size-based cleanup of a flat directory of data files.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Overwrite buffer size for shredding.
+const SHRED_CHUNK: usize = 64 * 1024;
+
+/// Files and bytes removed from a directory.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Swept {
+    pub files: u64,
+    pub bytes: u64,
+}
+
+/// Remove regular files in `dir` older than `max_age`, then the oldest
+/// until the remainder fits `max_bytes`. Symlinks and subdirectories are
+/// never followed or removed. A missing directory is not an error.
+pub fn sweep(dir: &Path, max_age: Option<Duration>, max_bytes: Option<u64>, shred: bool) -> io::Result<Swept> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Swept::default()),
+        Err(e) => return Err(e),
+    };
+    let mut files: Vec<(PathBuf, u64, SystemTime)> = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        let meta = entry.path().symlink_metadata()?;
+        if meta.file_type().is_file() {
+            files.push((entry.path(), meta.len(), meta.modified().unwrap_or(SystemTime::UNIX_EPOCH)));
+        }
+    }
+    files.sort_by_key(|(_, _, modified)| *modified);
+
+    let now = SystemTime::now();
+    let mut total: u64 = files.iter().map(|(_, len, _)| len).sum();
+    let mut swept = Swept::default();
+    for (path, len, modified) in files {
+        let age = now.duration_since(modified).unwrap_or_default();
+        let expired = max_age.is_some_and(|max| age > max);
+        let oversize = max_bytes.is_some_and(|max| total > max);
+        if !expired && !oversize {
+            break;
+        }
+        remove(&path, shred)?;
+        total -= len;
+        swept.files += 1;
+        swept.bytes += len;
+    }
+    Ok(swept)
+}
+
+/// Unlink `path`, first overwriting its contents with zeros when `shred`
+/// is set. Shredding is best effort: copy-on-write and journaling
+/// filesystems may keep older blocks.
+fn remove(path: &Path, shred: bool) -> io::Result<()> {
+    if shred {
+        overwrite(path)?;
+    }
+    fs::remove_file(path)
+}
+
+fn overwrite(path: &Path) -> io::Result<()> {
+    let mut file: File = OpenOptions::new().write(true).open(path)?;
+    let mut remaining = file.metadata()?.len();
+    let zeros = vec![0u8; SHRED_CHUNK];
+    file.seek(SeekFrom::Start(0))?;
+    while remaining > 0 {
+        let n = remaining.min(SHRED_CHUNK as u64) as usize;
+        file.write_all(&zeros[..n])?;
+        remaining -= n as u64;
+    }
+    file.sync_all()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sweep_removes_oldest_over_budget() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["a", "b", "c"] {
+            fs::write(dir.path().join(name), [1u8; 100]).unwrap();
+            std::thread::sleep(Duration::from_millis(20));
+        }
+
+        let swept = sweep(dir.path(), None, Some(150), true).unwrap();
+        assert_eq!(swept, Swept { files: 2, bytes: 200 });
+        assert!(dir.path().join("c").exists());
+        assert_eq!(sweep(dir.path(), Some(Duration::ZERO), None, false).unwrap().files, 1);
+    }
+
+    #[test]
+    fn test_missing_dir_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(sweep(&dir.path().join("none"), Some(Duration::ZERO), None, false).unwrap(), Swept::default());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlinks_are_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = tempfile::NamedTempFile::new().unwrap();
+        std::os::unix::fs::symlink(target.path(), dir.path().join("link")).unwrap();
+        assert_eq!(sweep(dir.path(), Some(Duration::ZERO), None, true).unwrap().files, 0);
+        assert!(target.path().exists());
+    }
+}
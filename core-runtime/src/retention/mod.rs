@@ -0,0 +1,267 @@
+//! Retention policies for audit, journal and usage data.
+//!
+//! Each dataset has its own policy: a maximum age, a maximum size, and for
+//! file-backed data whether to overwrite files before deleting them. A
+//! background task enforces the policies on a fixed interval, oldest data
+//! first, and keeps cumulative statistics on what it reclaimed.
+//!
+//! Audit events and usage aggregates live in memory, so their size is the
+//! serialized JSON size. Journal data is every regular file directly under
+//! the journal directory.
+
+mod files;
+
+pub use files::{sweep, Swept};
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::security::audit::audit_logger;
+use crate::telemetry::{self, UsageLedger};
+
+const DAY_SECS: u64 = 24 * 60 * 60;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum RetentionError {
+    #[error("Invalid retention config: {0}")]
+    InvalidConfig(String),
+}
+
+/// Data governed by a retention policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Dataset {
+    Audit,
+    Journal,
+    Usage,
+}
+
+impl Dataset {
+    pub const ALL: [Dataset; 3] = [Dataset::Audit, Dataset::Journal, Dataset::Usage];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Dataset::Audit => "audit",
+            Dataset::Journal => "journal",
+            Dataset::Usage => "usage",
+        }
+    }
+}
+
+/// Limits for one dataset. Unset limits are not enforced.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetentionPolicy {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_age_secs: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_bytes: Option<u64>,
+    /// Overwrite files with zeros before unlinking. File-backed data only.
+    #[serde(default)]
+    pub shred: bool,
+}
+
+impl RetentionPolicy {
+    fn max_age(&self) -> Option<Duration> {
+        self.max_age_secs.map(Duration::from_secs)
+    }
+}
+
+/// Retention configuration.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetentionConfig {
+    #[serde(default = "default_audit_policy")]
+    pub audit: RetentionPolicy,
+    #[serde(default = "default_journal_policy")]
+    pub journal: RetentionPolicy,
+    #[serde(default = "default_usage_policy")]
+    pub usage: RetentionPolicy,
+    /// Seconds between enforcement passes.
+    #[serde(default = "default_interval_secs")]
+    pub interval_secs: u64,
+}
+
+fn default_audit_policy() -> RetentionPolicy {
+    RetentionPolicy { max_age_secs: Some(90 * DAY_SECS), max_bytes: None, shred: false }
+}
+
+fn default_journal_policy() -> RetentionPolicy {
+    RetentionPolicy { max_age_secs: Some(30 * DAY_SECS), max_bytes: Some(1024 * 1024 * 1024), shred: true }
+}
+
+fn default_usage_policy() -> RetentionPolicy {
+    RetentionPolicy { max_age_secs: Some(400 * DAY_SECS), max_bytes: None, shred: false }
+}
+
+fn default_interval_secs() -> u64 {
+    3600
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            audit: default_audit_policy(),
+            journal: default_journal_policy(),
+            usage: default_usage_policy(),
+            interval_secs: default_interval_secs(),
+        }
+    }
+}
+
+impl RetentionConfig {
+    pub fn validate(&self) -> Result<(), RetentionError> {
+        if self.interval_secs == 0 {
+            return Err(RetentionError::InvalidConfig("interval_secs must be > 0".into()));
+        }
+        for dataset in Dataset::ALL {
+            if self.policy(dataset).max_bytes == Some(0) {
+                return Err(RetentionError::InvalidConfig(format!("{}.max_bytes must be > 0", dataset.as_str())));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn policy(&self, dataset: Dataset) -> &RetentionPolicy {
+        match dataset {
+            Dataset::Audit => &self.audit,
+            Dataset::Journal => &self.journal,
+            Dataset::Usage => &self.usage,
+        }
+    }
+}
+
+/// Cumulative enforcement results for one dataset.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DatasetStats {
+    pub passes: u64,
+    /// Events, files or usage records removed.
+    pub removed: u64,
+    pub reclaimed_bytes: u64,
+    /// Last pass (RFC 3339), if any.
+    pub last_run: Option<String>,
+    pub last_error: Option<String>,
+}
+
+/// Enforces retention policies on a timer.
+pub struct RetentionEnforcer {
+    config: RetentionConfig,
+    journal_dir: PathBuf,
+    usage: Arc<UsageLedger>,
+    stats: Mutex<BTreeMap<Dataset, DatasetStats>>,
+}
+
+impl RetentionEnforcer {
+    pub fn new(config: RetentionConfig, journal_dir: PathBuf, usage: Arc<UsageLedger>) -> Self {
+        Self { config, journal_dir, usage, stats: Mutex::new(BTreeMap::new()) }
+    }
+
+    pub fn config(&self) -> &RetentionConfig {
+        &self.config
+    }
+
+    /// Cumulative statistics per dataset.
+    pub fn stats(&self) -> BTreeMap<Dataset, DatasetStats> {
+        self.stats.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Run one pass over every dataset.
+    pub async fn enforce(&self) {
+        for dataset in Dataset::ALL {
+            let result = self.enforce_dataset(dataset).await;
+            let mut stats = self.stats.lock().unwrap_or_else(|e| e.into_inner());
+            let entry = stats.entry(dataset).or_default();
+            entry.passes += 1;
+            entry.last_run = Some(chrono::Utc::now().to_rfc3339());
+            match result {
+                Ok((removed, bytes)) => {
+                    entry.removed += removed;
+                    entry.reclaimed_bytes += bytes;
+                    entry.last_error = None;
+                    if removed > 0 {
+                        telemetry::record_retention(dataset.as_str(), removed, bytes);
+                        tracing::info!(dataset = dataset.as_str(), removed, bytes, "Retention pass reclaimed data");
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!(dataset = dataset.as_str(), error = %e, "Retention pass failed");
+                    entry.last_error = Some(e);
+                }
+            }
+        }
+    }
+
+    async fn enforce_dataset(&self, dataset: Dataset) -> Result<(u64, u64), String> {
+        let policy = self.config.policy(dataset);
+        match dataset {
+            Dataset::Audit => {
+                let Some(logger) = audit_logger() else {
+                    return Ok((0, 0));
+                };
+                let cutoff = policy
+                    .max_age()
+                    .and_then(|age| chrono::Duration::from_std(age).ok())
+                    .map(|age| chrono::Utc::now() - age);
+                let (removed, bytes) = logger.enforce_retention(cutoff, policy.max_bytes).await;
+                Ok((removed as u64, bytes))
+            }
+            Dataset::Journal => sweep(&self.journal_dir, policy.max_age(), policy.max_bytes, policy.shred)
+                .map(|swept| (swept.files, swept.bytes))
+                .map_err(|e| e.to_string()),
+            Dataset::Usage => {
+                let (removed, bytes) = self.usage.enforce_retention(policy.max_age(), policy.max_bytes);
+                Ok((removed as u64, bytes))
+            }
+        }
+    }
+
+    /// Enforce on `interval_secs` until the task is aborted.
+    pub async fn run(self: Arc<Self>) {
+        let mut interval = tokio::time::interval(Duration::from_secs(self.config.interval_secs.max(1)));
+        loop {
+            interval.tick().await;
+            self.enforce().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_validate_and_round_trip() {
+        let config = RetentionConfig::default();
+        assert!(config.validate().is_ok());
+        let parsed: RetentionConfig = serde_json::from_str(r#"{"usage": {"max_age_secs": 60}}"#).unwrap();
+        assert_eq!(parsed.usage.max_age_secs, Some(60));
+        assert_eq!(parsed.audit, config.audit);
+        assert!(RetentionConfig { interval_secs: 0, ..config }.validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_enforce_records_stats() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("j1"), [0u8; 64]).unwrap();
+        let usage = Arc::new(UsageLedger::new(None));
+        usage.record("session", 10);
+        let config = RetentionConfig {
+            journal: RetentionPolicy { max_bytes: Some(1), ..Default::default() },
+            usage: RetentionPolicy { max_age_secs: Some(0), ..Default::default() },
+            ..Default::default()
+        };
+        let enforcer = RetentionEnforcer::new(config, dir.path().to_path_buf(), Arc::clone(&usage));
+        std::thread::sleep(Duration::from_millis(5));
+        enforcer.enforce().await;
+
+        let stats = enforcer.stats();
+        assert_eq!(stats[&Dataset::Journal].removed, 1);
+        assert_eq!(stats[&Dataset::Journal].reclaimed_bytes, 64);
+        assert_eq!(stats[&Dataset::Usage].removed, 1);
+        assert!(usage.exact().is_empty());
+        assert_eq!(stats[&Dataset::Audit].passes, 1);
+    }
+}
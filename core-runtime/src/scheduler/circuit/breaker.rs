@@ -0,0 +1,237 @@
+//! Admission, status and holds across every model's circuit.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use crate::engine::inference::InferenceError;
+
+use super::outcome::ModelCircuit;
+use super::{CircuitConfig, CircuitOpen, CircuitPermit, CircuitState, CircuitStatus};
+
+/// Tracks per-model failure rates and decides admission.
+pub struct CircuitBreaker {
+    pub(super) config: CircuitConfig,
+    pub(super) models: Mutex<HashMap<String, ModelCircuit>>,
+}
+
+impl CircuitBreaker {
+    pub fn new(config: CircuitConfig) -> Self {
+        Self { config, models: Mutex::new(HashMap::new()) }
+    }
+
+    /// Admit a request for `model`, or refuse it while the circuit is open.
+    /// The permit must be resolved with the request's outcome.
+    pub fn try_acquire(&self, model: &str) -> Result<CircuitPermit<'_>, CircuitOpen> {
+        let mut models = self.models.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(circuit) = models.get_mut(model) else {
+            return Ok(self.permit(model, false));
+        };
+        if circuit.held.is_some() {
+            return Err(CircuitOpen { model: model.to_string(), retry_after_ms: 0 });
+        }
+        if !self.config.enabled {
+            return Ok(self.permit(model, false));
+        }
+
+        if circuit.state == CircuitState::Open {
+            let elapsed = circuit.opened_at.elapsed();
+            if elapsed < self.config.open_duration {
+                return Err(CircuitOpen {
+                    model: model.to_string(),
+                    retry_after_ms: (self.config.open_duration - elapsed).as_millis() as u64,
+                });
+            }
+            circuit.state = CircuitState::HalfOpen;
+            tracing::info!(model, "Circuit half-open; admitting canary requests");
+        }
+        if circuit.state == CircuitState::HalfOpen {
+            if circuit.probes_in_flight + circuit.probe_successes >= self.config.half_open_probes.max(1) {
+                return Err(CircuitOpen { model: model.to_string(), retry_after_ms: 0 });
+            }
+            circuit.probes_in_flight += 1;
+            return Ok(self.permit(model, true));
+        }
+        Ok(self.permit(model, false))
+    }
+
+    /// Current state of every model that has failed since startup, sorted
+    /// by model.
+    pub fn status(&self) -> Vec<CircuitStatus> {
+        let models = self.models.lock().unwrap_or_else(|e| e.into_inner());
+        let mut status: Vec<CircuitStatus> = models
+            .iter()
+            .map(|(model, circuit)| {
+                let retry_after_ms = match circuit.state {
+                    CircuitState::Open if circuit.held.is_none() => {
+                        self.config.open_duration.saturating_sub(circuit.opened_at.elapsed()).as_millis() as u64
+                    }
+                    _ => 0,
+                };
+                CircuitStatus {
+                    model: model.clone(),
+                    state: circuit.state,
+                    failure_ratio: circuit.failure_ratio(),
+                    trips: circuit.trips,
+                    retry_after_ms,
+                    held: circuit.held.clone(),
+                }
+            })
+            .collect();
+        status.sort_by(|a, b| a.model.cmp(&b.model));
+        status
+    }
+
+    /// Hold `model`'s circuit open for `reason` until [`release_hold`].
+    ///
+    /// [`release_hold`]: Self::release_hold
+    pub fn hold_open(&self, model: &str, reason: &str) {
+        let mut models = self.models.lock().unwrap_or_else(|e| e.into_inner());
+        let circuit = models.entry(model.to_string()).or_insert_with(ModelCircuit::new);
+        if circuit.held.is_none() {
+            circuit.trips += 1;
+            metrics::counter!("core_circuit_trips_total", "model" => model.to_string()).increment(1);
+        }
+        circuit.state = CircuitState::Open;
+        circuit.opened_at = Instant::now();
+        circuit.probes_in_flight = 0;
+        circuit.probe_successes = 0;
+        circuit.held = Some(reason.to_string());
+        tracing::warn!(model, reason, "Circuit held open");
+    }
+
+    /// Close a held circuit with a fresh window. Returns whether it was held.
+    pub fn release_hold(&self, model: &str) -> bool {
+        let mut models = self.models.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(circuit) = models.get_mut(model).filter(|c| c.held.is_some()) else {
+            return false;
+        };
+        circuit.held = None;
+        circuit.state = CircuitState::Closed;
+        circuit.outcomes.clear();
+        tracing::info!(model, "Circuit hold released");
+        true
+    }
+
+    /// Whether `error` indicates an unhealthy model.
+    pub fn counts_as_failure(error: &InferenceError) -> bool {
+        matches!(error, InferenceError::ExecutionFailed(_))
+    }
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self::new(CircuitConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn breaker(open_duration: Duration) -> CircuitBreaker {
+        CircuitBreaker::new(CircuitConfig {
+            window: 4,
+            min_requests: 4,
+            failure_ratio: 0.5,
+            open_duration,
+            half_open_probes: 2,
+            ..Default::default()
+        })
+    }
+
+    fn fail(breaker: &CircuitBreaker, model: &str, times: usize) {
+        for _ in 0..times {
+            breaker.try_acquire(model).unwrap().failure();
+        }
+    }
+
+    #[test]
+    fn test_opens_at_failure_ratio() {
+        let breaker = breaker(Duration::from_secs(60));
+        fail(&breaker, "m", 1);
+        breaker.try_acquire("m").unwrap().success();
+        breaker.try_acquire("m").unwrap().success();
+        assert_eq!(breaker.status()[0].state, CircuitState::Closed);
+        fail(&breaker, "m", 1);
+
+        let err = breaker.try_acquire("m").err().unwrap();
+        assert_eq!(err.model, "m");
+        assert!(err.retry_after_ms > 0);
+        assert_eq!(breaker.status()[0].state, CircuitState::Open);
+        assert_eq!(breaker.status()[0].trips, 1);
+        // Other models are unaffected
+        assert!(breaker.try_acquire("other").is_ok());
+    }
+
+    #[test]
+    fn test_half_open_closes_after_canaries() {
+        let breaker = breaker(Duration::ZERO);
+        fail(&breaker, "m", 4);
+
+        let first = breaker.try_acquire("m").unwrap();
+        let second = breaker.try_acquire("m").unwrap();
+        // Canary slots are capped
+        assert!(breaker.try_acquire("m").is_err());
+        assert_eq!(breaker.status()[0].state, CircuitState::HalfOpen);
+
+        first.success();
+        second.success();
+        assert_eq!(breaker.status()[0].state, CircuitState::Closed);
+        assert_eq!(breaker.status()[0].failure_ratio, 0.0);
+    }
+
+    #[test]
+    fn test_failed_canary_reopens() {
+        let breaker = breaker(Duration::ZERO);
+        fail(&breaker, "m", 4);
+        breaker.try_acquire("m").unwrap().failure();
+        let status = &breaker.status()[0];
+        assert_eq!((status.state, status.trips), (CircuitState::Open, 2));
+    }
+
+    #[test]
+    fn test_dropped_canary_frees_slot() {
+        let breaker = breaker(Duration::ZERO);
+        fail(&breaker, "m", 4);
+        drop(breaker.try_acquire("m").unwrap());
+        drop(breaker.try_acquire("m").unwrap());
+        assert!(breaker.try_acquire("m").is_ok());
+    }
+
+    #[test]
+    fn test_client_errors_do_not_count() {
+        let breaker = CircuitBreaker::new(CircuitConfig { min_requests: 1, ..Default::default() });
+        let result: Result<(), _> = Err(InferenceError::InvalidParams("bad".into()));
+        breaker.try_acquire("m").unwrap().resolve(&result);
+        let result: Result<(), _> = Err(InferenceError::ExecutionFailed("NaN logits".into()));
+        assert!(breaker.try_acquire("m").is_ok());
+        breaker.try_acquire("m").unwrap().resolve(&result);
+        assert_eq!(breaker.status()[0].state, CircuitState::Open);
+    }
+
+    #[test]
+    fn test_held_circuit_stays_open_until_released() {
+        let config = CircuitConfig { enabled: false, open_duration: Duration::ZERO, ..Default::default() };
+        let breaker = CircuitBreaker::new(config);
+        breaker.hold_open("m", "integrity mismatch");
+        assert!(breaker.try_acquire("m").is_err());
+        let status = &breaker.status()[0];
+        assert_eq!((status.state, status.held.as_deref()), (CircuitState::Open, Some("integrity mismatch")));
+
+        assert!(breaker.release_hold("m"));
+        assert!(!breaker.release_hold("m"));
+        assert!(breaker.try_acquire("m").is_ok());
+        assert_eq!(breaker.status()[0].state, CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_disabled_never_opens() {
+        let breaker = CircuitBreaker::new(CircuitConfig { enabled: false, min_requests: 1, ..Default::default() });
+        fail(&breaker, "m", 10);
+        assert!(breaker.try_acquire("m").is_ok());
+        assert!(breaker.status().is_empty());
+    }
+}
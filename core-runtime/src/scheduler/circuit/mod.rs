@@ -0,0 +1,99 @@
+//! Per-model circuit breaker for repeated inference failures.
+//!
+//! Each model keeps a sliding window of recent outcomes. Once at least
+//! `min_requests` are recorded and the failure ratio reaches
+//! `failure_ratio`, the circuit opens: requests fail fast (or move down the
+//! model's fallback chain) instead of piling onto a broken backend.
+//! After `open_duration` the circuit half-opens and admits up to
+//! `half_open_probes` canary requests; if all of them succeed it closes,
+//! and any failure re-opens it.
+//!
+//! Only execution failures count. Client errors (bad parameters, context
+//! overflow, unknown model) say nothing about the model's health.
+//!
+//! A circuit can also be held open from outside, e.g. when the model's
+//! file fails integrity verification. A held circuit never half-opens and
+//! refuses requests even with the breaker disabled, until the hold is
+//! released.
+
+mod breaker;
+mod outcome;
+mod permit;
+
+pub use breaker::CircuitBreaker;
+pub use permit::CircuitPermit;
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Debug, Clone)]
+pub struct CircuitConfig {
+    pub enabled: bool,
+    /// Number of recent outcomes considered per model.
+    pub window: usize,
+    /// Outcomes required in the window before the circuit may open.
+    pub min_requests: usize,
+    /// Failure ratio (0.0-1.0] that opens the circuit.
+    pub failure_ratio: f64,
+    /// How long an open circuit rejects before admitting canaries.
+    pub open_duration: Duration,
+    /// Canary requests admitted while half-open; all must succeed to close.
+    pub half_open_probes: u32,
+}
+
+impl Default for CircuitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            window: 20,
+            min_requests: 10,
+            failure_ratio: 0.5,
+            open_duration: Duration::from_secs(30),
+            half_open_probes: 3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+impl CircuitState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Closed => "closed",
+            Self::Open => "open",
+            Self::HalfOpen => "half_open",
+        }
+    }
+}
+
+/// Request refused because the model's circuit is open.
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+#[error("Model not available: {model} circuit is open after repeated failures (retry in {retry_after_ms} ms)")]
+pub struct CircuitOpen {
+    pub model: String,
+    pub retry_after_ms: u64,
+}
+
+/// Per-model breaker state for status output.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CircuitStatus {
+    pub model: String,
+    pub state: CircuitState,
+    /// Failure ratio over the current window.
+    pub failure_ratio: f64,
+    /// Times the circuit has opened since startup.
+    pub trips: u64,
+    /// Remaining open time; 0 unless open.
+    pub retry_after_ms: u64,
+    /// Why the circuit is held open, if it is.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub held: Option<String>,
+}
@@ -0,0 +1,110 @@
+//! Per-model outcome windows and the state transitions they drive.
+
+use std::collections::VecDeque;
+use std::time::Instant;
+
+use super::{CircuitBreaker, CircuitPermit, CircuitState};
+
+pub(super) struct ModelCircuit {
+    pub(super) state: CircuitState,
+    pub(super) outcomes: VecDeque<bool>,
+    pub(super) opened_at: Instant,
+    pub(super) probes_in_flight: u32,
+    pub(super) probe_successes: u32,
+    pub(super) trips: u64,
+    pub(super) held: Option<String>,
+}
+
+impl ModelCircuit {
+    pub(super) fn new() -> Self {
+        Self {
+            state: CircuitState::Closed,
+            outcomes: VecDeque::new(),
+            opened_at: Instant::now(),
+            probes_in_flight: 0,
+            probe_successes: 0,
+            trips: 0,
+            held: None,
+        }
+    }
+
+    pub(super) fn failure_ratio(&self) -> f64 {
+        if self.outcomes.is_empty() {
+            return 0.0;
+        }
+        let failures = self.outcomes.iter().filter(|ok| !**ok).count();
+        failures as f64 / self.outcomes.len() as f64
+    }
+
+    /// A half-open canary finished: one failure reopens the circuit, and
+    /// `needed` successes close it.
+    fn record_probe(&mut self, model: &str, success: bool, needed: u32) {
+        self.probes_in_flight = self.probes_in_flight.saturating_sub(1);
+        if !success {
+            self.open(model);
+            return;
+        }
+        self.probe_successes += 1;
+        if self.probe_successes >= needed {
+            self.state = CircuitState::Closed;
+            self.outcomes.clear();
+            tracing::info!(model, "Circuit closed after successful canaries");
+        }
+    }
+
+    pub(super) fn open(&mut self, model: &str) {
+        self.state = CircuitState::Open;
+        self.opened_at = Instant::now();
+        self.probes_in_flight = 0;
+        self.probe_successes = 0;
+        self.trips += 1;
+        tracing::warn!(model, trips = self.trips, "Circuit opened after repeated inference failures");
+        metrics::counter!("core_circuit_trips_total", "model" => model.to_string()).increment(1);
+    }
+}
+
+impl CircuitBreaker {
+    pub(super) fn permit(&self, model: &str, probe: bool) -> CircuitPermit<'_> {
+        CircuitPermit { breaker: self, model: model.to_string(), probe, resolved: false }
+    }
+
+    pub(super) fn record(&self, model: &str, probe: bool, success: bool) {
+        if !self.config.enabled {
+            return;
+        }
+        let mut models = self.models.lock().unwrap_or_else(|e| e.into_inner());
+        // Only failures start tracking a model, so arbitrary model ids from
+        // clients cannot grow the map
+        let circuit = match models.get_mut(model) {
+            Some(circuit) => circuit,
+            None if success => return,
+            None => models.entry(model.to_string()).or_insert_with(ModelCircuit::new),
+        };
+
+        if probe && circuit.state == CircuitState::HalfOpen {
+            circuit.record_probe(model, success, self.config.half_open_probes.max(1));
+            return;
+        }
+        if circuit.state != CircuitState::Closed {
+            // Late result from before the circuit opened
+            return;
+        }
+
+        circuit.outcomes.push_back(success);
+        while circuit.outcomes.len() > self.config.window.max(1) {
+            circuit.outcomes.pop_front();
+        }
+        if circuit.outcomes.len() >= self.config.min_requests.max(1)
+            && circuit.failure_ratio() >= self.config.failure_ratio
+        {
+            circuit.open(model);
+        }
+    }
+
+    pub(super) fn release(&self, model: &str) {
+        let mut models = self.models.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(circuit) = models.get_mut(model) {
+            circuit.probes_in_flight = circuit.probes_in_flight.saturating_sub(1);
+        }
+    }
+}
@@ -0,0 +1,47 @@
+//! Per-request admission and how its outcome is reported.
+
+use crate::engine::inference::InferenceError;
+
+use super::CircuitBreaker;
+
+/// Admission for one request. Dropping it unresolved (e.g. the request was
+/// cancelled) frees a canary slot without counting an outcome.
+pub struct CircuitPermit<'a> {
+    pub(super) breaker: &'a CircuitBreaker,
+    pub(super) model: String,
+    pub(super) probe: bool,
+    pub(super) resolved: bool,
+}
+
+impl CircuitPermit<'_> {
+    /// Model this permit admits (the fallback when one was chosen).
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    pub fn success(mut self) {
+        self.resolved = true;
+        self.breaker.record(&self.model, self.probe, true);
+    }
+
+    pub fn failure(mut self) {
+        self.resolved = true;
+        self.breaker.record(&self.model, self.probe, false);
+    }
+
+    /// Resolve from an inference result.
+    pub fn resolve<T>(self, result: &Result<T, InferenceError>) {
+        match result {
+            Err(e) if CircuitBreaker::counts_as_failure(e) => self.failure(),
+            _ => self.success(),
+        }
+    }
+}
+
+impl Drop for CircuitPermit<'_> {
+    fn drop(&mut self) {
+        if !self.resolved && self.probe {
+            self.breaker.release(&self.model);
+        }
+    }
+}
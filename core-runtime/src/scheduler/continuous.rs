@@ -1,10 +1,13 @@
 //! Continuous batching for iteration-level dynamic batch membership.
 //!
 //! Requests join and leave the batch between token generation steps.
+//! With prefix sharing enabled, members with a common prompt prefix map
+//! the same KV pages and only prefill the remainder.
 
 use std::collections::VecDeque;
 
 use crate::engine::FinishReason;
+use crate::memory::{PrefixShareStats, SequenceId, SharedKvPool};
 
 /// Unique identifier for a request.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -29,6 +32,8 @@ pub struct BatchSlot {
     pub tokens_generated: usize,
     pub max_tokens: usize,
     pub prompt_len: usize,
+    /// Leading prompt tokens whose KV is shared with other batch members.
+    pub cached_prefix_len: usize,
 }
 
 impl BatchSlot {
@@ -40,9 +45,15 @@ impl BatchSlot {
             tokens_generated: 0,
             max_tokens,
             prompt_len,
+            cached_prefix_len: 0,
         }
     }
 
+    /// Prompt tokens that still need prefill.
+    pub fn prefill_len(&self) -> usize {
+        self.prompt_len - self.cached_prefix_len
+    }
+
     /// Transition from prefill to decode phase.
     pub fn finish_prefill(&mut self) {
         self.phase = RequestPhase::Decode;
@@ -87,6 +98,7 @@ pub struct ContinuousBatcher {
     slots: Vec<Option<BatchSlot>>,
     _max_slots: usize,
     pending: VecDeque<PendingRequest>,
+    prefix: Option<SharedKvPool>,
 }
 
 impl ContinuousBatcher {
@@ -96,9 +108,16 @@ impl ContinuousBatcher {
             slots: vec![None; max_slots],
             _max_slots: max_slots,
             pending: VecDeque::new(),
+            prefix: None,
         }
     }
 
+    /// Share KV pages for common prompt prefixes across batch members.
+    pub fn with_prefix_sharing(mut self, pool: SharedKvPool) -> Self {
+        self.prefix = Some(pool);
+        self
+    }
+
     /// Add a request to the pending queue.
     pub fn enqueue(&mut self, request: PendingRequest) {
         self.pending.push_back(request);
@@ -110,8 +129,18 @@ impl ContinuousBatcher {
         for (idx, slot) in self.slots.iter_mut().enumerate() {
             if slot.is_none() {
                 if let Some(req) = self.pending.pop_front() {
-                    let batch_slot =
+                    let mut batch_slot =
                         BatchSlot::new(req.request_id, req.prompt_tokens.len(), req.max_tokens);
+                    if let Some(pool) = self.prefix.as_mut() {
+                        match pool.attach(SequenceId(req.request_id.0), &req.prompt_tokens) {
+                            Ok(cached) => batch_slot.cached_prefix_len = cached,
+                            // Out of KV pages: wait for members to complete
+                            Err(_) => {
+                                self.pending.push_front(req);
+                                break;
+                            }
+                        }
+                    }
                     *slot = Some(batch_slot);
                     admitted.push((idx, req));
                 }
@@ -126,6 +155,9 @@ impl ContinuousBatcher {
         for slot in &mut self.slots {
             if let Some(s) = slot {
                 if s.is_complete() {
+                    if let Some(pool) = self.prefix.as_mut() {
+                        pool.release(SequenceId(s.request_id.0));
+                    }
                     evicted.push(s.request_id);
                     *slot = None;
                 }
@@ -157,6 +189,17 @@ impl ContinuousBatcher {
             .filter_map(|(i, s)| s.as_ref().map(|slot| (i, slot)))
     }
 
+    /// Shared KV pool, for writing and reading members' KV data.
+    /// Members are keyed by `SequenceId(request_id.0)`.
+    pub fn prefix_pool_mut(&mut self) -> Option<&mut SharedKvPool> {
+        self.prefix.as_mut()
+    }
+
+    /// Prefix sharing statistics, if enabled.
+    pub fn prefix_stats(&self) -> Option<PrefixShareStats> {
+        self.prefix.as_ref().map(SharedKvPool::stats)
+    }
+
     /// Check if batch is empty (no active or pending requests).
     pub fn is_empty(&self) -> bool {
         self.active_count() == 0 && self.pending.is_empty()
@@ -5,9 +5,11 @@
 
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use crate::engine::InferenceParams;
+use crate::features::{Feature, FeatureFlags};
 
 /// Cached output for a completed request.
 #[derive(Debug, Clone)]
@@ -37,6 +39,7 @@ pub struct OutputCache {
     entries: HashMap<[u8; 32], CachedOutput>,
     ttl: Duration,
     max_entries: usize,
+    features: Option<Arc<FeatureFlags>>,
 }
 
 impl OutputCache {
@@ -45,9 +48,20 @@ impl OutputCache {
             entries: HashMap::new(),
             ttl: config.ttl,
             max_entries: config.max_entries,
+            features: None,
         }
     }
 
+    /// Serve and store nothing while the `response_cache` flag is off.
+    pub fn with_features(mut self, features: Arc<FeatureFlags>) -> Self {
+        self.features = Some(features);
+        self
+    }
+
+    fn enabled(&self) -> bool {
+        self.features.as_ref().is_none_or(|f| f.enabled(Feature::ResponseCache))
+    }
+
     /// Compute cache key from prompt tokens and params.
     pub fn cache_key(tokens: &[u32], params: &InferenceParams) -> [u8; 32] {
         let mut hasher = Sha256::new();
@@ -63,6 +77,9 @@ impl OutputCache {
 
     /// Get cached output if within TTL.
     pub fn get(&self, key: &[u8; 32]) -> Option<&CachedOutput> {
+        if !self.enabled() {
+            return None;
+        }
         let entry = self.entries.get(key)?;
         if entry.cached_at.elapsed() <= self.ttl {
             Some(entry)
@@ -73,6 +90,9 @@ impl OutputCache {
 
     /// Store output for future dedup.
     pub fn insert(&mut self, key: [u8; 32], output_tokens: Vec<u32>) {
+        if !self.enabled() {
+            return;
+        }
         // Evict oldest if at capacity
         if self.entries.len() >= self.max_entries {
             self.evict_oldest();
@@ -0,0 +1,223 @@
+//! Latency-aware hedging across paired model replicas.
+//!
+//! When the same model is resident twice (e.g. a GPU and a CPU copy under
+//! different ids), a request starts on the primary. If the primary has not
+//! answered within the TTFT threshold, the request is also launched on the
+//! secondary and whichever finishes first wins; the loser is aborted.
+//!
+//! Hedges are strictly capped so they cannot double load: a bounded number
+//! may run at once, at most `max_ratio` of paired requests may hedge, and
+//! no hedge starts once the overload ladder has left `Normal`.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use tokio::task::{JoinError, JoinHandle};
+
+use super::overload::DegradationLevel;
+
+/// Hedging configuration. No pairs disables hedging.
+#[derive(Debug, Clone)]
+pub struct HedgeConfig {
+    /// Primary model id -> secondary replica model id.
+    pub pairs: HashMap<String, String>,
+    /// Launch the secondary when the primary has not answered by now.
+    /// Non-streaming requests have no separate first token, so this is the
+    /// time to the complete response.
+    pub ttft_threshold: Duration,
+    /// Hedges running at once, across all pairs.
+    pub max_in_flight: usize,
+    /// Largest fraction (0.0-1.0] of paired requests that may hedge.
+    pub max_ratio: f64,
+}
+
+impl Default for HedgeConfig {
+    fn default() -> Self {
+        Self {
+            pairs: HashMap::new(),
+            ttft_threshold: Duration::from_millis(500),
+            max_in_flight: 2,
+            max_ratio: 0.05,
+        }
+    }
+}
+
+/// Reason a hedge was not launched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HedgeSkip {
+    Overloaded,
+    Concurrency,
+    Ratio,
+}
+
+impl HedgeSkip {
+    /// Label for skip metrics.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Overloaded => "overloaded",
+            Self::Concurrency => "concurrency",
+            Self::Ratio => "ratio",
+        }
+    }
+}
+
+/// Decides which requests may hedge and enforces the caps.
+pub struct Hedger {
+    config: HedgeConfig,
+    observed: AtomicU64,
+    launched: AtomicU64,
+    in_flight: Arc<AtomicUsize>,
+}
+
+impl Hedger {
+    pub fn new(config: HedgeConfig) -> Self {
+        Self {
+            config,
+            observed: AtomicU64::new(0),
+            launched: AtomicU64::new(0),
+            in_flight: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Secondary replica paired with `primary`, if any.
+    pub fn secondary(&self, primary: &str) -> Option<&str> {
+        self.config.pairs.get(primary).map(String::as_str)
+    }
+
+    pub fn threshold(&self) -> Duration {
+        self.config.ttft_threshold
+    }
+
+    /// Count one request that could hedge; the ratio cap is relative to these.
+    pub fn observe(&self) {
+        self.observed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Reserve a hedge slot at overload `level`. The guard frees the
+    /// concurrency slot when dropped; the ratio budget is spent for good.
+    pub fn try_hedge(&self, level: DegradationLevel) -> Result<HedgeGuard, HedgeSkip> {
+        if level > DegradationLevel::Normal {
+            return Err(HedgeSkip::Overloaded);
+        }
+        let max_in_flight = self.config.max_in_flight;
+        self.in_flight
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| (n < max_in_flight).then_some(n + 1))
+            .map_err(|_| HedgeSkip::Concurrency)?;
+        let guard = HedgeGuard { in_flight: Arc::clone(&self.in_flight) };
+
+        let budget = self.config.max_ratio.clamp(0.0, 1.0) * self.observed.load(Ordering::Relaxed) as f64;
+        self.launched
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| ((n + 1) as f64 <= budget).then_some(n + 1))
+            .map_err(|_| HedgeSkip::Ratio)?;
+        Ok(guard)
+    }
+
+    /// Hedges currently running.
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::Acquire)
+    }
+}
+
+impl Default for Hedger {
+    fn default() -> Self {
+        Self::new(HedgeConfig::default())
+    }
+}
+
+/// RAII guard holding one hedge concurrency slot.
+pub struct HedgeGuard {
+    in_flight: Arc<AtomicUsize>,
+}
+
+impl Drop for HedgeGuard {
+    fn drop(&mut self) {
+        self.in_flight.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// A spawned run that is aborted when dropped, so a losing (or abandoned)
+/// replica stops at its next await point. A backend already inside a
+/// blocking call finishes that call first.
+pub struct AbortOnDrop<T>(JoinHandle<T>);
+
+impl<T: Send + 'static> AbortOnDrop<T> {
+    pub fn spawn<F>(future: F) -> Self
+    where
+        F: Future<Output = T> + Send + 'static,
+    {
+        Self(tokio::spawn(future))
+    }
+}
+
+impl<T> Future for AbortOnDrop<T> {
+    type Output = Result<T, JoinError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.0).poll(cx)
+    }
+}
+
+impl<T> Drop for AbortOnDrop<T> {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hedger(max_in_flight: usize, max_ratio: f64) -> Hedger {
+        Hedger::new(HedgeConfig {
+            pairs: HashMap::from([("gpu".to_string(), "cpu".to_string())]),
+            max_in_flight,
+            max_ratio,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_concurrency_cap_and_release() {
+        let hedger = hedger(1, 1.0);
+        hedger.observe();
+        hedger.observe();
+        let guard = hedger.try_hedge(DegradationLevel::Normal).unwrap();
+        assert_eq!(hedger.try_hedge(DegradationLevel::Normal).err(), Some(HedgeSkip::Concurrency));
+        drop(guard);
+        assert_eq!(hedger.in_flight(), 0);
+        assert!(hedger.try_hedge(DegradationLevel::Normal).is_ok());
+    }
+
+    #[test]
+    fn test_ratio_and_overload_caps() {
+        let hedger = hedger(8, 0.5);
+        assert_eq!(hedger.secondary("gpu"), Some("cpu"));
+        assert_eq!(hedger.secondary("cpu"), None);
+        hedger.observe();
+        assert_eq!(hedger.try_hedge(DegradationLevel::Normal).err(), Some(HedgeSkip::Ratio));
+        assert_eq!(hedger.in_flight(), 0);
+        hedger.observe();
+        assert!(hedger.try_hedge(DegradationLevel::Normal).is_ok());
+        hedger.observe();
+        hedger.observe();
+        assert_eq!(hedger.try_hedge(DegradationLevel::NoSpeculative).err(), Some(HedgeSkip::Overloaded));
+    }
+
+    #[tokio::test]
+    async fn test_abort_on_drop_cancels_loser() {
+        let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+        let loser = AbortOnDrop::spawn(async move {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            let _ = tx.send(());
+        });
+        drop(loser);
+        // The sender is dropped by the abort, never used
+        assert!(rx.await.is_err());
+        assert_eq!(AbortOnDrop::spawn(async { 7 }).await.unwrap(), 7);
+    }
+}
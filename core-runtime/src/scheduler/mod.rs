@@ -1,24 +1,44 @@
 //! Request scheduling module for CORE Runtime.
 //!
 //! Manages request queuing, prioritization, batching, continuous batching,
-//! deduplication, and thread pool configuration.
+//! deduplication, overload degradation, tenant quotas, replica hedging,
+//! GPU sharing between models, thermal throttling, worker occupancy and
+//! thread pool configuration.
 
 mod batch;
+pub mod circuit;
 pub mod continuous;
 mod dedup;
+pub mod gpu_share;
+pub mod hedge;
+pub mod occupancy;
+pub mod overload;
 mod pool;
 mod priority;
 mod queue;
+pub mod quota;
+#[cfg(feature = "redis-quota")]
+pub mod redis_quota;
+pub mod thermal;
 pub mod thread_pool;
 
 pub use batch::{BatchConfig, BatchProcessor, RequestBatch};
+pub use circuit::{CircuitBreaker, CircuitConfig, CircuitOpen, CircuitPermit, CircuitState, CircuitStatus};
 pub use continuous::{
     BatchSlot, ContinuousBatcher, PendingRequest, RequestId, RequestPhase, StepResult,
 };
 pub use dedup::{CachedOutput, DedupResult, OutputCache, OutputCacheConfig};
+pub use gpu_share::{GpuModelOccupancy, GpuScheduler, GpuShareConfig, GpuShareMode, GpuTurn};
+pub use hedge::{AbortOnDrop, HedgeConfig, HedgeGuard, HedgeSkip, Hedger};
+pub use occupancy::{BatchComposition, Occupancy, OccupancyGuard, OccupancySnapshot, WorkerSlot};
+pub use overload::{
+    AdmissionGuard, DegradationLevel, OverloadConfig, OverloadController, OverloadRejection,
+};
 pub use pool::ThreadPoolConfig;
 pub use priority::{Priority, PriorityQueue};
-pub use queue::{QueuedRequest, RequestQueue, RequestQueueConfig};
+pub use queue::{ModelQueueAge, QueueError, QueuedRequest, RequestQueue, RequestQueueConfig, SwitchPolicy};
+pub use quota::{QuotaBackend, QuotaConfig, QuotaError, QuotaRejection, QuotaStore};
+pub use thermal::{GpuReading, GpuSensor, ThermalConfig, ThermalGovernor, ThermalStatus};
 pub use thread_pool::{
-    TaskPriority, ThreadPool, ThreadPoolConfig as TunableThreadPoolConfig, ThreadPoolStats,
+    RejectCallback, TaskPriority, ThreadPool, ThreadPoolConfig as TunableThreadPoolConfig, ThreadPoolStats,
 };
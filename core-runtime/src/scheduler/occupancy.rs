@@ -0,0 +1,210 @@
+//! Worker occupancy and batch composition.
+//!
+//! Requests execute on a fixed set of worker slots; each running request
+//! holds one for its duration (requests beyond the slot count still run,
+//! but are counted as in flight without a worker). Requests running on the
+//! same model at the same time are decoded as one batch by the backend, so
+//! a batch is the set of in-flight requests of one model. When models
+//! share a GPU, the snapshot also carries each model's GPU occupancy.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+use super::gpu_share::{GpuModelOccupancy, GpuScheduler};
+use crate::telemetry;
+
+#[derive(Debug, Clone)]
+struct Running {
+    request_id: String,
+    model_id: String,
+    started: Instant,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    /// One entry per worker slot.
+    workers: Vec<Option<Running>>,
+    /// Requests running without a free worker slot.
+    overflow: Vec<Running>,
+}
+
+impl State {
+    fn running(&self) -> impl Iterator<Item = &Running> {
+        self.workers.iter().flatten().chain(self.overflow.iter())
+    }
+}
+
+/// Tracks which request each worker is running.
+pub struct Occupancy {
+    state: Arc<Mutex<State>>,
+    gpu: Option<Arc<GpuScheduler>>,
+}
+
+impl Occupancy {
+    pub fn new(workers: usize) -> Self {
+        let state = State { workers: vec![None; workers.max(1)], overflow: Vec::new() };
+        Self { state: Arc::new(Mutex::new(state)), gpu: None }
+    }
+
+    /// Report GPU occupancy from `gpu` in snapshots.
+    pub fn with_gpu(mut self, gpu: Arc<GpuScheduler>) -> Self {
+        self.gpu = Some(gpu);
+        self
+    }
+
+    /// Mark a request as running until the guard is dropped.
+    pub fn begin(&self, request_id: &str, model_id: &str) -> OccupancyGuard {
+        let running = Running {
+            request_id: request_id.to_string(),
+            model_id: model_id.to_string(),
+            started: Instant::now(),
+        };
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let worker = state.workers.iter().position(Option::is_none);
+        match worker {
+            Some(slot) => state.workers[slot] = Some(running),
+            None => state.overflow.push(running),
+        }
+        record(&state);
+        OccupancyGuard { state: Arc::clone(&self.state), worker, request_id: request_id.to_string() }
+    }
+
+    pub fn snapshot(&self) -> OccupancySnapshot {
+        let mut snapshot = snapshot(&self.state.lock().unwrap_or_else(|e| e.into_inner()));
+        snapshot.gpu = self.gpu.as_ref().map(|gpu| gpu.snapshot()).unwrap_or_default();
+        snapshot
+    }
+}
+
+/// Releases a worker slot on drop.
+pub struct OccupancyGuard {
+    state: Arc<Mutex<State>>,
+    worker: Option<usize>,
+    request_id: String,
+}
+
+impl Drop for OccupancyGuard {
+    fn drop(&mut self) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        match self.worker {
+            Some(slot) => state.workers[slot] = None,
+            None => {
+                if let Some(i) = state.overflow.iter().position(|r| r.request_id == self.request_id) {
+                    state.overflow.swap_remove(i);
+                }
+            }
+        }
+        record(&state);
+    }
+}
+
+/// Point-in-time scheduler occupancy.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OccupancySnapshot {
+    pub workers: usize,
+    pub busy_workers: usize,
+    pub in_flight: usize,
+    /// Models with requests running, i.e. batches being decoded.
+    pub active_batches: usize,
+    /// Mean requests per active batch.
+    pub avg_batch_size: f64,
+    pub batches: Vec<BatchComposition>,
+    /// Current request per worker slot.
+    pub worker_slots: Vec<WorkerSlot>,
+    /// GPU use per model sharing the GPU; empty when sharing is off.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub gpu: Vec<GpuModelOccupancy>,
+}
+
+/// Requests running together on one model.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchComposition {
+    pub model_id: String,
+    pub requests: usize,
+}
+
+/// One worker slot and the request it is running, if any.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkerSlot {
+    pub worker: usize,
+    pub request_id: Option<String>,
+    pub model_id: Option<String>,
+    pub busy_ms: u64,
+}
+
+fn snapshot(state: &State) -> OccupancySnapshot {
+    let mut batches: BTreeMap<&str, usize> = BTreeMap::new();
+    for running in state.running() {
+        *batches.entry(running.model_id.as_str()).or_insert(0) += 1;
+    }
+    let in_flight = state.workers.iter().flatten().count() + state.overflow.len();
+    let active_batches = batches.len();
+    OccupancySnapshot {
+        workers: state.workers.len(),
+        busy_workers: state.workers.iter().flatten().count(),
+        in_flight,
+        active_batches,
+        avg_batch_size: if active_batches > 0 { in_flight as f64 / active_batches as f64 } else { 0.0 },
+        batches: batches
+            .into_iter()
+            .map(|(model_id, requests)| BatchComposition { model_id: model_id.to_string(), requests })
+            .collect(),
+        worker_slots: state
+            .workers
+            .iter()
+            .enumerate()
+            .map(|(worker, running)| WorkerSlot {
+                worker,
+                request_id: running.as_ref().map(|r| r.request_id.clone()),
+                model_id: running.as_ref().map(|r| r.model_id.clone()),
+                busy_ms: running.as_ref().map_or(0, |r| r.started.elapsed().as_millis() as u64),
+            })
+            .collect(),
+        gpu: Vec::new(),
+    }
+}
+
+fn record(state: &State) {
+    let snapshot = snapshot(state);
+    telemetry::record_scheduler_occupancy(
+        snapshot.busy_workers,
+        snapshot.in_flight,
+        snapshot.active_batches,
+        snapshot.avg_batch_size,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tracks_workers_and_batches() {
+        let occupancy = Occupancy::new(2);
+        let a = occupancy.begin("1", "llama");
+        let b = occupancy.begin("2", "llama");
+        let c = occupancy.begin("3", "phi");
+
+        let snap = occupancy.snapshot();
+        assert_eq!((snap.workers, snap.busy_workers, snap.in_flight), (2, 2, 3));
+        assert_eq!(snap.active_batches, 2);
+        assert_eq!(snap.avg_batch_size, 1.5);
+        assert_eq!(snap.batches[0], BatchComposition { model_id: "llama".into(), requests: 2 });
+        assert_eq!(snap.worker_slots[1].request_id.as_deref(), Some("2"));
+
+        drop(a);
+        let snap = occupancy.snapshot();
+        assert_eq!(snap.worker_slots[0].request_id, None);
+        assert_eq!(snap.in_flight, 2);
+
+        // A freed slot is reused
+        let d = occupancy.begin("4", "phi");
+        assert_eq!(occupancy.snapshot().worker_slots[0].request_id.as_deref(), Some("4"));
+        drop((b, c, d));
+        let snap = occupancy.snapshot();
+        assert_eq!((snap.in_flight, snap.active_batches, snap.avg_batch_size), (0, 0, 0.0));
+    }
+}
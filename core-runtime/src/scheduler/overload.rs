@@ -0,0 +1,372 @@
+//! Graceful degradation ladder under overload.
+//!
+//! As queue depth or latency grows, the controller steps through
+//! progressively harsher levels. Each level keeps the behavior of the
+//! levels below it, so the system degrades predictably:
+//!
+//! 1. `NoSpeculative` - speculative decoding disabled
+//! 2. `ReducedTokens` - `max_tokens` lowered; responses report the cap
+//! 3. `RejectLowPriority` - `Priority::Low` traffic rejected
+//! 4. `ShedByQuota` - tenants over their in-flight quota are shed
+//!
+//! Levels are raised as soon as a threshold is reached, but only lowered
+//! once load falls a margin below it, so load hovering at a threshold does
+//! not flip the level on every sample.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::priority::Priority;
+use crate::engine::InferenceParams;
+use crate::features::{Feature, FeatureFlags};
+
+/// Degradation level, ordered from healthy to most aggressive shedding.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DegradationLevel {
+    #[default]
+    Normal = 0,
+    NoSpeculative = 1,
+    ReducedTokens = 2,
+    RejectLowPriority = 3,
+    ShedByQuota = 4,
+}
+
+impl DegradationLevel {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            0 => Self::Normal,
+            1 => Self::NoSpeculative,
+            2 => Self::ReducedTokens,
+            3 => Self::RejectLowPriority,
+            _ => Self::ShedByQuota,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Normal => "normal",
+            Self::NoSpeculative => "no_speculative",
+            Self::ReducedTokens => "reduced_tokens",
+            Self::RejectLowPriority => "reject_low_priority",
+            Self::ShedByQuota => "shed_by_quota",
+        }
+    }
+}
+
+/// Thresholds for each ladder step. Index 0 enters `NoSpeculative`,
+/// index 3 enters `ShedByQuota`. A step is entered when EITHER the queue
+/// depth or the smoothed latency reaches its threshold.
+#[derive(Debug, Clone)]
+pub struct OverloadConfig {
+    pub queue_depth_steps: [usize; 4],
+    pub latency_ms_steps: [u64; 4],
+    /// `max_tokens` ceiling applied from `ReducedTokens` upward.
+    pub reduced_max_tokens: usize,
+    /// Maximum in-flight requests per tenant once shedding by quota.
+    pub tenant_quota: usize,
+    /// EWMA smoothing factor for latency samples (0.0-1.0].
+    pub latency_alpha: f64,
+    /// Fraction below a step's thresholds that both signals must fall to
+    /// before the ladder steps back down past it (0.0-1.0).
+    pub recovery_margin: f64,
+}
+
+impl Default for OverloadConfig {
+    fn default() -> Self {
+        Self {
+            queue_depth_steps: [64, 128, 192, 240],
+            latency_ms_steps: [2_000, 5_000, 10_000, 20_000],
+            reduced_max_tokens: 128,
+            tenant_quota: 4,
+            latency_alpha: 0.2,
+            recovery_margin: 0.1,
+        }
+    }
+}
+
+/// Reason a request was refused by the overload controller.
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum OverloadRejection {
+    #[error("Overloaded ({level}): low-priority requests are rejected")]
+    LowPriority { level: &'static str },
+
+    #[error("Overloaded ({level}): tenant quota of {quota} in-flight requests exceeded")]
+    TenantQuota { level: &'static str, quota: usize },
+}
+
+impl OverloadRejection {
+    /// Label for rejection metrics.
+    pub fn reason(&self) -> &'static str {
+        match self {
+            Self::LowPriority { .. } => "low_priority",
+            Self::TenantQuota { .. } => "tenant_quota",
+        }
+    }
+}
+
+/// Tracks load signals and decides the current degradation level.
+pub struct OverloadController {
+    config: OverloadConfig,
+    level: AtomicU8,
+    latency_ewma: AtomicU64, // f64 bits stored as u64
+    tenants: Arc<Mutex<HashMap<String, usize>>>,
+    features: Option<Arc<FeatureFlags>>,
+}
+
+impl OverloadController {
+    pub fn new(config: OverloadConfig) -> Self {
+        Self {
+            config,
+            level: AtomicU8::new(DegradationLevel::Normal as u8),
+            latency_ewma: AtomicU64::new(f64::to_bits(0.0)),
+            tenants: Arc::new(Mutex::new(HashMap::new())),
+            features: None,
+        }
+    }
+
+    /// Also require the `speculative_decoding` flag for speculative decoding.
+    pub fn with_features(mut self, features: Arc<FeatureFlags>) -> Self {
+        self.features = Some(features);
+        self
+    }
+
+    /// Current degradation level.
+    pub fn level(&self) -> DegradationLevel {
+        DegradationLevel::from_u8(self.level.load(Ordering::Relaxed))
+    }
+
+    /// Smoothed request latency in milliseconds.
+    pub fn smoothed_latency_ms(&self) -> f64 {
+        f64::from_bits(self.latency_ewma.load(Ordering::Relaxed))
+    }
+
+    /// Feed a completed request latency into the smoothed estimate.
+    pub fn record_latency(&self, latency_ms: u64) {
+        let alpha = self.config.latency_alpha.clamp(f64::EPSILON, 1.0);
+        let _ = self
+            .latency_ewma
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |bits| {
+                let current = f64::from_bits(bits);
+                let next = if current == 0.0 {
+                    latency_ms as f64
+                } else {
+                    alpha * latency_ms as f64 + (1.0 - alpha) * current
+                };
+                Some(f64::to_bits(next))
+            });
+    }
+
+    /// Highest step whose thresholds, scaled by `scale`, the signals reach.
+    fn step_for(&self, queue_depth: usize, latency: f64, scale: f64) -> u8 {
+        (0..4)
+            .rev()
+            .find(|&i| {
+                queue_depth as f64 >= self.config.queue_depth_steps[i] as f64 * scale
+                    || latency >= self.config.latency_ms_steps[i] as f64 * scale
+            })
+            .map_or(0, |i| i as u8 + 1)
+    }
+
+    /// Re-evaluate the ladder from the current queue depth. Steps up at a
+    /// threshold, but only steps down once load is `recovery_margin` below
+    /// it. Logs transitions.
+    pub fn evaluate(&self, queue_depth: usize) -> DegradationLevel {
+        let latency = self.smoothed_latency_ms();
+        let current = self.level.load(Ordering::Relaxed);
+        let mut step = self.step_for(queue_depth, latency, 1.0);
+        if step < current {
+            let margin = self.config.recovery_margin.clamp(0.0, 1.0);
+            step = self.step_for(queue_depth, latency, 1.0 - margin).min(current);
+        }
+
+        let previous = DegradationLevel::from_u8(self.level.swap(step, Ordering::Relaxed));
+        let next = DegradationLevel::from_u8(step);
+        if next > previous {
+            tracing::warn!(
+                from = previous.as_str(),
+                to = next.as_str(),
+                queue_depth,
+                latency_ms = latency,
+                "Overload: degradation level raised"
+            );
+        } else if next < previous {
+            tracing::info!(
+                from = previous.as_str(),
+                to = next.as_str(),
+                queue_depth,
+                latency_ms = latency,
+                "Overload: degradation level lowered"
+            );
+        }
+        next
+    }
+
+    /// Whether speculative decoding may be used at the current level.
+    pub fn speculative_allowed(&self) -> bool {
+        let enabled = self.features.as_ref().is_none_or(|f| f.enabled(Feature::SpeculativeDecoding));
+        enabled && self.level() < DegradationLevel::NoSpeculative
+    }
+
+    /// Clamp request parameters to the current level's limits. Returns the
+    /// `max_tokens` ceiling when it lowered the request, so the response
+    /// can tell the caller its output may have been cut there.
+    pub fn apply_param_caps(&self, params: &mut InferenceParams) -> Option<usize> {
+        let cap = self.config.reduced_max_tokens;
+        if self.level() < DegradationLevel::ReducedTokens || params.max_tokens <= cap {
+            return None;
+        }
+        params.max_tokens = cap;
+        Some(cap)
+    }
+
+    /// Admit a request at the current level. The returned guard holds the
+    /// tenant's in-flight slot until dropped.
+    pub fn try_admit(
+        &self,
+        priority: Priority,
+        tenant: Option<&str>,
+    ) -> Result<AdmissionGuard, OverloadRejection> {
+        let level = self.level();
+        if level >= DegradationLevel::RejectLowPriority && priority == Priority::Low {
+            return Err(OverloadRejection::LowPriority { level: level.as_str() });
+        }
+
+        let tenant = tenant.map(str::to_string);
+        if let Some(ref key) = tenant {
+            let mut tenants = self.tenants.lock().unwrap_or_else(|e| e.into_inner());
+            let in_flight = tenants.entry(key.clone()).or_insert(0);
+            if level >= DegradationLevel::ShedByQuota && *in_flight >= self.config.tenant_quota {
+                return Err(OverloadRejection::TenantQuota {
+                    level: level.as_str(),
+                    quota: self.config.tenant_quota,
+                });
+            }
+            *in_flight += 1;
+        }
+
+        Ok(AdmissionGuard {
+            tenant,
+            tenants: Arc::clone(&self.tenants),
+        })
+    }
+}
+
+impl Default for OverloadController {
+    fn default() -> Self {
+        Self::new(OverloadConfig::default())
+    }
+}
+
+/// RAII guard releasing a tenant's in-flight slot on drop.
+pub struct AdmissionGuard {
+    tenant: Option<String>,
+    tenants: Arc<Mutex<HashMap<String, usize>>>,
+}
+
+impl Drop for AdmissionGuard {
+    fn drop(&mut self) {
+        let Some(ref key) = self.tenant else { return };
+        let mut tenants = self.tenants.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(count) = tenants.get_mut(key) {
+            *count = count.saturating_sub(1);
+            if *count == 0 {
+                tenants.remove(key);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn controller() -> OverloadController {
+        OverloadController::new(OverloadConfig {
+            queue_depth_steps: [10, 20, 30, 40],
+            latency_ms_steps: [1_000, 2_000, 3_000, 4_000],
+            reduced_max_tokens: 32,
+            tenant_quota: 1,
+            latency_alpha: 1.0,
+            recovery_margin: 0.2,
+        })
+    }
+
+    #[test]
+    fn test_ladder_follows_queue_depth() {
+        let c = controller();
+        assert_eq!(c.evaluate(0), DegradationLevel::Normal);
+        assert_eq!(c.evaluate(10), DegradationLevel::NoSpeculative);
+        assert_eq!(c.evaluate(25), DegradationLevel::ReducedTokens);
+        assert_eq!(c.evaluate(35), DegradationLevel::RejectLowPriority);
+        assert_eq!(c.evaluate(100), DegradationLevel::ShedByQuota);
+        assert_eq!(c.evaluate(0), DegradationLevel::Normal);
+    }
+
+    #[test]
+    fn test_ladder_steps_down_only_past_margin() {
+        let c = controller();
+        assert_eq!(c.evaluate(20), DegradationLevel::ReducedTokens);
+        // Hovering just under the threshold holds the level
+        for depth in [19, 20, 17, 16] {
+            assert_eq!(c.evaluate(depth), DegradationLevel::ReducedTokens);
+        }
+        // 20% below the step enters the level below, not lower
+        assert_eq!(c.evaluate(15), DegradationLevel::NoSpeculative);
+        assert_eq!(c.evaluate(9), DegradationLevel::NoSpeculative);
+        assert_eq!(c.evaluate(7), DegradationLevel::Normal);
+        // Stepping up needs no margin
+        assert_eq!(c.evaluate(10), DegradationLevel::NoSpeculative);
+    }
+
+    #[test]
+    fn test_ladder_follows_latency() {
+        let c = controller();
+        c.record_latency(2_500);
+        assert_eq!(c.evaluate(0), DegradationLevel::ReducedTokens);
+        assert!(!c.speculative_allowed());
+    }
+
+    #[test]
+    fn test_param_caps_only_when_reduced() {
+        let c = controller();
+        let mut params = InferenceParams { max_tokens: 256, ..Default::default() };
+        assert_eq!(c.apply_param_caps(&mut params), None);
+        assert_eq!(params.max_tokens, 256);
+
+        c.evaluate(20);
+        assert_eq!(c.apply_param_caps(&mut params), Some(32));
+        assert_eq!(params.max_tokens, 32);
+        // Already within the cap: nothing to report
+        assert_eq!(c.apply_param_caps(&mut params), None);
+    }
+
+    #[test]
+    fn test_low_priority_rejected() {
+        let c = controller();
+        c.evaluate(30);
+        assert!(matches!(
+            c.try_admit(Priority::Low, None),
+            Err(OverloadRejection::LowPriority { .. })
+        ));
+        assert!(c.try_admit(Priority::Normal, None).is_ok());
+    }
+
+    #[test]
+    fn test_tenant_quota_shedding_and_release() {
+        let c = controller();
+        c.evaluate(40);
+        let guard = c.try_admit(Priority::Normal, Some("tenant-a")).unwrap();
+        assert!(matches!(
+            c.try_admit(Priority::Normal, Some("tenant-a")),
+            Err(OverloadRejection::TenantQuota { quota: 1, .. })
+        ));
+        assert!(c.try_admit(Priority::Normal, Some("tenant-b")).is_ok());
+        drop(guard);
+        assert!(c.try_admit(Priority::Normal, Some("tenant-a")).is_ok());
+    }
+}
@@ -3,8 +3,11 @@
 use std::cmp::Ordering;
 use std::collections::BinaryHeap;
 
+use serde::{Deserialize, Serialize};
+
 /// Priority level for inference requests.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Priority {
     Low = 0,
     Normal = 1,
@@ -18,6 +21,21 @@ impl Default for Priority {
     }
 }
 
+impl Priority {
+    /// All levels, lowest first.
+    pub const ALL: [Priority; 4] = [Self::Low, Self::Normal, Self::High, Self::Critical];
+
+    /// Lowercase label used in metrics and logs.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Normal => "normal",
+            Self::High => "high",
+            Self::Critical => "critical",
+        }
+    }
+}
+
 impl From<u8> for Priority {
     fn from(value: u8) -> Self {
         match value {
@@ -100,6 +118,15 @@ impl<T> PriorityQueue<T> {
     pub fn iter(&self) -> impl Iterator<Item = &T> {
         self.heap.iter().map(|p| &p.item)
     }
+
+    /// Remove and return the items matching `pred`. The rest keep their
+    /// priority and order.
+    pub fn remove_where(&mut self, mut pred: impl FnMut(&T) -> bool) -> Vec<T> {
+        let (removed, kept): (Vec<_>, Vec<_>) =
+            std::mem::take(&mut self.heap).into_vec().into_iter().partition(|p| pred(&p.item));
+        self.heap = kept.into();
+        removed.into_iter().map(|p| p.item).collect()
+    }
 }
 
 impl<T> Default for PriorityQueue<T> {
@@ -1,12 +1,22 @@
 //! Request queue management.
+//!
+//! When the model pool switches away from a model, requests still queued
+//! for it follow the queue's [`SwitchPolicy`]: they either stay queued
+//! with their priority and position, reported as stranded in the
+//! per-model ages, or fail with `ModelDeactivated` together with new
+//! requests for the model until it is active again.
 
+use std::collections::{BTreeMap, HashSet};
+use std::str::FromStr;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
+use serde::{Deserialize, Serialize};
 use tokio::sync::Mutex;
 
 use super::priority::{Priority, PriorityQueue};
 use crate::engine::InferenceParams;
+use crate::telemetry;
 
 /// Configuration for request queue.
 #[derive(Debug, Clone)]
@@ -20,6 +30,51 @@ impl Default for RequestQueueConfig {
     }
 }
 
+/// What happens to queued requests for a model the pool switched away from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SwitchPolicy {
+    /// Keep them queued, reported as stranded until the model is active
+    /// again.
+    #[default]
+    Keep,
+    /// Fail them, and new requests for the model until it is active
+    /// again, with `ModelDeactivated`.
+    FailFast,
+}
+
+impl SwitchPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Keep => "keep",
+            Self::FailFast => "fail-fast",
+        }
+    }
+}
+
+impl FromStr for SwitchPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "keep" => Ok(Self::Keep),
+            "fail-fast" | "fail_fast" | "fail" => Ok(Self::FailFast),
+            other => Err(format!("unknown queue switch policy '{}' (keep, fail-fast)", other)),
+        }
+    }
+}
+
+/// Pending requests for one model.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelQueueAge {
+    pub model_id: String,
+    pub pending: usize,
+    /// Wait of the oldest pending request.
+    pub oldest_age_ms: u64,
+    /// The pool switched away from the model; these wait for it to be
+    /// active again.
+    pub stranded: bool,
+}
+
 /// A queued inference request with timeout and cancellation support.
 #[derive(Debug)]
 pub struct QueuedRequest {
@@ -28,9 +83,11 @@ pub struct QueuedRequest {
     /// Text prompt for inference.
     pub prompt: String,
     pub params: InferenceParams,
+    pub priority: Priority,
     pub enqueued_at: Instant,
     pub deadline: Option<Instant>,
     cancelled: Arc<AtomicBool>,
+    deactivated: Arc<AtomicBool>,
 }
 
 impl Clone for QueuedRequest {
@@ -40,9 +97,11 @@ impl Clone for QueuedRequest {
             model_id: self.model_id.clone(),
             prompt: self.prompt.clone(),
             params: self.params.clone(),
+            priority: self.priority,
             enqueued_at: self.enqueued_at,
             deadline: self.deadline,
             cancelled: Arc::clone(&self.cancelled),
+            deactivated: Arc::clone(&self.deactivated),
         }
     }
 }
@@ -62,9 +121,11 @@ impl QueuedRequest {
             model_id,
             prompt,
             params,
+            priority: Priority::Normal,
             enqueued_at,
             deadline,
             cancelled: Arc::new(AtomicBool::new(false)),
+            deactivated: Arc::new(AtomicBool::new(false)),
         }
     }
 
@@ -82,6 +143,12 @@ impl QueuedRequest {
     pub fn cancel(&self) {
         self.cancelled.store(true, Ordering::Relaxed);
     }
+
+    /// Whether the request failed because the pool switched away from its
+    /// model.
+    pub fn is_deactivated(&self) -> bool {
+        self.deactivated.load(Ordering::Relaxed)
+    }
 }
 
 /// Thread-safe request queue with priority support.
@@ -89,6 +156,11 @@ pub struct RequestQueue {
     queue: Arc<Mutex<PriorityQueue<QueuedRequest>>>,
     next_id: AtomicU64,
     config: RequestQueueConfig,
+    switch_policy: SwitchPolicy,
+    /// Models switched away from and not active since.
+    inactive: std::sync::Mutex<HashSet<String>>,
+    /// Models with a published queue age, to zero once they drain.
+    reported: std::sync::Mutex<HashSet<String>>,
 }
 
 impl RequestQueue {
@@ -97,9 +169,26 @@ impl RequestQueue {
             queue: Arc::new(Mutex::new(PriorityQueue::new())),
             next_id: AtomicU64::new(1),
             config,
+            switch_policy: SwitchPolicy::default(),
+            inactive: std::sync::Mutex::new(HashSet::new()),
+            reported: std::sync::Mutex::new(HashSet::new()),
         }
     }
 
+    /// Handle requests for models the pool switches away from per `policy`.
+    pub fn with_switch_policy(mut self, policy: SwitchPolicy) -> Self {
+        self.switch_policy = policy;
+        self
+    }
+
+    pub fn switch_policy(&self) -> SwitchPolicy {
+        self.switch_policy
+    }
+
+    fn inactive(&self) -> std::sync::MutexGuard<'_, HashSet<String>> {
+        self.inactive.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
     /// Enqueue a new request. Returns request ID and queue position.
     pub async fn enqueue(
         &self,
@@ -111,8 +200,13 @@ impl RequestQueue {
         let mut queue = self.queue.lock().await;
 
         if queue.len() >= self.config.max_pending {
+            telemetry::record_queue_rejection(priority.as_str(), "queue_full");
             return Err(QueueError::QueueFull);
         }
+        if self.switch_policy == SwitchPolicy::FailFast && self.inactive().contains(&model_id) {
+            telemetry::record_queue_rejection(priority.as_str(), "model_deactivated");
+            return Err(QueueError::ModelDeactivated(model_id));
+        }
 
         let id = self.next_id.fetch_add(1, Ordering::SeqCst);
         let enqueued_at = Instant::now();
@@ -122,12 +216,15 @@ impl RequestQueue {
             model_id,
             prompt,
             params,
+            priority,
             enqueued_at,
             deadline,
             cancelled: Arc::new(AtomicBool::new(false)),
+            deactivated: Arc::new(AtomicBool::new(false)),
         };
         let position = queue.len();
         queue.push(request, priority);
+        self.record_depths(&queue);
 
         Ok((id, position))
     }
@@ -147,13 +244,75 @@ impl RequestQueue {
     /// Dequeue the highest priority request, skipping cancelled/expired.
     pub async fn dequeue(&self) -> Option<QueuedRequest> {
         let mut queue = self.queue.lock().await;
-        loop {
-            let request = queue.pop()?;
-            if request.is_cancelled() || request.is_expired() {
-                continue; // Skip cancelled/expired requests
+        let result = loop {
+            let Some(request) = queue.pop() else { break None };
+            if request.is_cancelled() {
+                continue;
+            }
+            if request.is_expired() {
+                telemetry::record_queue_rejection(request.priority.as_str(), "expired");
+                continue;
+            }
+            let waited = request.enqueued_at.elapsed();
+            telemetry::record_queue_wait(request.priority.as_str(), waited.as_secs_f64() * 1000.0);
+            break Some(request);
+        };
+        self.record_depths(&queue);
+        result
+    }
+
+    /// The pool switched away from `model_id`. Under
+    /// [`SwitchPolicy::FailFast`] its queued requests are removed, marked
+    /// deactivated and returned so their callers can be answered; under
+    /// [`SwitchPolicy::Keep`] they stay and nothing is returned.
+    pub async fn model_deactivated(&self, model_id: &str) -> Vec<QueuedRequest> {
+        self.inactive().insert(model_id.to_string());
+        let mut queue = self.queue.lock().await;
+        if self.switch_policy == SwitchPolicy::Keep {
+            let pending = queue.iter().filter(|r| r.model_id == model_id && !r.is_cancelled()).count();
+            if pending > 0 {
+                tracing::info!(model = model_id, pending, "Requests stay queued for deactivated model");
             }
-            return Some(request);
+            self.record_depths(&queue);
+            return Vec::new();
+        }
+
+        let failed: Vec<QueuedRequest> = queue
+            .remove_where(|r| r.model_id == model_id)
+            .into_iter()
+            .filter(|r| !r.is_cancelled())
+            .collect();
+        for request in &failed {
+            request.deactivated.store(true, Ordering::Relaxed);
+            telemetry::record_queue_rejection(request.priority.as_str(), "model_deactivated");
+        }
+        if !failed.is_empty() {
+            tracing::warn!(model = model_id, failed = failed.len(), "Failed queued requests for deactivated model");
         }
+        self.record_depths(&queue);
+        failed
+    }
+
+    /// The pool made `model_id` active; its requests are served again.
+    pub async fn model_activated(&self, model_id: &str) {
+        if self.inactive().remove(model_id) {
+            self.record_depths(&*self.queue.lock().await);
+        }
+    }
+
+    /// Pending requests and oldest wait per model, sorted by model.
+    pub async fn model_ages(&self) -> Vec<ModelQueueAge> {
+        model_ages(&*self.queue.lock().await, &self.inactive())
+    }
+
+    /// Pending request count per priority level, indexed by `Priority as usize`.
+    pub async fn depth_by_priority(&self) -> [usize; 4] {
+        count_by_priority(&*self.queue.lock().await)
+    }
+
+    /// How long the oldest pending request has been waiting.
+    pub async fn oldest_age(&self) -> Option<Duration> {
+        oldest_enqueued(&*self.queue.lock().await).map(|t| t.elapsed())
     }
 
     /// Current queue length.
@@ -167,15 +326,72 @@ impl RequestQueue {
     }
 }
 
+fn count_by_priority(queue: &PriorityQueue<QueuedRequest>) -> [usize; 4] {
+    let mut depths = [0usize; 4];
+    for request in queue.iter() {
+        depths[request.priority as usize] += 1;
+    }
+    depths
+}
+
+fn oldest_enqueued(queue: &PriorityQueue<QueuedRequest>) -> Option<Instant> {
+    queue.iter().map(|r| r.enqueued_at).min()
+}
+
+fn model_ages(queue: &PriorityQueue<QueuedRequest>, inactive: &HashSet<String>) -> Vec<ModelQueueAge> {
+    let mut oldest: BTreeMap<&str, (usize, Instant)> = BTreeMap::new();
+    for request in queue.iter().filter(|r| !r.is_cancelled()) {
+        let entry = oldest.entry(request.model_id.as_str()).or_insert((0, request.enqueued_at));
+        entry.0 += 1;
+        entry.1 = entry.1.min(request.enqueued_at);
+    }
+    oldest
+        .into_iter()
+        .map(|(model_id, (pending, enqueued_at))| ModelQueueAge {
+            model_id: model_id.to_string(),
+            pending,
+            oldest_age_ms: enqueued_at.elapsed().as_millis() as u64,
+            stranded: inactive.contains(model_id),
+        })
+        .collect()
+}
+
+impl RequestQueue {
+    fn record_depths(&self, queue: &PriorityQueue<QueuedRequest>) {
+        let depths = count_by_priority(queue);
+        telemetry::record_queue_depth(queue.len());
+        let oldest_ms = oldest_enqueued(queue).map_or(0.0, |t| t.elapsed().as_secs_f64() * 1000.0);
+        telemetry::record_queue_oldest_age(oldest_ms);
+        for priority in Priority::ALL {
+            telemetry::record_priority_queue_depth(priority.as_str(), depths[priority as usize]);
+        }
+
+        let ages = model_ages(queue, &self.inactive());
+        let mut reported = self.reported.lock().unwrap_or_else(|e| e.into_inner());
+        for model_id in reported.iter().filter(|m| !ages.iter().any(|a| &a.model_id == *m)) {
+            telemetry::record_model_queue_age(model_id, 0, 0.0, false);
+        }
+        reported.clear();
+        for age in &ages {
+            telemetry::record_model_queue_age(&age.model_id, age.pending, age.oldest_age_ms as f64, age.stranded);
+            reported.insert(age.model_id.clone());
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum QueueError {
     QueueFull,
+    /// The pool switched away from the model and the queue fails its
+    /// requests.
+    ModelDeactivated(String),
 }
 
 impl std::fmt::Display for QueueError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::QueueFull => write!(f, "request queue is full"),
+            Self::ModelDeactivated(model) => write!(f, "model {} is no longer active", model),
         }
     }
 }
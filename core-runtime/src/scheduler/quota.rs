@@ -0,0 +1,330 @@
+//! Tenant rate limits and idempotency keys, optionally shared across replicas.
+//!
+//! Each tenant may start `tenant_requests_per_window` requests per fixed
+//! window, and an idempotency key is accepted once per `idempotency_ttl`.
+//! Counters and keys live in a `QuotaBackend`. Locally that is process
+//! memory, so with N replicas a tenant effectively gets N times its limit;
+//! a remote backend (Redis or compatible, `redis-quota` feature) holds one
+//! count for the whole fleet.
+//!
+//! If the remote backend fails or times out, the store falls back to local
+//! counters for `retry_after` and then tries the backend again. Requests
+//! keep flowing; only the fleet-wide guarantee is lost while degraded.
+//!
+//! A tenant is the identity its session authenticated as (a name from
+//! `CORE_TENANT_TOKENS`, else the session role), so limits hold across
+//! reconnects. Keys carry the tenant label, never the identity itself.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use thiserror::Error;
+
+use crate::telemetry::tenant_label;
+
+/// Quota and idempotency settings.
+#[derive(Debug, Clone)]
+pub struct QuotaConfig {
+    /// Requests per tenant per window; `None` disables rate limiting.
+    pub tenant_requests_per_window: Option<u64>,
+    pub window: Duration,
+    /// How long an accepted idempotency key blocks repeats.
+    pub idempotency_ttl: Duration,
+    /// `redis://[:password@]host:port[/db]`; `None` keeps quotas local.
+    pub backend_url: Option<String>,
+    /// Per-operation deadline for the remote backend.
+    pub backend_timeout: Duration,
+    /// How long to stay local after a backend failure.
+    pub retry_after: Duration,
+    /// Prefix for every backend key, so deployments can share a server.
+    pub key_prefix: String,
+}
+
+impl Default for QuotaConfig {
+    fn default() -> Self {
+        Self {
+            tenant_requests_per_window: None,
+            window: Duration::from_secs(60),
+            idempotency_ttl: Duration::from_secs(24 * 60 * 60),
+            backend_url: None,
+            backend_timeout: Duration::from_millis(50),
+            retry_after: Duration::from_secs(30),
+            key_prefix: "gg-core:".into(),
+        }
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum QuotaError {
+    #[error("Quota backend error: {0}")]
+    Backend(String),
+
+    #[error("Quota backend timed out")]
+    Timeout,
+
+    #[error("Invalid quota backend URL: {0}")]
+    InvalidUrl(String),
+}
+
+/// Reason a request was refused by the quota store.
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum QuotaRejection {
+    #[error("Tenant rate limit of {limit} requests per {window_secs}s exceeded")]
+    RateLimited { limit: u64, window_secs: u64 },
+
+    #[error("Duplicate idempotency key")]
+    DuplicateKey,
+}
+
+impl QuotaRejection {
+    /// Label for rejection metrics.
+    pub fn reason(&self) -> &'static str {
+        match self {
+            Self::RateLimited { .. } => "tenant_rate",
+            Self::DuplicateKey => "duplicate_idempotency_key",
+        }
+    }
+}
+
+/// Storage for window counters and idempotency claims.
+#[async_trait::async_trait]
+pub trait QuotaBackend: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Increment `key`, creating it with a lifetime of `ttl`. Returns the new count.
+    async fn increment(&self, key: &str, ttl: Duration) -> Result<u64, QuotaError>;
+
+    /// Claim `key` for `ttl`. Returns false if it is already claimed.
+    async fn claim(&self, key: &str, ttl: Duration) -> Result<bool, QuotaError>;
+}
+
+/// In-process backend; also the fallback for remote backends.
+#[derive(Default)]
+pub struct LocalQuota {
+    /// Key -> (count, expires).
+    entries: Mutex<HashMap<String, (u64, Instant)>>,
+}
+
+impl LocalQuota {
+    fn update<T>(&self, key: &str, ttl: Duration, f: impl FnOnce(&mut u64) -> T) -> T {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        entries.retain(|_, (_, expires)| *expires > now);
+        let (count, _) = entries.entry(key.to_string()).or_insert((0, now + ttl));
+        f(count)
+    }
+}
+
+#[async_trait::async_trait]
+impl QuotaBackend for LocalQuota {
+    fn name(&self) -> &'static str {
+        "local"
+    }
+
+    async fn increment(&self, key: &str, ttl: Duration) -> Result<u64, QuotaError> {
+        Ok(self.update(key, ttl, |count| {
+            *count += 1;
+            *count
+        }))
+    }
+
+    async fn claim(&self, key: &str, ttl: Duration) -> Result<bool, QuotaError> {
+        Ok(self.update(key, ttl, |count| {
+            *count += 1;
+            *count == 1
+        }))
+    }
+}
+
+/// Admission checks for tenant rate limits and idempotency keys.
+pub struct QuotaStore {
+    config: QuotaConfig,
+    local: LocalQuota,
+    remote: Option<Arc<dyn QuotaBackend>>,
+    /// Set while the remote backend is bypassed after a failure.
+    degraded_until: Mutex<Option<Instant>>,
+}
+
+impl QuotaStore {
+    /// Store for `config`. An unusable backend URL is logged and the store
+    /// stays local.
+    pub fn new(config: QuotaConfig) -> Self {
+        let remote = config.backend_url.as_deref().and_then(|url| match remote_backend(url, &config) {
+            Ok(backend) => Some(backend),
+            Err(e) => {
+                tracing::warn!(error = %e, "Quota backend disabled; limits are per replica");
+                None
+            }
+        });
+        Self::with_remote(config, remote)
+    }
+
+    /// Store backed by an explicit remote backend.
+    pub fn with_remote(config: QuotaConfig, remote: Option<Arc<dyn QuotaBackend>>) -> Self {
+        Self { config, local: LocalQuota::default(), remote, degraded_until: Mutex::new(None) }
+    }
+
+    /// Whether checks are currently local because the remote backend failed.
+    pub fn is_degraded(&self) -> bool {
+        let degraded = self.degraded_until.lock().unwrap_or_else(|e| e.into_inner());
+        degraded.is_some_and(|until| Instant::now() < until)
+    }
+
+    /// Name of the backend answering checks right now.
+    pub fn backend_name(&self) -> &'static str {
+        match &self.remote {
+            Some(remote) if !self.is_degraded() => remote.name(),
+            _ => self.local.name(),
+        }
+    }
+
+    /// Count a request against the tenant's window, then claim its
+    /// idempotency key. A key is spent once the rate check passes.
+    pub async fn admit(&self, tenant: Option<&str>, idempotency_key: Option<&str>) -> Result<(), QuotaRejection> {
+        let label = tenant.map(tenant_label);
+        let label = label.as_deref().unwrap_or("anonymous");
+
+        if let Some(limit) = self.config.tenant_requests_per_window {
+            let window_secs = self.config.window.as_secs().max(1);
+            let index = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() / window_secs;
+            let key = format!("{}rate:{}:{}", self.config.key_prefix, label, index);
+            if self.increment(&key, Duration::from_secs(window_secs)).await > limit {
+                return Err(QuotaRejection::RateLimited { limit, window_secs });
+            }
+        }
+
+        if let Some(idempotency_key) = idempotency_key {
+            let key = format!("{}idem:{}:{}", self.config.key_prefix, label, idempotency_key);
+            let ttl = self.config.idempotency_ttl;
+            if !self.claim(&key, ttl).await {
+                return Err(QuotaRejection::DuplicateKey);
+            }
+        }
+        Ok(())
+    }
+
+    async fn increment(&self, key: &str, ttl: Duration) -> u64 {
+        if let Some(remote) = self.healthy_remote() {
+            let result = tokio::time::timeout(self.config.backend_timeout, remote.increment(key, ttl)).await;
+            if let Some(count) = self.settle(result) {
+                return count;
+            }
+        }
+        // The local backend cannot fail
+        self.local.increment(key, ttl).await.unwrap_or_default()
+    }
+
+    async fn claim(&self, key: &str, ttl: Duration) -> bool {
+        if let Some(remote) = self.healthy_remote() {
+            let result = tokio::time::timeout(self.config.backend_timeout, remote.claim(key, ttl)).await;
+            if let Some(claimed) = self.settle(result) {
+                return claimed;
+            }
+        }
+        self.local.claim(key, ttl).await.unwrap_or(true)
+    }
+
+    fn healthy_remote(&self) -> Option<&dyn QuotaBackend> {
+        self.remote.as_deref().filter(|_| !self.is_degraded())
+    }
+
+    /// The remote result, or `None` after marking the backend degraded.
+    fn settle<T>(&self, result: Result<Result<T, QuotaError>, tokio::time::error::Elapsed>) -> Option<T> {
+        match result {
+            Ok(Ok(value)) => Some(value),
+            Ok(Err(e)) => {
+                self.degrade(&e);
+                None
+            }
+            Err(_) => {
+                self.degrade(&QuotaError::Timeout);
+                None
+            }
+        }
+    }
+
+    fn degrade(&self, error: &QuotaError) {
+        let mut degraded = self.degraded_until.lock().unwrap_or_else(|e| e.into_inner());
+        if degraded.is_none_or(|until| Instant::now() >= until) {
+            tracing::warn!(error = %error, retry_secs = self.config.retry_after.as_secs(), "Quota backend unreachable; using local limits");
+            metrics::counter!("core_quota_backend_fallbacks_total").increment(1);
+        }
+        *degraded = Some(Instant::now() + self.config.retry_after);
+    }
+}
+
+impl Default for QuotaStore {
+    fn default() -> Self {
+        Self::new(QuotaConfig::default())
+    }
+}
+
+#[cfg(feature = "redis-quota")]
+fn remote_backend(url: &str, config: &QuotaConfig) -> Result<Arc<dyn QuotaBackend>, QuotaError> {
+    let backend = super::redis_quota::RedisQuota::from_url(url, config.backend_timeout)?;
+    Ok(Arc::new(backend))
+}
+
+#[cfg(not(feature = "redis-quota"))]
+fn remote_backend(_url: &str, _config: &QuotaConfig) -> Result<Arc<dyn QuotaBackend>, QuotaError> {
+    Err(QuotaError::Backend("not compiled in. Enable 'redis-quota' feature.".into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct Failing(AtomicUsize);
+
+    #[async_trait::async_trait]
+    impl QuotaBackend for Failing {
+        fn name(&self) -> &'static str {
+            "failing"
+        }
+        async fn increment(&self, _key: &str, _ttl: Duration) -> Result<u64, QuotaError> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Err(QuotaError::Backend("connection refused".into()))
+        }
+        async fn claim(&self, _key: &str, _ttl: Duration) -> Result<bool, QuotaError> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Err(QuotaError::Backend("connection refused".into()))
+        }
+    }
+
+    fn limited(limit: u64) -> QuotaConfig {
+        QuotaConfig { tenant_requests_per_window: Some(limit), window: Duration::from_secs(3600), ..Default::default() }
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_per_tenant() {
+        let store = QuotaStore::new(limited(2));
+        assert!(store.admit(Some("a"), None).await.is_ok());
+        assert!(store.admit(Some("a"), None).await.is_ok());
+        assert!(matches!(store.admit(Some("a"), None).await, Err(QuotaRejection::RateLimited { limit: 2, .. })));
+        assert!(store.admit(Some("b"), None).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_idempotency_key_accepted_once_per_tenant() {
+        let store = QuotaStore::default();
+        assert!(store.admit(Some("a"), Some("k1")).await.is_ok());
+        assert_eq!(store.admit(Some("a"), Some("k1")).await, Err(QuotaRejection::DuplicateKey));
+        assert!(store.admit(Some("b"), Some("k1")).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_to_local_when_backend_fails() {
+        let remote = Arc::new(Failing(AtomicUsize::new(0)));
+        let store = QuotaStore::with_remote(limited(1), Some(remote.clone()));
+        assert_eq!(store.backend_name(), "failing");
+
+        assert!(store.admit(Some("a"), None).await.is_ok());
+        assert!(store.is_degraded());
+        assert_eq!(store.backend_name(), "local");
+        // Local counters enforce the limit; the backend is not retried yet
+        assert!(store.admit(Some("a"), None).await.is_err());
+        assert_eq!(remote.0.load(Ordering::SeqCst), 1);
+    }
+}
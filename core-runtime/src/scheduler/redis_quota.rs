@@ -0,0 +1,254 @@
+//! Redis-compatible quota backend over a minimal RESP2 client.
+//!
+//! Only the commands quota checks need are spoken: `AUTH`, `SELECT` and
+//! `SET ... PX ... NX` / `INCR`. One connection is kept and re-opened
+//! after any error. Every key is written with a TTL, so the server never
+//! accumulates state past one window or idempotency period.
+
+use std::time::Duration;
+
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio::sync::Mutex;
+
+use super::quota::{QuotaBackend, QuotaError};
+
+/// Longest bulk reply accepted; quota replies are tiny.
+const MAX_BULK_LEN: usize = 64 * 1024;
+
+/// A parsed RESP2 reply.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Reply {
+    Simple(String),
+    Integer(i64),
+    Bulk(Option<Vec<u8>>),
+}
+
+/// Redis (or compatible) quota backend.
+pub struct RedisQuota {
+    addr: String,
+    password: Option<String>,
+    db: u32,
+    connect_timeout: Duration,
+    conn: Mutex<Option<BufReader<TcpStream>>>,
+}
+
+impl RedisQuota {
+    /// Parse `redis://[:password@]host[:port][/db]`.
+    pub fn from_url(url: &str, connect_timeout: Duration) -> Result<Self, QuotaError> {
+        let rest = url
+            .strip_prefix("redis://")
+            .ok_or_else(|| QuotaError::InvalidUrl("expected redis://".into()))?;
+        let (auth, rest) = match rest.rsplit_once('@') {
+            Some((auth, rest)) => (Some(auth), rest),
+            None => (None, rest),
+        };
+        let password = auth
+            .map(|auth| auth.split_once(':').map_or(auth, |(_, password)| password).to_string())
+            .filter(|p| !p.is_empty());
+        let (host, db) = match rest.split_once('/') {
+            Some((host, "")) => (host, 0),
+            Some((host, db)) => (host, db.parse().map_err(|_| QuotaError::InvalidUrl(format!("bad db '{}'", db)))?),
+            None => (rest, 0),
+        };
+        if host.is_empty() {
+            return Err(QuotaError::InvalidUrl("missing host".into()));
+        }
+        let addr = if host.rsplit_once(':').is_some_and(|(_, port)| port.parse::<u16>().is_ok()) {
+            host.to_string()
+        } else {
+            format!("{}:6379", host)
+        };
+        Ok(Self { addr, password, db, connect_timeout, conn: Mutex::new(None) })
+    }
+
+    async fn connect(&self) -> Result<BufReader<TcpStream>, QuotaError> {
+        let stream = tokio::time::timeout(self.connect_timeout, TcpStream::connect(&self.addr))
+            .await
+            .map_err(|_| QuotaError::Timeout)?
+            .map_err(|e| QuotaError::Backend(e.to_string()))?;
+        let _ = stream.set_nodelay(true);
+        let mut conn = BufReader::new(stream);
+        if let Some(password) = &self.password {
+            let auth: [&[u8]; 2] = [b"AUTH", password.as_bytes()];
+            expect_ok(round_trip(&mut conn, &auth).await?)?;
+        }
+        if self.db != 0 {
+            let db = self.db.to_string();
+            let select: [&[u8]; 2] = [b"SELECT", db.as_bytes()];
+            expect_ok(round_trip(&mut conn, &select).await?)?;
+        }
+        Ok(conn)
+    }
+
+    /// Send pipelined commands and read one reply each, reconnecting first
+    /// if needed. The connection is dropped after any error.
+    async fn pipeline(&self, commands: &[&[&[u8]]]) -> Result<Vec<Reply>, QuotaError> {
+        let mut guard = self.conn.lock().await;
+        if guard.is_none() {
+            *guard = Some(self.connect().await?);
+        }
+        let Some(conn) = guard.as_mut() else {
+            return Err(QuotaError::Backend("not connected".into()));
+        };
+        let result = async {
+            let mut buf = Vec::new();
+            for args in commands {
+                encode(&mut buf, args);
+            }
+            conn.get_mut().write_all(&buf).await.map_err(|e| QuotaError::Backend(e.to_string()))?;
+            let mut replies = Vec::with_capacity(commands.len());
+            for _ in commands {
+                replies.push(read_reply(conn).await?);
+            }
+            Ok::<_, QuotaError>(replies)
+        }
+        .await;
+        if result.is_err() {
+            *guard = None;
+        }
+        result
+    }
+}
+
+#[async_trait::async_trait]
+impl QuotaBackend for RedisQuota {
+    fn name(&self) -> &'static str {
+        "redis"
+    }
+
+    async fn increment(&self, key: &str, ttl: Duration) -> Result<u64, QuotaError> {
+        let ttl_ms = ttl.as_millis().max(1).to_string();
+        let set: [&[u8]; 6] = [b"SET", key.as_bytes(), b"0", b"PX", ttl_ms.as_bytes(), b"NX"];
+        let incr: [&[u8]; 2] = [b"INCR", key.as_bytes()];
+        let replies = self.pipeline(&[&set[..], &incr[..]]).await?;
+        match replies.last() {
+            Some(Reply::Integer(count)) => Ok((*count).max(0) as u64),
+            other => Err(QuotaError::Backend(format!("unexpected INCR reply: {:?}", other))),
+        }
+    }
+
+    async fn claim(&self, key: &str, ttl: Duration) -> Result<bool, QuotaError> {
+        let ttl_ms = ttl.as_millis().max(1).to_string();
+        let set: [&[u8]; 6] = [b"SET", key.as_bytes(), b"1", b"PX", ttl_ms.as_bytes(), b"NX"];
+        let replies = self.pipeline(&[&set[..]]).await?;
+        match replies.first() {
+            Some(Reply::Simple(ok)) if ok == "OK" => Ok(true),
+            Some(Reply::Bulk(None)) => Ok(false),
+            other => Err(QuotaError::Backend(format!("unexpected SET reply: {:?}", other))),
+        }
+    }
+}
+
+fn encode(buf: &mut Vec<u8>, args: &[&[u8]]) {
+    buf.extend_from_slice(format!("*{}\r\n", args.len()).as_bytes());
+    for arg in args {
+        buf.extend_from_slice(format!("${}\r\n", arg.len()).as_bytes());
+        buf.extend_from_slice(arg);
+        buf.extend_from_slice(b"\r\n");
+    }
+}
+
+async fn round_trip(conn: &mut BufReader<TcpStream>, args: &[&[u8]]) -> Result<Reply, QuotaError> {
+    let mut buf = Vec::new();
+    encode(&mut buf, args);
+    conn.get_mut().write_all(&buf).await.map_err(|e| QuotaError::Backend(e.to_string()))?;
+    read_reply(conn).await
+}
+
+fn expect_ok(reply: Reply) -> Result<(), QuotaError> {
+    match reply {
+        Reply::Simple(ok) if ok == "OK" => Ok(()),
+        other => Err(QuotaError::Backend(format!("unexpected reply: {:?}", other))),
+    }
+}
+
+async fn read_reply<R: AsyncBufRead + Unpin>(conn: &mut R) -> Result<Reply, QuotaError> {
+    let mut line = String::new();
+    let n = conn.read_line(&mut line).await.map_err(|e| QuotaError::Backend(e.to_string()))?;
+    if n == 0 {
+        return Err(QuotaError::Backend("connection closed".into()));
+    }
+    let line = line.trim_end_matches("\r\n");
+    let (kind, body) = line.split_at(line.len().min(1));
+    let bad = || QuotaError::Backend(format!("malformed reply '{}'", line));
+    match kind {
+        "+" => Ok(Reply::Simple(body.to_string())),
+        "-" => Err(QuotaError::Backend(body.to_string())),
+        ":" => body.parse().map(Reply::Integer).map_err(|_| bad()),
+        "$" => {
+            let len: i64 = body.parse().map_err(|_| bad())?;
+            if len < 0 {
+                return Ok(Reply::Bulk(None));
+            }
+            let len = len as usize;
+            if len > MAX_BULK_LEN {
+                return Err(bad());
+            }
+            let mut data = vec![0u8; len + 2];
+            conn.read_exact(&mut data).await.map_err(|e| QuotaError::Backend(e.to_string()))?;
+            data.truncate(len);
+            Ok(Reply::Bulk(Some(data)))
+        }
+        _ => Err(bad()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_url_parsing() {
+        let r = RedisQuota::from_url("redis://:secret@cache.local:6380/2", Duration::from_millis(10)).unwrap();
+        assert_eq!((r.addr.as_str(), r.password.as_deref(), r.db), ("cache.local:6380", Some("secret"), 2));
+        let r = RedisQuota::from_url("redis://10.0.0.5", Duration::from_millis(10)).unwrap();
+        assert_eq!((r.addr.as_str(), r.password, r.db), ("10.0.0.5:6379", None, 0));
+        assert!(RedisQuota::from_url("http://x", Duration::from_millis(10)).is_err());
+        assert!(RedisQuota::from_url("redis://host/db", Duration::from_millis(10)).is_err());
+    }
+
+    #[test]
+    fn test_encode_is_binary_safe() {
+        let mut buf = Vec::new();
+        let args: [&[u8]; 2] = [b"SET", b"a\r\nb"];
+        encode(&mut buf, &args);
+        assert_eq!(buf, b"*2\r\n$3\r\nSET\r\n$4\r\na\r\nb\r\n");
+    }
+
+    /// Answer each read from the client with the next canned reply.
+    async fn fake_server(replies: Vec<&'static [u8]>) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 1024];
+            for reply in replies {
+                if socket.read(&mut buf).await.unwrap_or(0) == 0 {
+                    return;
+                }
+                socket.write_all(reply).await.unwrap();
+            }
+        });
+        addr
+    }
+
+    #[tokio::test]
+    async fn test_increment_and_claim_against_server() {
+        let addr = fake_server(vec![b"+OK\r\n:1\r\n", b"$-1\r\n:2\r\n", b"$-1\r\n"]).await;
+        let redis = RedisQuota::from_url(&format!("redis://{}", addr), Duration::from_secs(1)).unwrap();
+        assert_eq!(redis.increment("k", Duration::from_secs(60)).await.unwrap(), 1);
+        assert_eq!(redis.increment("k", Duration::from_secs(60)).await.unwrap(), 2);
+        assert!(!redis.claim("idem", Duration::from_secs(60)).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_read_replies() {
+        let mut input: &[u8] = b"+OK\r\n:7\r\n$-1\r\n$3\r\nabc\r\n-ERR nope\r\n";
+        assert_eq!(read_reply(&mut input).await.unwrap(), Reply::Simple("OK".into()));
+        assert_eq!(read_reply(&mut input).await.unwrap(), Reply::Integer(7));
+        assert_eq!(read_reply(&mut input).await.unwrap(), Reply::Bulk(None));
+        assert_eq!(read_reply(&mut input).await.unwrap(), Reply::Bulk(Some(b"abc".to_vec())));
+        assert!(read_reply(&mut input).await.is_err());
+    }
+}
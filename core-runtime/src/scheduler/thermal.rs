@@ -0,0 +1,317 @@
+//! Thermal and power throttling response.
+//!
+//! A GPU at its temperature or power limit clocks itself down, which shows
+//! up as a latency cliff with nothing in the logs. The governor polls the
+//! GPU sensors and, once any reading crosses its limit, throttles:
+//!
+//! - requests for GPU models shift to the first alternate in their
+//!   fallback chain that is not on the GPU (a CPU or secondary replica);
+//! - requests that stay on the GPU run at most `throttled_batch` at a time
+//!   per model, shrinking decode batches so the device sheds heat.
+//!
+//! Each transition emits a `ThermalThrottle` event. Throttling ends once
+//! every reading is back under its recovery threshold; the gap between
+//! limit and recovery keeps the governor from flapping.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tracing::Level;
+
+use crate::telemetry::{self, emit_event, RuntimeEvent, RuntimeEventKind};
+
+#[derive(Debug, Clone)]
+pub struct ThermalConfig {
+    pub enabled: bool,
+    /// Throttle at or above this GPU temperature.
+    pub temp_limit_c: f64,
+    /// Recover once every GPU is at or below this temperature.
+    pub temp_recover_c: f64,
+    /// Throttle when power draw reaches this fraction of the power limit.
+    pub power_limit_ratio: f64,
+    /// Recover once power draw is at or below this fraction.
+    pub power_recover_ratio: f64,
+    pub poll_interval: Duration,
+    /// Requests per model running at once while throttled.
+    pub throttled_batch: usize,
+    /// Models resident on the monitored GPUs; empty means every model.
+    pub gpu_models: HashSet<String>,
+}
+
+impl Default for ThermalConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            temp_limit_c: 83.0,
+            temp_recover_c: 75.0,
+            power_limit_ratio: 0.98,
+            power_recover_ratio: 0.90,
+            poll_interval: Duration::from_secs(5),
+            throttled_batch: 1,
+            gpu_models: HashSet::new(),
+        }
+    }
+}
+
+/// One GPU's sensor readings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GpuReading {
+    pub gpu: u32,
+    pub temperature_c: f64,
+    pub power_draw_w: Option<f64>,
+    pub power_limit_w: Option<f64>,
+}
+
+impl GpuReading {
+    fn power_ratio(&self) -> Option<f64> {
+        match (self.power_draw_w, self.power_limit_w) {
+            (Some(draw), Some(limit)) if limit > 0.0 => Some(draw / limit),
+            _ => None,
+        }
+    }
+}
+
+/// Source of GPU readings.
+pub trait GpuSensor: Send + Sync {
+    fn read(&self) -> Result<Vec<GpuReading>, String>;
+}
+
+/// Reads NVIDIA GPUs through `nvidia-smi`.
+pub struct NvidiaSmiSensor;
+
+impl GpuSensor for NvidiaSmiSensor {
+    fn read(&self) -> Result<Vec<GpuReading>, String> {
+        let output = std::process::Command::new("nvidia-smi")
+            .args([
+                "--query-gpu=index,temperature.gpu,power.draw,power.limit",
+                "--format=csv,noheader,nounits",
+            ])
+            .output()
+            .map_err(|e| format!("nvidia-smi: {}", e))?;
+        if !output.status.success() {
+            return Err(format!("nvidia-smi exited with {}", output.status));
+        }
+        Ok(parse_nvidia_smi(&String::from_utf8_lossy(&output.stdout)))
+    }
+}
+
+/// Parse `index, temperature, power.draw, power.limit` CSV rows. Fields a
+/// GPU does not report (`[N/A]`) are left unset.
+pub fn parse_nvidia_smi(csv: &str) -> Vec<GpuReading> {
+    csv.lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            let number = |i: usize| fields.get(i).and_then(|f| f.parse::<f64>().ok());
+            Some(GpuReading {
+                gpu: fields.first()?.parse().ok()?,
+                temperature_c: number(1)?,
+                power_draw_w: number(2),
+                power_limit_w: number(3),
+            })
+        })
+        .collect()
+}
+
+/// Throttling state for status reports.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ThermalStatus {
+    pub throttled: bool,
+    pub readings: Vec<GpuReading>,
+}
+
+/// Watches GPU sensors and throttles GPU traffic while they run hot.
+pub struct ThermalGovernor {
+    config: ThermalConfig,
+    sensor: Box<dyn GpuSensor>,
+    throttled: AtomicBool,
+    readings: Mutex<Vec<GpuReading>>,
+    batches: Mutex<HashMap<String, Arc<Semaphore>>>,
+}
+
+impl ThermalGovernor {
+    pub fn new(config: ThermalConfig) -> Self {
+        Self::with_sensor(config, Box::new(NvidiaSmiSensor))
+    }
+
+    pub fn with_sensor(config: ThermalConfig, sensor: Box<dyn GpuSensor>) -> Self {
+        Self {
+            config,
+            sensor,
+            throttled: AtomicBool::new(false),
+            readings: Mutex::new(Vec::new()),
+            batches: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn is_throttled(&self) -> bool {
+        self.throttled.load(Ordering::Acquire)
+    }
+
+    pub fn is_gpu_model(&self, model_id: &str) -> bool {
+        self.config.gpu_models.is_empty() || self.config.gpu_models.contains(model_id)
+    }
+
+    /// Whether a request for `candidate` should move on to a later model
+    /// in its chain: the GPU is throttled, `candidate` runs on it and
+    /// `later` includes a model that does not.
+    pub fn should_shift(&self, candidate: &str, later: &[String]) -> bool {
+        self.is_throttled() && self.is_gpu_model(candidate) && later.iter().any(|m| !self.is_gpu_model(m))
+    }
+
+    /// Wait for a batch slot on `model_id` while throttled. `None` when
+    /// not throttled or the model is not on the GPU.
+    pub async fn admit(&self, model_id: &str) -> Option<OwnedSemaphorePermit> {
+        if !self.is_throttled() || !self.is_gpu_model(model_id) {
+            return None;
+        }
+        let slots = {
+            let mut batches = self.batches.lock().unwrap_or_else(|e| e.into_inner());
+            let permits = self.config.throttled_batch.max(1);
+            Arc::clone(batches.entry(model_id.to_string()).or_insert_with(|| Arc::new(Semaphore::new(permits))))
+        };
+        slots.acquire_owned().await.ok()
+    }
+
+    pub fn status(&self) -> ThermalStatus {
+        ThermalStatus {
+            throttled: self.is_throttled(),
+            readings: self.readings.lock().unwrap_or_else(|e| e.into_inner()).clone(),
+        }
+    }
+
+    /// Apply new readings, emitting an event when throttling starts or
+    /// ends. Returns whether the governor is throttled.
+    pub fn observe(&self, readings: Vec<GpuReading>) -> bool {
+        let config = &self.config;
+        let hot = readings.iter().find(|r| {
+            r.temperature_c >= config.temp_limit_c || r.power_ratio().is_some_and(|p| p >= config.power_limit_ratio)
+        });
+        let cool = readings.iter().all(|r| {
+            r.temperature_c <= config.temp_recover_c
+                && r.power_ratio().is_none_or(|p| p <= config.power_recover_ratio)
+        });
+        for reading in &readings {
+            telemetry::record_gpu_reading(reading.gpu, reading.temperature_c, reading.power_draw_w);
+        }
+
+        let was = self.is_throttled();
+        let now = match hot {
+            Some(_) => true,
+            None if cool => false,
+            None => was,
+        };
+        if now != was {
+            self.throttled.store(now, Ordering::Release);
+            telemetry::record_thermal_throttled(now);
+            let event = match hot {
+                Some(r) => RuntimeEvent::new(
+                    RuntimeEventKind::ThermalThrottle,
+                    Level::WARN,
+                    format!("gpu{}", r.gpu),
+                    format!(
+                        "throttling GPU traffic at {:.0}°C{}; batches capped at {}",
+                        r.temperature_c,
+                        r.power_draw_w.zip(r.power_limit_w).map(|(d, l)| format!(", {:.0}/{:.0} W", d, l)).unwrap_or_default(),
+                        config.throttled_batch.max(1)
+                    ),
+                ),
+                None => RuntimeEvent::new(
+                    RuntimeEventKind::ThermalThrottle,
+                    Level::INFO,
+                    "gpu",
+                    "GPU readings back to normal, throttling lifted",
+                ),
+            };
+            emit_event(event);
+        }
+        *self.readings.lock().unwrap_or_else(|e| e.into_inner()) = readings;
+        now
+    }
+
+    /// Poll the sensor once. A failed read keeps the current state.
+    pub fn poll(&self) {
+        match self.sensor.read() {
+            Ok(readings) => {
+                self.observe(readings);
+            }
+            Err(e) => tracing::debug!(error = %e, "GPU sensor read failed"),
+        }
+    }
+
+    /// Poll every `poll_interval` until the task is aborted.
+    pub async fn run(self: Arc<Self>) {
+        if !self.config.enabled {
+            return;
+        }
+        let mut interval = tokio::time::interval(self.config.poll_interval);
+        loop {
+            interval.tick().await;
+            let governor = Arc::clone(&self);
+            // nvidia-smi blocks for tens of milliseconds
+            let _ = tokio::task::spawn_blocking(move || governor.poll()).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reading(temperature_c: f64, draw: f64) -> GpuReading {
+        GpuReading { gpu: 0, temperature_c, power_draw_w: Some(draw), power_limit_w: Some(300.0) }
+    }
+
+    fn governor() -> ThermalGovernor {
+        let config = ThermalConfig {
+            enabled: true,
+            gpu_models: ["llama-gpu".to_string()].into_iter().collect(),
+            ..Default::default()
+        };
+        ThermalGovernor::new(config)
+    }
+
+    #[test]
+    fn test_parse_nvidia_smi() {
+        let readings = parse_nvidia_smi("0, 71, 245.31, 300.00\n1, 45, [N/A], [N/A]\n");
+        assert_eq!(readings[0], reading(71.0, 245.31));
+        assert_eq!((readings[1].gpu, readings[1].power_draw_w), (1, None));
+    }
+
+    #[test]
+    fn test_throttle_with_hysteresis() {
+        let governor = governor();
+        assert!(!governor.observe(vec![reading(80.0, 200.0)]));
+        // Power at the limit throttles even when cool
+        assert!(governor.observe(vec![reading(60.0, 299.0)]));
+        // Between recovery and limit the state holds
+        assert!(governor.observe(vec![reading(79.0, 200.0)]));
+        assert!(!governor.observe(vec![reading(74.0, 200.0)]));
+        assert!(governor.observe(vec![reading(90.0, 100.0)]));
+        assert!(governor.status().throttled);
+    }
+
+    #[tokio::test]
+    async fn test_shift_and_batch_cap() {
+        let governor = governor();
+        let chain = ["llama-cpu".to_string()];
+        assert!(!governor.should_shift("llama-gpu", &chain));
+        assert!(governor.admit("llama-gpu").await.is_none());
+
+        governor.observe(vec![reading(88.0, 100.0)]);
+        assert!(governor.should_shift("llama-gpu", &chain));
+        assert!(!governor.should_shift("llama-cpu", &[]));
+        assert!(!governor.should_shift("llama-gpu", &["llama-gpu".to_string()]));
+
+        let slot = governor.admit("llama-gpu").await;
+        assert!(slot.is_some());
+        let second = tokio::time::timeout(Duration::from_millis(20), governor.admit("llama-gpu")).await;
+        assert!(second.is_err());
+        assert!(governor.admit("llama-cpu").await.is_none());
+        drop(slot);
+        assert!(governor.admit("llama-gpu").await.is_some());
+    }
+}
@@ -3,35 +3,41 @@
 //! Provides work-stealing thread pool with configurable thread counts,
 //! priority queues, and affinity settings for optimal CPU utilization.
 //!
+//! # Priority Ordering
+//! Every queue (per-worker and the global overflow queue) is kept in
+//! priority order, FIFO within a priority. A worker takes the
+//! highest-priority front among its own queue and the global queue, and
+//! steals from peers when they hold something more urgent or it has
+//! nothing. Stealing takes the victim's most urgent task. A worker that
+//! starts a task while a higher-priority one is still queued anywhere
+//! counts a priority inversion.
+//!
+//! # Deadlines
+//! Tasks may carry a deadline. Within a priority, tasks with deadlines run
+//! earliest-deadline-first, ahead of tasks without one. A task whose
+//! deadline has passed by the time a worker takes it is shed instead of
+//! run: its rejection callback is invoked and a deadline miss is counted,
+//! so stale background work cannot hold up latency-critical tasks.
+//!
 //! # Panic Safety
-//! This module uses poison-recovering lock guards to maintain availability
-//! even if a worker thread panics. A poisoned lock logs a warning but
-//! continues operation rather than propagating the panic.
+//! This module uses poison-recovering lock guards ([`crate::locks`]) to
+//! maintain availability even if a worker thread panics. A recovery is
+//! logged and counted rather than propagating the panic. No path holds
+//! two of its locks at once.
 
+use std::cmp::Reverse;
 use std::collections::VecDeque;
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::{Arc, Condvar, Mutex, MutexGuard, RwLock, RwLockReadGuard};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex, RwLock};
 use std::thread::{self, JoinHandle};
 use std::time::{Duration, Instant};
 
-/// Acquire a mutex lock, recovering from poison if a thread panicked.
-/// Logs a warning but continues operation to maintain availability.
-#[inline]
-fn lock_or_recover<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
-    mutex.lock().unwrap_or_else(|poisoned| {
-        tracing::warn!("Mutex was poisoned, recovering to maintain availability");
-        poisoned.into_inner()
-    })
-}
+use crate::locks::{lock_or_recover, read_or_recover, write_or_recover};
 
-/// Acquire a read lock, recovering from poison if a thread panicked.
-#[inline]
-fn read_or_recover<T>(rwlock: &RwLock<T>) -> RwLockReadGuard<'_, T> {
-    rwlock.read().unwrap_or_else(|poisoned| {
-        tracing::warn!("RwLock was poisoned, recovering to maintain availability");
-        poisoned.into_inner()
-    })
-}
+/// Worker and global task queues.
+const QUEUE: &str = "thread_pool.queue";
+const WAKE: &str = "thread_pool.wake";
+const STATS: &str = "thread_pool.stats";
 
 /// Configuration for the thread pool.
 #[derive(Debug, Clone)]
@@ -111,11 +117,61 @@ pub enum TaskPriority {
 /// A task to be executed by the thread pool.
 pub type Task = Box<dyn FnOnce() + Send + 'static>;
 
+/// Called instead of a task shed for missing its deadline, with how late
+/// the task was when a worker reached it.
+pub type RejectCallback = Box<dyn FnOnce(Duration) + Send + 'static>;
+
 /// Prioritized task wrapper.
 struct PrioritizedTask {
     task: Task,
     priority: TaskPriority,
     sequence: u64, // For FIFO ordering within same priority
+    deadline: Option<Instant>,
+    on_reject: Option<RejectCallback>,
+}
+
+/// Ordering key; greater runs first.
+type Urgency = (TaskPriority, Reverse<(bool, Option<Instant>, u64)>);
+
+impl PrioritizedTask {
+    /// Higher priority first, then earliest deadline (tasks without a
+    /// deadline last), then oldest.
+    fn urgency(&self) -> Urgency {
+        (self.priority, Reverse((self.deadline.is_none(), self.deadline, self.sequence)))
+    }
+
+    /// How far past its deadline the task is at `now`, if it is.
+    fn expired_by(&self, now: Instant) -> Option<Duration> {
+        self.deadline.filter(|d| *d <= now).map(|d| now - d)
+    }
+}
+
+type TaskQueue = Arc<Mutex<VecDeque<PrioritizedTask>>>;
+
+/// Insert `task` behind every task at least as urgent.
+fn insert_by_priority(queue: &mut VecDeque<PrioritizedTask>, task: PrioritizedTask) {
+    let urgency = task.urgency();
+    let pos = queue.iter().position(|t| t.urgency() < urgency).unwrap_or(queue.len());
+    queue.insert(pos, task);
+}
+
+/// Tasks queued anywhere in the pool, per priority.
+#[derive(Default)]
+struct QueuedCounts([AtomicUsize; 4]);
+
+impl QueuedCounts {
+    fn add(&self, priority: TaskPriority) {
+        self.0[priority as usize].fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn remove(&self, priority: TaskPriority) {
+        let _ = self.0[priority as usize].fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1));
+    }
+
+    /// Whether any task more urgent than `priority` is queued.
+    fn any_above(&self, priority: TaskPriority) -> bool {
+        self.0[priority as usize + 1..].iter().any(|n| n.load(Ordering::SeqCst) > 0)
+    }
 }
 
 /// Statistics for thread pool performance.
@@ -124,7 +180,12 @@ pub struct ThreadPoolStats {
     pub total_tasks_executed: u64,
     pub high_priority_tasks: u64,
     pub work_steals: u64,
+    /// Tasks placed in the global queue because every worker queue was full.
     pub queue_overflows: u64,
+    /// Tasks started while a higher-priority task was still queued.
+    pub priority_inversions: u64,
+    /// Tasks shed because their deadline passed before they started.
+    pub deadline_misses: u64,
     pub avg_wait_time_us: u64,
     pub avg_exec_time_us: u64,
     pub threads_active: usize,
@@ -133,7 +194,7 @@ pub struct ThreadPoolStats {
 
 /// Worker thread state.
 struct Worker {
-    queue: Arc<Mutex<VecDeque<PrioritizedTask>>>,
+    queue: TaskQueue,
     active: Arc<AtomicBool>,
     handle: Option<JoinHandle<()>>,
 }
@@ -146,8 +207,9 @@ pub struct ThreadPool {
     task_sequence: AtomicU64,
     shutdown: Arc<AtomicBool>,
     condvar: Arc<(Mutex<bool>, Condvar)>,
-    global_queue: Arc<Mutex<VecDeque<PrioritizedTask>>>,
-    _all_queues: Vec<Arc<Mutex<VecDeque<PrioritizedTask>>>>,
+    global_queue: TaskQueue,
+    queued: Arc<QueuedCounts>,
+    _all_queues: Vec<TaskQueue>,
 }
 
 impl ThreadPool {
@@ -163,9 +225,10 @@ impl ThreadPool {
         let condvar = Arc::new((Mutex::new(false), Condvar::new()));
         let global_queue = Arc::new(Mutex::new(VecDeque::with_capacity(config.queue_size)));
         let stats = Arc::new(RwLock::new(ThreadPoolStats::default()));
+        let queued = Arc::new(QueuedCounts::default());
 
         // Create worker queues
-        let all_queues: Vec<Arc<Mutex<VecDeque<PrioritizedTask>>>> = (0..num_threads)
+        let all_queues: Vec<TaskQueue> = (0..num_threads)
             .map(|_| Arc::new(Mutex::new(VecDeque::with_capacity(config.queue_size))))
             .collect();
 
@@ -183,6 +246,7 @@ impl ThreadPool {
             let condvar_clone = condvar.clone();
             let global_queue_clone = global_queue.clone();
             let stats_clone = stats.clone();
+            let queued_clone = queued.clone();
             let config_clone = config.clone();
             let active_clone = active.clone();
 
@@ -204,6 +268,7 @@ impl ThreadPool {
                         shutdown_clone,
                         condvar_clone,
                         global_queue_clone,
+                        queued_clone,
                         stats_clone,
                         config_clone,
                     );
@@ -225,6 +290,7 @@ impl ThreadPool {
             shutdown,
             condvar,
             global_queue,
+            queued,
             _all_queues: all_queues,
         }
     }
@@ -239,6 +305,33 @@ impl ThreadPool {
         &self,
         task: Task,
         priority: TaskPriority,
+    ) -> Result<(), ThreadPoolError> {
+        self.enqueue(task, priority, None, None)
+    }
+
+    /// Submit a task that must start by `deadline`. If a worker reaches it
+    /// later, it is shed and `on_reject` runs instead. A deadline that has
+    /// already passed is rejected here without queuing or calling back.
+    pub fn submit_with_deadline(
+        &self,
+        task: Task,
+        priority: TaskPriority,
+        deadline: Instant,
+        on_reject: Option<RejectCallback>,
+    ) -> Result<(), ThreadPoolError> {
+        if deadline <= Instant::now() {
+            write_or_recover(STATS, &self.stats).deadline_misses += 1;
+            return Err(ThreadPoolError::DeadlineExceeded);
+        }
+        self.enqueue(task, priority, Some(deadline), on_reject)
+    }
+
+    fn enqueue(
+        &self,
+        task: Task,
+        priority: TaskPriority,
+        deadline: Option<Instant>,
+        on_reject: Option<RejectCallback>,
     ) -> Result<(), ThreadPoolError> {
         if self.shutdown.load(Ordering::SeqCst) {
             return Err(ThreadPoolError::PoolShutdown);
@@ -248,39 +341,34 @@ impl ThreadPool {
             task,
             priority,
             sequence: self.task_sequence.fetch_add(1, Ordering::SeqCst),
+            deadline,
+            on_reject,
         };
 
-        // Find least loaded worker
-        let min_queue_worker = self.find_least_loaded_worker();
-
-        let queue = if let Some(id) = min_queue_worker {
-            self.workers[id].queue.clone()
-        } else {
-            self.global_queue.clone()
-        };
+        // Least loaded worker, or the global queue once every worker is full
+        let local = self
+            .find_least_loaded_worker()
+            .map(|id| &self.workers[id].queue)
+            .filter(|q| lock_or_recover(QUEUE, q).len() < self.config.queue_size);
+        let overflow = local.is_none() && !self.workers.is_empty();
+        let queue = local.unwrap_or(&self.global_queue);
 
         {
-            let mut q = lock_or_recover(&queue);
+            let mut q = lock_or_recover(QUEUE, queue);
             if q.len() >= self.config.queue_size {
                 return Err(ThreadPoolError::QueueFull);
             }
-
-            // Insert in priority order
-            let insert_pos = q
-                .iter()
-                .position(|t| {
-                    t.priority < prioritized.priority
-                        || (t.priority == prioritized.priority && t.sequence > prioritized.sequence)
-                })
-                .unwrap_or(q.len());
-
-            q.insert(insert_pos, prioritized);
+            self.queued.add(priority);
+            insert_by_priority(&mut q, prioritized);
+        }
+        if overflow {
+            write_or_recover(STATS, &self.stats).queue_overflows += 1;
         }
 
         // Wake up a worker
         let (lock, cvar) = &*self.condvar;
         {
-            let _guard = lock_or_recover(lock);
+            let _guard = lock_or_recover(WAKE, lock);
             cvar.notify_one();
         }
 
@@ -292,56 +380,65 @@ impl ThreadPool {
         self.workers
             .iter()
             .enumerate()
-            .min_by_key(|(_, w)| lock_or_recover(&w.queue).len())
+            .min_by_key(|(_, w)| lock_or_recover(QUEUE, &w.queue).len())
             .map(|(i, _)| i)
     }
 
     /// Worker thread main loop.
+    #[allow(clippy::too_many_arguments)]
     fn worker_loop(
         worker_id: usize,
-        queue: Arc<Mutex<VecDeque<PrioritizedTask>>>,
-        all_queues: Vec<Arc<Mutex<VecDeque<PrioritizedTask>>>>,
+        queue: TaskQueue,
+        all_queues: Vec<TaskQueue>,
         active: Arc<AtomicBool>,
         shutdown: Arc<AtomicBool>,
         condvar: Arc<(Mutex<bool>, Condvar)>,
-        global_queue: Arc<Mutex<VecDeque<PrioritizedTask>>>,
+        global_queue: TaskQueue,
+        queued: Arc<QueuedCounts>,
         stats: Arc<RwLock<ThreadPoolStats>>,
         config: ThreadPoolConfig,
     ) {
         let idle_timeout = Duration::from_millis(config.idle_timeout_ms);
 
         while !shutdown.load(Ordering::SeqCst) {
-            // Try to get a task from local queue
-            let task = lock_or_recover(&queue).pop_front();
-
-            let task = match task {
-                Some(t) => Some(t),
-                None => {
-                    // Try global queue
-                    if let Some(t) = lock_or_recover(&global_queue).pop_front() {
-                        Some(t)
-                    } else if config.enable_work_stealing {
-                        // Try to steal from other workers
-                        Self::try_steal(worker_id, &all_queues)
-                    } else {
-                        None
+            let steal_from = if config.enable_work_stealing { &all_queues[..] } else { &[] };
+            let task = Self::next_task(worker_id, &queue, &global_queue, steal_from, &queued);
+
+            let late = task.as_ref().and_then(|(t, _)| t.expired_by(Instant::now()));
+            let shed = late.is_some();
+            let task = match (task, late) {
+                (Some((prioritized, _)), Some(late)) => {
+                    tracing::debug!(priority = ?prioritized.priority, ?late, "Shed task past its deadline");
+                    write_or_recover(STATS, &stats).deadline_misses += 1;
+                    if let Some(on_reject) = prioritized.on_reject {
+                        on_reject(late);
                     }
+                    None
                 }
+                (task, _) => task,
             };
 
-            if let Some(prioritized) = task {
+            if let Some((prioritized, stolen)) = task {
                 active.store(true, Ordering::SeqCst);
+                let inverted = queued.any_above(prioritized.priority);
 
                 let start = Instant::now();
                 (prioritized.task)();
                 let exec_time = start.elapsed();
 
                 // Update stats
-                if let Ok(mut s) = stats.write() {
+                {
+                    let mut s = write_or_recover(STATS, &stats);
                     s.total_tasks_executed += 1;
                     if prioritized.priority >= TaskPriority::High {
                         s.high_priority_tasks += 1;
                     }
+                    if stolen {
+                        s.work_steals += 1;
+                    }
+                    if inverted {
+                        s.priority_inversions += 1;
+                    }
                     // Rolling average of execution time
                     let exec_us = exec_time.as_micros() as u64;
                     if s.avg_exec_time_us == 0 {
@@ -352,36 +449,59 @@ impl ThreadPool {
                 }
 
                 active.store(false, Ordering::SeqCst);
-            } else {
-                // No work available, wait
+            } else if !shed {
+                // No work available, wait (after a shed task, look for the
+                // next one right away)
                 let (lock, cvar) = &*condvar;
-                let guard = lock_or_recover(lock);
+                let guard = lock_or_recover(WAKE, lock).into_inner();
                 // wait_timeout can return Err if mutex was poisoned during wait
                 let _ = cvar.wait_timeout(guard, idle_timeout);
             }
         }
     }
 
-    /// Try to steal a task from other workers.
-    fn try_steal(
+    /// Take the most urgent task among the local and global queues, or
+    /// steal from a peer in `steal_from` when something more urgent is
+    /// queued elsewhere (or there is nothing local). Returns the task and
+    /// whether it was stolen.
+    fn next_task(
         worker_id: usize,
-        all_queues: &[Arc<Mutex<VecDeque<PrioritizedTask>>>],
-    ) -> Option<PrioritizedTask> {
-        for (id, target) in all_queues.iter().enumerate() {
-            if id == worker_id {
-                continue; // Don't steal from self
-            }
-            let mut q = lock_or_recover(target);
-            if let Some(task) = q.pop_back() {
-                return Some(task);
+        local: &TaskQueue,
+        global: &TaskQueue,
+        steal_from: &[TaskQueue],
+        queued: &QueuedCounts,
+    ) -> Option<(PrioritizedTask, bool)> {
+        let front = |q: &TaskQueue| lock_or_recover(QUEUE, q).front().map(PrioritizedTask::urgency);
+        let mut best = [local, global]
+            .into_iter()
+            .filter_map(|q| front(q).map(|u| (u, q, false)))
+            .max_by_key(|(u, _, _)| *u);
+
+        let outranked = best.is_none_or(|((priority, _), _, _)| queued.any_above(priority));
+        if outranked {
+            for (id, peer) in steal_from.iter().enumerate() {
+                if id == worker_id {
+                    continue; // Don't steal from self
+                }
+                if let Some(urgency) = front(peer) {
+                    if best.is_none_or(|(b, _, _)| urgency > b) {
+                        best = Some((urgency, peer, true));
+                    }
+                }
             }
         }
-        None
+
+        // The front may have changed since it was inspected; whatever is
+        // at the front now is still that queue's most urgent task
+        let (_, source, stolen) = best?;
+        let task = lock_or_recover(QUEUE, source).pop_front()?;
+        queued.remove(task.priority);
+        Some((task, stolen))
     }
 
     /// Get current statistics.
     pub fn stats(&self) -> ThreadPoolStats {
-        let mut stats = read_or_recover(&self.stats).clone();
+        let mut stats = read_or_recover(STATS, &self.stats).clone();
         stats.threads_active = self
             .workers
             .iter()
@@ -406,7 +526,7 @@ impl ThreadPool {
         self.shutdown.store(true, Ordering::SeqCst);
         let (lock, cvar) = &*self.condvar;
         {
-            let _guard = lock_or_recover(lock);
+            let _guard = lock_or_recover(WAKE, lock);
             cvar.notify_all();
         }
     }
@@ -418,7 +538,7 @@ impl ThreadPool {
         // Wake all workers
         let (lock, cvar) = &*self.condvar;
         {
-            let _guard = lock_or_recover(lock);
+            let _guard = lock_or_recover(WAKE, lock);
             cvar.notify_all();
         }
 
@@ -436,7 +556,7 @@ impl Drop for ThreadPool {
         self.shutdown.store(true, Ordering::SeqCst);
         let (lock, cvar) = &*self.condvar;
         {
-            let _guard = lock_or_recover(lock);
+            let _guard = lock_or_recover(WAKE, lock);
             cvar.notify_all();
         }
 
@@ -458,6 +578,9 @@ pub enum ThreadPoolError {
     #[error("Task queue is full")]
     QueueFull,
 
+    #[error("Task deadline already passed")]
+    DeadlineExceeded,
+
     #[error("Failed to spawn thread: {0}")]
     ThreadSpawnFailed(String),
 }
@@ -533,6 +656,182 @@ mod tests {
         assert!(!batch_config.enable_priority);
     }
 
+    fn task(priority: TaskPriority, sequence: u64) -> PrioritizedTask {
+        PrioritizedTask { task: Box::new(|| {}), priority, sequence, deadline: None, on_reject: None }
+    }
+
+    fn queue_of(tasks: Vec<PrioritizedTask>, queued: &QueuedCounts) -> TaskQueue {
+        let mut q = VecDeque::new();
+        for t in tasks {
+            queued.add(t.priority);
+            insert_by_priority(&mut q, t);
+        }
+        Arc::new(Mutex::new(q))
+    }
+
+    #[test]
+    fn test_insert_by_priority_is_fifo_within_priority() {
+        let queued = QueuedCounts::default();
+        let q = queue_of(
+            vec![
+                task(TaskPriority::Normal, 0),
+                task(TaskPriority::High, 1),
+                task(TaskPriority::Normal, 2),
+                task(TaskPriority::High, 3),
+            ],
+            &queued,
+        );
+        let order: Vec<u64> = lock_or_recover(QUEUE, &q).iter().map(|t| t.sequence).collect();
+        assert_eq!(order, vec![1, 3, 0, 2]);
+        assert!(queued.any_above(TaskPriority::Normal));
+        assert!(!queued.any_above(TaskPriority::High));
+    }
+
+    #[test]
+    fn test_next_task_takes_most_urgent_source() {
+        let queued = QueuedCounts::default();
+        let local = queue_of(vec![task(TaskPriority::Low, 0)], &queued);
+        let global = queue_of(vec![task(TaskPriority::High, 1)], &queued);
+        let peer = queue_of(vec![task(TaskPriority::Normal, 2), task(TaskPriority::Critical, 3)], &queued);
+        let all = vec![local.clone(), peer.clone()];
+
+        let mut order = Vec::new();
+        while let Some((t, stolen)) = ThreadPool::next_task(0, &local, &global, &all, &queued) {
+            order.push((t.priority, stolen));
+        }
+        assert_eq!(
+            order,
+            vec![
+                (TaskPriority::Critical, true),
+                (TaskPriority::High, false),
+                (TaskPriority::Normal, true),
+                (TaskPriority::Low, false),
+            ]
+        );
+
+        // Without stealing, peer work is left alone
+        let queued = QueuedCounts::default();
+        let local = queue_of(vec![task(TaskPriority::Low, 0)], &queued);
+        let peer = queue_of(vec![task(TaskPriority::Critical, 1)], &queued);
+        let (t, _) = ThreadPool::next_task(0, &local, &global, &[], &queued).unwrap();
+        assert_eq!(t.priority, TaskPriority::Low);
+        assert!(queued.any_above(TaskPriority::Low));
+        assert_eq!(lock_or_recover(QUEUE, &peer).len(), 1);
+    }
+
+    #[test]
+    fn test_mixed_priority_flood_runs_in_priority_order() {
+        let pool = ThreadPool::new(ThreadPoolConfig {
+            num_threads: 1,
+            queue_size: 8,
+            ..Default::default()
+        });
+
+        // Hold the only worker so the flood queues up behind it
+        let (started_tx, started_rx) = std::sync::mpsc::channel();
+        let (release_tx, release_rx) = std::sync::mpsc::channel::<()>();
+        pool.submit(Box::new(move || {
+            let _ = started_tx.send(());
+            let _ = release_rx.recv();
+        }))
+        .unwrap();
+        started_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+
+        // 14 tasks: 8 fill the worker queue, 6 overflow to the global queue
+        let priorities = [TaskPriority::Low, TaskPriority::Normal, TaskPriority::High, TaskPriority::Critical];
+        let ran = Arc::new(Mutex::new(Vec::new()));
+        for i in 0..14 {
+            let priority = priorities[i % priorities.len()];
+            let ran = ran.clone();
+            pool.submit_with_priority(Box::new(move || ran.lock().unwrap().push(priority)), priority)
+                .unwrap();
+        }
+        release_tx.send(()).unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while ran.lock().unwrap().len() < 14 && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(5));
+        }
+        let ran = ran.lock().unwrap().clone();
+        assert_eq!(ran.len(), 14);
+        assert!(ran.windows(2).all(|w| w[0] >= w[1]), "ran out of priority order: {:?}", ran);
+
+        let stats = pool.stats();
+        assert_eq!(stats.queue_overflows, 6);
+        assert_eq!(stats.priority_inversions, 0);
+    }
+
+    #[test]
+    fn test_deadlines_order_within_priority() {
+        let queued = QueuedCounts::default();
+        let now = Instant::now();
+        let with_deadline = |sequence, after_ms| PrioritizedTask {
+            deadline: Some(now + Duration::from_millis(after_ms)),
+            ..task(TaskPriority::Normal, sequence)
+        };
+        let q = queue_of(
+            vec![
+                task(TaskPriority::Normal, 0),
+                with_deadline(1, 50),
+                with_deadline(2, 10),
+                task(TaskPriority::High, 3),
+            ],
+            &queued,
+        );
+        let order: Vec<u64> = lock_or_recover(QUEUE, &q).iter().map(|t| t.sequence).collect();
+        assert_eq!(order, vec![3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn test_expired_tasks_are_shed_with_callback() {
+        let pool = ThreadPool::new(ThreadPoolConfig { num_threads: 1, ..Default::default() });
+        let past = Instant::now() - Duration::from_millis(1);
+        assert!(matches!(
+            pool.submit_with_deadline(Box::new(|| {}), TaskPriority::Low, past, None),
+            Err(ThreadPoolError::DeadlineExceeded)
+        ));
+
+        let (started_tx, started_rx) = std::sync::mpsc::channel();
+        let (release_tx, release_rx) = std::sync::mpsc::channel::<()>();
+        pool.submit(Box::new(move || {
+            let _ = started_tx.send(());
+            let _ = release_rx.recv();
+        }))
+        .unwrap();
+        started_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+
+        let ran = Arc::new(AtomicUsize::new(0));
+        let (rejected_tx, rejected_rx) = std::sync::mpsc::channel();
+        let ran_clone = ran.clone();
+        pool.submit_with_deadline(
+            Box::new(move || {
+                ran_clone.fetch_add(1, Ordering::SeqCst);
+            }),
+            TaskPriority::Low,
+            Instant::now() + Duration::from_millis(10),
+            Some(Box::new(move |late| {
+                let _ = rejected_tx.send(late);
+            })),
+        )
+        .unwrap();
+        let ran_clone = ran.clone();
+        pool.submit(Box::new(move || {
+            ran_clone.fetch_add(10, Ordering::SeqCst);
+        }))
+        .unwrap();
+
+        thread::sleep(Duration::from_millis(30));
+        release_tx.send(()).unwrap();
+        rejected_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while ran.load(Ordering::SeqCst) == 0 && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(5));
+        }
+        assert_eq!(ran.load(Ordering::SeqCst), 10);
+        assert_eq!(pool.stats().deadline_misses, 2);
+    }
+
     #[test]
     fn test_stats_tracking() {
         let pool = ThreadPool::new(ThreadPoolConfig::default());
@@ -261,12 +261,17 @@ impl AuditLogger {
     }
 
     /// Log an audit event
-    pub async fn log(&self, event: AuditEvent) {
+    pub async fn log(&self, mut event: AuditEvent) {
         // Check severity threshold
         if event.severity < self.config.min_severity {
             return;
         }
 
+        // Tag with the instance identity so fleet-wide logs can be told apart
+        if let Some(resource) = crate::telemetry::resource() {
+            resource.apply_to(&mut event.metadata);
+        }
+
         // Log to stdout if configured
         if self.config.log_to_stdout {
             println!("{}", event.to_log_string());
@@ -346,6 +351,37 @@ impl AuditLogger {
             .collect()
     }
 
+    /// Rotate the event buffer, keeping the `keep_recent` newest events.
+    /// Returns the rotated-out events so callers can archive them.
+    pub async fn rotate(&self, keep_recent: usize) -> Vec<AuditEvent> {
+        let mut events = self.events.write().await;
+        let cut = events.len().saturating_sub(keep_recent);
+        events.drain(..cut).collect()
+    }
+
+    /// Drop events older than `cutoff`, then the oldest until the
+    /// serialized log fits `max_bytes`. Returns (events removed, bytes reclaimed).
+    pub async fn enforce_retention(&self, cutoff: Option<DateTime<Utc>>, max_bytes: Option<u64>) -> (usize, u64) {
+        let mut events = self.events.write().await;
+        let sizes: Vec<u64> = events
+            .iter()
+            .map(|e| serde_json::to_vec(e).map_or(0, |bytes| bytes.len() as u64))
+            .collect();
+        let mut total: u64 = sizes.iter().sum();
+        let mut cut = 0;
+        for (event, size) in events.iter().zip(&sizes) {
+            let expired = cutoff.is_some_and(|c| event.timestamp < c);
+            let oversize = max_bytes.is_some_and(|max| total > max);
+            if !expired && !oversize {
+                break;
+            }
+            total -= size;
+            cut += 1;
+        }
+        events.drain(..cut);
+        (cut, sizes[..cut].iter().sum())
+    }
+
     /// Clear all events (use with caution)
     pub async fn clear(&self) {
         self.events.write().await.clear();
@@ -549,6 +585,34 @@ mod tests {
         assert_eq!(logger.event_count().await, 5);
     }
 
+    #[tokio::test]
+    async fn test_enforce_retention_drops_oldest() {
+        let logger = AuditLogger::new(AuditConfig { log_to_stdout: false, ..Default::default() });
+        for i in 0..4 {
+            let event = AuditEvent::builder()
+                .severity(AuditSeverity::Info)
+                .category(AuditCategory::System)
+                .event_type("test")
+                .message(format!("Event {}", i))
+                .source("test")
+                .build()
+                .unwrap();
+            logger.log(event).await;
+        }
+        let sizes: Vec<u64> = logger
+            .get_events()
+            .await
+            .iter()
+            .map(|e| serde_json::to_vec(e).unwrap().len() as u64)
+            .collect();
+
+        let (removed, bytes) = logger.enforce_retention(None, Some(sizes[2] + sizes[3])).await;
+        assert_eq!((removed, bytes), (2, sizes[0] + sizes[1]));
+        assert_eq!(logger.get_events().await[0].message, "Event 2");
+        let (removed, _) = logger.enforce_retention(Some(Utc::now()), None).await;
+        assert_eq!(removed, 2);
+    }
+
     #[tokio::test]
     async fn test_get_events_by_category() {
         let logger = AuditLogger::new(AuditConfig::default());
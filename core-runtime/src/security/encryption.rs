@@ -32,6 +32,8 @@ use std::sync::Mutex;
 use std::sync::OnceLock;
 use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
 
+use crate::storage::{self, Storage, StorageError};
+
 /// Encryption key size (256 bits)
 pub const KEY_SIZE: usize = 32;
 /// Nonce size (96 bits for GCM)
@@ -107,6 +109,13 @@ fn get_or_create_installation_salt() -> Result<Vec<u8>, EncryptionError> {
         return Ok(salt.clone());
     }
 
+    // A diskless container keeps the salt in its object store
+    if let Some(storage) = storage::installed() {
+        let salt = get_or_create_stored_salt(storage.as_ref())?;
+        let _ = INSTALLATION_SALT.set(salt.clone());
+        return Ok(salt);
+    }
+
     let salt_path = get_salt_file_path()?;
 
     // Try to read existing salt
@@ -131,6 +140,25 @@ fn get_or_create_installation_salt() -> Result<Vec<u8>, EncryptionError> {
     Ok(salt)
 }
 
+/// Get or create the salt in an object store, under the salt file name.
+///
+/// Replicas starting together race to create it. The create is
+/// conditional, so the first write wins, and every replica (the winner
+/// included) then uses whatever the store holds. A stored salt that is too
+/// short is an error rather than being replaced, since other replicas may
+/// already derive keys from it.
+fn get_or_create_stored_salt(storage: &dyn Storage) -> Result<Vec<u8>, EncryptionError> {
+    let io = |e: StorageError| EncryptionError::IoError(format!("Salt storage: {}", e));
+    if storage.get(SALT_FILE_NAME).map_err(io)?.is_none() {
+        storage.put_if_absent(SALT_FILE_NAME, &generate_random_salt()).map_err(io)?;
+    }
+    match storage.get(SALT_FILE_NAME).map_err(io)? {
+        Some(stored) if stored.len() >= MIN_SALT_SIZE => Ok(stored),
+        Some(_) => Err(EncryptionError::IoError("Stored salt is too short".to_string())),
+        None => Err(EncryptionError::IoError("Stored salt vanished after creation".to_string())),
+    }
+}
+
 /// Generate a new salt and save it to disk.
 fn generate_and_save_salt(salt_path: &Path) -> Result<Vec<u8>, EncryptionError> {
     // Generate new salt
@@ -176,6 +204,11 @@ fn get_salt_file_path() -> Result<PathBuf, EncryptionError> {
     }
 }
 
+/// Location of the installation salt file; it may not exist yet.
+pub fn installation_salt_path() -> Result<PathBuf, EncryptionError> {
+    get_salt_file_path()
+}
+
 /// Generate a cryptographically random salt.
 fn generate_random_salt() -> Vec<u8> {
     use rand::RngCore;
@@ -186,14 +219,14 @@ fn generate_random_salt() -> Vec<u8> {
 
 /// Write salt file with restrictive permissions.
 #[cfg(target_os = "windows")]
-fn write_salt_file(path: &Path, salt: &[u8]) -> Result<(), EncryptionError> {
+pub(crate) fn write_salt_file(path: &Path, salt: &[u8]) -> Result<(), EncryptionError> {
     std::fs::write(path, salt)
         .map_err(|e| EncryptionError::IoError(format!("Failed to write salt file: {}", e)))
 }
 
 /// Write salt file with restrictive permissions (Unix).
 #[cfg(not(target_os = "windows"))]
-fn write_salt_file(path: &Path, salt: &[u8]) -> Result<(), EncryptionError> {
+pub(crate) fn write_salt_file(path: &Path, salt: &[u8]) -> Result<(), EncryptionError> {
     use std::os::unix::fs::OpenOptionsExt;
 
     std::fs::OpenOptions::new()
@@ -264,10 +297,7 @@ impl ModelEncryption {
     /// The key is wrapped in `Zeroizing` to ensure it is securely erased on drop.
     pub fn new(key: [u8; KEY_SIZE]) -> Self {
         // Check for AES-NI support
-        #[cfg(target_arch = "x86_64")]
-        let hw_accelerated = is_x86_feature_detected!("aes");
-        #[cfg(not(target_arch = "x86_64"))]
-        let hw_accelerated = false;
+        let hw_accelerated = crate::engine::cpu_dispatch::dispatch().features.aes_ni;
 
         Self {
             key: Zeroizing::new(key),
@@ -588,6 +618,11 @@ impl ModelEncryption {
         self.hw_accelerated
     }
 
+    /// Raw key bytes, for wrapping under a recovery key (see `escrow`).
+    pub(crate) fn key_material(&self) -> &[u8; KEY_SIZE] {
+        &self.key
+    }
+
     /// Generate random nonce using cryptographically secure RNG
     /// Also registers the nonce to detect reuse.
     fn generate_nonce() -> Result<Vec<u8>, EncryptionError> {
@@ -779,37 +814,15 @@ mod tests {
     }
 
     #[test]
-    fn test_performance() {
+    fn test_large_roundtrip() {
+        // Throughput is tracked by the encryption group in benches/regression.rs
         let encryption = ModelEncryption::new(create_test_key());
         let plaintext: Vec<u8> = (0..1_000_000).map(|i| (i % 256) as u8).collect();
 
-        let start = std::time::Instant::now();
         let (nonce, ciphertext) = encryption.encrypt(&plaintext).unwrap();
-        let encrypt_time = start.elapsed();
-
-        let start = std::time::Instant::now();
         let decrypted = encryption.decrypt(&nonce, &ciphertext).unwrap();
-        let decrypt_time = start.elapsed();
 
         assert_eq!(plaintext, decrypted);
-
-        // Release builds: 1 MB must complete in <1 s.
-        // Debug builds: allow 30 s (no optimizations).
-        let max_ms: u128 = if cfg!(debug_assertions) {
-            30_000
-        } else {
-            1_000
-        };
-        assert!(
-            encrypt_time.as_millis() < max_ms,
-            "Encryption too slow: {:?}",
-            encrypt_time
-        );
-        assert!(
-            decrypt_time.as_millis() < max_ms,
-            "Decryption too slow: {:?}",
-            decrypt_time
-        );
     }
 
     #[test]
@@ -895,6 +908,24 @@ mod tests {
         assert_eq!(plaintext.as_slice(), decrypted.as_slice());
     }
 
+    #[test]
+    fn test_stored_salt_is_created_once_and_reread() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = crate::storage::LocalStorage::new(dir.path());
+        let salt = get_or_create_stored_salt(&storage).unwrap();
+        assert_eq!(salt.len(), MIN_SALT_SIZE);
+        assert_eq!(get_or_create_stored_salt(&storage).unwrap(), salt);
+
+        // A replica that lost the create race still uses the stored salt
+        let winner = vec![7u8; MIN_SALT_SIZE];
+        let other = crate::storage::LocalStorage::new(dir.path().join("other"));
+        assert!(other.put_if_absent(SALT_FILE_NAME, &winner).unwrap());
+        assert_eq!(get_or_create_stored_salt(&other).unwrap(), winner);
+
+        other.put(SALT_FILE_NAME, b"short").unwrap();
+        assert!(get_or_create_stored_salt(&other).is_err());
+    }
+
     #[test]
     fn test_encryption_error_display() {
         let err = EncryptionError::InvalidKeySize;
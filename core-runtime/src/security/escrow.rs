@@ -0,0 +1,344 @@
+//! Key escrow for machine-bound model encryption.
+//!
+//! Keys from [`ModelEncryption::from_machine_id`] depend on the hostname and
+//! user, so a rename or rebuild strands every encrypted artifact. When an
+//! operator configures a recovery public key, the derived key is sealed to
+//! it at encryption time and stored beside the artifact as
+//! `<artifact>.escrow.json`. The recovery private key stays offline; with it,
+//! [`rewrap_artifact`] re-encrypts an artifact under the current machine key
+//! and seals the new key to the same recovery key.
+//!
+//! Sealing is ephemeral-static X25519: the wrapping key is
+//! SHA-256(context || shared secret || ephemeral public || recipient public),
+//! used with AES-256-GCM.
+
+use std::fs;
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use x25519_dalek::{x25519, X25519_BASEPOINT_BYTES};
+use zeroize::Zeroizing;
+
+use super::encryption::{ModelEncryption, KEY_SIZE};
+use super::fips_mode::{self, Algorithm, FipsError};
+
+/// Current escrow record version.
+pub const ESCROW_VERSION: u32 = 1;
+
+const KDF_CONTEXT: &[u8] = b"gg-core model key escrow v1";
+/// X25519 scalar and point size.
+const KEY_LEN: usize = 32;
+
+#[derive(Error, Debug)]
+pub enum EscrowError {
+    #[error("IO error on {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Malformed escrow record {path}: {source}")]
+    Parse {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error("Invalid recovery key: {0}")]
+    InvalidKey(String),
+
+    #[error("Unsupported escrow record version {0}")]
+    UnsupportedVersion(u32),
+
+    #[error("Escrow was sealed to recovery key {expected}, not {found}")]
+    KeyMismatch { expected: String, found: String },
+
+    #[error("Cannot unseal escrowed key: record is corrupted")]
+    Unseal,
+
+    #[error("Encryption error: {0}")]
+    Encryption(String),
+
+    #[error(transparent)]
+    Fips(#[from] FipsError),
+}
+
+fn io_err(path: &Path) -> impl FnOnce(std::io::Error) -> EscrowError + '_ {
+    move |source| EscrowError::Io { path: path.to_path_buf(), source }
+}
+
+fn parse_key(text: &str) -> Result<[u8; KEY_LEN], EscrowError> {
+    hex::decode(text.trim())
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| EscrowError::InvalidKey(format!("expected {} hex-encoded bytes", KEY_LEN)))
+}
+
+fn read_key_file(path: &Path) -> Result<[u8; KEY_LEN], EscrowError> {
+    let text = Zeroizing::new(fs::read_to_string(path).map_err(io_err(path))?);
+    parse_key(&text)
+}
+
+/// Operator recovery key that escrowed model keys are sealed to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecoveryPublicKey([u8; KEY_LEN]);
+
+impl RecoveryPublicKey {
+    pub fn from_hex(text: &str) -> Result<Self, EscrowError> {
+        parse_key(text).map(Self)
+    }
+
+    /// Read a key file holding the hex-encoded key.
+    pub fn load(path: &Path) -> Result<Self, EscrowError> {
+        read_key_file(path).map(Self)
+    }
+
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.0)
+    }
+
+    /// Short fingerprint recorded in escrow records.
+    pub fn key_id(&self) -> String {
+        hex::encode(&Sha256::digest(self.0)[..8])
+    }
+}
+
+/// Offline half of the recovery key pair.
+pub struct RecoveryPrivateKey(Zeroizing<[u8; KEY_LEN]>);
+
+impl RecoveryPrivateKey {
+    pub fn generate() -> Self {
+        let mut key = Zeroizing::new([0u8; KEY_LEN]);
+        rand::rngs::OsRng.fill_bytes(key.as_mut());
+        Self(key)
+    }
+
+    pub fn from_hex(text: &str) -> Result<Self, EscrowError> {
+        parse_key(text).map(|k| Self(Zeroizing::new(k)))
+    }
+
+    pub fn load(path: &Path) -> Result<Self, EscrowError> {
+        read_key_file(path).map(|k| Self(Zeroizing::new(k)))
+    }
+
+    pub fn public_key(&self) -> RecoveryPublicKey {
+        RecoveryPublicKey(x25519(*self.0, X25519_BASEPOINT_BYTES))
+    }
+
+    /// Write the key to a new file readable only by the owner.
+    pub fn save(&self, path: &Path) -> Result<(), EscrowError> {
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let text = Zeroizing::new(format!("{}\n", hex::encode(*self.0)));
+        options
+            .open(path)
+            .and_then(|mut file| std::io::Write::write_all(&mut file, text.as_bytes()))
+            .map_err(io_err(path))
+    }
+}
+
+/// A model key sealed to a recovery public key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EscrowRecord {
+    pub version: u32,
+    pub recovery_key_id: String,
+    pub ephemeral_public: String,
+    pub nonce: String,
+    /// AES-256-GCM ciphertext of the model key, tag appended.
+    pub wrapped_key: String,
+}
+
+/// Escrow uses X25519 and a non-approved KDF, so fips-strict refuses it.
+fn require_algorithms() -> Result<(), EscrowError> {
+    fips_mode::require(Algorithm::X25519)?;
+    fips_mode::require(Algorithm::Sha256ConcatKdf)?;
+    Ok(())
+}
+
+fn wrapping_key(shared: &[u8; KEY_LEN], ephemeral: &[u8; KEY_LEN], recipient: &[u8; KEY_LEN]) -> ModelEncryption {
+    let mut hasher = Sha256::new();
+    hasher.update(KDF_CONTEXT);
+    hasher.update(shared);
+    hasher.update(ephemeral);
+    hasher.update(recipient);
+    let mut key = Zeroizing::new([0u8; KEY_SIZE]);
+    key.copy_from_slice(&hasher.finalize());
+    ModelEncryption::new(*key)
+}
+
+impl EscrowRecord {
+    /// Seal `key` so that only the holder of `recipient`'s private key can recover it.
+    pub fn seal(key: &ModelEncryption, recipient: &RecoveryPublicKey) -> Result<Self, EscrowError> {
+        require_algorithms()?;
+        let ephemeral = RecoveryPrivateKey::generate();
+        let ephemeral_public = ephemeral.public_key();
+        let shared = Zeroizing::new(x25519(*ephemeral.0, recipient.0));
+        if shared.iter().all(|b| *b == 0) {
+            return Err(EscrowError::InvalidKey("low-order public key".into()));
+        }
+        let (nonce, wrapped) = wrapping_key(&shared, &ephemeral_public.0, &recipient.0)
+            .encrypt(key.key_material())
+            .map_err(|e| EscrowError::Encryption(e.to_string()))?;
+        Ok(Self {
+            version: ESCROW_VERSION,
+            recovery_key_id: recipient.key_id(),
+            ephemeral_public: ephemeral_public.to_hex(),
+            nonce: hex::encode(nonce),
+            wrapped_key: hex::encode(wrapped),
+        })
+    }
+
+    /// Recover the sealed model key.
+    pub fn open(&self, recovery: &RecoveryPrivateKey) -> Result<ModelEncryption, EscrowError> {
+        require_algorithms()?;
+        if self.version != ESCROW_VERSION {
+            return Err(EscrowError::UnsupportedVersion(self.version));
+        }
+        let public = recovery.public_key();
+        if public.key_id() != self.recovery_key_id {
+            return Err(EscrowError::KeyMismatch {
+                expected: self.recovery_key_id.clone(),
+                found: public.key_id(),
+            });
+        }
+        let ephemeral = parse_key(&self.ephemeral_public).map_err(|_| EscrowError::Unseal)?;
+        let nonce = hex::decode(&self.nonce).map_err(|_| EscrowError::Unseal)?;
+        let wrapped = hex::decode(&self.wrapped_key).map_err(|_| EscrowError::Unseal)?;
+
+        let shared = Zeroizing::new(x25519(*recovery.0, ephemeral));
+        let key = Zeroizing::new(
+            wrapping_key(&shared, &ephemeral, &public.0)
+                .decrypt(&nonce, &wrapped)
+                .map_err(|_| EscrowError::Unseal)?,
+        );
+        let key: [u8; KEY_SIZE] = key.as_slice().try_into().map_err(|_| EscrowError::Unseal)?;
+        Ok(ModelEncryption::new(key))
+    }
+}
+
+fn sidecar(artifact: &Path, suffix: &str) -> PathBuf {
+    let mut name = artifact.as_os_str().to_owned();
+    name.push(".");
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// Escrow record location for an encrypted artifact.
+pub fn escrow_path(artifact: &Path) -> PathBuf {
+    sidecar(artifact, "escrow.json")
+}
+
+fn write_record(path: &Path, record: &EscrowRecord) -> Result<(), EscrowError> {
+    let tmp = path.with_extension("json.tmp");
+    let json = serde_json::to_vec_pretty(record).map_err(|source| EscrowError::Parse {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    fs::write(&tmp, json).map_err(io_err(&tmp))?;
+    fs::rename(&tmp, path).map_err(io_err(path))
+}
+
+/// Seal `key` to `recipient` and store the record beside `artifact`.
+pub fn write_escrow(artifact: &Path, key: &ModelEncryption, recipient: &RecoveryPublicKey) -> Result<PathBuf, EscrowError> {
+    let path = escrow_path(artifact);
+    write_record(&path, &EscrowRecord::seal(key, recipient)?)?;
+    Ok(path)
+}
+
+pub fn read_escrow(artifact: &Path) -> Result<EscrowRecord, EscrowError> {
+    let path = escrow_path(artifact);
+    let bytes = fs::read(&path).map_err(io_err(&path))?;
+    serde_json::from_slice(&bytes).map_err(|source| EscrowError::Parse { path, source })
+}
+
+fn sha256_file(path: &Path) -> Result<String, EscrowError> {
+    let mut reader = BufReader::new(fs::File::open(path).map_err(io_err(path))?);
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; 1 << 20];
+    loop {
+        let n = reader.read(&mut buf).map_err(io_err(path))?;
+        if n == 0 {
+            return Ok(hex::encode(hasher.finalize()));
+        }
+        hasher.update(&buf[..n]);
+    }
+}
+
+/// Re-encrypt an escrowed artifact under `new_key` and re-seal the new key
+/// to the same recovery key. A `.sha256` sidecar is refreshed if present.
+pub fn rewrap_artifact(artifact: &Path, recovery: &RecoveryPrivateKey, new_key: &ModelEncryption) -> Result<(), EscrowError> {
+    let old_key = read_escrow(artifact)?.open(recovery)?;
+    let dir = artifact
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let encryption = |e: super::encryption::EncryptionError| EscrowError::Encryption(e.to_string());
+
+    // Plaintext only ever lives in a temp file removed on drop
+    let plain = tempfile::NamedTempFile::new_in(dir).map_err(io_err(dir))?;
+    old_key.decrypt_file(artifact, plain.path()).map_err(encryption)?;
+    let rewrapped = tempfile::NamedTempFile::new_in(dir).map_err(io_err(dir))?;
+    new_key.encrypt_file(plain.path(), rewrapped.path()).map_err(encryption)?;
+    drop(plain);
+    let record = EscrowRecord::seal(new_key, &recovery.public_key())?;
+
+    // Artifact first: if we stop before the record is replaced, the file is
+    // still readable with the current machine key.
+    rewrapped.persist(artifact).map_err(|e| io_err(artifact)(e.error))?;
+    write_record(&escrow_path(artifact), &record)?;
+
+    let checksum = sidecar(artifact, "sha256");
+    if checksum.exists() {
+        let file_name = artifact.file_name().unwrap_or_default().to_string_lossy();
+        let line = format!("{}  {}\n", sha256_file(artifact)?, file_name);
+        fs::write(&checksum, line).map_err(io_err(&checksum))?;
+    }
+    Ok(())
+}
+
+#[cfg(all(test, not(feature = "fips-strict")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_open_roundtrip() {
+        let recovery = RecoveryPrivateKey::generate();
+        let key = ModelEncryption::new([42u8; KEY_SIZE]);
+        let record = EscrowRecord::seal(&key, &recovery.public_key()).unwrap();
+        assert_eq!(record.recovery_key_id, recovery.public_key().key_id());
+
+        let opened = record.open(&recovery).unwrap();
+        assert_eq!(opened.key_material(), key.key_material());
+    }
+
+    #[test]
+    fn test_wrong_recovery_key_is_rejected() {
+        let key = ModelEncryption::new([1u8; KEY_SIZE]);
+        let record = EscrowRecord::seal(&key, &RecoveryPrivateKey::generate().public_key()).unwrap();
+        assert!(matches!(
+            record.open(&RecoveryPrivateKey::generate()),
+            Err(EscrowError::KeyMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_tampered_record_fails_to_open() {
+        let recovery = RecoveryPrivateKey::generate();
+        let key = ModelEncryption::new([3u8; KEY_SIZE]);
+        let mut record = EscrowRecord::seal(&key, &recovery.public_key()).unwrap();
+        let mut wrapped = hex::decode(&record.wrapped_key).unwrap();
+        wrapped[0] ^= 1;
+        record.wrapped_key = hex::encode(wrapped);
+        assert!(matches!(record.open(&recovery), Err(EscrowError::Unseal)));
+    }
+}
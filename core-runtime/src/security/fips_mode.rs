@@ -0,0 +1,282 @@
+//! FIPS-strict algorithm allow-list.
+//!
+//! In strict mode only FIPS-approved algorithms may be constructed. Code
+//! that sets up a non-approved cipher, KDF or key agreement calls
+//! [`require`] first; under strict mode the call fails with
+//! [`FipsError::NotApproved`] and the refusal is audited.
+//!
+//! Strict mode is fixed for a build by the `fips-strict` cargo feature, or
+//! chosen at startup with `CORE_FIPS_MODE=strict`. Before serving,
+//! [`verify_allow_list`] walks every algorithm the build links: approved
+//! ones must pass their known answer test, and under strict mode the
+//! others must be refused.
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::audit::{audit_logger, AuditCategory, AuditEvent, AuditSeverity};
+use super::fips_tests::{self, SelfTestError};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FipsMode {
+    /// Non-approved algorithms are allowed.
+    #[default]
+    Off,
+    /// Only approved algorithms are constructible.
+    Strict,
+}
+
+impl FipsMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Strict => "strict",
+        }
+    }
+
+    /// Whether `algorithm` may be constructed in this mode.
+    pub fn check(&self, algorithm: Algorithm) -> Result<(), FipsError> {
+        if *self == Self::Strict && !algorithm.approved() {
+            return Err(FipsError::NotApproved(algorithm.name()));
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for FipsMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl FromStr for FipsMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "off" | "" => Ok(Self::Off),
+            "strict" | "fips-strict" => Ok(Self::Strict),
+            other => Err(format!("unknown FIPS mode '{}' (off, strict)", other)),
+        }
+    }
+}
+
+/// Cryptographic algorithms the runtime knows about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    /// Model and backup encryption.
+    Aes256Gcm,
+    /// Password and machine-bound key derivation.
+    Pbkdf2HmacSha256,
+    /// Hashing and fingerprints.
+    Sha256,
+    /// Purge receipt signatures.
+    HmacSha256,
+    /// Model provenance attestation signatures.
+    EcdsaP256,
+    /// Recovery-key escrow key agreement.
+    X25519,
+    /// Escrow wrapping-key derivation. It has no counter, so it is not the
+    /// SP 800-56C one-step KDF.
+    Sha256ConcatKdf,
+    ChaCha20Poly1305,
+}
+
+impl Algorithm {
+    pub const ALL: [Algorithm; 8] = [
+        Algorithm::Aes256Gcm,
+        Algorithm::Pbkdf2HmacSha256,
+        Algorithm::Sha256,
+        Algorithm::HmacSha256,
+        Algorithm::EcdsaP256,
+        Algorithm::X25519,
+        Algorithm::Sha256ConcatKdf,
+        Algorithm::ChaCha20Poly1305,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Aes256Gcm => "AES-256-GCM",
+            Self::Pbkdf2HmacSha256 => "PBKDF2-HMAC-SHA256",
+            Self::Sha256 => "SHA-256",
+            Self::HmacSha256 => "HMAC-SHA256",
+            Self::EcdsaP256 => "ECDSA P-256",
+            Self::X25519 => "X25519",
+            Self::Sha256ConcatKdf => "SHA-256 concat KDF",
+            Self::ChaCha20Poly1305 => "ChaCha20-Poly1305",
+        }
+    }
+
+    /// On the FIPS 140-3 approved list.
+    pub fn approved(&self) -> bool {
+        matches!(self, Self::Aes256Gcm | Self::Pbkdf2HmacSha256 | Self::Sha256 | Self::HmacSha256 | Self::EcdsaP256)
+    }
+
+    /// Compiled into this build.
+    pub fn linked(&self) -> bool {
+        !matches!(self, Self::ChaCha20Poly1305)
+    }
+
+    /// Known answer test for an approved algorithm.
+    fn kat(&self) -> Option<fn() -> Result<(), SelfTestError>> {
+        match self {
+            Self::Aes256Gcm => Some(fips_tests::aes_gcm_kat),
+            Self::Pbkdf2HmacSha256 => Some(fips_tests::pbkdf2_kat),
+            Self::Sha256 => Some(fips_tests::sha256_kat),
+            Self::HmacSha256 => Some(fips_tests::hmac_sha256_kat),
+            Self::EcdsaP256 => Some(fips_tests::ecdsa_p256_kat),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum FipsError {
+    #[error("{0} is not FIPS-approved and is disabled in fips-strict mode")]
+    NotApproved(&'static str),
+
+    #[error("FIPS allow-list check failed for {algorithm}: {reason}")]
+    AllowList { algorithm: &'static str, reason: String },
+}
+
+static MODE: OnceLock<FipsMode> = OnceLock::new();
+
+/// Fix the mode for the process. A `fips-strict` build is strict whatever
+/// is requested; otherwise the first call wins.
+pub fn init(requested: FipsMode) -> FipsMode {
+    *MODE.get_or_init(|| if cfg!(feature = "fips-strict") { FipsMode::Strict } else { requested })
+}
+
+/// The process mode; the build default if `init` was never called.
+pub fn mode() -> FipsMode {
+    init(FipsMode::Off)
+}
+
+/// Refuse to construct `algorithm` unless the process mode allows it.
+pub fn require(algorithm: Algorithm) -> Result<(), FipsError> {
+    let result = mode().check(algorithm);
+    if let Err(e) = &result {
+        tracing::warn!(algorithm = algorithm.name(), "{}", e);
+        audit_refusal(algorithm);
+    }
+    result
+}
+
+fn audit_refusal(algorithm: Algorithm) {
+    let (Some(logger), Ok(runtime)) = (audit_logger(), tokio::runtime::Handle::try_current()) else {
+        return;
+    };
+    let Ok(event) = AuditEvent::builder()
+        .severity(AuditSeverity::Warning)
+        .category(AuditCategory::Encryption)
+        .event_type("fips_algorithm_refused")
+        .message(format!("Refused non-approved algorithm {}", algorithm.name()))
+        .source("fips_mode")
+        .success(false)
+        .metadata("algorithm", algorithm.name())
+        .metadata("fips_mode", mode().as_str())
+        .build()
+    else {
+        return;
+    };
+    runtime.spawn(async move { logger.log(event).await });
+}
+
+/// Where one algorithm stands under the allow-list.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AlgorithmStatus {
+    pub algorithm: &'static str,
+    pub approved: bool,
+    /// May be constructed in the current mode.
+    pub allowed: bool,
+}
+
+/// Result of checking the linked algorithms against the allow-list.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AllowListReport {
+    pub mode: FipsMode,
+    pub algorithms: Vec<AlgorithmStatus>,
+}
+
+impl AllowListReport {
+    /// Linked algorithms `mode` refuses.
+    pub fn blocked(&self) -> Vec<&'static str> {
+        self.algorithms.iter().filter(|a| !a.allowed).map(|a| a.algorithm).collect()
+    }
+
+    /// Record the allow-list as a startup audit event.
+    pub async fn audit(&self) {
+        let Some(logger) = audit_logger() else {
+            return;
+        };
+        let allowed: Vec<&str> = self.algorithms.iter().filter(|a| a.allowed).map(|a| a.algorithm).collect();
+        let Ok(event) = AuditEvent::builder()
+            .severity(AuditSeverity::Info)
+            .category(AuditCategory::Encryption)
+            .event_type("fips_mode")
+            .message(format!("FIPS mode {}", self.mode))
+            .source("fips_mode")
+            .metadata("fips_mode", self.mode.as_str())
+            .metadata("allowed", allowed.join(","))
+            .metadata("blocked", self.blocked().join(","))
+            .build()
+        else {
+            return;
+        };
+        logger.log(event).await;
+    }
+}
+
+/// Check every linked algorithm against the allow-list for `mode`:
+/// approved ones must pass their known answer test, and in strict mode
+/// the rest must be refused.
+pub fn verify_allow_list(mode: FipsMode) -> Result<AllowListReport, FipsError> {
+    let mut algorithms = Vec::new();
+    for algorithm in Algorithm::ALL.into_iter().filter(Algorithm::linked) {
+        let fail = |reason: String| FipsError::AllowList { algorithm: algorithm.name(), reason };
+        if algorithm.approved() {
+            let kat = algorithm.kat().ok_or_else(|| fail("no known answer test".into()))?;
+            kat().map_err(|e| fail(e.to_string()))?;
+        }
+        let allowed = mode.check(algorithm).is_ok();
+        if mode == FipsMode::Strict && allowed && !algorithm.approved() {
+            return Err(fail("non-approved algorithm is constructible".into()));
+        }
+        algorithms.push(AlgorithmStatus { algorithm: algorithm.name(), approved: algorithm.approved(), allowed });
+    }
+    Ok(AllowListReport { mode, algorithms })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strict_refuses_non_approved() {
+        assert!(FipsMode::Off.check(Algorithm::ChaCha20Poly1305).is_ok());
+        assert_eq!(
+            FipsMode::Strict.check(Algorithm::ChaCha20Poly1305),
+            Err(FipsError::NotApproved("ChaCha20-Poly1305"))
+        );
+        assert!(FipsMode::Strict.check(Algorithm::X25519).is_err());
+        assert!(FipsMode::Strict.check(Algorithm::Aes256Gcm).is_ok());
+        assert_eq!("STRICT".parse::<FipsMode>(), Ok(FipsMode::Strict));
+        assert!("lenient".parse::<FipsMode>().is_err());
+    }
+
+    #[test]
+    fn test_verify_allow_list() {
+        let off = verify_allow_list(FipsMode::Off).unwrap();
+        assert!(off.blocked().is_empty());
+        assert!(!off.algorithms.iter().any(|a| a.algorithm == "ChaCha20-Poly1305"));
+
+        let strict = verify_allow_list(FipsMode::Strict).unwrap();
+        assert_eq!(strict.blocked(), ["X25519", "SHA-256 concat KDF"]);
+        assert!(strict.algorithms.iter().filter(|a| a.approved).all(|a| a.allowed));
+    }
+}
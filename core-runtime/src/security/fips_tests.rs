@@ -3,6 +3,8 @@
 //! Provides power-on self-tests (POST) for cryptographic algorithm validation:
 //! - Known Answer Tests (KAT) for AES-256-GCM
 //! - Known Answer Tests (KAT) for PBKDF2-SHA256
+//! - Known Answer Tests (KAT) for SHA-256 and HMAC-SHA256
+//! - Known Answer Test (KAT) for ECDSA P-256 signature verification
 //! - Continuous RNG health testing
 //! - Integrity self-tests
 //!
@@ -14,7 +16,7 @@ use aes_gcm::{
     Aes256Gcm, Nonce,
 };
 use pbkdf2::pbkdf2_hmac;
-use sha2::Sha256;
+use sha2::{Digest, Sha256};
 
 /// Self-test error types
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -25,6 +27,8 @@ pub enum SelfTestError {
     AesGcmDecryptFailed,
     /// PBKDF2 KAT failed
     Pbkdf2Failed,
+    /// ECDSA P-256 KAT failed
+    EcdsaFailed,
     /// RNG health test failed
     RngHealthFailed,
     /// Integrity test failed
@@ -39,6 +43,7 @@ impl std::fmt::Display for SelfTestError {
             Self::AesGcmEncryptFailed => write!(f, "AES-GCM encryption KAT failed"),
             Self::AesGcmDecryptFailed => write!(f, "AES-GCM decryption KAT failed"),
             Self::Pbkdf2Failed => write!(f, "PBKDF2-SHA256 KAT failed"),
+            Self::EcdsaFailed => write!(f, "ECDSA P-256 KAT failed"),
             Self::RngHealthFailed => write!(f, "RNG health test failed"),
             Self::IntegrityFailed => write!(f, "Integrity self-test failed"),
             Self::KatMismatch { .. } => write!(f, "KAT output mismatch"),
@@ -177,6 +182,80 @@ pub fn pbkdf2_kat() -> Result<(), SelfTestError> {
     Ok(())
 }
 
+/// SHA-256 Known Answer Test
+///
+/// FIPS 180-4 example "abc".
+pub fn sha256_kat() -> Result<(), SelfTestError> {
+    let expected: [u8; 32] = [
+        0xba, 0x78, 0x16, 0xbf, 0x8f, 0x01, 0xcf, 0xea, 0x41, 0x41, 0x40, 0xde, 0x5d, 0xae, 0x22,
+        0x23, 0xb0, 0x03, 0x61, 0xa3, 0x96, 0x17, 0x7a, 0x9c, 0xb4, 0x10, 0xff, 0x61, 0xf2, 0x00,
+        0x15, 0xad,
+    ];
+    let output: [u8; 32] = Sha256::digest(b"abc").into();
+
+    if output != expected {
+        return Err(SelfTestError::KatMismatch {
+            expected: expected.to_vec(),
+            actual: output.to_vec(),
+        });
+    }
+
+    Ok(())
+}
+
+/// HMAC-SHA256 Known Answer Test
+///
+/// RFC 4231 test case 2, run through the receipt signer's HMAC.
+pub fn hmac_sha256_kat() -> Result<(), SelfTestError> {
+    let expected: [u8; 32] = [
+        0x5b, 0xdc, 0xc1, 0x46, 0xbf, 0x60, 0x75, 0x4e, 0x6a, 0x04, 0x24, 0x26, 0x08, 0x95, 0x75,
+        0xc7, 0x5a, 0x00, 0x3f, 0x08, 0x9d, 0x27, 0x39, 0x83, 0x9d, 0xec, 0x58, 0xb9, 0x64, 0xec,
+        0x38, 0x43,
+    ];
+    let output = super::receipt::hmac_sha256(b"Jefe", b"what do ya want for nothing?");
+
+    if output != expected {
+        return Err(SelfTestError::KatMismatch {
+            expected: expected.to_vec(),
+            actual: output.to_vec(),
+        });
+    }
+
+    Ok(())
+}
+
+/// ECDSA P-256 Known Answer Test
+///
+/// Verifies a fixed SHA-256 signature, and that it fails for another
+/// message.
+pub fn ecdsa_p256_kat() -> Result<(), SelfTestError> {
+    use p256::ecdsa::signature::Verifier;
+    use p256::ecdsa::{Signature, VerifyingKey};
+
+    let public_key: [u8; 65] = [
+        0x04, 0xd6, 0xff, 0x7a, 0xf8, 0xf3, 0xbb, 0x64, 0xf8, 0x71, 0xb9, 0x0c, 0xeb, 0x93, 0x9a,
+        0x2d, 0xc6, 0x09, 0xf0, 0x7d, 0x00, 0x69, 0xfe, 0x53, 0x36, 0xd8, 0x28, 0x7b, 0x2f, 0xa9,
+        0x7e, 0xc7, 0x40, 0xe2, 0x15, 0x42, 0x24, 0x2d, 0x88, 0xb2, 0xc4, 0xd1, 0xe5, 0x62, 0x26,
+        0x77, 0x9a, 0x64, 0x69, 0x27, 0x70, 0x4f, 0x13, 0xfd, 0x0f, 0x0b, 0x5b, 0x1c, 0xfe, 0xde,
+        0xe6, 0xab, 0x41, 0x06, 0xc0,
+    ];
+    let signature: [u8; 70] = [
+        0x30, 0x44, 0x02, 0x20, 0x0e, 0x5d, 0xbb, 0xd5, 0x7e, 0xb7, 0xa3, 0x00, 0x04, 0xd0, 0xef,
+        0x02, 0xf5, 0xd0, 0x6a, 0xb3, 0xd6, 0x8b, 0xdd, 0xbe, 0xbb, 0xb5, 0xdf, 0x4f, 0x8c, 0x58,
+        0x65, 0xa8, 0x36, 0xf3, 0xe3, 0xe2, 0x02, 0x20, 0x71, 0x62, 0x75, 0xf0, 0x4d, 0xe8, 0x2e,
+        0xa4, 0xfe, 0xa8, 0x6e, 0xfd, 0x20, 0x41, 0xa0, 0x6f, 0xe6, 0xf0, 0xf1, 0x0e, 0xcb, 0x3a,
+        0x13, 0x4a, 0x89, 0x43, 0x30, 0xa4, 0xd4, 0xac, 0x70, 0x1b,
+    ];
+    let key = VerifyingKey::from_sec1_bytes(&public_key).map_err(|_| SelfTestError::EcdsaFailed)?;
+    let signature = Signature::from_der(&signature).map_err(|_| SelfTestError::EcdsaFailed)?;
+
+    if key.verify(b"FIPS 186-5 KAT", &signature).is_err() || key.verify(b"FIPS 186-5 KAT!", &signature).is_ok() {
+        return Err(SelfTestError::EcdsaFailed);
+    }
+
+    Ok(())
+}
+
 /// RNG Health Test (Continuous)
 ///
 /// Verifies the random number generator produces non-repeating output.
@@ -266,6 +345,13 @@ mod tests {
         assert!(pbkdf2_kat().is_ok());
     }
 
+    #[test]
+    fn test_sha256_and_hmac_kat() {
+        assert!(sha256_kat().is_ok());
+        assert!(hmac_sha256_kat().is_ok());
+        assert!(ecdsa_p256_kat().is_ok());
+    }
+
     #[test]
     fn test_rng_health() {
         assert!(rng_health_test().is_ok());
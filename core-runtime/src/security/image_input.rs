@@ -0,0 +1,84 @@
+//! Image parts on inference requests, and the base64 codec they use.
+//!
+//! Images arrive base64-encoded inside the request or as a reference to a
+//! POSIX shared-memory object. No vision backend is linked, so requests
+//! carrying image parts are rejected at validation; the parts are parsed
+//! only so that rejection is explicit rather than a silent drop.
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ImageError {
+    #[error("Invalid base64 image data")]
+    InvalidBase64,
+}
+
+/// Where the image bytes come from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "source", rename_all = "snake_case")]
+pub enum ImagePart {
+    /// Standard-alphabet base64, padding optional.
+    Base64 { data: String },
+    /// POSIX shared-memory object name (as passed to `shm_open`) and length.
+    Shm { name: String, len: usize },
+}
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encode bytes as standard padded base64.
+pub fn encode_base64(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = u32::from_be_bytes([0, b[0], b[1], b[2]]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(BASE64_ALPHABET[(n >> (18 - 6 * i) & 0x3F) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// Decode standard base64, ignoring ASCII whitespace.
+pub fn decode_base64(input: &str) -> Result<Vec<u8>, ImageError> {
+    let mut out = Vec::with_capacity(input.len() / 4 * 3);
+    let (mut acc, mut bits) = (0u32, 0u32);
+    let body = input.trim_end_matches(|c: char| c == '=' || c.is_ascii_whitespace());
+    for byte in body.bytes().filter(|b| !b.is_ascii_whitespace()) {
+        let value = BASE64_ALPHABET
+            .iter()
+            .position(|&c| c == byte)
+            .ok_or(ImageError::InvalidBase64)? as u32;
+        acc = (acc << 6) | value;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((acc >> bits) as u8);
+            acc &= (1 << bits) - 1;
+        }
+    }
+    if bits >= 6 {
+        return Err(ImageError::InvalidBase64); // a lone trailing character
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64_roundtrip() {
+        for input in [&b""[..], b"f", b"fo", b"foo", b"foob", b"\x00\xff\x10"] {
+            assert_eq!(decode_base64(&encode_base64(input)).unwrap(), input);
+        }
+        assert_eq!(encode_base64(b"foob"), "Zm9vYg==");
+        assert_eq!(decode_base64("Zm9v\nYg").unwrap(), b"foob");
+        assert_eq!(decode_base64("Zm9v!"), Err(ImageError::InvalidBase64));
+    }
+}
@@ -0,0 +1,89 @@
+//! Audit events for sampled, captured and held inference.
+
+use sha2::{Digest, Sha256};
+
+use super::{LegalHold, LegalHoldError};
+use crate::security::audit::{audit_logger, AuditCategory, AuditEvent, AuditSeverity};
+
+fn sha256_hex(data: &str) -> String {
+    hex::encode(Sha256::digest(data.as_bytes()))
+}
+
+/// A completed inference, borrowed for auditing.
+pub(super) struct Inference<'a> {
+    pub(super) tenant: Option<&'a str>,
+    pub(super) request_id: &'a str,
+    pub(super) model_id: &'a str,
+    pub(super) prompt: &'a str,
+    pub(super) output: &'a str,
+}
+
+impl Inference<'_> {
+    /// Hash-only event; the content itself never enters the audit log.
+    pub(super) fn audit(&self, event_type: &str, message: &str, record: Option<String>) {
+        let (Some(logger), Ok(runtime)) = (audit_logger(), tokio::runtime::Handle::try_current()) else {
+            return;
+        };
+        let mut builder = AuditEvent::builder()
+            .severity(AuditSeverity::Info)
+            .category(AuditCategory::DataAccess)
+            .event_type(event_type)
+            .message(message)
+            .source("legal_hold")
+            .metadata("tenant", self.tenant.unwrap_or("anonymous"))
+            .metadata("request_id", self.request_id)
+            .metadata("model_id", self.model_id)
+            .metadata("prompt_sha256", sha256_hex(self.prompt))
+            .metadata("output_sha256", sha256_hex(self.output));
+        if let Some(record) = record {
+            builder = builder.metadata("record", record);
+        }
+        let Ok(event) = builder.build() else {
+            return;
+        };
+        runtime.spawn(async move { logger.log(event).await });
+    }
+
+    pub(super) fn audit_capture_failure(&self, error: &LegalHoldError) {
+        let (Some(logger), Ok(runtime)) = (audit_logger(), tokio::runtime::Handle::try_current()) else {
+            return;
+        };
+        let Ok(event) = AuditEvent::builder()
+            .severity(AuditSeverity::Critical)
+            .category(AuditCategory::DataAccess)
+            .event_type("legal_hold_capture_failed")
+            .message(format!("Inference under legal hold was not captured: {}", error))
+            .source("legal_hold")
+            .success(false)
+            .metadata("tenant", self.tenant.unwrap_or("anonymous"))
+            .metadata("request_id", self.request_id)
+            .build()
+        else {
+            return;
+        };
+        runtime.spawn(async move { logger.log(event).await });
+    }
+}
+
+pub(super) fn audit_hold(event_type: &str, message: &str, hold: &LegalHold, actor: Option<&str>) {
+    let (Some(logger), Ok(runtime)) = (audit_logger(), tokio::runtime::Handle::try_current()) else {
+        return;
+    };
+    let mut builder = AuditEvent::builder()
+        .severity(AuditSeverity::Critical)
+        .category(AuditCategory::DataAccess)
+        .event_type(event_type)
+        .message(format!("{} for {}", message, hold.tenant))
+        .source("legal_hold")
+        .metadata("tenant", hold.tenant.as_str())
+        .metadata("reason", hold.reason.as_str())
+        .metadata("expires_at", hold.expires_at.to_rfc3339())
+        .metadata("records", hold.records.to_string());
+    if let Some(actor) = actor {
+        builder = builder.metadata("actor", actor);
+    }
+    let Ok(event) = builder.build() else {
+        return;
+    };
+    runtime.spawn(async move { logger.log(event).await });
+}
@@ -0,0 +1,130 @@
+//! Per-inference audit planning and sealed capture.
+
+use chrono::{DateTime, Utc};
+
+use super::audit::Inference;
+use super::{AuditPlan, LegalHoldManager, SealedRecord};
+use crate::telemetry::tenant_label;
+
+impl LegalHoldManager {
+    /// Decide, before an inference runs, how it is audited: sealed in full
+    /// when its tenant is held, hashed when sampled, otherwise not at all.
+    /// A hold overrides the client's `no_log`; sampling does not.
+    /// `principal` is the session's stable identity, so a hold keeps
+    /// matching its tenant across reconnects.
+    pub fn plan(&self, principal: Option<&str>, no_log: bool) -> Option<AuditPlan> {
+        let now = Utc::now();
+        self.sweep_if_due(now);
+        let tenant = principal.map(tenant_label);
+        if let Some(held) = tenant.as_deref().filter(|t| self.is_held(t, now)) {
+            return Some(AuditPlan::Capture { tenant: held.to_string() });
+        }
+        let sampled = !no_log && self.config.sample_rate > 0.0 && rand::random::<f64>() < self.config.sample_rate;
+        sampled.then_some(AuditPlan::Sample { tenant })
+    }
+
+    /// Audit a completed inference as planned.
+    pub fn record(&self, plan: AuditPlan, request_id: &str, model_id: &str, prompt: &str, output: &str) {
+        match &plan {
+            AuditPlan::Capture { tenant } => {
+                let inference = Inference { tenant: Some(tenant.as_str()), request_id, model_id, prompt, output };
+                self.capture(&inference, Utc::now());
+            }
+            AuditPlan::Sample { tenant } => {
+                let inference = Inference { tenant: tenant.as_deref(), request_id, model_id, prompt, output };
+                inference.audit("inference_sampled", "Inference sampled for audit", None);
+            }
+        }
+    }
+
+    pub(super) fn is_held(&self, tenant: &str, now: DateTime<Utc>) -> bool {
+        let holds = self.holds.lock().unwrap_or_else(|e| e.into_inner());
+        holds.get(tenant).is_some_and(|h| h.is_active(now))
+    }
+
+    fn capture(&self, inference: &Inference<'_>, now: DateTime<Utc>) {
+        let tenant = inference.tenant.unwrap_or_default();
+        let record = SealedRecord {
+            tenant: tenant.to_string(),
+            request_id: inference.request_id.to_string(),
+            model_id: inference.model_id.to_string(),
+            captured_at: now,
+            prompt: inference.prompt.to_string(),
+            output: inference.output.to_string(),
+        };
+        match self.store().and_then(|store| store.seal(&record)) {
+            Ok(path) => {
+                let mut holds = self.holds.lock().unwrap_or_else(|e| e.into_inner());
+                if let Some(hold) = holds.get_mut(tenant) {
+                    hold.records += 1;
+                }
+                if let Err(e) = self.save(&holds) {
+                    tracing::error!(error = %e, "Cannot save legal holds");
+                }
+                drop(holds);
+                let file = path.file_name().map(|f| f.to_string_lossy().into_owned());
+                inference.audit("inference_captured", "Inference sealed under legal hold", file);
+            }
+            Err(e) => {
+                tracing::error!(tenant, error = %e, "Legal hold capture failed");
+                inference.audit_capture_failure(&e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::security::legal_hold::store::{records_dir, KEY_LEN};
+    use crate::security::legal_hold::{
+        default_key_path, default_store_dir, export, LegalHoldConfig, LegalHoldError, ACCESS_LOG_FILE,
+    };
+
+    fn manager(dir: &Path) -> LegalHoldManager {
+        LegalHoldManager::new(LegalHoldConfig::default().with_base_path(dir))
+    }
+
+    fn observe(holds: &LegalHoldManager, principal: &str, request_id: &str, prompt: &str) {
+        // A hold overrides no_log
+        if let Some(plan) = holds.plan(Some(principal), true) {
+            holds.record(plan, request_id, "m", prompt, "output");
+        }
+    }
+
+    #[test]
+    fn test_capture_only_while_held() {
+        let dir = tempfile::tempdir().unwrap();
+        let holds = manager(dir.path());
+        let tenant = tenant_label("acme");
+
+        observe(&holds, "acme", "r1", "before");
+        holds.activate(&tenant, Some(7), "case 42", Some("admin".into())).unwrap();
+        observe(&holds, "acme", "r2", "secret prompt");
+        observe(&holds, "globex", "r3", "other tenant");
+        holds.release(&tenant, None).unwrap();
+        observe(&holds, "acme", "r4", "after");
+
+        let store_dir = default_store_dir(dir.path());
+        let records = export(&store_dir, &default_key_path(dir.path()), &tenant).unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!((records[0].request_id.as_str(), records[0].prompt.as_str()), ("r2", "secret prompt"));
+        assert!(store_dir.join(ACCESS_LOG_FILE).exists());
+
+        // Sealed on disk, and useless without the key
+        let sealed = std::fs::read_dir(records_dir(&store_dir, &tenant)).unwrap().next().unwrap().unwrap().path();
+        let bytes = std::fs::read(&sealed).unwrap();
+        assert!(!bytes.windows(13).any(|w| w == b"secret prompt"));
+        let other_key = dir.path().join("other.key");
+        std::fs::write(&other_key, [7u8; KEY_LEN]).unwrap();
+        assert!(matches!(export(&store_dir, &other_key, &tenant), Err(LegalHoldError::Corrupt { .. })));
+
+        // Holds survive a restart
+        let reloaded = manager(dir.path()).list();
+        assert_eq!(reloaded.len(), 1);
+        assert_eq!(reloaded[0].records, 1);
+        assert!(reloaded[0].ended_at.is_some());
+    }
+}
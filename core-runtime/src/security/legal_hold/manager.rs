@@ -0,0 +1,176 @@
+//! Hold state: activation, release and persistence.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+
+use chrono::Utc;
+
+use super::audit::audit_hold;
+use super::store::SealedStore;
+use super::{validate_tenant, LegalHold, LegalHoldConfig, LegalHoldError, HOLDS_FILE};
+
+/// Holds in effect, inference sampling and capture.
+pub struct LegalHoldManager {
+    pub(super) config: LegalHoldConfig,
+    pub(super) holds: Mutex<HashMap<String, LegalHold>>,
+    /// Opened on first activation or capture, so no key exists until a
+    /// hold is ever placed.
+    pub(super) store: OnceLock<SealedStore>,
+    pub(super) last_sweep: Mutex<Instant>,
+}
+
+impl LegalHoldManager {
+    pub fn new(config: LegalHoldConfig) -> Self {
+        let holds = match config.store_dir.as_deref().map(load_holds) {
+            Some(Ok(holds)) => holds,
+            Some(Err(e)) => {
+                tracing::error!(error = %e, "Cannot load legal holds; none are in effect");
+                HashMap::new()
+            }
+            None => HashMap::new(),
+        };
+        Self { config, holds: Mutex::new(holds), store: OnceLock::new(), last_sweep: Mutex::new(Instant::now()) }
+    }
+
+    pub(super) fn store(&self) -> Result<&SealedStore, LegalHoldError> {
+        if let Some(store) = self.store.get() {
+            return Ok(store);
+        }
+        let (Some(dir), Some(key_path)) = (&self.config.store_dir, &self.config.key_path) else {
+            return Err(LegalHoldError::NotConfigured);
+        };
+        let _ = self.store.set(SealedStore::open(dir, key_path)?);
+        Ok(self.store.get().expect("store was just set"))
+    }
+
+    /// Place `tenant` under hold for `days` (default `max_hold_days`).
+    /// Activating a held tenant renews the hold.
+    pub fn activate(
+        &self,
+        tenant: &str,
+        days: Option<u32>,
+        reason: &str,
+        actor: Option<String>,
+    ) -> Result<LegalHold, LegalHoldError> {
+        validate_tenant(tenant)?;
+        if reason.trim().is_empty() {
+            return Err(LegalHoldError::MissingReason);
+        }
+        let max = self.config.max_hold_days;
+        let days = days.unwrap_or(max);
+        if days == 0 || days > max {
+            return Err(LegalHoldError::InvalidDuration { days, max });
+        }
+        self.store()?;
+
+        let now = Utc::now();
+        let hold = {
+            let mut holds = self.holds.lock().unwrap_or_else(|e| e.into_inner());
+            let records = holds.get(tenant).filter(|h| h.is_active(now)).map_or(0, |h| h.records);
+            let hold = LegalHold {
+                tenant: tenant.to_string(),
+                reason: reason.trim().to_string(),
+                activated_by: actor,
+                activated_at: now,
+                expires_at: now + chrono::Duration::days(i64::from(days)),
+                ended_at: None,
+                records,
+            };
+            holds.insert(tenant.to_string(), hold.clone());
+            self.save(&holds)?;
+            hold
+        };
+        tracing::warn!(tenant, expires_at = %hold.expires_at, "Legal hold activated");
+        audit_hold("legal_hold_activated", "Legal hold activated", &hold, hold.activated_by.as_deref());
+        Ok(hold)
+    }
+
+    /// End the hold on `tenant`. Its records stay sealed until disposal.
+    pub fn release(&self, tenant: &str, actor: Option<String>) -> Result<LegalHold, LegalHoldError> {
+        let now = Utc::now();
+        let hold = {
+            let mut holds = self.holds.lock().unwrap_or_else(|e| e.into_inner());
+            let hold = holds
+                .get_mut(tenant)
+                .filter(|h| h.is_active(now))
+                .ok_or_else(|| LegalHoldError::NotHeld(tenant.to_string()))?;
+            hold.ended_at = Some(now);
+            let hold = hold.clone();
+            self.save(&holds)?;
+            hold
+        };
+        tracing::warn!(tenant, "Legal hold released");
+        audit_hold("legal_hold_released", "Legal hold released", &hold, actor.as_deref());
+        Ok(hold)
+    }
+
+    /// Current and ended holds whose records are still kept.
+    pub fn list(&self) -> Vec<LegalHold> {
+        self.sweep(Utc::now());
+        let holds = self.holds.lock().unwrap_or_else(|e| e.into_inner());
+        let mut list: Vec<LegalHold> = holds.values().cloned().collect();
+        list.sort_by(|a, b| a.tenant.cmp(&b.tenant));
+        list
+    }
+
+    pub(super) fn save(&self, holds: &HashMap<String, LegalHold>) -> Result<(), LegalHoldError> {
+        let Some(dir) = &self.config.store_dir else {
+            return Ok(());
+        };
+        std::fs::create_dir_all(dir)?;
+        let path = dir.join(HOLDS_FILE);
+        let temp = path.with_extension("json.tmp");
+        let mut list: Vec<&LegalHold> = holds.values().collect();
+        list.sort_by(|a, b| a.tenant.cmp(&b.tenant));
+        std::fs::write(&temp, serde_json::to_vec_pretty(&list).map_err(std::io::Error::from)?)?;
+        std::fs::rename(&temp, &path)?;
+        Ok(())
+    }
+}
+
+impl Default for LegalHoldManager {
+    fn default() -> Self {
+        Self::new(LegalHoldConfig::default())
+    }
+}
+
+fn load_holds(dir: &Path) -> Result<HashMap<String, LegalHold>, LegalHoldError> {
+    let data = match std::fs::read(dir.join(HOLDS_FILE)) {
+        Ok(data) => data,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(HashMap::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let list: Vec<LegalHold> = serde_json::from_slice(&data).map_err(std::io::Error::from)?;
+    Ok(list.into_iter().map(|hold| (hold.tenant.clone(), hold)).collect())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::legal_hold::default_key_path;
+
+    fn manager(dir: &Path) -> LegalHoldManager {
+        LegalHoldManager::new(LegalHoldConfig::default().with_base_path(dir))
+    }
+
+    #[test]
+    fn test_activation_checks() {
+        let dir = tempfile::tempdir().unwrap();
+        let holds = manager(dir.path());
+        assert!(matches!(holds.activate("../etc", None, "r", None), Err(LegalHoldError::InvalidTenant(_))));
+        assert!(matches!(holds.activate("tenant-a", None, " ", None), Err(LegalHoldError::MissingReason)));
+        assert!(matches!(
+            holds.activate("tenant-a", Some(400), "r", None),
+            Err(LegalHoldError::InvalidDuration { days: 400, max: 365 })
+        ));
+        assert!(matches!(holds.release("tenant-a", None), Err(LegalHoldError::NotHeld(_))));
+        assert!(matches!(
+            LegalHoldManager::default().activate("tenant-a", None, "r", None),
+            Err(LegalHoldError::NotConfigured)
+        ));
+        assert!(!default_key_path(dir.path()).exists());
+    }
+}
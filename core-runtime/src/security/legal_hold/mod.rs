@@ -0,0 +1,172 @@
+//! Inference audit sampling and legal hold.
+//!
+//! Inference normally leaves no content in the audit trail. A sampled
+//! fraction of requests (`sample_rate`) is audited with SHA-256 hashes of
+//! the prompt and output, enough to match a disputed exchange against a
+//! copy someone kept.
+//!
+//! An admin can place a tenant under legal hold. Tenants are labelled
+//! from the session principal (see `SessionAuth::with_tenant_token`), not
+//! the per-handshake session id, so a hold follows a tenant across
+//! reconnects. While the hold lasts,
+//! every request of that tenant is captured in full: prompt and output are
+//! sealed with AES-256-GCM into a store of their own, under a key of their
+//! own. The serving path only ever writes the store; reading it takes the
+//! key file (`GG-CORE legal-hold export`), so access is limited to whoever
+//! holds that file.
+//!
+//! Holds end when released or when they expire, at most `max_hold_days`
+//! after activation. Sealed records are disposed of
+//! `retain_after_release_days` after their hold ends. Activation, release,
+//! expiry and export raise Critical audit events.
+
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::encryption::EncryptionError;
+
+mod audit;
+mod capture;
+mod manager;
+mod store;
+mod sweep;
+
+pub use manager::LegalHoldManager;
+pub use store::{export, SealedStore};
+
+/// Hold state, under the store directory.
+pub const HOLDS_FILE: &str = "holds.json";
+/// Export log, under the store directory.
+pub const ACCESS_LOG_FILE: &str = "access.log";
+
+#[derive(Error, Debug)]
+pub enum LegalHoldError {
+    #[error("Legal hold store is not configured")]
+    NotConfigured,
+
+    #[error("Invalid tenant label '{0}'")]
+    InvalidTenant(String),
+
+    #[error("No active legal hold on {0}")]
+    NotHeld(String),
+
+    #[error("Hold duration must be 1 to {max} days, got {days}")]
+    InvalidDuration { days: u32, max: u32 },
+
+    #[error("A legal hold needs a reason")]
+    MissingReason,
+
+    #[error("Sealed record {path}: {reason}")]
+    Corrupt { path: String, reason: String },
+
+    #[error("Encryption error: {0}")]
+    Encryption(#[from] EncryptionError),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LegalHoldConfig {
+    /// Fraction of inference requests audited with prompt/output hashes.
+    pub sample_rate: f64,
+    /// Longest a hold runs before it must be renewed.
+    pub max_hold_days: u32,
+    /// How long sealed records outlive the hold that captured them.
+    pub retain_after_release_days: u32,
+    /// Sealed store; `None` refuses activation.
+    pub store_dir: Option<PathBuf>,
+    /// Sealing key; keep it off the store's volume where possible.
+    pub key_path: Option<PathBuf>,
+}
+
+impl Default for LegalHoldConfig {
+    fn default() -> Self {
+        Self {
+            sample_rate: 0.0,
+            max_hold_days: 365,
+            retain_after_release_days: 30,
+            store_dir: None,
+            key_path: None,
+        }
+    }
+}
+
+impl LegalHoldConfig {
+    /// Fill unset paths from the runtime base path.
+    pub fn with_base_path(mut self, base_path: &Path) -> Self {
+        self.store_dir.get_or_insert_with(|| default_store_dir(base_path));
+        self.key_path.get_or_insert_with(|| default_key_path(base_path));
+        self
+    }
+}
+
+/// Default sealed store location under the runtime base path.
+pub fn default_store_dir(base_path: &Path) -> PathBuf {
+    base_path.join("legal-hold")
+}
+
+/// Default sealing key location under the runtime base path.
+pub fn default_key_path(base_path: &Path) -> PathBuf {
+    base_path.join("legal_hold.key")
+}
+
+/// A hold on one tenant.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LegalHold {
+    /// Tenant label as it appears in usage exports.
+    pub tenant: String,
+    pub reason: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub activated_by: Option<String>,
+    pub activated_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    /// When the hold was released or expired.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ended_at: Option<DateTime<Utc>>,
+    /// Requests sealed under this hold.
+    #[serde(default)]
+    pub records: u64,
+}
+
+impl LegalHold {
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.ended_at.is_none() && now < self.expires_at
+    }
+}
+
+/// One captured request, as sealed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SealedRecord {
+    pub tenant: String,
+    pub request_id: String,
+    pub model_id: String,
+    pub captured_at: DateTime<Utc>,
+    pub prompt: String,
+    pub output: String,
+}
+
+/// Tenant labels become directory names, so only `[A-Za-z0-9_-]` passes.
+fn validate_tenant(tenant: &str) -> Result<(), LegalHoldError> {
+    let valid = !tenant.is_empty()
+        && tenant.len() <= 64
+        && tenant.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_');
+    if valid {
+        Ok(())
+    } else {
+        Err(LegalHoldError::InvalidTenant(tenant.to_string()))
+    }
+}
+
+
+/// How one inference is audited.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuditPlan {
+    /// Sealed in full under the tenant's hold.
+    Capture { tenant: String },
+    /// Prompt and output hashes only.
+    Sample { tenant: Option<String> },
+}
@@ -0,0 +1,145 @@
+//! Sealed record files and their export.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+use rand::RngCore;
+use zeroize::Zeroizing;
+
+use super::{validate_tenant, LegalHoldError, SealedRecord, ACCESS_LOG_FILE};
+use crate::security::audit::{audit_logger, AuditCategory, AuditEvent, AuditSeverity};
+use crate::security::encryption::{EncryptionError, ModelEncryption};
+
+const RECORDS_DIR: &str = "records";
+const RECORD_EXT: &str = "sealed";
+pub(super) const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+
+/// Encrypted record files, one directory per tenant.
+pub struct SealedStore {
+    dir: PathBuf,
+    cipher: ModelEncryption,
+}
+
+impl SealedStore {
+    /// Open the store for sealing, creating the key on first use.
+    pub fn open(dir: &Path, key_path: &Path) -> Result<Self, LegalHoldError> {
+        let key = match std::fs::read(key_path) {
+            Ok(bytes) => parse_key(&bytes)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                let mut key = Zeroizing::new([0u8; KEY_LEN]);
+                rand::rngs::OsRng.fill_bytes(&mut key[..]);
+                write_private(key_path, &key[..])?;
+                key
+            }
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Self { dir: dir.to_path_buf(), cipher: ModelEncryption::new(*key) })
+    }
+
+    /// Open the store for reading; the key must already exist.
+    pub fn open_existing(dir: &Path, key_path: &Path) -> Result<Self, LegalHoldError> {
+        let key = parse_key(&std::fs::read(key_path)?)?;
+        Ok(Self { dir: dir.to_path_buf(), cipher: ModelEncryption::new(*key) })
+    }
+
+    pub fn seal(&self, record: &SealedRecord) -> Result<PathBuf, LegalHoldError> {
+        let plaintext = Zeroizing::new(serde_json::to_vec(record).map_err(std::io::Error::from)?);
+        let (nonce, ciphertext) = self.cipher.encrypt(&plaintext)?;
+        let name = format!(
+            "{:013}-{:08x}.{}",
+            record.captured_at.timestamp_millis(),
+            rand::random::<u32>(),
+            RECORD_EXT
+        );
+        let path = records_dir(&self.dir, &record.tenant).join(name);
+        write_private(&path, &[nonce, ciphertext].concat())?;
+        Ok(path)
+    }
+
+    /// Every record sealed for `tenant`, oldest first.
+    pub fn read(&self, tenant: &str) -> Result<Vec<SealedRecord>, LegalHoldError> {
+        let dir = records_dir(&self.dir, tenant);
+        let mut paths: Vec<PathBuf> = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|path| path.extension().is_some_and(|ext| ext == RECORD_EXT))
+                .collect(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e.into()),
+        };
+        paths.sort();
+        paths.iter().map(|path| self.open_record(path, tenant)).collect()
+    }
+
+    fn open_record(&self, path: &Path, tenant: &str) -> Result<SealedRecord, LegalHoldError> {
+        let corrupt = |reason: String| LegalHoldError::Corrupt { path: path.display().to_string(), reason };
+        let data = std::fs::read(path)?;
+        if data.len() < NONCE_LEN {
+            return Err(corrupt("truncated".into()));
+        }
+        let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+        let plaintext = Zeroizing::new(self.cipher.decrypt(nonce, ciphertext).map_err(|e| corrupt(e.to_string()))?);
+        let record: SealedRecord = serde_json::from_slice(&plaintext).map_err(|e| corrupt(e.to_string()))?;
+        // The tenant is inside the ciphertext; a file moved between
+        // tenant directories is caught here
+        if record.tenant != tenant {
+            return Err(corrupt(format!("sealed for {}", record.tenant)));
+        }
+        Ok(record)
+    }
+}
+
+fn parse_key(bytes: &[u8]) -> Result<Zeroizing<[u8; KEY_LEN]>, LegalHoldError> {
+    let key: [u8; KEY_LEN] = bytes.try_into().map_err(|_| EncryptionError::InvalidKeySize)?;
+    Ok(Zeroizing::new(key))
+}
+
+pub(super) fn records_dir(store_dir: &Path, tenant: &str) -> PathBuf {
+    store_dir.join(RECORDS_DIR).join(tenant)
+}
+
+fn write_private(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options.open(path)?.write_all(bytes)
+}
+
+/// Decrypt every record held for `tenant` with the key at `key_path`.
+/// The export is appended to the store's access log and audited.
+pub fn export(store_dir: &Path, key_path: &Path, tenant: &str) -> Result<Vec<SealedRecord>, LegalHoldError> {
+    validate_tenant(tenant)?;
+    let records = SealedStore::open_existing(store_dir, key_path)?.read(tenant)?;
+
+    let entry = serde_json::json!({
+        "at": Utc::now(),
+        "event": "export",
+        "tenant": tenant,
+        "records": records.len(),
+        "pid": std::process::id(),
+    });
+    let mut log = std::fs::OpenOptions::new().create(true).append(true).open(store_dir.join(ACCESS_LOG_FILE))?;
+    writeln!(log, "{}", entry)?;
+
+    if let (Some(logger), Ok(runtime)) = (audit_logger(), tokio::runtime::Handle::try_current()) {
+        if let Ok(event) = AuditEvent::builder()
+            .severity(AuditSeverity::Critical)
+            .category(AuditCategory::DataAccess)
+            .event_type("legal_hold_export")
+            .message(format!("Sealed legal hold records exported for {}", tenant))
+            .source("legal_hold")
+            .metadata("tenant", tenant)
+            .metadata("records", records.len().to_string())
+            .build()
+        {
+            runtime.spawn(async move { logger.log(event).await });
+        }
+    }
+    Ok(records)
+}
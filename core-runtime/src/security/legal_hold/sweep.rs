@@ -0,0 +1,106 @@
+//! Expiry of holds and disposal of their records.
+
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+
+use super::audit::audit_hold;
+use super::store::records_dir;
+use super::LegalHoldManager;
+
+/// How often serving checks for expired holds and disposable records.
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+impl LegalHoldManager {
+    /// End expired holds and dispose of records past retention.
+    pub fn sweep(&self, now: DateTime<Utc>) {
+        let retention = chrono::Duration::days(i64::from(self.config.retain_after_release_days));
+        let mut expired = Vec::new();
+        let mut disposed = Vec::new();
+        {
+            let mut holds = self.holds.lock().unwrap_or_else(|e| e.into_inner());
+            for hold in holds.values_mut().filter(|h| h.ended_at.is_none() && now >= h.expires_at) {
+                hold.ended_at = Some(hold.expires_at);
+                expired.push(hold.clone());
+            }
+            holds.retain(|_, hold| match hold.ended_at {
+                Some(ended) if now >= ended + retention => {
+                    disposed.push(hold.clone());
+                    false
+                }
+                _ => true,
+            });
+            if expired.is_empty() && disposed.is_empty() {
+                return;
+            }
+            if let Err(e) = self.save(&holds) {
+                tracing::error!(error = %e, "Cannot save legal holds");
+            }
+        }
+        for hold in &expired {
+            tracing::warn!(tenant = hold.tenant.as_str(), "Legal hold expired");
+            audit_hold("legal_hold_expired", "Legal hold expired", hold, None);
+        }
+        for hold in &disposed {
+            let Some(dir) = &self.config.store_dir else {
+                continue;
+            };
+            match std::fs::remove_dir_all(records_dir(dir, &hold.tenant)) {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => {
+                    tracing::error!(tenant = hold.tenant.as_str(), error = %e, "Cannot dispose of sealed records")
+                }
+            }
+            audit_hold("legal_hold_records_disposed", "Sealed legal hold records disposed of", hold, None);
+        }
+    }
+
+    pub(super) fn sweep_if_due(&self, now: DateTime<Utc>) {
+        {
+            let mut last = self.last_sweep.lock().unwrap_or_else(|e| e.into_inner());
+            if last.elapsed() < SWEEP_INTERVAL {
+                return;
+            }
+            *last = Instant::now();
+        }
+        self.sweep(now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::security::legal_hold::{default_store_dir, LegalHoldConfig};
+    use crate::telemetry::tenant_label;
+
+    fn manager(dir: &Path) -> LegalHoldManager {
+        LegalHoldManager::new(LegalHoldConfig::default().with_base_path(dir))
+    }
+
+    fn observe(holds: &LegalHoldManager, principal: &str, request_id: &str, prompt: &str) {
+        // A hold overrides no_log
+        if let Some(plan) = holds.plan(Some(principal), true) {
+            holds.record(plan, request_id, "m", prompt, "output");
+        }
+    }
+
+    #[test]
+    fn test_expiry_and_disposal() {
+        let dir = tempfile::tempdir().unwrap();
+        let holds = manager(dir.path());
+        let tenant = tenant_label("acme");
+        let hold = holds.activate(&tenant, Some(1), "case 42", None).unwrap();
+        observe(&holds, "acme", "r1", "p");
+
+        holds.sweep(hold.expires_at);
+        assert!(!holds.is_held(&tenant, hold.expires_at));
+        assert_eq!(holds.holds.lock().unwrap()[&tenant].ended_at, Some(hold.expires_at));
+
+        holds.sweep(hold.expires_at + chrono::Duration::days(31));
+        assert!(holds.holds.lock().unwrap().is_empty());
+        assert!(!records_dir(&default_store_dir(dir.path()), &tenant).exists());
+    }
+}
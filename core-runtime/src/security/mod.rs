@@ -3,26 +3,45 @@
 //! This module provides comprehensive security features including:
 //! - Prompt injection protection
 //! - Output sanitization and PII detection
+//! - Hard blocking of responses containing configured PII types
 //! - Model file encryption with key rotation (SOC2-2)
-//! - FIPS 140-3 self-tests (FIPS-3)
+//! - Recovery-key escrow for machine-bound model keys
+//! - FIPS 140-3 self-tests (FIPS-3) and the fips-strict algorithm allow-list
+//! - Signed purge receipts for right-to-erasure requests
+//! - Hashed inference audit sampling and per-tenant legal hold capture
+//! - Image input validation for vision models
 //! - Secure communication
 //! - Enterprise audit logging
 
 pub mod audit;
 pub mod encryption;
+pub mod escrow;
+pub mod fips_mode;
 pub mod fips_tests;
+pub mod image_input;
 pub mod key_rotation;
+pub mod legal_hold;
 pub mod output_sanitizer;
+pub mod pii_block;
 pub mod pii_detector;
 pub mod prompt_injection;
+pub mod receipt;
+pub mod shadow;
 
 pub use audit::{AuditCategory, AuditEvent, AuditLogger, AuditSeverity};
 pub use encryption::ModelEncryption;
+pub use escrow::{EscrowError, EscrowRecord, RecoveryPrivateKey, RecoveryPublicKey};
+pub use fips_mode::{Algorithm, AllowListReport, FipsError, FipsMode};
 pub use fips_tests::{run_power_on_self_tests, SelfTestError, SelfTestResults};
+pub use image_input::{ImageError, ImagePart};
 pub use key_rotation::{KeyRotationError, KeyRotationManager};
+pub use legal_hold::{LegalHold, LegalHoldConfig, LegalHoldError, LegalHoldManager};
 pub use output_sanitizer::OutputSanitizer;
-pub use pii_detector::{PIIDetector, PIIMatch};
+pub use pii_block::{PiiBlock, PiiBlockConfig, PiiBlockPolicy};
+pub use pii_detector::{PIIDetector, PIIMatch, PIIType};
 pub use prompt_injection::{InjectionMatch, PromptInjectionFilter};
+pub use receipt::{PurgeReceipt, PurgeScope, ReceiptSigner};
+pub use shadow::{PolicySpec, ShadowConfig, ShadowPolicy, ShadowStats};
 
 /// Security configuration
 #[derive(Debug, Clone)]
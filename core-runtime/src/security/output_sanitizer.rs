@@ -3,22 +3,27 @@
 //! Sanitizes model outputs for security and safety.
 //! Combines PII detection, content filtering, and format validation.
 
+use crate::engine::utf8;
 use crate::security::{PIIDetector, pii_detector::PIIType};
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 /// Output sanitizer configuration
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SanitizerConfig {
     /// Enable PII redaction
     pub redact_pii: bool,
     /// Enable content filtering
     pub filter_content: bool,
-    /// Maximum output length
+    /// Maximum output length in bytes; cut on a character boundary
     pub max_length: usize,
     /// Minimum confidence for PII detection
     pub pii_confidence_threshold: f32,
     /// PII types to redact
     pub redact_types: Vec<PIIType>,
+    /// Mojibake score (0.0-1.0) at which a warning is added
+    pub mojibake_warn_score: f32,
 }
 
 impl Default for SanitizerConfig {
@@ -38,6 +43,7 @@ impl Default for SanitizerConfig {
                 PIIType::BankAccount,
                 PIIType::MedicalRecord,
             ],
+            mojibake_warn_score: 0.5,
         }
     }
 }
@@ -88,32 +94,40 @@ impl OutputSanitizer {
         let mut warnings = Vec::new();
         
         // Check length limit
-        if result.len() > self.config.max_length {
-            result.truncate(self.config.max_length);
+        if utf8::truncate_bytes(&mut result, self.config.max_length) {
             warnings.push(format!(
-                "Output truncated to {} characters",
+                "Output truncated to {} bytes",
                 self.config.max_length
             ));
             modified = true;
         }
+
+        // Likely encoding damage is reported, not treated as an error
+        let mojibake = utf8::mojibake_score(&result);
+        if mojibake >= self.config.mojibake_warn_score {
+            warnings.push(format!("Output may have encoding issues (mojibake score {:.2})", mojibake));
+        }
         
         // PII detection and redaction
         if self.config.redact_pii {
-            let pii_matches = self.pii_detector.detect(&result);
+            let mut pii_matches: Vec<_> = self
+                .pii_detector
+                .detect(&result)
+                .into_iter()
+                .filter(|m| self.config.redact_types.contains(&m.pii_type))
+                .filter(|m| m.confidence >= self.config.pii_confidence_threshold)
+                .collect();
             
-            for m in pii_matches {
-                // Check if this PII type should be redacted
-                if !self.config.redact_types.contains(&m.pii_type) {
-                    continue;
-                }
-                
-                // Check confidence threshold
-                if m.confidence < self.config.pii_confidence_threshold {
+            // Redact from the end so earlier offsets stay valid; skip matches
+            // overlapping one already redacted
+            pii_matches.sort_by_key(|m| (m.start, m.end));
+            let mut redacted_from = result.len();
+            for m in pii_matches.iter().rev() {
+                if m.end > redacted_from {
                     continue;
                 }
-                
-                // Redact
-                result = self.redact_pii(&result, &m);
+                result = self.redact_pii(&result, m);
+                redacted_from = m.start;
                 pii_redacted += 1;
                 modified = true;
             }
@@ -160,7 +174,11 @@ impl OutputSanitizer {
                         let chunk_start = m.start.saturating_sub(state.processed_until);
                         let chunk_end = m.end.saturating_sub(state.processed_until);
                         
-                        if chunk_start < result.len() && chunk_end <= result.len() {
+                        if chunk_start < result.len()
+                            && chunk_end <= result.len()
+                            && result.is_char_boundary(chunk_start)
+                            && result.is_char_boundary(chunk_end)
+                        {
                             result.replace_range(chunk_start..chunk_end, &redacted);
                         }
                         
@@ -204,8 +222,8 @@ impl OutputSanitizer {
         
         // Find a word boundary near the candidate trim point
         // This reduces the chance of splitting PII patterns
-        let search_start = candidate.saturating_sub(20);
-        let search_end = (candidate + 20).min(buffer.len());
+        let search_start = utf8::floor_char_boundary(buffer, candidate.saturating_sub(20));
+        let search_end = utf8::floor_char_boundary(buffer, candidate + 20);
         
         // Look for whitespace or punctuation as safe trim points
         if let Some(safe_pos) = buffer[search_start..search_end]
@@ -222,7 +240,7 @@ impl OutputSanitizer {
         
         // If no safe boundary found, trim conservatively to preserve potential PII
         // This is safer than potentially splitting PII
-        buffer.len().saturating_sub(MAX_PII_LENGTH * 2).min(max_trim)
+        utf8::floor_char_boundary(buffer, buffer.len().saturating_sub(MAX_PII_LENGTH * 2).min(max_trim))
     }
     
     /// Redact a single PII instance
@@ -271,15 +289,14 @@ impl OutputSanitizer {
             return Err("Output contains excessive repetition".to_string());
         }
         
-        // Check for broken encoding patterns
-        if output.contains("Ã") || output.contains("Â") {
-            return Err("Output may have encoding issues".to_string());
-        }
-        
+        // Possible mojibake is scored by `sanitize` as a warning; the same
+        // characters occur in legitimate text
         Ok(())
     }
     
-    /// Check for excessive repetition (model degradation indicator)
+    /// Check for excessive repetition (model degradation indicator).
+    /// Generation loops are normally stopped in the decode loop
+    /// (`FinishReason::Degenerated`); this catches output from elsewhere.
     fn has_excessive_repetition(&self, text: &str) -> bool {
         let words: Vec<&str> = text.split_whitespace().collect();
         
@@ -383,6 +400,34 @@ mod tests {
         
         // Null characters
         assert!(sanitizer.validate_format("Invalid\0output").is_err());
+
+        // Possible mojibake is no longer an error
+        assert!(sanitizer.validate_format("cafÃ© Ã¨").is_ok());
+    }
+
+    #[test]
+    fn test_mojibake_warning() {
+        let sanitizer = OutputSanitizer::default_sanitizer();
+        let result = sanitizer.sanitize("The cafÃ© is open");
+        assert!(result.warnings.iter().any(|w| w.contains("mojibake score")));
+        assert!(!result.modified);
+
+        let clean = sanitizer.sanitize("The café in São Paulo is open");
+        assert!(clean.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_truncation_on_char_boundary() {
+        let config = SanitizerConfig {
+            max_length: 4,
+            ..Default::default()
+        };
+        let sanitizer = OutputSanitizer::new(config);
+
+        // Byte 4 falls inside the second "é"
+        let result = sanitizer.sanitize("aéé€x");
+        assert_eq!(result.output, "aé");
+        assert!(result.modified);
     }
     
     #[test]
@@ -444,18 +489,35 @@ mod tests {
     }
     
     #[test]
-    fn test_performance() {
+    fn test_redacts_every_match_in_place() {
         let sanitizer = OutputSanitizer::default_sanitizer();
-        let output = "Contact support@example.com for help. Call 555-123-4567. SSN: 123-45-6789.".repeat(100);
+        let output = "Mail a.person@example.com or b.person@example.org today";
+        let result = sanitizer.sanitize(output);
         
-        let start = std::time::Instant::now();
-        for _ in 0..100 {
-            let _ = sanitizer.sanitize(&output);
-        }
-        let duration = start.elapsed();
+        assert_eq!(result.pii_redacted, 2);
+        assert_eq!(result.output, "Mail [REDACTED:Email Address] or [REDACTED:Email Address] today");
+    }
+
+    #[test]
+    fn test_redacts_overlapping_candidates_once() {
+        // The card number also matches as four phone fragments; redacting it
+        // lengthens the text ahead of the email's offsets
+        let sanitizer = OutputSanitizer::default_sanitizer();
+        let output = "Card 4111 1111 1111 1111 or mail a.person@example.com";
+        let result = sanitizer.sanitize(output);
+
+        assert_eq!(result.pii_redacted, 2);
+        assert_eq!(result.output, "Card [REDACTED:Credit Card] or mail [REDACTED:Email Address]");
+    }
+    
+    #[test]
+    fn test_large_output() {
+        // Speed is tracked by the sanitizer group in benches/regression.rs
+        let sanitizer = OutputSanitizer::default_sanitizer();
+        let output = "Contact support@example.com for help. Call 555-123-4567. SSN: 123-45-6789.".repeat(100);
         
-        // Should complete 100 sanitizations in under 10 seconds
-        assert!(duration.as_millis() < 10000, "Sanitization too slow: {:?}", duration);
+        let result = sanitizer.sanitize(&output);
+        assert!(!result.output.contains("support@example.com"));
     }
     
     #[test]
@@ -0,0 +1,212 @@
+//! Hard-block policy for PII in model output.
+//!
+//! Redaction still ships a response around the masked value. Deployments
+//! that must never emit certain PII at all configure the types here: a
+//! detection of one of them at or above the confidence threshold fails
+//! the whole response with `PolicyBlocked` instead. Streams are screened
+//! through a [`PiiStreamGuard`], which holds tokens back until enough
+//! later text has been seen that no blocked value can still be forming
+//! across them, so a blocked stream never sent any part of the value.
+//!
+//! A [`PiiBlock`] names the types and counts only; the matched text never
+//! leaves this module, in errors, metrics or audit.
+
+use std::collections::VecDeque;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::pii_detector::{PIIDetector, PIIType};
+
+/// Unreleased stream text kept behind the newest token. Longer than any
+/// value the detector's patterns match, so a blocked value is whole
+/// before any of it is released.
+pub const HOLD_BACK: usize = 128;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PiiBlockConfig {
+    /// Types that fail the response; empty blocks nothing.
+    pub types: Vec<PIIType>,
+    /// Lowest detection confidence that blocks, 0.0 to 1.0.
+    pub min_confidence: f32,
+}
+
+impl Default for PiiBlockConfig {
+    fn default() -> Self {
+        Self { types: Vec::new(), min_confidence: 0.8 }
+    }
+}
+
+/// Why a response was blocked.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PiiBlock {
+    /// Blocked types found, in detection order, without repeats.
+    pub types: Vec<PIIType>,
+    /// Blocking detections.
+    pub matches: usize,
+    /// Highest confidence among them.
+    pub confidence: f32,
+}
+
+impl PiiBlock {
+    /// A block for output that could not be decoded to screen it.
+    pub fn unscreened() -> Self {
+        Self { types: Vec::new(), matches: 0, confidence: 0.0 }
+    }
+
+    /// Type keys joined by commas, e.g. `ssn,email`.
+    pub fn type_list(&self) -> String {
+        self.types.iter().map(PIIType::as_str).collect::<Vec<_>>().join(",")
+    }
+}
+
+impl fmt::Display for PiiBlock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.types.is_empty() {
+            return write!(f, "Response blocked by output policy: output could not be screened");
+        }
+        write!(f, "Response blocked by output policy: contains {}", self.type_list())
+    }
+}
+
+/// Screens output for the configured PII types.
+pub struct PiiBlockPolicy {
+    config: PiiBlockConfig,
+    detector: PIIDetector,
+}
+
+impl PiiBlockPolicy {
+    pub fn new(config: PiiBlockConfig) -> Self {
+        Self { config, detector: PIIDetector::new() }
+    }
+
+    pub fn config(&self) -> &PiiBlockConfig {
+        &self.config
+    }
+
+    /// The block `text` triggers, if any.
+    pub fn check(&self, text: &str) -> Option<PiiBlock> {
+        if self.config.types.is_empty() {
+            return None;
+        }
+        let mut block = PiiBlock { types: Vec::new(), matches: 0, confidence: 0.0 };
+        let blocking = self.detector.detect(text).into_iter().filter(|m| {
+            self.config.types.contains(&m.pii_type) && m.confidence >= self.config.min_confidence
+        });
+        for found in blocking {
+            if !block.types.contains(&found.pii_type) {
+                block.types.push(found.pii_type);
+            }
+            block.matches += 1;
+            block.confidence = block.confidence.max(found.confidence);
+        }
+        (block.matches > 0).then_some(block)
+    }
+
+    /// A guard for one streamed response of `T` tokens.
+    pub fn stream<T>(&self) -> PiiStreamGuard<'_, T> {
+        PiiStreamGuard { policy: self, text: String::new(), released: 0, pending: VecDeque::new() }
+    }
+}
+
+/// Releases streamed tokens once they are clear of blocked PII.
+pub struct PiiStreamGuard<'a, T> {
+    policy: &'a PiiBlockPolicy,
+    /// Recent output: released context followed by the pending tokens.
+    text: String,
+    /// Bytes of `text` already released.
+    released: usize,
+    /// Held tokens and the length of their text.
+    pending: VecDeque<(T, usize)>,
+}
+
+impl<T> PiiStreamGuard<'_, T> {
+    /// Add a token decoded to `text`; returns the tokens now safe to send.
+    pub fn push(&mut self, token: T, text: &str) -> Result<Vec<T>, PiiBlock> {
+        self.text.push_str(text);
+        self.pending.push_back((token, text.len()));
+        if let Some(block) = self.policy.check(&self.text) {
+            return Err(block);
+        }
+        let mut ready = Vec::new();
+        while let Some(&(_, len)) = self.pending.front() {
+            if self.released + len + HOLD_BACK > self.text.len() {
+                break;
+            }
+            self.released += len;
+            ready.extend(self.pending.pop_front().map(|(token, _)| token));
+        }
+        self.trim();
+        Ok(ready)
+    }
+
+    /// Add the last token; returns everything still held.
+    pub fn finish(&mut self, token: T, text: &str) -> Result<Vec<T>, PiiBlock> {
+        let mut ready = self.push(token, text)?;
+        ready.extend(self.pending.drain(..).map(|(token, _)| token));
+        self.released = self.text.len();
+        Ok(ready)
+    }
+
+    /// Drop released text that no longer gives context to pending tokens,
+    /// cutting at whitespace so the next scan does not start mid-value.
+    fn trim(&mut self) {
+        let keep_from = self.released.saturating_sub(HOLD_BACK);
+        let Some(cut) = self.text[..self.released].char_indices().rev().find_map(|(i, c)| {
+            (i <= keep_from && c.is_whitespace()).then_some(i)
+        }) else {
+            return;
+        };
+        self.text.drain(..cut);
+        self.released -= cut;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(types: Vec<PIIType>) -> PiiBlockPolicy {
+        PiiBlockPolicy::new(PiiBlockConfig { types, ..Default::default() })
+    }
+
+    #[test]
+    fn test_blocks_configured_types_only() {
+        let output = "Write to alice@example.com about it";
+        let block = policy(vec![PIIType::Email]).check(output).unwrap();
+        assert_eq!((block.type_list().as_str(), block.matches), ("email", 1));
+        assert!(!block.to_string().contains("alice"));
+
+        assert!(policy(vec![PIIType::SSN]).check(output).is_none());
+        assert!(policy(Vec::new()).check(output).is_none());
+        let strict = PiiBlockPolicy::new(PiiBlockConfig { types: vec![PIIType::Email], min_confidence: 0.99 });
+        assert!(strict.check(output).is_none());
+    }
+
+    #[test]
+    fn test_stream_guard_holds_back_until_clear() {
+        let policy = policy(vec![PIIType::Email]);
+        let filler = "word ".repeat(40);
+        let mut guard = policy.stream();
+        let mut sent = String::new();
+        for token in filler.split_inclusive(' ') {
+            sent.extend(guard.push(token, token).unwrap());
+        }
+        assert!(!sent.is_empty() && sent.len() + HOLD_BACK <= filler.len());
+
+        // The address arrives in pieces; none of it may have been released
+        assert!(guard.push("contact alice", "contact alice").unwrap().iter().all(|t| !t.contains("alice")));
+        let block = guard.push("@example.com", "@example.com").unwrap_err();
+        assert_eq!(block.types, vec![PIIType::Email]);
+
+        // Clean output comes out whole and in order
+        let mut clean = policy.stream();
+        let mut ids = Vec::new();
+        for (id, token) in filler.split_inclusive(' ').enumerate() {
+            ids.extend(clean.push(id, token).unwrap());
+        }
+        ids.extend(clean.finish(40, "done.").unwrap());
+        assert_eq!(ids, (0..=40).collect::<Vec<_>>());
+    }
+}
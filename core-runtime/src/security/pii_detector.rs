@@ -8,11 +8,12 @@
 //! homograph attacks where visually similar characters bypass detection.
 
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use unicode_normalization::UnicodeNormalization;
 
 /// PII types that can be detected
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PIIType {
     /// Credit card numbers
     CreditCard,
@@ -43,6 +44,41 @@ pub enum PIIType {
 }
 
 impl PIIType {
+    pub const ALL: [PIIType; 13] = [
+        PIIType::CreditCard,
+        PIIType::SSN,
+        PIIType::Email,
+        PIIType::Phone,
+        PIIType::IPAddress,
+        PIIType::MACAddress,
+        PIIType::DateOfBirth,
+        PIIType::Address,
+        PIIType::Passport,
+        PIIType::DriverLicense,
+        PIIType::BankAccount,
+        PIIType::MedicalRecord,
+        PIIType::APIKey,
+    ];
+
+    /// Stable key for config values and metric names
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PIIType::CreditCard => "credit_card",
+            PIIType::SSN => "ssn",
+            PIIType::Email => "email",
+            PIIType::Phone => "phone",
+            PIIType::IPAddress => "ip_address",
+            PIIType::MACAddress => "mac_address",
+            PIIType::DateOfBirth => "date_of_birth",
+            PIIType::Address => "address",
+            PIIType::Passport => "passport",
+            PIIType::DriverLicense => "driver_license",
+            PIIType::BankAccount => "bank_account",
+            PIIType::MedicalRecord => "medical_record",
+            PIIType::APIKey => "api_key",
+        }
+    }
+
     /// Get human-readable name
     pub fn name(&self) -> &'static str {
         match self {
@@ -82,6 +118,17 @@ impl PIIType {
     }
 }
 
+impl std::str::FromStr for PIIType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PIIType::ALL
+            .into_iter()
+            .find(|t| t.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("unknown PII type: {s}"))
+    }
+}
+
 /// Detected PII instance
 #[derive(Debug, Clone)]
 pub struct PIIMatch {
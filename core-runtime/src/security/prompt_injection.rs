@@ -143,6 +143,12 @@ impl PromptInjectionFilter {
         }
     }
 
+    /// Override the risk score at which text is considered unsafe (default 50)
+    pub fn with_risk_threshold(mut self, risk_threshold: u8) -> Self {
+        self.risk_threshold = risk_threshold;
+        self
+    }
+
     /// Scan text for prompt injection patterns
     /// Returns (is_safe, risk_score, detected_patterns)
     ///
@@ -0,0 +1,169 @@
+//! Signed receipts for data purges.
+//!
+//! A purge returns a receipt stating what was removed, signed with
+//! HMAC-SHA256 (RFC 2104) under an installation key so an operator can
+//! later show the receipt was issued by this runtime and not edited. The
+//! receipt carries a hash of the purged identifier, never the identifier.
+
+use std::path::{Path, PathBuf};
+
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+/// HMAC key length in bytes.
+const KEY_LEN: usize = 32;
+/// SHA-256 block size in bytes.
+const BLOCK_LEN: usize = 64;
+
+/// HMAC-SHA256 of `message` under `key`.
+pub fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    let mut block = Zeroizing::new([0u8; BLOCK_LEN]);
+    if key.len() > BLOCK_LEN {
+        block[..32].copy_from_slice(&Sha256::digest(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+    let pad = |byte: u8| Zeroizing::new(block.iter().map(|b| b ^ byte).collect::<Vec<u8>>());
+    let inner = Sha256::new().chain_update(pad(0x36).as_slice()).chain_update(message).finalize();
+    Sha256::new().chain_update(pad(0x5c).as_slice()).chain_update(inner).finalize().into()
+}
+
+/// What a purge was scoped to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PurgeScope {
+    Tenant,
+    Session,
+    Conversation,
+}
+
+/// Record of one purge.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PurgeReceipt {
+    pub receipt_id: String,
+    pub scope: PurgeScope,
+    /// Hex SHA-256 of the purged identifier.
+    pub subject_hash: String,
+    /// Issue time (RFC 3339).
+    pub issued_at: String,
+    pub conversations_removed: usize,
+    pub usage_records_removed: usize,
+    pub cache_entries_removed: usize,
+    /// Hex HMAC-SHA256 over the receipt with this field empty.
+    #[serde(default)]
+    pub signature: String,
+}
+
+impl PurgeReceipt {
+    /// Unsigned receipt for `subject` issued now.
+    pub fn new(scope: PurgeScope, subject: &str) -> Self {
+        Self {
+            receipt_id: uuid::Uuid::new_v4().to_string(),
+            scope,
+            subject_hash: hex::encode(Sha256::digest(subject.as_bytes())),
+            issued_at: chrono::Utc::now().to_rfc3339(),
+            conversations_removed: 0,
+            usage_records_removed: 0,
+            cache_entries_removed: 0,
+            signature: String::new(),
+        }
+    }
+
+    fn signed_bytes(&self) -> Vec<u8> {
+        let unsigned = Self { signature: String::new(), ..self.clone() };
+        serde_json::to_vec(&unsigned).unwrap_or_default()
+    }
+}
+
+/// Signs and verifies purge receipts.
+pub struct ReceiptSigner {
+    key: Zeroizing<[u8; KEY_LEN]>,
+}
+
+impl ReceiptSigner {
+    /// Signer with a fresh random key; receipts only verify in this process.
+    pub fn ephemeral() -> Self {
+        let mut key = Zeroizing::new([0u8; KEY_LEN]);
+        rand::rngs::OsRng.fill_bytes(&mut key[..]);
+        Self { key }
+    }
+
+    /// Load the installation key at `path`, creating it (mode 0600) if absent.
+    pub fn load_or_create(path: &Path) -> std::io::Result<Self> {
+        if let Ok(bytes) = std::fs::read(path) {
+            let key: [u8; KEY_LEN] = bytes.as_slice().try_into().map_err(|_| {
+                std::io::Error::new(std::io::ErrorKind::InvalidData, "receipt key must be 32 bytes")
+            })?;
+            return Ok(Self { key: Zeroizing::new(key) });
+        }
+        let signer = Self::ephemeral();
+        write_private(path, &signer.key[..])?;
+        Ok(signer)
+    }
+
+    pub fn sign(&self, receipt: &mut PurgeReceipt) {
+        receipt.signature = hex::encode(hmac_sha256(&self.key[..], &receipt.signed_bytes()));
+    }
+
+    pub fn verify(&self, receipt: &PurgeReceipt) -> bool {
+        let expected = hmac_sha256(&self.key[..], &receipt.signed_bytes());
+        match hex::decode(&receipt.signature) {
+            Ok(signature) => signature.len() == expected.len()
+                && signature.iter().zip(expected.iter()).fold(0u8, |acc, (a, b)| acc | (a ^ b)) == 0,
+            Err(_) => false,
+        }
+    }
+}
+
+fn write_private(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    std::io::Write::write_all(&mut options.open(path)?, bytes)
+}
+
+/// Default key location under the runtime base path.
+pub fn default_key_path(base_path: &Path) -> PathBuf {
+    base_path.join("purge_receipt.key")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// RFC 4231 test case 2.
+    #[test]
+    fn test_hmac_rfc4231() {
+        let mac = hmac_sha256(b"Jefe", b"what do ya want for nothing?");
+        assert_eq!(hex::encode(mac), "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843");
+    }
+
+    #[test]
+    fn test_sign_verify_and_tamper() {
+        let signer = ReceiptSigner::ephemeral();
+        let mut receipt = PurgeReceipt::new(PurgeScope::Conversation, "conv-1");
+        receipt.conversations_removed = 1;
+        signer.sign(&mut receipt);
+        assert!(signer.verify(&receipt));
+
+        receipt.conversations_removed = 0;
+        assert!(!signer.verify(&receipt));
+        assert!(!ReceiptSigner::ephemeral().verify(&receipt));
+    }
+
+    #[test]
+    fn test_key_persists() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = default_key_path(dir.path());
+        let mut receipt = PurgeReceipt::new(PurgeScope::Tenant, "tenant-abc");
+        ReceiptSigner::load_or_create(&path).unwrap().sign(&mut receipt);
+        assert!(ReceiptSigner::load_or_create(&path).unwrap().verify(&receipt));
+        assert!(!receipt.subject_hash.contains("tenant-abc"));
+    }
+}
@@ -161,6 +161,17 @@ mod tests {
             model_id: "test".to_string(),
             prompt: "Hello".to_string(),
             parameters: Default::default(),
+            preset: None,
+            priority: Default::default(),
+            session_affinity_key: None,
+            tools: Vec::new(),
+            images: Vec::new(),
+            template_id: None,
+            variables: Default::default(),
+            context_documents: Vec::new(),
+            cite_sources: false,
+            privacy: Default::default(),
+            idempotency_key: None,
         };
 
         let result = interceptor.intercept(&request, None);
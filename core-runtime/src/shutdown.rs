@@ -3,10 +3,20 @@
 //! Provides a state machine for clean process termination that drains
 //! in-flight requests before exit.
 
-use std::sync::atomic::{AtomicU32, Ordering};
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
 use tokio::sync::{Notify, RwLock};
+use tokio::task::AbortHandle;
+use tokio_util::sync::CancellationToken;
+
+/// Grace period after the drain deadline for idle tasks to observe
+/// cancellation before they are aborted.
+const ABORT_GRACE: Duration = Duration::from_millis(100);
 
 /// Shutdown state machine.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -23,11 +33,101 @@ pub enum ShutdownResult {
     Timeout { remaining: u32 },
 }
 
+/// How many tracked units finished on their own vs were aborted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DrainCounts {
+    pub drained: u32,
+    pub aborted: u32,
+}
+
+/// Outcome of a shutdown, including connection and request accounting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShutdownReport {
+    pub result: ShutdownResult,
+    pub connections: DrainCounts,
+    pub requests: DrainCounts,
+}
+
+/// Tracks spawned tasks so shutdown can wait for them or abort them.
+#[derive(Default)]
+pub struct TaskTracker {
+    tasks: Arc<Mutex<HashMap<u64, AbortHandle>>>,
+    next_id: AtomicU64,
+    notify: Arc<Notify>,
+}
+
+impl TaskTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Spawn a task that is tracked until it completes.
+    pub fn spawn<F>(&self, future: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let tasks = Arc::clone(&self.tasks);
+        let notify = Arc::clone(&self.notify);
+        // Hold the lock across spawn so the task cannot deregister first
+        let mut registered = self.tasks.lock().unwrap_or_else(|e| e.into_inner());
+        let handle = tokio::spawn(async move {
+            future.await;
+            tasks.lock().unwrap_or_else(|e| e.into_inner()).remove(&id);
+            notify.notify_waiters();
+        });
+        registered.insert(id, handle.abort_handle());
+    }
+
+    /// Number of tasks still running.
+    pub fn len(&self) -> usize {
+        self.tasks.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Wait until all tasks finish or `deadline` passes. Returns the
+    /// number still running.
+    pub async fn wait_until(&self, deadline: tokio::time::Instant) -> usize {
+        loop {
+            let notified = self.notify.notified();
+            let remaining = self.len();
+            if remaining == 0 {
+                return 0;
+            }
+            tokio::select! {
+                _ = notified => continue,
+                _ = tokio::time::sleep_until(deadline) => return self.len(),
+            }
+        }
+    }
+
+    /// Abort every running task. Returns how many were aborted.
+    pub fn abort_all(&self) -> usize {
+        let tasks: Vec<_> = self
+            .tasks
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .drain()
+            .map(|(_, handle)| handle)
+            .collect();
+        let aborted = tasks.iter().filter(|h| !h.is_finished()).count();
+        for handle in tasks {
+            handle.abort();
+        }
+        aborted
+    }
+}
+
 /// Coordinates graceful shutdown across runtime components.
 pub struct ShutdownCoordinator {
     state: Arc<RwLock<ShutdownState>>,
     in_flight: Arc<AtomicU32>,
     notify: Arc<Notify>,
+    connections: TaskTracker,
+    stopping: CancellationToken,
 }
 
 impl ShutdownCoordinator {
@@ -36,9 +136,22 @@ impl ShutdownCoordinator {
             state: Arc::new(RwLock::new(ShutdownState::Running)),
             in_flight: Arc::new(AtomicU32::new(0)),
             notify: Arc::new(Notify::new()),
+            connections: TaskTracker::new(),
+            stopping: CancellationToken::new(),
         }
     }
 
+    /// Tracker for connection tasks.
+    pub fn connections(&self) -> &TaskTracker {
+        &self.connections
+    }
+
+    /// Cancelled once in-flight requests have drained (or timed out);
+    /// idle connections should close when it fires.
+    pub fn stopping(&self) -> &CancellationToken {
+        &self.stopping
+    }
+
     /// Get current shutdown state.
     pub async fn state(&self) -> ShutdownState {
         *self.state.read().await
@@ -70,23 +183,62 @@ impl ShutdownCoordinator {
         self.in_flight.load(Ordering::SeqCst)
     }
 
+    /// Stop accepting new requests without shutting down; in-flight work
+    /// finishes and readiness reports not-ready. Returns the in-flight count.
+    pub async fn drain(&self) -> u32 {
+        let mut state = self.state.write().await;
+        if *state == ShutdownState::Running {
+            *state = ShutdownState::Draining;
+        }
+        self.in_flight_count()
+    }
+
     /// Initiate shutdown: stop accepting, wait for drain.
     pub async fn initiate(&self, timeout: Duration) -> ShutdownResult {
+        self.initiate_with_report(timeout).await.result
+    }
+
+    /// Initiate shutdown and report drained vs aborted work.
+    ///
+    /// In-flight requests get until `timeout` to finish. Idle connections
+    /// are then told to close; connection tasks still running after a
+    /// short grace period are aborted, taking their requests with them.
+    pub async fn initiate_with_report(&self, timeout: Duration) -> ShutdownReport {
         // Transition to draining
         {
             let mut state = self.state.write().await;
             *state = ShutdownState::Draining;
         }
 
+        let deadline = tokio::time::Instant::now() + timeout;
+        let requests_at_start = self.in_flight_count();
+        let connections_at_start = self.connections.len() as u32;
+
         let result = self.wait_for_drain(timeout).await;
 
+        self.stopping.cancel();
+        let grace = deadline.max(tokio::time::Instant::now() + ABORT_GRACE);
+        self.connections.wait_until(grace).await;
+        let requests_aborted = self.in_flight_count();
+        let connections_aborted = self.connections.abort_all() as u32;
+
         // Transition to stopped
         {
             let mut state = self.state.write().await;
             *state = ShutdownState::Stopped;
         }
 
-        result
+        ShutdownReport {
+            result,
+            connections: DrainCounts {
+                drained: connections_at_start.saturating_sub(connections_aborted),
+                aborted: connections_aborted,
+            },
+            requests: DrainCounts {
+                drained: requests_at_start.saturating_sub(requests_aborted),
+                aborted: requests_aborted,
+            },
+        }
     }
 
     async fn wait_for_drain(&self, timeout: Duration) -> ShutdownResult {
@@ -0,0 +1,96 @@
+//! Configuration captured in a snapshot.
+//!
+//! Most settings are fixed once the runtime starts, so a restore stages
+//! the snapshot's configuration next to the archives and the next start
+//! applies it.
+
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::SnapshotError;
+use crate::maintenance::MaintenanceConfig;
+use crate::RuntimeConfig;
+
+/// Configuration staged by a restore, under the snapshot directory.
+pub const PENDING_CONFIG_FILE: &str = "pending-config.json";
+
+/// Configuration captured in a snapshot. Secrets (auth tokens) are never stored.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigSnapshot {
+    pub max_context_length: usize,
+    pub session_timeout_secs: u64,
+    pub shutdown_timeout_secs: u64,
+    #[serde(default)]
+    pub maintenance: MaintenanceConfig,
+}
+
+impl ConfigSnapshot {
+    pub fn capture(config: &RuntimeConfig) -> Self {
+        Self {
+            max_context_length: config.max_context_length,
+            session_timeout_secs: config.session_timeout.as_secs(),
+            shutdown_timeout_secs: config.shutdown_timeout.as_secs(),
+            maintenance: config.maintenance.clone(),
+        }
+    }
+
+    /// Overwrite the captured fields of `config`.
+    pub fn apply_to(&self, config: &mut RuntimeConfig) {
+        config.max_context_length = self.max_context_length;
+        config.session_timeout = Duration::from_secs(self.session_timeout_secs);
+        config.shutdown_timeout = Duration::from_secs(self.shutdown_timeout_secs);
+        config.maintenance = self.maintenance.clone();
+    }
+
+    /// Stage this configuration in `dir` for the next start.
+    pub fn stage(&self, dir: &Path) -> Result<(), SnapshotError> {
+        fs::create_dir_all(dir)?;
+        let path = dir.join(PENDING_CONFIG_FILE);
+        let temp_path = path.with_extension("tmp");
+        fs::write(&temp_path, serde_json::to_vec_pretty(self)?)?;
+        fs::rename(&temp_path, &path)?;
+        Ok(())
+    }
+
+    /// Apply and remove the configuration staged in `dir`, if any.
+    /// Returns whether one was applied.
+    pub fn apply_staged(dir: &Path, config: &mut RuntimeConfig) -> Result<bool, SnapshotError> {
+        let path = dir.join(PENDING_CONFIG_FILE);
+        let data = match fs::read(&path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+            Err(e) => return Err(e.into()),
+        };
+        let staged: Self = serde_json::from_slice(&data)?;
+        staged.apply_to(config);
+        fs::remove_file(&path)?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_snapshot_excludes_secrets() {
+        let config = RuntimeConfig { auth_token: "secret".into(), ..Default::default() };
+        let json = serde_json::to_string(&ConfigSnapshot::capture(&config)).unwrap();
+        assert!(!json.contains("secret"));
+    }
+
+    #[test]
+    fn test_staged_config_applies_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let restored = RuntimeConfig { max_context_length: 8192, ..Default::default() };
+        ConfigSnapshot::capture(&restored).stage(dir.path()).unwrap();
+
+        let mut config = RuntimeConfig::default();
+        assert!(ConfigSnapshot::apply_staged(dir.path(), &mut config).unwrap());
+        assert_eq!(config.max_context_length, 8192);
+        assert!(!ConfigSnapshot::apply_staged(dir.path(), &mut config).unwrap());
+    }
+}
@@ -0,0 +1,162 @@
+//! Runtime state snapshots.
+//!
+//! Persists the model catalog, pool membership, warmup state and the
+//! non-secret parts of the configuration to a versioned JSON archive, so a
+//! replacement node can return to a known-good serving state without
+//! re-running warmups from scratch.
+
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::models::{ModelPool, ModelRegistry, PoolMember};
+
+mod config;
+mod restore;
+
+pub use config::{ConfigSnapshot, PENDING_CONFIG_FILE};
+
+/// Archive directory under the runtime base path.
+pub const SNAPSHOT_DIR: &str = "snapshots";
+
+/// Archive format version written by this build.
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+
+#[derive(Error, Debug)]
+pub enum SnapshotError {
+    #[error("Snapshot I/O failed: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Snapshot is malformed: {0}")]
+    Parse(#[from] serde_json::Error),
+
+    #[error("Unsupported snapshot format version {found} (supported: {supported})")]
+    UnsupportedVersion { found: u32, supported: u32 },
+
+    #[error("Pool restore failed: {0}")]
+    Pool(String),
+}
+
+/// A registered model as captured in a snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CatalogEntry {
+    pub name: String,
+    pub format: String,
+    pub size_bytes: u64,
+    pub memory_bytes: u64,
+    pub warmed: bool,
+    /// File the model was loaded from; restore loads it from there.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+}
+
+/// Full runtime snapshot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuntimeSnapshot {
+    pub format_version: u32,
+    pub runtime_version: String,
+    /// Seconds since the Unix epoch.
+    pub created_at: u64,
+    pub config: ConfigSnapshot,
+    pub catalog: Vec<CatalogEntry>,
+    #[serde(default)]
+    pub pool: Vec<PoolMember>,
+    #[serde(default)]
+    pub active_model: Option<String>,
+}
+
+/// What a restore changed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RestoreSummary {
+    pub models_restored: usize,
+    pub models_skipped: usize,
+    pub pool_members: usize,
+}
+
+impl RuntimeSnapshot {
+    /// Capture the current state. `pool` is optional since not every
+    /// deployment runs a model pool.
+    pub async fn capture(
+        config: ConfigSnapshot,
+        registry: &ModelRegistry,
+        pool: Option<&ModelPool>,
+    ) -> Self {
+        let catalog = registry
+            .list_models()
+            .await
+            .into_iter()
+            .map(|m| CatalogEntry {
+                name: m.name,
+                format: m.format,
+                size_bytes: m.size_bytes,
+                memory_bytes: m.memory_bytes,
+                warmed: m.warmed,
+                path: m.path,
+            })
+            .collect();
+        let (pool_members, active_model) = match pool {
+            Some(p) => (p.members().await, p.active().await),
+            None => (Vec::new(), None),
+        };
+        Self {
+            format_version: SNAPSHOT_FORMAT_VERSION,
+            runtime_version: env!("CARGO_PKG_VERSION").to_string(),
+            created_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            config,
+            catalog,
+            pool: pool_members,
+            active_model,
+        }
+    }
+
+    /// Write atomically (temp file + rename).
+    pub fn write_to(&self, path: &Path) -> Result<(), SnapshotError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let temp_path = path.with_extension("tmp");
+        serde_json::to_writer_pretty(BufWriter::new(File::create(&temp_path)?), self)?;
+        fs::rename(&temp_path, path)?;
+        Ok(())
+    }
+
+    /// Read and check the format version.
+    pub fn read_from(path: &Path) -> Result<Self, SnapshotError> {
+        let snapshot: Self = serde_json::from_reader(BufReader::new(File::open(path)?))?;
+        if snapshot.format_version > SNAPSHOT_FORMAT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion {
+                found: snapshot.format_version,
+                supported: SNAPSHOT_FORMAT_VERSION,
+            });
+        }
+        Ok(snapshot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rejects_newer_format_version() {
+        let path = std::env::temp_dir().join("veritas_test_snapshot_version.json");
+        let value = serde_json::json!({
+            "format_version": SNAPSHOT_FORMAT_VERSION + 1,
+            "runtime_version": "9.9.9",
+            "created_at": 0,
+            "config": { "max_context_length": 1, "session_timeout_secs": 1, "shutdown_timeout_secs": 1 },
+            "catalog": []
+        });
+        fs::write(&path, value.to_string()).unwrap();
+        let result = RuntimeSnapshot::read_from(&path);
+        let _ = fs::remove_file(&path);
+        assert!(matches!(result, Err(SnapshotError::UnsupportedVersion { .. })));
+    }
+}
@@ -0,0 +1,161 @@
+//! Loading a snapshot back into the registry and pool.
+
+use std::future::Future;
+
+use super::{CatalogEntry, RestoreSummary, RuntimeSnapshot, SnapshotError};
+use crate::models::{ModelHandle, ModelPool, ModelRegistry};
+
+impl RuntimeSnapshot {
+    /// Load the catalog with `load` (keeping warmup state) and rebuild the
+    /// pool. Models already registered under the same name are left
+    /// untouched; entries that fail to load are skipped, never registered
+    /// without their model.
+    pub async fn restore<F, Fut>(
+        &self,
+        registry: &ModelRegistry,
+        pool: Option<&ModelPool>,
+        load: F,
+    ) -> Result<RestoreSummary, SnapshotError>
+    where
+        F: Fn(&CatalogEntry) -> Fut,
+        Fut: Future<Output = Result<(), String>>,
+    {
+        let mut summary = RestoreSummary::default();
+        let existing: Vec<String> = registry.list_models().await.into_iter().map(|m| m.name).collect();
+        for entry in &self.catalog {
+            if existing.contains(&entry.name) {
+                summary.models_skipped += 1;
+                continue;
+            }
+            if let Err(reason) = load(entry).await {
+                tracing::warn!(model = %entry.name, %reason, "Snapshot model not restored");
+                summary.models_skipped += 1;
+                continue;
+            }
+            if entry.warmed {
+                registry.mark_warmed(&entry.name).await;
+            }
+            summary.models_restored += 1;
+        }
+        if let Some(pool) = pool {
+            summary.pool_members = self.restore_pool(registry, pool).await?;
+        }
+        Ok(summary)
+    }
+
+    async fn restore_pool(
+        &self,
+        registry: &ModelRegistry,
+        pool: &ModelPool,
+    ) -> Result<usize, SnapshotError> {
+        let loaded = registry.list_models().await;
+        let mut restored = 0;
+        for member in &self.pool {
+            let Some(info) = loaded.iter().find(|m| m.name == member.model_id) else {
+                continue;
+            };
+            if pool.contains(&member.model_id).await {
+                continue;
+            }
+            let handle = ModelHandle::new(info.handle_id);
+            pool.preload(member.model_id.clone(), handle, member.tier, member.memory_bytes)
+                .await
+                .map_err(|e| SnapshotError::Pool(e.to_string()))?;
+            if member.warmed {
+                pool.mark_warmed(&member.model_id).await;
+            }
+            restored += 1;
+        }
+        if let Some(ref active) = self.active_model {
+            let _ = pool.switch_to(active).await;
+        }
+        Ok(restored)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ModelMetadata, PoolConfig, PoolModelTier};
+    use crate::snapshot::ConfigSnapshot;
+    use crate::RuntimeConfig;
+    use std::fs;
+    use std::pin::Pin;
+    use std::sync::Arc;
+
+    type LoadFuture = Pin<Box<dyn Future<Output = Result<(), String>> + Send>>;
+
+    /// Stands in for the model loader: registers what the entry describes.
+    fn register_into(registry: &Arc<ModelRegistry>) -> impl Fn(&CatalogEntry) -> LoadFuture {
+        let registry = Arc::clone(registry);
+        move |entry| {
+            let registry = Arc::clone(&registry);
+            let metadata = ModelMetadata { name: entry.name.clone(), size_bytes: entry.size_bytes };
+            let (memory_bytes, format) = (entry.memory_bytes as usize, entry.format.clone());
+            Box::pin(async move {
+                registry.register_with_format(metadata, memory_bytes, format).await;
+                Ok(())
+            })
+        }
+    }
+
+    async fn populated() -> (Arc<ModelRegistry>, ModelPool) {
+        let registry = Arc::new(ModelRegistry::new());
+        let metadata = ModelMetadata { name: "phi-3".into(), size_bytes: 42 };
+        let handle = registry.register_with_format(metadata, 1024, "gguf".into()).await;
+        registry.mark_warmed("phi-3").await;
+        let pool = ModelPool::new(PoolConfig::default(), Arc::clone(&registry));
+        pool.preload("phi-3".into(), handle, PoolModelTier::Quality, 1024).await.unwrap();
+        pool.mark_warmed("phi-3").await;
+        pool.switch_to("phi-3").await.unwrap();
+        (registry, pool)
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_round_trip_restores_warm_state() {
+        let (registry, pool) = populated().await;
+        let config = ConfigSnapshot::capture(&RuntimeConfig::default());
+        let snapshot = RuntimeSnapshot::capture(config, &registry, Some(&pool)).await;
+
+        let path = std::env::temp_dir().join("veritas_test_snapshot/state.json");
+        snapshot.write_to(&path).unwrap();
+        let loaded = RuntimeSnapshot::read_from(&path).unwrap();
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+
+        let fresh = Arc::new(ModelRegistry::new());
+        let fresh_pool = ModelPool::new(PoolConfig::default(), Arc::clone(&fresh));
+        let summary = loaded.restore(&fresh, Some(&fresh_pool), register_into(&fresh)).await.unwrap();
+
+        assert_eq!(summary.models_restored, 1);
+        assert_eq!(summary.pool_members, 1);
+        assert!(fresh.list_models().await[0].warmed);
+        assert!(fresh_pool.members().await[0].warmed);
+        assert_eq!(fresh_pool.active().await.as_deref(), Some("phi-3"));
+    }
+
+    #[tokio::test]
+    async fn test_restore_skips_already_loaded_models() {
+        let (registry, _pool) = populated().await;
+        let config = ConfigSnapshot::capture(&RuntimeConfig::default());
+        let snapshot = RuntimeSnapshot::capture(config, &registry, None).await;
+        let summary = snapshot.restore(&registry, None, register_into(&registry)).await.unwrap();
+        assert_eq!(summary.models_skipped, 1);
+        assert_eq!(registry.count().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_restore_never_registers_unloaded_models() {
+        let (registry, pool) = populated().await;
+        let config = ConfigSnapshot::capture(&RuntimeConfig::default());
+        let snapshot = RuntimeSnapshot::capture(config, &registry, Some(&pool)).await;
+
+        let fresh = Arc::new(ModelRegistry::new());
+        let fresh_pool = ModelPool::new(PoolConfig::default(), Arc::clone(&fresh));
+        let fail = |_: &CatalogEntry| async { Err::<(), _>("model file missing".to_string()) };
+        let summary = snapshot.restore(&fresh, Some(&fresh_pool), fail).await.unwrap();
+
+        assert_eq!((summary.models_restored, summary.models_skipped, summary.pool_members), (0, 1, 0));
+        assert_eq!(fresh.count().await, 0);
+        assert!(fresh_pool.members().await.is_empty());
+    }
+}
@@ -0,0 +1,162 @@
+//! A remote store fronted by a local copy.
+
+use super::{LocalStorage, Storage, StorageError};
+
+/// A remote store with a local copy of everything read or written.
+pub struct CachedStorage {
+    remote: Box<dyn Storage>,
+    cache: LocalStorage,
+}
+
+impl CachedStorage {
+    pub fn new(remote: Box<dyn Storage>, cache: LocalStorage) -> Self {
+        Self { remote, cache }
+    }
+
+    fn refresh(&self, key: &str, data: Option<&[u8]>) {
+        let result = match data {
+            Some(data) => self.cache.put(key, data),
+            None => self.cache.delete(key),
+        };
+        if let Err(e) = result {
+            tracing::debug!(key, error = %e, "Storage cache update failed");
+        }
+    }
+}
+
+impl Storage for CachedStorage {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, StorageError> {
+        match self.remote.get(key) {
+            Ok(data) => {
+                self.refresh(key, data.as_deref());
+                Ok(data)
+            }
+            Err(e) => match self.cache.get(key) {
+                Ok(Some(data)) => {
+                    tracing::warn!(key, error = %e, "Object storage unreachable; using cached copy");
+                    metrics::counter!("core_storage_cache_fallbacks_total").increment(1);
+                    Ok(Some(data))
+                }
+                _ => Err(e),
+            },
+        }
+    }
+
+    fn put(&self, key: &str, data: &[u8]) -> Result<(), StorageError> {
+        self.remote.put(key, data)?;
+        self.refresh(key, Some(data));
+        Ok(())
+    }
+
+    fn put_if_absent(&self, key: &str, data: &[u8]) -> Result<bool, StorageError> {
+        let created = self.remote.put_if_absent(key, data)?;
+        if created {
+            self.refresh(key, Some(data));
+        }
+        Ok(created)
+    }
+
+    fn delete(&self, key: &str) -> Result<(), StorageError> {
+        self.remote.delete(key)?;
+        self.refresh(key, None);
+        Ok(())
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<String>, StorageError> {
+        self.remote.list(prefix)
+    }
+
+    fn describe(&self) -> String {
+        format!("{} (cache {})", self.remote.describe(), self.cache.describe())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    /// In-memory remote that can be taken offline.
+    #[derive(Default)]
+    struct Flaky {
+        inner: std::sync::Mutex<std::collections::BTreeMap<String, Vec<u8>>>,
+        down: Arc<AtomicBool>,
+    }
+
+    impl Flaky {
+        fn check(&self) -> Result<(), StorageError> {
+            if self.down.load(Ordering::SeqCst) {
+                return Err(StorageError::Timeout);
+            }
+            Ok(())
+        }
+    }
+
+    impl Storage for Flaky {
+        fn get(&self, key: &str) -> Result<Option<Vec<u8>>, StorageError> {
+            self.check()?;
+            Ok(self.inner.lock().unwrap().get(key).cloned())
+        }
+
+        fn put(&self, key: &str, data: &[u8]) -> Result<(), StorageError> {
+            self.check()?;
+            self.inner.lock().unwrap().insert(key.to_string(), data.to_vec());
+            Ok(())
+        }
+
+        fn put_if_absent(&self, key: &str, data: &[u8]) -> Result<bool, StorageError> {
+            self.check()?;
+            let mut inner = self.inner.lock().unwrap();
+            if inner.contains_key(key) {
+                return Ok(false);
+            }
+            inner.insert(key.to_string(), data.to_vec());
+            Ok(true)
+        }
+
+        fn delete(&self, key: &str) -> Result<(), StorageError> {
+            self.check()?;
+            self.inner.lock().unwrap().remove(key);
+            Ok(())
+        }
+
+        fn list(&self, prefix: &str) -> Result<Vec<String>, StorageError> {
+            self.check()?;
+            Ok(self.inner.lock().unwrap().keys().filter(|k| k.starts_with(prefix)).cloned().collect())
+        }
+
+        fn describe(&self) -> String {
+            "flaky".into()
+        }
+    }
+
+    #[test]
+    fn test_cached_storage_falls_back_while_remote_down() {
+        let dir = tempfile::tempdir().unwrap();
+        let remote = Flaky::default();
+        let down = Arc::clone(&remote.down);
+        let storage = CachedStorage::new(Box::new(remote), LocalStorage::new(dir.path()));
+
+        storage.put("registry_state.json", b"{}").unwrap();
+        assert_eq!(fs::read(dir.path().join("registry_state.json")).unwrap(), b"{}");
+
+        down.store(true, Ordering::SeqCst);
+        assert_eq!(storage.get("registry_state.json").unwrap(), Some(b"{}".to_vec()));
+        // Never cached, so the remote error surfaces
+        assert_eq!(storage.get("salt"), Err(StorageError::Timeout));
+        // Writes must reach the remote
+        assert_eq!(storage.put("salt", b"x"), Err(StorageError::Timeout));
+        assert!(!dir.path().join("salt").exists());
+    }
+
+    #[test]
+    fn test_put_if_absent_reaches_the_remote() {
+        let dir = tempfile::tempdir().unwrap();
+        let cached = CachedStorage::new(Box::new(Flaky::default()), LocalStorage::new(dir.path()));
+        assert!(cached.put_if_absent("salt", b"first").unwrap());
+        assert!(!cached.put_if_absent("salt", b"second").unwrap());
+        assert_eq!(fs::read(dir.path().join("salt")).unwrap(), b"first");
+    }
+}
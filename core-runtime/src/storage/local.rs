@@ -0,0 +1,178 @@
+//! Objects as files under a local directory.
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use super::{validate_key, Storage, StorageError};
+
+/// Objects as files under a root directory.
+#[derive(Debug, Clone)]
+pub struct LocalStorage {
+    root: PathBuf,
+}
+
+impl LocalStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn path(&self, key: &str) -> Result<PathBuf, StorageError> {
+        validate_key(key)?;
+        Ok(self.root.join(key))
+    }
+}
+
+impl Storage for LocalStorage {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, StorageError> {
+        match fs::read(self.path(key)?) {
+            Ok(data) => Ok(Some(data)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(io_error(key, e)),
+        }
+    }
+
+    fn put(&self, key: &str, data: &[u8]) -> Result<(), StorageError> {
+        let path = self.path(key)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| io_error(key, e))?;
+        }
+        // Write a sibling then rename, so a crash leaves the old object
+        let temp = temp_path(&path);
+        write_private(&temp, data).and_then(|()| fs::rename(&temp, &path)).map_err(|e| {
+            let _ = fs::remove_file(&temp);
+            io_error(key, e)
+        })
+    }
+
+    fn put_if_absent(&self, key: &str, data: &[u8]) -> Result<bool, StorageError> {
+        let path = self.path(key)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| io_error(key, e))?;
+        }
+        // Linking a complete sibling fails if the key exists, so the object
+        // appears whole and only one writer wins
+        let temp = temp_path(&path);
+        let linked = write_private(&temp, data).and_then(|()| fs::hard_link(&temp, &path));
+        let _ = fs::remove_file(&temp);
+        match linked {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => Ok(false),
+            Err(e) => Err(io_error(key, e)),
+        }
+    }
+
+    fn delete(&self, key: &str) -> Result<(), StorageError> {
+        match fs::remove_file(self.path(key)?) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(io_error(key, e)),
+        }
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<String>, StorageError> {
+        let mut keys = Vec::new();
+        collect_keys(&self.root, "", &mut keys).map_err(|e| io_error(prefix, e))?;
+        keys.retain(|key| key.starts_with(prefix));
+        keys.sort();
+        Ok(keys)
+    }
+
+    fn describe(&self) -> String {
+        self.root.display().to_string()
+    }
+}
+
+/// A sibling temporary unique to this write, skipped by `list`.
+fn temp_path(path: &Path) -> PathBuf {
+    static NEXT: AtomicU64 = AtomicU64::new(0);
+    path.with_file_name(format!(
+        ".{}.{}-{}.tmp",
+        path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default(),
+        std::process::id(),
+        NEXT.fetch_add(1, Ordering::Relaxed)
+    ))
+}
+
+fn collect_keys(dir: &Path, prefix: &str, keys: &mut Vec<String>) -> io::Result<()> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e),
+    };
+    for entry in entries {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        // In-flight temporaries are not objects
+        if name.starts_with('.') && name.ends_with(".tmp") {
+            continue;
+        }
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            collect_keys(&entry.path(), &format!("{}{}/", prefix, name), keys)?;
+        } else if file_type.is_file() {
+            keys.push(format!("{}{}", prefix, name));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn write_private(path: &Path, data: &[u8]) -> io::Result<()> {
+    use std::os::unix::fs::OpenOptionsExt;
+
+    let mut file = fs::OpenOptions::new().write(true).create(true).truncate(true).mode(0o600).open(path)?;
+    file.write_all(data)?;
+    file.sync_all()
+}
+
+#[cfg(not(unix))]
+fn write_private(path: &Path, data: &[u8]) -> io::Result<()> {
+    let mut file = fs::File::create(path)?;
+    file.write_all(data)?;
+    file.sync_all()
+}
+
+fn io_error(key: &str, e: io::Error) -> StorageError {
+    StorageError::Io { key: key.to_string(), reason: e.to_string() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_storage() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = LocalStorage::new(dir.path());
+        assert_eq!(storage.get("salt").unwrap(), None);
+        storage.put("salt", b"one").unwrap();
+        storage.put("journal/2026-10-17.log", b"two").unwrap();
+        storage.put("salt", b"three").unwrap();
+        assert_eq!(storage.get("salt").unwrap(), Some(b"three".to_vec()));
+        assert_eq!(storage.list("").unwrap(), ["journal/2026-10-17.log", "salt"]);
+        assert_eq!(storage.list("journal/").unwrap(), ["journal/2026-10-17.log"]);
+        storage.delete("salt").unwrap();
+        storage.delete("salt").unwrap();
+        assert_eq!(storage.get("salt").unwrap(), None);
+
+        for key in ["", "/etc/passwd", "../salt", "a//b", "a/./b", "a\\b"] {
+            assert_eq!(storage.put(key, b"x"), Err(StorageError::InvalidKey(key.to_string())));
+        }
+    }
+
+    #[test]
+    fn test_put_if_absent_keeps_the_first_object() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = LocalStorage::new(dir.path());
+        assert!(storage.put_if_absent("keys/salt", b"first").unwrap());
+        assert!(!storage.put_if_absent("keys/salt", b"second").unwrap());
+        assert_eq!(storage.get("keys/salt").unwrap(), Some(b"first".to_vec()));
+        assert_eq!(storage.list("").unwrap(), ["keys/salt"]);
+    }
+}
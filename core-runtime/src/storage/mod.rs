@@ -0,0 +1,177 @@
+//! Storage for runtime state kept under `base_path`.
+//!
+//! The installation salt and the model catalog (`registry_state.json`) are
+//! read and written through a [`Storage`]. By default nothing is installed
+//! and both stay on the local filesystem where they always lived. A
+//! diskless container installs an object store instead (S3 or compatible,
+//! `object-storage` feature) so the salt, and with it every key derived
+//! from the machine, survives a restart.
+//!
+//! Remote stores are wrapped in a [`CachedStorage`]: writes go to the
+//! remote first and only succeed once it has them, and reads fall back to
+//! the local copy while the remote is unreachable.
+//!
+//! Nothing else under `base_path` goes through a store. The runtime keeps
+//! no KV spill files, and `<base_path>/journal` is only swept in place by
+//! retention; it is written by external tooling, not the runtime.
+
+mod cached;
+mod local;
+#[cfg(feature = "object-storage")]
+pub mod s3;
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use thiserror::Error;
+
+pub use cached::CachedStorage;
+pub use local::LocalStorage;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum StorageError {
+    #[error("Invalid storage key '{0}'")]
+    InvalidKey(String),
+
+    #[error("Invalid storage URL: {0}")]
+    InvalidUrl(String),
+
+    #[error("Storage I/O error on '{key}': {reason}")]
+    Io { key: String, reason: String },
+
+    #[error("Object storage error: {0}")]
+    Remote(String),
+
+    #[error("Object storage timed out")]
+    Timeout,
+}
+
+/// Byte objects addressed by `/`-separated keys such as
+/// `registry_state.json`.
+pub trait Storage: Send + Sync {
+    /// The object at `key`, or `None` if there is none.
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, StorageError>;
+
+    /// Replace the object at `key`. Readers see the old or the new bytes,
+    /// never a mix.
+    fn put(&self, key: &str, data: &[u8]) -> Result<(), StorageError>;
+
+    /// Create the object at `key` only if there is none, atomically with
+    /// respect to other writers. Returns whether this call created it; an
+    /// existing object is left untouched.
+    fn put_if_absent(&self, key: &str, data: &[u8]) -> Result<bool, StorageError>;
+
+    /// Remove the object at `key`; a missing object is not an error.
+    fn delete(&self, key: &str) -> Result<(), StorageError>;
+
+    /// Keys starting with `prefix`, sorted.
+    fn list(&self, prefix: &str) -> Result<Vec<String>, StorageError>;
+
+    /// Short description for logs, e.g. `s3://bucket/prefix`.
+    fn describe(&self) -> String;
+}
+
+/// Reject keys that are empty, absolute or climb out of the store.
+pub fn validate_key(key: &str) -> Result<(), StorageError> {
+    let bad = key.is_empty()
+        || key.starts_with('/')
+        || key.contains('\\')
+        || key.split('/').any(|segment| segment.is_empty() || segment == "." || segment == "..");
+    if bad {
+        return Err(StorageError::InvalidKey(key.to_string()));
+    }
+    Ok(())
+}
+
+/// Object storage settings.
+#[derive(Clone)]
+pub struct StorageConfig {
+    /// `s3://bucket[/prefix]`; `None` keeps state on the local filesystem.
+    pub url: Option<String>,
+    /// `https://host[:port]` of the S3-compatible service; plaintext
+    /// `http://` is refused.
+    pub endpoint: Option<String>,
+    pub region: String,
+    pub access_key: String,
+    pub secret_key: String,
+    /// Local copy of remote objects; `<base_path>/storage-cache` if unset.
+    pub cache_dir: Option<PathBuf>,
+    /// Per-request deadline.
+    pub timeout: Duration,
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self {
+            url: None,
+            endpoint: None,
+            region: "us-east-1".into(),
+            access_key: String::new(),
+            secret_key: String::new(),
+            cache_dir: None,
+            timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+impl fmt::Debug for StorageConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StorageConfig")
+            .field("url", &self.url)
+            .field("endpoint", &self.endpoint)
+            .field("region", &self.region)
+            .field("access_key", &self.access_key)
+            .field("secret_key", &"<redacted>")
+            .field("cache_dir", &self.cache_dir)
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+/// Open the store `config` names, cached under `base_path`. `None` when
+/// no remote store is configured.
+pub fn open(config: &StorageConfig, base_path: &Path) -> Result<Option<Arc<dyn Storage>>, StorageError> {
+    let Some(url) = &config.url else {
+        return Ok(None);
+    };
+    let remote = remote_storage(url, config)?;
+    let cache = LocalStorage::new(config.cache_dir.clone().unwrap_or_else(|| base_path.join("storage-cache")));
+    Ok(Some(Arc::new(CachedStorage::new(remote, cache))))
+}
+
+#[cfg(feature = "object-storage")]
+fn remote_storage(url: &str, config: &StorageConfig) -> Result<Box<dyn Storage>, StorageError> {
+    Ok(Box::new(s3::S3Storage::from_config(url, config)?))
+}
+
+#[cfg(not(feature = "object-storage"))]
+fn remote_storage(_url: &str, _config: &StorageConfig) -> Result<Box<dyn Storage>, StorageError> {
+    Err(StorageError::Remote("not compiled in. Enable 'object-storage' feature.".into()))
+}
+
+static STORAGE: OnceLock<Arc<dyn Storage>> = OnceLock::new();
+
+/// Install the process-wide store. Only the first call takes effect.
+pub fn install(storage: Arc<dyn Storage>) {
+    let _ = STORAGE.set(storage);
+}
+
+/// The installed store; `None` means state lives on the local filesystem.
+pub fn installed() -> Option<Arc<dyn Storage>> {
+    STORAGE.get().cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_open_without_url_keeps_local() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(open(&StorageConfig::default(), dir.path()).unwrap().is_none());
+        let debug = format!("{:?}", StorageConfig { secret_key: "hunter2".into(), ..Default::default() });
+        assert!(!debug.contains("hunter2"));
+    }
+}
@@ -0,0 +1,102 @@
+//! Signed HTTPS requests to the object store.
+
+use std::io::{self, Read};
+use std::time::SystemTime;
+
+use aws_sigv4::http_request::{
+    sign, PayloadChecksumKind, PercentEncodingMode, SignableBody, SignableRequest, SigningSettings,
+    UriPathNormalizationMode,
+};
+use aws_sigv4::sign::v4;
+
+use super::{xml, S3Storage};
+use crate::storage::StorageError;
+
+/// Largest response body accepted; catalogs and salts are small.
+const MAX_BODY_LEN: u64 = 64 * 1024 * 1024;
+
+pub(super) struct Response {
+    pub(super) status: u16,
+    pub(super) body: Vec<u8>,
+}
+
+impl Response {
+    /// A 404 for the object itself, not for a missing bucket.
+    pub(super) fn is_missing_key(&self) -> bool {
+        self.status == 404 && xml::error_code(&self.body).as_deref() != Some("NoSuchBucket")
+    }
+}
+
+impl S3Storage {
+    pub(super) fn request(
+        &self,
+        method: &str,
+        url: &str,
+        headers: &[(&str, &str)],
+        body: &[u8],
+    ) -> Result<Response, StorageError> {
+        let signed = self.signature_headers(method, url, headers, body)?;
+        let mut request = self.agent.request(method, url);
+        for (name, value) in headers.iter().copied().chain(signed.iter().map(|(n, v)| (n.as_str(), v.as_str()))) {
+            request = request.set(name, value);
+        }
+        // Error statuses still carry the S3 error document
+        let response = match request.send_bytes(body) {
+            Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+            Err(ureq::Error::Transport(e)) => return Err(transport(&e)),
+        };
+        let status = response.status();
+        let mut body = Vec::new();
+        response.into_reader().take(MAX_BODY_LEN + 1).read_to_end(&mut body).map_err(|e| io_failure(&e))?;
+        if body.len() as u64 > MAX_BODY_LEN {
+            return Err(StorageError::Remote("response too large".into()));
+        }
+        Ok(Response { status, body })
+    }
+
+    /// The SigV4 `authorization`, `x-amz-date` and `x-amz-content-sha256`
+    /// headers for a request carrying `headers`.
+    fn signature_headers(
+        &self,
+        method: &str,
+        url: &str,
+        headers: &[(&str, &str)],
+        body: &[u8],
+    ) -> Result<Vec<(String, String)>, StorageError> {
+        let failed = |e: &dyn std::fmt::Display| StorageError::Remote(format!("request signing failed: {}", e));
+        // S3 wants the payload hash header and the path signed exactly as sent
+        let mut settings = SigningSettings::default();
+        settings.payload_checksum_kind = PayloadChecksumKind::XAmzSha256;
+        settings.percent_encoding_mode = PercentEncodingMode::Single;
+        settings.uri_path_normalization_mode = UriPathNormalizationMode::Disabled;
+
+        let identity = self.credentials.clone().into();
+        let params = v4::SigningParams::builder()
+            .identity(&identity)
+            .region(&self.region)
+            .name("s3")
+            .time(SystemTime::now())
+            .settings(settings)
+            .build()
+            .map_err(|e| failed(&e))?
+            .into();
+        let signable = SignableRequest::new(method, url, headers.iter().copied(), SignableBody::Bytes(body))
+            .map_err(|e| failed(&e))?;
+        let (instructions, _) = sign(signable, &params).map_err(|e| failed(&e))?.into_parts();
+        Ok(instructions.headers().map(|(name, value)| (name.to_string(), value.to_string())).collect())
+    }
+}
+
+fn transport(e: &ureq::Transport) -> StorageError {
+    match std::error::Error::source(e).and_then(|source| source.downcast_ref::<io::Error>()) {
+        Some(io) => io_failure(io),
+        None => StorageError::Remote(e.to_string()),
+    }
+}
+
+fn io_failure(e: &io::Error) -> StorageError {
+    match e.kind() {
+        io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock => StorageError::Timeout,
+        _ => StorageError::Remote(e.to_string()),
+    }
+}
@@ -0,0 +1,67 @@
+//! ListObjectsV2 and error documents, parsed with `quick-xml`.
+
+use serde::Deserialize;
+
+use crate::storage::StorageError;
+
+/// One page of a ListObjectsV2 response.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub(super) struct ListPage {
+    #[serde(default)]
+    pub(super) contents: Vec<ListEntry>,
+    #[serde(default)]
+    pub(super) is_truncated: bool,
+    pub(super) next_continuation_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub(super) struct ListEntry {
+    pub(super) key: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ErrorDocument {
+    code: String,
+}
+
+pub(super) fn list_page(body: &[u8]) -> Result<ListPage, StorageError> {
+    let body = std::str::from_utf8(body).map_err(|e| StorageError::Remote(format!("LIST response: {}", e)))?;
+    quick_xml::de::from_str(body).map_err(|e| StorageError::Remote(format!("LIST response: {}", e)))
+}
+
+/// The `<Code>` of an S3 error body, if it is one.
+pub(super) fn error_code(body: &[u8]) -> Option<String> {
+    let body = std::str::from_utf8(body).ok()?;
+    quick_xml::de::from_str::<ErrorDocument>(body).ok().map(|doc| doc.code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_parsing() {
+        let xml = "<ListBucketResult><IsTruncated>true</IsTruncated>\
+                   <Contents><Key>prod/salt</Key><Size>16</Size></Contents>\
+                   <Contents><Key>prod/a&amp;b</Key></Contents>\
+                   <NextContinuationToken>t1</NextContinuationToken></ListBucketResult>";
+        let page = list_page(xml.as_bytes()).unwrap();
+        let keys: Vec<_> = page.contents.iter().map(|entry| entry.key.as_str()).collect();
+        assert_eq!(keys, ["prod/salt", "prod/a&b"]);
+        assert!(page.is_truncated);
+        assert_eq!(page.next_continuation_token.as_deref(), Some("t1"));
+
+        let empty = list_page(b"<ListBucketResult><IsTruncated>false</IsTruncated></ListBucketResult>").unwrap();
+        assert!(empty.contents.is_empty() && !empty.is_truncated);
+    }
+
+    #[test]
+    fn test_error_code() {
+        let xml = "<Error><Code>NoSuchBucket</Code><Message>gone</Message></Error>";
+        assert_eq!(error_code(xml.as_bytes()).as_deref(), Some("NoSuchBucket"));
+        assert_eq!(error_code(b"not xml"), None);
+    }
+}
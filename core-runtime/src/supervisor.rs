@@ -0,0 +1,303 @@
+//! Warm standby supervisor for installs without systemd.
+//!
+//! `serve --supervise` keeps a small parent process that binds the IPC
+//! socket once and runs the server as a child, restarting it with
+//! exponential backoff when it crashes. The listening socket is passed to
+//! every child as fd 3, the way socket activation passes it, so clients
+//! wait in the listen backlog across a restart instead of finding the
+//! socket gone. The parent holds the instance lock for the whole run.
+//!
+//! A child that exits 0 has shut down gracefully and ends supervision.
+//! SIGTERM or SIGINT to the supervisor is forwarded to the child as
+//! SIGTERM, so it drains as usual. Progress is published to
+//! [`SUPERVISOR_STATUS_FILE`] under `base_path`.
+
+use std::future::Future;
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::os::unix::net::UnixListener;
+use std::os::unix::process::ExitStatusExt;
+use std::path::{Path, PathBuf};
+use std::process::ExitStatus;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::process::{Child, Command};
+
+use crate::ipc::listener::{LISTEN_FDS_START, SUPERVISOR_PID_ENV};
+
+/// Status file under `base_path`.
+pub const SUPERVISOR_STATUS_FILE: &str = "supervisor.json";
+
+#[derive(Error, Debug)]
+pub enum SupervisorError {
+    #[error("Failed to start server child: {0}")]
+    Spawn(std::io::Error),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Restart timing.
+#[derive(Debug, Clone)]
+pub struct SupervisorConfig {
+    /// Delay before the first restart.
+    pub initial_backoff: Duration,
+    /// Longest delay between restarts.
+    pub max_backoff: Duration,
+    /// A child that ran this long resets the delay.
+    pub stable_after: Duration,
+}
+
+impl Default for SupervisorConfig {
+    fn default() -> Self {
+        Self {
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+            stable_after: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Exponential restart delay.
+#[derive(Debug)]
+pub struct Backoff {
+    config: SupervisorConfig,
+    next: Duration,
+}
+
+impl Backoff {
+    pub fn new(config: &SupervisorConfig) -> Self {
+        Self { config: config.clone(), next: config.initial_backoff }
+    }
+
+    /// Delay before restarting a child that ran for `ran`.
+    pub fn after(&mut self, ran: Duration) -> Duration {
+        if ran >= self.config.stable_after {
+            self.next = self.config.initial_backoff;
+        }
+        let delay = self.next;
+        self.next = (self.next * 2).min(self.config.max_backoff);
+        delay
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChildState {
+    Running,
+    /// Crashed; waiting to restart.
+    Backoff,
+    /// Supervision ended.
+    Stopped,
+}
+
+/// Contents of [`SUPERVISOR_STATUS_FILE`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SupervisorStatus {
+    pub supervisor_pid: u32,
+    pub state: ChildState,
+    pub child_pid: Option<u32>,
+    pub started_at: DateTime<Utc>,
+    pub child_started_at: Option<DateTime<Utc>>,
+    /// Children started after the first.
+    pub restarts: u64,
+    pub last_restart_at: Option<DateTime<Utc>>,
+    /// How the previous child ended, e.g. `signal 11`.
+    pub last_exit: Option<String>,
+}
+
+impl SupervisorStatus {
+    fn new() -> Self {
+        Self {
+            supervisor_pid: std::process::id(),
+            state: ChildState::Running,
+            child_pid: None,
+            started_at: Utc::now(),
+            child_started_at: None,
+            restarts: 0,
+            last_restart_at: None,
+            last_exit: None,
+        }
+    }
+
+    /// Publish the status under `base_path`, replacing the previous one.
+    pub fn write(&self, base_path: &Path) -> std::io::Result<()> {
+        let path = base_path.join(SUPERVISOR_STATUS_FILE);
+        let temp = path.with_extension("json.tmp");
+        std::fs::write(&temp, serde_json::to_vec_pretty(self)?)?;
+        std::fs::rename(&temp, &path)
+    }
+
+    pub fn read(base_path: &Path) -> std::io::Result<Self> {
+        let data = std::fs::read(base_path.join(SUPERVISOR_STATUS_FILE))?;
+        Ok(serde_json::from_slice(&data)?)
+    }
+}
+
+/// Runs and restarts the server child.
+pub struct Supervisor {
+    config: SupervisorConfig,
+    program: PathBuf,
+    args: Vec<String>,
+    base_path: PathBuf,
+    status: SupervisorStatus,
+}
+
+impl Supervisor {
+    pub fn new(config: SupervisorConfig, program: PathBuf, args: Vec<String>, base_path: PathBuf) -> Self {
+        Self { config, program, args, base_path, status: SupervisorStatus::new() }
+    }
+
+    /// Supervise children serving `listener` until one exits cleanly or
+    /// `shutdown` completes. Returns the final status.
+    pub async fn run(
+        mut self,
+        listener: &UnixListener,
+        shutdown: impl Future<Output = ()>,
+    ) -> Result<SupervisorStatus, SupervisorError> {
+        tokio::pin!(shutdown);
+        let mut backoff = Backoff::new(&self.config);
+        loop {
+            let mut child = self.spawn(listener.as_raw_fd()).map_err(SupervisorError::Spawn)?;
+            let started = Instant::now();
+            self.status.state = ChildState::Running;
+            self.status.child_pid = child.id();
+            self.status.child_started_at = Some(Utc::now());
+            self.publish();
+
+            let (exit, stopping) = tokio::select! {
+                exit = child.wait() => (exit?, false),
+                () = &mut shutdown => {
+                    if let Some(pid) = child.id() {
+                        // SAFETY: signalling our own child
+                        unsafe { libc::kill(pid as libc::pid_t, libc::SIGTERM) };
+                    }
+                    (child.wait().await?, true)
+                }
+            };
+            self.status.child_pid = None;
+            self.status.last_exit = Some(describe_exit(exit));
+            if stopping || exit.success() {
+                return Ok(self.stop());
+            }
+
+            let delay = backoff.after(started.elapsed());
+            tracing::warn!(
+                exit = self.status.last_exit.as_deref().unwrap_or_default(),
+                delay_ms = delay.as_millis() as u64,
+                "Server child crashed; restarting"
+            );
+            eprintln!(
+                "Supervisor: server {}; restarting in {:?}",
+                self.status.last_exit.as_deref().unwrap_or_default(),
+                delay
+            );
+            self.status.state = ChildState::Backoff;
+            self.publish();
+            tokio::select! {
+                () = tokio::time::sleep(delay) => {}
+                () = &mut shutdown => return Ok(self.stop()),
+            }
+            self.status.restarts += 1;
+            self.status.last_restart_at = Some(Utc::now());
+        }
+    }
+
+    fn spawn(&self, listener: RawFd) -> std::io::Result<Child> {
+        let mut command = Command::new(&self.program);
+        command
+            .args(&self.args)
+            .env(SUPERVISOR_PID_ENV, std::process::id().to_string())
+            .env_remove("LISTEN_PID")
+            .env_remove("LISTEN_FDS");
+        // SAFETY: pass_listener makes only async-signal-safe calls
+        unsafe {
+            command.pre_exec(move || pass_listener(listener));
+        }
+        command.spawn()
+    }
+
+    fn stop(mut self) -> SupervisorStatus {
+        self.status.state = ChildState::Stopped;
+        self.publish();
+        self.status
+    }
+
+    fn publish(&self) {
+        if let Err(e) = self.status.write(&self.base_path) {
+            tracing::warn!(error = %e, "Failed to write supervisor status");
+        }
+    }
+}
+
+/// Put the listener at fd 3 in the child, open across exec.
+fn pass_listener(fd: RawFd) -> std::io::Result<()> {
+    // dup2 clears close-on-exec on the copy; a listener already at fd 3
+    // needs the flag cleared directly
+    let rc = if fd == LISTEN_FDS_START {
+        // SAFETY: fcntl on a descriptor we own
+        unsafe { libc::fcntl(fd, libc::F_SETFD, 0) }
+    } else {
+        // SAFETY: dup2 between descriptors we own
+        unsafe { libc::dup2(fd, LISTEN_FDS_START) }
+    };
+    if rc < 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+fn describe_exit(status: ExitStatus) -> String {
+    match (status.code(), status.signal()) {
+        (Some(code), _) => format!("exit code {}", code),
+        (None, Some(signal)) => format!("signal {}", signal),
+        (None, None) => status.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles_and_resets() {
+        let config = SupervisorConfig {
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(3),
+            stable_after: Duration::from_secs(60),
+        };
+        let mut backoff = Backoff::new(&config);
+        let quick = Duration::from_secs(1);
+        assert_eq!(backoff.after(quick), Duration::from_secs(1));
+        assert_eq!(backoff.after(quick), Duration::from_secs(2));
+        assert_eq!(backoff.after(quick), Duration::from_secs(3));
+        assert_eq!(backoff.after(Duration::from_secs(60)), Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_restarts_crashed_child() {
+        let dir = tempfile::tempdir().unwrap();
+        let listener = UnixListener::bind(dir.path().join("test.sock")).unwrap();
+        // Crash once, then exit cleanly; fd 3 must be the listener both times
+        let marker = dir.path().join("crashed");
+        let script = format!(
+            "test -S /dev/fd/3 || exit 9; test -f {0} && exit 0; touch {0}; exit 3",
+            marker.display()
+        );
+        let config = SupervisorConfig { initial_backoff: Duration::from_millis(10), ..Default::default() };
+        let supervisor = Supervisor::new(
+            config,
+            PathBuf::from("/bin/sh"),
+            vec!["-c".into(), script],
+            dir.path().to_path_buf(),
+        );
+
+        let status = supervisor.run(&listener, std::future::pending()).await.unwrap();
+        assert_eq!(status.state, ChildState::Stopped);
+        assert_eq!(status.restarts, 1);
+        assert_eq!(status.last_exit.as_deref(), Some("exit code 0"));
+        assert_eq!(SupervisorStatus::read(dir.path()).unwrap(), status);
+    }
+}
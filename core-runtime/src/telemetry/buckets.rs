@@ -20,6 +20,16 @@ pub const DEFAULT_THROUGHPUT_BUCKETS: [f64; 10] = [
     10.0, 25.0, 50.0, 100.0, 250.0, 500.0, 1000.0, 2500.0, 5000.0, 10000.0,
 ];
 
+/// End-to-end request latency buckets in milliseconds.
+pub const REQUEST_LATENCY_BUCKETS: [f64; 12] = [
+    50.0, 100.0, 250.0, 500.0, 1000.0, 2500.0, 5000.0, 10000.0, 15000.0, 30000.0, 60000.0, 120000.0,
+];
+
+/// Time-to-first-token buckets in milliseconds.
+pub const TTFT_BUCKETS: [f64; 11] = [
+    10.0, 25.0, 50.0, 100.0, 250.0, 500.0, 1000.0, 2500.0, 5000.0, 10000.0, 30000.0,
+];
+
 /// Snapshot of a bucketed histogram for serialization.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BucketedHistogramSnapshot {
@@ -29,6 +39,51 @@ pub struct BucketedHistogramSnapshot {
     pub sum: f64,
 }
 
+impl BucketedHistogramSnapshot {
+    /// Observations known to be at most `threshold`.
+    ///
+    /// Only buckets whose upper bound is `<= threshold` are counted, so a
+    /// threshold between boundaries is rounded down (conservative).
+    pub fn count_at_most(&self, threshold: f64) -> u64 {
+        self.boundaries
+            .iter()
+            .zip(&self.bucket_counts)
+            .take_while(|(boundary, _)| **boundary <= threshold)
+            .map(|(_, count)| count)
+            .sum()
+    }
+
+    /// Mean of all observations (0 when empty).
+    pub fn mean(&self) -> f64 {
+        if self.count > 0 {
+            self.sum / self.count as f64
+        } else {
+            0.0
+        }
+    }
+
+    /// Estimate quantile `q` (0..=1) by linear interpolation within the
+    /// bucket that contains it. Values in the +Inf bucket report the
+    /// largest finite boundary.
+    pub fn quantile(&self, q: f64) -> f64 {
+        if self.count == 0 {
+            return 0.0;
+        }
+        let rank = q.clamp(0.0, 1.0) * self.count as f64;
+        let mut cumulative = 0u64;
+        let mut lower = 0.0;
+        for (boundary, &count) in self.boundaries.iter().zip(&self.bucket_counts) {
+            if count > 0 && (cumulative + count) as f64 >= rank {
+                let fraction = (rank - cumulative as f64) / count as f64;
+                return lower + (boundary - lower) * fraction;
+            }
+            cumulative += count;
+            lower = *boundary;
+        }
+        self.boundaries.last().copied().unwrap_or(0.0)
+    }
+}
+
 /// Thread-safe bucketed histogram with configurable boundaries.
 pub struct BucketedHistogram {
     boundaries: Vec<f64>,
@@ -124,6 +179,34 @@ mod tests {
         assert_eq!(snap.bucket_counts, vec![1, 1, 1, 1]);
     }
 
+    #[test]
+    fn test_count_at_most_rounds_down() {
+        let h = BucketedHistogram::new(&[1.0, 5.0, 10.0]);
+        for v in [0.5, 3.0, 7.0, 15.0] {
+            h.observe(v);
+        }
+        let snap = h.snapshot();
+        assert_eq!(snap.count_at_most(5.0), 2);
+        assert_eq!(snap.count_at_most(8.0), 2);
+        assert_eq!(snap.count_at_most(0.1), 0);
+    }
+
+    #[test]
+    fn test_quantile_interpolates_within_bucket() {
+        let h = BucketedHistogram::new(&[10.0, 20.0, 40.0]);
+        for _ in 0..50 {
+            h.observe(5.0);
+        }
+        for _ in 0..50 {
+            h.observe(30.0);
+        }
+        let snap = h.snapshot();
+        assert_eq!(snap.quantile(0.5), 10.0);
+        assert_eq!(snap.quantile(0.75), 30.0);
+        assert_eq!(snap.mean(), 17.5);
+        assert_eq!(BucketedHistogram::latency().snapshot().quantile(0.95), 0.0);
+    }
+
     #[test]
     fn test_default_latency_buckets() {
         let h = BucketedHistogram::latency();
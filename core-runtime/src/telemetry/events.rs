@@ -0,0 +1,132 @@
+//! Runtime lifecycle events for in-process subscribers.
+//!
+//! Embedding hosts want to react to model loads, evictions and health
+//! transitions without scraping logs. Components call [`emit_event`];
+//! subscribers registered with [`subscribe_events`] are invoked
+//! synchronously on the emitting thread, so they must be cheap and must
+//! not block.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tracing::Level;
+
+/// Kind of runtime event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuntimeEventKind {
+    ModelLoaded,
+    ModelUnloaded,
+    ModelEvicted,
+    HealthChanged,
+    /// An SLO started or stopped burning its error budget too fast.
+    SloBurn,
+    /// GPU traffic throttling started or ended on temperature or power.
+    ThermalThrottle,
+    /// A process resource started or stopped growing steadily.
+    ResourceGrowth,
+}
+
+impl RuntimeEventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ModelLoaded => "model_loaded",
+            Self::ModelUnloaded => "model_unloaded",
+            Self::ModelEvicted => "model_evicted",
+            Self::HealthChanged => "health_changed",
+            Self::SloBurn => "slo_burn",
+            Self::ThermalThrottle => "thermal_throttle",
+            Self::ResourceGrowth => "resource_growth",
+        }
+    }
+}
+
+/// A single runtime event.
+#[derive(Debug, Clone)]
+pub struct RuntimeEvent {
+    pub kind: RuntimeEventKind,
+    pub level: Level,
+    /// What the event is about (model ID, health state).
+    pub subject: String,
+    /// Free-form human-readable detail.
+    pub detail: String,
+    pub timestamp_ms: u64,
+}
+
+impl RuntimeEvent {
+    pub fn new(kind: RuntimeEventKind, level: Level, subject: impl Into<String>, detail: impl Into<String>) -> Self {
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        Self {
+            kind,
+            level,
+            subject: subject.into(),
+            detail: detail.into(),
+            timestamp_ms,
+        }
+    }
+}
+
+type Listener = Arc<dyn Fn(&RuntimeEvent) + Send + Sync>;
+
+static LISTENERS: RwLock<Vec<(u64, Listener)>> = RwLock::new(Vec::new());
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Register a listener. Returns an ID for [`unsubscribe_events`].
+pub fn subscribe_events(listener: impl Fn(&RuntimeEvent) + Send + Sync + 'static) -> u64 {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    LISTENERS
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .push((id, Arc::new(listener)));
+    id
+}
+
+/// Remove a listener. Returns false if the ID was unknown.
+pub fn unsubscribe_events(id: u64) -> bool {
+    let mut listeners = LISTENERS.write().unwrap_or_else(|e| e.into_inner());
+    let before = listeners.len();
+    listeners.retain(|(lid, _)| *lid != id);
+    listeners.len() != before
+}
+
+/// Deliver an event to every listener.
+pub fn emit_event(event: RuntimeEvent) {
+    tracing::debug!(kind = event.kind.as_str(), subject = %event.subject, "{}", event.detail);
+    // Clone the list so listeners may (un)subscribe without deadlocking
+    let listeners: Vec<Listener> = LISTENERS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .map(|(_, l)| Arc::clone(l))
+        .collect();
+    for listener in listeners {
+        listener(&event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_subscribe_receive_unsubscribe() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        let id = subscribe_events(move |e| {
+            if e.subject == "events-test-model" {
+                sink.lock().unwrap().push(e.kind);
+            }
+        });
+
+        emit_event(RuntimeEvent::new(RuntimeEventKind::ModelLoaded, Level::INFO, "events-test-model", "loaded"));
+        assert!(unsubscribe_events(id));
+        assert!(!unsubscribe_events(id));
+        emit_event(RuntimeEvent::new(RuntimeEventKind::ModelEvicted, Level::WARN, "events-test-model", "evicted"));
+
+        assert_eq!(*seen.lock().unwrap(), vec![RuntimeEventKind::ModelLoaded]);
+    }
+}
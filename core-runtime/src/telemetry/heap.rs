@@ -0,0 +1,333 @@
+//! Heap allocation tracking by tag.
+//!
+//! `TrackingAllocator` wraps the system allocator and attributes every
+//! allocation to the tag active on the allocating thread (see `tag_scope`).
+//! Each block carries its tag in a small header so frees are credited to the
+//! tag that allocated, whichever thread or tag releases them. Counters are
+//! fixed atomics, so the allocation path never allocates or locks.
+//!
+//! Installed as the global allocator only with the `heap-profiling`
+//! feature; otherwise reports come back inactive. `HeapGuard` is the OOM
+//! guard: when tracked live bytes cross a soft limit it dumps the top sites
+//! to the log and a JSON file, then re-arms once usage falls back.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// Tag slots, including slot 0 for untagged allocations.
+pub const MAX_TAGS: usize = 64;
+const UNTAGGED: &str = "untagged";
+/// Header space reserved before each block; holds the tag id in its last byte.
+const MIN_HEADER: usize = 16;
+
+struct TagStats {
+    live_bytes: AtomicU64,
+    live_allocations: AtomicU64,
+    total_bytes: AtomicU64,
+    total_allocations: AtomicU64,
+}
+
+#[allow(clippy::declare_interior_mutable_const)]
+const EMPTY_STATS: TagStats = TagStats {
+    live_bytes: AtomicU64::new(0),
+    live_allocations: AtomicU64::new(0),
+    total_bytes: AtomicU64::new(0),
+    total_allocations: AtomicU64::new(0),
+};
+#[allow(clippy::declare_interior_mutable_const)]
+const EMPTY_NAME: OnceLock<&'static str> = OnceLock::new();
+
+static STATS: [TagStats; MAX_TAGS] = [EMPTY_STATS; MAX_TAGS];
+static NAMES: [OnceLock<&'static str>; MAX_TAGS] = [EMPTY_NAME; MAX_TAGS];
+static NEXT_TAG: AtomicUsize = AtomicUsize::new(1);
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static CURRENT_TAG: Cell<u8> = const { Cell::new(0) };
+}
+
+/// Global allocator that records live allocation per tag.
+pub struct TrackingAllocator;
+
+impl TrackingAllocator {
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for TrackingAllocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn header_len(layout: &Layout) -> usize {
+    layout.align().max(MIN_HEADER)
+}
+
+// SAFETY: every block is the caller's layout offset by a header that keeps
+// the caller's alignment; dealloc reverses exactly the same offset.
+unsafe impl GlobalAlloc for TrackingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let header = header_len(&layout);
+        let Some(outer) = layout
+            .size()
+            .checked_add(header)
+            .and_then(|size| Layout::from_size_align(size, layout.align().max(MIN_HEADER)).ok())
+        else {
+            return std::ptr::null_mut();
+        };
+        let base = System.alloc(outer);
+        if base.is_null() {
+            return base;
+        }
+        // try_with: the thread-local may already be torn down at thread exit
+        let tag = CURRENT_TAG.try_with(Cell::get).unwrap_or(0);
+        let ptr = base.add(header);
+        *ptr.sub(1) = tag;
+        let stats = &STATS[tag as usize];
+        stats.live_bytes.fetch_add(layout.size() as u64, Ordering::Relaxed);
+        stats.live_allocations.fetch_add(1, Ordering::Relaxed);
+        stats.total_bytes.fetch_add(layout.size() as u64, Ordering::Relaxed);
+        stats.total_allocations.fetch_add(1, Ordering::Relaxed);
+        ACTIVE.store(true, Ordering::Relaxed);
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        let header = header_len(&layout);
+        let tag = *ptr.sub(1) as usize;
+        let stats = &STATS[tag.min(MAX_TAGS - 1)];
+        stats.live_bytes.fetch_sub(layout.size() as u64, Ordering::Relaxed);
+        stats.live_allocations.fetch_sub(1, Ordering::Relaxed);
+        let outer = Layout::from_size_align_unchecked(layout.size() + header, layout.align().max(MIN_HEADER));
+        System.dealloc(ptr.sub(header), outer);
+    }
+}
+
+/// Id for `name`, registering it on first use. Falls back to the untagged
+/// slot once all slots are taken.
+fn tag_id(name: &'static str) -> u8 {
+    let registered = NEXT_TAG.load(Ordering::Acquire).min(MAX_TAGS);
+    for (id, slot) in NAMES.iter().enumerate().take(registered).skip(1) {
+        if slot.get() == Some(&name) {
+            return id as u8;
+        }
+    }
+    let id = NEXT_TAG.fetch_add(1, Ordering::AcqRel);
+    if id >= MAX_TAGS {
+        return 0;
+    }
+    let _ = NAMES[id].set(name);
+    id as u8
+}
+
+/// Attributes allocations on this thread to a tag until dropped.
+pub struct TagGuard {
+    previous: u8,
+}
+
+impl Drop for TagGuard {
+    fn drop(&mut self) {
+        CURRENT_TAG.with(|t| t.set(self.previous));
+    }
+}
+
+/// Tag allocations made on the current thread until the guard drops.
+///
+/// Thread-local: do not hold the guard across an `.await`.
+pub fn tag_scope(name: &'static str) -> TagGuard {
+    let id = tag_id(name);
+    TagGuard { previous: CURRENT_TAG.with(|t| t.replace(id)) }
+}
+
+/// Whether the tracking allocator is installed and has seen allocations.
+pub fn is_active() -> bool {
+    ACTIVE.load(Ordering::Relaxed)
+}
+
+/// Allocation totals for one tag.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HeapSite {
+    pub tag: String,
+    pub live_bytes: u64,
+    pub live_allocations: u64,
+    pub total_bytes: u64,
+    pub total_allocations: u64,
+}
+
+/// Top allocation sites by live bytes.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HeapReport {
+    /// False when the tracking allocator is not installed.
+    pub active: bool,
+    pub live_bytes: u64,
+    pub sites: Vec<HeapSite>,
+}
+
+/// Snapshot the `top` tags with the most live bytes.
+pub fn heap_report(top: usize) -> HeapReport {
+    let registered = NEXT_TAG.load(Ordering::Acquire).min(MAX_TAGS);
+    let mut sites: Vec<HeapSite> = (0..registered)
+        .map(|id| {
+            let stats = &STATS[id];
+            HeapSite {
+                tag: NAMES[id].get().copied().unwrap_or(UNTAGGED).to_string(),
+                live_bytes: stats.live_bytes.load(Ordering::Relaxed),
+                live_allocations: stats.live_allocations.load(Ordering::Relaxed),
+                total_bytes: stats.total_bytes.load(Ordering::Relaxed),
+                total_allocations: stats.total_allocations.load(Ordering::Relaxed),
+            }
+        })
+        .filter(|s| s.total_allocations > 0)
+        .collect();
+    let live_bytes = sites.iter().map(|s| s.live_bytes).sum();
+    sites.sort_by_key(|s| std::cmp::Reverse(s.live_bytes));
+    sites.truncate(top);
+    HeapReport { active: is_active(), live_bytes, sites }
+}
+
+/// OOM guard settings.
+#[derive(Debug, Clone)]
+pub struct HeapGuardConfig {
+    /// Live tracked bytes that trigger a dump; `None` disables the guard.
+    pub soft_limit_bytes: Option<u64>,
+    pub check_interval: Duration,
+    /// Directory for `heap-<unix seconds>.json` dumps.
+    pub report_dir: Option<PathBuf>,
+    pub top_sites: usize,
+}
+
+impl Default for HeapGuardConfig {
+    fn default() -> Self {
+        Self {
+            soft_limit_bytes: None,
+            check_interval: Duration::from_secs(5),
+            report_dir: None,
+            top_sites: 20,
+        }
+    }
+}
+
+/// Dumps a heap report when tracked usage crosses the soft limit.
+pub struct HeapGuard {
+    config: HeapGuardConfig,
+    tripped: AtomicBool,
+}
+
+impl HeapGuard {
+    pub fn new(config: HeapGuardConfig) -> Self {
+        Self { config, tripped: AtomicBool::new(false) }
+    }
+
+    /// Compare `report` against the limit; returns true when the guard
+    /// activates. Re-arms below 90% of the limit.
+    pub fn check(&self, report: &HeapReport) -> bool {
+        let Some(limit) = self.config.soft_limit_bytes else {
+            return false;
+        };
+        if report.live_bytes < limit {
+            if report.live_bytes < limit / 10 * 9 {
+                self.tripped.store(false, Ordering::Relaxed);
+            }
+            return false;
+        }
+        if self.tripped.swap(true, Ordering::Relaxed) {
+            return false;
+        }
+        tracing::warn!(
+            live_bytes = report.live_bytes,
+            limit_bytes = limit,
+            top_sites = ?report.sites.iter().map(|s| (&s.tag, s.live_bytes)).collect::<Vec<_>>(),
+            "Heap soft limit exceeded"
+        );
+        if let Some(dir) = &self.config.report_dir {
+            if let Err(e) = write_report(dir, report) {
+                tracing::warn!(error = %e, "Failed to write heap report");
+            }
+        }
+        true
+    }
+
+    /// Check on `check_interval` until the task is aborted.
+    pub async fn run(self: std::sync::Arc<Self>) {
+        if self.config.soft_limit_bytes.is_none() || !is_active() {
+            return;
+        }
+        let mut interval = tokio::time::interval(self.config.check_interval);
+        loop {
+            interval.tick().await;
+            self.check(&heap_report(self.config.top_sites));
+        }
+    }
+}
+
+fn write_report(dir: &std::path::Path, report: &HeapReport) -> std::io::Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let path = dir.join(format!("heap-{secs}.json"));
+    std::fs::write(&path, serde_json::to_vec_pretty(report)?)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allocations_attributed_to_tag_and_freed() {
+        let alloc = TrackingAllocator::new();
+        let layout = Layout::from_size_align(1000, 64).unwrap();
+        let ptr = {
+            let _tag = tag_scope("heap_test_site");
+            unsafe { alloc.alloc(layout) }
+        };
+        assert!(!ptr.is_null());
+        assert_eq!(ptr as usize % 64, 0);
+
+        let site = |r: &HeapReport| r.sites.iter().find(|s| s.tag == "heap_test_site").cloned();
+        let before = site(&heap_report(MAX_TAGS)).unwrap();
+        assert_eq!((before.live_bytes, before.live_allocations), (1000, 1));
+
+        // Freed under another tag, still credited to the allocating one
+        let _other = tag_scope("heap_test_other");
+        unsafe { alloc.dealloc(ptr, layout) };
+        let after = site(&heap_report(MAX_TAGS)).unwrap();
+        assert_eq!((after.live_bytes, after.total_bytes), (0, 1000));
+    }
+
+    #[test]
+    fn test_tag_ids_are_stable() {
+        assert_eq!(tag_id("heap_test_stable"), tag_id("heap_test_stable"));
+        assert_ne!(tag_id("heap_test_stable"), 0);
+    }
+
+    #[test]
+    fn test_guard_trips_once_and_rearms() {
+        let dir = tempfile::tempdir().unwrap();
+        let guard = HeapGuard::new(HeapGuardConfig {
+            soft_limit_bytes: Some(1000),
+            report_dir: Some(dir.path().to_path_buf()),
+            ..Default::default()
+        });
+        let report = |live_bytes| HeapReport { active: true, live_bytes, sites: Vec::new() };
+
+        assert!(!guard.check(&report(500)));
+        assert!(guard.check(&report(1200)));
+        assert!(!guard.check(&report(1500)));
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+
+        assert!(!guard.check(&report(950)));
+        assert!(!guard.check(&report(1100)), "still armed off above 90%");
+        assert!(!guard.check(&report(100)));
+        assert!(guard.check(&report(1100)));
+    }
+}
@@ -5,6 +5,8 @@
 
 use metrics::{counter, describe_counter, describe_gauge, describe_histogram, gauge, histogram};
 
+use super::streaming::TokenLatency;
+
 /// Initialize metric descriptions.
 ///
 /// Call once at startup to register metric metadata.
@@ -20,6 +22,8 @@ pub fn init_metrics() {
         "core_tokenization_latency_ms",
         "Tokenization latency in milliseconds"
     );
+    describe_histogram!("core_ttft_ms", "Time to first streamed token in milliseconds");
+    describe_histogram!("core_inter_token_ms", "Gap between streamed tokens in milliseconds");
 
     // Token counters
     describe_counter!("core_tokens_input_total", "Total input tokens processed");
@@ -27,8 +31,29 @@ pub fn init_metrics() {
 
     // Resource gauges
     describe_gauge!("core_memory_pool_used_bytes", "Memory pool bytes in use");
+    describe_gauge!("core_model_pool_bytes", "Model pool memory by kind (weights, kv)");
+    describe_counter!("core_pool_kv_evicted_sequences_total", "KV sequences evicted to keep the model pool within budget");
+    describe_counter!("core_pool_kv_reclaimed_bytes_total", "KV bytes reclaimed to keep the model pool within budget");
     describe_gauge!("core_queue_depth", "Number of pending requests");
+    describe_gauge!("core_queue_depth_by_priority", "Pending requests per priority level");
+    describe_gauge!("core_queue_oldest_age_ms", "Age of the oldest pending request in milliseconds");
+    describe_gauge!("core_queue_model_pending", "Pending requests per model");
+    describe_gauge!("core_queue_model_oldest_age_ms", "Age of the oldest pending request per model in milliseconds");
+    describe_gauge!("core_queue_model_stranded", "1 when a model's queued requests wait on a model switched away from");
+    describe_histogram!("core_queue_wait_ms", "Time from enqueue to dequeue in milliseconds");
+    describe_counter!("core_queue_rejections_total", "Requests refused admission or dropped from the queue");
+    describe_gauge!("core_scheduler_busy_workers", "Worker slots running a request");
+    describe_gauge!("core_scheduler_in_flight", "Requests currently executing");
+    describe_gauge!("core_scheduler_active_batches", "Models with requests executing");
+    describe_gauge!("core_scheduler_avg_batch_size", "Mean requests per active batch");
+    describe_histogram!("core_gpu_turn_ms", "Length of a model's GPU turn in milliseconds");
+    describe_gauge!("core_gpu_occupancy", "Fraction of uptime a model held the shared GPU");
+    describe_gauge!("core_gpu_temperature_celsius", "GPU temperature");
+    describe_gauge!("core_gpu_power_watts", "GPU power draw");
+    describe_gauge!("core_thermal_throttled", "1 while GPU traffic is throttled for heat or power");
     describe_gauge!("core_active_sessions", "Number of active sessions");
+    describe_counter!("core_retention_removed_total", "Records and files removed by retention policies");
+    describe_counter!("core_retention_reclaimed_bytes_total", "Bytes reclaimed by retention policies");
 
     // Arena metrics (Tier 3)
     describe_gauge!("core_arena_used_bytes", "Arena allocator bytes in use");
@@ -38,6 +63,10 @@ pub fn init_metrics() {
     describe_counter!("core_speculative_drafts_total", "Total draft generation cycles");
     describe_counter!("core_speculative_accepted_tokens", "Draft tokens accepted");
     describe_counter!("core_speculative_rejected_tokens", "Draft tokens rejected");
+
+    // Samplers
+    describe_counter!("core_sampler_requests_total", "Generations per sampler");
+    describe_counter!("core_sampler_tokens_total", "Tokens sampled per sampler");
 }
 
 /// Record a successful inference request.
@@ -48,6 +77,16 @@ pub fn record_request_success(model: &str, latency_ms: u64, tokens_out: u64) {
     histogram!("core_inference_latency_ms", "model" => model.to_string()).record(latency_ms as f64);
 }
 
+/// Record a streamed token's TTFT or inter-token gap.
+pub fn record_token_latency(model: &str, latency: TokenLatency) {
+    match latency {
+        TokenLatency::FirstToken(ms) => histogram!("core_ttft_ms", "model" => model.to_string()).record(ms),
+        TokenLatency::InterToken(ms) => {
+            histogram!("core_inter_token_ms", "model" => model.to_string()).record(ms)
+        }
+    }
+}
+
 /// Record a failed inference request.
 pub fn record_request_failure(model: &str, error_type: &str) {
     counter!("core_requests_total", "model" => model.to_string()).increment(1);
@@ -64,11 +103,117 @@ pub fn record_memory_pool(used_bytes: usize) {
     gauge!("core_memory_pool_used_bytes").set(used_bytes as f64);
 }
 
+/// Record model pool memory: weights and attached KV caches.
+pub fn record_pool_memory(weight_bytes: usize, kv_bytes: usize) {
+    gauge!("core_model_pool_bytes", "kind" => "weights").set(weight_bytes as f64);
+    gauge!("core_model_pool_bytes", "kind" => "kv").set(kv_bytes as f64);
+}
+
+/// Record KV memory reclaimed to keep the model pool within budget.
+pub fn record_pool_kv_reclaim(sequences: usize, bytes: usize) {
+    counter!("core_pool_kv_evicted_sequences_total").increment(sequences as u64);
+    counter!("core_pool_kv_reclaimed_bytes_total").increment(bytes as u64);
+}
+
 /// Record queue depth.
 pub fn record_queue_depth(depth: usize) {
     gauge!("core_queue_depth").set(depth as f64);
 }
 
+/// Record queue depth for a single priority level.
+pub fn record_priority_queue_depth(priority: &str, depth: usize) {
+    gauge!("core_queue_depth_by_priority", "priority" => priority.to_string()).set(depth as f64);
+}
+
+/// Record worker occupancy and batch composition.
+pub fn record_scheduler_occupancy(busy_workers: usize, in_flight: usize, active_batches: usize, avg_batch_size: f64) {
+    gauge!("core_scheduler_busy_workers").set(busy_workers as f64);
+    gauge!("core_scheduler_in_flight").set(in_flight as f64);
+    gauge!("core_scheduler_active_batches").set(active_batches as f64);
+    gauge!("core_scheduler_avg_batch_size").set(avg_batch_size);
+}
+
+/// Record a client's use of a deprecated protocol feature.
+pub fn record_deprecated_use(target: &str, client: &str) {
+    counter!("core_protocol_deprecated_use_total", "target" => target.to_string(), "client" => client.to_string())
+        .increment(1);
+}
+
+/// Record a lock recovered after a holder panicked.
+pub fn record_lock_poisoned(lock: &'static str) {
+    counter!("core_lock_poison_recoveries_total", "lock" => lock).increment(1);
+}
+
+/// Record `acquired` taken while holding `held`, against the established order.
+pub fn record_lock_order_violation(held: &'static str, acquired: &'static str) {
+    counter!("core_lock_order_violations_total", "held" => held, "acquired" => acquired).increment(1);
+}
+
+/// Record a lock held longer than the tracker allows.
+pub fn record_lock_long_hold(lock: &'static str, held: std::time::Duration) {
+    counter!("core_lock_long_holds_total", "lock" => lock).increment(1);
+    histogram!("core_lock_long_hold_ms", "lock" => lock).record(held.as_secs_f64() * 1000.0);
+}
+
+/// Record one generation's use of a sampler.
+pub fn record_sampler_usage(sampler: &str, tokens: u64) {
+    counter!("core_sampler_requests_total", "sampler" => sampler.to_string()).increment(1);
+    counter!("core_sampler_tokens_total", "sampler" => sampler.to_string()).increment(tokens);
+}
+
+/// Record the end of a model's turn on the shared GPU.
+pub fn record_gpu_turn(model: &str, turn_ms: f64, occupancy: f64) {
+    histogram!("core_gpu_turn_ms", "model" => model.to_string()).record(turn_ms);
+    gauge!("core_gpu_occupancy", "model" => model.to_string()).set(occupancy);
+}
+
+/// Record a GPU's temperature and power draw.
+pub fn record_gpu_reading(gpu: u32, temperature_c: f64, power_draw_w: Option<f64>) {
+    gauge!("core_gpu_temperature_celsius", "gpu" => gpu.to_string()).set(temperature_c);
+    if let Some(watts) = power_draw_w {
+        gauge!("core_gpu_power_watts", "gpu" => gpu.to_string()).set(watts);
+    }
+}
+
+/// Record whether GPU traffic is thermally throttled.
+pub fn record_thermal_throttled(throttled: bool) {
+    gauge!("core_thermal_throttled").set(if throttled { 1.0 } else { 0.0 });
+}
+
+/// Record the age of the oldest pending request (0 when the queue is empty).
+pub fn record_queue_oldest_age(age_ms: f64) {
+    gauge!("core_queue_oldest_age_ms").set(age_ms);
+}
+
+/// Record pending requests for one model and the age of its oldest;
+/// `stranded` when the pool has switched away from the model.
+pub fn record_model_queue_age(model: &str, pending: usize, oldest_age_ms: f64, stranded: bool) {
+    gauge!("core_queue_model_pending", "model" => model.to_string()).set(pending as f64);
+    gauge!("core_queue_model_oldest_age_ms", "model" => model.to_string()).set(oldest_age_ms);
+    gauge!("core_queue_model_stranded", "model" => model.to_string()).set(if stranded { 1.0 } else { 0.0 });
+}
+
+/// Record how long a request waited in the queue before dispatch.
+pub fn record_queue_wait(priority: &str, wait_ms: f64) {
+    histogram!("core_queue_wait_ms", "priority" => priority.to_string()).record(wait_ms);
+}
+
+/// Record a request refused at admission or dropped before dispatch.
+pub fn record_queue_rejection(priority: &str, reason: &str) {
+    counter!(
+        "core_queue_rejections_total",
+        "priority" => priority.to_string(),
+        "reason" => reason.to_string()
+    )
+    .increment(1);
+}
+
+/// Record one retention pass over a dataset.
+pub fn record_retention(dataset: &str, removed: u64, reclaimed_bytes: u64) {
+    counter!("core_retention_removed_total", "dataset" => dataset.to_string()).increment(removed);
+    counter!("core_retention_reclaimed_bytes_total", "dataset" => dataset.to_string()).increment(reclaimed_bytes);
+}
+
 /// Record speculative decoding cycle stats.
 pub fn record_speculative_cycle(accepted: usize, rejected: usize) {
     counter!("core_speculative_drafts_total").increment(1);
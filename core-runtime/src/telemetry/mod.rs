@@ -4,22 +4,46 @@
 //! All output is file-based or via existing IPC - no network dependencies.
 
 pub mod buckets;
+mod events;
+pub mod heap;
 mod logging;
 mod metrics;
+pub mod profiler;
 pub mod prometheus;
+pub mod rates;
+pub mod resource;
 pub mod security_log;
+pub mod slo;
+pub mod stability;
 pub mod span_export;
 mod spans;
 mod store;
+pub mod streaming;
+pub mod usage;
 
 pub use buckets::{BucketedHistogram, BucketedHistogramSnapshot};
+pub use events::{emit_event, subscribe_events, unsubscribe_events, RuntimeEvent, RuntimeEventKind};
+pub use heap::{
+    heap_report, tag_scope, HeapGuard, HeapGuardConfig, HeapReport, HeapSite, TagGuard, TrackingAllocator,
+};
 pub use logging::{init_logging, LogConfig, LogError, LogFormat};
 pub use metrics::{
-    init_metrics, record_memory_pool, record_queue_depth, record_request_failure,
-    record_request_success, record_speculative_cycle,
+    init_metrics, record_deprecated_use, record_gpu_reading, record_gpu_turn, record_lock_long_hold, record_lock_order_violation,
+    record_lock_poisoned, record_memory_pool, record_pool_kv_reclaim, record_pool_memory, record_priority_queue_depth, record_queue_depth,
+    record_model_queue_age,
+    record_queue_oldest_age, record_queue_rejection, record_scheduler_occupancy, record_queue_wait, record_request_failure, record_request_success, record_retention, record_sampler_usage, record_speculative_cycle, record_thermal_throttled, record_token_latency,
 };
+pub use profiler::{ProfileError, Profiler, ProfilerConfig};
+pub use rates::{RateTracker, FALLBACK_COUNTER};
+pub use resource::{init_resource, resource, ResourceAttributes};
 pub use prometheus::{encode_bucketed_histogram, encode_prometheus};
 pub use security_log::{log_security_event, SecurityEvent, SecuritySeverity};
+pub use slo::{
+    SloConfig, SloIndicator, SloMonitor, SloObjective, SloStatus, SloTracker, REQUEST_LATENCY_HISTOGRAM,
+};
+pub use stability::{ProcessSample, Resource, StabilityConfig, StabilityMonitor};
 pub use span_export::{ExportableSpan, SpanAttributeValue, SpanCollector, SpanStatus};
 pub use spans::{RequestSpan, SpanExt};
 pub use store::{HistogramSummary, MetricsSnapshot, MetricsStore};
+pub use streaming::{StreamTimer, StreamTimings, TokenLatency};
+pub use usage::{tenant_label, DpConfig, PrivacyMetadata, TenantUsage, UsageExport, UsageLedger};
@@ -0,0 +1,155 @@
+//! Opt-in CPU self-profiler.
+//!
+//! Samples the runtime's own stacks with pprof-rs (SIGPROF) for a bounded
+//! window and returns a gzipped pprof protobuf, readable by `go tool pprof`
+//! and most flamegraph tooling. Only one capture runs at a time. Sampling
+//! needs `setitimer`; under the seccomp sandbox the capture fails cleanly
+//! unless that syscall is allowed.
+//!
+//! Requires the `profiling` feature and `ProfilerConfig::enabled`.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use thiserror::Error;
+
+/// Default sampling frequency; off the 100 Hz grid to avoid lockstep with timers.
+pub const DEFAULT_FREQUENCY_HZ: i32 = 99;
+/// Longest capture accepted.
+pub const DEFAULT_MAX_SECONDS: u32 = 120;
+
+#[derive(Error, Debug)]
+pub enum ProfileError {
+    #[error("Profiling is disabled (set CORE_PROFILING=1)")]
+    Disabled,
+
+    #[error("Profiling support not compiled in. Enable 'profiling' feature.")]
+    NotCompiled,
+
+    #[error("Profile duration must be 1..={max} seconds, got {got}")]
+    InvalidDuration { got: u32, max: u32 },
+
+    #[error("A profile is already being captured")]
+    Busy,
+
+    #[error("Profiling failed: {0}")]
+    Failed(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct ProfilerConfig {
+    pub enabled: bool,
+    pub frequency_hz: i32,
+    pub max_seconds: u32,
+}
+
+impl Default for ProfilerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            frequency_hz: DEFAULT_FREQUENCY_HZ,
+            max_seconds: DEFAULT_MAX_SECONDS,
+        }
+    }
+}
+
+/// Serializes captures and enforces the configured limits.
+pub struct Profiler {
+    config: ProfilerConfig,
+    busy: AtomicBool,
+}
+
+impl Profiler {
+    pub fn new(config: ProfilerConfig) -> Self {
+        Self { config, busy: AtomicBool::new(false) }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Sample for `seconds` and return a gzipped pprof profile.
+    pub async fn capture(&self, seconds: u32) -> Result<Vec<u8>, ProfileError> {
+        if !self.config.enabled {
+            return Err(ProfileError::Disabled);
+        }
+        if seconds == 0 || seconds > self.config.max_seconds {
+            return Err(ProfileError::InvalidDuration { got: seconds, max: self.config.max_seconds });
+        }
+        if self.busy.swap(true, Ordering::AcqRel) {
+            return Err(ProfileError::Busy);
+        }
+        let _release = BusyGuard(&self.busy);
+
+        let frequency = self.config.frequency_hz;
+        let duration = Duration::from_secs(u64::from(seconds));
+        // The sampler blocks its thread; keep it off the async workers
+        tokio::task::spawn_blocking(move || sample(frequency, duration))
+            .await
+            .map_err(|e| ProfileError::Failed(e.to_string()))?
+    }
+}
+
+struct BusyGuard<'a>(&'a AtomicBool);
+
+impl Drop for BusyGuard<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+#[cfg(feature = "profiling")]
+fn sample(frequency: i32, duration: Duration) -> Result<Vec<u8>, ProfileError> {
+    use std::io::Write;
+
+    use pprof::protos::Message;
+
+    let failed = |e: &dyn std::fmt::Display| ProfileError::Failed(e.to_string());
+    let guard = pprof::ProfilerGuardBuilder::default()
+        .frequency(frequency)
+        .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+        .build()
+        .map_err(|e| failed(&e))?;
+    std::thread::sleep(duration);
+    let profile = guard
+        .report()
+        .build()
+        .and_then(|report| report.pprof())
+        .map_err(|e| failed(&e))?;
+
+    let mut encoded = Vec::new();
+    profile.encode(&mut encoded).map_err(|e| failed(&e))?;
+    let mut gz = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    gz.write_all(&encoded).map_err(|e| failed(&e))?;
+    gz.finish().map_err(|e| failed(&e))
+}
+
+#[cfg(not(feature = "profiling"))]
+fn sample(_frequency: i32, _duration: Duration) -> Result<Vec<u8>, ProfileError> {
+    Err(ProfileError::NotCompiled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_disabled_by_default() {
+        let profiler = Profiler::new(ProfilerConfig::default());
+        assert!(matches!(profiler.capture(1).await, Err(ProfileError::Disabled)));
+    }
+
+    #[tokio::test]
+    async fn test_duration_bounds() {
+        let profiler = Profiler::new(ProfilerConfig { enabled: true, max_seconds: 5, ..Default::default() });
+        assert!(matches!(profiler.capture(0).await, Err(ProfileError::InvalidDuration { .. })));
+        assert!(matches!(profiler.capture(6).await, Err(ProfileError::InvalidDuration { got: 6, max: 5 })));
+    }
+
+    #[tokio::test]
+    async fn test_busy_flag_released_after_capture() {
+        let profiler = Profiler::new(ProfilerConfig { enabled: true, ..Default::default() });
+        let _ = profiler.capture(1).await;
+        assert!(!profiler.busy.load(Ordering::Acquire));
+    }
+}
@@ -25,16 +25,57 @@ pub const METRIC_DEFINITIONS: &[MetricHelp] = &[
     MetricHelp { name: "core_requests_failed", help: "Failed inference requests", metric_type: "counter" },
     MetricHelp { name: "core_tokens_generated", help: "Total tokens generated", metric_type: "counter" },
     MetricHelp { name: "core_queue_depth", help: "Current request queue depth", metric_type: "gauge" },
+    MetricHelp { name: "core_queue_oldest_age_ms", help: "Age of the oldest pending request in milliseconds", metric_type: "gauge" },
+    MetricHelp { name: "core_queue_wait_ms", help: "Time from enqueue to dequeue in milliseconds", metric_type: "histogram" },
+    MetricHelp { name: "core_scheduler_busy_workers", help: "Worker slots running a request", metric_type: "gauge" },
+    MetricHelp { name: "core_scheduler_in_flight", help: "Requests currently executing", metric_type: "gauge" },
+    MetricHelp { name: "core_scheduler_active_batches", help: "Models with requests executing", metric_type: "gauge" },
+    MetricHelp { name: "core_scheduler_avg_batch_size", help: "Mean requests per active batch", metric_type: "gauge" },
+    MetricHelp { name: "core_gpu_turn_ms", help: "Length of a model's GPU turn in milliseconds", metric_type: "histogram" },
+    MetricHelp { name: "core_gpu_occupancy", help: "Fraction of uptime a model held the shared GPU", metric_type: "gauge" },
+    MetricHelp { name: "core_gpu_temperature_celsius", help: "GPU temperature", metric_type: "gauge" },
+    MetricHelp { name: "core_gpu_power_watts", help: "GPU power draw", metric_type: "gauge" },
+    MetricHelp { name: "core_thermal_throttled", help: "1 while GPU traffic is throttled for heat or power", metric_type: "gauge" },
+    MetricHelp { name: "core_sampler_requests_total", help: "Generations per sampler", metric_type: "counter" },
+    MetricHelp { name: "core_sampler_tokens_total", help: "Tokens sampled per sampler", metric_type: "counter" },
     MetricHelp { name: "core_memory_used_bytes", help: "Memory currently in use", metric_type: "gauge" },
+    MetricHelp { name: "core_process_open_fds", help: "Open file descriptors", metric_type: "gauge" },
+    MetricHelp { name: "core_process_threads", help: "OS threads in the process", metric_type: "gauge" },
+    MetricHelp { name: "core_process_resident_memory_bytes", help: "Resident set size", metric_type: "gauge" },
+    MetricHelp { name: "core_runtime_alive_tasks", help: "Live tokio tasks", metric_type: "gauge" },
     MetricHelp { name: "core_models_loaded", help: "Number of loaded models", metric_type: "gauge" },
     MetricHelp { name: "core_latency_ms", help: "Request latency in milliseconds", metric_type: "histogram" },
     MetricHelp { name: "core_throughput_tps", help: "Token throughput per second", metric_type: "histogram" },
+    MetricHelp { name: "core_request_latency_ms", help: "End-to-end request latency in milliseconds", metric_type: "histogram" },
+    MetricHelp { name: "core_ttft_ms", help: "Time to first streamed token in milliseconds", metric_type: "histogram" },
+    MetricHelp { name: "core_inter_token_ms", help: "Gap between streamed tokens in milliseconds", metric_type: "histogram" },
+    MetricHelp { name: "core_requests_rate_1m", help: "Requests per second, 1m EWMA", metric_type: "gauge" },
+    MetricHelp { name: "core_requests_rate_5m", help: "Requests per second, 5m EWMA", metric_type: "gauge" },
+    MetricHelp { name: "core_tokens_rate_1m", help: "Tokens per second, 1m EWMA", metric_type: "gauge" },
+    MetricHelp { name: "core_tokens_rate_5m", help: "Tokens per second, 5m EWMA", metric_type: "gauge" },
+    MetricHelp { name: "core_error_ratio_1m", help: "Failed/total requests, 1m EWMA", metric_type: "gauge" },
+    MetricHelp { name: "core_error_ratio_5m", help: "Failed/total requests, 5m EWMA", metric_type: "gauge" },
+    MetricHelp { name: "core_fallback_requests", help: "Requests served by a fallback model", metric_type: "counter" },
+    MetricHelp { name: "core_fallback_ratio_1m", help: "Fallback/total requests, 1m EWMA", metric_type: "gauge" },
+    MetricHelp { name: "core_fallback_ratio_5m", help: "Fallback/total requests, 5m EWMA", metric_type: "gauge" },
+    MetricHelp { name: "target_info", help: "Resource attributes of this instance", metric_type: "gauge" },
 ];
 
 /// Encode metrics snapshot to Prometheus text format.
 pub fn encode_prometheus(snapshot: &MetricsSnapshot) -> String {
     let mut output = String::with_capacity(4096);
 
+    // Resource attributes, as the OpenTelemetry `target_info` info metric
+    if let Some(resource) = &snapshot.resource {
+        write_metric_header(&mut output, "target_info");
+        let labels: Vec<String> = resource
+            .pairs()
+            .iter()
+            .map(|(key, value)| format!("{}=\"{}\"", key.replace('.', "_"), escape_label(value)))
+            .collect();
+        writeln!(output, "target_info{{{}}} 1", labels.join(",")).unwrap();
+    }
+
     // Counters
     for (name, value) in &snapshot.counters {
         write_metric_header(&mut output, name);
@@ -47,6 +88,12 @@ pub fn encode_prometheus(snapshot: &MetricsSnapshot) -> String {
         writeln!(output, "{name} {value}").unwrap();
     }
 
+    // Derived rates (exported as gauges)
+    for (name, value) in &snapshot.rates {
+        write_metric_header(&mut output, name);
+        writeln!(output, "{name} {value}").unwrap();
+    }
+
     // Summary histograms (basic stats)
     for (name, summary) in &snapshot.histograms {
         write_metric_header(&mut output, name);
@@ -54,6 +101,11 @@ pub fn encode_prometheus(snapshot: &MetricsSnapshot) -> String {
         writeln!(output, "{name}_sum {}", summary.sum).unwrap();
     }
 
+    // Bucketed histograms
+    for (name, snap) in &snapshot.bucketed_histograms {
+        output.push_str(&encode_bucketed_histogram(name, snap));
+    }
+
     output
 }
 
@@ -88,6 +140,11 @@ fn write_metric_header(output: &mut String, name: &str) {
     }
 }
 
+/// Escape a label value per the exposition format.
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -100,6 +157,8 @@ mod tests {
             gauges: HashMap::new(),
             histograms: HashMap::new(),
             bucketed_histograms: HashMap::new(),
+            rates: HashMap::new(),
+            resource: None,
         };
         snapshot.counters.insert("core_requests_total".to_string(), 42);
 
@@ -109,6 +168,23 @@ mod tests {
         assert!(output.contains("core_requests_total 42"));
     }
 
+    #[test]
+    fn test_encode_rates_as_gauges() {
+        let mut snapshot = MetricsSnapshot {
+            counters: HashMap::new(),
+            gauges: HashMap::new(),
+            histograms: HashMap::new(),
+            bucketed_histograms: HashMap::new(),
+            rates: HashMap::new(),
+            resource: None,
+        };
+        snapshot.rates.insert("core_tokens_rate_1m".to_string(), 12.5);
+
+        let output = encode_prometheus(&snapshot);
+        assert!(output.contains("# TYPE core_tokens_rate_1m gauge"));
+        assert!(output.contains("core_tokens_rate_1m 12.5"));
+    }
+
     #[test]
     fn test_encode_bucketed_histogram() {
         let snap = BucketedHistogramSnapshot {
@@ -123,4 +199,29 @@ mod tests {
         assert!(output.contains("test_latency_bucket{le=\"5\"} 5"));
         assert!(output.contains("test_latency_bucket{le=\"+Inf\"} 7"));
     }
+
+    #[test]
+    fn test_encode_resource_as_target_info() {
+        let snapshot = MetricsSnapshot {
+            counters: HashMap::new(),
+            gauges: HashMap::new(),
+            histograms: HashMap::new(),
+            bucketed_histograms: HashMap::new(),
+            rates: HashMap::new(),
+            resource: Some(crate::telemetry::ResourceAttributes {
+                service_name: "gg-core".to_string(),
+                service_version: "1.0.0".to_string(),
+                instance_id: "abc".to_string(),
+                node: "node \"a\"".to_string(),
+                profile: Some("production".to_string()),
+            }),
+        };
+
+        let output = encode_prometheus(&snapshot);
+        assert!(output.contains("# TYPE target_info gauge"));
+        assert!(output.contains(
+            "target_info{service_name=\"gg-core\",service_version=\"1.0.0\",service_instance_id=\"abc\",\
+             host_name=\"node \\\"a\\\"\",deployment_environment=\"production\"} 1"
+        ));
+    }
 }
@@ -0,0 +1,157 @@
+//! Server-side derived rates over exponentially weighted windows.
+//!
+//! Counters are lifetime totals; dividing by uptime gives averages that
+//! barely move after the first hour. Each update folds the counter delta
+//! since the previous update into 1- and 5-minute EWMAs (like load
+//! averages), weighting by elapsed time so irregular update intervals
+//! stay correct.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Counter holding total requests.
+pub const REQUESTS_COUNTER: &str = "core_requests_total";
+/// Counter holding failed requests.
+pub const FAILED_COUNTER: &str = "core_requests_failed";
+/// Counter holding generated tokens.
+pub const TOKENS_COUNTER: &str = "core_tokens_output_total";
+/// Counter holding requests served by a fallback model.
+pub const FALLBACK_COUNTER: &str = "core_fallback_requests";
+
+/// Updates closer together than this are skipped.
+pub const MIN_UPDATE_INTERVAL: Duration = Duration::from_secs(1);
+
+const WINDOWS: [(&str, f64); 2] = [("1m", 60.0), ("5m", 300.0)];
+
+/// Per-second rate smoothed over one window.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Ewma {
+    rate: f64,
+    primed: bool,
+}
+
+impl Ewma {
+    pub(crate) fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    pub(crate) fn update(&mut self, instant_rate: f64, elapsed_secs: f64, window_secs: f64) {
+        if !self.primed {
+            self.rate = instant_rate;
+            self.primed = true;
+            return;
+        }
+        let alpha = 1.0 - (-elapsed_secs / window_secs).exp();
+        self.rate += alpha * (instant_rate - self.rate);
+    }
+}
+
+/// Derives request/token rates and error ratios from counter totals.
+#[derive(Debug, Default)]
+pub struct RateTracker {
+    last: Option<(Instant, HashMap<String, u64>)>,
+    ewmas: HashMap<(&'static str, &'static str), Ewma>,
+}
+
+impl RateTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fold the current counter totals in. Returns false if skipped
+    /// because the previous update was too recent.
+    pub fn update(&mut self, counters: &HashMap<String, u64>, now: Instant) -> bool {
+        let Some((last_at, last_counters)) = &self.last else {
+            self.last = Some((now, counters.clone()));
+            return true;
+        };
+        let elapsed = now.saturating_duration_since(*last_at);
+        if elapsed < MIN_UPDATE_INTERVAL {
+            return false;
+        }
+        let secs = elapsed.as_secs_f64();
+        for name in [REQUESTS_COUNTER, FAILED_COUNTER, TOKENS_COUNTER, FALLBACK_COUNTER] {
+            let current = counters.get(name).copied().unwrap_or(0);
+            let previous = last_counters.get(name).copied().unwrap_or(0);
+            let instant_rate = current.saturating_sub(previous) as f64 / secs;
+            for (label, window) in WINDOWS {
+                self.ewmas.entry((name, label)).or_default().update(instant_rate, secs, window);
+            }
+        }
+        self.last = Some((now, counters.clone()));
+        true
+    }
+
+    fn rate(&self, counter: &'static str, window: &'static str) -> f64 {
+        self.ewmas.get(&(counter, window)).map_or(0.0, Ewma::rate)
+    }
+
+    /// Derived metrics keyed by exported name, e.g. `core_requests_rate_1m`.
+    pub fn rates(&self) -> HashMap<String, f64> {
+        let mut out = HashMap::new();
+        for (label, _) in WINDOWS {
+            let requests = self.rate(REQUESTS_COUNTER, label);
+            let failed = self.rate(FAILED_COUNTER, label);
+            out.insert(format!("core_requests_rate_{label}"), requests);
+            out.insert(format!("core_tokens_rate_{label}"), self.rate(TOKENS_COUNTER, label));
+            let ratio = |count: f64| if requests > 0.0 { (count / requests).min(1.0) } else { 0.0 };
+            out.insert(format!("core_error_ratio_{label}"), ratio(failed));
+            out.insert(format!("core_fallback_ratio_{label}"), ratio(self.rate(FALLBACK_COUNTER, label)));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counters(requests: u64, failed: u64, tokens: u64) -> HashMap<String, u64> {
+        HashMap::from([
+            (REQUESTS_COUNTER.to_string(), requests),
+            (FAILED_COUNTER.to_string(), failed),
+            (TOKENS_COUNTER.to_string(), tokens),
+        ])
+    }
+
+    #[test]
+    fn test_rates_follow_current_throughput() {
+        let mut tracker = RateTracker::new();
+        let start = Instant::now();
+        // Large lifetime totals must not leak into the rate
+        tracker.update(&counters(1_000_000, 0, 9_000_000), start);
+        tracker.update(&counters(1_000_010, 1, 9_000_100), start + Duration::from_secs(10));
+
+        let rates = tracker.rates();
+        assert!((rates["core_requests_rate_1m"] - 1.0).abs() < 1e-9);
+        assert!((rates["core_tokens_rate_5m"] - 10.0).abs() < 1e-9);
+        assert!((rates["core_error_ratio_1m"] - 0.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_short_window_decays_faster() {
+        let mut tracker = RateTracker::new();
+        let start = Instant::now();
+        tracker.update(&counters(0, 0, 0), start);
+        tracker.update(&counters(600, 0, 0), start + Duration::from_secs(60));
+        // A minute of silence
+        tracker.update(&counters(600, 0, 0), start + Duration::from_secs(120));
+
+        let rates = tracker.rates();
+        assert!(rates["core_requests_rate_1m"] < rates["core_requests_rate_5m"]);
+        assert!(!tracker.update(&counters(600, 0, 0), start + Duration::from_millis(120_500)));
+    }
+
+    #[test]
+    fn test_fallback_ratio() {
+        let mut tracker = RateTracker::new();
+        let start = Instant::now();
+        let mut totals = counters(0, 0, 0);
+        tracker.update(&totals, start);
+        totals.insert(REQUESTS_COUNTER.to_string(), 20);
+        totals.insert(FALLBACK_COUNTER.to_string(), 5);
+        tracker.update(&totals, start + Duration::from_secs(10));
+
+        assert!((tracker.rates()["core_fallback_ratio_1m"] - 0.25).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,174 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Resource attributes identifying this runtime instance.
+//!
+//! Follows the OpenTelemetry resource semantic conventions (`service.name`,
+//! `service.version`, `service.instance.id`, `host.name`,
+//! `deployment.environment`) so replicas can be told apart fleet-wide. The
+//! instance ID is a UUID persisted under the base path and survives restarts.
+//!
+//! Once [`init_resource`] has run, the attributes are attached to metrics
+//! snapshots (and the Prometheus `target_info` series), collected spans and
+//! audit events.
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+
+/// File under the base path holding the persisted instance ID.
+pub const INSTANCE_ID_FILE: &str = "instance-id";
+
+/// Default `service.name`; override with `CORE_SERVICE_NAME`.
+pub const DEFAULT_SERVICE_NAME: &str = "gg-core";
+
+static RESOURCE: OnceLock<ResourceAttributes> = OnceLock::new();
+
+/// Identity of this runtime instance.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourceAttributes {
+    pub service_name: String,
+    pub service_version: String,
+    pub instance_id: String,
+    /// Host or node name.
+    pub node: String,
+    /// Deployment profile (`VERITAS_ENV`), if set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+}
+
+impl ResourceAttributes {
+    /// Detect attributes from the environment, loading or creating the
+    /// instance ID under `base_path`. If the ID cannot be persisted an
+    /// ephemeral one is used for this process.
+    pub fn detect(base_path: &Path) -> Self {
+        let instance_id = load_or_create_instance_id(base_path).unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "Cannot persist instance ID; using an ephemeral one");
+            uuid::Uuid::new_v4().to_string()
+        });
+        let env = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+        let node = env("CORE_NODE_NAME")
+            .or_else(|| env("HOSTNAME"))
+            .or_else(|| hostname::get().ok().and_then(|h| h.into_string().ok()))
+            .unwrap_or_else(|| "unknown".to_string());
+        Self {
+            service_name: env("CORE_SERVICE_NAME").unwrap_or_else(|| DEFAULT_SERVICE_NAME.to_string()),
+            service_version: env!("CARGO_PKG_VERSION").to_string(),
+            instance_id,
+            node,
+            profile: env("VERITAS_ENV"),
+        }
+    }
+
+    /// Attributes keyed by their OpenTelemetry semantic-convention names.
+    pub fn pairs(&self) -> Vec<(&'static str, &str)> {
+        let mut pairs = vec![
+            ("service.name", self.service_name.as_str()),
+            ("service.version", self.service_version.as_str()),
+            ("service.instance.id", self.instance_id.as_str()),
+            ("host.name", self.node.as_str()),
+        ];
+        if let Some(profile) = &self.profile {
+            pairs.push(("deployment.environment", profile.as_str()));
+        }
+        pairs
+    }
+
+    /// Add the attributes to `metadata` without replacing existing keys.
+    pub fn apply_to(&self, metadata: &mut HashMap<String, String>) {
+        for (key, value) in self.pairs() {
+            metadata.entry(key.to_string()).or_insert_with(|| value.to_string());
+        }
+    }
+}
+
+/// Read the instance ID from `<base_path>/instance-id`, creating it on first
+/// use. A file that does not hold a UUID is an error rather than being
+/// silently replaced, since that would change the instance's identity.
+pub fn load_or_create_instance_id(base_path: &Path) -> std::io::Result<String> {
+    let path = base_path.join(INSTANCE_ID_FILE);
+    let id = uuid::Uuid::new_v4().to_string();
+    match std::fs::OpenOptions::new().write(true).create_new(true).open(&path) {
+        Ok(mut file) => {
+            file.write_all(format!("{id}\n").as_bytes())?;
+            file.sync_all()?;
+            return Ok(id);
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {}
+        Err(e) => return Err(e),
+    }
+
+    let existing = std::fs::read_to_string(&path)?;
+    let existing = existing.trim();
+    uuid::Uuid::parse_str(existing).map_err(|e| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("{} is not a valid instance ID: {}", path.display(), e),
+        )
+    })?;
+    Ok(existing.to_string())
+}
+
+/// Install the process-wide resource; the first call wins.
+pub fn init_resource(attributes: ResourceAttributes) -> &'static ResourceAttributes {
+    RESOURCE.get_or_init(|| attributes)
+}
+
+/// The process-wide resource, if initialized.
+pub fn resource() -> Option<&'static ResourceAttributes> {
+    RESOURCE.get()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> ResourceAttributes {
+        ResourceAttributes {
+            service_name: "gg-core".into(),
+            service_version: "1.2.3".into(),
+            instance_id: "0b0f7c5e-2a44-4f8e-9d0e-2d8f9e3c1a77".into(),
+            node: "node-a".into(),
+            profile: None,
+        }
+    }
+
+    #[test]
+    fn test_instance_id_is_stable_across_loads() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = load_or_create_instance_id(dir.path()).unwrap();
+        let second = load_or_create_instance_id(dir.path()).unwrap();
+        assert_eq!(first, second);
+        assert!(uuid::Uuid::parse_str(&first).is_ok());
+    }
+
+    #[test]
+    fn test_corrupt_instance_id_is_not_replaced() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(INSTANCE_ID_FILE), "not-a-uuid").unwrap();
+        assert!(load_or_create_instance_id(dir.path()).is_err());
+        assert_eq!(std::fs::read_to_string(dir.path().join(INSTANCE_ID_FILE)).unwrap(), "not-a-uuid");
+    }
+
+    #[test]
+    fn test_pairs_use_otel_keys() {
+        let mut attrs = sample();
+        assert!(!attrs.pairs().iter().any(|(k, _)| *k == "deployment.environment"));
+        attrs.profile = Some("staging".into());
+        let pairs = attrs.pairs();
+        assert!(pairs.contains(&("service.instance.id", "0b0f7c5e-2a44-4f8e-9d0e-2d8f9e3c1a77")));
+        assert!(pairs.contains(&("host.name", "node-a")));
+        assert!(pairs.contains(&("deployment.environment", "staging")));
+    }
+
+    #[test]
+    fn test_apply_keeps_existing_metadata() {
+        let mut metadata = HashMap::from([("host.name".to_string(), "caller".to_string())]);
+        sample().apply_to(&mut metadata);
+        assert_eq!(metadata["host.name"], "caller");
+        assert_eq!(metadata["service.name"], "gg-core");
+    }
+}
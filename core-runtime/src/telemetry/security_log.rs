@@ -34,6 +34,8 @@ pub enum SecurityEvent {
     ModelHashMismatch,
     /// Sandbox violation attempt.
     SandboxViolation,
+    /// Model file quarantined after repeated load failures.
+    ModelQuarantined,
 }
 
 impl SecurityEvent {
@@ -53,6 +55,7 @@ impl SecurityEvent {
             Self::ResourceLimitExceeded => SecuritySeverity::Warning,
             Self::ModelHashMismatch => SecuritySeverity::Critical,
             Self::SandboxViolation => SecuritySeverity::Critical,
+            Self::ModelQuarantined => SecuritySeverity::Error,
         }
     }
 
@@ -72,6 +75,7 @@ impl SecurityEvent {
             Self::ResourceLimitExceeded => "resource_limit_exceeded",
             Self::ModelHashMismatch => "model_hash_mismatch",
             Self::SandboxViolation => "sandbox_violation",
+            Self::ModelQuarantined => "model_quarantined",
         }
     }
 }
@@ -0,0 +1,337 @@
+//! Service level objectives with multi-window burn-rate alerts.
+//!
+//! Each objective classifies events as good or bad: a request succeeded,
+//! or a latency observation landed at or under the threshold. The error
+//! budget is `1 - target`; the burn rate is the observed bad fraction
+//! divided by that budget, so 1.0 spends the budget exactly over the SLO
+//! period. Burn rates are tracked over a 5-minute and a 1-hour EWMA and an
+//! objective is "burning" only while both exceed the threshold, which
+//! ignores short blips but still reacts within minutes.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tracing::Level;
+
+use super::buckets::{BucketedHistogramSnapshot, REQUEST_LATENCY_BUCKETS};
+use super::events::{emit_event, RuntimeEvent, RuntimeEventKind};
+use super::rates::{Ewma, FAILED_COUNTER, MIN_UPDATE_INTERVAL, REQUESTS_COUNTER};
+use super::store::{MetricsSnapshot, MetricsStore};
+
+/// Bucketed histogram of end-to-end inference latency.
+pub const REQUEST_LATENCY_HISTOGRAM: &str = "core_request_latency_ms";
+
+/// Short and long burn-rate windows in seconds.
+const WINDOWS: [f64; 2] = [300.0, 3600.0];
+
+/// What an objective measures.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SloIndicator {
+    /// Fraction of requests that succeed.
+    Availability,
+    /// Fraction of observations in `histogram` at or under `threshold_ms`.
+    Latency { histogram: String, threshold_ms: f64 },
+}
+
+/// A named objective, e.g. "95% of requests under 5s".
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SloObjective {
+    pub name: String,
+    pub indicator: SloIndicator,
+    /// Required good fraction in (0, 1), e.g. 0.995.
+    pub target: f64,
+}
+
+impl SloObjective {
+    pub fn availability(name: impl Into<String>, target: f64) -> Self {
+        Self { name: name.into(), indicator: SloIndicator::Availability, target }
+    }
+
+    pub fn latency(name: impl Into<String>, histogram: impl Into<String>, threshold_ms: f64, target: f64) -> Self {
+        Self {
+            name: name.into(),
+            indicator: SloIndicator::Latency { histogram: histogram.into(), threshold_ms },
+            target,
+        }
+    }
+
+    fn error_budget(&self) -> f64 {
+        (1.0 - self.target).max(f64::EPSILON)
+    }
+
+    /// Lifetime (good, total) event counts.
+    fn measure(&self, snapshot: &MetricsSnapshot) -> (u64, u64) {
+        match &self.indicator {
+            SloIndicator::Availability => {
+                let total = snapshot.counters.get(REQUESTS_COUNTER).copied().unwrap_or(0);
+                let failed = snapshot.counters.get(FAILED_COUNTER).copied().unwrap_or(0);
+                (total.saturating_sub(failed), total)
+            }
+            SloIndicator::Latency { histogram, threshold_ms } => snapshot
+                .bucketed_histograms
+                .get(histogram)
+                .map_or((0, 0), |h: &BucketedHistogramSnapshot| (h.count_at_most(*threshold_ms), h.count)),
+        }
+    }
+}
+
+/// SLO evaluation configuration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SloConfig {
+    pub objectives: Vec<SloObjective>,
+    /// Burn rate both windows must reach to alert. The default 14.4 spends
+    /// 2% of a 30-day budget per hour.
+    pub burn_rate_threshold: f64,
+    /// How often the background monitor evaluates.
+    pub eval_interval: Duration,
+}
+
+impl Default for SloConfig {
+    fn default() -> Self {
+        Self {
+            objectives: vec![
+                SloObjective::availability("availability", 0.995),
+                SloObjective::latency("latency_p95", REQUEST_LATENCY_HISTOGRAM, 5000.0, 0.95),
+            ],
+            burn_rate_threshold: 14.4,
+            eval_interval: Duration::from_secs(10),
+        }
+    }
+}
+
+/// Current state of one objective.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SloStatus {
+    pub name: String,
+    pub target: f64,
+    pub good: u64,
+    pub total: u64,
+    /// Lifetime good/total (1.0 with no traffic).
+    pub compliance: f64,
+    /// Fraction of the lifetime error budget left; negative once overspent.
+    pub budget_remaining: f64,
+    /// Burn rate over the 5-minute window.
+    pub burn_rate_short: f64,
+    /// Burn rate over the 1-hour window.
+    pub burn_rate_long: f64,
+    pub burning: bool,
+}
+
+#[derive(Debug, Default)]
+struct ObjectiveState {
+    bad: u64,
+    total: u64,
+    bad_rates: [Ewma; 2],
+    total_rates: [Ewma; 2],
+    burning: bool,
+}
+
+impl ObjectiveState {
+    fn burn_rate(&self, window: usize, budget: f64) -> f64 {
+        let total = self.total_rates[window].rate();
+        if total > 0.0 {
+            self.bad_rates[window].rate() / total / budget
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Folds metrics snapshots into per-objective burn rates.
+#[derive(Debug)]
+pub struct SloTracker {
+    config: SloConfig,
+    states: Vec<ObjectiveState>,
+    last_at: Option<Instant>,
+    statuses: Vec<SloStatus>,
+}
+
+impl SloTracker {
+    pub fn new(config: SloConfig) -> Self {
+        let states = config.objectives.iter().map(|_| ObjectiveState::default()).collect();
+        Self { config, states, last_at: None, statuses: Vec::new() }
+    }
+
+    pub fn config(&self) -> &SloConfig {
+        &self.config
+    }
+
+    /// Last evaluated statuses.
+    pub fn statuses(&self) -> &[SloStatus] {
+        &self.statuses
+    }
+
+    /// Evaluate against a snapshot, emitting an `SloBurn` event whenever an
+    /// objective starts or stops burning. Calls closer together than
+    /// [`MIN_UPDATE_INTERVAL`] return the previous result.
+    pub fn evaluate(&mut self, snapshot: &MetricsSnapshot, now: Instant) -> &[SloStatus] {
+        let elapsed = self.last_at.map(|at| now.saturating_duration_since(at));
+        if elapsed.is_some_and(|e| e < MIN_UPDATE_INTERVAL) {
+            return &self.statuses;
+        }
+        self.last_at = Some(now);
+
+        let threshold = self.config.burn_rate_threshold;
+        let mut statuses = Vec::with_capacity(self.states.len());
+        for (objective, state) in self.config.objectives.iter().zip(&mut self.states) {
+            let (good, total) = objective.measure(snapshot);
+            let bad = total.saturating_sub(good);
+            if let Some(elapsed) = elapsed {
+                let secs = elapsed.as_secs_f64();
+                let bad_rate = bad.saturating_sub(state.bad) as f64 / secs;
+                let total_rate = total.saturating_sub(state.total) as f64 / secs;
+                for (i, window) in WINDOWS.into_iter().enumerate() {
+                    state.bad_rates[i].update(bad_rate, secs, window);
+                    state.total_rates[i].update(total_rate, secs, window);
+                }
+            }
+            state.bad = bad;
+            state.total = total;
+
+            let budget = objective.error_budget();
+            let burn_rate_short = state.burn_rate(0, budget);
+            let burn_rate_long = state.burn_rate(1, budget);
+            let burning = burn_rate_short >= threshold && burn_rate_long >= threshold;
+            if burning != state.burning {
+                state.burning = burning;
+                let (level, detail) = if burning {
+                    (Level::WARN, format!(
+                        "error budget burning at {:.1}x (5m) / {:.1}x (1h), threshold {:.1}x",
+                        burn_rate_short, burn_rate_long, threshold
+                    ))
+                } else {
+                    (Level::INFO, "burn rate back under threshold".to_string())
+                };
+                emit_event(RuntimeEvent::new(RuntimeEventKind::SloBurn, level, &objective.name, detail));
+            }
+
+            let compliance = if total > 0 { good as f64 / total as f64 } else { 1.0 };
+            statuses.push(SloStatus {
+                name: objective.name.clone(),
+                target: objective.target,
+                good,
+                total,
+                compliance,
+                budget_remaining: 1.0 - (1.0 - compliance) / budget,
+                burn_rate_short,
+                burn_rate_long,
+                burning,
+            });
+        }
+        self.statuses = statuses;
+        &self.statuses
+    }
+}
+
+/// Evaluates SLOs against a live metrics store.
+pub struct SloMonitor {
+    store: Arc<MetricsStore>,
+    tracker: Mutex<SloTracker>,
+}
+
+impl SloMonitor {
+    /// Registers a latency histogram for every latency objective, with the
+    /// objective's threshold added as a bucket boundary so it is exact.
+    pub fn new(config: SloConfig, store: Arc<MetricsStore>) -> Self {
+        for objective in &config.objectives {
+            if let SloIndicator::Latency { histogram, threshold_ms } = &objective.indicator {
+                let mut boundaries = REQUEST_LATENCY_BUCKETS.to_vec();
+                if !boundaries.contains(threshold_ms) {
+                    boundaries.push(*threshold_ms);
+                }
+                store.register_bucketed(histogram, &boundaries);
+            }
+        }
+        Self { store, tracker: Mutex::new(SloTracker::new(config)) }
+    }
+
+    /// Evaluate now and return every objective's status.
+    pub fn evaluate(&self) -> Vec<SloStatus> {
+        let snapshot = self.store.snapshot();
+        let mut tracker = self.tracker.lock().unwrap_or_else(|e| e.into_inner());
+        tracker.evaluate(&snapshot, Instant::now()).to_vec()
+    }
+
+    /// Evaluate every `eval_interval` until the task is aborted.
+    pub async fn run(self: Arc<Self>) {
+        let period = self.tracker.lock().unwrap_or_else(|e| e.into_inner()).config().eval_interval;
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            self.evaluate();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn snapshot(total: u64, failed: u64) -> MetricsSnapshot {
+        MetricsSnapshot {
+            counters: HashMap::from([
+                (REQUESTS_COUNTER.to_string(), total),
+                (FAILED_COUNTER.to_string(), failed),
+            ]),
+            gauges: HashMap::new(),
+            histograms: HashMap::new(),
+            bucketed_histograms: HashMap::new(),
+            rates: HashMap::new(),
+            resource: None,
+        }
+    }
+
+    fn availability_tracker() -> SloTracker {
+        SloTracker::new(SloConfig {
+            objectives: vec![SloObjective::availability("availability", 0.99)],
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_burn_rate_is_bad_fraction_over_budget() {
+        let mut tracker = availability_tracker();
+        let start = Instant::now();
+        tracker.evaluate(&snapshot(0, 0), start);
+        let status = &tracker.evaluate(&snapshot(100, 2), start + Duration::from_secs(10))[0];
+
+        // 2% bad against a 1% budget burns at 2x
+        assert!((status.burn_rate_short - 2.0).abs() < 1e-9);
+        assert!((status.compliance - 0.98).abs() < 1e-9);
+        assert!(status.budget_remaining < 0.0);
+        assert!(!status.burning);
+    }
+
+    #[test]
+    fn test_burning_requires_both_windows() {
+        let mut tracker = availability_tracker();
+        let start = Instant::now();
+        tracker.evaluate(&snapshot(0, 0), start);
+        // A long healthy period fills both windows with good traffic
+        tracker.evaluate(&snapshot(36_000, 0), start + Duration::from_secs(3600));
+        // Then a total outage for a few minutes
+        let status = tracker.evaluate(&snapshot(37_800, 1_800), start + Duration::from_secs(3780))[0].clone();
+        assert!(status.burn_rate_short >= 14.4);
+        assert!(status.burn_rate_long < 14.4);
+        assert!(!status.burning);
+
+        let status = tracker.evaluate(&snapshot(49_800, 13_800), start + Duration::from_secs(4980))[0].clone();
+        assert!(status.burning);
+    }
+
+    #[test]
+    fn test_latency_objective_reads_histogram() {
+        let store = Arc::new(MetricsStore::new());
+        let monitor = SloMonitor::new(SloConfig::default(), Arc::clone(&store));
+        for latency in [100.0, 4000.0, 5000.0, 9000.0] {
+            store.record_bucketed(REQUEST_LATENCY_HISTOGRAM, latency);
+        }
+
+        let statuses = monitor.evaluate();
+        let latency = statuses.iter().find(|s| s.name == "latency_p95").unwrap();
+        assert_eq!((latency.good, latency.total), (3, 4));
+    }
+}
@@ -12,6 +12,8 @@ use std::time::{SystemTime, UNIX_EPOCH};
 
 use serde::{Deserialize, Serialize};
 
+use super::resource::{self, ResourceAttributes};
+
 /// Maximum spans retained in the collector buffer.
 const MAX_SPAN_BUFFER: usize = 1000;
 
@@ -51,6 +53,8 @@ pub enum SpanStatus {
 pub struct SpanCollector {
     spans: RwLock<VecDeque<ExportableSpan>>,
     max_buffer: usize,
+    /// Added to every recorded span's attributes.
+    resource: Option<ResourceAttributes>,
 }
 
 impl SpanCollector {
@@ -64,11 +68,25 @@ impl SpanCollector {
         Self {
             spans: RwLock::new(VecDeque::with_capacity(capacity)),
             max_buffer: capacity,
+            resource: resource::resource().cloned(),
         }
     }
 
+    /// Tag recorded spans with `resource` instead of the process-wide one.
+    pub fn with_resource(mut self, resource: ResourceAttributes) -> Self {
+        self.resource = Some(resource);
+        self
+    }
+
     /// Record a completed span.
-    pub fn record(&self, span: ExportableSpan) {
+    pub fn record(&self, mut span: ExportableSpan) {
+        if let Some(resource) = &self.resource {
+            for (key, value) in resource.pairs() {
+                span.attributes
+                    .entry(key.to_string())
+                    .or_insert_with(|| SpanAttributeValue::String(value.to_string()));
+            }
+        }
         let mut spans = self.spans.write().unwrap();
         if spans.len() >= self.max_buffer {
             spans.pop_front(); // Drop oldest span
@@ -156,4 +174,31 @@ mod tests {
         assert_eq!(drained.len(), 2);
         assert_eq!(drained[0].trace_id, "trace_2");
     }
+
+    #[test]
+    fn test_recorded_spans_carry_resource() {
+        let collector = SpanCollector::new().with_resource(ResourceAttributes {
+            service_name: "gg-core".to_string(),
+            service_version: "1.0.0".to_string(),
+            instance_id: "instance-1".to_string(),
+            node: "node-a".to_string(),
+            profile: None,
+        });
+        collector.record(ExportableSpan {
+            trace_id: "trace".to_string(),
+            span_id: "span".to_string(),
+            parent_span_id: None,
+            name: "op".to_string(),
+            start_time_unix_ns: 0,
+            end_time_unix_ns: 100,
+            status: SpanStatus::Ok,
+            attributes: HashMap::new(),
+        });
+
+        let span = collector.drain(1).remove(0);
+        assert!(matches!(
+            span.attributes.get("service.instance.id"),
+            Some(SpanAttributeValue::String(id)) if id == "instance-1"
+        ));
+    }
 }
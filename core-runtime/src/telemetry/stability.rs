@@ -0,0 +1,275 @@
+//! Long-running stability telemetry.
+//!
+//! [`StabilityMonitor`] samples open file descriptors, OS threads, resident
+//! memory and live tokio tasks on a fixed interval and publishes them as
+//! gauges. Leaks that take down week-long deployments (abandoned
+//! connections, tasks that never finish) show up as a count that keeps
+//! climbing, unlike a load spike that drains again. When a resource's
+//! lowest reading in the last quarter of the trend window is above its
+//! highest in the first quarter by a configured margin, the monitor emits
+//! a `ResourceGrowth` warning event, and an info event once it levels off.
+//!
+//! Process counts come from `/proc/self` and are absent on platforms
+//! without it; the task count needs a tokio runtime.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tracing::Level;
+
+use super::events::{emit_event, RuntimeEvent, RuntimeEventKind};
+use super::store::MetricsStore;
+use crate::locks::lock_or_recover;
+
+/// A sampled resource.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resource {
+    OpenFds,
+    Threads,
+    RssBytes,
+    TokioTasks,
+}
+
+impl Resource {
+    pub const ALL: [Resource; 4] = [Resource::OpenFds, Resource::Threads, Resource::RssBytes, Resource::TokioTasks];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::OpenFds => "open_fds",
+            Self::Threads => "threads",
+            Self::RssBytes => "rss_bytes",
+            Self::TokioTasks => "tokio_tasks",
+        }
+    }
+
+    /// Gauge the resource is published under.
+    pub fn gauge(&self) -> &'static str {
+        match self {
+            Self::OpenFds => "core_process_open_fds",
+            Self::Threads => "core_process_threads",
+            Self::RssBytes => "core_process_resident_memory_bytes",
+            Self::TokioTasks => "core_runtime_alive_tasks",
+        }
+    }
+
+    /// Smallest rise across the window worth reporting, so a handful of
+    /// extra descriptors on a quiet process is not a leak.
+    fn min_growth(&self) -> u64 {
+        match self {
+            Self::OpenFds => 32,
+            Self::Threads => 16,
+            Self::RssBytes => 64 * 1024 * 1024,
+            Self::TokioTasks => 256,
+        }
+    }
+}
+
+/// One reading of every resource; `None` where unavailable.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProcessSample {
+    pub open_fds: Option<u64>,
+    pub threads: Option<u64>,
+    pub rss_bytes: Option<u64>,
+    pub tokio_tasks: Option<u64>,
+}
+
+impl ProcessSample {
+    /// Read the current process and tokio runtime.
+    pub fn collect() -> Self {
+        let status = std::fs::read_to_string("/proc/self/status").ok();
+        let field = |name: &str| status.as_deref().and_then(|s| status_field(s, name));
+        Self {
+            open_fds: std::fs::read_dir("/proc/self/fd").ok().map(|dir| dir.count() as u64),
+            threads: field("Threads:"),
+            rss_bytes: field("VmRSS:").map(|kb| kb * 1024),
+            tokio_tasks: tokio::runtime::Handle::try_current()
+                .ok()
+                .map(|handle| handle.metrics().num_alive_tasks() as u64),
+        }
+    }
+
+    pub fn get(&self, resource: Resource) -> Option<u64> {
+        match resource {
+            Resource::OpenFds => self.open_fds,
+            Resource::Threads => self.threads,
+            Resource::RssBytes => self.rss_bytes,
+            Resource::TokioTasks => self.tokio_tasks,
+        }
+    }
+}
+
+/// First number after `name` in `/proc/self/status`.
+fn status_field(status: &str, name: &str) -> Option<u64> {
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix(name))
+        .and_then(|rest| rest.split_whitespace().next())
+        .and_then(|value| value.parse().ok())
+}
+
+/// Stability monitor settings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StabilityConfig {
+    pub sample_interval: Duration,
+    /// Samples in the trend window; at least 4.
+    pub window: usize,
+    /// Rise across the window, relative to its start, that counts as growth.
+    pub min_growth_ratio: f64,
+}
+
+impl Default for StabilityConfig {
+    fn default() -> Self {
+        Self {
+            sample_interval: Duration::from_secs(60),
+            window: 60,
+            min_growth_ratio: 0.25,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Trend {
+    samples: VecDeque<u64>,
+    growing: bool,
+}
+
+impl Trend {
+    /// Rise from the first quarter's peak to the last quarter's trough of
+    /// a full window, if the last quarter sits entirely above the first.
+    fn rise(&self, window: usize) -> Option<(u64, u64)> {
+        if self.samples.len() < window {
+            return None;
+        }
+        let quarter = (window / 4).max(1);
+        let start = self.samples.iter().take(quarter).copied().max()?;
+        let end = self.samples.iter().skip(window - quarter).copied().min()?;
+        (end > start).then_some((start, end))
+    }
+}
+
+/// Samples process resources and reports sustained growth.
+pub struct StabilityMonitor {
+    config: StabilityConfig,
+    store: Arc<MetricsStore>,
+    trends: Mutex<Vec<Trend>>,
+}
+
+impl StabilityMonitor {
+    pub fn new(config: StabilityConfig, store: Arc<MetricsStore>) -> Self {
+        let trends = Resource::ALL.iter().map(|_| Trend::default()).collect();
+        Self { config: StabilityConfig { window: config.window.max(4), ..config }, store, trends: Mutex::new(trends) }
+    }
+
+    /// Publish `sample` and fold it into the trends, emitting a
+    /// `ResourceGrowth` event whenever a resource starts or stops growing.
+    /// Returns the resources currently growing.
+    pub fn record(&self, sample: &ProcessSample) -> Vec<Resource> {
+        let window = self.config.window;
+        let mut trends = lock_or_recover("stability.trends", &self.trends);
+        let mut growing = Vec::new();
+        let mut events = Vec::new();
+        for (resource, trend) in Resource::ALL.into_iter().zip(trends.iter_mut()) {
+            let Some(value) = sample.get(resource) else {
+                continue;
+            };
+            self.store.set_gauge(resource.gauge(), value as f64);
+            if trend.samples.len() == window {
+                trend.samples.pop_front();
+            }
+            trend.samples.push_back(value);
+
+            let rise = trend.rise(window).filter(|(start, end)| {
+                end - start >= resource.min_growth()
+                    && (end - start) as f64 >= self.config.min_growth_ratio * *start as f64
+            });
+            if rise.is_some() != trend.growing {
+                trend.growing = rise.is_some();
+                let (level, detail) = match rise {
+                    Some((start, end)) => (Level::WARN, format!(
+                        "grew from {} to {} over the last {:?}",
+                        start,
+                        end,
+                        self.config.sample_interval * window as u32
+                    )),
+                    None => (Level::INFO, format!("levelled off at {}", value)),
+                };
+                if level == Level::WARN {
+                    tracing::warn!(resource = resource.as_str(), "Sustained resource growth: {}", detail);
+                }
+                events.push(RuntimeEvent::new(RuntimeEventKind::ResourceGrowth, level, resource.as_str(), detail));
+            }
+            if trend.growing {
+                growing.push(resource);
+            }
+        }
+        // Listeners run synchronously; do not hold the trends lock for them
+        drop(trends);
+        events.into_iter().for_each(emit_event);
+        growing
+    }
+
+    /// Collect and record one sample.
+    pub fn sample(&self) -> Vec<Resource> {
+        self.record(&ProcessSample::collect())
+    }
+
+    /// Sample every `sample_interval` until the task is aborted.
+    pub async fn run(self: Arc<Self>) {
+        let mut interval = tokio::time::interval(self.config.sample_interval);
+        loop {
+            interval.tick().await;
+            self.sample();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fds(open_fds: u64) -> ProcessSample {
+        ProcessSample { open_fds: Some(open_fds), ..Default::default() }
+    }
+
+    #[test]
+    fn test_status_field_parsing() {
+        let status = "Name:\tgg-core\nVmRSS:\t  20480 kB\nThreads:\t17\n";
+        assert_eq!(status_field(status, "Threads:"), Some(17));
+        assert_eq!(status_field(status, "VmRSS:"), Some(20480));
+        assert_eq!(status_field(status, "VmSwap:"), None);
+    }
+
+    #[test]
+    fn test_sustained_growth_warns_and_clears() {
+        let store = Arc::new(MetricsStore::new());
+        let config = StabilityConfig { window: 8, ..Default::default() };
+        let monitor = StabilityMonitor::new(config, Arc::clone(&store));
+
+        // A spike that drains again is not growth
+        for open in [100, 100, 400, 400, 100, 100, 100, 100] {
+            assert!(monitor.record(&fds(open)).is_empty());
+        }
+        // Steady climb once the spike has left the first quarter
+        for open in [120, 140, 160] {
+            assert!(monitor.record(&fds(open)).is_empty());
+        }
+        assert_eq!(monitor.record(&fds(180)), vec![Resource::OpenFds]);
+        assert_eq!(store.snapshot().gauges.get("core_process_open_fds"), Some(&180.0));
+
+        for _ in 0..8 {
+            monitor.record(&fds(180));
+        }
+        assert!(monitor.record(&fds(180)).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_collect_reads_runtime() {
+        let sample = ProcessSample::collect();
+        assert!(sample.tokio_tasks.is_some());
+        if cfg!(target_os = "linux") {
+            assert!(sample.open_fds.unwrap() > 0);
+            assert!(sample.threads.unwrap() >= 1);
+        }
+    }
+}
@@ -6,11 +6,15 @@
 
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::RwLock;
+use std::sync::{Mutex, RwLock};
+use std::time::Instant;
 
 use serde::{Deserialize, Serialize};
 
-use super::buckets::{BucketedHistogram, BucketedHistogramSnapshot};
+use super::buckets::{BucketedHistogram, BucketedHistogramSnapshot, DEFAULT_LATENCY_BUCKETS, TTFT_BUCKETS};
+use super::rates::{self, RateTracker};
+use super::resource::{self, ResourceAttributes};
+use super::streaming::{TokenLatency, INTER_TOKEN_HISTOGRAM, TTFT_HISTOGRAM};
 
 /// Snapshot of all metrics at a point in time.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,6 +24,12 @@ pub struct MetricsSnapshot {
     pub histograms: HashMap<String, HistogramSummary>,
     #[serde(default)]
     pub bucketed_histograms: HashMap<String, BucketedHistogramSnapshot>,
+    /// Derived 1m/5m rates and error ratios (see [`super::rates`]).
+    #[serde(default)]
+    pub rates: HashMap<String, f64>,
+    /// Identity of the reporting instance (see [`super::resource`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource: Option<ResourceAttributes>,
 }
 
 /// Summary statistics for a histogram.
@@ -113,6 +123,7 @@ pub struct MetricsStore {
     gauges: RwLock<HashMap<String, AtomicU64>>,
     histograms: RwLock<HashMap<String, HistogramData>>,
     bucketed_histograms: RwLock<HashMap<String, BucketedHistogram>>,
+    rates: Mutex<RateTracker>,
 }
 
 impl MetricsStore {
@@ -123,6 +134,20 @@ impl MetricsStore {
             gauges: RwLock::new(HashMap::new()),
             histograms: RwLock::new(HashMap::new()),
             bucketed_histograms: RwLock::new(HashMap::new()),
+            rates: Mutex::new(RateTracker::new()),
+        }
+    }
+
+    /// Count a completed request and its generated tokens.
+    pub fn record_request(&self, success: bool, tokens: u64) {
+        self.increment_counter(rates::REQUESTS_COUNTER, 1);
+        if success {
+            self.increment_counter("core_requests_success", 1);
+        } else {
+            self.increment_counter(rates::FAILED_COUNTER, 1);
+        }
+        if tokens > 0 {
+            self.increment_counter(rates::TOKENS_COUNTER, tokens);
         }
     }
 
@@ -190,6 +215,19 @@ impl MetricsStore {
         }
     }
 
+    /// Record a streamed token's TTFT or inter-token gap, registering the
+    /// histograms on first use.
+    pub fn record_token_latency(&self, latency: TokenLatency) {
+        let (name, boundaries, ms): (_, &[f64], _) = match latency {
+            TokenLatency::FirstToken(ms) => (TTFT_HISTOGRAM, &TTFT_BUCKETS, ms),
+            TokenLatency::InterToken(ms) => (INTER_TOKEN_HISTOGRAM, &DEFAULT_LATENCY_BUCKETS, ms),
+        };
+        if !self.bucketed_histograms.read().unwrap().contains_key(name) {
+            self.register_bucketed(name, boundaries);
+        }
+        self.record_bucketed(name, ms);
+    }
+
     /// Take a snapshot of all metrics.
     pub fn snapshot(&self) -> MetricsSnapshot {
         let counters = self.counters.read().unwrap();
@@ -197,11 +235,18 @@ impl MetricsStore {
         let histograms = self.histograms.read().unwrap();
         let bucketed = self.bucketed_histograms.read().unwrap();
 
+        let counters: HashMap<String, u64> = counters
+            .iter()
+            .map(|(k, v)| (k.clone(), v.load(Ordering::Relaxed)))
+            .collect();
+        let rates = {
+            let mut tracker = self.rates.lock().unwrap_or_else(|e| e.into_inner());
+            tracker.update(&counters, Instant::now());
+            tracker.rates()
+        };
+
         MetricsSnapshot {
-            counters: counters
-                .iter()
-                .map(|(k, v)| (k.clone(), v.load(Ordering::Relaxed)))
-                .collect(),
+            counters,
             gauges: gauges
                 .iter()
                 .map(|(k, v)| (k.clone(), f64::from_bits(v.load(Ordering::Relaxed))))
@@ -214,6 +259,8 @@ impl MetricsStore {
                 .iter()
                 .map(|(k, v)| (k.clone(), v.snapshot()))
                 .collect(),
+            rates,
+            resource: resource::resource().cloned(),
         }
     }
 }
@@ -0,0 +1,121 @@
+//! Time-to-first-token and inter-token latency for streamed responses.
+//!
+//! End-to-end latency hides what a streaming UI user perceives: how long
+//! until text starts appearing, and how smoothly it arrives afterwards.
+//! A [`StreamTimer`] is started when the request is accepted and ticked
+//! once per token; each tick yields the latency to record.
+
+use std::time::Instant;
+
+/// Bucketed histogram of time to first token.
+pub const TTFT_HISTOGRAM: &str = "core_ttft_ms";
+/// Bucketed histogram of gaps between consecutive tokens.
+pub const INTER_TOKEN_HISTOGRAM: &str = "core_inter_token_ms";
+
+/// Latency observed for one streamed token, in milliseconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TokenLatency {
+    /// Request start to the first token.
+    FirstToken(f64),
+    /// Previous token to this one.
+    InterToken(f64),
+}
+
+/// Per-stream summary.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct StreamTimings {
+    /// None if no token was produced.
+    pub ttft_ms: Option<f64>,
+    pub inter_token_count: u64,
+    pub inter_token_total_ms: f64,
+    pub inter_token_max_ms: f64,
+}
+
+impl StreamTimings {
+    pub fn avg_inter_token_ms(&self) -> f64 {
+        if self.inter_token_count > 0 {
+            self.inter_token_total_ms / self.inter_token_count as f64
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Measures token arrival times for a single stream.
+#[derive(Debug, Clone)]
+pub struct StreamTimer {
+    start: Instant,
+    last: Option<Instant>,
+    timings: StreamTimings,
+}
+
+impl StreamTimer {
+    pub fn start() -> Self {
+        Self::start_at(Instant::now())
+    }
+
+    pub fn start_at(start: Instant) -> Self {
+        Self { start, last: None, timings: StreamTimings::default() }
+    }
+
+    /// Record a token arriving now.
+    pub fn token(&mut self) -> TokenLatency {
+        self.token_at(Instant::now())
+    }
+
+    pub fn token_at(&mut self, now: Instant) -> TokenLatency {
+        let latency = match self.last {
+            None => {
+                let ms = duration_ms(self.start, now);
+                self.timings.ttft_ms = Some(ms);
+                TokenLatency::FirstToken(ms)
+            }
+            Some(last) => {
+                let ms = duration_ms(last, now);
+                self.timings.inter_token_count += 1;
+                self.timings.inter_token_total_ms += ms;
+                self.timings.inter_token_max_ms = self.timings.inter_token_max_ms.max(ms);
+                TokenLatency::InterToken(ms)
+            }
+        };
+        self.last = Some(now);
+        latency
+    }
+
+    pub fn timings(&self) -> StreamTimings {
+        self.timings
+    }
+}
+
+fn duration_ms(from: Instant, to: Instant) -> f64 {
+    to.saturating_duration_since(from).as_secs_f64() * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_first_token_then_intervals() {
+        let start = Instant::now();
+        let mut timer = StreamTimer::start_at(start);
+
+        assert_eq!(timer.token_at(start + Duration::from_millis(200)), TokenLatency::FirstToken(200.0));
+        assert_eq!(timer.token_at(start + Duration::from_millis(220)), TokenLatency::InterToken(20.0));
+        assert_eq!(timer.token_at(start + Duration::from_millis(260)), TokenLatency::InterToken(40.0));
+
+        let timings = timer.timings();
+        assert_eq!(timings.ttft_ms, Some(200.0));
+        assert_eq!(timings.inter_token_count, 2);
+        assert_eq!(timings.avg_inter_token_ms(), 30.0);
+        assert_eq!(timings.inter_token_max_ms, 40.0);
+    }
+
+    #[test]
+    fn test_empty_stream_has_no_ttft() {
+        let timings = StreamTimer::start().timings();
+        assert_eq!(timings.ttft_ms, None);
+        assert_eq!(timings.avg_inter_token_ms(), 0.0);
+    }
+}
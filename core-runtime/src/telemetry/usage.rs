@@ -0,0 +1,262 @@
+//! Per-tenant usage aggregates with optional differential-privacy export.
+//!
+//! The ledger keeps exact per-tenant request and token counts for internal
+//! use. Exports leaving the host (`gg-core usage export`) can add Laplace
+//! noise instead: with privacy budget ε, each aggregate gets noise of scale
+//! `sensitivity / (ε / 2)`, splitting ε evenly between requests and tokens
+//! (sequential composition). One request changes the request count by at
+//! most 1 and the token count by at most `max_tokens_per_request`, so a
+//! single request's contribution is ε-differentially private. Noisy values
+//! are rounded and clamped at zero, which does not weaken the guarantee.
+//! The mechanism and its parameters travel in the export's `privacy` block.
+//!
+//! Tenants are keyed by a hash of the session identity, never the raw token.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Differential-privacy settings for usage exports.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DpConfig {
+    /// Total privacy budget per export.
+    pub epsilon: f64,
+    /// Token sensitivity: tokens counted for one request are capped here.
+    pub max_tokens_per_request: u64,
+}
+
+impl Default for DpConfig {
+    fn default() -> Self {
+        Self { epsilon: 1.0, max_tokens_per_request: 4096 }
+    }
+}
+
+/// How exported numbers were produced.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PrivacyMetadata {
+    /// `laplace` or `none` (exact).
+    pub mechanism: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub epsilon: Option<f64>,
+    /// Budget spent on each of the two aggregates.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub epsilon_per_metric: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requests_sensitivity: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tokens_sensitivity: Option<u64>,
+}
+
+/// One tenant's aggregates in an export.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TenantUsage {
+    pub tenant: String,
+    pub requests: u64,
+    pub tokens: u64,
+}
+
+/// Usage export handed to external systems.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsageExport {
+    /// Export time (RFC 3339).
+    pub generated_at: String,
+    pub privacy: PrivacyMetadata,
+    pub tenants: Vec<TenantUsage>,
+}
+
+/// Exact per-tenant counters.
+#[derive(Debug, Default)]
+pub struct UsageLedger {
+    /// Tenant -> (requests, tokens, last updated).
+    tenants: Mutex<HashMap<String, (u64, u64, Instant)>>,
+    /// Token cap applied when counting; equals the DP token sensitivity.
+    max_tokens_per_request: Option<u64>,
+}
+
+/// Stable, non-reversible tenant label for a session identity.
+pub fn tenant_label(identity: &str) -> String {
+    let digest = Sha256::digest(identity.as_bytes());
+    format!("tenant-{}", &hex::encode(digest)[..12])
+}
+
+impl UsageLedger {
+    pub fn new(dp: Option<DpConfig>) -> Self {
+        Self { tenants: Mutex::new(HashMap::new()), max_tokens_per_request: dp.map(|c| c.max_tokens_per_request) }
+    }
+
+    /// Count one completed request for the tenant behind `identity`.
+    pub fn record(&self, identity: &str, tokens: u64) {
+        let tokens = self.max_tokens_per_request.map_or(tokens, |cap| tokens.min(cap));
+        let mut tenants = self.tenants.lock().unwrap_or_else(|e| e.into_inner());
+        let entry = tenants.entry(tenant_label(identity)).or_insert((0, 0, Instant::now()));
+        entry.0 += 1;
+        entry.1 += tokens;
+        entry.2 = Instant::now();
+    }
+
+    /// Remove a tenant's aggregates. Returns false if it had none.
+    pub fn purge(&self, tenant: &str) -> bool {
+        self.tenants.lock().unwrap_or_else(|e| e.into_inner()).remove(tenant).is_some()
+    }
+
+    /// Drop tenants idle longer than `max_age`, then the least recently
+    /// updated until the serialized aggregates fit `max_bytes`.
+    /// Returns (tenants removed, bytes reclaimed).
+    pub fn enforce_retention(&self, max_age: Option<Duration>, max_bytes: Option<u64>) -> (usize, u64) {
+        let mut tenants = self.tenants.lock().unwrap_or_else(|e| e.into_inner());
+        let mut entries: Vec<(String, u64, Instant)> = tenants
+            .iter()
+            .map(|(tenant, (requests, tokens, updated))| {
+                let usage = TenantUsage { tenant: tenant.clone(), requests: *requests, tokens: *tokens };
+                (tenant.clone(), serialized_len(&usage), *updated)
+            })
+            .collect();
+        entries.sort_by_key(|(_, _, updated)| *updated);
+
+        let mut total: u64 = entries.iter().map(|(_, bytes, _)| bytes).sum();
+        let (mut removed, mut reclaimed) = (0, 0);
+        for (tenant, bytes, updated) in entries {
+            let expired = max_age.is_some_and(|age| updated.elapsed() > age);
+            let oversize = max_bytes.is_some_and(|max| total > max);
+            if !expired && !oversize {
+                break;
+            }
+            tenants.remove(&tenant);
+            total -= bytes;
+            removed += 1;
+            reclaimed += bytes;
+        }
+        (removed, reclaimed)
+    }
+
+    /// Exact aggregates, sorted by tenant. For internal use only.
+    pub fn exact(&self) -> Vec<TenantUsage> {
+        let tenants = self.tenants.lock().unwrap_or_else(|e| e.into_inner());
+        let mut usage: Vec<TenantUsage> = tenants
+            .iter()
+            .map(|(tenant, (requests, tokens, _))| TenantUsage { tenant: tenant.clone(), requests: *requests, tokens: *tokens })
+            .collect();
+        usage.sort_by(|a, b| a.tenant.cmp(&b.tenant));
+        usage
+    }
+
+    /// Export aggregates, adding Laplace noise when `dp` is set.
+    pub fn export(&self, dp: Option<&DpConfig>) -> UsageExport {
+        let generated_at = chrono::Utc::now().to_rfc3339();
+        let exact = self.exact();
+        let Some(dp) = dp else {
+            let privacy = PrivacyMetadata {
+                mechanism: "none".into(),
+                epsilon: None,
+                epsilon_per_metric: None,
+                requests_sensitivity: None,
+                tokens_sensitivity: None,
+            };
+            return UsageExport { generated_at, privacy, tenants: exact };
+        };
+
+        let per_metric = dp.epsilon / 2.0;
+        let mut rng = rand::thread_rng();
+        let tenants = exact
+            .into_iter()
+            .map(|usage| TenantUsage {
+                tenant: usage.tenant,
+                requests: noisy(usage.requests, 1.0 / per_metric, &mut rng),
+                tokens: noisy(usage.tokens, dp.max_tokens_per_request as f64 / per_metric, &mut rng),
+            })
+            .collect();
+        let privacy = PrivacyMetadata {
+            mechanism: "laplace".into(),
+            epsilon: Some(dp.epsilon),
+            epsilon_per_metric: Some(per_metric),
+            requests_sensitivity: Some(1),
+            tokens_sensitivity: Some(dp.max_tokens_per_request),
+        };
+        UsageExport { generated_at, privacy, tenants }
+    }
+}
+
+fn serialized_len(usage: &TenantUsage) -> u64 {
+    serde_json::to_vec(usage).map_or(0, |bytes| bytes.len() as u64)
+}
+
+/// Sample Laplace(0, `scale`) by inverse CDF.
+fn laplace(scale: f64, rng: &mut impl Rng) -> f64 {
+    let u: f64 = rng.gen_range(-0.5..0.5);
+    -scale * u.signum() * (1.0 - 2.0 * u.abs()).ln()
+}
+
+fn noisy(value: u64, scale: f64, rng: &mut impl Rng) -> u64 {
+    (value as f64 + laplace(scale, rng)).round().max(0.0) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tenant_labels_hide_identity() {
+        let label = tenant_label("session-secret");
+        assert!(label.starts_with("tenant-") && !label.contains("secret"));
+        assert_eq!(label, tenant_label("session-secret"));
+        assert_ne!(label, tenant_label("other"));
+    }
+
+    #[test]
+    fn test_exact_export_without_dp() {
+        let ledger = UsageLedger::new(None);
+        ledger.record("a", 10);
+        ledger.record("a", 5);
+        ledger.record("b", 1);
+        let export = ledger.export(None);
+        assert_eq!(export.privacy.mechanism, "none");
+        let a = export.tenants.iter().find(|t| t.tenant == tenant_label("a")).unwrap();
+        assert_eq!((a.requests, a.tokens), (2, 15));
+    }
+
+    #[test]
+    fn test_dp_export_adds_noise_but_keeps_exact_counters() {
+        let dp = DpConfig { epsilon: 0.5, max_tokens_per_request: 100 };
+        let ledger = UsageLedger::new(Some(dp));
+        for _ in 0..50 {
+            ledger.record("a", 1_000);
+        }
+        // Tokens are capped at the sensitivity
+        assert_eq!(ledger.exact()[0].tokens, 5_000);
+
+        let exports: Vec<_> = (0..20).map(|_| ledger.export(Some(&dp))).collect();
+        assert_eq!(exports[0].privacy.mechanism, "laplace");
+        assert_eq!(exports[0].privacy.epsilon_per_metric, Some(0.25));
+        assert_eq!(exports[0].privacy.tokens_sensitivity, Some(100));
+        let distinct: std::collections::HashSet<u64> =
+            exports.iter().map(|e| e.tenants[0].tokens).collect();
+        assert!(distinct.len() > 1, "exports should be noised");
+        assert_eq!(ledger.exact()[0].requests, 50);
+    }
+
+    #[test]
+    fn test_retention_drops_least_recent_first() {
+        let ledger = UsageLedger::new(None);
+        ledger.record("old", 1);
+        std::thread::sleep(Duration::from_millis(5));
+        ledger.record("new", 1);
+        let one = serialized_len(&ledger.exact()[0]);
+
+        assert_eq!(ledger.enforce_retention(None, Some(one)).0, 1);
+        assert_eq!(ledger.exact()[0].tenant, tenant_label("new"));
+        assert_eq!(ledger.enforce_retention(Some(Duration::ZERO), None).0, 1);
+        assert!(ledger.exact().is_empty());
+    }
+
+    #[test]
+    fn test_laplace_noise_is_centered() {
+        let mut rng = rand::thread_rng();
+        let samples = 20_000;
+        let mean: f64 = (0..samples).map(|_| laplace(2.0, &mut rng)).sum::<f64>() / samples as f64;
+        assert!(mean.abs() < 0.2, "mean {mean}");
+    }
+}
@@ -0,0 +1,273 @@
+//! Server-side prompt templates.
+//!
+//! Named, versioned templates live under `<base>/templates/<id>.json`.
+//! Clients send `template_id` (optionally pinned as `id@version`) plus
+//! `variables` instead of a raw prompt, so prompt wording is governed in one
+//! place. Placeholders are `{{ name }}`; every placeholder must be supplied,
+//! unknown variables are rejected, and each value is injection-scanned and
+//! escaped before it is substituted.
+
+mod render;
+
+pub use render::{escape_variable, variable_filter, MAX_VARIABLE_BYTES, VARIABLE_RISK_THRESHOLD};
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::security::PromptInjectionFilter;
+
+/// Longest accepted template ID.
+pub const MAX_TEMPLATE_ID_LEN: usize = 64;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum TemplateError {
+    #[error("Invalid template ID '{0}': use letters, digits, '-', '_' or '.'")]
+    InvalidId(String),
+
+    #[error("Template not found: {0}")]
+    NotFound(String),
+
+    #[error("Template {id} has no version {version}")]
+    VersionNotFound { id: String, version: u32 },
+
+    #[error("Unclosed placeholder in template")]
+    UnclosedPlaceholder,
+
+    #[error("Invalid placeholder name '{0}'")]
+    InvalidPlaceholder(String),
+
+    #[error("Missing template variable: {0}")]
+    MissingVariable(String),
+
+    #[error("Unexpected template variable: {0}")]
+    UnexpectedVariable(String),
+
+    #[error("Template variable {name} exceeds {max} bytes")]
+    VariableTooLarge { name: String, max: usize },
+
+    #[error("Template variable {name} rejected: possible prompt injection ({pattern})")]
+    InjectionDetected { name: String, pattern: String },
+
+    #[error("Template storage error: {0}")]
+    Storage(String),
+}
+
+/// One immutable version of a template.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplateVersion {
+    pub version: u32,
+    pub body: String,
+    /// Placeholder names, sorted.
+    pub variables: Vec<String>,
+    /// Creation time (RFC 3339).
+    pub created_at: String,
+}
+
+/// All versions of a template, as stored on disk.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PromptTemplate {
+    pub id: String,
+    pub versions: Vec<TemplateVersion>,
+}
+
+impl PromptTemplate {
+    pub fn latest(&self) -> Option<&TemplateVersion> {
+        self.versions.last()
+    }
+
+    pub fn version(&self, version: u32) -> Option<&TemplateVersion> {
+        self.versions.iter().find(|v| v.version == version)
+    }
+}
+
+/// A rendered prompt and the template version that produced it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderedPrompt {
+    pub prompt: String,
+    pub id: String,
+    pub version: u32,
+}
+
+/// Split `id@version` into its parts; a bare ID means the latest version.
+pub fn parse_template_ref(reference: &str) -> Result<(&str, Option<u32>), TemplateError> {
+    let invalid = || TemplateError::InvalidId(reference.to_string());
+    let (id, version) = match reference.split_once('@') {
+        Some((id, v)) => (id, Some(v.parse().map_err(|_| invalid())?)),
+        None => (reference, None),
+    };
+    validate_id(id)?;
+    Ok((id, version))
+}
+
+fn validate_id(id: &str) -> Result<(), TemplateError> {
+    let valid = !id.is_empty()
+        && id.len() <= MAX_TEMPLATE_ID_LEN
+        && !id.starts_with('.')
+        && id.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if valid {
+        Ok(())
+    } else {
+        Err(TemplateError::InvalidId(id.to_string()))
+    }
+}
+
+/// File-backed template library with an mtime-checked read cache, so
+/// templates added by `gg-core templates add` are picked up by a running
+/// server.
+pub struct TemplateStore {
+    dir: PathBuf,
+    cache: RwLock<HashMap<String, (SystemTime, PromptTemplate)>>,
+    filter: PromptInjectionFilter,
+}
+
+impl TemplateStore {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir, cache: RwLock::new(HashMap::new()), filter: variable_filter() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{id}.json"))
+    }
+
+    /// Load a template, reusing the cached copy while the file is unchanged.
+    pub fn get(&self, id: &str) -> Result<PromptTemplate, TemplateError> {
+        validate_id(id)?;
+        let path = self.path(id);
+        let modified = match fs::metadata(&path).and_then(|m| m.modified()) {
+            Ok(modified) => modified,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Err(TemplateError::NotFound(id.to_string()))
+            }
+            Err(e) => return Err(TemplateError::Storage(e.to_string())),
+        };
+        if let Some((cached_at, template)) = self.cache.read().unwrap_or_else(|e| e.into_inner()).get(id) {
+            if *cached_at == modified {
+                return Ok(template.clone());
+            }
+        }
+        let bytes = fs::read(&path).map_err(|e| TemplateError::Storage(e.to_string()))?;
+        let template: PromptTemplate =
+            serde_json::from_slice(&bytes).map_err(|e| TemplateError::Storage(e.to_string()))?;
+        self.cache
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(id.to_string(), (modified, template.clone()));
+        Ok(template)
+    }
+
+    /// Add `body` as the next version of `id`, returning the new version.
+    pub fn put(&self, id: &str, body: &str) -> Result<u32, TemplateError> {
+        validate_id(id)?;
+        let variables = render::variables(&render::parse(body)?);
+        let mut template = match self.get(id) {
+            Ok(template) => template,
+            Err(TemplateError::NotFound(_)) => PromptTemplate { id: id.to_string(), versions: Vec::new() },
+            Err(e) => return Err(e),
+        };
+        let version = template.latest().map_or(1, |v| v.version + 1);
+        template.versions.push(TemplateVersion {
+            version,
+            body: body.to_string(),
+            variables,
+            created_at: chrono::Utc::now().to_rfc3339(),
+        });
+
+        let storage = |e: std::io::Error| TemplateError::Storage(e.to_string());
+        fs::create_dir_all(&self.dir).map_err(storage)?;
+        let path = self.path(id);
+        let temp_path = path.with_extension("tmp");
+        let json = serde_json::to_vec_pretty(&template).map_err(|e| TemplateError::Storage(e.to_string()))?;
+        fs::write(&temp_path, json).map_err(storage)?;
+        fs::rename(&temp_path, &path).map_err(storage)?;
+        if let Ok(modified) = fs::metadata(&path).and_then(|m| m.modified()) {
+            self.cache.write().unwrap_or_else(|e| e.into_inner()).insert(id.to_string(), (modified, template));
+        }
+        Ok(version)
+    }
+
+    /// All templates, sorted by ID. Unreadable files are skipped.
+    pub fn list(&self) -> Vec<PromptTemplate> {
+        let Ok(entries) = fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+        let mut templates: Vec<PromptTemplate> = entries
+            .filter_map(|e| e.ok())
+            .filter_map(|e| e.path().file_stem()?.to_str().map(str::to_string))
+            .filter_map(|id| self.get(&id).ok())
+            .collect();
+        templates.sort_by(|a, b| a.id.cmp(&b.id));
+        templates
+    }
+
+    /// Render `reference` (`id` or `id@version`) with `variables`.
+    pub fn render(
+        &self,
+        reference: &str,
+        variables: &BTreeMap<String, String>,
+    ) -> Result<RenderedPrompt, TemplateError> {
+        let (id, version) = parse_template_ref(reference)?;
+        let template = self.get(id)?;
+        let selected = match version {
+            Some(v) => template
+                .version(v)
+                .ok_or_else(|| TemplateError::VersionNotFound { id: id.to_string(), version: v })?,
+            None => template.latest().ok_or_else(|| TemplateError::NotFound(id.to_string()))?,
+        };
+        let prompt = render::render(&render::parse(&selected.body)?, variables, &self.filter)?;
+        Ok(RenderedPrompt { prompt, id: id.to_string(), version: selected.version })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_template_refs() {
+        assert_eq!(parse_template_ref("summarize").unwrap(), ("summarize", None));
+        assert_eq!(parse_template_ref("summarize@3").unwrap(), ("summarize", Some(3)));
+        assert!(parse_template_ref("summarize@x").is_err());
+        assert!(parse_template_ref("../etc/passwd").is_err());
+        assert!(parse_template_ref("").is_err());
+    }
+
+    #[test]
+    fn test_versions_and_pinning() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = TemplateStore::new(dir.path().join("templates"));
+        assert_eq!(store.put("greet", "Hello {{name}}").unwrap(), 1);
+        assert_eq!(store.put("greet", "Hi {{ name }}!").unwrap(), 2);
+
+        let vars = BTreeMap::from([("name".to_string(), "Ada".to_string())]);
+        assert_eq!(store.render("greet", &vars).unwrap().prompt, "Hi Ada!");
+        let pinned = store.render("greet@1", &vars).unwrap();
+        assert_eq!((pinned.prompt.as_str(), pinned.version), ("Hello Ada", 1));
+        assert_eq!(
+            store.render("greet@9", &vars),
+            Err(TemplateError::VersionNotFound { id: "greet".into(), version: 9 })
+        );
+        assert_eq!(store.render("other", &vars), Err(TemplateError::NotFound("other".into())));
+
+        let listed = store.list();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].latest().unwrap().variables, ["name"]);
+    }
+
+    #[test]
+    fn test_invalid_body_is_not_stored() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = TemplateStore::new(dir.path().to_path_buf());
+        assert_eq!(store.put("bad", "Hello {{name"), Err(TemplateError::UnclosedPlaceholder));
+        assert!(store.list().is_empty());
+    }
+}
@@ -0,0 +1,310 @@
+//! In-process server fixture for end-to-end tests (`testing` feature).
+//!
+//! [`TestRuntime::spawn`] builds a [`Runtime`] under a temporary base path,
+//! registers [`StubModel`] as [`STUB_MODEL`], serves IPC on a socket in
+//! that directory and returns a [`TestClient`] that has completed the
+//! handshake. Requests go through the real framing, auth, protocol and
+//! scheduler path, with no binary to build or shell out to.
+//!
+//! ```ignore
+//! let (server, mut client) = TestRuntime::spawn().await?;
+//! let response = client.infer(STUB_MODEL, "hello").await?;
+//! assert_eq!(response.output, "hello");
+//! server.shutdown().await;
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tempfile::TempDir;
+use thiserror::Error;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::UnixStream;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+use crate::engine::{
+    FinishReason, GenerationResult, GgufModel, InferenceCapability, InferenceConfig, InferenceEngine,
+    InferenceError, InferenceInput, InferenceOutput, InferenceParams,
+};
+use crate::ipc::protocol::{decode_message, encode_message, InferenceRequest, InferenceResponse, IpcMessage, RequestId};
+use crate::ipc::server::{self, ServerError};
+use crate::ipc::IpcHandler;
+use crate::models::{ModelMetadata, ModelRegistry};
+use crate::telemetry::MetricsStore;
+use crate::{Runtime, RuntimeConfig};
+
+/// Handshake token used when the config sets none.
+pub const TEST_AUTH_TOKEN: &str = "test-token";
+
+/// Model id the stub model is registered under.
+pub const STUB_MODEL: &str = "stub";
+
+/// Socket file inside the fixture's base path.
+const SOCKET_FILE: &str = "core.sock";
+
+/// How long [`TestRuntime::spawn`] waits for the socket to accept.
+const READY_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Error, Debug)]
+pub enum TestError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Protocol error: {0}")]
+    Protocol(String),
+
+    #[error("Handshake refused: {0}")]
+    Handshake(String),
+
+    #[error("Server did not accept connections within {0:?}")]
+    NotReady(Duration),
+}
+
+/// Text model that answers with its prompt, one token per word.
+pub struct StubModel {
+    model_id: String,
+}
+
+impl StubModel {
+    pub fn new(model_id: impl Into<String>) -> Self {
+        Self { model_id: model_id.into() }
+    }
+}
+
+#[async_trait::async_trait]
+impl GgufModel for StubModel {
+    fn model_id(&self) -> &str {
+        &self.model_id
+    }
+
+    fn capabilities(&self) -> &[InferenceCapability] {
+        &[InferenceCapability::TextGeneration]
+    }
+
+    fn memory_usage(&self) -> usize {
+        0
+    }
+
+    async fn infer(
+        &self,
+        input: &InferenceInput,
+        _config: &InferenceConfig,
+    ) -> Result<InferenceOutput, InferenceError> {
+        let InferenceInput::Text(prompt) = input else {
+            return Err(InferenceError::CapabilityNotSupported("stub model takes text only".into()));
+        };
+        Ok(InferenceOutput::Generation(GenerationResult {
+            text: prompt.clone(),
+            tokens_generated: prompt.split_whitespace().count() as u32,
+            finish_reason: FinishReason::Stop,
+        }))
+    }
+
+    async fn unload(&mut self) -> Result<(), InferenceError> {
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Configures a [`TestRuntime`] before it starts.
+pub struct TestRuntimeBuilder {
+    config: RuntimeConfig,
+    models: Vec<(String, Arc<dyn GgufModel>)>,
+}
+
+impl TestRuntimeBuilder {
+    /// Runtime config to start from; `base_path` is always replaced by a
+    /// temporary directory, and an empty `auth_token` by [`TEST_AUTH_TOKEN`].
+    pub fn config(mut self, config: RuntimeConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Register another model alongside the stub.
+    pub fn model(mut self, model_id: impl Into<String>, model: Arc<dyn GgufModel>) -> Self {
+        self.models.push((model_id.into(), model));
+        self
+    }
+
+    /// Start the server and connect an authenticated client.
+    pub async fn spawn(self) -> Result<(TestRuntime, TestClient), TestError> {
+        let dir = tempfile::tempdir()?;
+        let mut config = self.config;
+        config.base_path = dir.path().to_path_buf();
+        if config.auth_token.is_empty() {
+            config.auth_token = TEST_AUTH_TOKEN.to_string();
+        }
+        let token = config.auth_token.clone();
+        let socket_path = dir.path().join(SOCKET_FILE);
+
+        let runtime = Runtime::new(config);
+        for (model_id, model) in self.models {
+            let metadata = ModelMetadata { name: model_id.clone(), size_bytes: model.memory_usage() as u64 };
+            let handle = runtime.model_registry.register(metadata, model.memory_usage()).await;
+            runtime.inference_engine.register_model(model_id, handle, model).await;
+        }
+
+        let handler = Arc::new(runtime.ipc_handler);
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let server = tokio::spawn(server::run_server(
+            socket_path.to_string_lossy().into_owned(),
+            Arc::clone(&handler),
+            runtime.connections,
+            shutdown_rx,
+        ));
+        let test_runtime = TestRuntime {
+            dir,
+            socket_path,
+            token,
+            handler,
+            engine: runtime.inference_engine,
+            registry: runtime.model_registry,
+            metrics_store: runtime.metrics_store,
+            shutdown_tx,
+            server,
+        };
+
+        let deadline = tokio::time::Instant::now() + READY_TIMEOUT;
+        while UnixStream::connect(&test_runtime.socket_path).await.is_err() {
+            if tokio::time::Instant::now() >= deadline {
+                return Err(TestError::NotReady(READY_TIMEOUT));
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let client = test_runtime.client().await?;
+        Ok((test_runtime, client))
+    }
+}
+
+/// A runtime serving IPC on a temporary socket.
+pub struct TestRuntime {
+    dir: TempDir,
+    socket_path: PathBuf,
+    token: String,
+    handler: Arc<IpcHandler>,
+    engine: Arc<InferenceEngine>,
+    registry: Arc<ModelRegistry>,
+    metrics_store: Arc<MetricsStore>,
+    shutdown_tx: watch::Sender<bool>,
+    server: JoinHandle<Result<(), ServerError>>,
+}
+
+impl TestRuntime {
+    /// Start a default runtime with the stub model.
+    pub async fn spawn() -> Result<(Self, TestClient), TestError> {
+        Self::builder().spawn().await
+    }
+
+    pub fn builder() -> TestRuntimeBuilder {
+        TestRuntimeBuilder {
+            config: RuntimeConfig::default(),
+            models: vec![(STUB_MODEL.to_string(), Arc::new(StubModel::new(STUB_MODEL)))],
+        }
+    }
+
+    /// Another authenticated client on its own connection.
+    pub async fn client(&self) -> Result<TestClient, TestError> {
+        TestClient::connect(&self.socket_path, Some(&self.token)).await
+    }
+
+    pub fn socket_path(&self) -> &Path {
+        &self.socket_path
+    }
+
+    pub fn base_path(&self) -> &Path {
+        self.dir.path()
+    }
+
+    pub fn handler(&self) -> &Arc<IpcHandler> {
+        &self.handler
+    }
+
+    pub fn engine(&self) -> &Arc<InferenceEngine> {
+        &self.engine
+    }
+
+    pub fn registry(&self) -> &Arc<ModelRegistry> {
+        &self.registry
+    }
+
+    pub fn metrics_store(&self) -> &Arc<MetricsStore> {
+        &self.metrics_store
+    }
+
+    /// Stop accepting connections and wait for the server loop to exit.
+    pub async fn shutdown(self) {
+        let _ = self.shutdown_tx.send(true);
+        let _ = self.server.await;
+    }
+}
+
+/// A connection to a [`TestRuntime`], speaking the length-prefixed framing.
+pub struct TestClient {
+    stream: UnixStream,
+    session_id: Option<String>,
+}
+
+impl TestClient {
+    /// Connect, and handshake with `token` when given.
+    pub async fn connect(socket_path: &Path, token: Option<&str>) -> Result<Self, TestError> {
+        let mut client = Self { stream: UnixStream::connect(socket_path).await?, session_id: None };
+        if let Some(token) = token {
+            let handshake = IpcMessage::Handshake { token: token.to_string(), protocol_version: None };
+            match client.request(&handshake).await? {
+                IpcMessage::HandshakeAck { session_id, .. } => client.session_id = Some(session_id),
+                IpcMessage::Error { message, .. } => return Err(TestError::Handshake(message)),
+                other => return Err(TestError::Protocol(format!("unexpected handshake reply: {:?}", other))),
+            }
+        }
+        Ok(client)
+    }
+
+    pub fn session_id(&self) -> Option<&str> {
+        self.session_id.as_deref()
+    }
+
+    /// Send one message and read one reply frame.
+    pub async fn request(&mut self, message: &IpcMessage) -> Result<IpcMessage, TestError> {
+        let bytes = encode_message(message).map_err(|e| TestError::Protocol(e.to_string()))?;
+        self.stream.write_all(&(bytes.len() as u32).to_le_bytes()).await?;
+        self.stream.write_all(&bytes).await?;
+        self.stream.flush().await?;
+
+        let mut len = [0u8; 4];
+        self.stream.read_exact(&mut len).await?;
+        let mut reply = vec![0u8; u32::from_le_bytes(len) as usize];
+        self.stream.read_exact(&mut reply).await?;
+        decode_message(&reply).map_err(|e| TestError::Protocol(e.to_string()))
+    }
+
+    /// Run a non-streaming inference with default parameters.
+    pub async fn infer(&mut self, model_id: &str, prompt: &str) -> Result<InferenceResponse, TestError> {
+        let request = InferenceRequest {
+            request_id: RequestId::generate(),
+            model_id: model_id.to_string(),
+            prompt: prompt.to_string(),
+            parameters: InferenceParams::default(),
+            preset: None,
+            priority: Default::default(),
+            session_affinity_key: None,
+            tools: Vec::new(),
+            images: Vec::new(),
+            template_id: None,
+            variables: Default::default(),
+            context_documents: Vec::new(),
+            cite_sources: false,
+            privacy: Default::default(),
+            idempotency_key: None,
+        };
+        match self.request(&IpcMessage::InferenceRequest(request)).await? {
+            IpcMessage::InferenceResponse(response) => Ok(response),
+            IpcMessage::Error { code, message, .. } => Err(TestError::Protocol(format!("{}: {}", code, message))),
+            other => Err(TestError::Protocol(format!("unexpected reply: {:?}", other))),
+        }
+    }
+}
@@ -0,0 +1,115 @@
+//! Tests for backup and restore of installation state.
+
+use std::path::Path;
+
+use gg_core::backup::{self, BackupError, BackupSources};
+
+const PASSPHRASE: &str = "rebuild-night-passphrase";
+
+fn sources(dir: &Path) -> BackupSources {
+    BackupSources {
+        base_path: dir.join("base"),
+        salt_path: Some(dir.join("salt").join("installation.salt")),
+        config: None,
+    }
+}
+
+fn populated() -> (tempfile::TempDir, BackupSources) {
+    let dir = tempfile::tempdir().unwrap();
+    let sources = sources(dir.path());
+    std::fs::create_dir_all(sources.base_path.join("snapshots")).unwrap();
+    std::fs::create_dir_all(sources.salt_path.as_ref().unwrap().parent().unwrap()).unwrap();
+    std::fs::write(sources.salt_path.as_ref().unwrap(), [7u8; 32]).unwrap();
+    std::fs::write(sources.base_path.join("registry_state.json"), r#"{"models":{}}"#).unwrap();
+    std::fs::write(sources.base_path.join("snapshots").join("nightly.json"), "{}").unwrap();
+    (dir, sources)
+}
+
+fn archive(sources: &BackupSources) -> Vec<u8> {
+    let mut out = Vec::new();
+    backup::create(sources, PASSPHRASE, &mut out).unwrap();
+    out
+}
+
+#[test]
+fn restore_onto_fresh_node_recovers_salt_and_catalog() {
+    let (_src, source) = populated();
+    let archive = archive(&source);
+
+    let fresh = tempfile::tempdir().unwrap();
+    let target = sources(fresh.path());
+    let report = backup::restore(&mut archive.as_slice(), PASSPHRASE, &target, false).unwrap();
+    assert_eq!(report.written.len(), 3);
+
+    let salt_path = target.salt_path.as_ref().unwrap();
+    assert_eq!(std::fs::read(salt_path).unwrap(), vec![7u8; 32]);
+    assert_eq!(
+        std::fs::read_to_string(target.base_path.join("registry_state.json")).unwrap(),
+        r#"{"models":{}}"#
+    );
+    assert!(target.base_path.join("snapshots").join("nightly.json").exists());
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(salt_path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    // Restoring again is a no-op
+    let again = backup::restore(&mut archive.as_slice(), PASSPHRASE, &target, false).unwrap();
+    assert!(again.written.is_empty());
+    assert_eq!(again.unchanged.len(), 3);
+}
+
+#[test]
+fn salt_is_never_stored_in_clear() {
+    let (_src, source) = populated();
+    let archive = archive(&source);
+    assert!(!archive.windows(32).any(|w| w == [7u8; 32]));
+}
+
+#[test]
+fn tampered_entry_is_rejected_before_writing() {
+    let (_src, source) = populated();
+    let mut archive = archive(&source);
+    let at = archive.windows(13).position(|w| w == br#"{"models":{}}"#).unwrap();
+    archive[at + 2] = b'M';
+
+    let fresh = tempfile::tempdir().unwrap();
+    let target = sources(fresh.path());
+    let err = backup::restore(&mut archive.as_slice(), PASSPHRASE, &target, false).unwrap_err();
+    assert!(matches!(err, BackupError::Integrity(name) if name == "registry_state.json"));
+    assert!(!target.base_path.exists());
+}
+
+#[test]
+fn wrong_passphrase_and_weak_passphrase_fail() {
+    let (_src, source) = populated();
+    let mut out = Vec::new();
+    assert!(matches!(backup::create(&source, "short", &mut out), Err(BackupError::WeakPassphrase)));
+
+    let archive = archive(&source);
+    let fresh = tempfile::tempdir().unwrap();
+    let err = backup::restore(&mut archive.as_slice(), "not-the-passphrase", &sources(fresh.path()), false)
+        .unwrap_err();
+    assert!(matches!(err, BackupError::Unwrap));
+}
+
+#[test]
+fn differing_salt_needs_force() {
+    let (_src, source) = populated();
+    let archive = archive(&source);
+
+    let fresh = tempfile::tempdir().unwrap();
+    let target = sources(fresh.path());
+    let salt_path = target.salt_path.clone().unwrap();
+    std::fs::create_dir_all(salt_path.parent().unwrap()).unwrap();
+    std::fs::write(&salt_path, [1u8; 32]).unwrap();
+
+    let err = backup::restore(&mut archive.as_slice(), PASSPHRASE, &target, false).unwrap_err();
+    assert!(matches!(err, BackupError::Conflict(path) if path == salt_path));
+    assert_eq!(std::fs::read(&salt_path).unwrap(), vec![1u8; 32]);
+
+    backup::restore(&mut archive.as_slice(), PASSPHRASE, &target, true).unwrap();
+    assert_eq!(std::fs::read(&salt_path).unwrap(), vec![7u8; 32]);
+}
@@ -86,6 +86,17 @@ fn chaos_ipc_massive_prompt() {
         model_id: "test".to_string(),
         prompt: large_prompt,
         parameters: InferenceParams::default(),
+        preset: None,
+        priority: Default::default(),
+        session_affinity_key: None,
+        tools: Vec::new(),
+        images: Vec::new(),
+        template_id: None,
+        variables: Default::default(),
+        context_documents: Vec::new(),
+        cite_sources: false,
+        privacy: Default::default(),
+        idempotency_key: None,
     };
     let msg = IpcMessage::InferenceRequest(request);
     if let Ok(bytes) = encode_message(&msg) {
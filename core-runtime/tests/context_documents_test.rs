@@ -0,0 +1,57 @@
+//! Tests for RAG context documents on inference requests.
+
+use gg_core::engine::{ContextDocument, InferenceParams};
+use gg_core::ipc::{decode_message, encode_message, InferenceRequest, IpcMessage, RequestId};
+use gg_core::{Runtime, RuntimeConfig};
+
+fn request(documents: Vec<ContextDocument>) -> Vec<u8> {
+    encode_message(&IpcMessage::InferenceRequest(InferenceRequest {
+        request_id: RequestId(3),
+        model_id: "missing-model".into(),
+        prompt: "What is the capital?".into(),
+        parameters: InferenceParams::default(),
+        preset: None,
+        priority: Default::default(),
+        session_affinity_key: None,
+        tools: Vec::new(),
+        images: Vec::new(),
+        template_id: None,
+        variables: Default::default(),
+        context_documents: documents,
+        cite_sources: true,
+        privacy: Default::default(),
+        idempotency_key: None,
+    }))
+    .unwrap()
+}
+
+fn doc(id: &str, len: usize) -> ContextDocument {
+    ContextDocument { id: id.into(), text: "fact ".repeat(len), source: Some(format!("kb://{id}")) }
+}
+
+#[tokio::test]
+async fn documents_are_budgeted_and_validated() {
+    let rt = Runtime::new(RuntimeConfig::default());
+    let handler = &rt.ipc_handler;
+    let handshake = IpcMessage::Handshake { token: String::new(), protocol_version: None };
+    let (_, session) = handler.process(&encode_message(&handshake).unwrap(), None).await.unwrap();
+    let session = session.unwrap();
+
+    let error_for = |bytes: Vec<u8>| match decode_message(&bytes).unwrap() {
+        IpcMessage::InferenceResponse(response) => response.error.unwrap_or_default(),
+        other => panic!("unexpected message: {:?}", other),
+    };
+
+    // Default budgets: 1024 tokens per document, 3072 in total
+    let documents = vec![doc("a", 2000), doc("b", 100), doc("c", 2000), doc("d", 2000)];
+    let (bytes, _) = handler.process(&request(documents), Some(&session)).await.unwrap();
+    assert!(error_for(bytes).contains("missing-model"));
+
+    let counters = rt.metrics_store.snapshot().counters;
+    assert_eq!(counters.get("core_context_documents"), Some(&4));
+    assert_eq!(counters.get("core_context_documents_truncated"), Some(&3));
+    assert_eq!(counters.get("core_context_documents_dropped"), None);
+
+    let (bytes, _) = handler.process(&request(vec![doc("a", 1), doc("a", 1)]), Some(&session)).await.unwrap();
+    assert!(error_for(bytes).contains("Duplicate context document ID: a"));
+}
@@ -0,0 +1,63 @@
+//! Tests for IPC conversations: ownership and turn rollback.
+
+use gg_core::ipc::{
+    decode_message, encode_message, ConversationTurnRequest, InferenceResponse, IpcHandler, IpcMessage,
+    SessionToken,
+};
+use gg_core::{Runtime, RuntimeConfig};
+
+fn runtime() -> Runtime {
+    Runtime::new(RuntimeConfig {
+        tenant_tokens: vec![("alice".into(), "alice-token".into()), ("bob".into(), "bob-token".into())],
+        ..Default::default()
+    })
+}
+
+async fn send(handler: &IpcHandler, message: IpcMessage, session: &SessionToken) -> IpcMessage {
+    let (bytes, _) = handler.process(&encode_message(&message).unwrap(), Some(session)).await.unwrap();
+    decode_message(&bytes).unwrap()
+}
+
+async fn login(handler: &IpcHandler, token: &str) -> SessionToken {
+    let handshake = IpcMessage::Handshake { token: token.into(), protocol_version: None };
+    let (_, session) = handler.process(&encode_message(&handshake).unwrap(), None).await.unwrap();
+    session.unwrap()
+}
+
+async fn turn(handler: &IpcHandler, conversation_id: &str, session: &SessionToken) -> InferenceResponse {
+    let turn = ConversationTurnRequest {
+        conversation_id: conversation_id.into(),
+        request_id: Default::default(),
+        message: "hello".into(),
+        parameters: Default::default(),
+        preset: None,
+        tools: Vec::new(),
+    };
+    match send(handler, IpcMessage::ConversationTurn(turn), session).await {
+        IpcMessage::InferenceResponse(response) => response,
+        other => panic!("unexpected message: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn other_tenants_cannot_use_a_conversation() {
+    let rt = runtime();
+    let handler = &rt.ipc_handler;
+    let alice = login(handler, "alice-token").await;
+    let create = IpcMessage::ConversationCreate { model_id: "missing-model".into() };
+    let IpcMessage::ConversationCreated { conversation_id } = send(handler, create, &alice).await else {
+        panic!("expected a conversation");
+    };
+
+    let bob = login(handler, "bob-token").await;
+    let error = turn(handler, &conversation_id, &bob).await.error.unwrap();
+    assert!(error.contains("Conversation not found"), "unexpected error: {error}");
+
+    // The owner gets through, even from a new session; the failed
+    // inference rolls the turn back so the next one is not blocked.
+    let alice = login(handler, "alice-token").await;
+    for _ in 0..2 {
+        let error = turn(handler, &conversation_id, &alice).await.error.unwrap();
+        assert!(!error.contains("Conversation"), "unexpected error: {error}");
+    }
+}
@@ -0,0 +1,128 @@
+//! Tests for the IPC data purge: admin gating, scope and signed receipts.
+
+use gg_core::ipc::{decode_message, encode_message, IpcHandler, IpcMessage, PurgeTarget, SessionToken};
+use gg_core::security::{PurgeReceipt, PurgeScope};
+use gg_core::telemetry::tenant_label;
+use gg_core::{Runtime, RuntimeConfig};
+
+fn runtime(base: &std::path::Path) -> Runtime {
+    Runtime::new(RuntimeConfig {
+        base_path: base.to_path_buf(),
+        auth_token: "user-token".into(),
+        admin_token: Some("admin-token".into()),
+        tenant_tokens: vec![("alice".into(), "alice-token".into()), ("bob".into(), "bob-token".into())],
+        ..Default::default()
+    })
+}
+
+async fn send(handler: &IpcHandler, message: IpcMessage, session: Option<&SessionToken>) -> IpcMessage {
+    let (bytes, _) = handler.process(&encode_message(&message).unwrap(), session).await.unwrap();
+    decode_message(&bytes).unwrap()
+}
+
+async fn login(handler: &IpcHandler, token: &str) -> SessionToken {
+    let handshake = IpcMessage::Handshake { token: token.into(), protocol_version: None };
+    let (_, session) = handler.process(&encode_message(&handshake).unwrap(), None).await.unwrap();
+    session.unwrap()
+}
+
+async fn create(handler: &IpcHandler, session: &SessionToken) -> String {
+    match send(handler, IpcMessage::ConversationCreate { model_id: "m".into() }, Some(session)).await {
+        IpcMessage::ConversationCreated { conversation_id } => conversation_id,
+        other => panic!("unexpected message: {:?}", other),
+    }
+}
+
+async fn purge(handler: &IpcHandler, target: PurgeTarget, session: &SessionToken) -> PurgeReceipt {
+    match send(handler, IpcMessage::PurgeRequest { target }, Some(session)).await {
+        IpcMessage::PurgeResponse(receipt) => receipt,
+        other => panic!("unexpected message: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn purge_requires_admin() {
+    let dir = tempfile::tempdir().unwrap();
+    let rt = runtime(dir.path());
+    let user = login(&rt.ipc_handler, "user-token").await;
+    let request = IpcMessage::PurgeRequest { target: PurgeTarget::Conversation("c".into()) };
+    assert!(matches!(send(&rt.ipc_handler, request, Some(&user)).await, IpcMessage::Error { code: 403, .. }));
+}
+
+#[tokio::test]
+async fn session_purge_removes_only_that_tenant() {
+    let dir = tempfile::tempdir().unwrap();
+    let rt = runtime(dir.path());
+    let handler = &rt.ipc_handler;
+    let admin = login(handler, "admin-token").await;
+    let alice = login(handler, "alice-token").await;
+    let bob = login(handler, "bob-token").await;
+    create(handler, &alice).await;
+    // A reconnect belongs to the same tenant, so the purge takes it too
+    create(handler, &login(handler, "alice-token").await).await;
+    let kept = create(handler, &bob).await;
+
+    let receipt = purge(handler, PurgeTarget::Session(alice.as_str().into()), &admin).await;
+    assert_eq!(receipt.scope, PurgeScope::Session);
+    assert_eq!(receipt.conversations_removed, 2);
+    assert!(handler.verify_receipt(&receipt));
+    assert!(!receipt.subject_hash.contains(alice.as_str()));
+
+    match send(handler, IpcMessage::ConversationList, Some(&admin)).await {
+        IpcMessage::ConversationListResponse { conversations } => {
+            assert_eq!(conversations.len(), 1);
+            assert_eq!(conversations[0].conversation_id, kept);
+        }
+        other => panic!("unexpected message: {:?}", other),
+    }
+
+    let receipt = purge(handler, PurgeTarget::Tenant(tenant_label("bob")), &admin).await;
+    assert_eq!(receipt.conversations_removed, 1);
+
+    let request = IpcMessage::PurgeRequest { target: PurgeTarget::Session("expired".into()) };
+    assert!(matches!(send(handler, request, Some(&admin)).await, IpcMessage::Error { code: 404, .. }));
+}
+
+/// Usage and conversations are keyed on the same tenant, so either purge
+/// target clears both.
+#[cfg(all(feature = "testing", unix))]
+#[tokio::test]
+async fn purge_clears_usage_and_conversations_together() {
+    use gg_core::testing::{TestClient, TestRuntime, STUB_MODEL};
+
+    let config = RuntimeConfig {
+        admin_token: Some("admin-token".into()),
+        tenant_tokens: vec![("alice".into(), "alice-token".into()), ("bob".into(), "bob-token".into())],
+        ..Default::default()
+    };
+    let (server, _client) = TestRuntime::builder().config(config).spawn().await.unwrap();
+    let handler = server.handler();
+    for token in ["alice-token", "bob-token"] {
+        let mut client = TestClient::connect(server.socket_path(), Some(token)).await.unwrap();
+        assert_eq!(client.infer(STUB_MODEL, "hello").await.unwrap().error, None);
+        create(handler, &login(handler, token).await).await;
+    }
+    let admin = login(handler, "admin-token").await;
+
+    let receipt = purge(handler, PurgeTarget::Tenant(tenant_label("alice")), &admin).await;
+    assert_eq!((receipt.usage_records_removed, receipt.conversations_removed), (1, 1));
+
+    let bob = login(handler, "bob-token").await;
+    let receipt = purge(handler, PurgeTarget::Session(bob.as_str().into()), &admin).await;
+    assert_eq!((receipt.usage_records_removed, receipt.conversations_removed), (1, 1));
+    server.shutdown().await;
+}
+
+#[tokio::test]
+async fn receipts_verify_across_restart() {
+    let dir = tempfile::tempdir().unwrap();
+    let rt = runtime(dir.path());
+    let admin = login(&rt.ipc_handler, "admin-token").await;
+    let mut receipt = purge(&rt.ipc_handler, PurgeTarget::Conversation("gone".into()), &admin).await;
+    assert_eq!(receipt.conversations_removed, 0);
+
+    let restarted = runtime(dir.path());
+    assert!(restarted.ipc_handler.verify_receipt(&receipt));
+    receipt.conversations_removed = 3;
+    assert!(!restarted.ipc_handler.verify_receipt(&receipt));
+}
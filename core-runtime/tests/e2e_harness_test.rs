@@ -0,0 +1,78 @@
+//! End-to-end tests through the in-process server fixture.
+
+#![cfg(all(feature = "testing", unix))]
+
+use gg_core::ipc::{IpcMessage, ResponseErrorCode};
+use gg_core::security::{PIIType, PiiBlockConfig};
+use gg_core::telemetry::tenant_label;
+use gg_core::testing::{TestClient, TestRuntime, STUB_MODEL};
+use gg_core::RuntimeConfig;
+
+#[tokio::test]
+async fn stub_inference_over_socket() {
+    let (server, mut client) = TestRuntime::spawn().await.unwrap();
+    assert!(client.session_id().is_some());
+
+    let response = client.infer(STUB_MODEL, "hello over the wire").await.unwrap();
+    assert_eq!(response.error, None);
+    assert_eq!(response.output, "hello over the wire");
+    assert_eq!(response.tokens_generated, 4);
+
+    // Each client gets its own session
+    let mut other = server.client().await.unwrap();
+    assert_ne!(other.session_id(), client.session_id());
+    assert!(other.infer("missing", "hi").await.unwrap().error.is_some());
+    server.shutdown().await;
+}
+
+#[tokio::test]
+async fn unauthenticated_client_is_refused() {
+    let (server, _client) = TestRuntime::spawn().await.unwrap();
+    let mut anonymous = TestClient::connect(server.socket_path(), None).await.unwrap();
+    let err = anonymous.infer(STUB_MODEL, "hi").await.unwrap_err();
+    assert!(err.to_string().contains("Not authenticated"), "unexpected error: {err}");
+
+    assert!(TestClient::connect(server.socket_path(), Some("wrong-token")).await.is_err());
+    server.shutdown().await;
+}
+
+#[tokio::test]
+async fn pii_block_fails_response_without_leaking() {
+    let config = RuntimeConfig {
+        pii_block: Some(PiiBlockConfig { types: vec![PIIType::SSN], ..Default::default() }),
+        ..Default::default()
+    };
+    let (server, mut client) = TestRuntime::builder().config(config).spawn().await.unwrap();
+
+    let response = client.infer(STUB_MODEL, "my number is 123-45-6789").await.unwrap();
+    assert_eq!(response.error_code, Some(ResponseErrorCode::PolicyBlocked));
+    assert!(response.output.is_empty());
+    assert!(!response.error.unwrap().contains("6789"));
+    assert_eq!(server.metrics_store().snapshot().counters.get("core_pii_blocked_ssn"), Some(&1));
+
+    let clean = client.infer(STUB_MODEL, "nothing to see here").await.unwrap();
+    assert_eq!(clean.error, None);
+    server.shutdown().await;
+}
+
+#[tokio::test]
+async fn usage_follows_tenant_across_reconnects() {
+    let config = RuntimeConfig {
+        admin_token: Some("admin-token".into()),
+        tenant_tokens: vec![("acme".into(), "acme-token".into())],
+        ..Default::default()
+    };
+    let (server, _client) = TestRuntime::builder().config(config).spawn().await.unwrap();
+    for prompt in ["first session", "second session"] {
+        let mut acme = TestClient::connect(server.socket_path(), Some("acme-token")).await.unwrap();
+        assert_eq!(acme.infer(STUB_MODEL, prompt).await.unwrap().error, None);
+    }
+
+    let mut admin = TestClient::connect(server.socket_path(), Some("admin-token")).await.unwrap();
+    let IpcMessage::UsageExportResponse(export) = admin.request(&IpcMessage::UsageExportRequest).await.unwrap() else {
+        panic!("expected usage export");
+    };
+    let acme = export.tenants.iter().find(|t| t.tenant == tenant_label("acme")).expect("acme usage");
+    assert_eq!(acme.requests, 2);
+    server.shutdown().await;
+}
@@ -0,0 +1,64 @@
+//! Tests for catalog-defined fallback model chains.
+
+use gg_core::engine::InferenceParams;
+use gg_core::ipc::{decode_message, encode_message, InferenceRequest, IpcMessage, RequestId};
+use gg_core::models::{RegistryPersistence, RegistryState};
+use gg_core::{Runtime, RuntimeConfig};
+
+fn request(model: &str) -> Vec<u8> {
+    encode_message(&IpcMessage::InferenceRequest(InferenceRequest {
+        request_id: RequestId(1),
+        model_id: model.into(),
+        prompt: "Hello".into(),
+        parameters: InferenceParams::default(),
+        preset: None,
+        priority: Default::default(),
+        session_affinity_key: None,
+        tools: Vec::new(),
+        images: Vec::new(),
+        template_id: None,
+        variables: Default::default(),
+        context_documents: Vec::new(),
+        cite_sources: false,
+        privacy: Default::default(),
+        idempotency_key: None,
+    }))
+    .unwrap()
+}
+
+#[tokio::test]
+async fn unavailable_primary_moves_down_the_chain() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut state = RegistryState::default();
+    state.fallbacks.set("prod-13b", vec!["prod-7b".into(), "tiny".into()]).unwrap();
+    RegistryPersistence::new(dir.path().join("registry_state.json")).save(&state).unwrap();
+
+    let rt = Runtime::new(RuntimeConfig { base_path: dir.path().to_path_buf(), ..Default::default() });
+    let handler = &rt.ipc_handler;
+    let handshake = IpcMessage::Handshake { token: String::new(), protocol_version: None };
+    let (_, session) = handler.process(&encode_message(&handshake).unwrap(), None).await.unwrap();
+    let session = session.unwrap();
+
+    // Nothing is loaded: the chain ends at `tiny`, which the engine reports
+    let (bytes, _) = handler.process(&request("prod-13b"), Some(&session)).await.unwrap();
+    match decode_message(&bytes).unwrap() {
+        IpcMessage::InferenceResponse(response) => {
+            let error = response.error.unwrap_or_default();
+            assert!(error.contains("tiny"), "unexpected error: {error}");
+        }
+        other => panic!("unexpected message: {:?}", other),
+    }
+
+    // Models without a chain are untouched
+    let (bytes, _) = handler.process(&request("other"), Some(&session)).await.unwrap();
+    match decode_message(&bytes).unwrap() {
+        IpcMessage::InferenceResponse(response) => {
+            assert!(response.error.unwrap_or_default().contains("other"));
+        }
+        other => panic!("unexpected message: {:?}", other),
+    }
+
+    let counters = rt.metrics_store.snapshot().counters;
+    assert_eq!(counters.get("core_fallback_requests"), Some(&1));
+    assert_eq!(counters.get("core_fallback_requests_unavailable"), Some(&1));
+}
@@ -14,7 +14,13 @@ use gg_core::ffi::{
     core_runtime_create, core_runtime_destroy,
     CoreConfig, CoreErrorCode, CoreHealthReport, CoreHealthState,
     CoreInferenceParams, CoreInferenceResult, CoreModelMetadata,
+    core_abi_version, core_inference_params_default, CORE_ABI_VERSION,
+    CORE_INFERENCE_PARAMS_VERSION, core_subscribe_events, core_unsubscribe_events, CoreEvent,
+    CoreEventKind, CoreLogLevel,
 };
+use gg_core::telemetry::{emit_event, RuntimeEvent, RuntimeEventKind};
+use std::ffi::c_void;
+use std::sync::atomic::{AtomicU32, Ordering};
 
 // ============================================================================
 // Error Code Tests
@@ -142,12 +148,29 @@ fn test_inference_params_default_values() {
     assert_eq!(params.top_k, 40);
     assert!(!params.stream);
     assert_eq!(params.timeout_ms, 0);
+    assert_eq!(params.struct_version, CORE_INFERENCE_PARAMS_VERSION);
+}
+
+#[test]
+fn test_inference_params_default_via_c_api() {
+    let mut params = CoreInferenceParams::default();
+    params.struct_version = 0;
+    core_inference_params_default(&mut params);
+    assert_eq!(params.struct_version, CORE_INFERENCE_PARAMS_VERSION);
+    core_inference_params_default(ptr::null_mut());
+}
+
+#[test]
+fn test_abi_version_reports_major_one() {
+    assert_eq!(core_abi_version(), CORE_ABI_VERSION);
+    assert_eq!(core_abi_version() >> 16, 1);
 }
 
 #[test]
 fn test_inference_params_repr_c() {
     // Verify struct is correctly laid out for C
     let params = CoreInferenceParams {
+        struct_version: CORE_INFERENCE_PARAMS_VERSION,
         max_tokens: 512,
         temperature: 0.5,
         top_p: 0.95,
@@ -161,6 +184,38 @@ fn test_inference_params_repr_c() {
     assert_eq!(params.timeout_ms, 30000);
 }
 
+// ============================================================================
+// Event Subscription Tests
+// ============================================================================
+
+unsafe extern "C" fn count_evictions(user_data: *mut c_void, event: *const CoreEvent) {
+    let event = &*event;
+    let subject = CStr::from_ptr(event.subject).to_str().unwrap();
+    if event.kind == CoreEventKind::ModelEvicted && subject == "ffi-event-test" {
+        (*(user_data as *const AtomicU32)).fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn test_event_subscription_filters_by_level() {
+    let hits = AtomicU32::new(0);
+    let user_data = &hits as *const AtomicU32 as *mut c_void;
+    let mut id = 0u64;
+    let code = unsafe {
+        core_subscribe_events(Some(count_evictions), CoreLogLevel::Warn, user_data, &mut id)
+    };
+    assert_eq!(code, CoreErrorCode::Ok);
+
+    let evicted = |level| RuntimeEvent::new(RuntimeEventKind::ModelEvicted, level, "ffi-event-test", "");
+    emit_event(evicted(tracing::Level::INFO));
+    emit_event(evicted(tracing::Level::WARN));
+    assert_eq!(core_unsubscribe_events(id), CoreErrorCode::Ok);
+    emit_event(evicted(tracing::Level::ERROR));
+
+    assert_eq!(hits.load(Ordering::SeqCst), 1);
+    assert_eq!(core_unsubscribe_events(id), CoreErrorCode::InvalidParams);
+}
+
 // ============================================================================
 // CoreInferenceResult Tests
 // ============================================================================
@@ -125,3 +125,14 @@ fn filter_multiple_blocklist_entries() {
     let result = filter.filter("bad1 and bad2 and bad3").unwrap();
     assert_eq!(result, "[X] and [X] and [X]");
 }
+
+#[test]
+fn filter_truncates_multibyte_output_on_char_boundary() {
+    let config = FilterConfig {
+        max_output_chars: 3,
+        ..Default::default()
+    };
+    let filter = OutputFilter::new(config).unwrap();
+
+    assert_eq!(filter.filter("héé€ tail").unwrap(), "héé");
+}
@@ -40,6 +40,7 @@ fn test_gpu_device_has_memory() {
         total_memory: 1000,
         available_memory: 500,
         compute_capability: Some((8, 0)),
+        sharing: Default::default(),
     };
 
     assert!(device.has_memory(400));
@@ -171,6 +171,7 @@ fn create_test_gpu_devices() -> Vec<Arc<GpuDevice>> {
             total_memory: 24_000_000_000,
             available_memory: 20_000_000_000,
             compute_capability: Some((8, 9)),
+            sharing: Default::default(),
         }),
         Arc::new(GpuDevice {
             backend: GpuBackend::Cuda,
@@ -179,6 +180,7 @@ fn create_test_gpu_devices() -> Vec<Arc<GpuDevice>> {
             total_memory: 24_000_000_000,
             available_memory: 22_000_000_000,
             compute_capability: Some((8, 9)),
+            sharing: Default::default(),
         }),
     ]
 }
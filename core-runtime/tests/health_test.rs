@@ -34,6 +34,15 @@ fn test_not_ready_when_stopped() {
     assert!(!checker.is_ready(ShutdownState::Stopped, 0, 0));
 }
 
+#[test]
+fn test_observe_reports_only_transitions() {
+    let checker = HealthChecker::default();
+    assert!(!checker.observe(HealthState::Healthy));
+    assert!(!checker.observe(HealthState::Healthy));
+    assert!(checker.observe(HealthState::Degraded));
+    assert!(checker.observe(HealthState::Healthy));
+}
+
 #[test]
 fn test_ready_respects_model_requirement() {
     let config = HealthConfig {
@@ -0,0 +1,65 @@
+//! Tests for the IPC heap profile request: admin gating and allocator presence.
+
+use gg_core::ipc::{decode_message, encode_message, IpcHandler, IpcMessage, SessionToken};
+use gg_core::telemetry::{HeapReport, HeapSite};
+use gg_core::{Runtime, RuntimeConfig};
+
+fn runtime() -> Runtime {
+    Runtime::new(RuntimeConfig {
+        auth_token: "user-token".into(),
+        admin_token: Some("admin-token".into()),
+        ..Default::default()
+    })
+}
+
+async fn login(handler: &IpcHandler, token: &str) -> SessionToken {
+    let handshake = IpcMessage::Handshake { token: token.into(), protocol_version: None };
+    let (_, session) = handler.process(&encode_message(&handshake).unwrap(), None).await.unwrap();
+    session.unwrap()
+}
+
+async fn heap(handler: &IpcHandler, session: &SessionToken) -> IpcMessage {
+    let request = encode_message(&IpcMessage::HeapProfileRequest { top: 10 }).unwrap();
+    let (bytes, _) = handler.process(&request, Some(session)).await.unwrap();
+    decode_message(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn heap_profile_requires_auth_and_admin() {
+    let rt = runtime();
+    let request = encode_message(&IpcMessage::HeapProfileRequest { top: 10 }).unwrap();
+    assert!(rt.ipc_handler.process(&request, None).await.is_err());
+
+    let user = login(&rt.ipc_handler, "user-token").await;
+    assert!(matches!(heap(&rt.ipc_handler, &user).await, IpcMessage::Error { code: 403, .. }));
+}
+
+#[tokio::test]
+async fn heap_profile_unavailable_without_tracking_allocator() {
+    let rt = runtime();
+    let admin = login(&rt.ipc_handler, "admin-token").await;
+    assert!(matches!(heap(&rt.ipc_handler, &admin).await, IpcMessage::Error { code: 501, .. }));
+}
+
+#[test]
+fn heap_profile_response_roundtrip() {
+    let report = HeapReport {
+        active: true,
+        live_bytes: 4096,
+        sites: vec![HeapSite {
+            tag: "kv_cache".into(),
+            live_bytes: 4096,
+            live_allocations: 2,
+            total_bytes: 8192,
+            total_allocations: 4,
+        }],
+    };
+    let bytes = encode_message(&IpcMessage::HeapProfileResponse(report)).unwrap();
+    match decode_message(&bytes).unwrap() {
+        IpcMessage::HeapProfileResponse(decoded) => {
+            assert_eq!(decoded.live_bytes, 4096);
+            assert_eq!(decoded.sites[0].tag, "kv_cache");
+        }
+        other => panic!("unexpected message: {:?}", other),
+    }
+}
@@ -21,7 +21,19 @@ fn ipc_request_roundtrip() {
             top_k: 50,
             stream: false,
             timeout_ms: None,
+            sampler: None,
         },
+        preset: None,
+        priority: Default::default(),
+        session_affinity_key: None,
+        tools: Vec::new(),
+        images: Vec::new(),
+        template_id: None,
+        variables: Default::default(),
+        context_documents: Vec::new(),
+        cite_sources: false,
+        privacy: Default::default(),
+        idempotency_key: None,
     };
 
     let message = IpcMessage::InferenceRequest(request.clone());
@@ -115,6 +127,7 @@ fn inference_params_serialization() {
         top_k: 40,
         stream: false,
         timeout_ms: None,
+        sampler: None,
     };
 
     // Params should be serializable
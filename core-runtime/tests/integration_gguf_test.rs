@@ -54,9 +54,10 @@ fn stop_reason_variants_correct() {
         FinishReason::MaxTokens,
         FinishReason::Timeout,
         FinishReason::ContentFiltered,
+        FinishReason::Degenerated,
     ];
 
-    assert_eq!(reasons.len(), 4, "Should have 4 finish reasons");
+    assert_eq!(reasons.len(), 5, "Should have 5 finish reasons");
 }
 
 #[test]
@@ -68,6 +69,7 @@ fn inference_params_valid_values() {
         top_k: 50,
         stream: false,
         timeout_ms: None,
+        sampler: None,
     };
 
     // Temperature should be usable even if high
@@ -84,6 +86,7 @@ fn inference_params_typical_defaults() {
         top_k: 40,
         stream: false,
         timeout_ms: None,
+        sampler: None,
     };
 
     assert!(params.max_tokens > 0);
@@ -138,6 +141,7 @@ fn max_tokens_param_respected() {
         top_k: 1,
         stream: false,
         timeout_ms: None,
+        sampler: None,
     };
 
     assert_eq!(params.max_tokens, 10);
@@ -226,7 +226,7 @@ mod windows_server_tests {
         let _ = tokio::time::timeout(Duration::from_secs(2), server).await;
     }
 
-    /// Sequential connections reuse the pipe (Windows creates one instance at a time).
+    /// Sequential connections are served as pool instances are replaced.
     #[tokio::test]
     async fn test_server_sequential_connections() {
         let pipe = unique_pipe_name("sequential");
@@ -329,6 +329,183 @@ mod windows_server_tests {
         drop(client);
         let _ = tokio::time::timeout(Duration::from_secs(2), server).await;
     }
+
+    /// Open a client, retrying while every pipe instance is busy.
+    async fn open_client(pipe: &str) -> tokio::net::windows::named_pipe::NamedPipeClient {
+        const ERROR_PIPE_BUSY: i32 = 231;
+        loop {
+            match ClientOptions::new().open(pipe) {
+                Ok(client) => return client,
+                Err(e) if e.raw_os_error() == Some(ERROR_PIPE_BUSY) => {
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                }
+                Err(e) => panic!("open {}: {}", pipe, e),
+            }
+        }
+    }
+
+    /// Many clients connecting at once are all served.
+    #[tokio::test]
+    async fn test_server_concurrent_clients() {
+        let pipe = unique_pipe_name("concurrent");
+        let pool = Arc::new(ConnectionPool::new(ConnectionConfig {
+            max_connections: 32,
+        }));
+
+        let (tx, rx) = tokio::sync::watch::channel(false);
+        let sp = pipe.clone();
+        let sh = test_handler();
+        let sc = Arc::clone(&pool);
+        let server = tokio::spawn(async move {
+            gg_core::ipc::server::run_server(sp, sh, sc, rx).await
+        });
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let clients: Vec<_> = (0..24)
+            .map(|_| {
+                let pipe = pipe.clone();
+                tokio::spawn(async move {
+                    let mut c = open_client(&pipe).await;
+                    let req = br#"{"type":"health_check","check_type":"Liveness"}"#;
+                    write_frame(&mut c, req).await;
+                    let resp = read_frame(&mut c).await;
+                    String::from_utf8_lossy(&resp).contains("health_response")
+                })
+            })
+            .collect();
+        for client in clients {
+            assert!(client.await.unwrap());
+        }
+
+        let _ = tx.send(true);
+        let _ = tokio::time::timeout(Duration::from_secs(2), server).await;
+    }
+
+    /// Over-limit clients are accepted and closed, as on Unix.
+    #[tokio::test]
+    async fn test_server_rejects_over_limit() {
+        let pipe = unique_pipe_name("limit");
+        let pool = Arc::new(ConnectionPool::new(ConnectionConfig {
+            max_connections: 2,
+        }));
+
+        let (tx, rx) = tokio::sync::watch::channel(false);
+        let sp = pipe.clone();
+        let sh = test_handler();
+        let sc = Arc::clone(&pool);
+        let server = tokio::spawn(async move {
+            gg_core::ipc::server::run_server(sp, sh, sc, rx).await
+        });
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let _held: Vec<_> = hold_clients(&pipe, 2).await;
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(pool.active_count(), 2);
+
+        let mut rejected = open_client(&pipe).await;
+        let mut buf = [0u8; 4];
+        assert!(rejected.read_exact(&mut buf).await.is_err());
+
+        let _ = tx.send(true);
+        let _ = tokio::time::timeout(Duration::from_secs(2), server).await;
+    }
+
+    async fn hold_clients(
+        pipe: &str,
+        n: usize,
+    ) -> Vec<tokio::net::windows::named_pipe::NamedPipeClient> {
+        let mut held = Vec::new();
+        for _ in 0..n {
+            held.push(open_client(pipe).await);
+        }
+        held
+    }
+}
+
+#[cfg(unix)]
+mod unix_server_tests {
+    use super::*;
+    use tokio::net::UnixStream;
+
+    /// Socket path inside a private (0700) directory; the server refuses
+    /// world-writable parents such as `/tmp`.
+    fn unique_socket_path(label: &str) -> (tempfile::TempDir, String) {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::set_permissions(dir.path(), std::fs::Permissions::from_mode(0o700)).unwrap();
+        let path = dir.path().join(format!("{}.sock", label));
+        (dir, path.to_string_lossy().into_owned())
+    }
+
+    async fn start(
+        path: &str,
+        max_connections: usize,
+    ) -> (Arc<ConnectionPool>, tokio::sync::watch::Sender<bool>) {
+        let pool = Arc::new(ConnectionPool::new(ConnectionConfig { max_connections }));
+        let (tx, rx) = tokio::sync::watch::channel(false);
+        let sp = path.to_string();
+        let sh = test_handler();
+        let sc = Arc::clone(&pool);
+        tokio::spawn(async move { gg_core::ipc::server::run_server(sp, sh, sc, rx).await });
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        (pool, tx)
+    }
+
+    /// Many clients connecting at once are all served.
+    #[tokio::test]
+    async fn test_server_concurrent_clients() {
+        let (_dir, path) = unique_socket_path("concurrent");
+        let (_pool, tx) = start(&path, 32).await;
+
+        let clients: Vec<_> = (0..24)
+            .map(|_| {
+                let path = path.clone();
+                tokio::spawn(async move {
+                    let mut c = UnixStream::connect(&path).await.unwrap();
+                    let req = br#"{"type":"health_check","check_type":"Liveness"}"#;
+                    write_frame(&mut c, req).await;
+                    let resp = read_frame(&mut c).await;
+                    String::from_utf8_lossy(&resp).contains("health_response")
+                })
+            })
+            .collect();
+        for client in clients {
+            assert!(client.await.unwrap());
+        }
+        let _ = tx.send(true);
+    }
+
+    /// Over-limit clients are accepted and closed.
+    #[tokio::test]
+    async fn test_server_rejects_over_limit() {
+        let (_dir, path) = unique_socket_path("limit");
+        let (pool, tx) = start(&path, 2).await;
+
+        let _a = UnixStream::connect(&path).await.unwrap();
+        let _b = UnixStream::connect(&path).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(pool.active_count(), 2);
+
+        let mut rejected = UnixStream::connect(&path).await.unwrap();
+        let mut buf = [0u8; 4];
+        assert!(rejected.read_exact(&mut buf).await.is_err());
+        let _ = tx.send(true);
+    }
+
+    /// The socket is created owner-only by default.
+    #[tokio::test]
+    async fn test_server_socket_is_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let (_dir, path) = unique_socket_path("mode");
+        let (_pool, tx) = start(&path, 4).await;
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode, 0o600);
+        let _ = tx.send(true);
+    }
 }
 
 // ---------------------------------------------------------------------------
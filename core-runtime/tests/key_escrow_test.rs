@@ -0,0 +1,83 @@
+//! Tests for recovering escrowed model keys after a machine identity change.
+//! Escrow is refused in fips-strict builds.
+#![cfg(not(feature = "fips-strict"))]
+
+use gg_core::security::escrow::{self, EscrowError, RecoveryPrivateKey};
+use gg_core::security::ModelEncryption;
+use sha2::{Digest, Sha256};
+
+const SALT: &[u8; 16] = b"installation-slt";
+
+#[test]
+fn recover_rewraps_model_to_new_identity() {
+    let dir = tempfile::tempdir().unwrap();
+    let plain = dir.path().join("plain.gguf");
+    let model = dir.path().join("model.gguf");
+    std::fs::write(&plain, b"GGUF model weights").unwrap();
+
+    let recovery = RecoveryPrivateKey::generate();
+    let old_identity = ModelEncryption::from_password("old-host-svc", SALT);
+    old_identity.encrypt_file(&plain, &model).unwrap();
+    escrow::write_escrow(&model, &old_identity, &recovery.public_key()).unwrap();
+    std::fs::write(dir.path().join("model.gguf.sha256"), "stale  model.gguf\n").unwrap();
+
+    let new_identity = ModelEncryption::from_password("new-host-svc", SALT);
+    let decrypted = dir.path().join("decrypted.gguf");
+    assert!(new_identity.decrypt_file(&model, &decrypted).is_err());
+
+    escrow::rewrap_artifact(&model, &recovery, &new_identity).unwrap();
+
+    new_identity.decrypt_file(&model, &decrypted).unwrap();
+    assert_eq!(std::fs::read(&decrypted).unwrap(), b"GGUF model weights");
+    assert!(old_identity.decrypt_file(&model, &decrypted).is_err());
+
+    // The escrow now holds the new key, so a second rebuild is recoverable too
+    let reopened = escrow::read_escrow(&model).unwrap().open(&recovery).unwrap();
+    reopened.decrypt_file(&model, &decrypted).unwrap();
+
+    let digest = hex::encode(Sha256::digest(std::fs::read(&model).unwrap()));
+    let checksum = std::fs::read_to_string(dir.path().join("model.gguf.sha256")).unwrap();
+    assert_eq!(checksum, format!("{}  model.gguf\n", digest));
+
+    // No plaintext temp files left behind
+    let mut names: Vec<_> = std::fs::read_dir(dir.path())
+        .unwrap()
+        .map(|e| e.unwrap().file_name().into_string().unwrap())
+        .collect();
+    names.sort();
+    assert_eq!(names, ["decrypted.gguf", "model.gguf", "model.gguf.escrow.json", "model.gguf.sha256", "plain.gguf"]);
+}
+
+#[test]
+fn recover_with_other_recovery_key_leaves_model_untouched() {
+    let dir = tempfile::tempdir().unwrap();
+    let plain = dir.path().join("plain.gguf");
+    let model = dir.path().join("model.gguf");
+    std::fs::write(&plain, b"weights").unwrap();
+
+    let key = ModelEncryption::from_password("host-svc", SALT);
+    key.encrypt_file(&plain, &model).unwrap();
+    escrow::write_escrow(&model, &key, &RecoveryPrivateKey::generate().public_key()).unwrap();
+    let before = std::fs::read(&model).unwrap();
+
+    let err = escrow::rewrap_artifact(&model, &RecoveryPrivateKey::generate(), &key).unwrap_err();
+    assert!(matches!(err, EscrowError::KeyMismatch { .. }));
+    assert_eq!(std::fs::read(&model).unwrap(), before);
+}
+
+#[test]
+fn recovery_keys_roundtrip_through_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("recovery.key");
+    let key = RecoveryPrivateKey::generate();
+    key.save(&path).unwrap();
+    assert_eq!(RecoveryPrivateKey::load(&path).unwrap().public_key(), key.public_key());
+
+    // Never overwrites an existing key
+    assert!(key.save(&path).is_err());
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        assert_eq!(std::fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+    }
+}
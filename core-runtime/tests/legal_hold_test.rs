@@ -0,0 +1,111 @@
+//! Tests for IPC legal hold control: admin gating and hold lifecycle.
+
+use gg_core::engine::InferenceParams;
+use gg_core::ipc::{
+    decode_message, encode_message, InferenceRequest, IpcHandler, IpcMessage, LegalHoldAction, RequestId, SessionToken,
+};
+use gg_core::security::legal_hold;
+use gg_core::telemetry::tenant_label;
+use gg_core::{Runtime, RuntimeConfig};
+
+fn runtime(base: &std::path::Path) -> Runtime {
+    Runtime::new(RuntimeConfig {
+        base_path: base.to_path_buf(),
+        auth_token: "user-token".into(),
+        admin_token: Some("admin-token".into()),
+        tenant_tokens: vec![("acme".into(), "acme-token".into())],
+        ..Default::default()
+    })
+}
+
+async fn send(handler: &IpcHandler, action: LegalHoldAction, session: &SessionToken) -> IpcMessage {
+    let message = IpcMessage::LegalHoldRequest { action };
+    let (bytes, _) = handler.process(&encode_message(&message).unwrap(), Some(session)).await.unwrap();
+    decode_message(&bytes).unwrap()
+}
+
+async fn login(handler: &IpcHandler, token: &str) -> SessionToken {
+    let handshake = IpcMessage::Handshake { token: token.into(), protocol_version: None };
+    let (_, session) = handler.process(&encode_message(&handshake).unwrap(), None).await.unwrap();
+    session.unwrap()
+}
+
+async fn infer(handler: &IpcHandler, id: u64, session: &SessionToken) {
+    let request = IpcMessage::InferenceRequest(InferenceRequest {
+        request_id: RequestId(id.into()),
+        model_id: "missing-model".into(),
+        prompt: format!("prompt {}", id),
+        parameters: InferenceParams::default(),
+        preset: None,
+        priority: Default::default(),
+        session_affinity_key: None,
+        tools: Vec::new(),
+        images: Vec::new(),
+        template_id: None,
+        variables: Default::default(),
+        context_documents: Vec::new(),
+        cite_sources: false,
+        privacy: Default::default(),
+        idempotency_key: None,
+    });
+    handler.process(&encode_message(&request).unwrap(), Some(session)).await.unwrap();
+}
+
+fn activate(tenant: &str) -> LegalHoldAction {
+    LegalHoldAction::Activate { tenant: tenant.into(), days: Some(30), reason: "case 42".into() }
+}
+
+#[tokio::test]
+async fn legal_hold_requires_admin() {
+    let dir = tempfile::tempdir().unwrap();
+    let rt = runtime(dir.path());
+    let user = login(&rt.ipc_handler, "user-token").await;
+    let response = send(&rt.ipc_handler, activate("tenant-abc"), &user).await;
+    assert!(matches!(response, IpcMessage::Error { code: 403, .. }));
+}
+
+#[tokio::test]
+async fn hold_activate_and_release() {
+    let dir = tempfile::tempdir().unwrap();
+    let rt = runtime(dir.path());
+    let handler = &rt.ipc_handler;
+    let admin = login(handler, "admin-token").await;
+
+    let IpcMessage::LegalHoldResponse { holds } = send(handler, activate("tenant-abc"), &admin).await else {
+        panic!("expected legal hold response");
+    };
+    assert_eq!(holds.len(), 1);
+    assert!(holds[0].ended_at.is_none());
+    assert!(dir.path().join("legal_hold.key").exists());
+
+    let release = LegalHoldAction::Release { tenant: "tenant-abc".into() };
+    let IpcMessage::LegalHoldResponse { holds } = send(handler, release.clone(), &admin).await else {
+        panic!("expected legal hold response");
+    };
+    assert!(holds[0].ended_at.is_some());
+    assert!(matches!(send(handler, release, &admin).await, IpcMessage::Error { code: 404, .. }));
+    assert!(matches!(send(handler, activate("../x"), &admin).await, IpcMessage::Error { code: 400, .. }));
+}
+
+#[tokio::test]
+async fn hold_follows_tenant_across_reconnects() {
+    let dir = tempfile::tempdir().unwrap();
+    let rt = runtime(dir.path());
+    let handler = &rt.ipc_handler;
+    let admin = login(handler, "admin-token").await;
+    let tenant = tenant_label("acme");
+    send(handler, activate(&tenant), &admin).await;
+
+    let first = login(handler, "acme-token").await;
+    infer(handler, 1, &first).await;
+    let second = login(handler, "acme-token").await;
+    assert_ne!(first, second);
+    infer(handler, 2, &second).await;
+    let other = login(handler, "user-token").await;
+    infer(handler, 3, &other).await;
+
+    let store = legal_hold::default_store_dir(dir.path());
+    let records = legal_hold::export(&store, &legal_hold::default_key_path(dir.path()), &tenant).unwrap();
+    let ids: Vec<&str> = records.iter().map(|r| r.request_id.as_str()).collect();
+    assert_eq!(ids, ["1", "2"]);
+}
@@ -157,6 +157,8 @@ fn test_metrics_response_roundtrip() {
         gauges,
         histograms,
         bucketed_histograms: std::collections::HashMap::new(),
+        rates: std::collections::HashMap::new(),
+        resource: None,
     };
     let message = IpcMessage::MetricsResponse(snapshot);
 
@@ -185,6 +187,8 @@ fn test_metrics_response_empty_snapshot() {
         gauges: std::collections::HashMap::new(),
         histograms: std::collections::HashMap::new(),
         bucketed_histograms: std::collections::HashMap::new(),
+        rates: std::collections::HashMap::new(),
+        resource: None,
     };
     let message = IpcMessage::MetricsResponse(snapshot);
 
@@ -200,3 +204,86 @@ fn test_metrics_response_empty_snapshot() {
         _ => panic!("Expected MetricsResponse message"),
     }
 }
+
+#[test]
+fn test_snapshot_includes_derived_rates() {
+    let store = MetricsStore::new();
+    store.record_request(true, 20);
+    store.record_request(false, 0);
+
+    let snapshot = store.snapshot();
+    assert_eq!(snapshot.counters.get("core_requests_total"), Some(&2));
+    assert_eq!(snapshot.counters.get("core_requests_failed"), Some(&1));
+    assert_eq!(snapshot.counters.get("core_tokens_output_total"), Some(&20));
+    for name in ["core_requests_rate_1m", "core_tokens_rate_5m", "core_error_ratio_1m"] {
+        assert!(snapshot.rates.contains_key(name), "missing {name}");
+    }
+}
+
+#[test]
+fn test_slo_status_response_roundtrip() {
+    use gg_core::telemetry::{SloConfig, SloMonitor};
+    use std::sync::Arc;
+
+    let store = Arc::new(MetricsStore::new());
+    store.record_request(true, 5);
+    let monitor = SloMonitor::new(SloConfig::default(), Arc::clone(&store));
+
+    let message = IpcMessage::SloStatusResponse { objectives: monitor.evaluate() };
+    let decoded = decode_message(&encode_message(&message).unwrap()).unwrap();
+
+    match decoded {
+        IpcMessage::SloStatusResponse { objectives } => {
+            let availability = objectives.iter().find(|s| s.name == "availability").unwrap();
+            assert_eq!((availability.good, availability.total), (1, 1));
+            assert!(!availability.burning);
+        }
+        _ => panic!("Expected SloStatusResponse message"),
+    }
+}
+
+#[test]
+fn test_token_latency_histograms() {
+    use gg_core::telemetry::streaming::{INTER_TOKEN_HISTOGRAM, TTFT_HISTOGRAM};
+    use gg_core::telemetry::TokenLatency;
+
+    let store = MetricsStore::new();
+    store.record_token_latency(TokenLatency::FirstToken(180.0));
+    store.record_token_latency(TokenLatency::InterToken(20.0));
+    store.record_token_latency(TokenLatency::InterToken(30.0));
+
+    let snapshot = store.snapshot();
+    let ttft = &snapshot.bucketed_histograms[TTFT_HISTOGRAM];
+    let inter_token = &snapshot.bucketed_histograms[INTER_TOKEN_HISTOGRAM];
+    assert_eq!(ttft.count, 1);
+    assert_eq!(inter_token.count, 2);
+    assert_eq!(inter_token.mean(), 25.0);
+
+    let text = gg_core::telemetry::encode_prometheus(&snapshot);
+    assert!(text.contains("# TYPE core_ttft_ms histogram"));
+    assert!(text.contains("core_inter_token_ms_count 2"));
+}
+
+#[tokio::test]
+async fn test_registry_records_stream_timings() {
+    use gg_core::models::{ModelMetadata, ModelRegistry};
+    use gg_core::telemetry::StreamTimer;
+    use std::time::{Duration, Instant};
+
+    let registry = ModelRegistry::new();
+    let metadata = ModelMetadata { name: "stream-model".into(), size_bytes: 1 };
+    let handle = registry.register(metadata, 1).await;
+
+    let start = Instant::now();
+    let mut timer = StreamTimer::start_at(start);
+    timer.token_at(start + Duration::from_millis(100));
+    timer.token_at(start + Duration::from_millis(110));
+    registry.record_stream(handle, &timer.timings()).await;
+    // Streams that produced nothing are not counted
+    registry.record_stream(handle, &StreamTimer::start().timings()).await;
+
+    let info = &registry.list_models().await[0];
+    assert_eq!(info.stream_count, 1);
+    assert_eq!(info.total_ttft_ms, 100.0);
+    assert_eq!(info.inter_token_count, 1);
+}
@@ -0,0 +1,48 @@
+//! Tests for the built-in on-disk migrations.
+
+use gg_core::migrations::{MigrationOptions, Migrator, VERSION_FILE};
+use gg_core::models::RegistryPersistence;
+
+const OLD_REGISTRY_STATE: &str = r#"{
+    "schema_version": 1,
+    "saved_at": 1700000000,
+    "models": {},
+    "default_model": "phi-3",
+    "operator_note": "kept"
+}"#;
+
+#[test]
+fn registry_catalog_gains_stats_and_keeps_unknown_fields() {
+    let dir = tempfile::tempdir().unwrap();
+    let state_path = dir.path().join("registry_state.json");
+    std::fs::write(&state_path, OLD_REGISTRY_STATE).unwrap();
+
+    let migrator = Migrator::builtin(dir.path());
+    assert_eq!(migrator.pending().unwrap()[0].files, vec![state_path.clone()]);
+
+    let report = migrator.run(MigrationOptions::default()).unwrap();
+    assert_eq!(report.to_version, migrator.latest_version());
+    assert_eq!(report.backups.len(), 1);
+    let backed_up = std::fs::read_to_string(report.backups[0].join("registry_state.json")).unwrap();
+    assert_eq!(backed_up, OLD_REGISTRY_STATE);
+
+    let migrated: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(&state_path).unwrap()).unwrap();
+    assert!(migrated["stats"].as_object().unwrap().is_empty());
+    assert_eq!(migrated["operator_note"], "kept");
+    let state = RegistryPersistence::new(state_path).load().unwrap();
+    assert_eq!(state.default_model.as_deref(), Some("phi-3"));
+
+    assert!(migrator.pending().unwrap().is_empty());
+}
+
+#[test]
+fn fresh_install_records_version_without_backups() {
+    let dir = tempfile::tempdir().unwrap();
+    let migrator = Migrator::builtin(dir.path());
+
+    let report = migrator.run(MigrationOptions::default()).unwrap();
+    assert!(report.backups.is_empty());
+    assert!(dir.path().join(VERSION_FILE).exists());
+    assert_eq!(migrator.current().unwrap().version, migrator.latest_version());
+}
@@ -0,0 +1,67 @@
+//! Tests for model format detection and validation in the loader.
+
+use gg_core::models::{LoadError, ModelFormat, ModelLoader};
+
+fn safetensors_bytes() -> Vec<u8> {
+    let header = br#"{"embeddings.weight":{"dtype":"F32","shape":[4,2],"data_offsets":[0,32]}}"#;
+    let mut bytes = (header.len() as u64).to_le_bytes().to_vec();
+    bytes.extend_from_slice(header);
+    bytes.extend_from_slice(&[0u8; 32]);
+    bytes
+}
+
+fn loader_with(files: &[(&str, Vec<u8>)]) -> (tempfile::TempDir, ModelLoader) {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(dir.path().join("models")).unwrap();
+    for (name, bytes) in files {
+        std::fs::write(dir.path().join("models").join(name), bytes).unwrap();
+    }
+    let loader = ModelLoader::new(dir.path().to_path_buf());
+    (dir, loader)
+}
+
+#[test]
+fn mixed_format_directory_is_detected_by_content() {
+    let (_dir, loader) = loader_with(&[
+        ("embedder.safetensors", safetensors_bytes()),
+        ("classifier.onnx", vec![0x08, 0x07, 0x12, 0x04, b't', b'e', b's', b't', 0]),
+        // Misleading extension: content wins
+        ("renamed.bin", safetensors_bytes()),
+    ]);
+
+    for (name, expected) in [
+        ("models/embedder.safetensors", ModelFormat::Safetensors),
+        ("models/classifier.onnx", ModelFormat::Onnx),
+        ("models/renamed.bin", ModelFormat::Safetensors),
+    ] {
+        let path = loader.validate_path(name).unwrap();
+        assert_eq!(loader.detect_format(&path).unwrap(), expected, "{name}");
+    }
+}
+
+#[test]
+fn guarded_load_passes_detected_format() {
+    let (_dir, loader) = loader_with(&[("embedder.safetensors", safetensors_bytes())]);
+    let path = loader.validate_path("models/embedder.safetensors").unwrap();
+
+    let format = loader.load_guarded(&path, |_, format| Ok(format)).unwrap();
+    assert_eq!(format, ModelFormat::Safetensors);
+}
+
+#[test]
+fn corrupt_safetensors_is_rejected_before_loading() {
+    let mut bytes = safetensors_bytes();
+    bytes.truncate(bytes.len() - 8);
+    let (_dir, loader) = loader_with(&[("embedder.safetensors", bytes)]);
+    let path = loader.validate_path("models/embedder.safetensors").unwrap();
+
+    let result = loader.load_guarded(&path, |_, _| -> Result<(), LoadError> { panic!("backend reached") });
+    assert!(matches!(result, Err(LoadError::InvalidFormat(_))));
+}
+
+#[test]
+fn unknown_format_is_rejected() {
+    let (_dir, loader) = loader_with(&[("weights.pt", b"PK\x03\x04 pickle zip".to_vec())]);
+    let path = loader.validate_path("models/weights.pt").unwrap();
+    assert!(matches!(loader.detect_format(&path), Err(LoadError::InvalidFormat(_))));
+}
@@ -0,0 +1,84 @@
+//! Tests for the offline quantization pipeline with a stand-in backend.
+
+use std::path::Path;
+
+use gg_core::models::{
+    quantize_model, QuantizeBackend, QuantizeError, QuantizeMethod, QuantizeOptions, QuantizeStage,
+};
+use gg_core::security::ModelEncryption;
+
+/// Copies the input, standing in for llama.cpp.
+struct CopyBackend;
+
+impl QuantizeBackend for CopyBackend {
+    fn quantize(&self, input: &Path, output: &Path, _: QuantizeMethod, _: u32) -> Result<(), QuantizeError> {
+        std::fs::copy(input, output)?;
+        Ok(())
+    }
+}
+
+/// Minimal valid GGUF v3 file with no tensors.
+fn tiny_gguf() -> Vec<u8> {
+    let mut b = b"GGUF".to_vec();
+    b.extend_from_slice(&3u32.to_le_bytes());
+    b.extend_from_slice(&0u64.to_le_bytes());
+    b.extend_from_slice(&0u64.to_le_bytes());
+    b
+}
+
+fn options(dir: &Path, encrypt_output: bool) -> QuantizeOptions {
+    QuantizeOptions {
+        input: dir.join("model-f16.gguf"),
+        output: dir.join("model-q4.gguf"),
+        method: QuantizeMethod::Q4KM,
+        threads: 0,
+        encrypt_output,
+    }
+}
+
+#[test]
+fn quantize_writes_output_and_checksum() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("model-f16.gguf"), tiny_gguf()).unwrap();
+
+    let mut stages = Vec::new();
+    let report = quantize_model(&options(dir.path(), false), &CopyBackend, None, &mut |s| stages.push(s)).unwrap();
+
+    assert_eq!(std::fs::read(dir.path().join("model-q4.gguf")).unwrap(), tiny_gguf());
+    let checksum = std::fs::read_to_string(&report.checksum_path).unwrap();
+    assert_eq!(checksum, format!("{}  model-q4.gguf\n", report.sha256));
+    assert_eq!(&stages[..2], &[QuantizeStage::Validating, QuantizeStage::Quantizing]);
+    assert!(matches!(stages.last(), Some(QuantizeStage::Hashing { done, total }) if done == total));
+    assert!(!dir.path().join("model-q4.gguf.partial").exists());
+}
+
+#[test]
+fn invalid_input_and_existing_output_are_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("model-f16.gguf"), b"not gguf").unwrap();
+    let result = quantize_model(&options(dir.path(), false), &CopyBackend, None, &mut |_| {});
+    assert!(matches!(result, Err(QuantizeError::InvalidInput(_))));
+
+    std::fs::write(dir.path().join("model-q4.gguf"), b"keep me").unwrap();
+    let result = quantize_model(&options(dir.path(), false), &CopyBackend, None, &mut |_| {});
+    assert!(matches!(result, Err(QuantizeError::OutputExists(_))));
+    assert_eq!(std::fs::read(dir.path().join("model-q4.gguf")).unwrap(), b"keep me");
+}
+
+#[test]
+fn encrypted_input_is_re_encrypted() {
+    let dir = tempfile::tempdir().unwrap();
+    let key = ModelEncryption::new([7u8; 32]);
+    let plain = dir.path().join("plain.gguf");
+    std::fs::write(&plain, tiny_gguf()).unwrap();
+    key.encrypt_file(&plain, &dir.path().join("model-f16.gguf")).unwrap();
+
+    let result = quantize_model(&options(dir.path(), true), &CopyBackend, None, &mut |_| {});
+    assert!(matches!(result, Err(QuantizeError::KeyRequired)));
+
+    let report = quantize_model(&options(dir.path(), true), &CopyBackend, Some(&key), &mut |_| {}).unwrap();
+    assert!(report.encrypted);
+    let decrypted = dir.path().join("roundtrip.gguf");
+    key.decrypt_file(&dir.path().join("model-q4.gguf"), &decrypted).unwrap();
+    assert_eq!(std::fs::read(decrypted).unwrap(), tiny_gguf());
+}
@@ -0,0 +1,40 @@
+//! Tests for GGUF pre-load validation and quarantine of failing files.
+
+use gg_core::models::{LoadError, ModelLoader, Quarantine};
+
+fn loader_with_model(bytes: &[u8]) -> (tempfile::TempDir, ModelLoader) {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(dir.path().join("models")).unwrap();
+    std::fs::write(dir.path().join("models/corrupt.gguf"), bytes).unwrap();
+    let loader = ModelLoader::new(dir.path().to_path_buf()).with_quarantine(Quarantine::new(2));
+    (dir, loader)
+}
+
+#[test]
+fn corrupt_gguf_is_rejected_before_loading() {
+    let (_dir, loader) = loader_with_model(b"GGUF\x03\x00\x00\x00");
+    let path = loader.validate_path("models/corrupt.gguf").unwrap();
+
+    let mut loaded = false;
+    let result = loader.load_guarded(&path, |_, _| {
+        loaded = true;
+        Ok(())
+    });
+    assert!(matches!(result, Err(LoadError::InvalidFormat(_))));
+    assert!(!loaded);
+}
+
+#[test]
+fn repeatedly_failing_gguf_is_quarantined() {
+    let (dir, loader) = loader_with_model(b"not a gguf file at all");
+    let path = loader.validate_path("models/corrupt.gguf").unwrap();
+
+    assert!(matches!(loader.load_guarded(&path, |_, _| Ok(())), Err(LoadError::InvalidFormat(_))));
+    let err = loader.load_guarded(&path, |_, _| Ok(())).unwrap_err();
+    assert!(matches!(err, LoadError::Quarantined(_)));
+
+    assert!(!dir.path().join("models/corrupt.gguf").exists());
+    assert!(dir.path().join("models/corrupt.gguf.quarantined").exists());
+    // A quarantined file can no longer be addressed by its original path
+    assert!(loader.validate_path("models/corrupt.gguf").is_err());
+}
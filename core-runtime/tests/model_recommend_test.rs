@@ -0,0 +1,92 @@
+//! Tests for quantization recommendation against fixed calibrations.
+
+use std::path::Path;
+use std::time::Duration;
+
+use gg_core::models::{recommend, Calibration, CpuCalibration, RecommendTarget};
+
+/// F16 GGUF header (`general.file_type = 1`) extended to `size` bytes.
+fn write_f16_model(path: &Path, size: usize) {
+    let mut b = b"GGUF".to_vec();
+    b.extend_from_slice(&3u32.to_le_bytes());
+    b.extend_from_slice(&0u64.to_le_bytes());
+    b.extend_from_slice(&1u64.to_le_bytes());
+    let key = b"general.file_type";
+    b.extend_from_slice(&(key.len() as u64).to_le_bytes());
+    b.extend_from_slice(key);
+    b.extend_from_slice(&4u32.to_le_bytes()); // uint32
+    b.extend_from_slice(&1u32.to_le_bytes()); // MOSTLY_F16
+    std::fs::write(path, b).unwrap();
+    // Sparse: only the header occupies disk
+    std::fs::OpenOptions::new().write(true).open(path).unwrap().set_len(size as u64).unwrap();
+}
+
+struct Gpu;
+
+impl Calibration for Gpu {
+    fn backend(&self) -> &str {
+        "cuda"
+    }
+    fn bandwidth(&self) -> f64 {
+        100e9
+    }
+    fn memory_bytes(&self) -> Option<u64> {
+        Some(400_000_000)
+    }
+}
+
+#[test]
+fn picks_highest_quality_that_meets_targets() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("model-f16.gguf");
+    write_f16_model(&path, 1_000_000_000);
+    let cpu = CpuCalibration::with_bandwidth(20e9);
+
+    // 1 GB of F16 weights at 20 GB/s: f16 = 50ms, q8_0 = 26.6ms, q4_k_m = 15.2ms
+    let target = RecommendTarget { latency: Duration::from_millis(30), memory_bytes: 2_000_000_000 };
+    let report = recommend(&path, target, &[&cpu]).unwrap();
+    assert_eq!(report.source_method.as_deref(), Some("f16"));
+    let chosen = report.recommendation.unwrap();
+    assert_eq!((chosen.method.as_str(), chosen.backend.as_str()), ("q8_0", "cpu"));
+
+    // Tight memory forces a smaller quantization
+    let target = RecommendTarget { latency: Duration::from_millis(30), memory_bytes: 400_000_000 };
+    let report = recommend(&path, target, &[&cpu]).unwrap();
+    assert_eq!(report.recommendation.unwrap().method, "q4_k_m");
+}
+
+#[test]
+fn faster_backend_wins_at_equal_quality_within_its_memory() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("model-f16.gguf");
+    write_f16_model(&path, 1_000_000_000);
+    let cpu = CpuCalibration::with_bandwidth(20e9);
+
+    let target = RecommendTarget { latency: Duration::from_millis(60), memory_bytes: 8 << 30 };
+    let report = recommend(&path, target, &[&cpu, &Gpu]).unwrap();
+    // f16 only fits on the CPU; the GPU's 400MB holds q4 at most
+    let chosen = report.recommendation.unwrap();
+    assert_eq!((chosen.method.as_str(), chosen.backend.as_str()), ("f16", "cpu"));
+    assert!(report.candidates.iter().any(|c| c.backend == "cuda" && c.method == "q4_0" && c.fits_memory));
+}
+
+#[test]
+fn unmet_latency_falls_back_and_report_is_camel_case() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("model-f16.gguf");
+    write_f16_model(&path, 1_000_000_000);
+    let cpu = CpuCalibration::with_bandwidth(1e9);
+
+    let target = RecommendTarget { latency: Duration::from_millis(10), memory_bytes: 8 << 30 };
+    let report = recommend(&path, target, &[&cpu]).unwrap();
+    assert_eq!(report.recommendation.as_ref().unwrap().method, "q4_0");
+    assert!(report.reason.contains("fastest"));
+
+    let json = serde_json::to_value(&report).unwrap();
+    assert!(json["recommendation"]["estimatedMsPerToken"].is_number());
+    assert!(json["estimateBasis"].as_str().unwrap().contains("bandwidth"));
+    assert!(json["memoryBudgetBytes"].is_number());
+
+    let target = RecommendTarget { latency: Duration::from_millis(10), memory_bytes: 1_000 };
+    assert!(recommend(&path, target, &[&cpu]).unwrap().recommendation.is_none());
+}
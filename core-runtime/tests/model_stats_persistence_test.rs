@@ -0,0 +1,62 @@
+//! Tests for model request statistics surviving unloads and restarts.
+
+use gg_core::models::{ModelMetadata, ModelRegistry, RegistryPersistence};
+
+fn metadata(name: &str) -> ModelMetadata {
+    ModelMetadata { name: name.into(), size_bytes: 1 }
+}
+
+#[tokio::test]
+async fn stats_restored_after_restart() {
+    let dir = tempfile::tempdir().unwrap();
+    let state_path = dir.path().join("registry_state.json");
+
+    let registry = ModelRegistry::with_persistence(RegistryPersistence::new(state_path.clone()));
+    let handle = registry.register(metadata("phi-3"), 0).await;
+    for latency in [10.0, 20.0, 30.0] {
+        registry.record_request(handle, latency).await;
+    }
+    registry.persist_stats().await.unwrap();
+
+    // A fresh process sees the lifetime totals once the model registers again
+    let restarted = ModelRegistry::with_persistence(RegistryPersistence::new(state_path));
+    let handle = restarted.register(metadata("phi-3"), 0).await;
+    restarted.record_request(handle, 40.0).await;
+
+    let info = &restarted.list_models().await[0];
+    assert_eq!(info.request_count, 4);
+    assert_eq!(info.total_latency_ms, 100.0);
+    assert_eq!(info.recent_p95_latency_ms, Some(40.0));
+}
+
+#[tokio::test]
+async fn stats_survive_unload_and_keep_catalog() {
+    let dir = tempfile::tempdir().unwrap();
+    let state_path = dir.path().join("registry_state.json");
+    let persistence = RegistryPersistence::new(state_path.clone());
+    let mut state = persistence.load_or_default();
+    state.default_model = Some("phi-3".into());
+    persistence.save(&state).unwrap();
+
+    let registry = ModelRegistry::with_persistence(persistence);
+    let handle = registry.register(metadata("phi-3"), 0).await;
+    registry.record_request(handle, 5.0).await;
+    registry.unregister(handle).await;
+    assert_eq!(registry.stats_snapshot().await["phi-3"].request_count, 1);
+
+    registry.persist_stats().await.unwrap();
+    let saved = RegistryPersistence::new(state_path).load().unwrap();
+    assert_eq!(saved.default_model.as_deref(), Some("phi-3"));
+    assert_eq!(saved.stats["phi-3"].request_count, 1);
+}
+
+#[tokio::test]
+async fn unreadable_state_starts_fresh() {
+    let dir = tempfile::tempdir().unwrap();
+    let state_path = dir.path().join("registry_state.json");
+    std::fs::write(&state_path, "{ not json").unwrap();
+
+    let registry = ModelRegistry::with_persistence(RegistryPersistence::new(state_path));
+    registry.register(metadata("phi-3"), 0).await;
+    assert_eq!(registry.list_models().await[0].request_count, 0);
+}
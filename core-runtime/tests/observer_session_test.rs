@@ -0,0 +1,80 @@
+//! Tests for read-only observer sessions.
+
+use gg_core::engine::InferenceParams;
+use gg_core::ipc::{decode_message, encode_message, InferenceRequest, IpcHandler, IpcMessage, RequestId, SessionToken};
+use gg_core::{Runtime, RuntimeConfig};
+
+fn runtime() -> Runtime {
+    Runtime::new(RuntimeConfig {
+        auth_token: "user-token".into(),
+        observer_token: Some("observer-token".into()),
+        ..Default::default()
+    })
+}
+
+async fn login(handler: &IpcHandler, token: &str) -> SessionToken {
+    let handshake = IpcMessage::Handshake { token: token.into(), protocol_version: None };
+    let (_, session) = handler.process(&encode_message(&handshake).unwrap(), None).await.unwrap();
+    session.unwrap()
+}
+
+async fn send(handler: &IpcHandler, message: &IpcMessage, session: &SessionToken) -> IpcMessage {
+    let (bytes, _) = handler.process(&encode_message(message).unwrap(), Some(session)).await.unwrap();
+    decode_message(&bytes).unwrap()
+}
+
+fn inference() -> IpcMessage {
+    IpcMessage::InferenceRequest(InferenceRequest {
+        request_id: RequestId(1),
+        model_id: "missing-model".into(),
+        prompt: "Hello".into(),
+        parameters: InferenceParams::default(),
+        preset: None,
+        priority: Default::default(),
+        session_affinity_key: None,
+        tools: Vec::new(),
+        images: Vec::new(),
+        template_id: None,
+        variables: Default::default(),
+        context_documents: Vec::new(),
+        cite_sources: false,
+        privacy: Default::default(),
+        idempotency_key: None,
+    })
+}
+
+#[tokio::test]
+async fn observer_can_query_but_not_mutate() {
+    let rt = runtime();
+    let observer = login(&rt.ipc_handler, "observer-token").await;
+
+    let metrics = send(&rt.ipc_handler, &IpcMessage::MetricsRequest, &observer).await;
+    assert!(matches!(metrics, IpcMessage::MetricsResponse(_)));
+    assert!(matches!(
+        send(&rt.ipc_handler, &IpcMessage::SpansRequest { max_count: 10 }, &observer).await,
+        IpcMessage::SpansResponse { .. }
+    ));
+
+    for message in [inference(), IpcMessage::ConversationCreate { model_id: "m".into() }] {
+        match send(&rt.ipc_handler, &message, &observer).await {
+            IpcMessage::Error { code, message } => {
+                assert_eq!(code, 403);
+                assert!(message.contains("read-only"));
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+    }
+
+    let counters = rt.metrics_store.snapshot().counters;
+    assert_eq!(counters.get("core_observer_sessions"), Some(&1));
+    assert_eq!(counters.get("core_observer_requests"), Some(&4));
+    assert_eq!(counters.get("core_observer_denied"), Some(&2));
+}
+
+#[tokio::test]
+async fn standard_sessions_are_not_observers() {
+    let rt = runtime();
+    let user = login(&rt.ipc_handler, "user-token").await;
+    assert!(matches!(send(&rt.ipc_handler, &inference(), &user).await, IpcMessage::InferenceResponse(_)));
+    assert_eq!(rt.metrics_store.snapshot().counters.get("core_observer_requests"), None);
+}
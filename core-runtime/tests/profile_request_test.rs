@@ -0,0 +1,47 @@
+//! Tests for the IPC profile request: admin gating and opt-in.
+
+use gg_core::ipc::{decode_message, encode_message, IpcHandler, IpcMessage, SessionToken};
+use gg_core::telemetry::ProfilerConfig;
+use gg_core::{Runtime, RuntimeConfig};
+
+fn runtime(profiling: bool) -> Runtime {
+    Runtime::new(RuntimeConfig {
+        auth_token: "user-token".into(),
+        admin_token: Some("admin-token".into()),
+        profiler: ProfilerConfig { enabled: profiling, max_seconds: 5, ..Default::default() },
+        ..Default::default()
+    })
+}
+
+async fn login(handler: &IpcHandler, token: &str) -> SessionToken {
+    let handshake = IpcMessage::Handshake { token: token.into(), protocol_version: None };
+    let (_, session) = handler.process(&encode_message(&handshake).unwrap(), None).await.unwrap();
+    session.unwrap()
+}
+
+async fn profile(handler: &IpcHandler, session: &SessionToken, seconds: u32) -> IpcMessage {
+    let request = encode_message(&IpcMessage::ProfileRequest { seconds }).unwrap();
+    let (bytes, _) = handler.process(&request, Some(session)).await.unwrap();
+    decode_message(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn profile_requires_auth_and_admin() {
+    let rt = runtime(true);
+    let request = encode_message(&IpcMessage::ProfileRequest { seconds: 1 }).unwrap();
+    assert!(rt.ipc_handler.process(&request, None).await.is_err());
+
+    let user = login(&rt.ipc_handler, "user-token").await;
+    assert!(matches!(profile(&rt.ipc_handler, &user, 1).await, IpcMessage::Error { code: 403, .. }));
+}
+
+#[tokio::test]
+async fn profile_is_opt_in_and_bounded() {
+    let rt = runtime(false);
+    let admin = login(&rt.ipc_handler, "admin-token").await;
+    assert!(matches!(profile(&rt.ipc_handler, &admin, 1).await, IpcMessage::Error { code: 501, .. }));
+
+    let rt = runtime(true);
+    let admin = login(&rt.ipc_handler, "admin-token").await;
+    assert!(matches!(profile(&rt.ipc_handler, &admin, 60).await, IpcMessage::Error { code: 400, .. }));
+}
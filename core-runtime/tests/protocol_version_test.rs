@@ -62,13 +62,14 @@ fn handshake_ack_includes_negotiated_version() {
     let message = IpcMessage::HandshakeAck {
         session_id: "session-abc".to_string(),
         protocol_version: ProtocolVersion::V1,
+        deprecations: Vec::new(),
     };
 
     let encoded = encode_message(&message).unwrap();
     let decoded = decode_message(&encoded).unwrap();
 
     match decoded {
-        IpcMessage::HandshakeAck { session_id, protocol_version } => {
+        IpcMessage::HandshakeAck { session_id, protocol_version, .. } => {
             assert_eq!(session_id, "session-abc");
             assert_eq!(protocol_version, ProtocolVersion::V1);
         }
@@ -81,13 +82,14 @@ fn handshake_ack_v2_negotiated() {
     let message = IpcMessage::HandshakeAck {
         session_id: "session-xyz".to_string(),
         protocol_version: ProtocolVersion::V2,
+        deprecations: Vec::new(),
     };
 
     let encoded = encode_message(&message).unwrap();
     let decoded = decode_message(&encoded).unwrap();
 
     match decoded {
-        IpcMessage::HandshakeAck { session_id, protocol_version } => {
+        IpcMessage::HandshakeAck { session_id, protocol_version, .. } => {
             assert_eq!(session_id, "session-xyz");
             assert_eq!(protocol_version, ProtocolVersion::V2);
         }
@@ -102,7 +104,7 @@ fn legacy_handshake_ack_defaults_to_v1() {
     let message = decode_message(legacy_json.as_bytes()).unwrap();
 
     match message {
-        IpcMessage::HandshakeAck { session_id, protocol_version } => {
+        IpcMessage::HandshakeAck { session_id, protocol_version, .. } => {
             assert_eq!(session_id, "session-old");
             assert_eq!(protocol_version, ProtocolVersion::V1); // Default
         }
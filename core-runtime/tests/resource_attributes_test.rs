@@ -0,0 +1,37 @@
+//! Tests for instance identity on metrics and audit events.
+
+use gg_core::ipc::{decode_message, encode_message, IpcMessage};
+use gg_core::security::audit::{AuditCategory, AuditConfig, AuditLogger, AuditSeverity};
+use gg_core::telemetry::{init_resource, ResourceAttributes};
+use gg_core::{Runtime, RuntimeConfig};
+
+#[tokio::test]
+async fn metrics_and_audit_events_carry_instance_identity() {
+    let dir = tempfile::tempdir().unwrap();
+    let resource = init_resource(ResourceAttributes::detect(dir.path()));
+    // Restarting on the same base path keeps the identity
+    assert_eq!(ResourceAttributes::detect(dir.path()).instance_id, resource.instance_id);
+
+    let rt = Runtime::new(RuntimeConfig::default());
+    let handler = &rt.ipc_handler;
+    let handshake = IpcMessage::Handshake { token: String::new(), protocol_version: None };
+    let (_, session) = handler.process(&encode_message(&handshake).unwrap(), None).await.unwrap();
+    let session = session.unwrap();
+
+    let request = encode_message(&IpcMessage::MetricsRequest).unwrap();
+    let (bytes, _) = handler.process(&request, Some(&session)).await.unwrap();
+    match decode_message(&bytes).unwrap() {
+        IpcMessage::MetricsResponse(snapshot) => {
+            assert_eq!(snapshot.resource.as_ref(), Some(resource));
+        }
+        other => panic!("unexpected message: {:?}", other),
+    }
+
+    let logger = AuditLogger::new(AuditConfig { log_to_stdout: false, ..Default::default() });
+    logger
+        .log_event(AuditSeverity::Info, AuditCategory::Configuration, "test", "event", "test")
+        .await;
+    let events = logger.get_events().await;
+    assert_eq!(events[0].metadata.get("service.instance.id"), Some(&resource.instance_id));
+    assert_eq!(events[0].metadata.get("host.name"), Some(&resource.node));
+}
@@ -0,0 +1,248 @@
+//! Tests for the safetensors encoder backend: a tiny BERT checkpoint is
+//! written to disk and run end to end, then checked against a plain-Rust
+//! forward pass over the same weights.
+
+#![cfg(feature = "safetensors")]
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use gg_core::engine::{
+    load_safetensors_model, InferenceConfig, InferenceError, InferenceInput, InferenceOutput, SafetensorsTask,
+};
+
+const HIDDEN: usize = 4;
+const HEADS: usize = 2;
+const INTERMEDIATE: usize = 8;
+const LAYERS: usize = 2;
+const MAX_POSITIONS: usize = 16;
+const VOCAB: [&str; 10] = ["[PAD]", "[UNK]", "[CLS]", "[SEP]", "the", "cat", "sat", "on", "mat", "dog"];
+
+/// Tensor name to (shape, row-major values).
+type Tensors = BTreeMap<String, (Vec<usize>, Vec<f32>)>;
+
+/// Builds a checkpoint from deterministic weights in [-0.5, 0.5).
+struct Builder {
+    seed: u64,
+    tensors: Tensors,
+}
+
+impl Builder {
+    fn add(&mut self, name: String, shape: &[usize]) {
+        let values = (0..shape.iter().product())
+            .map(|_| {
+                self.seed = self.seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                (self.seed >> 40) as f32 / (1u64 << 24) as f32 - 0.5
+            })
+            .collect();
+        self.tensors.insert(name, (shape.to_vec(), values));
+    }
+
+    fn linear(&mut self, name: &str, out: usize, input: usize) {
+        self.add(format!("{name}.weight"), &[out, input]);
+        self.add(format!("{name}.bias"), &[out]);
+    }
+
+    fn norm(&mut self, name: &str) {
+        self.add(format!("{name}.weight"), &[HIDDEN]);
+        self.add(format!("{name}.bias"), &[HIDDEN]);
+    }
+}
+
+fn checkpoint(prefix: &str, with_head: bool) -> Tensors {
+    let mut b = Builder { seed: 7, tensors: Tensors::new() };
+    for (kind, rows) in [("word", VOCAB.len()), ("position", MAX_POSITIONS), ("token_type", 2)] {
+        b.add(format!("{prefix}embeddings.{kind}_embeddings.weight"), &[rows, HIDDEN]);
+    }
+    b.norm(&format!("{prefix}embeddings.LayerNorm"));
+    for i in 0..LAYERS {
+        let layer = format!("{prefix}encoder.layer.{i}");
+        for part in ["self.query", "self.key", "self.value", "output.dense"] {
+            b.linear(&format!("{layer}.attention.{part}"), HIDDEN, HIDDEN);
+        }
+        b.norm(&format!("{layer}.attention.output.LayerNorm"));
+        b.linear(&format!("{layer}.intermediate.dense"), INTERMEDIATE, HIDDEN);
+        b.linear(&format!("{layer}.output.dense"), HIDDEN, INTERMEDIATE);
+        b.norm(&format!("{layer}.output.LayerNorm"));
+    }
+    if with_head {
+        b.linear(&format!("{prefix}pooler.dense"), HIDDEN, HIDDEN);
+        // The classifier sits outside the encoder prefix
+        b.linear("classifier", 1, HIDDEN);
+    }
+    b.tensors
+}
+
+/// Write the checkpoint with its `config.json` and `vocab.txt`.
+fn write_model(dir: &Path, tensors: &Tensors) -> PathBuf {
+    let (mut header, mut data) = (serde_json::Map::new(), Vec::new());
+    for (name, (shape, values)) in tensors {
+        let begin = data.len();
+        data.extend(values.iter().flat_map(|v| v.to_le_bytes()));
+        let info = serde_json::json!({ "dtype": "F32", "shape": shape, "data_offsets": [begin, data.len()] });
+        header.insert(name.clone(), info);
+    }
+    let header = serde_json::to_vec(&header).unwrap();
+    let mut bytes = (header.len() as u64).to_le_bytes().to_vec();
+    bytes.extend(header);
+    bytes.extend(data);
+
+    let path = dir.join("model.safetensors");
+    std::fs::write(&path, bytes).unwrap();
+    let config = serde_json::json!({
+        "num_attention_heads": HEADS,
+        "hidden_act": "gelu_new",
+        "layer_norm_eps": 1e-12,
+        "max_position_embeddings": MAX_POSITIONS,
+    });
+    std::fs::write(dir.join("config.json"), config.to_string()).unwrap();
+    std::fs::write(dir.join("vocab.txt"), VOCAB.join("\n")).unwrap();
+    path
+}
+
+/// Naive forward pass over the raw weights.
+struct Reference<'a> {
+    tensors: &'a Tensors,
+    prefix: &'a str,
+}
+
+impl Reference<'_> {
+    fn weight(&self, name: &str) -> &[f32] {
+        &self.tensors[&format!("{}{name}", self.prefix)].1
+    }
+
+    fn linear(&self, name: &str, x: &[f32]) -> Vec<f32> {
+        let (weight, bias) = (self.weight(&format!("{name}.weight")), self.weight(&format!("{name}.bias")));
+        let rows = weight.chunks(x.len());
+        bias.iter().zip(rows).map(|(b, row)| b + row.iter().zip(x).map(|(w, v)| w * v).sum::<f32>()).collect()
+    }
+
+    fn norm(&self, name: &str, x: &[f32]) -> Vec<f32> {
+        let mean = x.iter().sum::<f32>() / x.len() as f32;
+        let var = x.iter().map(|v| (v - mean).powi(2)).sum::<f32>() / x.len() as f32;
+        let (weight, bias) = (self.weight(&format!("{name}.weight")), self.weight(&format!("{name}.bias")));
+        x.iter().zip(weight.iter().zip(bias)).map(|(v, (w, b))| (v - mean) / (var + 1e-12).sqrt() * w + b).collect()
+    }
+
+    fn forward(&self, ids: &[usize], segments: &[usize]) -> Vec<Vec<f32>> {
+        let row = |name: &str, i: usize| {
+            self.weight(&format!("embeddings.{name}_embeddings.weight"))[i * HIDDEN..][..HIDDEN].to_vec()
+        };
+        let mut x: Vec<Vec<f32>> = (0..ids.len())
+            .map(|t| {
+                let sum = add(&add(&row("word", ids[t]), &row("position", t)), &row("token_type", segments[t]));
+                self.norm("embeddings.LayerNorm", &sum)
+            })
+            .collect();
+        for i in 0..LAYERS {
+            let layer = format!("encoder.layer.{i}");
+            let attended = self.attention(&layer, &x);
+            x = x
+                .iter()
+                .zip(attended)
+                .map(|(input, a)| {
+                    let a = self.linear(&format!("{layer}.attention.output.dense"), &a);
+                    let h = self.norm(&format!("{layer}.attention.output.LayerNorm"), &add(&a, input));
+                    let inner: Vec<f32> =
+                        self.linear(&format!("{layer}.intermediate.dense"), &h).into_iter().map(gelu_tanh).collect();
+                    let out = self.linear(&format!("{layer}.output.dense"), &inner);
+                    self.norm(&format!("{layer}.output.LayerNorm"), &add(&out, &h))
+                })
+                .collect();
+        }
+        x
+    }
+
+    fn attention(&self, layer: &str, x: &[Vec<f32>]) -> Vec<Vec<f32>> {
+        let project = |part: &str| -> Vec<Vec<f32>> {
+            x.iter().map(|v| self.linear(&format!("{layer}.attention.self.{part}"), v)).collect()
+        };
+        let (q, k, v) = (project("query"), project("key"), project("value"));
+        let head_dim = HIDDEN / HEADS;
+        let mut out = vec![vec![0.0; HIDDEN]; x.len()];
+        for h in (0..HIDDEN).step_by(head_dim) {
+            for (t, row) in out.iter_mut().enumerate() {
+                let dot =
+                    |s: usize| (h..h + head_dim).map(|d| q[t][d] * k[s][d]).sum::<f32>() / (head_dim as f32).sqrt();
+                let scores: Vec<f32> = (0..x.len()).map(dot).collect();
+                let max = scores.iter().cloned().fold(f32::MIN, f32::max);
+                let exp: Vec<f32> = scores.iter().map(|s| (s - max).exp()).collect();
+                let total: f32 = exp.iter().sum();
+                for d in h..h + head_dim {
+                    row[d] = exp.iter().zip(&v).map(|(e, v)| e / total * v[d]).sum();
+                }
+            }
+        }
+        out
+    }
+}
+
+fn add(a: &[f32], b: &[f32]) -> Vec<f32> {
+    a.iter().zip(b).map(|(a, b)| a + b).collect()
+}
+
+fn gelu_tanh(x: f32) -> f32 {
+    0.5 * x * (1.0 + ((2.0 / std::f32::consts::PI).sqrt() * (x + 0.044715 * x.powi(3))).tanh())
+}
+
+fn assert_close(actual: &[f32], expected: &[f32]) {
+    assert_eq!(actual.len(), expected.len());
+    for (a, e) in actual.iter().zip(expected) {
+        assert!((a - e).abs() < 1e-4, "{actual:?} != {expected:?}");
+    }
+}
+
+#[tokio::test]
+async fn embedding_matches_reference_forward_pass() {
+    let dir = tempfile::tempdir().unwrap();
+    let tensors = checkpoint("", false);
+    let model =
+        load_safetensors_model(&write_model(dir.path(), &tensors), "embedder", SafetensorsTask::Embedding).unwrap();
+
+    let input = InferenceInput::Text("The cat sat on the mat".into());
+    let InferenceOutput::Embedding(result) = model.infer(&input, &InferenceConfig::default()).await.unwrap() else {
+        panic!("expected an embedding");
+    };
+
+    let ids = [2, 4, 5, 6, 7, 4, 8, 3];
+    let hidden = Reference { tensors: &tensors, prefix: "" }.forward(&ids, &[0; 8]);
+    let mean: Vec<f32> = (0..HIDDEN).map(|d| hidden.iter().map(|h| h[d]).sum::<f32>() / ids.len() as f32).collect();
+    let norm = mean.iter().map(|v| v * v).sum::<f32>().sqrt();
+    assert_eq!(result.dimensions, HIDDEN);
+    assert_close(&result.vector, &mean.iter().map(|v| v / norm).collect::<Vec<_>>());
+}
+
+#[tokio::test]
+async fn reranker_scores_pairs_with_classifier_head() {
+    let dir = tempfile::tempdir().unwrap();
+    let tensors = checkpoint("bert.", true);
+    let model =
+        load_safetensors_model(&write_model(dir.path(), &tensors), "reranker", SafetensorsTask::Reranking).unwrap();
+
+    let input = InferenceInput::Rerank { query: "cat".into(), passages: vec!["the cat sat".into(), "dog".into()] };
+    let InferenceOutput::Rerank(scores) = model.infer(&input, &InferenceConfig::default()).await.unwrap() else {
+        panic!("expected rerank scores");
+    };
+
+    let reference = Reference { tensors: &tensors, prefix: "bert." };
+    let score = |ids: &[usize], segments: &[usize]| {
+        let pooled: Vec<f32> =
+            reference.linear("pooler.dense", &reference.forward(ids, segments)[0]).iter().map(|v| v.tanh()).collect();
+        Reference { tensors: &tensors, prefix: "" }.linear("classifier", &pooled)[0]
+    };
+    let expected = [score(&[2, 5, 3, 4, 5, 6, 3], &[0, 0, 0, 1, 1, 1, 1]), score(&[2, 5, 3, 9, 3], &[0, 0, 0, 1, 1])];
+    assert_close(&scores, &expected);
+}
+
+#[tokio::test]
+async fn checkpoint_needs_its_side_files_and_task_input() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write_model(dir.path(), &checkpoint("", false));
+    let model = load_safetensors_model(&path, "embedder", SafetensorsTask::Embedding).unwrap();
+    let rerank = InferenceInput::Rerank { query: "cat".into(), passages: vec!["dog".into()] };
+    let result = model.infer(&rerank, &InferenceConfig::default()).await;
+    assert!(matches!(result, Err(InferenceError::CapabilityNotSupported(_))));
+
+    std::fs::remove_file(dir.path().join("vocab.txt")).unwrap();
+    assert!(load_safetensors_model(&path, "embedder", SafetensorsTask::Embedding).is_err());
+}
@@ -2,8 +2,8 @@
 
 use gg_core::engine::InferenceParams;
 use gg_core::scheduler::{
-    BatchConfig, BatchProcessor, Priority, PriorityQueue, RequestQueue,
-    RequestQueueConfig, ThreadPoolConfig,
+    BatchConfig, BatchProcessor, Priority, PriorityQueue, QueueError, RequestQueue,
+    RequestQueueConfig, SwitchPolicy, ThreadPoolConfig,
 };
 
 #[test]
@@ -54,6 +54,83 @@ async fn request_queue_enqueue_dequeue() {
     assert_eq!(request.model_id, "model");
 }
 
+#[tokio::test]
+async fn request_queue_reports_oldest_age() {
+    let queue = RequestQueue::new(RequestQueueConfig::default());
+    assert_eq!(queue.oldest_age().await, None);
+
+    for priority in [Priority::Low, Priority::Critical] {
+        queue
+            .enqueue("model".to_string(), "p".to_string(), InferenceParams::default(), priority)
+            .await
+            .unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+
+    // The critical request dequeues first; the older low one is still waiting
+    assert_eq!(queue.dequeue().await.unwrap().priority, Priority::Critical);
+    assert!(queue.oldest_age().await.unwrap() >= std::time::Duration::from_millis(40));
+
+    queue.dequeue().await.unwrap();
+    assert_eq!(queue.oldest_age().await, None);
+}
+
+async fn enqueue(queue: &RequestQueue, model: &str, priority: Priority) -> Result<(u64, usize), QueueError> {
+    queue.enqueue(model.to_string(), "p".to_string(), InferenceParams::default(), priority).await
+}
+
+#[tokio::test]
+async fn request_queue_keeps_stranded_requests_on_switch() {
+    let queue = RequestQueue::new(RequestQueueConfig::default());
+    enqueue(&queue, "a", Priority::Low).await.unwrap();
+    enqueue(&queue, "b", Priority::Normal).await.unwrap();
+    enqueue(&queue, "a", Priority::High).await.unwrap();
+
+    assert!(queue.model_deactivated("a").await.is_empty());
+    let ages = queue.model_ages().await;
+    assert_eq!(ages.iter().map(|a| (a.model_id.as_str(), a.pending, a.stranded)).collect::<Vec<_>>(), vec![
+        ("a", 2, true),
+        ("b", 1, false)
+    ]);
+    // New work for the model is still accepted and order is unchanged
+    enqueue(&queue, "a", Priority::Normal).await.unwrap();
+    assert_eq!(queue.dequeue().await.unwrap().priority, Priority::High);
+
+    queue.model_activated("a").await;
+    assert!(queue.model_ages().await.iter().all(|a| !a.stranded));
+}
+
+#[tokio::test]
+async fn request_queue_fails_fast_for_deactivated_model() {
+    let queue = RequestQueue::new(RequestQueueConfig::default()).with_switch_policy(SwitchPolicy::FailFast);
+    enqueue(&queue, "a", Priority::Low).await.unwrap();
+    enqueue(&queue, "b", Priority::Low).await.unwrap();
+    enqueue(&queue, "b", Priority::Critical).await.unwrap();
+    enqueue(&queue, "a", Priority::Normal).await.unwrap();
+
+    let failed = queue.model_deactivated("a").await;
+    assert_eq!(failed.len(), 2);
+    assert!(failed.iter().all(|r| r.model_id == "a" && r.is_deactivated()));
+    assert!(matches!(enqueue(&queue, "a", Priority::High).await, Err(QueueError::ModelDeactivated(m)) if m == "a"));
+
+    // The remaining requests keep their priority and FIFO order
+    let order: Vec<_> = [queue.dequeue().await.unwrap(), queue.dequeue().await.unwrap()]
+        .iter()
+        .map(|r| (r.model_id.clone(), r.priority))
+        .collect();
+    assert_eq!(order, vec![("b".to_string(), Priority::Critical), ("b".to_string(), Priority::Low)]);
+
+    queue.model_activated("a").await;
+    assert!(enqueue(&queue, "a", Priority::High).await.is_ok());
+}
+
+#[test]
+fn switch_policy_parses_aliases() {
+    assert_eq!("fail_fast".parse::<SwitchPolicy>(), Ok(SwitchPolicy::FailFast));
+    assert_eq!("Keep".parse::<SwitchPolicy>(), Ok(SwitchPolicy::Keep));
+    assert!("drop".parse::<SwitchPolicy>().is_err());
+}
+
 #[test]
 fn batch_processor_respects_size_limit() {
     let config = BatchConfig {
@@ -4,7 +4,6 @@
 
 use gg_core::security::encryption::{ModelEncryption, KEY_SIZE, NONCE_SIZE};
 use std::collections::HashSet;
-use std::time::Instant;
 
 fn create_test_key() -> [u8; KEY_SIZE] {
     let mut key = [0u8; KEY_SIZE];
@@ -170,20 +169,14 @@ fn invalid_nonce_size_rejected() {
     assert!(result.is_err(), "Invalid nonce size should be rejected");
 }
 
-/// Encryption/decryption performance test.
+/// 1 MB round trip; throughput is benchmarked in benches/regression.rs.
 #[test]
-fn crypto_performance() {
+fn crypto_large_roundtrip() {
     let encryption = ModelEncryption::new(create_test_key());
     let plaintext: Vec<u8> = (0..1_000_000).map(|i| (i % 256) as u8).collect();
-    let start = Instant::now();
     let (nonce, ciphertext) = encryption.encrypt(&plaintext).unwrap();
-    let encrypt_time = start.elapsed();
-    let start = Instant::now();
     let decrypted = encryption.decrypt(&nonce, &ciphertext).unwrap();
-    let decrypt_time = start.elapsed();
     assert_eq!(plaintext, decrypted);
-    assert!(encrypt_time.as_millis() < 1000, "Encryption too slow: {:?}", encrypt_time);
-    assert!(decrypt_time.as_millis() < 1000, "Decryption too slow: {:?}", decrypt_time);
 }
 
 /// Verify encryption is not deterministic (IND-CPA security).
@@ -96,6 +96,17 @@ fn inference_request_requires_model_id() {
         model_id: String::new(),
         prompt: "test prompt".to_string(),
         parameters: InferenceParams::default(),
+        preset: None,
+        priority: Default::default(),
+        session_affinity_key: None,
+        tools: Vec::new(),
+        images: Vec::new(),
+        template_id: None,
+        variables: Default::default(),
+        context_documents: Vec::new(),
+        cite_sources: false,
+        privacy: Default::default(),
+        idempotency_key: None,
     };
     let result = request.validate();
     assert!(result.is_err());
@@ -109,6 +120,17 @@ fn inference_request_requires_prompt() {
         model_id: "test-model".to_string(),
         prompt: String::new(),
         parameters: InferenceParams::default(),
+        preset: None,
+        priority: Default::default(),
+        session_affinity_key: None,
+        tools: Vec::new(),
+        images: Vec::new(),
+        template_id: None,
+        variables: Default::default(),
+        context_documents: Vec::new(),
+        cite_sources: false,
+        privacy: Default::default(),
+        idempotency_key: None,
     };
     let result = request.validate();
     assert!(result.is_err());
@@ -122,6 +144,17 @@ fn valid_inference_request_passes() {
         model_id: "test-model".to_string(),
         prompt: "Hello, world!".to_string(),
         parameters: InferenceParams::default(),
+        preset: None,
+        priority: Default::default(),
+        session_affinity_key: None,
+        tools: Vec::new(),
+        images: Vec::new(),
+        template_id: None,
+        variables: Default::default(),
+        context_documents: Vec::new(),
+        cite_sources: false,
+        privacy: Default::default(),
+        idempotency_key: None,
     };
     let result = request.validate();
     assert!(result.is_ok());
@@ -166,6 +199,17 @@ fn large_prompt() {
         model_id: "test".to_string(),
         prompt: large_prompt.clone(),
         parameters: InferenceParams::default(),
+        preset: None,
+        priority: Default::default(),
+        session_affinity_key: None,
+        tools: Vec::new(),
+        images: Vec::new(),
+        template_id: None,
+        variables: Default::default(),
+        context_documents: Vec::new(),
+        cite_sources: false,
+        privacy: Default::default(),
+        idempotency_key: None,
     };
     let msg = IpcMessage::InferenceRequest(request);
     let encoded = encode_message(&msg).unwrap();
@@ -202,6 +246,17 @@ fn unicode_model_id() {
         model_id: "test-model-\u{4e2d}\u{6587}".to_string(),
         prompt: "test prompt".to_string(),
         parameters: InferenceParams::default(),
+        preset: None,
+        priority: Default::default(),
+        session_affinity_key: None,
+        tools: Vec::new(),
+        images: Vec::new(),
+        template_id: None,
+        variables: Default::default(),
+        context_documents: Vec::new(),
+        cite_sources: false,
+        privacy: Default::default(),
+        idempotency_key: None,
     };
     let msg = IpcMessage::InferenceRequest(request);
     let encoded = encode_message(&msg);
@@ -241,16 +296,27 @@ fn negative_request_id() {
 #[test]
 fn large_request_id() {
     let request = InferenceRequest {
-        request_id: RequestId(u64::MAX),
+        request_id: RequestId(u64::MAX.into()),
         model_id: "test".to_string(),
         prompt: "test prompt".to_string(),
         parameters: InferenceParams::default(),
+        preset: None,
+        priority: Default::default(),
+        session_affinity_key: None,
+        tools: Vec::new(),
+        images: Vec::new(),
+        template_id: None,
+        variables: Default::default(),
+        context_documents: Vec::new(),
+        cite_sources: false,
+        privacy: Default::default(),
+        idempotency_key: None,
     };
     let msg = IpcMessage::InferenceRequest(request);
     let encoded = encode_message(&msg).unwrap();
     let decoded = decode_message(&encoded).unwrap();
     if let IpcMessage::InferenceRequest(req) = decoded {
-        assert_eq!(req.request_id.0, u64::MAX);
+        assert_eq!(req.request_id.0, u128::from(u64::MAX));
     } else {
         panic!("Wrong message type");
     }
@@ -264,6 +330,17 @@ fn zero_request_id() {
         model_id: "test".to_string(),
         prompt: "test prompt".to_string(),
         parameters: InferenceParams::default(),
+        preset: None,
+        priority: Default::default(),
+        session_affinity_key: None,
+        tools: Vec::new(),
+        images: Vec::new(),
+        template_id: None,
+        variables: Default::default(),
+        context_documents: Vec::new(),
+        cite_sources: false,
+        privacy: Default::default(),
+        idempotency_key: None,
     };
     let msg = IpcMessage::InferenceRequest(request);
     let encoded = encode_message(&msg).unwrap();
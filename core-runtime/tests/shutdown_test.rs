@@ -1,6 +1,6 @@
 //! Tests for graceful shutdown coordination.
 
-use gg_core::shutdown::{ShutdownCoordinator, ShutdownResult, ShutdownState};
+use gg_core::shutdown::{DrainCounts, ShutdownCoordinator, ShutdownResult, ShutdownState};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -128,3 +128,43 @@ async fn test_state_is_stopped_after_drain() {
     let _ = coordinator.initiate(Duration::from_millis(50)).await;
     assert_eq!(coordinator.state().await, ShutdownState::Stopped);
 }
+
+#[tokio::test]
+async fn test_report_counts_drained_and_aborted_connections() {
+    let coordinator = Arc::new(ShutdownCoordinator::new());
+
+    // Idle connection: exits when told to stop
+    let stopping = coordinator.stopping().clone();
+    coordinator.connections().spawn(async move { stopping.cancelled().await });
+
+    // Stuck connection holding an in-flight request
+    let guard = coordinator.track().unwrap();
+    coordinator.connections().spawn(async move {
+        let _guard = guard;
+        std::future::pending::<()>().await;
+    });
+    assert_eq!(coordinator.connections().len(), 2);
+
+    let report = coordinator.initiate_with_report(Duration::from_millis(50)).await;
+
+    assert_eq!(report.result, ShutdownResult::Timeout { remaining: 1 });
+    assert_eq!(report.connections, DrainCounts { drained: 1, aborted: 1 });
+    assert_eq!(report.requests, DrainCounts { drained: 0, aborted: 1 });
+    assert!(coordinator.connections().is_empty());
+}
+
+#[tokio::test]
+async fn test_report_complete_when_all_drain() {
+    let coordinator = Arc::new(ShutdownCoordinator::new());
+    let guard = coordinator.track().unwrap();
+    coordinator.connections().spawn(async move {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        drop(guard);
+    });
+
+    let report = coordinator.initiate_with_report(Duration::from_secs(1)).await;
+
+    assert_eq!(report.result, ShutdownResult::Complete);
+    assert_eq!(report.connections, DrainCounts { drained: 1, aborted: 0 });
+    assert_eq!(report.requests, DrainCounts { drained: 1, aborted: 0 });
+}
@@ -0,0 +1,43 @@
+//! Tests for IPC snapshot control: restore is limited to admin sessions.
+
+use gg_core::ipc::{
+    decode_message, encode_message, IpcHandler, IpcMessage, SessionToken, SnapshotAction, SnapshotRequest,
+};
+use gg_core::{Runtime, RuntimeConfig};
+
+fn runtime(base: &std::path::Path) -> Runtime {
+    Runtime::new(RuntimeConfig {
+        base_path: base.to_path_buf(),
+        auth_token: "user-token".into(),
+        admin_token: Some("admin-token".into()),
+        ..Default::default()
+    })
+}
+
+async fn login(handler: &IpcHandler, token: &str) -> SessionToken {
+    let handshake = IpcMessage::Handshake { token: token.into(), protocol_version: None };
+    let (_, session) = handler.process(&encode_message(&handshake).unwrap(), None).await.unwrap();
+    session.unwrap()
+}
+
+async fn send(handler: &IpcHandler, action: SnapshotAction, session: &SessionToken) -> IpcMessage {
+    let message = IpcMessage::SnapshotRequest(SnapshotRequest { action, name: "known-good".into() });
+    let (bytes, _) = handler.process(&encode_message(&message).unwrap(), Some(session)).await.unwrap();
+    decode_message(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn snapshot_restore_requires_admin() {
+    let dir = tempfile::tempdir().unwrap();
+    let rt = runtime(dir.path());
+    let handler = &rt.ipc_handler;
+    let user = login(handler, "user-token").await;
+    let admin = login(handler, "admin-token").await;
+
+    let created = send(handler, SnapshotAction::Create, &user).await;
+    assert!(matches!(created, IpcMessage::SnapshotResponse(r) if r.success));
+    let denied = send(handler, SnapshotAction::Restore, &user).await;
+    assert!(matches!(denied, IpcMessage::Error { code: 403, .. }));
+    let restored = send(handler, SnapshotAction::Restore, &admin).await;
+    assert!(matches!(restored, IpcMessage::SnapshotResponse(r) if r.success));
+}
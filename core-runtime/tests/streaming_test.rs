@@ -132,6 +132,17 @@ fn test_inference_request_with_stream_flag() {
         model_id: "test-model".into(),
         prompt: "test prompt for streaming".into(),
         parameters: params,
+        preset: None,
+        priority: Default::default(),
+        session_affinity_key: None,
+        tools: Vec::new(),
+        images: Vec::new(),
+        template_id: None,
+        variables: Default::default(),
+        context_documents: Vec::new(),
+        cite_sources: false,
+        privacy: Default::default(),
+        idempotency_key: None,
     };
 
     let message = IpcMessage::InferenceRequest(request);
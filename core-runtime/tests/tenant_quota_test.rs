@@ -0,0 +1,141 @@
+//! Tests for tenant rate limits and idempotency keys at admission.
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tokio_util::sync::CancellationToken;
+
+use gg_core::engine::InferenceParams;
+use gg_core::ipc::{
+    decode_message, encode_message, HandlerError, IpcHandler, IpcMessage, InferenceRequest, RequestId, SessionToken,
+    StreamSender,
+};
+use gg_core::scheduler::QuotaConfig;
+use gg_core::{Runtime, RuntimeConfig};
+
+fn runtime(quota: QuotaConfig) -> Runtime {
+    Runtime::new(RuntimeConfig {
+        tenant_tokens: vec![("alice".into(), "alice-token".into()), ("bob".into(), "bob-token".into())],
+        quota,
+        ..Default::default()
+    })
+}
+
+async fn login(handler: &IpcHandler) -> SessionToken {
+    login_as(handler, "").await
+}
+
+async fn login_as(handler: &IpcHandler, token: &str) -> SessionToken {
+    let handshake = IpcMessage::Handshake { token: token.into(), protocol_version: None };
+    let (_, session) = handler.process(&encode_message(&handshake).unwrap(), None).await.unwrap();
+    session.unwrap()
+}
+
+/// Collects the chunks a streaming request sends.
+#[derive(Default)]
+struct Collect(Mutex<Vec<IpcMessage>>);
+
+#[async_trait::async_trait]
+impl StreamSender for Collect {
+    async fn send(&self, message: IpcMessage) -> Result<(), HandlerError> {
+        self.0.lock().unwrap().push(message);
+        Ok(())
+    }
+}
+
+fn request(id: u64, key: Option<&str>) -> InferenceRequest {
+    InferenceRequest {
+        request_id: RequestId(id.into()),
+        model_id: "missing-model".into(),
+        prompt: "hello".into(),
+        parameters: InferenceParams::default(),
+        preset: None,
+        priority: Default::default(),
+        session_affinity_key: None,
+        tools: Vec::new(),
+        images: Vec::new(),
+        template_id: None,
+        variables: Default::default(),
+        context_documents: Vec::new(),
+        cite_sources: false,
+        privacy: Default::default(),
+        idempotency_key: key.map(str::to_string),
+    }
+}
+
+async fn infer(handler: &IpcHandler, session: &SessionToken, id: u64, key: Option<&str>) -> String {
+    let request = IpcMessage::InferenceRequest(request(id, key));
+    let (bytes, _) = handler.process(&encode_message(&request).unwrap(), Some(session)).await.unwrap();
+    match decode_message(&bytes).unwrap() {
+        IpcMessage::InferenceResponse(response) => response.error.unwrap_or_default(),
+        other => panic!("unexpected message: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn tenant_rate_limit_applies_per_tenant() {
+    let rt = runtime(QuotaConfig {
+        tenant_requests_per_window: Some(1),
+        window: Duration::from_secs(3600),
+        ..Default::default()
+    });
+    let handler = &rt.ipc_handler;
+    let alice = login_as(handler, "alice-token").await;
+    let bob = login_as(handler, "bob-token").await;
+
+    assert!(!infer(handler, &alice, 1, None).await.contains("rate limit"));
+    assert!(infer(handler, &alice, 2, None).await.contains("rate limit"));
+    assert!(!infer(handler, &bob, 3, None).await.contains("rate limit"));
+
+    // A new session for the same tenant shares its quota
+    let alice_again = login_as(handler, "alice-token").await;
+    assert!(infer(handler, &alice_again, 4, None).await.contains("rate limit"));
+}
+
+#[tokio::test]
+async fn streaming_requests_share_the_tenant_quota() {
+    let rt = runtime(QuotaConfig {
+        tenant_requests_per_window: Some(1),
+        window: Duration::from_secs(3600),
+        ..Default::default()
+    });
+    let handler = &rt.ipc_handler;
+    let alice = login_as(handler, "alice-token").await;
+    assert!(!infer(handler, &alice, 1, None).await.contains("rate limit"));
+
+    let chunks = Collect::default();
+    handler.process_streaming(request(2, None), &alice, &chunks, CancellationToken::new()).await.unwrap();
+    let chunks = chunks.0.into_inner().unwrap();
+    let error = match chunks.as_slice() {
+        [IpcMessage::StreamChunk(chunk)] => chunk.error.clone().unwrap_or_default(),
+        other => panic!("unexpected messages: {:?}", other),
+    };
+    assert!(error.contains("rate limit"), "{}", error);
+}
+
+#[tokio::test]
+async fn repeated_idempotency_key_is_refused() {
+    let rt = runtime(QuotaConfig::default());
+    let handler = &rt.ipc_handler;
+    let session = login(handler).await;
+
+    assert!(!infer(handler, &session, 1, Some("order-17")).await.contains("idempotency"));
+    assert_eq!(infer(handler, &session, 2, Some("order-17")).await, "Duplicate idempotency key");
+    assert!(infer(handler, &session, 3, Some("")).await.contains("idempotency_key"));
+}
+
+#[tokio::test]
+async fn unreachable_backend_falls_back_to_local_limits() {
+    // Port 9 (discard) on loopback is expected to refuse connections
+    let rt = runtime(QuotaConfig {
+        tenant_requests_per_window: Some(1),
+        window: Duration::from_secs(3600),
+        backend_url: Some("redis://127.0.0.1:9".into()),
+        ..Default::default()
+    });
+    let handler = &rt.ipc_handler;
+    let session = login(handler).await;
+
+    assert!(!infer(handler, &session, 1, None).await.contains("rate limit"));
+    assert!(infer(handler, &session, 2, None).await.contains("rate limit"));
+}
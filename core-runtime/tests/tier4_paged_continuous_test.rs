@@ -1,6 +1,7 @@
 //! Tier 4 tests: Paged KV-Cache and Continuous Batching.
 
 use gg_core::memory::paged::{Page, PageId, PageTable, PAGE_TOKENS};
+use gg_core::memory::SharedKvPool;
 use gg_core::scheduler::continuous::{
     BatchSlot, ContinuousBatcher, PendingRequest, RequestId, RequestPhase,
 };
@@ -150,3 +151,33 @@ fn batch_slot_phase_transitions() {
     slot.mark_complete();
     assert!(slot.is_complete());
 }
+
+#[test]
+fn continuous_shares_common_prompt_prefix() {
+    let system: Vec<u32> = (1000..1040).collect();
+    let mut batcher = ContinuousBatcher::new(4).with_prefix_sharing(SharedKvPool::new(4, 32));
+    for (i, suffix) in [[1, 2], [3, 4], [5, 6]].iter().enumerate() {
+        batcher.enqueue(PendingRequest {
+            request_id: RequestId(i as u64),
+            prompt_tokens: [system.as_slice(), suffix].concat(),
+            max_tokens: 4,
+        });
+    }
+
+    let admitted = batcher.admit_pending();
+    assert_eq!(admitted.len(), 3);
+    let prefill: Vec<usize> = batcher
+        .active_slots()
+        .map(|(_, slot)| slot.prefill_len())
+        .collect();
+    // Later members only prefill past the shared system prompt
+    assert_eq!(prefill, vec![42, 2, 2]);
+    assert_eq!(batcher.prefix_stats().unwrap().shared_tokens, 80);
+
+    for idx in 0..3 {
+        batcher.get_slot_mut(idx).unwrap().mark_complete();
+    }
+    batcher.evict_completed();
+    let pool = batcher.prefix_pool_mut().unwrap();
+    assert_eq!(pool.pages_in_use(), 0);
+}
@@ -0,0 +1,61 @@
+//! Tests for the IPC usage export: admin gating and privacy metadata.
+
+use gg_core::ipc::{decode_message, encode_message, IpcHandler, IpcMessage, SessionToken};
+use gg_core::telemetry::DpConfig;
+use gg_core::{Runtime, RuntimeConfig};
+
+fn runtime(usage_privacy: Option<DpConfig>) -> Runtime {
+    Runtime::new(RuntimeConfig {
+        auth_token: "user-token".into(),
+        admin_token: Some("admin-token".into()),
+        usage_privacy,
+        ..Default::default()
+    })
+}
+
+async fn login(handler: &IpcHandler, token: &str) -> SessionToken {
+    let handshake = IpcMessage::Handshake { token: token.into(), protocol_version: None };
+    let (_, session) = handler.process(&encode_message(&handshake).unwrap(), None).await.unwrap();
+    session.unwrap()
+}
+
+async fn export(handler: &IpcHandler, session: &SessionToken) -> IpcMessage {
+    let request = encode_message(&IpcMessage::UsageExportRequest).unwrap();
+    let (bytes, _) = handler.process(&request, Some(session)).await.unwrap();
+    decode_message(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn usage_export_requires_admin() {
+    let rt = runtime(None);
+    let request = encode_message(&IpcMessage::UsageExportRequest).unwrap();
+    assert!(rt.ipc_handler.process(&request, None).await.is_err());
+
+    let user = login(&rt.ipc_handler, "user-token").await;
+    assert!(matches!(export(&rt.ipc_handler, &user).await, IpcMessage::Error { code: 403, .. }));
+}
+
+#[tokio::test]
+async fn usage_export_documents_mechanism() {
+    let rt = runtime(None);
+    let admin = login(&rt.ipc_handler, "admin-token").await;
+    match export(&rt.ipc_handler, &admin).await {
+        IpcMessage::UsageExportResponse(export) => {
+            assert_eq!(export.privacy.mechanism, "none");
+            assert!(export.tenants.is_empty());
+        }
+        other => panic!("unexpected message: {:?}", other),
+    }
+
+    let rt = runtime(Some(DpConfig { epsilon: 0.8, max_tokens_per_request: 512 }));
+    let admin = login(&rt.ipc_handler, "admin-token").await;
+    match export(&rt.ipc_handler, &admin).await {
+        IpcMessage::UsageExportResponse(export) => {
+            assert_eq!(export.privacy.mechanism, "laplace");
+            assert_eq!(export.privacy.epsilon, Some(0.8));
+            assert_eq!(export.privacy.epsilon_per_metric, Some(0.4));
+            assert_eq!(export.privacy.tokens_sensitivity, Some(512));
+        }
+        other => panic!("unexpected message: {:?}", other),
+    }
+}
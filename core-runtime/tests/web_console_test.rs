@@ -0,0 +1,57 @@
+//! Tests for the loopback admin web console.
+
+#![cfg(feature = "web-console")]
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use gg_core::console::{Console, Request};
+use gg_core::{Runtime, RuntimeConfig};
+
+fn console() -> (Console, Arc<gg_core::ipc::IpcHandler>) {
+    let rt = Runtime::new(RuntimeConfig {
+        auth_token: "user-token".into(),
+        admin_token: Some("admin-token".into()),
+        ..Default::default()
+    });
+    let handler = Arc::new(rt.ipc_handler);
+    (Console::new(Arc::clone(&handler)), handler)
+}
+
+fn request(method: &str, path: &str, token: Option<&str>) -> Request {
+    let mut headers = HashMap::from([("host".to_string(), "127.0.0.1:8787".to_string())]);
+    if let Some(token) = token {
+        headers.insert("authorization".into(), format!("Bearer {}", token));
+    }
+    Request { method: method.into(), path: path.into(), query: HashMap::new(), headers, body: Vec::new() }
+}
+
+#[tokio::test]
+async fn views_need_no_token() {
+    let (console, _) = console();
+    assert_eq!(console.route(&request("GET", "/", None)).await.status, 200);
+    assert_eq!(console.route(&request("GET", "/api/models", None)).await.status, 200);
+    assert_eq!(console.route(&request("GET", "/api/status", None)).await.status, 200);
+    assert_eq!(console.route(&request("GET", "/api/audit", None)).await.status, 401);
+}
+
+#[tokio::test]
+async fn drain_requires_admin_token() {
+    let (console, handler) = console();
+    assert_eq!(console.route(&request("POST", "/api/drain", None)).await.status, 401);
+    assert_eq!(console.route(&request("POST", "/api/drain", Some("wrong"))).await.status, 401);
+    assert_eq!(console.route(&request("POST", "/api/drain", Some("user-token"))).await.status, 403);
+    assert!(handler.shutdown().is_accepting());
+
+    assert_eq!(console.route(&request("POST", "/api/drain", Some("admin-token"))).await.status, 200);
+    assert!(!handler.shutdown().is_accepting());
+}
+
+#[tokio::test]
+async fn foreign_host_rejected() {
+    let (console, _) = console();
+    let mut req = request("GET", "/api/status", None);
+    req.headers.insert("host".into(), "rebind.example:8787".into());
+    assert_eq!(console.route(&req).await.status, 421);
+    assert_eq!(console.route(&request("GET", "/api/drain", None)).await.status, 405);
+}